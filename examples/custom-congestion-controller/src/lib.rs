@@ -5,9 +5,6 @@
 ///
 /// This example serves only to illustrate the integration points for incorporating a custom
 /// congestion controller into s2n-quic, and not as an actual congestion controller implementation.
-///
-/// NOTE: The `CongestionController` trait is considered unstable and may be subject to change
-///       in a future release.
 pub mod custom_congestion_controller {
     use s2n_quic::provider::{
         congestion_controller,
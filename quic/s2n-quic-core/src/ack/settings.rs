@@ -39,6 +39,13 @@ pub struct Settings {
 
     /// The number of packet number intervals an endpoint is willing to store
     pub ack_ranges_limit: u8,
+
+    /// Sends an ACK immediately when a received packet is marked with ECN-CE or arrives
+    /// out of order, instead of waiting for the ACK delay timer
+    ///
+    /// Both events signal congestion or loss to the peer, which can react sooner if it
+    /// doesn't have to wait for the acknowledgement.
+    pub immediate_ack_on_congestion: bool,
 }
 
 impl Default for Settings {
@@ -72,6 +79,7 @@ impl Settings {
         ack_delay_exponent: AckDelayExponent::RECOMMENDED.as_u8(),
         ack_elicitation_interval: RECOMMENDED_ELICITATION_INTERVAL,
         ack_ranges_limit: RECOMMENDED_RANGES_LIMIT,
+        immediate_ack_on_congestion: true,
     };
 
     /// Decodes the peer's `Ack Delay` field
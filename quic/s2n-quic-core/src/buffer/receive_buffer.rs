@@ -22,6 +22,8 @@ use slot::Slot;
 pub enum ReceiveBufferError {
     /// An invalid data range was provided
     OutOfRange,
+    /// The received data has more gaps than the configured maximum
+    TooManyGaps,
 }
 
 /// The default buffer size for slots that the [`ReceiveBuffer`] uses.
@@ -77,6 +79,9 @@ const MIN_BUFFER_ALLOCATION_SIZE: usize = 4096;
 pub struct ReceiveBuffer {
     slots: VecDeque<Slot>,
     start_offset: u64,
+    max_gaps: usize,
+    /// The number of ranges of missing data before the last received byte
+    gaps: usize,
 }
 
 impl Default for ReceiveBuffer {
@@ -88,9 +93,20 @@ impl Default for ReceiveBuffer {
 impl ReceiveBuffer {
     /// Creates a new `ReceiveBuffer`
     pub fn new() -> ReceiveBuffer {
+        Self::with_max_gaps(usize::MAX)
+    }
+
+    /// Creates a new `ReceiveBuffer` which tracks at most `max_gaps` gaps in the received data
+    ///
+    /// Each range of missing data between the consumed offset and the last received byte
+    /// counts as a gap. Writes which exceed the limit return
+    /// [`ReceiveBufferError::TooManyGaps`], after which the buffer should be discarded.
+    pub fn with_max_gaps(max_gaps: usize) -> ReceiveBuffer {
         ReceiveBuffer {
             slots: VecDeque::new(),
             start_offset: 0,
+            max_gaps,
+            gaps: 0,
         }
     }
 
@@ -125,6 +141,7 @@ impl ReceiveBuffer {
     pub fn write_at(&mut self, offset: VarInt, data: &[u8]) -> Result<(), ReceiveBufferError> {
         // create a request
         let request = Request::new(offset, data)?;
+        let request_end = offset.as_u64() + data.len() as u64;
 
         // trim off any data that we've already read
         let (_, mut request) = request.split(self.start_offset);
@@ -134,6 +151,10 @@ impl ReceiveBuffer {
             return Ok(());
         }
 
+        // only the gaps around the written range can change
+        let request_start = request.start();
+        let prev_gaps = self.gaps_around(request_start, request_end);
+
         // start from the back with the assumption that most data arrives in order
         for mut idx in (0..self.slots.len()).rev() {
             let slot = &mut self.slots[idx];
@@ -180,11 +201,57 @@ impl ReceiveBuffer {
 
         self.allocate_request(0, request);
 
+        self.gaps = self.gaps + self.gaps_around(request_start, request_end) - prev_gaps;
+
         self.check_consistency();
 
+        if self.gaps > self.max_gaps {
+            return Err(ReceiveBufferError::TooManyGaps);
+        }
+
         Ok(())
     }
 
+    /// Returns the number of ranges of missing data before the last received byte
+    #[inline]
+    pub fn gaps(&self) -> usize {
+        self.gaps
+    }
+
+    /// Counts the gaps in front of the slots which a write to `start..end` can modify
+    ///
+    /// This includes the slots which overlap the range, the slots which a write allocates in the
+    /// range, and the first slot after it, since the data in front of it can change.
+    #[inline]
+    fn gaps_around(&self, start: u64, end: u64) -> usize {
+        let lower = self
+            .slots
+            .partition_point(|slot| slot.end_allocated() <= start);
+        let upper = self.slots.partition_point(|slot| slot.start() <= end);
+        let upper = (upper + 1).min(self.slots.len());
+
+        self.count_gaps(lower..upper)
+    }
+
+    /// Counts the slots in `range` which start after the end of the previous slot's data
+    #[inline]
+    fn count_gaps(&self, range: core::ops::Range<usize>) -> usize {
+        let mut prev_end = range
+            .start
+            .checked_sub(1)
+            .map_or(self.start_offset, |idx| self.slots[idx].end());
+        let mut gaps = 0;
+
+        for slot in self.slots.range(range) {
+            if slot.start() > prev_end {
+                gaps += 1;
+            }
+            prev_end = slot.end();
+        }
+
+        gaps
+    }
+
     /// Iterates over all of the chunks waiting to be received
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
@@ -271,6 +338,7 @@ impl ReceiveBuffer {
     pub fn reset(&mut self) {
         self.slots.clear();
         self.start_offset = Default::default();
+        self.gaps = 0;
     }
 
     #[inline(always)]
@@ -356,6 +424,8 @@ impl ReceiveBuffer {
                 assert!(slot.start() >= prev_end, "{self:#?}");
                 prev_end = slot.end_allocated();
             }
+
+            assert_eq!(self.gaps, self.count_gaps(0..self.slots.len()), "{self:#?}");
         }
     }
 }
@@ -762,3 +762,63 @@ fn allocation_size_test() {
         }
     }
 }
+
+#[test]
+fn max_gaps_test() {
+    let mut buffer = ReceiveBuffer::with_max_gaps(3);
+
+    // each write leaves a single byte missing in front of it
+    for offset in [1u32, 3, 5] {
+        assert!(buffer.write_at(offset.into(), &[1]).is_ok());
+    }
+    assert_eq!(buffer.gaps(), 3);
+
+    assert_eq!(
+        Err(ReceiveBufferError::TooManyGaps),
+        buffer.write_at(7u32.into(), &[1])
+    );
+
+    // filling in a gap doesn't count against the limit
+    let mut buffer = ReceiveBuffer::with_max_gaps(3);
+    for offset in [1u32, 3, 5] {
+        assert!(buffer.write_at(offset.into(), &[1]).is_ok());
+    }
+    assert!(buffer.write_at(0u32.into(), &[0]).is_ok());
+    assert_eq!(buffer.gaps(), 2);
+    assert_eq!(&buffer.pop().unwrap()[..], &[0, 1]);
+    assert!(buffer.write_at(7u32.into(), &[1]).is_ok());
+    assert_eq!(buffer.gaps(), 3);
+}
+
+#[test]
+fn gaps_tracking_test() {
+    let mut buffer = ReceiveBuffer::new();
+    let chunk = [1; 1000];
+
+    // leave a chunk missing between each write, across several allocations
+    for offset in (1u32..20).step_by(2).rev() {
+        buffer.write_at((offset * 1000).into(), &chunk).unwrap();
+    }
+    assert_eq!(buffer.gaps(), 10);
+
+    // fill every other gap, which merges the neighboring writes
+    for offset in (2u32..20).step_by(4) {
+        buffer.write_at((offset * 1000).into(), &chunk).unwrap();
+    }
+    assert_eq!(buffer.gaps(), 5);
+
+    // filling the first gap makes the data readable without affecting the others
+    buffer.write_at(0u32.into(), &chunk).unwrap();
+    assert_eq!(buffer.gaps(), 4);
+    while buffer.pop().is_some() {}
+    assert_eq!(buffer.gaps(), 4);
+
+    // a write spanning all of the remaining gaps fills them at once
+    buffer.write_at(0u32.into(), &[1; 20_000]).unwrap();
+    assert_eq!(buffer.gaps(), 0);
+
+    buffer.write_at(30_000u32.into(), &chunk).unwrap();
+    assert_eq!(buffer.gaps(), 1);
+    buffer.reset();
+    assert_eq!(buffer.gaps(), 0);
+}
@@ -4,6 +4,20 @@
 use crate::{application, crypto, transport};
 pub use crate::{frame::ConnectionClose, inet::SocketAddress};
 
+/// Determines how a connection is closed when the application drops it without closing it first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CloseOnDrop {
+    /// The outstanding stream data is flushed and the peer is notified with a CONNECTION_CLOSE
+    /// frame
+    #[default]
+    CloseGracefully,
+    /// The connection state is discarded without notifying the peer
+    ///
+    /// The peer closes its side of the connection once its idle timer expires.
+    Silent,
+}
+
 /// Provides a hook for applications to rewrite CONNECTION_CLOSE frames
 ///
 /// Implementations should take care to not leak potentially sensitive information
@@ -158,3 +172,178 @@ impl Formatter for Production {
         transport::Error::APPLICATION_ERROR.into()
     }
 }
+
+/// A formatter that overrides the error codes sent for specific crypto (TLS) alerts
+///
+/// The callback is invoked with the alert for each transport error in the
+/// CRYPTO_ERROR range (0x0100-0x01ff). If it returns an error, that error is sent to the peer
+/// as-is. Otherwise the error is passed to the wrapped formatter.
+///
+/// ```rust
+/// use s2n_quic_core::{
+///     connection::close::{MapCryptoError, Production},
+///     crypto::CryptoError,
+///     transport,
+/// };
+///
+/// // report untrusted certificates to the peer while hiding all of the other alerts
+/// let formatter = MapCryptoError::new(Production, |error: CryptoError| {
+///     if error.code == CryptoError::UNKNOWN_CA.code {
+///         Some(transport::Error::from(CryptoError::UNKNOWN_CA))
+///     } else {
+///         None
+///     }
+/// });
+/// # let _ = formatter;
+/// ```
+#[derive(Clone, Copy)]
+pub struct MapCryptoError<F, M> {
+    formatter: F,
+    map: M,
+}
+
+impl<F: core::fmt::Debug, M> core::fmt::Debug for MapCryptoError<F, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MapCryptoError")
+            .field("formatter", &self.formatter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, M> MapCryptoError<F, M>
+where
+    F: Formatter,
+    M: 'static + Send + Fn(crypto::CryptoError) -> Option<transport::Error>,
+{
+    /// Creates a formatter that calls `map` for each crypto error before delegating to `formatter`
+    pub fn new(formatter: F, map: M) -> Self {
+        Self { formatter, map }
+    }
+
+    #[inline]
+    fn map(&self, error: transport::Error) -> Option<ConnectionClose<'static>> {
+        let crypto_error = error.try_into_crypto_error()?;
+        let error = (self.map)(crypto_error)?;
+        Some(error.into())
+    }
+}
+
+impl<F, M> Formatter for MapCryptoError<F, M>
+where
+    F: Formatter,
+    M: 'static + Send + Fn(crypto::CryptoError) -> Option<transport::Error>,
+{
+    fn format_transport_error(
+        &self,
+        context: &Context,
+        error: transport::Error,
+    ) -> ConnectionClose {
+        if let Some(close) = self.map(error) {
+            return close;
+        }
+
+        self.formatter.format_transport_error(context, error)
+    }
+
+    fn format_application_error(
+        &self,
+        context: &Context,
+        error: application::Error,
+    ) -> ConnectionClose {
+        self.formatter.format_application_error(context, error)
+    }
+
+    fn format_early_transport_error(
+        &self,
+        context: &Context,
+        error: transport::Error,
+    ) -> ConnectionClose {
+        if let Some(close) = self.map(error) {
+            return close;
+        }
+
+        self.formatter.format_early_transport_error(context, error)
+    }
+
+    fn format_early_application_error(
+        &self,
+        context: &Context,
+        error: application::Error,
+    ) -> ConnectionClose {
+        self.formatter
+            .format_early_application_error(context, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto::CryptoError, varint::VarInt};
+
+    fn format<F: Formatter>(formatter: &F, error: transport::Error) -> (VarInt, VarInt) {
+        let remote_address = SocketAddress::default();
+        let context = Context::new(&remote_address);
+        let close = formatter.format_transport_error(&context, error);
+        let early_close = formatter.format_early_transport_error(&context, error);
+        (close.error_code, early_close.error_code)
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
+    //= type=test
+    //# A TLS alert is converted into a QUIC connection error.  The
+    //# AlertDescription value is added to 0x0100 to produce a QUIC error
+    //# code from the range reserved for CRYPTO_ERROR; see Section 20.1 of
+    //# [QUIC-TRANSPORT].
+    #[test]
+    fn crypto_error_code_test() {
+        let unknown_ca = transport::Error::from(CryptoError::UNKNOWN_CA);
+        assert_eq!(unknown_ca.code.as_u64(), 0x130);
+        assert_eq!(
+            unknown_ca.try_into_crypto_error().map(|error| error.code),
+            Some(CryptoError::UNKNOWN_CA.code)
+        );
+
+        let expected = VarInt::from_u16(0x130);
+        assert_eq!(format(&Development, unknown_ca), (expected, expected));
+
+        // alerts are hidden behind a generic alert in production
+        let expected = VarInt::from_u16(0x128);
+        assert_eq!(format(&Production, unknown_ca), (expected, expected));
+
+        // non-crypto errors aren't affected
+        let error = transport::Error::PROTOCOL_VIOLATION;
+        let expected = error.code.as_varint();
+        assert_eq!(format(&Production, error), (expected, expected));
+    }
+
+    #[test]
+    fn map_crypto_error_test() {
+        let formatter = MapCryptoError::new(Production, |error: CryptoError| {
+            if error.code == CryptoError::UNKNOWN_CA.code {
+                Some(transport::Error::from(CryptoError::BAD_CERTIFICATE))
+            } else {
+                None
+            }
+        });
+
+        // the mapped alert is sent as-is
+        let expected = VarInt::from_u16(0x100 | CryptoError::BAD_CERTIFICATE.code as u16);
+        assert_eq!(
+            format(&formatter, CryptoError::UNKNOWN_CA.into()),
+            (expected, expected)
+        );
+
+        // other alerts fall back to the wrapped formatter
+        let expected = VarInt::from_u16(0x128);
+        assert_eq!(
+            format(&formatter, CryptoError::DECODE_ERROR.into()),
+            (expected, expected)
+        );
+
+        let expected = transport::Error::PROTOCOL_VIOLATION.code.as_varint();
+        assert_eq!(
+            format(&formatter, transport::Error::INTERNAL_ERROR),
+            (expected, expected)
+        );
+    }
+}
@@ -81,6 +81,15 @@ pub enum Error {
         source: &'static panic::Location<'static>,
     },
 
+    /// The peer does not support the QUIC version selected for the connection
+    ///
+    /// The argument contains a version supported by both endpoints, if the peer advertised one.
+    #[non_exhaustive]
+    UnsupportedVersion {
+        compatible_version: Option<u32>,
+        source: &'static panic::Location<'static>,
+    },
+
     /// The connection attempt was rejected because the endpoint is closing
     #[non_exhaustive]
     EndpointClosing {
@@ -146,6 +155,14 @@ impl fmt::Display for Error {
                 f,
                 "The connection was closed due to: {reason}"
             ),
+            Self::UnsupportedVersion { compatible_version: Some(version), .. } => write!(
+                f,
+                "The peer does not support the selected QUIC version; version {version:#x} is supported by both endpoints"
+            ),
+            Self::UnsupportedVersion { compatible_version: None, .. } => write!(
+                f,
+                "The peer does not support any of the local QUIC versions"
+            ),
             Self::EndpointClosing { .. } => {
                 write!(f, "The connection attempt was rejected because the endpoint is closing")
             }
@@ -169,6 +186,7 @@ impl Error {
             Error::StreamIdExhausted { source } => source,
             Error::MaxHandshakeDurationExceeded { source, .. } => source,
             Error::ImmediateClose { source, .. } => source,
+            Error::UnsupportedVersion { source, .. } => source,
             Error::EndpointClosing { source } => source,
             Error::Unspecified { source } => source,
         }
@@ -266,6 +284,17 @@ impl Error {
         }
     }
 
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn unsupported_version(compatible_version: Option<u32>) -> Error {
+        let source = panic::Location::caller();
+        Error::UnsupportedVersion {
+            compatible_version,
+            source,
+        }
+    }
+
     #[inline]
     #[track_caller]
     #[doc(hidden)]
@@ -302,6 +331,35 @@ impl Error {
     }
 }
 
+/// Errors that can occur when changing the connection's DSCP code point
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DscpError {
+    /// The code point doesn't fit in the 6 bits of the DS field
+    InvalidCodePoint,
+    /// The connection encountered an error
+    ConnectionError { error: Error },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DscpError {}
+
+impl fmt::Display for DscpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidCodePoint => write!(f, "The DSCP code point must not exceed 63"),
+            Self::ConnectionError { error } => write!(f, "{error}"),
+        }
+    }
+}
+
+impl From<Error> for DscpError {
+    #[inline]
+    fn from(error: Error) -> Self {
+        Self::ConnectionError { error }
+    }
+}
+
 /// Returns a CONNECTION_CLOSE frame for the given connection Error, if any
 ///
 /// The first item will be a close frame for an early (initial, handshake) packet.
@@ -374,6 +432,8 @@ pub fn as_frame<'a, F: connection::close::Formatter>(
         }
         Error::MaxHandshakeDurationExceeded { .. } => None,
         Error::ImmediateClose { .. } => None,
+        // Connection attempts are abandoned after version negotiation without notifying the peer
+        Error::UnsupportedVersion { .. } => None,
         Error::EndpointClosing { .. } => None,
         Error::Unspecified { .. } => {
             let error =
@@ -459,6 +519,7 @@ impl From<Error> for std::io::ErrorKind {
             Error::StreamIdExhausted { .. } => ErrorKind::Other,
             Error::MaxHandshakeDurationExceeded { .. } => ErrorKind::TimedOut,
             Error::ImmediateClose { .. } => ErrorKind::Other,
+            Error::UnsupportedVersion { .. } => ErrorKind::Unsupported,
             Error::EndpointClosing { .. } => ErrorKind::Other,
             Error::Unspecified { .. } => ErrorKind::Other,
         }
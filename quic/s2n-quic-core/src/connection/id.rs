@@ -253,9 +253,11 @@ impl TryFrom<LocalId> for InitialId {
 }
 
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     InvalidLength,
     InvalidLifetime,
+    InvalidWorker,
 }
 
 impl Error {
@@ -263,6 +265,7 @@ impl Error {
         match self {
             Error::InvalidLength => "invalid connection id length",
             Error::InvalidLifetime => "invalid connection id lifetime",
+            Error::InvalidWorker => "invalid connection id worker",
         }
     }
 }
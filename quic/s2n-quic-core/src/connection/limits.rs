@@ -4,7 +4,9 @@
 use crate::{
     ack,
     event::{api::SocketAddress, IntoEvent},
-    inet, stream,
+    inet,
+    packet::number::SlidingWindow,
+    recovery, stream,
     transport::parameters::{
         AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
         InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
@@ -29,6 +31,18 @@ const MAX_HANDSHAKE_DURATION_DEFAULT: Duration = Duration::from_secs(10);
 //# middleboxes from losing state for UDP flows [GATEWAY].
 const MAX_KEEP_ALIVE_PERIOD_DEFAULT: Duration = Duration::from_secs(30);
 
+/// The maximum number of paths that a connection maintains, which bounds the number of paths
+/// that can be validated at the same time
+const MAX_VALIDATING_PATHS: u8 = 5;
+
+/// A datagram contains at most one packet for each of the Initial, Handshake and Application
+/// Data packet number spaces
+const MAX_COALESCED_PACKETS: u8 = 3;
+
+/// The number of times a client restarts a connection attempt after receiving a Version
+/// Negotiation packet
+const MAX_VERSION_NEGOTIATION_RETRIES_DEFAULT: u8 = 2;
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct ConnectionInfo<'a> {
@@ -61,10 +75,25 @@ pub struct Limits {
     pub(crate) max_active_connection_ids: ActiveConnectionIdLimit,
     pub(crate) ack_elicitation_interval: u8,
     pub(crate) ack_ranges_limit: u8,
+    pub(crate) immediate_ack_on_congestion: bool,
     pub(crate) max_send_buffer_size: stream::limits::MaxSendBufferSize,
     pub(crate) max_handshake_duration: Duration,
     pub(crate) max_keep_alive_period: Duration,
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
+    pub(crate) initial_round_trip_time: Duration,
+    pub(crate) key_update_packet_threshold: u64,
+    pub(crate) max_validating_paths: u8,
+    pub(crate) max_connection_buffer_bytes: usize,
+    pub(crate) stream_scheduler: &'static dyn stream::scheduler::Scheduler,
+    pub(crate) replay_window: u64,
+    pub(crate) stream_limit_policy: stream::limits::StreamLimitPolicy,
+    pub(crate) max_stream_receive_gaps: usize,
+    pub(crate) ecn_mode: inet::EcnMode,
+    pub(crate) max_coalesced_packets: u8,
+    pub(crate) dscp: u8,
+    pub(crate) max_send_rate: Option<u64>,
+    pub(crate) max_send_burst: u32,
+    pub(crate) max_version_negotiation_retries: u8,
 }
 
 impl Default for Limits {
@@ -103,10 +132,25 @@ impl Limits {
             max_active_connection_ids: ActiveConnectionIdLimit::RECOMMENDED,
             ack_elicitation_interval: ack::Settings::RECOMMENDED.ack_elicitation_interval,
             ack_ranges_limit: ack::Settings::RECOMMENDED.ack_ranges_limit,
+            immediate_ack_on_congestion: ack::Settings::RECOMMENDED.immediate_ack_on_congestion,
             max_send_buffer_size: stream::Limits::RECOMMENDED.max_send_buffer_size,
             max_handshake_duration: MAX_HANDSHAKE_DURATION_DEFAULT,
             max_keep_alive_period: MAX_KEEP_ALIVE_PERIOD_DEFAULT,
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
+            initial_round_trip_time: recovery::DEFAULT_INITIAL_RTT,
+            key_update_packet_threshold: u64::MAX,
+            max_validating_paths: MAX_VALIDATING_PATHS,
+            max_connection_buffer_bytes: usize::MAX,
+            stream_scheduler: &stream::scheduler::RoundRobin,
+            replay_window: SlidingWindow::MAX_WIDTH,
+            stream_limit_policy: stream::limits::StreamLimitPolicy::Fixed,
+            max_stream_receive_gaps: usize::MAX,
+            ecn_mode: inet::EcnMode::Enabled,
+            max_coalesced_packets: MAX_COALESCED_PACKETS,
+            dscp: 0,
+            max_send_rate: None,
+            max_send_burst: 0,
+            max_version_negotiation_retries: MAX_VERSION_NEGOTIATION_RETRIES_DEFAULT,
         }
     }
 
@@ -219,6 +263,269 @@ impl Limits {
     );
     setter!(with_max_keep_alive_period, max_keep_alive_period, Duration);
 
+    /// Sets the round trip time assumed before any RTT samples are available
+    ///
+    /// This value determines the probe timeout (PTO) period during the handshake, before
+    /// the peer has acknowledged any packets. Once an RTT sample is obtained, the PTO period
+    /// is derived from the measured RTT.
+    ///
+    /// Defaults to 333ms, as recommended by [RFC 9002](https://www.rfc-editor.org/rfc/rfc9002#section-6.2.2).
+    pub fn with_initial_round_trip_time(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            value >= recovery::K_GRANULARITY,
+            "initial_round_trip_time must be at least 1ms"
+        );
+        self.initial_round_trip_time = value;
+        Ok(self)
+    }
+
+    /// Sets the number of packets protected with a 1-RTT key before a key update is initiated
+    ///
+    /// Keys are always updated before reaching the confidentiality limit of the negotiated
+    /// AEAD. This threshold can be used to update keys more frequently on connections that
+    /// send a large number of packets.
+    ///
+    /// Defaults to no threshold.
+    pub fn with_key_update_packet_threshold(mut self, value: u64) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            value > 0,
+            "key_update_packet_threshold must be greater than 0"
+        );
+        self.key_update_packet_threshold = value;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of paths which can be validated at the same time
+    ///
+    /// Paths created by the peer migrating or by probing from a new local address are rejected
+    /// while this many paths are waiting on a PATH_RESPONSE. Path validations which are already
+    /// in progress are not affected.
+    ///
+    /// Defaults to 5, which is also the maximum since it's the number of paths a connection
+    /// maintains.
+    pub fn with_max_validating_paths(mut self, value: u8) -> Result<Self, ValidationError> {
+        decoder_invariant!(value > 0, "max_validating_paths must be greater than 0");
+        decoder_invariant!(
+            value <= MAX_VALIDATING_PATHS,
+            "max_validating_paths must not exceed 5"
+        );
+        self.max_validating_paths = value;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of bytes a connection buffers across all of its streams
+    ///
+    /// This includes data which was written by the application but not yet acknowledged by the
+    /// peer, as well as data which was received but not yet read by the application. Streams
+    /// stop accepting data from the application and the flow control window advertised to the
+    /// peer stops growing once the limit is reached. The data window is reduced to this value
+    /// if it is larger.
+    ///
+    /// A `ConnectionMemoryPressure` event is emitted when 80% of the limit is used.
+    ///
+    /// Defaults to no limit.
+    pub fn with_max_connection_buffer_bytes(
+        mut self,
+        value: usize,
+    ) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            value > 0,
+            "max_connection_buffer_bytes must be greater than 0"
+        );
+        self.max_connection_buffer_bytes = value;
+        Ok(self)
+    }
+
+    /// Sets the strategy for choosing which stream transmits next when multiple streams have
+    /// data to send
+    ///
+    /// Defaults to [`RoundRobin`](stream::scheduler::RoundRobin).
+    pub fn with_stream_scheduler(
+        mut self,
+        scheduler: &'static dyn stream::scheduler::Scheduler,
+    ) -> Result<Self, ValidationError> {
+        self.stream_scheduler = scheduler;
+        Ok(self)
+    }
+
+    /// Sets how the limit of streams opened by the peer is managed once all of the allowed
+    /// streams are in use
+    ///
+    /// The ceiling of [`Grow`](stream::limits::StreamLimitPolicy::Grow) can't be larger than
+    /// 2^60. It also can't be lower than the configured maximum number of open remote streams,
+    /// which is checked by [`Self::validate`] when the endpoint is started.
+    ///
+    /// Defaults to [`Fixed`](stream::limits::StreamLimitPolicy::Fixed).
+    pub fn with_stream_limit_policy(
+        mut self,
+        policy: stream::limits::StreamLimitPolicy,
+    ) -> Result<Self, ValidationError> {
+        if let stream::limits::StreamLimitPolicy::Grow(max) = policy {
+            decoder_invariant!(max <= 1 << 60, "stream limit ceiling must not exceed 2^60");
+        }
+        self.stream_limit_policy = policy;
+        Ok(self)
+    }
+
+    /// Checks the limits which depend on each other
+    ///
+    /// The setters only validate their own value, so the limits can be set in any order. The
+    /// combination is checked once all of them are set.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let stream::limits::StreamLimitPolicy::Grow(max) = self.stream_limit_policy {
+            decoder_invariant!(
+                max >= self
+                    .max_open_remote_bidirectional_streams
+                    .as_varint()
+                    .as_u64()
+                    && max
+                        >= self
+                            .max_open_remote_unidirectional_streams
+                            .as_varint()
+                            .as_u64(),
+                "stream limit ceiling must be at least the maximum number of open remote streams"
+            );
+        }
+        Ok(())
+    }
+
+    /// Sets the maximum number of gaps in the data received on each stream
+    ///
+    /// A gap is a range of stream data which is missing while later data was already received.
+    /// Tracking each gap costs memory, so a peer which sends data in many small, out-of-order
+    /// pieces can be bounded with this limit. The connection is closed with a
+    /// PROTOCOL_VIOLATION error if a stream exceeds it.
+    ///
+    /// Defaults to no limit.
+    pub fn with_max_stream_receive_gaps(mut self, value: usize) -> Result<Self, ValidationError> {
+        decoder_invariant!(value > 0, "max_stream_receive_gaps must be greater than 0");
+        self.max_stream_receive_gaps = value;
+        Ok(self)
+    }
+
+    /// Sets the number of packet numbers tracked for detecting duplicate packets
+    ///
+    /// Application data packets which were already received, or which have a packet number
+    /// `value` or more below the largest packet number received, are dropped. Smaller values
+    /// drop packets which are heavily reordered.
+    ///
+    /// Defaults to 129, which is also the maximum.
+    pub fn with_replay_window(mut self, value: u64) -> Result<Self, ValidationError> {
+        decoder_invariant!(value > 0, "replay_window must be greater than 0");
+        decoder_invariant!(
+            value <= SlidingWindow::MAX_WIDTH,
+            "replay_window must not exceed 129"
+        );
+        self.replay_window = value;
+        Ok(self)
+    }
+
+    /// Sets whether ACKs are sent immediately on congestion or loss signals
+    ///
+    /// When enabled, a packet marked with ECN Congestion Experienced (CE) or received with a
+    /// gap in the packet numbers is acknowledged without waiting for the ACK delay timer. This
+    /// allows the peer to react to congestion sooner, at the cost of sending more ACKs.
+    ///
+    /// Defaults to `true`.
+    pub fn with_immediate_ack_on_congestion(
+        mut self,
+        enabled: bool,
+    ) -> Result<Self, ValidationError> {
+        self.immediate_ack_on_congestion = enabled;
+        Ok(self)
+    }
+
+    /// Sets whether packets are marked as ECN-capable
+    ///
+    /// With [`EcnMode::Enabled`](inet::EcnMode::Enabled), ECN is validated on each path before
+    /// the congestion controller relies on the feedback from the peer. Paths which fail
+    /// validation, e.g. because a middlebox clears the markings, stop using ECN.
+    ///
+    /// Defaults to [`EcnMode::Enabled`](inet::EcnMode::Enabled).
+    pub fn with_ecn(mut self, mode: inet::EcnMode) -> Result<Self, ValidationError> {
+        self.ecn_mode = mode;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of packets which are coalesced into a single datagram
+    ///
+    /// Packets from different packet number spaces, e.g. Initial and Handshake packets, are
+    /// written to the same datagram when they fit within the path MTU. A value of 1 disables
+    /// coalescing, which sends each packet in its own datagram at the cost of sending more
+    /// datagrams during the handshake.
+    ///
+    /// Defaults to 3, which is also the maximum.
+    pub fn with_max_coalesced_packets(mut self, value: u8) -> Result<Self, ValidationError> {
+        decoder_invariant!(value > 0, "max_coalesced_packets must be greater than 0");
+        decoder_invariant!(
+            value <= MAX_COALESCED_PACKETS,
+            "max_coalesced_packets must not exceed 3"
+        );
+        self.max_coalesced_packets = value;
+        Ok(self)
+    }
+
+    /// Sets the DSCP code point which marks the connection's packets
+    ///
+    /// The code point is written to the IP_TOS or IPV6_TCLASS field of each packet, along with
+    /// the ECN markings. Streams which set their own code point override it. The connection can
+    /// also change it after it's been established.
+    ///
+    /// Defaults to 0, which is the default forwarding class.
+    pub fn with_dscp(mut self, value: u8) -> Result<Self, ValidationError> {
+        decoder_invariant!(value <= 0b11_1111, "dscp must not exceed 63");
+        self.dscp = value;
+        Ok(self)
+    }
+
+    /// Limits the rate at which each connection sends data to `value` bytes per second
+    ///
+    /// The limit is a hard cap which applies regardless of the congestion window, so a single
+    /// connection can't send at line rate on links shared with other traffic. The congestion
+    /// controller may still pace the connection below the limit. The connection can also
+    /// change the limit after it's been established.
+    ///
+    /// Defaults to no limit.
+    pub fn with_max_send_rate(mut self, value: u64) -> Result<Self, ValidationError> {
+        decoder_invariant!(value > 0, "max_send_rate must be greater than 0");
+        self.max_send_rate = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the number of bytes which can be sent at once above the maximum send rate
+    ///
+    /// Datagrams are allowed to depart ahead of the rate until the burst is used up. The burst
+    /// is replenished at the maximum send rate while the connection isn't sending. This has no
+    /// effect unless a maximum send rate is set.
+    ///
+    /// The bursts sent by the congestion controller's pacer are limited separately, with
+    /// [`cubic::Builder::with_max_burst_packets`](crate::recovery::cubic::Builder::with_max_burst_packets)
+    /// or [`bbr::Builder::with_max_burst_packets`](crate::recovery::bbr::Builder::with_max_burst_packets).
+    ///
+    /// Defaults to 0, which spaces out every datagram.
+    pub fn with_max_send_burst(mut self, value: u32) -> Result<Self, ValidationError> {
+        self.max_send_burst = value;
+        Ok(self)
+    }
+
+    /// Sets the number of times a client restarts a connection attempt after the server responds
+    /// with a Version Negotiation packet
+    ///
+    /// Each restart selects a version supported by both endpoints. A value of 0 returns the
+    /// version negotiation error to the application instead.
+    ///
+    /// Defaults to 2.
+    pub fn with_max_version_negotiation_retries(
+        mut self,
+        value: u8,
+    ) -> Result<Self, ValidationError> {
+        self.max_version_negotiation_retries = value;
+        Ok(self)
+    }
+
     // internal APIs
 
     #[doc(hidden)]
@@ -236,15 +543,16 @@ impl Limits {
             max_ack_delay: self.max_ack_delay.as_duration(),
             ack_ranges_limit: self.ack_ranges_limit,
             ack_elicitation_interval: self.ack_elicitation_interval,
+            immediate_ack_on_congestion: self.immediate_ack_on_congestion,
         }
     }
 
     #[doc(hidden)]
     #[inline]
-    pub const fn initial_flow_control_limits(&self) -> InitialFlowControlLimits {
+    pub fn initial_flow_control_limits(&self) -> InitialFlowControlLimits {
         InitialFlowControlLimits {
             stream_limits: self.initial_stream_limits(),
-            max_data: self.data_window.as_varint(),
+            max_data: self.bounded_data_window().as_varint(),
             max_open_remote_bidirectional_streams: self
                 .max_open_remote_bidirectional_streams
                 .as_varint(),
@@ -271,6 +579,8 @@ impl Limits {
             max_send_buffer_size: self.max_send_buffer_size,
             max_open_local_unidirectional_streams: self.max_open_local_unidirectional_streams,
             max_open_local_bidirectional_streams: self.max_open_local_bidirectional_streams,
+            remote_stream_limit_policy: self.stream_limit_policy,
+            max_receive_gaps: self.max_stream_receive_gaps,
         }
     }
 
@@ -291,11 +601,104 @@ impl Limits {
     pub fn max_keep_alive_period(&self) -> Duration {
         self.max_keep_alive_period
     }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn initial_round_trip_time(&self) -> Duration {
+        self.initial_round_trip_time
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn key_update_packet_threshold(&self) -> u64 {
+        self.key_update_packet_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_validating_paths(&self) -> u8 {
+        self.max_validating_paths
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_connection_buffer_bytes(&self) -> usize {
+        self.max_connection_buffer_bytes
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn stream_scheduler(&self) -> &'static dyn stream::scheduler::Scheduler {
+        self.stream_scheduler
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn replay_window(&self) -> u64 {
+        self.replay_window
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn ecn_mode(&self) -> inet::EcnMode {
+        self.ecn_mode
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_coalesced_packets(&self) -> u8 {
+        self.max_coalesced_packets
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn dscp(&self) -> u8 {
+        self.dscp
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_send_rate(&self) -> Option<u64> {
+        self.max_send_rate
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_send_burst(&self) -> u32 {
+        self.max_send_burst
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_version_negotiation_retries(&self) -> u8 {
+        self.max_version_negotiation_retries
+    }
+
+    /// Returns the data window, which is bounded by the number of bytes the connection buffers
+    #[inline]
+    pub(crate) fn bounded_data_window(&self) -> InitialMaxData {
+        let max_buffered = self
+            .max_connection_buffer_bytes
+            .try_into()
+            .unwrap_or(u64::MAX);
+
+        if self.data_window.as_varint() > max_buffered {
+            // the data window is at most u32::MAX so the conversion can't fail
+            InitialMaxData::try_from(max_buffered).unwrap_or(self.data_window)
+        } else {
+            self.data_window
+        }
+    }
 }
 
 /// Creates limits for a given connection
 pub trait Limiter: 'static + Send {
     fn on_connection(&mut self, info: &ConnectionInfo) -> Limits;
+
+    /// Validates the limiter before the endpoint is started
+    fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
 }
 
 /// Implement Limiter for a Limits struct
@@ -303,11 +706,16 @@ impl Limiter for Limits {
     fn on_connection(&mut self, _into: &ConnectionInfo) -> Limits {
         *self
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        Limits::validate(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::varint::VarInt;
 
     // Local max data limits should be <= u32::MAX
     #[test]
@@ -325,4 +733,122 @@ mod tests {
         assert!(limits.with_bidirectional_remote_data_window(data).is_ok());
         assert!(limits.with_unidirectional_data_window(data).is_ok());
     }
+
+    #[test]
+    fn initial_round_trip_time_validation() {
+        let limits = Limits::default();
+        assert!(limits
+            .with_initial_round_trip_time(Duration::from_micros(999))
+            .is_err());
+        assert!(limits
+            .with_initial_round_trip_time(Duration::from_millis(1))
+            .is_ok());
+    }
+
+    #[test]
+    fn key_update_packet_threshold_validation() {
+        let limits = Limits::default();
+        assert!(limits.with_key_update_packet_threshold(0).is_err());
+        assert!(limits.with_key_update_packet_threshold(1).is_ok());
+    }
+
+    #[test]
+    fn max_validating_paths_validation() {
+        let limits = Limits::default();
+        assert!(limits.with_max_validating_paths(0).is_err());
+        assert!(limits.with_max_validating_paths(1).is_ok());
+        assert!(limits.with_max_validating_paths(5).is_ok());
+        assert!(limits.with_max_validating_paths(6).is_err());
+    }
+
+    #[test]
+    fn dscp_validation() {
+        let limits = Limits::default();
+        assert!(limits.with_dscp(63).is_ok());
+        assert!(limits.with_dscp(64).is_err());
+    }
+
+    #[test]
+    fn max_send_rate_validation() {
+        let limits = Limits::default();
+        assert!(limits.with_max_send_rate(0).is_err());
+        assert_eq!(
+            limits.with_max_send_rate(1).unwrap().max_send_rate(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn max_connection_buffer_bytes_validation() {
+        let limits = Limits::default();
+        assert!(limits.with_max_connection_buffer_bytes(0).is_err());
+        assert!(limits.with_max_connection_buffer_bytes(1).is_ok());
+    }
+
+    #[test]
+    fn replay_window_validation() {
+        let limits = Limits::default();
+        assert!(limits.with_replay_window(0).is_err());
+        assert!(limits.with_replay_window(1).is_ok());
+        assert!(limits.with_replay_window(SlidingWindow::MAX_WIDTH).is_ok());
+        assert!(limits
+            .with_replay_window(SlidingWindow::MAX_WIDTH + 1)
+            .is_err());
+    }
+
+    #[test]
+    fn max_stream_receive_gaps_validation() {
+        let limits = Limits::default();
+        assert!(limits.with_max_stream_receive_gaps(0).is_err());
+
+        let limits = limits.with_max_stream_receive_gaps(1).unwrap();
+        assert_eq!(limits.stream_limits().max_receive_gaps, 1);
+    }
+
+    #[test]
+    fn stream_limit_policy_validation() {
+        use stream::limits::StreamLimitPolicy;
+
+        let limits = Limits::default()
+            .with_max_open_remote_bidirectional_streams(10)
+            .unwrap()
+            .with_max_open_remote_unidirectional_streams(10)
+            .unwrap();
+        let grow = |max| limits.with_stream_limit_policy(StreamLimitPolicy::Grow(max));
+        assert!(grow(9).unwrap().validate().is_err());
+        assert!(grow(10).unwrap().validate().is_ok());
+        assert!(grow((1 << 60) + 1).is_err());
+        assert!(limits
+            .with_stream_limit_policy(StreamLimitPolicy::Reject)
+            .unwrap()
+            .validate()
+            .is_ok());
+
+        // the limits can be set in any order
+        let limits = grow(20)
+            .unwrap()
+            .with_max_open_remote_bidirectional_streams(30)
+            .unwrap();
+        assert!(limits.validate().is_err());
+        assert!(limits
+            .with_max_open_remote_bidirectional_streams(20)
+            .unwrap()
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn max_connection_buffer_bytes_data_window() {
+        let limits = Limits::default().with_data_window(100_000).unwrap();
+        assert_eq!(
+            limits.initial_flow_control_limits().max_data,
+            VarInt::from_u32(100_000)
+        );
+
+        let limits = limits.with_max_connection_buffer_bytes(50_000).unwrap();
+        assert_eq!(
+            limits.initial_flow_control_limits().max_data,
+            VarInt::from_u32(50_000)
+        );
+    }
 }
@@ -5,7 +5,12 @@ pub mod close;
 pub mod error;
 pub mod id;
 pub mod limits;
+pub mod stats;
+pub mod version;
 
-pub use error::{Error, ProcessingError};
+pub use close::CloseOnDrop;
+pub use error::{DscpError, Error, ProcessingError};
 pub use id::{InitialId, LocalId, PeerId, UnboundedId};
 pub use limits::Limits;
+pub use stats::{SpaceStats, Stats};
+pub use version::Version;
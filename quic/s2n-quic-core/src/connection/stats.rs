@@ -0,0 +1,190 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Statistics collected over the lifetime of a connection
+
+use crate::{frame::ack::EcnCounts, time::Timestamp};
+use core::time::Duration;
+
+/// Describes what prevented a connection from sending more data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SendLimit {
+    /// The application did not provide any more data to send
+    Application,
+    /// The remaining data is blocked by the peer's stream or connection flow control limits
+    FlowControl,
+    /// The remaining data is blocked by the congestion window or pacing
+    CongestionControl,
+}
+
+/// Statistics collected over the lifetime of a connection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The amount of time the connection was waiting on the application to provide data
+    pub app_limited: Duration,
+    /// The amount of time the connection had data to send which was blocked by flow control
+    pub flow_control_limited: Duration,
+    /// The amount of time the connection had data to send which was blocked by the congestion
+    /// controller
+    pub cwnd_limited: Duration,
+    /// The packets sent in the Initial packet number space
+    pub initial: SpaceStats,
+    /// The packets sent in the Handshake packet number space
+    pub handshake: SpaceStats,
+    /// The packets sent in the Application Data packet number space
+    pub application: SpaceStats,
+    /// The smoothed round trip time of the active path
+    pub smoothed_rtt: Duration,
+    /// The minimum round trip time observed on the active path
+    pub min_rtt: Duration,
+    /// The congestion window of the active path, in bytes
+    pub congestion_window: u32,
+    /// The number of bytes sent on the active path which have not been acknowledged or
+    /// declared lost
+    pub bytes_in_flight: u32,
+    /// The maximum transmission unit of the active path, in bytes
+    pub mtu: u16,
+    /// The rate at which packets are paced on the active path, in bytes per second
+    ///
+    /// This is `None` if the congestion controller is not currently pacing packets.
+    pub pacing_rate: Option<u64>,
+}
+
+/// Counts the packets sent in a single packet number space
+///
+/// This helps diagnosing handshakes which don't make progress, e.g. because a Handshake flight
+/// is repeatedly lost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SpaceStats {
+    /// The number of packets sent
+    pub packets_sent: u64,
+    /// The number of packets acknowledged by the peer
+    pub packets_acked: u64,
+    /// The number of packets declared lost
+    pub packets_lost: u64,
+    /// The ECN counts the peer reported in ACK frames for the packets it received
+    pub ecn_sent: EcnCounts,
+    /// The ECN markings of the packets received from the peer
+    pub ecn_received: EcnCounts,
+}
+
+impl Stats {
+    /// Returns the fraction of time the connection was application limited
+    #[inline]
+    pub fn app_limited_ratio(&self) -> f64 {
+        self.ratio(self.app_limited)
+    }
+
+    /// Returns the fraction of time the connection was limited by flow control
+    #[inline]
+    pub fn flow_control_limited_ratio(&self) -> f64 {
+        self.ratio(self.flow_control_limited)
+    }
+
+    /// Returns the fraction of time the connection was limited by the congestion controller
+    #[inline]
+    pub fn cwnd_limited_ratio(&self) -> f64 {
+        self.ratio(self.cwnd_limited)
+    }
+
+    /// Returns the number of packets declared lost, across all of the packet number spaces
+    #[inline]
+    pub fn packets_lost(&self) -> u64 {
+        self.initial.packets_lost + self.handshake.packets_lost + self.application.packets_lost
+    }
+
+    /// Returns the ECN counts the peer reported for the packets it received, across all of
+    /// the packet number spaces
+    #[inline]
+    pub fn ecn_sent(&self) -> EcnCounts {
+        let mut counts = self.initial.ecn_sent;
+        counts += self.handshake.ecn_sent;
+        counts += self.application.ecn_sent;
+        counts
+    }
+
+    /// Returns the ECN markings of the packets received from the peer, across all of the packet
+    /// number spaces
+    #[inline]
+    pub fn ecn_received(&self) -> EcnCounts {
+        let mut counts = self.initial.ecn_received;
+        counts += self.handshake.ecn_received;
+        counts += self.application.ecn_received;
+        counts
+    }
+
+    #[inline]
+    fn ratio(&self, value: Duration) -> f64 {
+        let total = self.app_limited + self.flow_control_limited + self.cwnd_limited;
+
+        if total.is_zero() {
+            return 0.0;
+        }
+
+        value.as_secs_f64() / total.as_secs_f64()
+    }
+}
+
+/// Accumulates the time a connection spends in each [`SendLimit`]
+#[derive(Clone, Debug, Default)]
+pub struct Tracker {
+    stats: Stats,
+    current: Option<(SendLimit, Timestamp)>,
+}
+
+impl Tracker {
+    /// Called after the connection transmitted as much as it could
+    ///
+    /// The time since the previous call is attributed to the previously reported limit.
+    #[inline]
+    pub fn on_send_limit(&mut self, limit: SendLimit, now: Timestamp) {
+        if let Some((previous, since)) = self.current.replace((limit, now)) {
+            let elapsed = now.saturating_duration_since(since);
+            match previous {
+                SendLimit::Application => self.stats.app_limited += elapsed,
+                SendLimit::FlowControl => self.stats.flow_control_limited += elapsed,
+                SendLimit::CongestionControl => self.stats.cwnd_limited += elapsed,
+            }
+        }
+    }
+
+    /// Returns the statistics accumulated up to the last reported limit
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{Clock, NoopClock};
+
+    #[test]
+    fn tracker_test() {
+        let mut tracker = Tracker::default();
+        let now = NoopClock.get_time();
+
+        // nothing is accounted for until the second limit is reported
+        tracker.on_send_limit(SendLimit::Application, now);
+        assert_eq!(tracker.stats(), Stats::default());
+        assert_eq!(tracker.stats().app_limited_ratio(), 0.0);
+
+        let now = now + Duration::from_millis(300);
+        tracker.on_send_limit(SendLimit::CongestionControl, now);
+        let now = now + Duration::from_millis(100);
+        tracker.on_send_limit(SendLimit::FlowControl, now);
+        let now = now + Duration::from_millis(100);
+        tracker.on_send_limit(SendLimit::Application, now);
+
+        let stats = tracker.stats();
+        assert_eq!(stats.app_limited, Duration::from_millis(300));
+        assert_eq!(stats.cwnd_limited, Duration::from_millis(100));
+        assert_eq!(stats.flow_control_limited, Duration::from_millis(100));
+        assert_eq!(stats.app_limited_ratio(), 0.6);
+        assert_eq!(stats.cwnd_limited_ratio(), 0.2);
+        assert_eq!(stats.flow_control_limited_ratio(), 0.2);
+    }
+}
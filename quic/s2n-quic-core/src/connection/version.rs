@@ -0,0 +1,100 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The QUIC version negotiated by a connection
+
+use core::fmt;
+
+/// A QUIC version, as carried in the version field of long header packets
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version(u32);
+
+impl Version {
+    /// QUIC version 1
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc9000>
+    pub const V1: Self = Self(0x0000_0001);
+
+    /// QUIC version 2
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc9369>
+    pub const V2: Self = Self(0x6b33_43cf);
+
+    /// Returns the value of the version on the wire
+    #[inline]
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if the version is reserved to exercise version negotiation
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc9000#section-15>
+    #[inline]
+    pub const fn is_reserved(self) -> bool {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-15
+        //# Versions that follow the pattern 0x?a?a?a?a are reserved for use in
+        //# forcing version negotiation to be exercised
+        self.0 & 0x0f0f_0f0f == 0x0a0a_0a0a
+    }
+
+    /// Returns the draft number if the version is an IETF draft version
+    #[inline]
+    pub const fn draft(self) -> Option<u8> {
+        // draft versions are encoded as 0xff0000XX
+        if self.0 >> 8 == 0x00ff_0000 {
+            Some(self.0 as u8)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<u32> for Version {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Version> for u32 {
+    #[inline]
+    fn from(version: Version) -> Self {
+        version.0
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::V1 => write!(f, "v1"),
+            Self::V2 => write!(f, "v2"),
+            version => match version.draft() {
+                Some(draft) => write!(f, "draft-{draft}"),
+                None => write!(f, "{:#010x}", version.0),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!(Version::V1.to_string(), "v1");
+        assert_eq!(Version::V2.to_string(), "v2");
+        assert_eq!(Version::from(0xff00_001d).to_string(), "draft-29");
+        assert_eq!(Version::from(0x0a0a_0a0a).to_string(), "0x0a0a0a0a");
+        assert_eq!(Version::V1.draft(), None);
+    }
+
+    #[test]
+    fn reserved_test() {
+        assert!(Version::from(0x0a0a_0a0a).is_reserved());
+        assert!(Version::from(0x1a2a_3a4a).is_reserved());
+        assert!(!Version::V1.is_reserved());
+        assert!(!Version::V2.is_reserved());
+        assert!(!Version::from(0x0a0a_0a0b).is_reserved());
+    }
+}
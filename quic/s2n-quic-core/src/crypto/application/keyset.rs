@@ -218,6 +218,18 @@ impl<K: OneRttKey> KeySet<K> {
             return KeyPhase::next_phase(self.key_phase());
         }
 
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.5
+        //# An endpoint MUST NOT initiate a subsequent key update unless it has
+        //# received an acknowledgment for a packet that was sent protected with
+        //# keys from the current key phase.
+        // The next key isn't derived until the update is complete so wait before initiating
+        // another update from the packet threshold.
+        if !self.key_update_in_progress()
+            && self.active_key().reached_packet_threshold(&self.limits)
+        {
+            return KeyPhase::next_phase(self.key_phase());
+        }
+
         self.key_phase()
     }
 
@@ -254,7 +266,7 @@ impl<K: OneRttKey> KeySet<K> {
         //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
         //# Endpoints MUST count the number of encrypted packets for each set of
         //# keys.
-        self.crypto[phase].on_packet_encryption(&self.limits);
+        self.crypto[phase].on_packet_encryption(r.0.len(), &self.limits);
 
         Ok(r)
     }
@@ -284,6 +296,16 @@ impl<K: OneRttKey> KeySet<K> {
         &mut self.crypto[self.key_phase]
     }
 
+    /// Returns the amount of data protected by the key in the current phase
+    pub fn usage(&self) -> KeyUsage {
+        let key = self.active_key();
+        KeyUsage {
+            generation: self.generation,
+            packets: key.encrypted_packets(),
+            bytes: key.encrypted_bytes(),
+        }
+    }
+
     fn decryption_error_count(&self) -> u64 {
         self.packet_decryption_failures
     }
@@ -293,6 +315,18 @@ impl<K: OneRttKey> KeySet<K> {
     }
 }
 
+/// The amount of data protected by the current 1-RTT key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeyUsage {
+    /// The number of times the key has been updated
+    pub generation: u16,
+    /// The number of packets protected by the current key
+    pub packets: u64,
+    /// The number of bytes protected by the current key
+    pub bytes: u64,
+}
+
 impl<K> timer::Provider for KeySet<K> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
@@ -542,6 +576,60 @@ mod tests {
         assert!(keyset.active_key().needs_update(&keyset.limits));
     }
 
+    #[test]
+    fn test_encrypted_packet_threshold() {
+        let limits = limited::Limits {
+            key_update_packet_threshold: 5,
+            ..Default::default()
+        };
+        let mut keyset = KeySet::new(TestKey::default(), limits);
+        let mut encoder_bytes = [0; 512];
+
+        for _ in 0..5 {
+            assert_eq!(keyset.encryption_phase(), KeyPhase::Zero);
+
+            let buffer = EncoderBuffer::new(&mut encoder_bytes);
+            let mut decoder_bytes = [0; 512];
+            assert!(keyset
+                .encrypt_packet(buffer, |buffer, _key, _phase| {
+                    let payload = ProtectedPayload::new(0, &mut decoder_bytes);
+
+                    Ok((payload, buffer))
+                })
+                .is_ok());
+        }
+
+        let usage = keyset.usage();
+        assert_eq!(usage.generation, 0);
+        assert_eq!(usage.packets, 5);
+        assert_eq!(usage.bytes, 5 * 512);
+
+        // once the threshold is reached the next phase should be used
+        assert_eq!(keyset.encryption_phase(), KeyPhase::One);
+
+        // the usage resets once the peer confirms the update
+        keyset.rotate_phase();
+        let usage = keyset.usage();
+        assert_eq!(usage.generation, 1);
+        assert_eq!(usage.packets, 0);
+
+        // another update shouldn't be initiated until the next key is derived
+        keyset.set_derivation_timer(Clock::default().get_time());
+        for _ in 0..5 {
+            let buffer = EncoderBuffer::new(&mut encoder_bytes);
+            let mut decoder_bytes = [0; 512];
+            assert!(keyset
+                .encrypt_packet(buffer, |buffer, _key, _phase| {
+                    let payload = ProtectedPayload::new(0, &mut decoder_bytes);
+
+                    Ok((payload, buffer))
+                })
+                .is_ok());
+        }
+        assert_eq!(keyset.encryption_phase(), KeyPhase::One);
+        assert!(keyset.key_update_in_progress());
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
     //= type=test
     //# If the total number of encrypted packets with the same key
@@ -12,6 +12,7 @@ pub struct Key<K> {
     // Keeping encrypted_packets out of the key allow keys to be immutable, which allows optimizations
     // later on.
     encrypted_packets: u64,
+    encrypted_bytes: u64,
     decrypted_packets: u64,
     confidentiality_limit: u64,
 }
@@ -21,6 +22,9 @@ pub struct Key<K> {
 pub struct Limits {
     /// The number of packets before the limit at which a key update will be scheduled
     pub key_update_window: u64,
+    /// The number of packets protected by a key at which a key update will be initiated, even
+    /// if the key is not close to its confidentiality limit
+    pub key_update_packet_threshold: u64,
     /// The number of packets at which the sealer key will be optimized
     pub sealer_optimization_threshold: u64,
     /// The number of packets at which the opener key will be optimized
@@ -33,6 +37,7 @@ impl Default for Limits {
     fn default() -> Self {
         Self {
             key_update_window: KEY_UPDATE_WINDOW,
+            key_update_packet_threshold: u64::MAX,
             sealer_optimization_threshold: 100,
             opener_optimization_threshold: 100,
             max_mtu: MaxMtu::default(),
@@ -53,6 +58,7 @@ impl<K: OneRttKey> Key<K> {
             confidentiality_limit: key.aead_confidentiality_limit(),
             key,
             encrypted_packets: 0,
+            encrypted_bytes: 0,
             decrypted_packets: 0,
         }
     }
@@ -74,6 +80,13 @@ impl<K: OneRttKey> Key<K> {
                 .saturating_sub(limits.key_update_window))
     }
 
+    /// If the key has protected at least the configured threshold of packets, an update should
+    /// be initiated.
+    #[inline]
+    pub fn reached_packet_threshold(&self, limits: &Limits) -> bool {
+        self.encrypted_packets >= limits.key_update_packet_threshold
+    }
+
     pub fn derive_next_key(&self) -> K {
         self.key.derive_next_key()
     }
@@ -84,8 +97,14 @@ impl<K: OneRttKey> Key<K> {
     }
 
     #[inline]
-    pub fn on_packet_encryption(&mut self, limits: &Limits) {
+    pub fn encrypted_bytes(&self) -> u64 {
+        self.encrypted_bytes
+    }
+
+    #[inline]
+    pub fn on_packet_encryption(&mut self, len: usize, limits: &Limits) {
         self.encrypted_packets += 1;
+        self.encrypted_bytes += len as u64;
 
         if self.encrypted_packets == limits.sealer_optimization_threshold {
             self.key.update_sealer_pmtu(limits.max_mtu.into());
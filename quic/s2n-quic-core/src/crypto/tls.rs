@@ -3,9 +3,14 @@
 
 #[cfg(feature = "alloc")]
 pub use bytes::{Bytes, BytesMut};
-use core::{convert::TryFrom, fmt::Debug};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+};
 use zerocopy::{AsBytes, FromBytes, Unaligned};
 
+#[cfg(feature = "alloc")]
+pub mod fingerprint;
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
@@ -54,6 +59,15 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_protocol: Bytes,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called with the DER-encoded certificate chain the peer authenticated with
+    ///
+    /// The chain starts with the peer's leaf certificate. This is only called if the peer
+    /// presented a certificate, which for servers requires client authentication to be enabled.
+    fn on_peer_certificate_chain(
+        &mut self,
+        chain: alloc::vec::Vec<Bytes>,
+    ) -> Result<(), crate::transport::Error>;
+
     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.1.1
     //# The TLS handshake is considered complete when the
     //# TLS stack has reported that the handshake is complete.  This happens
@@ -108,6 +122,15 @@ pub trait Endpoint: 'static + Sized + Send {
 
     /// The maximum length of a tag for any algorithm that may be negotiated
     fn max_tag_length(&self) -> usize;
+
+    /// Sets the clock which session ticket expirations are read from
+    ///
+    /// This is called with the endpoint's wall clock before any sessions are created.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn set_wall_clock(&mut self, clock: &std::sync::Arc<dyn crate::time::WallClock>) {
+        let _ = clock;
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -116,10 +139,108 @@ pub trait Session: crate::crypto::CryptoSuite + Sized + Send + Debug {
         &mut self,
         context: &mut C,
     ) -> core::task::Poll<Result<(), crate::transport::Error>>;
+
+    /// Exports keying material from the completed handshake
+    ///
+    /// `output` is filled with keying material derived from the exporter secret, as described in
+    /// [RFC 8446 Section 7.5](https://www.rfc-editor.org/rfc/rfc8446#section-7.5). An empty
+    /// `context` is equivalent to no context. This is only called once the handshake is
+    /// complete.
+    ///
+    /// Returns [`ExportError::Unsupported`] if the TLS provider doesn't support exporters.
+    #[inline]
+    fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), ExportError> {
+        let _ = (output, label, context);
+        Err(ExportError::Unsupported)
+    }
+
+    /// Serializes the state needed to resume the session in a future connection
+    ///
+    /// Returns `None` if the TLS provider doesn't support exporting resumption state or the
+    /// server hasn't issued a session ticket. This is only called once the handshake is
+    /// complete.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn resumption_state(&self) -> Option<alloc::vec::Vec<u8>> {
+        None
+    }
+}
+
+/// The parameters negotiated by the TLS handshake
+///
+/// QUIC always negotiates TLS 1.3 so the protocol version isn't included.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct HandshakeInfo {
+    /// The cipher suite protecting 1-RTT packets
+    pub cipher_suite: CipherSuite,
+    /// The server name the client connected to, if any
+    pub server_name: Option<crate::application::ServerName>,
+    /// The negotiated application protocol
+    pub application_protocol: Bytes,
+    /// The DER-encoded certificate chain the peer authenticated with, starting with its leaf
+    ///
+    /// This is empty if the peer didn't present a certificate.
+    pub peer_certificate_chain: alloc::vec::Vec<Bytes>,
+}
+
+/// Errors that can occur when exporting keying material from a connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExportError {
+    /// Keying material can't be exported until the handshake is complete
+    HandshakeNotComplete,
+    /// The TLS provider doesn't support exporting keying material
+    Unsupported,
+    /// The TLS provider was unable to export the keying material
+    CryptoError { error: crate::crypto::CryptoError },
+    /// The connection encountered an error
+    ConnectionError { error: crate::connection::Error },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExportError {}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::HandshakeNotComplete => write!(
+                f,
+                "Keying material can't be exported until the handshake is complete"
+            ),
+            Self::Unsupported => write!(
+                f,
+                "The TLS provider doesn't support exporting keying material"
+            ),
+            Self::CryptoError { error } => write!(f, "{error}"),
+            Self::ConnectionError { error } => write!(f, "{error}"),
+        }
+    }
+}
+
+impl From<crate::crypto::CryptoError> for ExportError {
+    #[inline]
+    fn from(error: crate::crypto::CryptoError) -> Self {
+        Self::CryptoError { error }
+    }
+}
+
+impl From<crate::connection::Error> for ExportError {
+    #[inline]
+    fn from(error: crate::connection::Error) -> Self {
+        Self::ConnectionError { error }
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
+#[non_exhaustive]
 pub enum CipherSuite {
     TLS_AES_128_GCM_SHA256,
     TLS_AES_256_GCM_SHA384,
@@ -0,0 +1,423 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fingerprints of the ClientHello message which can be used for bot detection and telemetry
+//!
+//! The [JA3](https://github.com/salesforce/ja3) and [JA4](https://github.com/FoxIO-LLC/ja4)
+//! fingerprints are always available in their unhashed form. The hashed forms, which are what
+//! fingerprint databases usually contain, require the `fingerprint-hashes` feature.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write;
+use s2n_codec::{DecoderBuffer, DecoderError};
+
+const SERVER_NAME: u16 = 0x0000;
+const SUPPORTED_GROUPS: u16 = 0x000a;
+const EC_POINT_FORMATS: u16 = 0x000b;
+const SIGNATURE_ALGORITHMS: u16 = 0x000d;
+const APPLICATION_LAYER_PROTOCOL_NEGOTIATION: u16 = 0x0010;
+const SUPPORTED_VERSIONS: u16 = 0x002b;
+
+/// The fields of a ClientHello message which are used to compute fingerprints
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClientHello {
+    legacy_version: u16,
+    cipher_suites: Vec<u16>,
+    extensions: Vec<u16>,
+    supported_groups: Vec<u16>,
+    ec_point_formats: Vec<u8>,
+    signature_algorithms: Vec<u16>,
+    supported_versions: Vec<u16>,
+    application_protocol: Option<Vec<u8>>,
+    has_server_name: bool,
+}
+
+impl ClientHello {
+    /// Decodes the body of a ClientHello message, without the handshake message header
+    pub fn decode(buffer: DecoderBuffer) -> Result<Self, DecoderError> {
+        let (legacy_version, buffer) = buffer.decode::<u16>()?;
+        // random
+        let buffer = buffer.skip(32)?;
+        // legacy_session_id
+        let buffer = buffer.skip_with_len_prefix::<u8>()?;
+        let (cipher_suites, buffer) = buffer.decode_slice_with_len_prefix::<u16>()?;
+        // legacy_compression_methods
+        let buffer = buffer.skip_with_len_prefix::<u8>()?;
+
+        let mut hello = Self {
+            legacy_version,
+            cipher_suites: decode_u16_list(cipher_suites)?,
+            ..Default::default()
+        };
+
+        // anything following the extensions belongs to the next message
+        let (mut extensions, _) = buffer.decode_slice_with_len_prefix::<u16>()?;
+
+        while !extensions.is_empty() {
+            let (extension_type, remaining) = extensions.decode::<u16>()?;
+            let (data, remaining) = remaining.decode_slice_with_len_prefix::<u16>()?;
+            extensions = remaining;
+
+            hello.extensions.push(extension_type);
+
+            match extension_type {
+                SERVER_NAME => hello.has_server_name = true,
+                SUPPORTED_GROUPS => {
+                    let (groups, _) = data.decode_slice_with_len_prefix::<u16>()?;
+                    hello.supported_groups = decode_u16_list(groups)?;
+                }
+                EC_POINT_FORMATS => {
+                    let (formats, _) = data.decode_slice_with_len_prefix::<u8>()?;
+                    hello.ec_point_formats = formats.into_less_safe_slice().to_vec();
+                }
+                SIGNATURE_ALGORITHMS => {
+                    let (algorithms, _) = data.decode_slice_with_len_prefix::<u16>()?;
+                    hello.signature_algorithms = decode_u16_list(algorithms)?;
+                }
+                APPLICATION_LAYER_PROTOCOL_NEGOTIATION => {
+                    let (protocols, _) = data.decode_slice_with_len_prefix::<u16>()?;
+                    if !protocols.is_empty() {
+                        let (protocol, _) = protocols.decode_slice_with_len_prefix::<u8>()?;
+                        hello.application_protocol = Some(protocol.into_less_safe_slice().to_vec());
+                    }
+                }
+                SUPPORTED_VERSIONS => {
+                    let (versions, _) = data.decode_slice_with_len_prefix::<u8>()?;
+                    hello.supported_versions = decode_u16_list(versions)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(hello)
+    }
+
+    /// Returns the JA3 string of the ClientHello
+    ///
+    /// The string contains the decimal values of the version, cipher suites, extensions,
+    /// supported groups and EC point formats, e.g. `771,4865-4866,0-10-16,29-23,0`. GREASE
+    /// values are ignored.
+    pub fn ja3(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(out, "{}", self.legacy_version);
+
+        for list in [
+            &self.cipher_suites,
+            &self.extensions,
+            &self.supported_groups,
+        ] {
+            out.push(',');
+            let list = list.iter().copied().filter(|v| !is_grease(*v));
+            write_list(&mut out, list, "-", |out, v| write!(out, "{v}"));
+        }
+
+        out.push(',');
+        write_list(&mut out, self.ec_point_formats.iter(), "-", |out, v| {
+            write!(out, "{v}")
+        });
+
+        out
+    }
+
+    /// Returns the raw JA4 fingerprint (`JA4_r`) of the ClientHello
+    ///
+    /// The cipher suites and extensions are sorted and listed in hex, rather than being
+    /// hashed, e.g. `q13d0310h3_1301,1302,1303_000a,000d,002b,..._0403,0804`. GREASE values
+    /// are ignored.
+    pub fn ja4_r(&self) -> String {
+        let (prefix, cipher_suites, extensions) = self.ja4_parts();
+        format!("{prefix}_{cipher_suites}_{extensions}")
+    }
+
+    /// Returns the MD5 digest of the JA3 string in hex
+    #[cfg(feature = "fingerprint-hashes")]
+    pub fn ja3_hash(&self) -> String {
+        use md5::Digest;
+
+        let mut out = String::new();
+        write_hex(&mut out, &md5::Md5::digest(self.ja3().as_bytes()));
+        out
+    }
+
+    /// Returns the JA4 fingerprint of the ClientHello
+    ///
+    /// The sorted cipher suites and the sorted extensions, followed by the signature algorithms,
+    /// are each replaced with the first 12 hex characters of their SHA-256 digest, e.g.
+    /// `q13d0310h3_55b375c5d22e_c5dff6720b55`. Empty lists are replaced with zeros.
+    #[cfg(feature = "fingerprint-hashes")]
+    pub fn ja4(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        fn truncated_hash(out: &mut String, list: &str) {
+            if list.is_empty() {
+                out.push_str("000000000000");
+            } else {
+                write_hex(out, &Sha256::digest(list.as_bytes())[..6]);
+            }
+        }
+
+        let (prefix, cipher_suites, extensions) = self.ja4_parts();
+
+        let mut out = prefix;
+        out.push('_');
+        truncated_hash(&mut out, &cipher_suites);
+        out.push('_');
+        truncated_hash(&mut out, &extensions);
+        out
+    }
+
+    /// Returns the prefix, the cipher suites and the extensions with the signature algorithms,
+    /// which make up the JA4 fingerprint
+    fn ja4_parts(&self) -> (String, String, String) {
+        let mut cipher_suites: Vec<u16> = self
+            .cipher_suites
+            .iter()
+            .copied()
+            .filter(|v| !is_grease(*v))
+            .collect();
+        let mut extensions: Vec<u16> = self
+            .extensions
+            .iter()
+            .copied()
+            .filter(|v| !is_grease(*v))
+            .collect();
+
+        let version = self
+            .supported_versions
+            .iter()
+            .copied()
+            .filter(|v| !is_grease(*v))
+            .max()
+            .unwrap_or(self.legacy_version);
+        let version = match version {
+            0x0304 => "13",
+            0x0303 => "12",
+            0x0302 => "11",
+            0x0301 => "10",
+            0x0300 => "s3",
+            _ => "00",
+        };
+        let server_name = if self.has_server_name { 'd' } else { 'i' };
+        let [alpn_first, alpn_last] = self.alpn_chars();
+
+        // QUIC ClientHellos are always prefixed with `q`
+        let mut prefix = String::new();
+        let _ = write!(
+            prefix,
+            "q{version}{server_name}{:02}{:02}{alpn_first}{alpn_last}",
+            cipher_suites.len().min(99),
+            extensions.len().min(99),
+        );
+
+        let mut cipher_list = String::new();
+        cipher_suites.sort_unstable();
+        write_list(&mut cipher_list, cipher_suites.iter(), ",", |out, v| {
+            write!(out, "{v:04x}")
+        });
+
+        // the server name and application protocol are already covered by the prefix
+        let mut extension_list = String::new();
+        extensions.retain(|v| *v != SERVER_NAME && *v != APPLICATION_LAYER_PROTOCOL_NEGOTIATION);
+        extensions.sort_unstable();
+        write_list(&mut extension_list, extensions.iter(), ",", |out, v| {
+            write!(out, "{v:04x}")
+        });
+
+        if !self.signature_algorithms.is_empty() {
+            extension_list.push('_');
+            write_list(
+                &mut extension_list,
+                self.signature_algorithms.iter(),
+                ",",
+                |out, v| write!(out, "{v:04x}"),
+            );
+        }
+
+        (prefix, cipher_list, extension_list)
+    }
+
+    /// Returns the first and last characters of the first offered application protocol
+    fn alpn_chars(&self) -> [char; 2] {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        let protocol = match self.application_protocol.as_deref() {
+            Some(protocol) if !protocol.is_empty() => protocol,
+            _ => return ['0', '0'],
+        };
+
+        let first = protocol[0];
+        let last = protocol[protocol.len() - 1];
+
+        if first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric() {
+            [first as char, last as char]
+        } else {
+            // use the first and last characters of the hex representation instead
+            [
+                HEX[(first >> 4) as usize] as char,
+                HEX[(last & 0xf) as usize] as char,
+            ]
+        }
+    }
+}
+
+/// Returns `true` if the value is reserved for GREASE
+///
+/// See [RFC 8701](https://www.rfc-editor.org/rfc/rfc8701#section-2).
+#[inline]
+fn is_grease(value: u16) -> bool {
+    value & 0x0f0f == 0x0a0a && value >> 8 == value & 0xff
+}
+
+fn decode_u16_list(mut buffer: DecoderBuffer) -> Result<Vec<u16>, DecoderError> {
+    let mut list = Vec::with_capacity(buffer.len() / 2);
+    while !buffer.is_empty() {
+        let (value, remaining) = buffer.decode::<u16>()?;
+        list.push(value);
+        buffer = remaining;
+    }
+    Ok(list)
+}
+
+#[cfg(feature = "fingerprint-hashes")]
+fn write_hex(out: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+}
+
+fn write_list<T, I, F>(out: &mut String, list: I, separator: &str, format: F)
+where
+    I: Iterator<Item = T>,
+    F: Fn(&mut String, T) -> core::fmt::Result,
+{
+    for (idx, value) in list.enumerate() {
+        if idx > 0 {
+            out.push_str(separator);
+        }
+        let _ = format(out, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a ClientHello body with the given extensions
+    fn client_hello(cipher_suites: &[u16], extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        fn encode_u16s(values: &[u16]) -> Vec<u8> {
+            values.iter().flat_map(|v| v.to_be_bytes()).collect()
+        }
+
+        let mut body = vec![];
+        body.extend_from_slice(&0x0303u16.to_be_bytes());
+        body.extend_from_slice(&[0u8; 32]);
+        body.push(0);
+
+        let cipher_suites = encode_u16s(cipher_suites);
+        body.extend_from_slice(&(cipher_suites.len() as u16).to_be_bytes());
+        body.extend_from_slice(&cipher_suites);
+
+        body.extend_from_slice(&[1, 0]);
+
+        let mut encoded_extensions = vec![];
+        for (extension_type, data) in extensions {
+            encoded_extensions.extend_from_slice(&extension_type.to_be_bytes());
+            encoded_extensions.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            encoded_extensions.extend_from_slice(data);
+        }
+        body.extend_from_slice(&(encoded_extensions.len() as u16).to_be_bytes());
+        body.extend_from_slice(&encoded_extensions);
+
+        body
+    }
+
+    /// Encodes a list of u16 values with a length prefix of `prefix` bytes
+    fn u16_list(prefix: usize, values: &[u16]) -> Vec<u8> {
+        let len = (values.len() as u16 * 2).to_be_bytes();
+        let mut out = len[2 - prefix..].to_vec();
+        out.extend(values.iter().flat_map(|v| v.to_be_bytes()));
+        out
+    }
+
+    fn example() -> ClientHello {
+        let bytes = client_hello(
+            &[0x2a2a, 0x1301, 0x1303, 0x1302],
+            &[
+                (0x0a0a, vec![]),
+                (SERVER_NAME, b"\x00\x0c\x00\x00\x09localhost".to_vec()),
+                (SUPPORTED_GROUPS, u16_list(2, &[0x3a3a, 0x001d, 0x0017])),
+                (EC_POINT_FORMATS, vec![1, 0]),
+                (SIGNATURE_ALGORITHMS, u16_list(2, &[0x0804, 0x0403])),
+                (
+                    APPLICATION_LAYER_PROTOCOL_NEGOTIATION,
+                    b"\x00\x03\x02h3".to_vec(),
+                ),
+                (SUPPORTED_VERSIONS, u16_list(1, &[0x4a4a, 0x0304])),
+                (0x0039, vec![]),
+            ],
+        );
+
+        ClientHello::decode(DecoderBuffer::new(&bytes)).unwrap()
+    }
+
+    #[test]
+    fn ja3_test() {
+        assert_eq!(
+            example().ja3(),
+            "771,4865-4867-4866,0-10-11-13-16-43-57,29-23,0"
+        );
+    }
+
+    #[test]
+    fn ja4_r_test() {
+        assert_eq!(
+            example().ja4_r(),
+            "q13d0307h3_1301,1302,1303_000a,000b,000d,002b,0039_0804,0403"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint-hashes")]
+    fn ja3_hash_test() {
+        assert_eq!(example().ja3_hash(), "72a7f9f045714e3a5207ece2c1b59534");
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint-hashes")]
+    fn ja4_test() {
+        assert_eq!(example().ja4(), "q13d0307h3_55b375c5d22e_c5dff6720b55");
+        assert_eq!(
+            ClientHello::default().ja4(),
+            "q00i000000_000000000000_000000000000"
+        );
+    }
+
+    #[test]
+    fn alpn_chars_test() {
+        let mut hello = ClientHello::default();
+        assert_eq!(hello.alpn_chars(), ['0', '0']);
+
+        hello.application_protocol = Some(b"http/1.1".to_vec());
+        assert_eq!(hello.alpn_chars(), ['h', '1']);
+
+        hello.application_protocol = Some(vec![0xab, 0xcd]);
+        assert_eq!(hello.alpn_chars(), ['a', 'd']);
+    }
+
+    #[test]
+    fn grease_test() {
+        for value in 0..=u16::MAX {
+            let expected = (0..16).any(|i| value == 0x0a0a + i * 0x1010);
+            assert_eq!(is_grease(value), expected, "{value:#06x}");
+        }
+    }
+
+    #[test]
+    fn truncated_test() {
+        let bytes = client_hello(&[0x1301], &[(SUPPORTED_GROUPS, u16_list(2, &[0x001d]))]);
+
+        for len in 0..bytes.len() {
+            assert!(ClientHello::decode(DecoderBuffer::new(&bytes[..len])).is_err());
+        }
+    }
+}
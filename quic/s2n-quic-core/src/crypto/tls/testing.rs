@@ -40,6 +40,8 @@ pub mod certificates {
     der!(CERT_DER, "cert.der");
     pem!(UNTRUSTED_CERT_PEM, "untrusted_cert.pem");
     pem!(UNTRUSTED_KEY_PEM, "untrusted_key.pem");
+    pem!(RSA_CERT_PEM, "rsa_cert.pem");
+    pem!(RSA_KEY_PEM, "rsa_key.pem");
 
     // Certificates and Keys used specifically for testing the mTLS (mutual TLS) feature.
     //
@@ -50,6 +52,25 @@ pub mod certificates {
     pem!(MTLS_SERVER_KEY, "mtls/server-key.pem");
     pem!(MTLS_CLIENT_CERT, "mtls/client-cert.pem");
     pem!(MTLS_CLIENT_KEY, "mtls/client-key.pem");
+
+    // Certificates for different server names used for testing certificate selection.
+    //
+    // SNI_EXACT_CERT is issued for `exact.qlaws` and SNI_WILDCARD_CERT for `*.wildcard.qlaws`.
+    pem!(SNI_EXACT_CERT, "sni/exact-cert.pem");
+    pem!(SNI_EXACT_KEY, "sni/exact-key.pem");
+    pem!(SNI_WILDCARD_CERT, "sni/wildcard-cert.pem");
+    pem!(SNI_WILDCARD_KEY, "sni/wildcard-key.pem");
+
+    // Certificates and responses used for testing OCSP stapling.
+    //
+    // OCSP_RESPONSE reports OCSP_SERVER_CERT as good and is signed by OCSP_CA_CERT.
+    // UNTRUSTED_OCSP_RESPONSE is signed by UNTRUSTED_CERT_PEM instead, which the CA never
+    // authorized to sign responses.
+    pem!(OCSP_CA_CERT, "ocsp/ca-cert.pem");
+    pem!(OCSP_SERVER_CERT, "ocsp/server-cert.pem");
+    pem!(OCSP_SERVER_KEY, "ocsp/server-key.pem");
+    der!(OCSP_RESPONSE, "ocsp/ocsp-response.der");
+    der!(UNTRUSTED_OCSP_RESPONSE, "ocsp/untrusted-ocsp-response.der");
 }
 
 #[derive(Debug)]
@@ -88,6 +109,15 @@ impl super::Session for Session {
     ) -> Poll<Result<(), transport::Error>> {
         todo!("implement dummy handshake")
     }
+
+    fn export_keying_material(
+        &self,
+        _output: &mut [u8],
+        _label: &[u8],
+        _context: &[u8],
+    ) -> Result<(), super::ExportError> {
+        todo!("implement dummy handshake")
+    }
 }
 
 impl CryptoSuite for Session {
@@ -338,6 +368,7 @@ pub struct Context<C: CryptoSuite, State: Debug> {
     pub server_name: Option<Bytes>,
     pub application_protocol: Option<Bytes>,
     pub transport_parameters: Option<Bytes>,
+    pub peer_certificate_chain: Vec<Bytes>,
     endpoint: endpoint::Type,
     pub state: State,
     waker: Waker,
@@ -354,6 +385,7 @@ impl<C: CryptoSuite, State: Debug> fmt::Debug for Context<C, State> {
             .field("sni", &self.server_name)
             .field("application_protocol", &self.application_protocol)
             .field("transport_parameters", &self.transport_parameters)
+            .field("peer_certificate_chain", &self.peer_certificate_chain.len())
             .field("endpoint", &self.endpoint)
             .finish()
     }
@@ -371,6 +403,7 @@ impl<C: CryptoSuite, State: Debug> Context<C, State> {
             server_name: None,
             application_protocol: None,
             transport_parameters: None,
+            peer_certificate_chain: Vec::new(),
             endpoint,
             state,
             waker,
@@ -622,6 +655,17 @@ impl<C: CryptoSuite, State: Debug> tls::Context<C> for Context<C, State> {
         Ok(())
     }
 
+    fn on_peer_certificate_chain(&mut self, chain: Vec<Bytes>) -> Result<(), transport::Error> {
+        assert!(
+            self.peer_certificate_chain.is_empty(),
+            "peer certificate chain emitted multiple times"
+        );
+        assert!(!chain.is_empty(), "peer certificate chain is empty");
+        self.log("peer certificate chain");
+        self.peer_certificate_chain = chain;
+        Ok(())
+    }
+
     fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
         assert!(
             !self.handshake_complete,
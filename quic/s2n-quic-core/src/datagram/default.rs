@@ -263,6 +263,10 @@ pub struct Sender {
 #[derive(Debug, PartialEq)]
 pub struct Datagram {
     pub data: Bytes,
+    /// The DSCP code point the packet carrying the datagram is marked with
+    ///
+    /// `None` indicates the datagram uses the connection's code point.
+    pub dscp: Option<u8>,
 }
 
 #[non_exhaustive]
@@ -334,6 +338,7 @@ impl Sender {
 
         let datagram = Datagram {
             data: core::mem::replace(data, bytes::Bytes::new()),
+            dscp: None,
         };
         self.queue.push_back(datagram);
         Poll::Ready(Ok(()))
@@ -368,7 +373,7 @@ impl Sender {
             oldest = self.queue.pop_front();
         }
 
-        let datagram = Datagram { data };
+        let datagram = Datagram { data, dscp: None };
         self.queue.push_back(datagram);
 
         match oldest {
@@ -385,6 +390,29 @@ impl Sender {
     /// - `Ok()` if the datagram was enqueued for sending
     /// - `Err(DatagramError)` if some error occurred
     pub fn send_datagram(&mut self, data: bytes::Bytes) -> Result<(), DatagramError> {
+        self.push_datagram(data, None)
+    }
+
+    /// Adds datagrams on the queue to be sent in packets marked with the given DSCP code point
+    ///
+    /// This allows marking datagrams with a different code point than the rest of the
+    /// connection's traffic, e.g. Expedited Forwarding (46) for real-time media. Only the lower
+    /// 6 bits of `dscp` are used.
+    ///
+    /// If the queue is full the newest datagram is not added and an error is returned.
+    ///
+    /// # Return value
+    /// - `Ok()` if the datagram was enqueued for sending
+    /// - `Err(DatagramError)` if some error occurred
+    pub fn send_datagram_with_dscp(
+        &mut self,
+        data: bytes::Bytes,
+        dscp: u8,
+    ) -> Result<(), DatagramError> {
+        self.push_datagram(data, Some(dscp & 0b11_1111))
+    }
+
+    fn push_datagram(&mut self, data: bytes::Bytes, dscp: Option<u8>) -> Result<(), DatagramError> {
         if data.len() as u64 > self.max_datagram_payload {
             return Err(DatagramError::ExceedsPeerTransportLimits);
         }
@@ -399,7 +427,7 @@ impl Sender {
             return Err(DatagramError::QueueAtCapacity);
         }
 
-        let datagram = Datagram { data };
+        let datagram = Datagram { data, dscp };
         self.queue.push_back(datagram);
         Ok(())
     }
@@ -460,6 +488,14 @@ impl super::Sender for Sender {
         self.record_capacity_stats(packet.remaining_capacity());
         let mut has_written = false;
         while packet.remaining_capacity() > 0 {
+            // Datagrams with a different code point are sent in a later packet
+            let connection_dscp = packet.connection_dscp();
+            if self.queue.front().map_or(false, |datagram| {
+                datagram.dscp.unwrap_or(connection_dscp) != packet.dscp()
+            }) {
+                return;
+            }
+
             if let Some(datagram) = self.queue.pop_front() {
                 // Ensure there is enough space in the packet to send a datagram
                 if packet.remaining_capacity() >= datagram.data.len() {
@@ -501,6 +537,13 @@ impl super::Sender for Sender {
             w.wake();
         }
     }
+
+    #[inline]
+    fn transmission_dscp(&self, default: u8) -> Option<u8> {
+        self.queue
+            .front()
+            .map(|datagram| datagram.dscp.unwrap_or(default))
+    }
 }
 
 /// A builder for the default datagram sender
@@ -678,6 +721,8 @@ mod tests {
             remaining_capacity: 10,
             has_pending_streams: false,
             datagrams_prioritized: false,
+            dscp: 0,
+            connection_dscp: 0,
         };
         crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
 
@@ -771,6 +816,8 @@ mod tests {
             remaining_capacity: 5,
             has_pending_streams: false,
             datagrams_prioritized: false,
+            dscp: 0,
+            connection_dscp: 0,
         };
         crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
 
@@ -780,6 +827,55 @@ mod tests {
         assert!(!default_sender.queue.is_empty());
     }
 
+    #[test]
+    // Check that datagrams are only written to packets marked with the same code point
+    fn dscp_test() {
+        use crate::datagram::Sender as _;
+
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let datagram_0 = bytes::Bytes::from_static(&[1, 2, 3]);
+        let datagram_1 = bytes::Bytes::from_static(&[4, 5, 6]);
+        assert_eq!(
+            default_sender.send_datagram_with_dscp(datagram_0, 46),
+            Ok(())
+        );
+        assert_eq!(default_sender.send_datagram(datagram_1), Ok(()));
+        assert_eq!(default_sender.transmission_dscp(0), Some(46));
+
+        // The unmarked packet can't carry the marked datagram
+        let mut packet = MockPacket {
+            remaining_capacity: 100,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+            dscp: 0,
+            connection_dscp: 0,
+        };
+        default_sender.on_transmit(&mut packet);
+        assert_eq!(packet.remaining_capacity, 100);
+
+        // The marked packet stops at the unmarked datagram
+        packet.dscp = 46;
+        default_sender.on_transmit(&mut packet);
+        assert_eq!(packet.remaining_capacity, 97);
+        assert_eq!(default_sender.transmission_dscp(0), Some(0));
+
+        // The unmarked datagram uses the connection's code point
+        assert_eq!(default_sender.transmission_dscp(10), Some(10));
+        packet.dscp = 0;
+        packet.connection_dscp = 10;
+        default_sender.on_transmit(&mut packet);
+        assert_eq!(packet.remaining_capacity, 97);
+
+        packet.dscp = 10;
+        default_sender.on_transmit(&mut packet);
+        assert_eq!(packet.remaining_capacity, 94);
+        assert_eq!(default_sender.transmission_dscp(10), None);
+    }
+
     fn fake_receive_context() -> crate::datagram::ReceiveContext<'static> {
         crate::datagram::ReceiveContext {
             path: crate::event::api::Path {
@@ -906,6 +1002,8 @@ mod tests {
         has_pending_streams: bool,
         datagrams_prioritized: bool,
         remaining_capacity: usize,
+        dscp: u8,
+        connection_dscp: u8,
     }
 
     impl crate::datagram::Packet for MockPacket {
@@ -928,5 +1026,13 @@ mod tests {
         fn datagrams_prioritized(&self) -> bool {
             self.datagrams_prioritized
         }
+
+        fn dscp(&self) -> u8 {
+            self.dscp
+        }
+
+        fn connection_dscp(&self) -> u8 {
+            self.connection_dscp
+        }
     }
 }
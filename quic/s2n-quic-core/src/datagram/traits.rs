@@ -95,6 +95,16 @@ pub trait Sender: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// Returns the DSCP code point of the next datagram ready to send
+    ///
+    /// The connection uses the value to mark the packets which carry the datagram. Datagrams
+    /// which don't set their own code point should use the connection's `default`. `None`
+    /// indicates the datagrams can be sent in packets with any code point.
+    #[inline]
+    fn transmission_dscp(&self, _default: u8) -> Option<u8> {
+        None
+    }
 }
 
 /// A packet will be available during the on_transmit callback. Use the methods
@@ -117,6 +127,24 @@ pub trait Packet {
     /// Datagrams get prioritized every other packet, which gives the application the best
     /// chance to send a large datagram.
     fn datagrams_prioritized(&self) -> bool;
+
+    /// Returns the DSCP code point the packet is marked with
+    ///
+    /// Datagrams which should be marked with a different code point should be held back
+    /// for a later packet.
+    #[inline]
+    fn dscp(&self) -> u8 {
+        0
+    }
+
+    /// Returns the connection's DSCP code point
+    ///
+    /// Datagrams which don't set their own code point should only be written to packets marked
+    /// with it.
+    #[inline]
+    fn connection_dscp(&self) -> u8 {
+        0
+    }
 }
 
 #[non_exhaustive]
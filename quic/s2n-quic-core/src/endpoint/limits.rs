@@ -71,6 +71,10 @@ pub struct ConnectionAttempt<'a> {
     /// Number of open connections
     pub connection_count: usize,
 
+    /// Number of connections which completed the handshake but haven't been accepted by the
+    /// application yet
+    pub pending_accepts: usize,
+
     /// The unverified address of the connecting peer
     /// This address comes from the datagram
     pub remote_address: SocketAddress<'a>,
@@ -88,10 +92,17 @@ impl<'a> ConnectionAttempt<'a> {
         Self {
             inflight_handshakes,
             connection_count,
+            pending_accepts: 0,
             remote_address: remote_address.into_event(),
             timestamp,
         }
     }
+
+    #[doc(hidden)]
+    pub fn with_pending_accepts(mut self, pending_accepts: usize) -> Self {
+        self.pending_accepts = pending_accepts;
+        self
+    }
 }
 
 pub trait Limiter: 'static + Send {
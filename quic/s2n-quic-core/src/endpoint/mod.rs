@@ -3,7 +3,7 @@
 
 use crate::{
     io::{rx, tx},
-    path::{self, MaxMtu},
+    path::{self, MaxMtu, MinMtu},
     time::{Clock, Timestamp},
 };
 use core::{
@@ -132,6 +132,14 @@ pub trait Endpoint: 'static + Send + Sized {
     /// Sets the largest maximum transmission unit (MTU) that can be sent on a path
     fn set_max_mtu(&mut self, max_mtu: MaxMtu);
 
+    /// Sets the smallest maximum transmission unit (MTU) that path MTU discovery will probe from
+    ///
+    /// By default, paths start with the [`MinMtu::MIN`].
+    #[inline]
+    fn set_min_mtu(&mut self, min_mtu: MinMtu) {
+        let _ = min_mtu;
+    }
+
     /// Returns the endpoint's event subscriber
     fn subscriber(&mut self) -> &mut Self::Subscriber;
 }
@@ -82,6 +82,20 @@ impl<T> IntoEvent<RangeInclusive<T>> for RangeInclusive<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'a> api::TlsClientHello<'a> {
+    /// Decodes the fields of the ClientHello which are used to compute the JA3 and JA4
+    /// fingerprints
+    pub fn fingerprint(
+        &self,
+    ) -> Result<crate::crypto::tls::fingerprint::ClientHello, s2n_codec::DecoderError> {
+        let payload = self.payload.concat();
+        crate::crypto::tls::fingerprint::ClientHello::decode(s2n_codec::DecoderBuffer::new(
+            &payload,
+        ))
+    }
+}
+
 #[derive(Clone, Debug, Copy)]
 pub struct Timestamp(crate::time::Timestamp);
 
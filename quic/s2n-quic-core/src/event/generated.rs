@@ -125,10 +125,11 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " The received packet number was outside the range of tracked packet numbers."]
         #[doc = ""]
-        #[doc = " This can happen when packets are heavily delayed or reordered. Currently, the maximum"]
+        #[doc = " This can happen when packets are heavily delayed or reordered. By default, the maximum"]
         #[doc = " amount of reordering is limited to 128 packets. For example, if packet number `142`"]
         #[doc = " is received, the allowed range would be limited to `14-142`. If an endpoint received"]
-        #[doc = " packet `< 14`, it would trigger this event."]
+        #[doc = " packet `< 14`, it would trigger this event. The limit can be reduced with"]
+        #[doc = " `connection::Limits::with_replay_window`."]
         TooOld {},
     }
     #[derive(Clone, Debug)]
@@ -302,6 +303,9 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds {},
+        #[non_exhaustive]
+        #[doc = " The amount of received data waiting to be processed exceeded the configured limit."]
+        ReceiveBufferLimitExceeded {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -426,13 +430,36 @@ pub mod api {
         Unknown {},
         #[non_exhaustive]
         #[doc = " ECN capability testing has failed validation"]
-        Failed {},
+        Failed { reason: EcnFailureReason },
         #[non_exhaustive]
         #[doc = " ECN capability has been confirmed"]
         Capable {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The reason ECN validation failed for the path"]
+    pub enum EcnFailureReason {
+        #[non_exhaustive]
+        #[doc = " ECN marked packets were acknowledged without ECN counts in the ACK frame"]
+        CountsMissing {},
+        #[non_exhaustive]
+        #[doc = " The ECN counts in the ACK frame decreased"]
+        CountsDecreased {},
+        #[non_exhaustive]
+        #[doc = " Fewer ECT(0) and ECN-CE markings were reported than ECT(0) marked packets were acknowledged"]
+        Ect0Underreported {},
+        #[non_exhaustive]
+        #[doc = " More ECT(0) or ECT(1) markings were reported than packets were sent with them"]
+        EctOverreported {},
+        #[non_exhaustive]
+        #[doc = " A packet sent with an ECN-CE marking was not reported as CE marked"]
+        CeSuppressed {},
+        #[non_exhaustive]
+        #[doc = " ECN marked packets were repeatedly lost, indicating the path drops them"]
+        BlackHole {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Events tracking the progress of handshake status"]
     pub enum HandshakeStatus {
         #[non_exhaustive]
@@ -645,6 +672,8 @@ pub mod api {
         pub congestion_window: u32,
         pub bytes_in_flight: u32,
         pub congestion_limited: bool,
+        #[doc = " Whether `latest_rtt` was updated with a new RTT sample from the ACK which triggered the metrics"]
+        pub rtt_sampled: bool,
     }
     impl<'a> Event for RecoveryMetrics<'a> {
         const NAME: &'static str = "recovery:metrics_updated";
@@ -802,6 +831,8 @@ pub mod api {
     #[doc = " Datagram received by a connection"]
     pub struct DatagramReceived {
         pub len: u16,
+        #[doc = " The IPv4 TTL or IPv6 hop limit of the datagram, if reported by the platform"]
+        pub ttl: Option<u8>,
     }
     impl Event for DatagramReceived {
         const NAME: &'static str = "transport:datagram_received";
@@ -963,6 +994,16 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The amount of stream data buffered by the connection is approaching the configured maximum"]
+    pub struct ConnectionMemoryPressure {
+        pub buffered_bytes: usize,
+        pub max_buffered_bytes: usize,
+    }
+    impl Event for ConnectionMemoryPressure {
+        const NAME: &'static str = "transport:connection_memory_pressure";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -1075,6 +1116,17 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " Emitted when the platform reports datagrams which were dropped by the socket before the"]
+    #[doc = " endpoint could receive them, e.g. because the socket's receive buffer was full"]
+    pub struct PlatformRxSocketDrops {
+        #[doc = " The number of datagrams dropped since the previous report"]
+        pub count: usize,
+    }
+    impl Event for PlatformRxSocketDrops {
+        const NAME: &'static str = "platform:rx_socket_drops";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Emitted when a platform feature is configured"]
     pub struct PlatformFeatureConfigured {
         pub configuration: PlatformFeatureConfiguration,
@@ -1124,6 +1176,16 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " Emitted when the maximum transmission unit is configured"]
         MaxMtu { mtu: u16 },
+        #[non_exhaustive]
+        #[doc = " Emitted when the size of the socket's receive buffer is configured"]
+        #[doc = ""]
+        #[doc = " The size is the value reported by the platform, which may differ from the requested size."]
+        RecvBufferSize { size: usize },
+        #[non_exhaustive]
+        #[doc = " Emitted when the size of the socket's send buffer is configured"]
+        #[doc = ""]
+        #[doc = " The size is the value reported by the platform, which may differ from the requested size."]
+        SendBufferSize { size: usize },
     }
     impl<'a> IntoEvent<builder::PreferredAddress<'a>>
         for &'a crate::transport::parameters::PreferredAddress
@@ -1605,7 +1667,7 @@ pub mod tracing {
                 api::EndpointType::Client {} => self.client.id(),
                 api::EndpointType::Server {} => self.server.id(),
             };
-            tracing :: span ! (target : "s2n_quic" , parent : parent , tracing :: Level :: DEBUG , "conn" , id = meta . id)
+            tracing :: span ! (target : "s2n_quic" , parent : parent , tracing :: Level :: DEBUG , "conn" , id = meta . id , cid = tracing :: field :: Empty)
         }
         #[inline]
         fn on_application_protocol_information(
@@ -1743,8 +1805,9 @@ pub mod tracing {
                 congestion_window,
                 bytes_in_flight,
                 congestion_limited,
+                rtt_sampled,
             } = event;
-            tracing :: event ! (target : "recovery_metrics" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , min_rtt = tracing :: field :: debug (min_rtt) , smoothed_rtt = tracing :: field :: debug (smoothed_rtt) , latest_rtt = tracing :: field :: debug (latest_rtt) , rtt_variance = tracing :: field :: debug (rtt_variance) , max_ack_delay = tracing :: field :: debug (max_ack_delay) , pto_count = tracing :: field :: debug (pto_count) , congestion_window = tracing :: field :: debug (congestion_window) , bytes_in_flight = tracing :: field :: debug (bytes_in_flight) , congestion_limited = tracing :: field :: debug (congestion_limited));
+            tracing :: event ! (target : "recovery_metrics" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , min_rtt = tracing :: field :: debug (min_rtt) , smoothed_rtt = tracing :: field :: debug (smoothed_rtt) , latest_rtt = tracing :: field :: debug (latest_rtt) , rtt_variance = tracing :: field :: debug (rtt_variance) , max_ack_delay = tracing :: field :: debug (max_ack_delay) , pto_count = tracing :: field :: debug (pto_count) , congestion_window = tracing :: field :: debug (congestion_window) , bytes_in_flight = tracing :: field :: debug (bytes_in_flight) , congestion_limited = tracing :: field :: debug (congestion_limited) , rtt_sampled = tracing :: field :: debug (rtt_sampled));
         }
         #[inline]
         fn on_congestion(
@@ -1860,6 +1923,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::ConnectionStarted { path } = event;
+            context.record("cid", tracing::field::debug(&path.local_cid));
             tracing :: event ! (target : "connection_started" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path));
         }
         #[inline]
@@ -1920,8 +1984,8 @@ pub mod tracing {
             event: &api::DatagramReceived,
         ) {
             let id = context.id();
-            let api::DatagramReceived { len } = event;
-            tracing :: event ! (target : "datagram_received" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len));
+            let api::DatagramReceived { len, ttl } = event;
+            tracing :: event ! (target : "datagram_received" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , ttl = tracing :: field :: debug (ttl));
         }
         #[inline]
         fn on_datagram_dropped(
@@ -2125,6 +2189,20 @@ pub mod tracing {
             tracing :: event ! (target : "bbr_state_changed" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , state = tracing :: field :: debug (state));
         }
         #[inline]
+        fn on_connection_memory_pressure(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ConnectionMemoryPressure,
+        ) {
+            let id = context.id();
+            let api::ConnectionMemoryPressure {
+                buffered_bytes,
+                max_buffered_bytes,
+            } = event;
+            tracing :: event ! (target : "connection_memory_pressure" , parent : id , tracing :: Level :: DEBUG , buffered_bytes = tracing :: field :: debug (buffered_bytes) , max_buffered_bytes = tracing :: field :: debug (max_buffered_bytes));
+        }
+        #[inline]
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -2256,6 +2334,19 @@ pub mod tracing {
             tracing :: event ! (target : "platform_rx_error" , parent : parent , tracing :: Level :: DEBUG , errno = tracing :: field :: debug (errno));
         }
         #[inline]
+        fn on_platform_rx_socket_drops(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformRxSocketDrops,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::PlatformRxSocketDrops { count } = event;
+            tracing :: event ! (target : "platform_rx_socket_drops" , parent : parent , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
+        }
+        #[inline]
         fn on_platform_feature_configured(
             &mut self,
             meta: &api::EndpointMeta,
@@ -2592,10 +2683,11 @@ pub mod builder {
         Duplicate,
         #[doc = " The received packet number was outside the range of tracked packet numbers."]
         #[doc = ""]
-        #[doc = " This can happen when packets are heavily delayed or reordered. Currently, the maximum"]
+        #[doc = " This can happen when packets are heavily delayed or reordered. By default, the maximum"]
         #[doc = " amount of reordering is limited to 128 packets. For example, if packet number `142`"]
         #[doc = " is received, the allowed range would be limited to `14-142`. If an endpoint received"]
-        #[doc = " packet `< 14`, it would trigger this event."]
+        #[doc = " packet `< 14`, it would trigger this event. The limit can be reduced with"]
+        #[doc = " `connection::Limits::with_replay_window`."]
         TooOld,
     }
     impl IntoEvent<api::DuplicatePacketError> for DuplicatePacketError {
@@ -2904,6 +2996,8 @@ pub mod builder {
         PathLimitExceeded,
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds,
+        #[doc = " The amount of received data waiting to be processed exceeded the configured limit."]
+        ReceiveBufferLimitExceeded,
     }
     impl IntoEvent<api::DatagramDropReason> for DatagramDropReason {
         #[inline]
@@ -2922,6 +3016,7 @@ pub mod builder {
                 Self::RejectedConnectionMigration => RejectedConnectionMigration {},
                 Self::PathLimitExceeded => PathLimitExceeded {},
                 Self::InsufficientConnectionIds => InsufficientConnectionIds {},
+                Self::ReceiveBufferLimitExceeded => ReceiveBufferLimitExceeded {},
             }
         }
     }
@@ -3115,7 +3210,7 @@ pub mod builder {
         #[doc = " ECN capability has been tested, but not validated yet"]
         Unknown,
         #[doc = " ECN capability testing has failed validation"]
-        Failed,
+        Failed { reason: EcnFailureReason },
         #[doc = " ECN capability has been confirmed"]
         Capable,
     }
@@ -3126,12 +3221,44 @@ pub mod builder {
             match self {
                 Self::Testing => Testing {},
                 Self::Unknown => Unknown {},
-                Self::Failed => Failed {},
+                Self::Failed { reason } => Failed {
+                    reason: reason.into_event(),
+                },
                 Self::Capable => Capable {},
             }
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The reason ECN validation failed for the path"]
+    pub enum EcnFailureReason {
+        #[doc = " ECN marked packets were acknowledged without ECN counts in the ACK frame"]
+        CountsMissing,
+        #[doc = " The ECN counts in the ACK frame decreased"]
+        CountsDecreased,
+        #[doc = " Fewer ECT(0) and ECN-CE markings were reported than ECT(0) marked packets were acknowledged"]
+        Ect0Underreported,
+        #[doc = " More ECT(0) or ECT(1) markings were reported than packets were sent with them"]
+        EctOverreported,
+        #[doc = " A packet sent with an ECN-CE marking was not reported as CE marked"]
+        CeSuppressed,
+        #[doc = " ECN marked packets were repeatedly lost, indicating the path drops them"]
+        BlackHole,
+    }
+    impl IntoEvent<api::EcnFailureReason> for EcnFailureReason {
+        #[inline]
+        fn into_event(self) -> api::EcnFailureReason {
+            use api::EcnFailureReason::*;
+            match self {
+                Self::CountsMissing => CountsMissing {},
+                Self::CountsDecreased => CountsDecreased {},
+                Self::Ect0Underreported => Ect0Underreported {},
+                Self::EctOverreported => EctOverreported {},
+                Self::CeSuppressed => CeSuppressed {},
+                Self::BlackHole => BlackHole {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Events tracking the progress of handshake status"]
     pub enum HandshakeStatus {
         #[doc = " The handshake has completed."]
@@ -3466,6 +3593,8 @@ pub mod builder {
         pub congestion_window: u32,
         pub bytes_in_flight: u32,
         pub congestion_limited: bool,
+        #[doc = " Whether `latest_rtt` was updated with a new RTT sample from the ACK which triggered the metrics"]
+        pub rtt_sampled: bool,
     }
     impl<'a> IntoEvent<api::RecoveryMetrics<'a>> for RecoveryMetrics<'a> {
         #[inline]
@@ -3481,6 +3610,7 @@ pub mod builder {
                 congestion_window,
                 bytes_in_flight,
                 congestion_limited,
+                rtt_sampled,
             } = self;
             api::RecoveryMetrics {
                 path: path.into_event(),
@@ -3493,6 +3623,7 @@ pub mod builder {
                 congestion_window: congestion_window.into_event(),
                 bytes_in_flight: bytes_in_flight.into_event(),
                 congestion_limited: congestion_limited.into_event(),
+                rtt_sampled: rtt_sampled.into_event(),
             }
         }
     }
@@ -3746,13 +3877,16 @@ pub mod builder {
     #[doc = " Datagram received by a connection"]
     pub struct DatagramReceived {
         pub len: u16,
+        #[doc = " The IPv4 TTL or IPv6 hop limit of the datagram, if reported by the platform"]
+        pub ttl: Option<u8>,
     }
     impl IntoEvent<api::DatagramReceived> for DatagramReceived {
         #[inline]
         fn into_event(self) -> api::DatagramReceived {
-            let DatagramReceived { len } = self;
+            let DatagramReceived { len, ttl } = self;
             api::DatagramReceived {
                 len: len.into_event(),
+                ttl: ttl.into_event(),
             }
         }
     }
@@ -4033,6 +4167,25 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The amount of stream data buffered by the connection is approaching the configured maximum"]
+    pub struct ConnectionMemoryPressure {
+        pub buffered_bytes: usize,
+        pub max_buffered_bytes: usize,
+    }
+    impl IntoEvent<api::ConnectionMemoryPressure> for ConnectionMemoryPressure {
+        #[inline]
+        fn into_event(self) -> api::ConnectionMemoryPressure {
+            let ConnectionMemoryPressure {
+                buffered_bytes,
+                max_buffered_bytes,
+            } = self;
+            api::ConnectionMemoryPressure {
+                buffered_bytes: buffered_bytes.into_event(),
+                max_buffered_bytes: max_buffered_bytes.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -4208,6 +4361,22 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " Emitted when the platform reports datagrams which were dropped by the socket before the"]
+    #[doc = " endpoint could receive them, e.g. because the socket's receive buffer was full"]
+    pub struct PlatformRxSocketDrops {
+        #[doc = " The number of datagrams dropped since the previous report"]
+        pub count: usize,
+    }
+    impl IntoEvent<api::PlatformRxSocketDrops> for PlatformRxSocketDrops {
+        #[inline]
+        fn into_event(self) -> api::PlatformRxSocketDrops {
+            let PlatformRxSocketDrops { count } = self;
+            api::PlatformRxSocketDrops {
+                count: count.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Emitted when a platform feature is configured"]
     pub struct PlatformFeatureConfigured {
         pub configuration: PlatformFeatureConfiguration,
@@ -4280,6 +4449,14 @@ pub mod builder {
         Ecn { enabled: bool },
         #[doc = " Emitted when the maximum transmission unit is configured"]
         MaxMtu { mtu: u16 },
+        #[doc = " Emitted when the size of the socket's receive buffer is configured"]
+        #[doc = ""]
+        #[doc = " The size is the value reported by the platform, which may differ from the requested size."]
+        RecvBufferSize { size: usize },
+        #[doc = " Emitted when the size of the socket's send buffer is configured"]
+        #[doc = ""]
+        #[doc = " The size is the value reported by the platform, which may differ from the requested size."]
+        SendBufferSize { size: usize },
     }
     impl IntoEvent<api::PlatformFeatureConfiguration> for PlatformFeatureConfiguration {
         #[inline]
@@ -4298,6 +4475,12 @@ pub mod builder {
                 Self::MaxMtu { mtu } => MaxMtu {
                     mtu: mtu.into_event(),
                 },
+                Self::RecvBufferSize { size } => RecvBufferSize {
+                    size: size.into_event(),
+                },
+                Self::SendBufferSize { size } => SendBufferSize {
+                    size: size.into_event(),
+                },
             }
         }
     }
@@ -4478,6 +4661,12 @@ mod traits {
         ) -> supervisor::Outcome {
             supervisor::Outcome::default()
         }
+        #[doc = r" Sets the clock which calendar times, such as the reference time of a trace, are read from"]
+        #[doc = r""]
+        #[doc = r" This is called with the endpoint's wall clock before any connections are created."]
+        #[cfg(feature = "std")]
+        #[allow(unused_variables)]
+        fn set_wall_clock(&mut self, clock: &std::sync::Arc<dyn crate::time::WallClock>) {}
         #[doc = "Called when the `ApplicationProtocolInformation` event is triggered"]
         #[inline]
         fn on_application_protocol_information(
@@ -4960,6 +5149,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `ConnectionMemoryPressure` event is triggered"]
+        #[inline]
+        fn on_connection_memory_pressure(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionMemoryPressure,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `VersionInformation` event is triggered"]
         #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
@@ -5042,6 +5243,16 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PlatformRxSocketDrops` event is triggered"]
+        #[inline]
+        fn on_platform_rx_socket_drops(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &PlatformRxSocketDrops,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `PlatformFeatureConfigured` event is triggered"]
         #[inline]
         fn on_platform_feature_configured(
@@ -5126,6 +5337,12 @@ mod traits {
                 self.1.create_connection_context(meta, info),
             )
         }
+        #[cfg(feature = "std")]
+        #[inline]
+        fn set_wall_clock(&mut self, clock: &std::sync::Arc<dyn crate::time::WallClock>) {
+            self.0.set_wall_clock(clock);
+            self.1.set_wall_clock(clock);
+        }
         #[inline]
         fn supervisor_timeout(
             &mut self,
@@ -5572,6 +5789,16 @@ mod traits {
             (self.1).on_bbr_state_changed(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_connection_memory_pressure(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionMemoryPressure,
+        ) {
+            (self.0).on_connection_memory_pressure(&mut context.0, meta, event);
+            (self.1).on_connection_memory_pressure(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
             (self.0).on_version_information(meta, event);
             (self.1).on_version_information(meta, event);
@@ -5643,6 +5870,15 @@ mod traits {
             (self.1).on_platform_rx_error(meta, event);
         }
         #[inline]
+        fn on_platform_rx_socket_drops(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &PlatformRxSocketDrops,
+        ) {
+            (self.0).on_platform_rx_socket_drops(meta, event);
+            (self.1).on_platform_rx_socket_drops(meta, event);
+        }
+        #[inline]
         fn on_platform_feature_configured(
             &mut self,
             meta: &EndpointMeta,
@@ -5731,6 +5967,8 @@ mod traits {
         fn on_platform_rx(&mut self, event: builder::PlatformRx);
         #[doc = "Publishes a `PlatformRxError` event to the publisher's subscriber"]
         fn on_platform_rx_error(&mut self, event: builder::PlatformRxError);
+        #[doc = "Publishes a `PlatformRxSocketDrops` event to the publisher's subscriber"]
+        fn on_platform_rx_socket_drops(&mut self, event: builder::PlatformRxSocketDrops);
         #[doc = "Publishes a `PlatformFeatureConfigured` event to the publisher's subscriber"]
         fn on_platform_feature_configured(&mut self, event: builder::PlatformFeatureConfigured);
         #[doc = "Publishes a `PlatformEventLoopWakeup` event to the publisher's subscriber"]
@@ -5843,6 +6081,13 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_platform_rx_socket_drops(&mut self, event: builder::PlatformRxSocketDrops) {
+            let event = event.into_event();
+            self.subscriber
+                .on_platform_rx_socket_drops(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_platform_feature_configured(&mut self, event: builder::PlatformFeatureConfigured) {
             let event = event.into_event();
             self.subscriber
@@ -5952,6 +6197,8 @@ mod traits {
         fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated);
         #[doc = "Publishes a `BbrStateChanged` event to the publisher's subscriber"]
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged);
+        #[doc = "Publishes a `ConnectionMemoryPressure` event to the publisher's subscriber"]
+        fn on_connection_memory_pressure(&mut self, event: builder::ConnectionMemoryPressure);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
         fn quic_version(&self) -> u32;
         #[doc = r" Returns the [`Subject`] for the current publisher"]
@@ -6356,6 +6603,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_connection_memory_pressure(&mut self, event: builder::ConnectionMemoryPressure) {
+            let event = event.into_event();
+            self.subscriber
+                .on_connection_memory_pressure(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> u32 {
             self.quic_version
         }
@@ -6412,6 +6668,7 @@ pub mod testing {
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub connection_memory_pressure: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -6423,6 +6680,7 @@ pub mod testing {
         pub platform_tx_error: u32,
         pub platform_rx: u32,
         pub platform_rx_error: u32,
+        pub platform_rx_socket_drops: u32,
         pub platform_feature_configured: u32,
         pub platform_event_loop_wakeup: u32,
         pub platform_event_loop_sleep: u32,
@@ -6490,6 +6748,7 @@ pub mod testing {
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                connection_memory_pressure: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -6501,6 +6760,7 @@ pub mod testing {
                 platform_tx_error: 0,
                 platform_rx: 0,
                 platform_rx_error: 0,
+                platform_rx_socket_drops: 0,
                 platform_feature_configured: 0,
                 platform_event_loop_wakeup: 0,
                 platform_event_loop_sleep: 0,
@@ -6956,6 +7216,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_connection_memory_pressure(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionMemoryPressure,
+        ) {
+            self.connection_memory_pressure += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -7028,6 +7299,14 @@ pub mod testing {
             self.platform_rx_error += 1;
             self.output.push(format!("{meta:?} {event:?}"));
         }
+        fn on_platform_rx_socket_drops(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformRxSocketDrops,
+        ) {
+            self.platform_rx_socket_drops += 1;
+            self.output.push(format!("{meta:?} {event:?}"));
+        }
         fn on_platform_feature_configured(
             &mut self,
             meta: &api::EndpointMeta,
@@ -7097,6 +7376,7 @@ pub mod testing {
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub connection_memory_pressure: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -7108,6 +7388,7 @@ pub mod testing {
         pub platform_tx_error: u32,
         pub platform_rx: u32,
         pub platform_rx_error: u32,
+        pub platform_rx_socket_drops: u32,
         pub platform_feature_configured: u32,
         pub platform_event_loop_wakeup: u32,
         pub platform_event_loop_sleep: u32,
@@ -7165,6 +7446,7 @@ pub mod testing {
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                connection_memory_pressure: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -7176,6 +7458,7 @@ pub mod testing {
                 platform_tx_error: 0,
                 platform_rx: 0,
                 platform_rx_error: 0,
+                platform_rx_socket_drops: 0,
                 platform_feature_configured: 0,
                 platform_event_loop_wakeup: 0,
                 platform_event_loop_sleep: 0,
@@ -7241,6 +7524,11 @@ pub mod testing {
             let event = event.into_event();
             self.output.push(format!("{event:?}"));
         }
+        fn on_platform_rx_socket_drops(&mut self, event: builder::PlatformRxSocketDrops) {
+            self.platform_rx_socket_drops += 1;
+            let event = event.into_event();
+            self.output.push(format!("{event:?}"));
+        }
         fn on_platform_feature_configured(&mut self, event: builder::PlatformFeatureConfigured) {
             self.platform_feature_configured += 1;
             let event = event.into_event();
@@ -7548,6 +7836,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_connection_memory_pressure(&mut self, event: builder::ConnectionMemoryPressure) {
+            self.connection_memory_pressure += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn quic_version(&self) -> u32 {
             1
         }
@@ -4,7 +4,7 @@
 use crate::{frame::Tag, inet::ExplicitCongestionNotification, number::CheckedSub, varint::VarInt};
 use core::{
     convert::TryInto,
-    ops::{RangeInclusive, SubAssign},
+    ops::{AddAssign, RangeInclusive, SubAssign},
 };
 use s2n_codec::{
     decoder_parameterized_value, decoder_value, DecoderBuffer, DecoderError, Encoder, EncoderValue,
@@ -487,6 +487,14 @@ impl EcnCounts {
     }
 }
 
+impl AddAssign for EcnCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.ect_0_count = self.ect_0_count.saturating_add(rhs.ect_0_count);
+        self.ect_1_count = self.ect_1_count.saturating_add(rhs.ect_1_count);
+        self.ce_count = self.ce_count.saturating_add(rhs.ce_count);
+    }
+}
+
 impl SubAssign for EcnCounts {
     fn sub_assign(&mut self, rhs: Self) {
         self.ect_0_count = self.ect_0_count.saturating_sub(rhs.ect_0_count);
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    connection, inet::ExplicitCongestionNotification, path::LocalAddress, time::Timestamp,
+    connection,
+    inet::ExplicitCongestionNotification,
+    path::LocalAddress,
+    time::{Duration, Timestamp},
 };
 
 /// Header information for a datagram sent/received over the network
@@ -10,6 +13,10 @@ use crate::{
 pub struct Header<Path> {
     pub path: Path,
     pub ecn: ExplicitCongestionNotification,
+    /// The IPv4 TTL or IPv6 hop limit of the datagram, if reported by the platform
+    pub ttl: Option<u8>,
+    /// The time between the platform receiving the datagram and it being read, if reported
+    pub receive_delay: Option<Duration>,
 }
 
 /// Metadata for a datagram sent/received over the network
@@ -20,12 +27,31 @@ pub struct DatagramInfo {
     pub ecn: ExplicitCongestionNotification,
     pub destination_connection_id: connection::LocalId,
     pub source_connection_id: Option<connection::PeerId>,
+    /// The IPv4 TTL or IPv6 hop limit of the datagram, if reported by the platform
+    pub ttl: Option<u8>,
+    /// The time between the platform receiving the datagram and it being read, if reported
+    pub receive_delay: Option<Duration>,
+}
+
+impl DatagramInfo {
+    /// Returns the time the platform received the datagram
+    ///
+    /// This is earlier than `timestamp` if the datagram waited in the socket buffer before being
+    /// read.
+    #[inline]
+    pub fn receive_time(&self) -> Timestamp {
+        self.receive_delay
+            .and_then(|delay| self.timestamp.checked_sub(delay))
+            .unwrap_or(self.timestamp)
+    }
 }
 
 /// Additional metadata for a datagram sent/received over the network
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct AncillaryData {
     pub ecn: ExplicitCongestionNotification,
+    /// The differentiated services code point the datagram is marked with
+    pub dscp: u8,
     pub local_address: LocalAddress,
     /// The network interface the datagram is sent/received on
     ///
@@ -34,4 +60,14 @@ pub struct AncillaryData {
     pub local_interface: Option<u32>,
     /// Set when the packet buffer is an aggregate of multiple received packets
     pub segment_size: u16,
+    /// The IPv4 TTL or IPv6 hop limit of the received datagram
+    ///
+    /// This is only set when the platform supports reporting it and the socket has been
+    /// configured to return it.
+    pub ttl: Option<u8>,
+    /// The time between the kernel receiving the datagram and it being read
+    ///
+    /// This is only set when the platform supports receive timestamps and the socket has been
+    /// configured to return them.
+    pub receive_delay: Option<Duration>,
 }
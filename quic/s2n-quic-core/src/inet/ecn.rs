@@ -97,6 +97,22 @@ impl ExplicitCongestionNotification {
     }
 }
 
+/// Controls whether packets are marked as ECN-capable
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EcnMode {
+    /// Packets are marked with ECT(0) and the ECN counts reported by the peer are used to
+    /// detect congestion once the path has been validated
+    ///
+    /// Paths which fail validation, e.g. because a middlebox clears the ECN markings, stop
+    /// marking packets.
+    #[default]
+    Enabled,
+
+    /// Packets are never marked as ECN-capable
+    Disabled,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
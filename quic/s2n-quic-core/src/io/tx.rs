@@ -62,6 +62,16 @@ pub trait Queue {
     /// Returns the number of remaining datagrams that can be transmitted
     fn capacity(&self) -> usize;
 
+    /// Returns how long the queue can hold a message before it is transmitted
+    ///
+    /// Queues which hold each message until its [`Message::delay`] has passed, e.g. with
+    /// `SO_TXTIME`, allow paced messages to be pushed up to this long before their departure
+    /// time.
+    #[inline]
+    fn max_transmit_delay(&self) -> Duration {
+        Duration::ZERO
+    }
+
     /// Returns `true` if the queue will accept additional transmissions
     fn has_capacity(&self) -> bool {
         self.capacity() != 0
@@ -108,6 +118,16 @@ pub trait Message {
     /// Returns the IPv6 flow label for the message
     fn ipv6_flow_label(&mut self) -> u32;
 
+    /// Returns the Differentiated Services Code Point (DSCP) for the message
+    ///
+    /// The code point is combined with the ECN markings in the IP TOS/traffic class field.
+    /// Queues read the value before writing the payload and only segment messages with the same
+    /// code point into a single GSO payload.
+    #[inline]
+    fn dscp(&mut self) -> u8 {
+        0
+    }
+
     /// Returns true if the packet can be used in a GSO packet
     fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool;
 
@@ -76,6 +76,15 @@ pub trait Interceptor: 'static + Send {
         let _ = packet;
         let _ = payload;
     }
+
+    /// Sets the clock which the capture times of datagrams are derived from
+    ///
+    /// This is called with the endpoint's wall clock before any datagrams are intercepted.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn set_wall_clock(&mut self, clock: &std::sync::Arc<dyn crate::time::WallClock>) {
+        let _ = clock;
+    }
 }
 
 #[derive(Debug, Default)]
@@ -131,6 +140,13 @@ where
         self.0.intercept_tx_payload(subject, packet, payload);
         self.1.intercept_tx_payload(subject, packet, payload);
     }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn set_wall_clock(&mut self, clock: &std::sync::Arc<dyn crate::time::WallClock>) {
+        self.0.set_wall_clock(clock);
+        self.1.set_wall_clock(clock);
+    }
 }
 
 #[derive(Debug, Default)]
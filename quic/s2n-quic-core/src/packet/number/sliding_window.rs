@@ -4,7 +4,7 @@
 use crate::packet::number::PacketNumber;
 use core::mem;
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct SlidingWindow {
     /// Bitfield representing each packet number less than
     /// the right edge up to the window width.
@@ -12,6 +12,15 @@ pub struct SlidingWindow {
     /// The highest packet number seen so far, which is the
     /// right edge of the window.
     right_edge: Option<PacketNumber>,
+    /// The number of packet numbers tracked by the window, including the right edge
+    width: u64,
+}
+
+impl Default for SlidingWindow {
+    #[inline]
+    fn default() -> Self {
+        Self::with_width(Self::MAX_WIDTH)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -53,6 +62,23 @@ enum WindowPosition {
 //# rejected.  Packets falling within the window are checked against a
 //# list of received packets within the window.
 impl SlidingWindow {
+    /// The maximum number of packet numbers which can be tracked by the window
+    pub const MAX_WIDTH: u64 = WINDOW_WIDTH;
+
+    /// Creates a window which tracks `width` packet numbers, including the highest
+    /// packet number seen so far
+    ///
+    /// Packet numbers which are `width` or more below the highest packet number are
+    /// considered too old. The width is clamped to `1..=MAX_WIDTH`.
+    #[inline]
+    pub fn with_width(width: u64) -> Self {
+        Self {
+            window: Window::default(),
+            right_edge: None,
+            width: width.clamp(1, Self::MAX_WIDTH),
+        }
+    }
+
     /// Inserts the `packet_number` into the sliding window, returning
     /// a SlidingWindowError::Duplicate if the `packet_number` has already
     /// been inserted into the sliding window or a SlidingWindowError::TooOld
@@ -63,7 +89,7 @@ impl SlidingWindow {
             WindowPosition::Left => Err(SlidingWindowError::TooOld),
             WindowPosition::RightEdge => Err(SlidingWindowError::Duplicate),
             WindowPosition::Right(delta) => {
-                if delta < WINDOW_WIDTH {
+                if delta < self.width {
                     // Make room for the current right edge
                     self.window <<= 1;
                     // Set the bit for the current right edge
@@ -117,7 +143,7 @@ impl SlidingWindow {
         if let Some(right_edge) = self.right_edge {
             match right_edge.checked_distance(packet_number) {
                 Some(0) => WindowPosition::RightEdge,
-                Some(delta) if delta >= WINDOW_WIDTH => WindowPosition::Left,
+                Some(delta) if delta >= self.width => WindowPosition::Left,
                 Some(delta) => WindowPosition::Within(delta),
                 None => WindowPosition::Right(
                     packet_number
@@ -261,6 +287,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn with_width() {
+        let space = PacketNumberSpace::ApplicationData;
+        let pn = |value: u8| space.new_packet_number(VarInt::from_u8(value));
+        let mut window = SlidingWindow::with_width(4);
+
+        assert_eq!(window.insert(pn(10)), Ok(()));
+        assert_eq!(window.insert(pn(7)), Ok(()));
+        assert_eq!(window.insert(pn(7)), Err(Duplicate));
+        // packet numbers `width` or more below the right edge are too old
+        assert_eq!(window.check(pn(6)), Err(TooOld));
+        assert_eq!(window.insert(pn(6)), Err(TooOld));
+
+        // moving the right edge forgets the packet numbers which are now too old
+        assert_eq!(window.insert(pn(12)), Ok(()));
+        assert_eq!(window.insert(pn(9)), Ok(()));
+        assert_eq!(window.insert(pn(10)), Err(Duplicate));
+        assert_eq!(window.insert(pn(7)), Err(TooOld));
+
+        // jumping further than the width resets the window
+        assert_eq!(window.insert(pn(16)), Ok(()));
+        assert_eq!(window.insert(pn(13)), Ok(()));
+        assert_eq!(window.insert(pn(12)), Err(TooOld));
+
+        // the width is clamped to the supported range
+        assert_eq!(SlidingWindow::with_width(0).width, 1);
+        assert_eq!(
+            SlidingWindow::with_width(u64::MAX).width,
+            SlidingWindow::MAX_WIDTH
+        );
+    }
+
     #[test]
     fn delta_larger_than_32_bits() {
         let mut window = SlidingWindow::default();
@@ -15,7 +15,9 @@ use crate::{
         long::{DestinationConnectionIdLen, SourceConnectionIdLen, Version},
         Tag,
     },
-    random, token,
+    random,
+    time::Timestamp,
+    token,
 };
 use core::{convert::TryInto, mem::size_of, ops::Range};
 use retry::INTEGRITY_TAG_LEN;
@@ -122,6 +124,7 @@ impl<'a> Retry<'a> {
         local_connection_id: &connection::LocalId,
         random: &mut dyn random::Generator,
         token_format: &mut T,
+        timestamp: Timestamp,
         packet_buf: &mut [u8],
     ) -> Option<Range<usize>> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.1
@@ -143,7 +146,8 @@ impl<'a> Retry<'a> {
 
         let destination_connection_id =
             &connection::PeerId::try_from_bytes(retry_packet.destination_connection_id).unwrap();
-        let mut context = token::Context::new(remote_address, destination_connection_id, random);
+        let mut context =
+            token::Context::new(remote_address, destination_connection_id, random, timestamp);
 
         let mut outcome = None;
 
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    event,
+    connection, event,
     inet::{SocketAddress, SocketAddressV4, SocketAddressV6},
 };
 use core::{
@@ -10,6 +10,7 @@ use core::{
     fmt,
     fmt::{Display, Formatter},
     num::NonZeroU16,
+    time::Duration,
 };
 
 #[cfg(any(test, feature = "generator"))]
@@ -85,6 +86,9 @@ pub trait Handle: 'static + Copy + Send + fmt::Debug {
     /// Creates a Handle from a RemoteAddress
     fn from_remote_address(remote_addr: RemoteAddress) -> Self;
 
+    /// Creates a Handle from a LocalAddress and RemoteAddress
+    fn from_addresses(local_addr: LocalAddress, remote_addr: RemoteAddress) -> Self;
+
     /// Returns the remote address for the given handle
     fn remote_address(&self) -> RemoteAddress;
 
@@ -167,6 +171,11 @@ impl Handle for RemoteAddress {
         remote_address
     }
 
+    #[inline]
+    fn from_addresses(_local_address: LocalAddress, remote_address: RemoteAddress) -> Self {
+        remote_address
+    }
+
     #[inline]
     fn remote_address(&self) -> RemoteAddress {
         *self
@@ -218,6 +227,14 @@ impl Handle for Tuple {
         }
     }
 
+    #[inline]
+    fn from_addresses(local_address: LocalAddress, remote_address: RemoteAddress) -> Self {
+        Self {
+            remote_address,
+            local_address,
+        }
+    }
+
     #[inline]
     fn remote_address(&self) -> RemoteAddress {
         self.remote_address
@@ -299,6 +316,152 @@ impl Display for MaxMtuError {
     }
 }
 
+/// The smallest maximum transmission unit (MTU) that path MTU discovery will probe from
+///
+/// Paths start with this MTU before probing raises it. When a black hole is detected, paths
+/// still fall back to [`MINIMUM_MTU`]. The value includes the IP and UDP headers, the same as
+/// [`MaxMtu`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinMtu(MaxMtu);
+
+impl MinMtu {
+    /// The minimum value required for path MTU
+    pub const MIN: Self = Self(MaxMtu::MIN);
+}
+
+impl Default for MinMtu {
+    fn default() -> Self {
+        Self::MIN
+    }
+}
+
+impl TryFrom<u16> for MinMtu {
+    type Error = MaxMtuError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        MaxMtu::try_from(value).map(Self)
+    }
+}
+
+impl From<MinMtu> for u16 {
+    #[inline]
+    fn from(value: MinMtu) -> Self {
+        value.0.into()
+    }
+}
+
+/// Information about the path that a connection is actively using
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathInfo {
+    /// The identifier of the path, which matches the `id` of the path in events
+    pub id: u64,
+    /// The local address of the path
+    pub local_address: SocketAddress,
+    /// The address of the peer on the path
+    pub remote_address: SocketAddress,
+    /// Set if the peer's address has been validated on the path
+    pub is_validated: bool,
+    /// The smoothed round trip time of the path
+    pub rtt: Duration,
+    /// The congestion window of the path, in bytes
+    pub congestion_window: u32,
+}
+
+/// The result of validating a path without migrating the connection to it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathProbe {
+    /// The local address of the probed path
+    pub local_address: SocketAddress,
+    /// The address of the peer on the probed path
+    pub remote_address: SocketAddress,
+    /// The time between sending the first PATH_CHALLENGE and receiving the PATH_RESPONSE
+    pub rtt: Duration,
+}
+
+/// Errors that can occur when probing or migrating to a path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathError {
+    /// Paths can't be probed until the handshake is confirmed
+    HandshakeNotConfirmed,
+    /// The probed path is the path the connection is actively using
+    ActivePath,
+    /// Another path is currently being probed
+    ProbeInProgress,
+    /// No path is currently being probed
+    NotProbing,
+    /// Servers can only probe paths on which the peer has sent packets
+    UnknownPath,
+    /// The connection is already tracking the maximum number of paths
+    PathLimitExceeded,
+    /// The connection is already validating the maximum number of paths
+    ValidationLimitExceeded,
+    /// The peer hasn't issued enough connection IDs to use on another path
+    InsufficientConnectionIds,
+    /// The peer didn't respond to the PATH_CHALLENGE before it was abandoned
+    ValidationAbandoned,
+    /// The path hasn't been validated by probing it
+    NotValidated,
+    /// Servers can't initiate connection migration
+    MigrationNotAllowed,
+    /// The IO provider can't send from a local port other than the one the endpoint is bound to
+    UnsupportedLocalPort,
+    /// The connection encountered an error
+    ConnectionError { error: connection::Error },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PathError {}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::HandshakeNotConfirmed => {
+                write!(f, "Paths can't be probed until the handshake is confirmed")
+            }
+            Self::ActivePath => write!(f, "The path is actively used by the connection"),
+            Self::ProbeInProgress => write!(f, "Another path is currently being probed"),
+            Self::NotProbing => write!(f, "No path is currently being probed"),
+            Self::UnknownPath => write!(f, "The peer hasn't sent any packets on the path"),
+            Self::PathLimitExceeded => {
+                write!(f, "The connection is tracking the maximum number of paths")
+            }
+            Self::ValidationLimitExceeded => {
+                write!(
+                    f,
+                    "The connection is validating the maximum number of paths"
+                )
+            }
+            Self::InsufficientConnectionIds => write!(
+                f,
+                "The peer hasn't issued enough connection IDs to use on another path"
+            ),
+            Self::ValidationAbandoned => write!(
+                f,
+                "The peer didn't respond to the PATH_CHALLENGE before it was abandoned"
+            ),
+            Self::NotValidated => write!(f, "The path hasn't been validated"),
+            Self::MigrationNotAllowed => {
+                write!(f, "Only clients can initiate connection migration")
+            }
+            Self::UnsupportedLocalPort => {
+                write!(
+                    f,
+                    "Paths can only use the local port the endpoint is bound to"
+                )
+            }
+            Self::ConnectionError { error } => write!(f, "{error}"),
+        }
+    }
+}
+
+impl From<connection::Error> for PathError {
+    #[inline]
+    fn from(error: connection::Error) -> Self {
+        Self::ConnectionError { error }
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9308#section-8.1
 //# Some UDP protocols are vulnerable to reflection attacks, where an
 //# attacker is able to direct traffic to a third party as a denial of
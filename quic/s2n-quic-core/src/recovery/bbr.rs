@@ -15,9 +15,10 @@ use crate::{
         },
         congestion_controller,
         congestion_controller::Publisher,
-        CongestionController, RttEstimator,
+        CongestionController, RttEstimator, MAX_BURST_PACKETS,
     },
     time::Timestamp,
+    transport::parameters::ValidationError,
 };
 use core::{
     cmp::{max, min},
@@ -26,6 +27,7 @@ use core::{
 };
 use num_rational::Ratio;
 use num_traits::{CheckedMul, Inv, One};
+use s2n_codec::decoder_invariant;
 
 mod congestion;
 mod data_rate;
@@ -246,6 +248,8 @@ pub struct BbrCongestionController {
     bw_probe_samples: bool,
     /// Controls the departure time and send quantum of packets
     pacer: Pacer,
+    /// True if the departure times and send quantum of the pacer are applied to packets
+    pacing: bool,
     /// If true, we can attempt to avoid updating control parameters and/or model parameters
     try_fast_path: bool,
     //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#2.1
@@ -526,12 +530,30 @@ impl CongestionController for BbrCongestionController {
 
     #[inline]
     fn earliest_departure_time(&self) -> Option<Timestamp> {
-        self.pacer.earliest_departure_time()
+        if self.pacing {
+            self.pacer.earliest_departure_time()
+        } else {
+            None
+        }
     }
 
     #[inline]
     fn send_quantum(&self) -> Option<usize> {
-        Some(self.pacer.send_quantum())
+        // without pacing, GSO batches are only limited by the maximum number of segments
+        if self.pacing {
+            Some(self.pacer.send_quantum())
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        if self.pacing {
+            Some(self.pacer.pacing_rate())
+        } else {
+            None
+        }
     }
 }
 
@@ -581,6 +603,7 @@ impl BbrCongestionController {
             idle_restart: false,
             bw_probe_samples: false,
             pacer: Pacer::new(max_datagram_size),
+            pacing: true,
             try_fast_path: false,
             cwnd_limited_in_round: false,
         }
@@ -1070,8 +1093,26 @@ impl BbrCongestionController {
 }
 
 #[non_exhaustive]
-#[derive(Debug, Default)]
-pub struct Endpoint {}
+#[derive(Debug)]
+pub struct Endpoint {
+    pacing: bool,
+    max_burst_packets: u32,
+}
+
+impl Default for Endpoint {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Endpoint {
+    /// Returns a builder for configuring the BBR congestion controller
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
 
 impl congestion_controller::Endpoint for Endpoint {
     type CongestionController = BbrCongestionController;
@@ -1080,7 +1121,60 @@ impl congestion_controller::Endpoint for Endpoint {
         &mut self,
         path_info: congestion_controller::PathInfo,
     ) -> Self::CongestionController {
-        BbrCongestionController::new(path_info.max_datagram_size)
+        let max_datagram_size = path_info.max_datagram_size;
+        let mut congestion_controller = BbrCongestionController::new(max_datagram_size);
+        congestion_controller.pacing = self.pacing;
+        congestion_controller.pacer =
+            Pacer::with_max_burst_packets(max_datagram_size, self.max_burst_packets);
+        congestion_controller
+    }
+}
+
+/// Configures the BBR congestion controller
+#[derive(Debug)]
+pub struct Builder {
+    pacing: bool,
+    max_burst_packets: u32,
+}
+
+impl Default for Builder {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pacing: true,
+            max_burst_packets: MAX_BURST_PACKETS,
+        }
+    }
+}
+
+impl Builder {
+    /// Enables or disables [pacing](CongestionController::pacing_rate), which defaults to `true`
+    #[inline]
+    pub fn with_pacing(mut self, enabled: bool) -> Self {
+        self.pacing = enabled;
+        self
+    }
+
+    /// Sets the maximum number of packets which are sent together when pacing
+    ///
+    /// The send quantum, which is the amount of data scheduled and transmitted together, is
+    /// limited to this many packets of the maximum datagram size. Smaller bursts are less likely
+    /// to overflow buffers along the path, at the cost of waking up more often to transmit.
+    ///
+    /// Defaults to 10 packets.
+    #[inline]
+    pub fn with_max_burst_packets(mut self, packets: u32) -> Result<Self, ValidationError> {
+        decoder_invariant!(packets > 0, "max_burst_packets must be greater than 0");
+        self.max_burst_packets = packets;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn build(self) -> Endpoint {
+        Endpoint {
+            pacing: self.pacing,
+            max_burst_packets: self.max_burst_packets,
+        }
     }
 }
 
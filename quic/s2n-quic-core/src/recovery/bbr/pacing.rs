@@ -6,7 +6,7 @@ use crate::{
     recovery::{
         bandwidth::Bandwidth,
         bbr::{BbrCongestionController, State},
-        congestion_controller::Publisher,
+        congestion_controller::{PacingGain, Publisher},
         pacing::{INITIAL_INTERVAL, MINIMUM_PACING_RTT},
         MAX_BURST_PACKETS,
     },
@@ -25,10 +25,18 @@ pub struct Pacer {
     pacing_rate: Bandwidth,
     // The maximum size of a data aggregate scheduled and transmitted together
     send_quantum: usize,
+    // The maximum number of packets in a data aggregate
+    max_burst_packets: u32,
 }
 
 impl Pacer {
     pub(super) fn new(max_datagram_size: u16) -> Self {
+        Self::with_max_burst_packets(max_datagram_size, MAX_BURST_PACKETS)
+    }
+
+    pub(super) fn with_max_burst_packets(max_datagram_size: u16, max_burst_packets: u32) -> Self {
+        debug_assert_ne!(max_burst_packets, 0);
+
         //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.6.2
         //# BBRInitPacingRate():
         //#   nominal_bandwidth = InitialCwnd / (SRTT ? SRTT : 1ms)
@@ -42,7 +50,8 @@ impl Pacer {
             capacity: Default::default(),
             next_packet_departure_time: None,
             pacing_rate,
-            send_quantum: Self::max_send_quantum(max_datagram_size),
+            send_quantum: Self::max_send_quantum(max_burst_packets, max_datagram_size),
+            max_burst_packets,
         }
     }
 
@@ -79,7 +88,11 @@ impl Pacer {
 
         let rate = Self::bandwidth_to_pacing_rate(bw, gain);
         self.pacing_rate = rate;
-        publisher.on_pacing_rate_updated(rate, self.send_quantum as u32, gain);
+        publisher.on_pacing_rate_updated(
+            rate,
+            self.send_quantum as u32,
+            PacingGain::from_ratio(gain),
+        );
     }
 
     /// Sets the pacing rate used for determining the earliest departure time
@@ -95,7 +108,11 @@ impl Pacer {
 
         if filled_pipe || rate > self.pacing_rate {
             self.pacing_rate = rate;
-            publisher.on_pacing_rate_updated(rate, self.send_quantum as u32, gain);
+            publisher.on_pacing_rate_updated(
+                rate,
+                self.send_quantum as u32,
+                PacingGain::from_ratio(gain),
+            );
         }
     }
 
@@ -121,9 +138,10 @@ impl Pacer {
         } as usize;
 
         let send_quantum = (self.pacing_rate * Duration::from_millis(1)) as usize;
-        self.send_quantum = send_quantum
-            .max(floor)
-            .min(Self::max_send_quantum(max_datagram_size));
+        self.send_quantum = send_quantum.max(floor).min(Self::max_send_quantum(
+            self.max_burst_packets,
+            max_datagram_size,
+        ));
     }
 
     /// Returns the earliest time that a packet may be transmitted.
@@ -140,12 +158,12 @@ impl Pacer {
 
     /// Returns the maximum value for send_quantum
     #[inline]
-    fn max_send_quantum(max_datagram_size: u16) -> usize {
+    fn max_send_quantum(max_burst_packets: u32, max_datagram_size: u16) -> usize {
         //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.6.3
         //= type=exception
         //= reason=QUIC recommends limiting bursts to the initial congestion window
         //# BBR.send_quantum = min(BBR.pacing_rate * 1ms, 64KBytes)
-        MAX_BURST_PACKETS as usize * max_datagram_size as usize
+        (max_burst_packets as usize).saturating_mul(max_datagram_size as usize)
     }
 
     // Recalculate the interval between bursts of paced packets
@@ -180,7 +198,8 @@ impl Pacer {
         self.send_quantum = send_quantum
     }
 
-    #[cfg(test)]
+    /// Returns the current pacing rate
+    #[inline]
     pub fn pacing_rate(&self) -> Bandwidth {
         self.pacing_rate
     }
@@ -196,6 +215,7 @@ mod tests {
             bbr::{pacing::Pacer, State, State::Startup},
             congestion_controller::PathPublisher,
             pacing::INITIAL_INTERVAL,
+            MAX_BURST_PACKETS,
         },
         time::{Clock, NoopClock},
     };
@@ -229,8 +249,11 @@ mod tests {
         // of 10 and 10 * MINIMUM_MTU is less than 64KB, this limit will always be higher than the
         // limit s2n-quic imposes. This test ensures that this remains true if MAX_BURST_PACKETS is
         // increased.
-        assert_eq!(Pacer::max_send_quantum(MINIMUM_MTU), 12_000);
-        assert!(Pacer::max_send_quantum(MINIMUM_MTU) < 64_000);
+        assert_eq!(
+            Pacer::max_send_quantum(MAX_BURST_PACKETS, MINIMUM_MTU),
+            12_000
+        );
+        assert!(Pacer::max_send_quantum(MAX_BURST_PACKETS, MINIMUM_MTU) < 64_000);
     }
 
     //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.6.2
@@ -319,6 +342,19 @@ mod tests {
         assert_eq!(12_000, pacer.send_quantum);
     }
 
+    #[test]
+    fn set_send_quantum_max_burst_packets() {
+        let mut pacer = Pacer::with_max_burst_packets(MINIMUM_MTU, 4);
+        assert_eq!(4 * MINIMUM_MTU as usize, pacer.send_quantum);
+
+        // pacing_rate = 100.0 MBps, floor = 2 * MINIMUM_MTU
+        pacer.pacing_rate = Bandwidth::new(100_000_000, Duration::from_secs(1));
+        pacer.set_send_quantum(MINIMUM_MTU);
+        // pacing_Rate * 1ms = 100000 bytes
+        // send_quantum = min(100000, 4 * MINIMUM_MTU) = 4 * MINIMUM_MTU
+        assert_eq!(4 * MINIMUM_MTU as usize, pacer.send_quantum);
+    }
+
     #[test]
     fn test_one_rtt() {
         let mut pacer = Pacer::new(MINIMUM_MTU);
@@ -11,7 +11,7 @@ use crate::{
         bandwidth::{Bandwidth, PacketInfo, RateSample},
         bbr,
         bbr::{probe_bw::CyclePhase, probe_rtt, BbrCongestionController, State},
-        congestion_controller::{PathPublisher, Publisher},
+        congestion_controller::{self, PathPublisher, Publisher},
         CongestionController,
     },
     time::{Clock, NoopClock},
@@ -1046,3 +1046,39 @@ fn enter_probe_bw_state<Pub: Publisher>(
         probe_bw_state.set_cycle_phase_for_test(cycle_phase);
     }
 }
+
+#[test]
+fn pacing_disabled() {
+    use congestion_controller::Endpoint as _;
+
+    let remote_address = crate::inet::SocketAddress::default();
+
+    for pacing in [true, false] {
+        let path_info = congestion_controller::PathInfo::new(&remote_address);
+        let bbr = bbr::Endpoint::builder()
+            .with_pacing(pacing)
+            .build()
+            .new_congestion_controller(path_info);
+
+        // GSO batches are only limited by the send quantum if pacing is enabled
+        assert_eq!(bbr.send_quantum().is_some(), pacing);
+    }
+}
+
+#[test]
+fn builder_max_burst_packets() {
+    use congestion_controller::Endpoint as _;
+
+    let remote_address = crate::inet::SocketAddress::default();
+    let path_info = congestion_controller::PathInfo::new(&remote_address);
+    let bbr = bbr::Endpoint::builder()
+        .with_max_burst_packets(4)
+        .unwrap()
+        .build()
+        .new_congestion_controller(path_info);
+
+    // the send quantum is limited to the maximum burst
+    assert_eq!(bbr.send_quantum(), Some(4 * MINIMUM_MTU as usize));
+
+    assert!(bbr::Endpoint::builder().with_max_burst_packets(0).is_err());
+}
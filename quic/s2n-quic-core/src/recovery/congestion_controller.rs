@@ -21,9 +21,15 @@ use core::fmt::Debug;
 use num_rational::Ratio;
 use num_traits::ToPrimitive;
 
+/// Creates a congestion controller for each path of the endpoint's connections
 pub trait Endpoint: 'static + Debug + Send {
     type CongestionController: CongestionController;
 
+    /// Called when a new path is created, either when a connection is opened or when the peer
+    /// migrates to a new address
+    ///
+    /// Each path has its own congestion controller, which is not shared with other paths or
+    /// connections.
     fn new_congestion_controller(&mut self, path_info: PathInfo) -> Self::CongestionController;
 }
 
@@ -46,7 +52,58 @@ impl<'a> PathInfo<'a> {
     }
 }
 
-pub trait Publisher {
+/// The gain applied to the bandwidth estimate to compute the pacing rate
+///
+/// The gain is expressed as a ratio, e.g. a gain of 1.25 is `PacingGain::new(5, 4)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PacingGain {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl PacingGain {
+    /// Creates a gain of `numerator / denominator`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero
+    #[inline]
+    pub const fn new(numerator: u64, denominator: u64) -> Self {
+        assert!(denominator != 0, "denominator must be non-zero");
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    #[inline]
+    pub const fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    #[inline]
+    pub const fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    #[inline]
+    pub(crate) fn from_ratio(ratio: Ratio<u64>) -> Self {
+        Self::new(*ratio.numer(), *ratio.denom())
+    }
+
+    #[inline]
+    fn as_f32(&self) -> f32 {
+        Ratio::new_raw(self.numerator, self.denominator)
+            .to_f32()
+            .expect("pacing gain should be representable as f32")
+    }
+}
+
+/// Publishes the events of a congestion controller
+///
+/// This trait is sealed and is only implemented by s2n-quic, which passes a publisher to each of
+/// the [`CongestionController`] methods.
+pub trait Publisher: private::Sealed {
     /// Invoked when the congestion controller has exited the Slow Start phase
     fn on_slow_start_exited(&mut self, cause: SlowStartExitCause, congestion_window: u32);
     /// Invoked when the delivery rate sample has been updated
@@ -56,7 +113,7 @@ pub trait Publisher {
         &mut self,
         pacing_rate: Bandwidth,
         burst_size: u32,
-        pacing_gain: Ratio<u64>,
+        pacing_gain: PacingGain,
     );
     /// Invoked when the state of the BBR congestion controller changes
     fn on_bbr_state_changed(&mut self, state: BbrState);
@@ -101,16 +158,14 @@ impl<'a, Pub: event::ConnectionPublisher> Publisher for PathPublisher<'a, Pub> {
         &mut self,
         pacing_rate: Bandwidth,
         burst_size: u32,
-        pacing_gain: Ratio<u64>,
+        pacing_gain: PacingGain,
     ) {
         self.publisher
             .on_pacing_rate_updated(event::builder::PacingRateUpdated {
                 path_id: self.path_id.into_event(),
                 bytes_per_second: pacing_rate.as_bytes_per_second(),
                 burst_size,
-                pacing_gain: pacing_gain
-                    .to_f32()
-                    .expect("pacing gain should be representable as f32"),
+                pacing_gain: pacing_gain.as_f32(),
             })
     }
 
@@ -124,11 +179,35 @@ impl<'a, Pub: event::ConnectionPublisher> Publisher for PathPublisher<'a, Pub> {
     }
 }
 
+mod private {
+    use crate::event;
+
+    pub trait Sealed {}
+
+    impl<'a, Pub: event::ConnectionPublisher> Sealed for super::PathPublisher<'a, Pub> {}
+}
+
 /// An algorithm for controlling congestion.
 ///
-/// NOTE: This trait is considered unstable and can only be implemented by
-///       including the `unstable-congestion-controller` feature.
-pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed {
+/// Implementations must uphold the following invariants:
+///
+/// * The methods are called from the connection's task and must not block. The current time is
+///   always provided by the caller and should be used instead of reading a clock.
+/// * `bytes_in_flight` increases by the `sent_bytes` of each [`Self::on_packet_sent`] call and
+///   decreases by the bytes passed to [`Self::on_ack`], [`Self::on_packet_lost`] and
+///   [`Self::on_packet_discarded`]. Every sent packet is eventually reported through exactly one
+///   of these methods, unless the connection is closed first.
+/// * The congestion window should not fall below two packets of the current maximum datagram
+///   size, as reported by [`PathInfo::max_datagram_size`] and [`Self::on_mtu_update`]. Otherwise
+///   the connection may not be able to recover from a loss.
+/// * While [`Self::is_congestion_limited`] returns `true`, only packets which are not counted
+///   towards bytes in flight, such as ACK-only packets, are sent. If
+///   [`Self::requires_fast_retransmission`] returns `true`, a single packet is sent regardless of
+///   the congestion window.
+///
+/// Methods may be added to this trait in future releases, but they will always provide a default
+/// implementation.
+pub trait CongestionController: 'static + Clone + Send + Debug {
     /// Additional metadata about a packet to track until a sent packet
     /// is either acknowledged or declared lost
     type PacketInfo: Copy + Send + Sized + Debug;
@@ -245,26 +324,19 @@ pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed
     fn send_quantum(&self) -> Option<usize> {
         None
     }
-}
-
-// Prevent implementation of the `CongestionController` trait if the
-// `unstable-congestion-controller` feature is not turned on.
-mod private {
-    use cfg_if::cfg_if;
 
-    pub trait Sealed {}
-
-    cfg_if!(
-        if #[cfg(any(test, feature = "unstable-congestion-controller", feature = "testing"))] {
-            // If `unstable-congestion-controller` is enabled, implement Sealed for any type that
-            // otherwise implements `CongestionController`
-            impl<T: crate::recovery::CongestionController> Sealed for T {}
-        } else {
-            // Otherwise only allow the included CUBIC and BBRv2 congestion controllers
-            impl Sealed for crate::recovery::CubicCongestionController {}
-            impl Sealed for crate::recovery::bbr::BbrCongestionController {}
-        }
-    );
+    /// The rate at which the congestion controller is currently pacing packets.
+    ///
+    /// If the value is `None`, the congestion controller is not pacing packets.
+    ///
+    /// When pacing is disabled, packets are sent as soon as the congestion window allows it,
+    /// without spacing them out over the round trip time, and are batched with GSO up to the
+    /// maximum number of segments. This can reduce latency on links with very small round trip
+    /// times, such as within the same host or rack, but causes bursts that can overflow
+    /// buffers on other networks.
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        None
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -4,15 +4,18 @@
 use crate::{
     counter::Counter,
     event::builder::SlowStartExitCause,
+    path::MINIMUM_MTU,
     random,
     recovery::{
+        bandwidth::Bandwidth,
         congestion_controller::{self, CongestionController, Publisher},
         cubic::{FastRetransmission::*, State::*},
-        hybrid_slow_start::HybridSlowStart,
+        hybrid_slow_start::{self, HybridSlowStart},
         pacing::Pacer,
-        RttEstimator,
+        RttEstimator, MAX_BURST_PACKETS,
     },
     time::Timestamp,
+    transport::parameters::ValidationError,
 };
 use core::{
     cmp::{max, min},
@@ -20,6 +23,9 @@ use core::{
 };
 #[cfg(not(feature = "std"))]
 use num_traits::Float as _;
+use s2n_codec::decoder_invariant;
+
+pub use hybrid_slow_start::SlowStartExit;
 
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3
 //#                 New Path or      +------------+
@@ -149,6 +155,8 @@ pub struct CubicCongestionController {
     //# distance networks.
     slow_start: HybridSlowStart,
     pacer: Pacer,
+    /// True if the departure times of packets are paced
+    pacing: bool,
     max_datagram_size: u16,
     congestion_window: f32,
     state: State,
@@ -239,6 +247,10 @@ impl CongestionController for CubicCongestionController {
 
         self.time_of_last_sent_packet = Some(time_sent);
 
+        if !self.pacing {
+            return;
+        }
+
         self.pacer.on_packet_sent(
             time_sent,
             bytes_sent,
@@ -489,7 +501,20 @@ impl CongestionController for CubicCongestionController {
 
     #[inline]
     fn earliest_departure_time(&self) -> Option<Timestamp> {
-        self.pacer.earliest_departure_time()
+        if self.pacing {
+            self.pacer.earliest_departure_time()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        if self.pacing {
+            self.pacer.pacing_rate()
+        } else {
+            None
+        }
     }
 }
 
@@ -501,6 +526,7 @@ impl CubicCongestionController {
             cubic: Cubic::new(max_datagram_size),
             slow_start: HybridSlowStart::new(max_datagram_size),
             pacer: Pacer::default(),
+            pacing: true,
             max_datagram_size,
             congestion_window: CubicCongestionController::initial_window(max_datagram_size) as f32,
             state: SlowStart,
@@ -695,6 +721,12 @@ struct Cubic {
     // k is the time until we expect to reach w_max
     k: Duration,
     max_datagram_size: u16,
+    // the constant determining the aggressiveness of the window increase
+    c: f32,
+    // the multiplicative decrease factor
+    beta: f32,
+    // the minimum congestion window, in packets
+    minimum_window: f32,
 }
 
 //= https://www.rfc-editor.org/rfc/rfc8312#section-5.1
@@ -707,6 +739,9 @@ const C: f32 = 0.4;
 //# Parameter beta_cubic SHOULD be set to 0.7.
 const BETA_CUBIC: f32 = 0.7;
 
+// The minimum congestion window recommended by RFC 9002, in packets
+const MINIMUM_WINDOW_PACKETS: u32 = 2;
+
 impl Cubic {
     pub fn new(max_datagram_size: u16) -> Self {
         Cubic {
@@ -714,6 +749,9 @@ impl Cubic {
             w_last_max: 0.0,
             k: Duration::ZERO,
             max_datagram_size,
+            c: C,
+            beta: BETA_CUBIC,
+            minimum_window: MINIMUM_WINDOW_PACKETS as f32,
         }
     }
 
@@ -742,7 +780,7 @@ impl Cubic {
     //# where beta_cubic is the CUBIC multiplication decrease factor
     #[inline]
     fn w_cubic(&self, t: Duration) -> f32 {
-        C * (t.as_secs_f32() - self.k.as_secs_f32()).powi(3) + self.w_max
+        self.c * (t.as_secs_f32() - self.k.as_secs_f32()).powi(3) + self.w_max
     }
 
     //= https://www.rfc-editor.org/rfc/rfc8312#section-4.2
@@ -751,8 +789,8 @@ impl Cubic {
     #[inline]
     fn w_est(&self, t: Duration, rtt: Duration) -> f32 {
         self.w_max.mul_add(
-            BETA_CUBIC,
-            (3.0 * (1.0 - BETA_CUBIC) / (1.0 + BETA_CUBIC)) * (t.as_secs_f32() / rtt.as_secs_f32()),
+            self.beta,
+            (3.0 * (1.0 - self.beta) / (1.0 + self.beta)) * (t.as_secs_f32() / rtt.as_secs_f32()),
         )
     }
 
@@ -799,12 +837,12 @@ impl Cubic {
         //# time for the new flow to catch up to its congestion window size.
         let w_max = self.w_max;
         if w_max < self.w_last_max {
-            self.w_max = (w_max * (1.0 + BETA_CUBIC) / 2.0)
-                .max(self.bytes_to_packets(self.minimum_window()));
+            self.w_max =
+                (w_max * (1.0 + self.beta) / 2.0).max(self.bytes_to_packets(self.minimum_window()));
         }
         self.w_last_max = w_max;
 
-        let cwnd_start = (cwnd * BETA_CUBIC).max(self.minimum_window());
+        let cwnd_start = (cwnd * self.beta).max(self.minimum_window());
 
         //= https://tools.ietf.org/id/draft-eggert-tcpm-rfc8312bis-01#4.2
         //# _K_ is the time period that the above
@@ -823,8 +861,9 @@ impl Cubic {
         //#
         //# where _cwnd_(start)_ is the congestion window at the beginning of the
         //# current congestion avoidance stage.
-        self.k =
-            Duration::from_secs_f32(((self.w_max - self.bytes_to_packets(cwnd_start)) / C).cbrt());
+        self.k = Duration::from_secs_f32(
+            ((self.w_max - self.bytes_to_packets(cwnd_start)) / self.c).cbrt(),
+        );
 
         cwnd_start
     }
@@ -853,7 +892,7 @@ impl Cubic {
     //# value is 2 * max_datagram_size.
     #[inline]
     fn minimum_window(&self) -> f32 {
-        2.0 * self.max_datagram_size as f32
+        self.minimum_window * self.max_datagram_size as f32
     }
 
     #[inline]
@@ -863,8 +902,31 @@ impl Cubic {
 }
 
 #[non_exhaustive]
-#[derive(Debug, Default)]
-pub struct Endpoint {}
+#[derive(Debug)]
+pub struct Endpoint {
+    pacing: bool,
+    max_burst_packets: u32,
+    initial_window: Option<InitialWindow>,
+    minimum_window: u32,
+    beta: f32,
+    c: f32,
+    slow_start: hybrid_slow_start::Settings,
+}
+
+impl Default for Endpoint {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Endpoint {
+    /// Returns a builder for configuring the CUBIC congestion controller
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
 
 impl congestion_controller::Endpoint for Endpoint {
     type CongestionController = CubicCongestionController;
@@ -873,7 +935,239 @@ impl congestion_controller::Endpoint for Endpoint {
         &mut self,
         path_info: congestion_controller::PathInfo,
     ) -> Self::CongestionController {
-        CubicCongestionController::new(path_info.max_datagram_size)
+        let max_datagram_size = path_info.max_datagram_size;
+        let mut congestion_controller = CubicCongestionController::new(max_datagram_size);
+        congestion_controller.pacing = self.pacing;
+        congestion_controller.pacer = Pacer::new(self.max_burst_packets);
+        congestion_controller.slow_start =
+            HybridSlowStart::with_settings(max_datagram_size, self.slow_start);
+        congestion_controller.cubic.c = self.c;
+        congestion_controller.cubic.beta = self.beta;
+        congestion_controller.cubic.minimum_window = self.minimum_window as f32;
+
+        let initial_window = match self.initial_window {
+            Some(InitialWindow::Packets(packets)) => {
+                packets.saturating_mul(max_datagram_size as u32)
+            }
+            Some(InitialWindow::Bytes(bytes)) => bytes,
+            None => CubicCongestionController::initial_window(max_datagram_size),
+        };
+        // the initial window can't be smaller than the minimum window
+        congestion_controller.congestion_window =
+            (initial_window as f32).max(congestion_controller.cubic.minimum_window());
+
+        congestion_controller
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum InitialWindow {
+    Packets(u32),
+    Bytes(u32),
+}
+
+/// Configures the CUBIC congestion controller
+#[derive(Debug)]
+pub struct Builder {
+    pacing: bool,
+    max_burst_packets: u32,
+    initial_window: Option<InitialWindow>,
+    minimum_window: u32,
+    beta: f32,
+    c: f32,
+    slow_start: hybrid_slow_start::Settings,
+}
+
+impl Default for Builder {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pacing: true,
+            max_burst_packets: MAX_BURST_PACKETS,
+            initial_window: None,
+            minimum_window: MINIMUM_WINDOW_PACKETS,
+            beta: BETA_CUBIC,
+            c: C,
+            slow_start: Default::default(),
+        }
+    }
+}
+
+impl Builder {
+    /// Enables or disables [pacing](CongestionController::pacing_rate), which defaults to `true`
+    #[inline]
+    pub fn with_pacing(mut self, enabled: bool) -> Self {
+        self.pacing = enabled;
+        self
+    }
+
+    /// Sets the maximum number of packets which are sent together when pacing
+    ///
+    /// The pacer spreads bursts of this many packets of the maximum datagram size evenly over the
+    /// round trip time. Smaller bursts are less likely to overflow buffers along the path, at the
+    /// cost of waking up more often to transmit.
+    ///
+    /// Defaults to 10 packets.
+    #[inline]
+    pub fn with_max_burst_packets(mut self, packets: u32) -> Result<Self, ValidationError> {
+        decoder_invariant!(packets > 0, "max_burst_packets must be greater than 0");
+        self.max_burst_packets = packets;
+        Ok(self)
+    }
+
+    /// Sets the initial congestion window as a number of packets of the maximum datagram size
+    ///
+    /// Replaces any value set with [`Self::with_initial_window_bytes`].
+    ///
+    /// Defaults to 10 packets, limited to the larger of 14,720 bytes or 2 packets, as
+    /// recommended by [RFC 9002](https://www.rfc-editor.org/rfc/rfc9002#section-7.2).
+    #[inline]
+    pub fn with_initial_window_packets(mut self, packets: u32) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            packets >= MINIMUM_WINDOW_PACKETS,
+            "initial_window must be at least 2 packets"
+        );
+        self.initial_window = Some(InitialWindow::Packets(packets));
+        Ok(self)
+    }
+
+    /// Sets the initial congestion window in bytes
+    ///
+    /// Replaces any value set with [`Self::with_initial_window_packets`].
+    ///
+    /// See [`Self::with_initial_window_packets`] for the default value.
+    #[inline]
+    pub fn with_initial_window_bytes(mut self, bytes: u32) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            bytes >= MINIMUM_WINDOW_PACKETS * MINIMUM_MTU as u32,
+            "initial_window must be at least 2 packets of the minimum MTU"
+        );
+        self.initial_window = Some(InitialWindow::Bytes(bytes));
+        Ok(self)
+    }
+
+    /// Sets the minimum congestion window as a number of packets of the maximum datagram size
+    ///
+    /// The congestion window is never reduced below this value in response to loss,
+    /// ECN-CE markings, or persistent congestion. The initial window is raised to this value if
+    /// it is smaller.
+    ///
+    /// Defaults to 2 packets, as recommended by [RFC 9002](https://www.rfc-editor.org/rfc/rfc9002#section-7.2).
+    #[inline]
+    pub fn with_minimum_window_packets(mut self, packets: u32) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            packets >= MINIMUM_WINDOW_PACKETS,
+            "minimum_window must be at least 2 packets"
+        );
+        self.minimum_window = packets;
+        Ok(self)
+    }
+
+    /// Sets the multiplicative decrease factor (`beta_cubic`)
+    ///
+    /// The congestion window is multiplied by this value in response to a congestion event.
+    /// Larger values reduce the window less, at the cost of being less fair to other flows.
+    ///
+    /// Defaults to 0.7, as recommended by [RFC 8312](https://www.rfc-editor.org/rfc/rfc8312#section-4.5).
+    #[inline]
+    pub fn with_beta(mut self, beta: f32) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            beta > 0.0 && beta < 1.0,
+            "beta must be greater than 0 and less than 1"
+        );
+        self.beta = beta;
+        Ok(self)
+    }
+
+    /// Sets the constant which determines the aggressiveness of the window increase (`C`)
+    ///
+    /// Defaults to 0.4, as recommended by [RFC 8312](https://www.rfc-editor.org/rfc/rfc8312#section-5.1).
+    #[inline]
+    pub fn with_c(mut self, c: f32) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            c > 0.0 && c.is_finite(),
+            "c must be a finite value greater than 0"
+        );
+        self.c = c;
+        Ok(self)
+    }
+
+    /// Sets the algorithm used to exit slow start before a congestion event is experienced
+    ///
+    /// Defaults to [`SlowStartExit::HyStart`], or [`SlowStartExit::HyStartPlusPlus`] if the
+    /// `S2N_UNSTABLE_USE_HYSTART_PP` environment variable is set.
+    #[inline]
+    pub fn with_slow_start_exit(mut self, exit: SlowStartExit) -> Self {
+        self.slow_start.exit = exit;
+        self
+    }
+
+    /// Sets the bounds of the RTT increase which is considered a signal to exit slow start
+    ///
+    /// The delay threshold is an eighth of the minimum RTT of the previous round, clamped to
+    /// these bounds.
+    ///
+    /// Defaults to 4ms and 16ms, as recommended by [RFC 9406](https://www.rfc-editor.org/rfc/rfc9406#section-4.3).
+    #[inline]
+    pub fn with_hystart_delay_thresholds(
+        mut self,
+        min: Duration,
+        max: Duration,
+    ) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            min > Duration::ZERO,
+            "min_delay_threshold must be greater than 0"
+        );
+        decoder_invariant!(
+            min <= max,
+            "min_delay_threshold must not be greater than max_delay_threshold"
+        );
+        self.slow_start.min_delay_threshold = min;
+        self.slow_start.max_delay_threshold = max;
+        Ok(self)
+    }
+
+    /// Sets the divisor applied to the window growth during Conservative Slow Start
+    ///
+    /// Only used with [`SlowStartExit::HyStartPlusPlus`].
+    ///
+    /// Defaults to 4, as recommended by [RFC 9406](https://www.rfc-editor.org/rfc/rfc9406#section-4.3).
+    #[inline]
+    pub fn with_hystart_css_growth_divisor(
+        mut self,
+        divisor: f32,
+    ) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            divisor >= 1.0 && divisor.is_finite(),
+            "css_growth_divisor must be a finite value of at least 1"
+        );
+        self.slow_start.css_growth_divisor = divisor;
+        Ok(self)
+    }
+
+    /// Sets the number of rounds spent in Conservative Slow Start before exiting slow start
+    ///
+    /// Only used with [`SlowStartExit::HyStartPlusPlus`].
+    ///
+    /// Defaults to 5, as recommended by [RFC 9406](https://www.rfc-editor.org/rfc/rfc9406#section-4.3).
+    #[inline]
+    pub fn with_hystart_css_rounds(mut self, rounds: usize) -> Result<Self, ValidationError> {
+        decoder_invariant!(rounds > 0, "css_rounds must be greater than 0");
+        self.slow_start.css_rounds = rounds;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn build(self) -> Endpoint {
+        Endpoint {
+            pacing: self.pacing,
+            max_burst_packets: self.max_burst_packets,
+            initial_window: self.initial_window,
+            minimum_window: self.minimum_window,
+            beta: self.beta,
+            c: self.c,
+            slow_start: self.slow_start,
+        }
     }
 }
 
@@ -1228,3 +1228,153 @@ fn on_packet_ack_congestion_avoidance_too_large_increase() {
     assert!(cc.cubic.w_cubic(t) > cc.cubic.w_est(t, rtt));
     assert_delta!(cc.congestion_window, 3_600_000.0 + 1000.0 / 2.0, 0.001);
 }
+
+#[test]
+fn pacing_disabled() {
+    use congestion_controller::Endpoint as _;
+
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+    let remote_address = crate::inet::SocketAddress::default();
+
+    for pacing in [true, false] {
+        let path_info = congestion_controller::PathInfo::new(&remote_address);
+        let mut cc = Endpoint::builder()
+            .with_pacing(pacing)
+            .build()
+            .new_congestion_controller(path_info);
+
+        for _ in 0..20 {
+            cc.on_packet_sent(now, 1200, None, &rtt_estimator, &mut publisher);
+        }
+
+        // packets are only delayed if pacing is enabled
+        assert_eq!(cc.earliest_departure_time().is_some(), pacing);
+    }
+}
+
+#[test]
+fn builder_max_burst_packets() {
+    use congestion_controller::Endpoint as _;
+
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+    let remote_address = crate::inet::SocketAddress::default();
+
+    for max_burst_packets in [2, 4] {
+        let path_info = congestion_controller::PathInfo::new(&remote_address);
+        let mut cc = Endpoint::builder()
+            .with_max_burst_packets(max_burst_packets)
+            .unwrap()
+            .build()
+            .new_congestion_controller(path_info);
+
+        // the first burst departs immediately
+        for _ in 0..=max_burst_packets {
+            assert!(cc
+                .earliest_departure_time()
+                .map_or(true, |time| time <= now));
+            cc.on_packet_sent(now, 1200, None, &rtt_estimator, &mut publisher);
+        }
+
+        // the packet after the burst is delayed
+        assert!(cc.earliest_departure_time().unwrap() > now);
+    }
+}
+
+#[test]
+fn builder_initial_window() {
+    use congestion_controller::Endpoint as _;
+
+    let remote_address = crate::inet::SocketAddress::default();
+    let path_info = || congestion_controller::PathInfo::new(&remote_address);
+
+    let mut endpoint = Endpoint::builder()
+        .with_initial_window_packets(30)
+        .unwrap()
+        .build();
+    let cc = endpoint.new_congestion_controller(path_info());
+    assert_eq!(cc.congestion_window(), 30 * MINIMUM_MTU as u32);
+
+    let mut endpoint = Endpoint::builder()
+        .with_initial_window_bytes(50_000)
+        .unwrap()
+        .build();
+    let cc = endpoint.new_congestion_controller(path_info());
+    assert_eq!(cc.congestion_window(), 50_000);
+
+    // the initial window is raised to the minimum window
+    let mut endpoint = Endpoint::builder()
+        .with_minimum_window_packets(20)
+        .unwrap()
+        .build();
+    let cc = endpoint.new_congestion_controller(path_info());
+    assert_eq!(cc.congestion_window(), 20 * MINIMUM_MTU as u32);
+}
+
+#[test]
+fn builder_loss_response() {
+    use congestion_controller::Endpoint as _;
+
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+    let remote_address = crate::inet::SocketAddress::default();
+
+    let mut endpoint = Endpoint::builder()
+        .with_beta(0.5)
+        .unwrap()
+        .with_c(0.8)
+        .unwrap()
+        .with_minimum_window_packets(4)
+        .unwrap()
+        .build();
+    let mut cc =
+        endpoint.new_congestion_controller(congestion_controller::PathInfo::new(&remote_address));
+    assert_delta!(cc.cubic.c, 0.8, 0.001);
+
+    cc.congestion_window = 100_000.0;
+    cc.bytes_in_flight = BytesInFlight::new(100_000);
+
+    cc.on_packet_lost(100, (), false, false, random, now, &mut publisher);
+    assert_delta!(cc.congestion_window, 100_000.0 * 0.5, 0.001);
+
+    cc.on_packet_lost(100, (), true, false, random, now, &mut publisher);
+    assert_delta!(cc.congestion_window, 4.0 * MINIMUM_MTU as f32, 0.001);
+}
+
+#[test]
+fn builder_validation() {
+    assert!(Endpoint::builder().with_initial_window_packets(1).is_err());
+    assert!(Endpoint::builder().with_initial_window_bytes(1200).is_err());
+    assert!(Endpoint::builder().with_minimum_window_packets(1).is_err());
+    assert!(Endpoint::builder().with_beta(0.0).is_err());
+    assert!(Endpoint::builder().with_beta(1.0).is_err());
+    assert!(Endpoint::builder().with_c(0.0).is_err());
+    assert!(Endpoint::builder().with_c(f32::NAN).is_err());
+    assert!(Endpoint::builder().with_c(f32::INFINITY).is_err());
+    assert!(Endpoint::builder().with_max_burst_packets(0).is_err());
+
+    let ms = Duration::from_millis;
+    assert!(Endpoint::builder()
+        .with_hystart_delay_thresholds(Duration::ZERO, ms(16))
+        .is_err());
+    assert!(Endpoint::builder()
+        .with_hystart_delay_thresholds(ms(16), ms(4))
+        .is_err());
+    assert!(Endpoint::builder()
+        .with_hystart_delay_thresholds(ms(16), ms(16))
+        .is_ok());
+    assert!(Endpoint::builder()
+        .with_hystart_css_growth_divisor(0.5)
+        .is_err());
+    assert!(Endpoint::builder()
+        .with_hystart_css_growth_divisor(f32::INFINITY)
+        .is_err());
+    assert!(Endpoint::builder().with_hystart_css_rounds(0).is_err());
+}
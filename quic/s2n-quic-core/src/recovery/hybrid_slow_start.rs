@@ -19,11 +19,60 @@ pub struct HybridSlowStart {
     pub(super) threshold: f32,
     max_datagram_size: u16,
     rtt_round_end_time: Option<Timestamp>,
+    enabled: bool,
     use_hystart_plus_plus: bool,
     ss_growth_divisor: f32,
     css_count: usize,
     css_baseline_min_rtt: Duration,
     css_threshold: f32,
+    min_delay_threshold: Duration,
+    max_delay_threshold: Duration,
+    css_growth_divisor: f32,
+    css_rounds: usize,
+}
+
+/// The algorithm used to exit slow start before a congestion event is experienced
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlowStartExit {
+    /// Slow start is only exited on packet loss or an increase in the ECN-CE count
+    Disabled,
+    /// Slow start is exited once the minimum RTT of a round increases beyond the delay
+    /// threshold, as done by the Hybrid Slow Start implementation in Linux
+    HyStart,
+    /// The window growth is slowed down once the minimum RTT of a round increases beyond the
+    /// delay threshold, and slow start is exited if the RTT doesn't decrease again within a
+    /// number of rounds, as specified by [HyStart++](https://www.rfc-editor.org/rfc/rfc9406.html)
+    HyStartPlusPlus,
+}
+
+/// Configures the slow start exit algorithm
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Settings {
+    pub exit: SlowStartExit,
+    pub min_delay_threshold: Duration,
+    pub max_delay_threshold: Duration,
+    pub css_growth_divisor: f32,
+    pub css_rounds: usize,
+}
+
+impl Default for Settings {
+    #[inline]
+    fn default() -> Self {
+        let exit = if HybridSlowStart::use_hystart_parameter() {
+            SlowStartExit::HyStartPlusPlus
+        } else {
+            SlowStartExit::HyStart
+        };
+
+        Self {
+            exit,
+            min_delay_threshold: MIN_DELAY_THRESHOLD,
+            max_delay_threshold: MAX_DELAY_THRESHOLD,
+            css_growth_divisor: CSS_GROWTH_DIVISOR,
+            css_rounds: CSS_ROUNDS,
+        }
+    }
 }
 
 /// Minimum slow start threshold in multiples of the max_datagram_size.
@@ -67,6 +116,11 @@ impl HybridSlowStart {
     /// Constructs a new `HybridSlowStart`. `max_datagram_size` is used for determining
     /// the minimum slow start threshold.
     pub fn new(max_datagram_size: u16) -> Self {
+        Self::with_settings(max_datagram_size, Settings::default())
+    }
+
+    /// Constructs a new `HybridSlowStart` which uses the given slow start exit `settings`
+    pub(super) fn with_settings(max_datagram_size: u16, settings: Settings) -> Self {
         Self {
             sample_count: 0,
             last_min_rtt: None,
@@ -77,11 +131,16 @@ impl HybridSlowStart {
             threshold: f32::MAX,
             max_datagram_size,
             rtt_round_end_time: None,
-            use_hystart_plus_plus: Self::use_hystart_parameter(),
+            enabled: settings.exit != SlowStartExit::Disabled,
+            use_hystart_plus_plus: settings.exit == SlowStartExit::HyStartPlusPlus,
             ss_growth_divisor: 1.0,
             css_count: 0,
             css_baseline_min_rtt: Duration::ZERO,
             css_threshold: f32::MAX,
+            min_delay_threshold: settings.min_delay_threshold,
+            max_delay_threshold: settings.max_delay_threshold,
+            css_growth_divisor: settings.css_growth_divisor,
+            css_rounds: settings.css_rounds,
         }
     }
 
@@ -97,6 +156,11 @@ impl HybridSlowStart {
         time_of_last_sent_packet: Timestamp,
         rtt: Duration,
     ) {
+        if !self.enabled {
+            // the slow start threshold is only set by congestion events
+            return;
+        }
+
         let ss_threshold_found = self.threshold < f32::MAX;
         if congestion_window >= self.threshold || (self.use_hystart_plus_plus && ss_threshold_found)
         {
@@ -144,7 +208,7 @@ impl HybridSlowStart {
                     self.ss_growth_divisor = 1.0;
                     self.css_count = 0;
                 }
-                if self.css_count >= CSS_ROUNDS {
+                if self.css_count >= self.css_rounds {
                     // exit slow start phase
                     self.threshold = congestion_window;
                     self.css_threshold = f32::MAX;
@@ -153,7 +217,9 @@ impl HybridSlowStart {
             } else {
                 let threshold = last_min_rtt / THRESHOLD_DIVIDEND;
                 // Clamp n to the min and max thresholds
-                let threshold = threshold.min(MAX_DELAY_THRESHOLD).max(MIN_DELAY_THRESHOLD);
+                let threshold = threshold
+                    .min(self.max_delay_threshold)
+                    .max(self.min_delay_threshold);
                 let delay_increase_is_over_threshold = cur_min_rtt >= last_min_rtt + threshold;
                 let congestion_window_is_above_minimum = congestion_window >= self.low_ssthresh();
 
@@ -162,7 +228,7 @@ impl HybridSlowStart {
                     if delay_increase_is_over_threshold {
                         self.css_threshold = congestion_window;
                         self.css_baseline_min_rtt = cur_min_rtt;
-                        self.ss_growth_divisor = CSS_GROWTH_DIVISOR;
+                        self.ss_growth_divisor = self.css_growth_divisor;
                         self.css_count = 0;
                     }
                 } else if delay_increase_is_over_threshold && congestion_window_is_above_minimum {
@@ -212,7 +278,7 @@ impl HybridSlowStart {
 mod test {
     use crate::{
         assert_delta,
-        recovery::hybrid_slow_start::HybridSlowStart,
+        recovery::hybrid_slow_start::{HybridSlowStart, Settings, SlowStartExit},
         time::{Clock, NoopClock},
     };
     use core::time::Duration;
@@ -627,4 +693,60 @@ mod test {
         assert_delta!(slow_start.css_threshold, f32::MAX, 0.001);
         assert_eq!(slow_start.css_count, 0);
     }
+
+    /// Completes two rounds of samples, with the minimum RTT increasing from 100ms to 200ms
+    fn increase_delay(slow_start: &mut HybridSlowStart) {
+        let time_zero = NoopClock.get_time() + Duration::from_secs(10);
+
+        for (round, rtt) in [(0..8, 100), (8..16, 200)] {
+            let time_of_last_sent_packet = time_zero + Duration::from_millis(round.end);
+            for i in round {
+                slow_start.on_rtt_update(
+                    1000.0,
+                    time_zero + Duration::from_millis(i),
+                    time_of_last_sent_packet,
+                    Duration::from_millis(rtt),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn with_settings() {
+        let settings = |exit| Settings {
+            exit,
+            ..Settings::default()
+        };
+
+        // HyStart exits slow start once the delay has increased
+        let mut slow_start = HybridSlowStart::with_settings(10, settings(SlowStartExit::HyStart));
+        increase_delay(&mut slow_start);
+        assert_delta!(slow_start.threshold, 1000.0, 0.001);
+
+        // HyStart++ enters conservative slow start instead of exiting
+        let mut slow_start =
+            HybridSlowStart::with_settings(10, settings(SlowStartExit::HyStartPlusPlus));
+        increase_delay(&mut slow_start);
+        assert_delta!(slow_start.threshold, f32::MAX, 0.001);
+        assert_delta!(slow_start.css_threshold, 1000.0, 0.001);
+        assert_delta!(slow_start.ss_growth_divisor, 4.0, 0.001);
+
+        // Slow start is only exited on congestion events when disabled
+        let mut slow_start = HybridSlowStart::with_settings(10, settings(SlowStartExit::Disabled));
+        increase_delay(&mut slow_start);
+        assert_delta!(slow_start.threshold, f32::MAX, 0.001);
+        assert_eq!(slow_start.sample_count, 0);
+
+        // The delay increase is below the minimum delay threshold
+        let mut slow_start = HybridSlowStart::with_settings(
+            10,
+            Settings {
+                min_delay_threshold: Duration::from_millis(150),
+                max_delay_threshold: Duration::from_millis(200),
+                ..settings(SlowStartExit::HyStart)
+            },
+        );
+        increase_delay(&mut slow_start);
+        assert_delta!(slow_start.threshold, f32::MAX, 0.001);
+    }
 }
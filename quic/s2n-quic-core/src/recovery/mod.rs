@@ -4,6 +4,7 @@
 pub use congestion_controller::CongestionController;
 pub use cubic::CubicCongestionController;
 pub use rtt_estimator::*;
+pub use send_rate::SendRateLimiter;
 pub use sent_packets::*;
 
 pub mod bandwidth;
@@ -13,6 +14,7 @@ pub mod cubic;
 mod hybrid_slow_start;
 mod pacing;
 mod rtt_estimator;
+mod send_rate;
 mod sent_packets;
 
 #[cfg(test)]
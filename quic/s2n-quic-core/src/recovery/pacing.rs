@@ -4,7 +4,9 @@
 use crate::{
     counter::{Counter, Saturating},
     recovery::{
-        bandwidth::Bandwidth, congestion_controller::Publisher, RttEstimator, MAX_BURST_PACKETS,
+        bandwidth::Bandwidth,
+        congestion_controller::{PacingGain, Publisher},
+        RttEstimator, MAX_BURST_PACKETS,
     },
     time::{Duration, Timestamp},
 };
@@ -33,15 +35,39 @@ pub const INITIAL_INTERVAL: Duration = Duration::from_millis(0);
 pub const MINIMUM_PACING_RTT: Duration = Duration::from_millis(2);
 
 /// A packet pacer that returns departure times that evenly distribute bursts of packets over time
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Pacer {
     // The capacity of the current departure time slot
     capacity: Counter<u32, Saturating>,
     // The time the next packet should be transmitted
     next_packet_departure_time: Option<Timestamp>,
+    // The rate used to calculate the most recent departure time
+    pacing_rate: Option<Bandwidth>,
+    // The number of packets sent together in each departure time slot
+    max_burst_packets: u32,
+}
+
+impl Default for Pacer {
+    #[inline]
+    fn default() -> Self {
+        Self::new(MAX_BURST_PACKETS)
+    }
 }
 
 impl Pacer {
+    /// Creates a pacer which sends bursts of up to `max_burst_packets` packets
+    #[inline]
+    pub fn new(max_burst_packets: u32) -> Self {
+        debug_assert_ne!(max_burst_packets, 0);
+
+        Self {
+            capacity: Default::default(),
+            next_packet_departure_time: None,
+            pacing_rate: None,
+            max_burst_packets,
+        }
+    }
+
     /// Called when each packet has been written
     #[allow(clippy::too_many_arguments)]
     #[inline]
@@ -56,12 +82,13 @@ impl Pacer {
         publisher: &mut Pub,
     ) {
         if rtt_estimator.smoothed_rtt() < MINIMUM_PACING_RTT {
+            self.pacing_rate = None;
             return;
         }
 
         if self.capacity == 0 {
             if let Some(next_packet_departure_time) = self.next_packet_departure_time {
-                let interval = Self::interval(
+                let interval = self.interval(
                     rtt_estimator.smoothed_rtt(),
                     congestion_window,
                     max_datagram_size,
@@ -73,7 +100,7 @@ impl Pacer {
             } else {
                 self.next_packet_departure_time = Some(now + INITIAL_INTERVAL);
             }
-            self.capacity = Counter::new(MAX_BURST_PACKETS * max_datagram_size as u32);
+            self.capacity = Counter::new(self.burst_size(max_datagram_size));
         }
 
         self.capacity -= bytes_sent as u32;
@@ -86,9 +113,17 @@ impl Pacer {
         self.next_packet_departure_time
     }
 
+    /// Returns the rate packets are currently paced at
+    ///
+    /// This is `None` until the first interval between bursts has been calculated.
+    pub fn pacing_rate(&self) -> Option<Bandwidth> {
+        self.pacing_rate
+    }
+
     // Recalculate the interval between bursts of paced packets
     #[inline]
     fn interval<Pub: Publisher>(
+        &mut self,
         rtt: Duration,
         congestion_window: u32,
         max_datagram_size: u16,
@@ -109,14 +144,22 @@ impl Pacer {
         //# rate = N * congestion_window / smoothed_rtt
         let pacing_rate = Bandwidth::new(congestion_window as u64, rtt) * n;
 
-        // `MAX_BURST_PACKETS` is incorporated into the formula since we are trying to spread
+        // The burst size is incorporated into the formula since we are trying to spread
         // bursts of packets evenly over time.
-        let packet_size = MAX_BURST_PACKETS * max_datagram_size as u32;
+        let packet_size = self.burst_size(max_datagram_size);
 
-        publisher.on_pacing_rate_updated(pacing_rate, packet_size, n);
+        publisher.on_pacing_rate_updated(pacing_rate, packet_size, PacingGain::from_ratio(n));
+        self.pacing_rate = Some(pacing_rate);
 
         packet_size as u64 / pacing_rate
     }
+
+    // The number of bytes sent together in each departure time slot
+    #[inline]
+    fn burst_size(&self, max_datagram_size: u16) -> u32 {
+        self.max_burst_packets
+            .saturating_mul(max_datagram_size as u32)
+    }
 }
 
 #[cfg(test)]
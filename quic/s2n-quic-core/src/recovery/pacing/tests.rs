@@ -196,6 +196,7 @@ fn interval_change() {
     }
 
     let interval = get_interval(now, &mut pacer, &rtt, cwnd, MINIMUM_MTU, false);
+    let pacing_rate = pacer.pacing_rate().unwrap();
 
     cwnd += MINIMUM_MTU as u32;
     let new_interval = get_interval(now, &mut pacer, &rtt, cwnd, MINIMUM_MTU, false);
@@ -203,6 +204,7 @@ fn interval_change() {
     // Interval decreases after the congestion window increases, as more bursts need to be
     // distributed evenly across the same time period (1 rtt)
     assert!(new_interval < interval);
+    assert!(pacer.pacing_rate().unwrap() > pacing_rate);
 
     let interval = new_interval;
     rtt.update_rtt(
@@ -234,6 +236,31 @@ fn interval_change() {
     assert!(new_interval < interval);
 }
 
+#[test]
+fn max_burst_packets() {
+    let now = NoopClock.get_time();
+    let rtt = RttEstimator::default();
+    let cwnd = MINIMUM_MTU as u32 * 100;
+
+    let mut pacer = Pacer::default();
+    get_interval(now, &mut pacer, &rtt, cwnd, MINIMUM_MTU, false);
+    let interval = get_interval(now, &mut pacer, &rtt, cwnd, MINIMUM_MTU, false);
+    let pacing_rate = pacer.pacing_rate();
+
+    let mut small_burst_pacer = Pacer::new(2);
+    get_interval(now, &mut small_burst_pacer, &rtt, cwnd, MINIMUM_MTU, false);
+    let small_burst_interval =
+        get_interval(now, &mut small_burst_pacer, &rtt, cwnd, MINIMUM_MTU, false);
+
+    // Smaller bursts are sent more often at the same pacing rate
+    assert_eq!(pacing_rate, small_burst_pacer.pacing_rate());
+    assert!(
+        abs_difference(small_burst_interval * MAX_BURST_PACKETS, interval * 2)
+            < Duration::from_micros(10),
+        "{small_burst_interval:?} {interval:?}"
+    );
+}
+
 /// This test aims to compare the rate based implementation of pacing with the inter-packet interval
 /// based implementation of pacing described in RFC 9002. Due to rounding issues while multiplying
 /// and dividing, the two implementations do not match exactly, so this test asserts that the
@@ -253,7 +280,7 @@ fn interval_differential_test() {
             let mut publisher = event::testing::Publisher::no_snapshot();
             let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
             let rtt = Duration::from_nanos(rtt as _);
-            let actual = Pacer::interval(
+            let actual = Pacer::default().interval(
                 rtt,
                 congestion_window,
                 max_datagram_size,
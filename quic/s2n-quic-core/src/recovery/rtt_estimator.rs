@@ -43,6 +43,8 @@ pub struct RttEstimator {
     max_ack_delay: Duration,
     /// The time that the first RTT sample was obtained
     first_rtt_sample: Option<Timestamp>,
+    /// The RTT used before any RTT samples are available
+    initial_rtt: Duration,
 }
 
 impl Default for RttEstimator {
@@ -54,6 +56,14 @@ impl Default for RttEstimator {
 impl RttEstimator {
     /// Creates a new RTT Estimator with default initial values using the given `max_ack_delay`.
     pub fn new(max_ack_delay: Duration) -> Self {
+        Self::new_with_initial_rtt(max_ack_delay, DEFAULT_INITIAL_RTT)
+    }
+
+    /// Creates a new RTT Estimator using the given `max_ack_delay` and `initial_rtt`.
+    ///
+    /// The `initial_rtt` determines the PTO period until the first RTT sample is obtained,
+    /// which is typically during the handshake.
+    pub fn new_with_initial_rtt(max_ack_delay: Duration, initial_rtt: Duration) -> Self {
         //= https://www.rfc-editor.org/rfc/rfc9002#section-5.3
         //# Before any RTT samples are available for a new path or when the
         //# estimator is reset, the estimator is initialized using the initial RTT;
@@ -64,16 +74,17 @@ impl RttEstimator {
         //
         //# smoothed_rtt = kInitialRtt
         //# rttvar = kInitialRtt / 2
-        let smoothed_rtt = DEFAULT_INITIAL_RTT;
-        let rttvar = DEFAULT_INITIAL_RTT / 2;
+        let smoothed_rtt = initial_rtt;
+        let rttvar = initial_rtt / 2;
 
         Self {
-            latest_rtt: DEFAULT_INITIAL_RTT,
-            min_rtt: DEFAULT_INITIAL_RTT,
+            latest_rtt: initial_rtt,
+            min_rtt: initial_rtt,
             smoothed_rtt,
             rttvar,
             max_ack_delay,
             first_rtt_sample: None,
+            initial_rtt,
         }
     }
 
@@ -113,6 +124,12 @@ impl RttEstimator {
         self.max_ack_delay
     }
 
+    /// Gets the RTT used before any RTT samples are available
+    #[inline]
+    pub fn initial_rtt(&self) -> Duration {
+        self.initial_rtt
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
     //# The PTO period is the amount of time that a sender ought to wait for
     //# an acknowledgement of a sent packet.
@@ -364,6 +381,31 @@ mod test {
         );
     }
 
+    /// Test a configured initial RTT is used before any RTT samples
+    #[test]
+    fn configured_initial_rtt() {
+        let initial_rtt = Duration::from_millis(100);
+        let rtt_estimator =
+            RttEstimator::new_with_initial_rtt(Duration::from_millis(10), initial_rtt);
+        assert_eq!(rtt_estimator.initial_rtt(), initial_rtt);
+        assert_eq!(rtt_estimator.min_rtt, initial_rtt);
+        assert_eq!(rtt_estimator.latest_rtt(), initial_rtt);
+        assert_eq!(rtt_estimator.smoothed_rtt(), initial_rtt);
+        assert_eq!(rtt_estimator.rttvar(), initial_rtt / 2);
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::Initial),
+            Duration::from_millis(300)
+        );
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::Handshake),
+            Duration::from_millis(300)
+        );
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData),
+            Duration::from_millis(310)
+        );
+    }
+
     /// Test a zero RTT value is treated as 1 µs
     #[test]
     fn zero_rtt_sample() {
@@ -0,0 +1,176 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::time::{Duration, Timestamp};
+
+/// Limits the rate at which a connection sends datagrams
+///
+/// Unlike the pacing performed by the congestion controller, the limit is a hard cap which
+/// applies regardless of the congestion window. Each datagram reserves the amount of time it
+/// takes to send it at the maximum rate, and the next datagram may only depart once that time
+/// has passed. A burst allowance lets datagrams depart ahead of their reservation, up to the
+/// amount of time it takes to send the burst at the maximum rate.
+#[derive(Clone, Debug, Default)]
+pub struct SendRateLimiter {
+    /// The maximum number of bytes sent per second
+    max_send_rate: Option<u64>,
+    /// The number of bytes which can be sent ahead of the maximum rate
+    max_burst: u32,
+    /// The time the next datagram is allowed to depart
+    next_departure_time: Option<Timestamp>,
+}
+
+impl SendRateLimiter {
+    /// Sets the maximum number of bytes sent per second
+    ///
+    /// `None` removes the limit.
+    #[inline]
+    pub fn set_max_send_rate(&mut self, max_send_rate: Option<u64>) {
+        self.max_send_rate = max_send_rate.map(|rate| rate.max(1));
+
+        if self.max_send_rate.is_none() {
+            self.next_departure_time = None;
+        }
+    }
+
+    /// Sets the number of bytes which can be sent at once before the maximum rate applies
+    #[inline]
+    pub fn set_max_burst(&mut self, max_burst: u32) {
+        self.max_burst = max_burst;
+    }
+
+    /// Returns the maximum number of bytes sent per second, if configured
+    #[inline]
+    pub fn max_send_rate(&self) -> Option<u64> {
+        self.max_send_rate
+    }
+
+    /// Returns `true` if a datagram can be sent at the given time
+    #[inline]
+    pub fn can_transmit(&self, now: Timestamp) -> bool {
+        self.earliest_departure_time()
+            .map_or(true, |edt| edt.has_elapsed(now))
+    }
+
+    /// Returns the time the next datagram is allowed to depart, if the rate is limited
+    #[inline]
+    pub fn earliest_departure_time(&self) -> Option<Timestamp> {
+        let max_send_rate = self.max_send_rate?;
+        let next_departure_time = self.next_departure_time?;
+
+        // the burst allowance is taken off the reservations. If that goes past the start of the
+        // clock, the datagram can already depart.
+        next_departure_time.checked_sub(Self::interval(self.max_burst as usize, max_send_rate))
+    }
+
+    /// Called when a datagram of `len` bytes was sent
+    #[inline]
+    pub fn on_datagram_sent(&mut self, now: Timestamp, len: usize) {
+        let max_send_rate = if let Some(rate) = self.max_send_rate {
+            rate
+        } else {
+            return;
+        };
+
+        // Reservations start from the previous departure time, even if it was slightly in the
+        // future, so sending early doesn't allow exceeding the rate over time. Idle time
+        // doesn't accumulate into a burst, though.
+        let start = self.next_departure_time.map_or(now, |next| next.max(now));
+
+        self.next_departure_time = Some(start + Self::interval(len, max_send_rate));
+    }
+
+    /// Returns the amount of time it takes to send `len` bytes at `max_send_rate`
+    #[inline]
+    fn interval(len: usize, max_send_rate: u64) -> Duration {
+        let interval = (len as u128 * 1_000_000_000) / max_send_rate as u128;
+        Duration::from_nanos(interval.try_into().unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{Clock, NoopClock};
+
+    #[test]
+    fn unlimited_test() {
+        let mut limiter = SendRateLimiter::default();
+        let now = NoopClock.get_time();
+
+        limiter.on_datagram_sent(now, 1200);
+        assert!(limiter.can_transmit(now));
+        assert_eq!(limiter.earliest_departure_time(), None);
+    }
+
+    #[test]
+    fn limited_test() {
+        let mut limiter = SendRateLimiter::default();
+        let now = NoopClock.get_time();
+
+        // 100KB/s
+        limiter.set_max_send_rate(Some(100_000));
+        assert!(limiter.can_transmit(now));
+
+        // sending 1000 bytes takes 10ms
+        limiter.on_datagram_sent(now, 1000);
+        assert_eq!(
+            limiter.earliest_departure_time(),
+            Some(now + Duration::from_millis(10))
+        );
+        assert!(!limiter.can_transmit(now));
+        assert!(limiter.can_transmit(now + Duration::from_millis(10)));
+
+        // the next reservation starts at the previous departure time
+        limiter.on_datagram_sent(now + Duration::from_millis(9), 1000);
+        assert_eq!(
+            limiter.earliest_departure_time(),
+            Some(now + Duration::from_millis(20))
+        );
+
+        // idle time isn't accumulated
+        let later = now + Duration::from_secs(1);
+        limiter.on_datagram_sent(later, 1000);
+        assert_eq!(
+            limiter.earliest_departure_time(),
+            Some(later + Duration::from_millis(10))
+        );
+
+        // removing the limit unblocks transmission
+        limiter.set_max_send_rate(None);
+        assert!(limiter.can_transmit(later));
+        assert_eq!(limiter.earliest_departure_time(), None);
+    }
+
+    #[test]
+    fn burst_test() {
+        let mut limiter = SendRateLimiter::default();
+        let now = NoopClock.get_time() + Duration::from_secs(1);
+
+        // 100KB/s with a 3000 byte burst
+        limiter.set_max_send_rate(Some(100_000));
+        limiter.set_max_burst(3000);
+
+        // the burst is sent ahead of the reservations, in addition to the datagram which departs
+        // at the current time
+        for _ in 0..4 {
+            assert!(limiter.can_transmit(now));
+            limiter.on_datagram_sent(now, 1000);
+        }
+
+        // the next datagram has to wait
+        assert!(!limiter.can_transmit(now));
+        assert_eq!(
+            limiter.earliest_departure_time(),
+            Some(now + Duration::from_millis(10))
+        );
+
+        // after being idle, the full burst is available again
+        let later = now + Duration::from_secs(1);
+        for _ in 0..4 {
+            assert!(limiter.can_transmit(later));
+            limiter.on_datagram_sent(later, 1000);
+        }
+        assert!(!limiter.can_transmit(later));
+    }
+}
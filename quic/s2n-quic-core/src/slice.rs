@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use core::ops::{Deref, DerefMut};
+use core::{
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+};
 
 /// Copies vectored slices from one slice into another
 ///
@@ -88,6 +91,40 @@ where
     count
 }
 
+/// Copies vectored slices into an uninitialized slice
+///
+/// The number of copied items is limited by the length of `to`.
+///
+/// Returns the number of items that were copied. Only the items in `to[..count]` are
+/// initialized by the call.
+pub fn vectored_copy_uninit<A, T>(from: &[A], to: &mut [MaybeUninit<T>]) -> usize
+where
+    A: Deref<Target = [T]>,
+    T: Copy,
+{
+    let mut count = 0;
+
+    for from in from {
+        let to = &mut to[count..];
+
+        if to.is_empty() {
+            break;
+        }
+
+        let len = from.len().min(to.len());
+
+        unsafe {
+            // Safety: `len` never exceeds either slice's length and `MaybeUninit<T>` has the
+            //         same layout as `T`. The regions can't overlap since `to` is borrowed mutably.
+            core::ptr::copy_nonoverlapping(from.as_ptr(), to.as_mut_ptr() as *mut T, len);
+        }
+
+        count += len;
+    }
+
+    count
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +166,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn vectored_copy_uninit_test() {
+        let from = [&[0][..], &[1, 2, 3][..], &[][..], &[4, 5, 6, 7][..]];
+
+        for len in 0..10 {
+            let mut to = vec![MaybeUninit::<u8>::uninit(); len];
+            let copied_len = vectored_copy_uninit(&from, &mut to);
+            assert_eq!(copied_len, len.min(8));
+
+            // only read the items which were initialized by the copy
+            let to: Vec<u8> = to[..copied_len]
+                .iter()
+                .map(|v| unsafe { v.assume_init() })
+                .collect();
+            assert_eq_slices(&from, &[&to[..]]);
+        }
+    }
+
     const LEN: usize = if cfg!(kani) { 2 } else { 32 };
 
     #[test]
@@ -3,7 +3,10 @@
 
 //! Defines the Stateless Reset token
 
-use core::convert::{TryFrom, TryInto};
+use core::{
+    convert::{TryFrom, TryInto},
+    time::Duration,
+};
 use s2n_codec::{decoder_value, Encoder, EncoderValue};
 use subtle::ConstantTimeEq;
 
@@ -106,6 +109,80 @@ pub trait Generator: 'static + Send {
     /// To enable stateless reset functionality, the stateless reset token must
     /// be generated the same for a given `local_connection_id` before and after loss of state.
     fn generate(&mut self, local_connection_id: &[u8]) -> Token;
+
+    /// The amount of time after the endpoint starts during which no stateless resets are sent
+    ///
+    /// After a restart, peers of connections from the previous run may still be sending packets
+    /// to the endpoint. Staying quiet for a period of time avoids answering those packets with
+    /// stateless resets before the peers have had a chance to time out on their own.
+    #[inline]
+    fn quiet_time(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+const BOOT_ID_LEN: usize = core::mem::size_of::<u64>();
+
+/// Incorporates a per-boot identifier into the tokens of a [`Generator`]
+///
+/// Endpoints which restart with the same address and stateless reset key would otherwise derive
+/// the same tokens as the previous run. Mixing in an identifier which changes on every boot means
+/// the tokens of connections from a previous run won't be reproduced, which prevents incorrectly
+/// resetting connections which happen to reuse a connection ID. Note that peers of connections
+/// from a previous run won't recognize stateless resets sent by the new run, so those connections
+/// will close once their idle timer expires instead.
+#[derive(Debug, Default)]
+pub struct PerBoot<G> {
+    generator: G,
+    boot_id: u64,
+    quiet_time: Duration,
+}
+
+impl<G: Generator> PerBoot<G> {
+    /// Wraps `generator` with a boot id of `0` and no quiet time
+    pub fn new(generator: G) -> Self {
+        Self {
+            generator,
+            boot_id: 0,
+            quiet_time: Duration::ZERO,
+        }
+    }
+
+    /// Sets the identifier of the current boot
+    ///
+    /// The value should be unique for each run of the endpoint, e.g. a random value or a counter
+    /// which is persisted across restarts.
+    pub fn with_boot_id(mut self, boot_id: u64) -> Self {
+        self.boot_id = boot_id;
+        self
+    }
+
+    /// Sets the amount of time after the endpoint starts during which no stateless resets are
+    /// sent
+    pub fn with_quiet_time(mut self, quiet_time: Duration) -> Self {
+        self.quiet_time = quiet_time;
+        self
+    }
+}
+
+impl<G: Generator> Generator for PerBoot<G> {
+    const ENABLED: bool = G::ENABLED;
+
+    #[inline]
+    fn generate(&mut self, local_connection_id: &[u8]) -> Token {
+        let mut input = [0; BOOT_ID_LEN + crate::connection::id::MAX_LEN];
+        let len = (BOOT_ID_LEN + local_connection_id.len()).min(input.len());
+
+        input[..BOOT_ID_LEN].copy_from_slice(&self.boot_id.to_be_bytes());
+        input[BOOT_ID_LEN..len].copy_from_slice(&local_connection_id[..len - BOOT_ID_LEN]);
+
+        self.generator.generate(&input[..len])
+    }
+
+    #[inline]
+    fn quiet_time(&self) -> Duration {
+        self.quiet_time
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -161,4 +238,44 @@ mod tests {
             assert_ne!(TEST_TOKEN_1, token);
         }
     }
+
+    #[test]
+    fn per_boot_test() {
+        use super::{Generator, PerBoot, Token};
+        use core::time::Duration;
+
+        /// Folds the whole input into the token so longer inputs can be used
+        #[derive(Debug, Default)]
+        struct Folding;
+
+        impl Generator for Folding {
+            fn generate(&mut self, input: &[u8]) -> Token {
+                let mut token = [0; LEN];
+                for (index, byte) in input.iter().enumerate() {
+                    token[index % LEN] ^= byte.rotate_left(index as u32);
+                }
+                token.into()
+            }
+        }
+
+        let connection_id = [1; 16];
+
+        let mut first = PerBoot::new(Folding).with_boot_id(1);
+        let mut restarted = PerBoot::new(Folding).with_boot_id(2);
+
+        // tokens are stable within a single boot
+        assert_eq!(
+            first.generate(&connection_id),
+            first.generate(&connection_id)
+        );
+        // tokens from a previous boot aren't reproduced
+        assert_ne!(
+            first.generate(&connection_id),
+            restarted.generate(&connection_id)
+        );
+
+        assert_eq!(first.quiet_time(), Duration::ZERO);
+        let restarted = restarted.with_quiet_time(Duration::from_secs(3));
+        assert_eq!(restarted.quiet_time(), Duration::from_secs(3));
+    }
 }
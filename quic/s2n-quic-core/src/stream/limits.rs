@@ -29,6 +29,11 @@ pub struct Limits {
     /// is not communicated to the peer, it is only used for limiting
     /// concurrent streams opened locally by the application.
     pub max_open_local_bidirectional_streams: LocalBidirectional,
+    /// Controls the limit of streams opened by the peer once the peer opens all of the
+    /// streams it is allowed to
+    pub remote_stream_limit_policy: StreamLimitPolicy,
+    /// The maximum number of gaps in the data received on a Stream
+    pub max_receive_gaps: usize,
 }
 
 impl Default for Limits {
@@ -42,9 +47,35 @@ impl Limits {
         max_send_buffer_size: MaxSendBufferSize::RECOMMENDED,
         max_open_local_unidirectional_streams: LocalUnidirectional::RECOMMENDED,
         max_open_local_bidirectional_streams: LocalBidirectional::RECOMMENDED,
+        remote_stream_limit_policy: StreamLimitPolicy::Fixed,
+        max_receive_gaps: usize::MAX,
     };
 }
 
+/// Controls how the limit of streams opened by the peer is managed once all of the allowed
+/// streams are in use
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StreamLimitPolicy {
+    /// The peer is held at the configured number of concurrent streams
+    ///
+    /// Additional credit is sent in MAX_STREAMS frames as the peer closes streams.
+    #[default]
+    Fixed,
+
+    /// The number of concurrent streams grows up to the given ceiling
+    ///
+    /// Each time the peer uses all of its streams, the limit is doubled and advertised in a
+    /// MAX_STREAMS frame without waiting for streams to close.
+    Grow(u64),
+
+    /// No additional credit is sent as the peer closes streams
+    ///
+    /// The peer is refused any streams beyond the configured limit over the lifetime of the
+    /// connection.
+    Reject,
+}
+
 macro_rules! local_limits {
     ($name:ident($encodable_type:ty)) => {
         #[derive(Debug, Copy, Clone, PartialEq, Eq)]
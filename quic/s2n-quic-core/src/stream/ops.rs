@@ -29,7 +29,7 @@
 //! ```
 
 use crate::{application, stream};
-use core::task::Poll;
+use core::{task::Poll, time::Duration};
 
 /// A request made on a stream
 #[derive(Default, Debug)]
@@ -113,6 +113,40 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Resets the tx stream with an error code if it doesn't make any progress for the
+    /// provided `timeout`
+    pub fn with_tx_inactivity_timeout(
+        &mut self,
+        timeout: Duration,
+        error: application::Error,
+    ) -> &mut Self {
+        self.tx_mut().inactivity_timeout = Some((timeout, error));
+        self
+    }
+
+    /// Requests the peer to stop sending data on the rx stream with an error code if it doesn't
+    /// make any progress for the provided `timeout`
+    pub fn with_rx_inactivity_timeout(
+        &mut self,
+        timeout: Duration,
+        error: application::Error,
+    ) -> &mut Self {
+        self.rx_mut().inactivity_timeout = Some((timeout, error));
+        self
+    }
+
+    /// Sets the transmission priority of the tx stream
+    pub fn with_priority(&mut self, priority: stream::scheduler::Priority) -> &mut Self {
+        self.tx_mut().priority = Some(priority);
+        self
+    }
+
+    /// Sets the DSCP code point which marks the packets carrying the tx stream's data
+    pub fn with_dscp(&mut self, dscp: u8) -> &mut Self {
+        self.tx_mut().dscp = Some(dscp);
+        self
+    }
+
     pub fn detach_tx(&mut self) -> &mut Self {
         let tx = self.tx_mut();
         tx.detached = true;
@@ -193,6 +227,16 @@ pub mod tx {
         /// Marks the tx stream as finished (e.g. no more data will be sent)
         pub finish: bool,
 
+        /// Optionally resets the stream with an error if it is inactive for the given duration
+        pub inactivity_timeout: Option<(Duration, application::Error)>,
+
+        /// Optionally changes the transmission priority of the stream
+        pub priority: Option<stream::scheduler::Priority>,
+
+        /// Optionally changes the DSCP code point which marks the packets carrying the stream's
+        /// data
+        pub dscp: Option<u8>,
+
         /// Marks the tx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
@@ -213,6 +257,9 @@ pub mod tx {
 
         /// The current status of the stream
         pub status: Status,
+
+        /// The number of bytes at the start of the stream which were acknowledged by the peer
+        pub acked_offset: u64,
     }
 
     impl Default for Response {
@@ -222,6 +269,7 @@ pub mod tx {
                 chunks: Chunks::default(),
                 will_wake: false,
                 status: Status::Open,
+                acked_offset: 0,
             }
         }
     }
@@ -271,6 +319,10 @@ pub mod rx {
         /// Optionally requests the peer to stop sending data with an error
         pub stop_sending: Option<application::Error>,
 
+        /// Optionally requests the peer to stop sending data with an error if the stream is
+        /// inactive for the given duration
+        pub inactivity_timeout: Option<(Duration, application::Error)>,
+
         /// Marks the rx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
@@ -283,6 +335,7 @@ pub mod rx {
                 low_watermark: 0,
                 high_watermark: core::usize::MAX,
                 stop_sending: None,
+                inactivity_timeout: None,
                 detached: false,
             }
         }
@@ -511,6 +564,9 @@ mod tests {
                     finish: true,
                     flush: true,
                     reset: Some(reset),
+                    inactivity_timeout: None,
+                    priority: None,
+                    dscp: None,
                     detached: false,
                 }),
                 rx: Some(rx::Request {
@@ -518,6 +574,7 @@ mod tests {
                     low_watermark: 5,
                     high_watermark: 10,
                     stop_sending: Some(stop_sending),
+                    inactivity_timeout: None,
                     detached: false,
                 })
             } if reset == application::Error::new(1).unwrap()
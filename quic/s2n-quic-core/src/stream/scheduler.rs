@@ -0,0 +1,253 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Strategies for choosing which stream transmits next when multiple streams have data to send
+//!
+//! Streams with pending data are kept in a transmission queue. Each time a packet is assembled,
+//! the streams are visited in the order of the queue and write as much data as they can. A
+//! [`Scheduler`] decides where a stream is placed in the queue after it transmitted.
+
+use core::fmt;
+
+/// The default urgency of a stream
+///
+/// This matches the default urgency of HTTP priorities.
+///
+/// See <https://www.rfc-editor.org/rfc/rfc9218#section-4.1>
+const DEFAULT_URGENCY: u8 = 3;
+
+/// The number of bytes a stream with a weight of 1 can transmit in each round of the
+/// [`WeightedFair`] scheduler
+const WEIGHT_QUANTUM: usize = 1200;
+
+/// The transmission priority of a stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Priority {
+    urgency: u8,
+    weight: u8,
+}
+
+impl Default for Priority {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            urgency: DEFAULT_URGENCY,
+            weight: 1,
+        }
+    }
+}
+
+impl Priority {
+    /// Creates a new priority with the given urgency and weight
+    ///
+    /// A weight of `0` is treated as `1`.
+    #[inline]
+    pub const fn new(urgency: u8, weight: u8) -> Self {
+        let weight = if weight == 0 { 1 } else { weight };
+        Self { urgency, weight }
+    }
+
+    /// Returns the urgency of the stream
+    ///
+    /// The [`StrictPriority`] scheduler transmits streams with a lower urgency before streams
+    /// with a higher urgency.
+    #[inline]
+    pub const fn urgency(&self) -> u8 {
+        self.urgency
+    }
+
+    /// Returns the weight of the stream
+    ///
+    /// The [`WeightedFair`] scheduler shares the available bandwidth between streams in
+    /// proportion to their weights.
+    #[inline]
+    pub const fn weight(&self) -> u8 {
+        self.weight
+    }
+}
+
+/// The scheduling state of a stream
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct State {
+    /// The number of bytes the stream can still transmit before giving up its turn
+    ///
+    /// This value is only used by schedulers which limit the amount of data a stream
+    /// transmits in each round.
+    pub credits: i64,
+}
+
+/// Decides the order in which streams with pending data are transmitted
+///
+/// Schedulers are shared by all of the connections which use them, so they can't hold any
+/// per-connection or per-stream state. Any state needed for a stream is stored in its
+/// [`State`].
+pub trait Scheduler: 'static + Send + Sync + fmt::Debug {
+    /// Returns the rank of a stream with the given priority
+    ///
+    /// Streams with a lower rank always transmit before streams with a higher rank. Streams
+    /// with the same rank take turns.
+    #[inline]
+    fn rank(&self, priority: &Priority) -> u8 {
+        let _ = priority;
+        0
+    }
+
+    /// Called when the stream is about to transmit
+    ///
+    /// Returns `false` if the stream should give up its turn without transmitting and move
+    /// behind the other streams of the same rank. Streams are visited again if all of them gave
+    /// up their turn, so this needs to eventually return `true`.
+    #[inline]
+    fn on_turn(&self, priority: &Priority, state: &mut State) -> bool {
+        let _ = priority;
+        let _ = state;
+        true
+    }
+
+    /// Called after the stream transmitted `len` bytes in a packet
+    ///
+    /// Returns `true` if the stream should give up its turn and move behind the other streams
+    /// of the same rank.
+    fn on_transmit(&self, priority: &Priority, state: &mut State, len: usize) -> bool;
+}
+
+/// Streams take turns transmitting a packet of data, regardless of their priority
+///
+/// This is the default scheduler.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoundRobin;
+
+impl Scheduler for RoundRobin {
+    #[inline]
+    fn on_transmit(&self, _priority: &Priority, _state: &mut State, _len: usize) -> bool {
+        true
+    }
+}
+
+/// Streams with a lower urgency always transmit before streams with a higher urgency
+///
+/// Streams with the same urgency take turns transmitting a packet of data.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StrictPriority;
+
+impl Scheduler for StrictPriority {
+    #[inline]
+    fn rank(&self, priority: &Priority) -> u8 {
+        priority.urgency()
+    }
+
+    #[inline]
+    fn on_transmit(&self, _priority: &Priority, _state: &mut State, _len: usize) -> bool {
+        true
+    }
+}
+
+/// Streams share the available bandwidth in proportion to their weights
+///
+/// Each round, a stream can transmit about `weight * 1200` bytes before giving up its turn.
+/// Streams which transmit more than their share in a round have the difference deducted from
+/// their next round.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WeightedFair;
+
+impl Scheduler for WeightedFair {
+    #[inline]
+    fn on_turn(&self, priority: &Priority, state: &mut State) -> bool {
+        // start a new round for the stream if it used all of its credits
+        if state.credits <= 0 {
+            let quantum = priority.weight() as usize * WEIGHT_QUANTUM;
+            state.credits = state.credits.saturating_add(quantum as i64);
+        }
+
+        // streams which are still paying off a previous round wait for the next one
+        state.credits > 0
+    }
+
+    #[inline]
+    fn on_transmit(&self, _priority: &Priority, state: &mut State, len: usize) -> bool {
+        state.credits = state
+            .credits
+            .saturating_sub(len.try_into().unwrap_or(i64::MAX));
+        state.credits <= 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Transmits `count` packets of `packet_len` bytes each and returns the number of packets
+    /// each stream transmitted
+    fn transmit<S: Scheduler, const N: usize>(
+        scheduler: &S,
+        priorities: [Priority; N],
+        packet_len: usize,
+        count: usize,
+    ) -> [usize; N] {
+        let mut queue: Vec<(usize, State)> = (0..N).map(|idx| (idx, State::default())).collect();
+        let mut packets = [0; N];
+
+        while packets.iter().sum::<usize>() < count {
+            // find the first stream with the lowest rank
+            let position = (0..queue.len())
+                .min_by_key(|idx| scheduler.rank(&priorities[queue[*idx].0]))
+                .unwrap();
+            let (idx, mut state) = queue.remove(position);
+            let priority = &priorities[idx];
+
+            if !scheduler.on_turn(priority, &mut state) {
+                queue.push((idx, state));
+                continue;
+            }
+
+            packets[idx] += 1;
+
+            if scheduler.on_transmit(priority, &mut state, packet_len) {
+                queue.push((idx, state));
+            } else {
+                queue.insert(position, (idx, state));
+            }
+        }
+
+        packets
+    }
+
+    #[test]
+    fn round_robin_test() {
+        let priorities = [
+            Priority::new(0, 1),
+            Priority::new(7, 10),
+            Priority::default(),
+        ];
+        assert_eq!(transmit(&RoundRobin, priorities, 1200, 30), [10, 10, 10]);
+    }
+
+    #[test]
+    fn strict_priority_test() {
+        let priorities = [
+            Priority::new(5, 1),
+            Priority::new(1, 1),
+            Priority::new(1, 1),
+        ];
+        assert_eq!(transmit(&StrictPriority, priorities, 1200, 30), [0, 15, 15]);
+    }
+
+    #[test]
+    fn weighted_fair_test() {
+        let priorities = [
+            Priority::new(0, 1),
+            Priority::new(0, 2),
+            Priority::new(0, 3),
+        ];
+        assert_eq!(transmit(&WeightedFair, priorities, 1200, 60), [10, 20, 30]);
+
+        // packets larger than the quantum are deducted from the next round
+        let priorities = [Priority::new(0, 1), Priority::new(0, 2)];
+        assert_eq!(transmit(&WeightedFair, priorities, 2400, 30), [10, 20]);
+    }
+
+    #[test]
+    fn zero_weight_test() {
+        assert_eq!(Priority::new(0, 0).weight(), 1);
+    }
+}
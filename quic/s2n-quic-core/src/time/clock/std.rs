@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use ::std::time::Instant;
+use ::std::time::{Instant, SystemTime};
 
 impl<C: 'static + Clock> Clock for &'static ::std::thread::LocalKey<C> {
     fn get_time(&self) -> Timestamp {
@@ -36,6 +36,27 @@ impl Clock for StdClock {
     }
 }
 
+/// A source of calendar time
+///
+/// Timers are driven by the endpoint's [`Clock`]. A `WallClock` is only read by components
+/// which persist or report absolute times, such as session ticket expirations and the
+/// timestamps in captures and logs, so simulations can control those as well.
+pub trait WallClock: 'static + Send + Sync {
+    /// Returns the current calendar time
+    fn now(&self) -> SystemTime;
+}
+
+/// A [`WallClock`] which reads the system time
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl WallClock for SystemClock {
+    #[inline]
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
 #[test]
 #[cfg_attr(miri, ignore)] // time isn't queryable in miri
 fn monotonicity_test() {
@@ -5,6 +5,7 @@ use crate::{
     connection,
     event::{api::SocketAddress, IntoEvent},
     inet, random,
+    time::Timestamp,
 };
 
 #[non_exhaustive]
@@ -12,6 +13,11 @@ pub struct Context<'a> {
     pub remote_address: SocketAddress<'a>,
     pub peer_connection_id: &'a [u8],
     pub random: &'a mut dyn random::Generator,
+    /// The current time, as reported by the endpoint's clock
+    ///
+    /// Token formats should use this value instead of reading a clock directly so all
+    /// timing within the endpoint is driven by the configured IO provider.
+    pub now: Timestamp,
 }
 
 impl<'a> Context<'a> {
@@ -21,11 +27,13 @@ impl<'a> Context<'a> {
         remote_address: &'a inet::SocketAddress,
         peer_connection_id: &'a connection::PeerId,
         random: &'a mut dyn random::Generator,
+        now: Timestamp,
     ) -> Self {
         Self {
             remote_address: remote_address.into_event(),
             peer_connection_id: peer_connection_id.as_bytes(),
             random,
+            now,
         }
     }
 }
@@ -58,6 +66,26 @@ pub trait Format: 'static + Send {
         context: &mut Context<'_>,
         token: &[u8],
     ) -> Option<connection::InitialId>;
+
+    /// Return how the token was delivered to the client.
+    ///
+    /// Tokens from a Retry packet are validated with `validate_token` and tokens from a
+    /// NEW_TOKEN frame are validated with `validate_new_token`. Formats which don't support
+    /// NEW_TOKEN frames don't need to implement this function.
+    #[inline]
+    fn token_source(&self, token: &[u8]) -> Source {
+        let _ = token;
+        Source::RetryPacket
+    }
+
+    /// Return true if the token delivered in a NEW_TOKEN frame is valid for the client.
+    /// Callers should detect duplicate tokens and treat them as invalid.
+    #[inline]
+    fn validate_new_token(&mut self, context: &mut Context<'_>, token: &[u8]) -> bool {
+        let _ = context;
+        let _ = token;
+        false
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
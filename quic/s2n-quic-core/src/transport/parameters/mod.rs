@@ -1443,7 +1443,7 @@ impl<
         }
 
         load!(max_idle_timeout, max_idle_timeout);
-        load!(data_window, initial_max_data);
+        self.initial_max_data = limits.bounded_data_window();
         load!(
             bidirectional_local_data_window,
             initial_max_stream_data_bidi_local
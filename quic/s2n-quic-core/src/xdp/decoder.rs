@@ -84,6 +84,7 @@ impl<P: EventHandler> EventHandler for datagram::Header<P> {
     fn on_ipv4_header(&mut self, header: &ipv4::Header) -> Result {
         self.path.on_ipv4_header(header)?;
         self.ecn = header.tos().ecn();
+        self.ttl = Some(*header.ttl());
         Ok(Some(()))
     }
 
@@ -91,6 +92,7 @@ impl<P: EventHandler> EventHandler for datagram::Header<P> {
     fn on_ipv6_header(&mut self, header: &ipv6::Header) -> Result {
         self.path.on_ipv6_header(header)?;
         self.ecn = header.vtcfl().ecn();
+        self.ttl = Some(*header.hop_limit());
         Ok(Some(()))
     }
 
@@ -108,6 +110,8 @@ pub fn decode_packet<'a, D: Decoder<'a>>(
     let mut header = datagram::Header {
         path: path::Tuple::UNSPECIFIED,
         ecn: Default::default(),
+        ttl: None,
+        receive_delay: None,
     };
     match decode_packet_with_event(buffer, &mut header)? {
         Some(buffer) => Ok(Some((header, buffer))),
@@ -124,7 +124,10 @@ fn encode_ipv4<M: Message<Handle = path::Tuple>>(
 
     buffer.write_zerocopy(|header: &mut ipv4::Header| {
         header.vihl_mut().set_version(4).set_header_len(5);
-        header.tos_mut().set_dscp(0).set_ecn(message.ecn());
+        header
+            .tos_mut()
+            .set_dscp(message.dscp())
+            .set_ecn(message.ecn());
         header
             .flag_fragment_mut()
             .set_reserved(false)
@@ -201,7 +204,7 @@ fn encode_ipv6<M: Message<Handle = path::Tuple>>(
         header
             .vtcfl_mut()
             .set_version(6)
-            .set_dscp(0)
+            .set_dscp(message.dscp())
             .set_ecn(message.ecn())
             .set_flow_label(message.ipv6_flow_label());
         header.payload_len_mut().set(payload_len);
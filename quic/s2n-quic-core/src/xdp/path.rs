@@ -91,6 +91,17 @@ impl Handle for Tuple {
         }
     }
 
+    #[inline]
+    fn from_addresses(
+        local_address: path::LocalAddress,
+        remote_address: path::RemoteAddress,
+    ) -> Self {
+        Self {
+            remote_address: remote_address.into(),
+            local_address: local_address.into(),
+        }
+    }
+
     #[inline]
     fn remote_address(&self) -> path::RemoteAddress {
         self.remote_address.into()
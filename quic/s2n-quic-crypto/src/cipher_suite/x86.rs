@@ -44,6 +44,9 @@ macro_rules! aesgcm {
 
                 pub fn should_update_pmtu(&self, mtu: u16) -> bool {
                     match self {
+                        // the precomputed key is implemented outside of the FIPS module so never
+                        // switch to it and keep using the AWS-LC key
+                        _ if cfg!(feature = "fips") => false,
                         // if the precomputed key isn't supported, do nothing
                         _ if !Avx2::is_supported() => false,
                         // if we've already precomputed a larger key, do nothing
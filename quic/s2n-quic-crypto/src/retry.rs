@@ -50,7 +50,7 @@ mod tests {
         crypto::{retry, RetryKey as _},
         inet, packet,
         packet::number::{PacketNumberSpace, TruncatedPacketNumber},
-        random, token,
+        random, time, token,
         varint::VarInt,
     };
 
@@ -107,6 +107,7 @@ mod tests {
                 &local_conn_id,
                 &mut random::testing::Generator(5),
                 &mut token_format,
+                time::clock::testing::now(),
                 &mut output_buf,
             ) {
                 assert_eq!(&output_buf[range], &retry::example::PACKET[..]);
@@ -157,6 +158,7 @@ mod tests {
                 &local_conn_id,
                 &mut random::testing::Generator(5),
                 &mut token_format,
+                time::clock::testing::now(),
                 &mut output_buf,
             )
             .is_none());
@@ -262,10 +262,11 @@ enum DuplicatePacketError {
 
     /// The received packet number was outside the range of tracked packet numbers.
     ///
-    /// This can happen when packets are heavily delayed or reordered. Currently, the maximum
+    /// This can happen when packets are heavily delayed or reordered. By default, the maximum
     /// amount of reordering is limited to 128 packets. For example, if packet number `142`
     /// is received, the allowed range would be limited to `14-142`. If an endpoint received
-    /// packet `< 14`, it would trigger this event.
+    /// packet `< 14`, it would trigger this event. The limit can be reduced with
+    /// `connection::Limits::with_replay_window`.
     TooOld,
 }
 
@@ -709,6 +710,8 @@ enum DatagramDropReason {
     PathLimitExceeded,
     /// The peer initiated a connection migration without supplying enough connection IDs to use.
     InsufficientConnectionIds,
+    /// The amount of received data waiting to be processed exceeded the configured limit.
+    ReceiveBufferLimitExceeded,
 }
 
 enum KeySpace {
@@ -802,11 +805,27 @@ enum EcnState {
     /// ECN capability has been tested, but not validated yet
     Unknown,
     /// ECN capability testing has failed validation
-    Failed,
+    Failed { reason: EcnFailureReason },
     /// ECN capability has been confirmed
     Capable,
 }
 
+/// The reason ECN validation failed for the path
+enum EcnFailureReason {
+    /// ECN marked packets were acknowledged without ECN counts in the ACK frame
+    CountsMissing,
+    /// The ECN counts in the ACK frame decreased
+    CountsDecreased,
+    /// Fewer ECT(0) and ECN-CE markings were reported than ECT(0) marked packets were acknowledged
+    Ect0Underreported,
+    /// More ECT(0) or ECT(1) markings were reported than packets were sent with them
+    EctOverreported,
+    /// A packet sent with an ECN-CE marking was not reported as CE marked
+    CeSuppressed,
+    /// ECN marked packets were repeatedly lost, indicating the path drops them
+    BlackHole,
+}
+
 /// Events tracking the progress of handshake status
 enum HandshakeStatus {
     /// The handshake has completed.
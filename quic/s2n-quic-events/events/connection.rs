@@ -95,6 +95,8 @@ struct RecoveryMetrics<'a> {
     congestion_window: u32,
     bytes_in_flight: u32,
     congestion_limited: bool,
+    /// Whether `latest_rtt` was updated with a new RTT sample from the ACK which triggered the metrics
+    rtt_sampled: bool,
 }
 
 #[event("recovery:congestion")]
@@ -213,6 +215,8 @@ struct DatagramSent {
 /// Datagram received by a connection
 struct DatagramReceived {
     len: u16,
+    /// The IPv4 TTL or IPv6 hop limit of the datagram, if reported by the platform
+    ttl: Option<u8>,
 }
 
 #[event("transport:datagram_dropped")]
@@ -323,3 +327,10 @@ struct BbrStateChanged {
     path_id: u64,
     state: BbrState,
 }
+
+#[event("transport:connection_memory_pressure")]
+/// The amount of stream data buffered by the connection is approaching the configured maximum
+struct ConnectionMemoryPressure {
+    buffered_bytes: usize,
+    max_buffered_bytes: usize,
+}
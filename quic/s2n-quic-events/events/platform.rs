@@ -47,6 +47,15 @@ impl From<PlatformRxError> for std::io::Error {
     }
 }
 
+#[event("platform:rx_socket_drops")]
+#[subject(endpoint)]
+/// Emitted when the platform reports datagrams which were dropped by the socket before the
+/// endpoint could receive them, e.g. because the socket's receive buffer was full
+struct PlatformRxSocketDrops {
+    /// The number of datagrams dropped since the previous report
+    count: usize,
+}
+
 #[event("platform:feature_configured")]
 #[subject(endpoint)]
 /// Emitted when a platform feature is configured
@@ -68,6 +77,14 @@ enum PlatformFeatureConfiguration {
     Ecn { enabled: bool },
     /// Emitted when the maximum transmission unit is configured
     MaxMtu { mtu: u16 },
+    /// Emitted when the size of the socket's receive buffer is configured
+    ///
+    /// The size is the value reported by the platform, which may differ from the requested size.
+    RecvBufferSize { size: usize },
+    /// Emitted when the size of the socket's send buffer is configured
+    ///
+    /// The size is the value reported by the platform, which may differ from the requested size.
+    SendBufferSize { size: usize },
 }
 
 #[event("platform:event_loop_wakeup")]
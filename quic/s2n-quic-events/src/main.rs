@@ -99,7 +99,7 @@ impl ToTokens for Output {
                                 self.server.id()
                             }
                         };
-                        tracing::span!(target: "s2n_quic", parent: parent, tracing::Level::DEBUG, "conn", id = meta.id)
+                        tracing::span!(target: "s2n_quic", parent: parent, tracing::Level::DEBUG, "conn", id = meta.id, cid = tracing::field::Empty)
                     }
 
                     #tracing_subscriber
@@ -300,6 +300,13 @@ impl ToTokens for Output {
                         supervisor::Outcome::default()
                     }
 
+                    /// Sets the clock which calendar times, such as the reference time of a trace, are read from
+                    ///
+                    /// This is called with the endpoint's wall clock before any connections are created.
+                    #[cfg(feature = "std")]
+                    #[allow(unused_variables)]
+                    fn set_wall_clock(&mut self, clock: &std::sync::Arc<dyn crate::time::WallClock>) {}
+
                     #subscriber
 
                     /// Called for each event that relates to the endpoint and all connections
@@ -344,6 +351,13 @@ impl ToTokens for Output {
                         (self.0.create_connection_context(meta, info), self.1.create_connection_context(meta, info))
                     }
 
+                    #[cfg(feature = "std")]
+                    #[inline]
+                    fn set_wall_clock(&mut self, clock: &std::sync::Arc<dyn crate::time::WallClock>) {
+                        self.0.set_wall_clock(clock);
+                        self.1.set_wall_clock(clock);
+                    }
+
                     #[inline]
                     fn supervisor_timeout(&mut self, conn_context: &mut Self::ConnectionContext, meta: &ConnectionMeta, context: &supervisor::Context) -> Option<Duration> {
                         let timeout_a = self.0.supervisor_timeout(&mut conn_context.0, meta, context);
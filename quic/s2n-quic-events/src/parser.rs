@@ -249,12 +249,21 @@ impl Struct {
                         }
                     ));
 
+                    // the connection span is keyed by the first local connection ID, which is
+                    // known once the connection is started
+                    let record_cid = if ident_str == "ConnectionStarted" {
+                        quote!(context.record("cid", tracing::field::debug(&path.local_cid));)
+                    } else {
+                        quote!()
+                    };
+
                     output.tracing_subscriber.extend(quote!(
                         #[inline]
                         #allow_deprecated
                         fn #function(&mut self, context: &mut Self::ConnectionContext, _meta: &api::ConnectionMeta, event: &api::#ident) {
                             let id = context.id();
                             let api::#ident { #(#destructure_fields),* } = event;
+                            #record_cid
                             tracing::event!(target: #snake, parent: id, tracing::Level::DEBUG, #(#destructure_fields = tracing::field::debug(#destructure_fields)),*);
                         }
                     ));
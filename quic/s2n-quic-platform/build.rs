@@ -5,13 +5,16 @@ use std::{fs::read_dir, io::Error, path::Path, process::Command};
 
 fn main() -> Result<(), Error> {
     let env = Env::new();
+    let mut detected = vec![];
 
     for feature in read_dir("features")? {
         let path = feature?.path();
         if let Some(name) = path.file_stem() {
             println!("cargo:rerun-if-changed={}", path.display());
             if env.check(&path)? {
-                supports(name.to_str().expect("valid feature name"));
+                let name = name.to_str().expect("valid feature name");
+                supports(name);
+                detected.push(name.to_string());
             }
         }
     }
@@ -23,15 +26,28 @@ fn main() -> Result<(), Error> {
             supports("mtu_disc");
             supports("pktinfo");
             supports("tos");
+            supports("ttl");
+            supports("txtime");
+            supports("rx_timestamp");
+            supports("flow_label");
+            supports("bind_interface");
+
+            // io_uring is opt-in and submits the same messages as the mmsg socket
+            if env.io_uring && detected.iter().any(|name| name == "socket_mmsg") {
+                supports("io_uring");
+            }
         }
         "macos" => {
             supports("pktinfo");
             supports("tos");
+            supports("bind_interface");
         }
         "android" => {
+            supports("bind_interface");
             supports("mtu_disc");
             supports("pktinfo");
             supports("tos");
+            supports("ttl");
         }
         _ => {
             // TODO others
@@ -50,6 +66,7 @@ struct Env {
     out_dir: String,
     target: String,
     target_os: String,
+    io_uring: bool,
 }
 
 impl Env {
@@ -60,6 +77,7 @@ impl Env {
             out_dir: env("OUT_DIR"),
             target: env("TARGET"),
             target_os: env("CARGO_CFG_TARGET_OS"),
+            io_uring: std::env::var_os("CARGO_FEATURE_IO_URING").is_some(),
         }
     }
 
@@ -1,6 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "dpdk")]
+pub mod dpdk;
+
+#[cfg(feature = "std")]
+pub mod driven;
+
+#[cfg(feature = "tokio")]
+pub mod loopback;
+
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
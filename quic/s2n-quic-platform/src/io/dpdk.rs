@@ -0,0 +1,139 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sends and receives packets with [DPDK](https://www.dpdk.org/) from a primary process
+//!
+//! The provider is independent of any particular DPDK bindings. The application initializes the
+//! EAL, configures the ethernet port and its mempool, and then hands the provider an RX and a
+//! TX queue of the port by implementing [`RxQueue`] and [`TxQueue`] with its bindings of choice.
+//!
+//! Packets are exchanged as raw ethernet frames, which are parsed and written with the same
+//! decoder and encoder as the XDP provider, so the path handles carry the MAC addresses of
+//! each peer. The RX queue should only be steered the endpoint's UDP traffic (e.g. with
+//! `rte_flow` rules) since frames which aren't IP/UDP are dropped and any other UDP datagram
+//! is handed to the endpoint.
+//!
+//! DPDK queues don't notify when packets arrive or when there is room to transmit, so the
+//! event loop continuously polls them. The provider should be started on a dedicated
+//! single-threaded runtime which runs on its own lcore.
+
+use crate::io::tokio::Clock;
+use s2n_quic_core::{
+    endpoint::Endpoint,
+    inet::SocketAddress,
+    io::{self, event_loop::EventLoop},
+    path::MaxMtu,
+};
+use tokio::runtime::Handle;
+
+pub use s2n_quic_core::xdp::path::Tuple as PathHandle;
+
+mod builder;
+pub mod rx;
+pub mod tx;
+
+pub use builder::Builder;
+
+#[cfg(test)]
+mod tests;
+
+// export the encoder configuration for writing packets
+pub mod encoder {
+    pub use s2n_quic_core::xdp::encoder::State as Config;
+}
+
+/// A packet buffer which was allocated from a DPDK mempool, e.g. a `rte_mbuf`
+///
+/// Dropping the buffer must return it to its mempool (`rte_pktmbuf_free`).
+pub trait Mbuf: 'static + Send {
+    /// Returns the packet data of the buffer (`rte_pktmbuf_mtod` with `data_len` bytes)
+    fn data(&mut self) -> &mut [u8];
+
+    /// Returns the free space which follows the packet data (`rte_pktmbuf_tailroom`)
+    fn tailroom(&mut self) -> &mut [u8];
+
+    /// Appends the first `len` bytes of the tailroom to the packet data (`rte_pktmbuf_append`)
+    fn append(&mut self, len: u16);
+}
+
+/// The RX queue of a DPDK ethernet port
+pub trait RxQueue: 'static + Send {
+    type Mbuf: Mbuf;
+
+    /// Receives up to `max` packets from the queue (`rte_eth_rx_burst`)
+    ///
+    /// The received buffers are appended to `mbufs`.
+    fn rx_burst(&mut self, mbufs: &mut Vec<Self::Mbuf>, max: usize);
+}
+
+/// The TX queue of a DPDK ethernet port
+pub trait TxQueue: 'static + Send {
+    type Mbuf: Mbuf;
+
+    /// Allocates an empty buffer from the mempool of the port (`rte_pktmbuf_alloc`)
+    ///
+    /// Returns `None` if the mempool is exhausted.
+    fn alloc(&mut self) -> Option<Self::Mbuf>;
+
+    /// Transmits the buffers at the front of `mbufs` (`rte_eth_tx_burst`)
+    ///
+    /// Ownership of the transmitted buffers passes to the port so they must be removed from the
+    /// front of `mbufs` without being dropped. The buffers which couldn't be transmitted are left
+    /// in `mbufs` and are retried on the next call.
+    fn tx_burst(&mut self, mbufs: &mut Vec<Self::Mbuf>);
+}
+
+pub struct Provider<Rx, Tx> {
+    rx: Rx,
+    tx: Tx,
+    max_mtu: MaxMtu,
+    handle: Option<Handle>,
+}
+
+impl Provider<(), ()> {
+    /// Creates a builder to construct a DPDK provider
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+impl<Rx, Tx> Provider<Rx, Tx>
+where
+    Rx: 'static + io::rx::Rx<PathHandle = PathHandle> + Send,
+    Tx: 'static + io::tx::Tx<PathHandle = PathHandle> + Send,
+{
+    pub fn start<E: Endpoint<PathHandle = PathHandle>>(
+        self,
+        mut endpoint: E,
+    ) -> std::io::Result<(tokio::task::JoinHandle<()>, SocketAddress)> {
+        let Self {
+            rx,
+            tx,
+            max_mtu,
+            handle,
+        } = self;
+
+        // tell the endpoint what our MTU is
+        endpoint.set_max_mtu(max_mtu);
+
+        // create a tokio clock
+        let clock = Clock::new();
+
+        // create an event loop
+        let event_loop = EventLoop {
+            endpoint,
+            clock,
+            rx,
+            tx,
+        };
+
+        // spawn the event loop on to the tokio handle
+        let task = if let Some(handle) = handle {
+            handle.spawn(event_loop.start())
+        } else {
+            tokio::spawn(event_loop.start())
+        };
+
+        Ok((task, SocketAddress::default()))
+    }
+}
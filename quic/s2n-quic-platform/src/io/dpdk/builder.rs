@@ -0,0 +1,136 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{encoder, rx, tx, Provider, RxQueue, TxQueue};
+use core::mem::size_of;
+use s2n_quic_core::{
+    inet::{ethernet, ipv4, udp},
+    path::{MaxMtu, MaxMtuError},
+};
+use tokio::runtime::Handle;
+
+/// The size of the data room in the default DPDK mempool (`RTE_MBUF_DEFAULT_DATAROOM`)
+const DEFAULT_FRAME_SIZE: u16 = 2048;
+
+/// The number of packets which are received or transmitted in a single burst
+const DEFAULT_BURST_SIZE: usize = 32;
+
+/// Calculate how much a packet will need for fixed-size headers
+const MIN_FRAME_OVERHEAD: u16 =
+    (size_of::<ethernet::Header>() + size_of::<ipv4::Header>() + size_of::<udp::Header>()) as _;
+
+#[must_use = "Builders do nothing without calling `build`"]
+pub struct Builder<Rx = (), Tx = ()> {
+    rx: Rx,
+    tx: Tx,
+    max_mtu: MaxMtu,
+    burst_size: usize,
+    encoder: encoder::Config,
+    handle: Option<Handle>,
+}
+
+impl Default for Builder<(), ()> {
+    fn default() -> Self {
+        Self {
+            rx: (),
+            tx: (),
+            max_mtu: MaxMtu::try_from(DEFAULT_FRAME_SIZE - MIN_FRAME_OVERHEAD).unwrap(),
+            burst_size: DEFAULT_BURST_SIZE,
+            encoder: Default::default(),
+            handle: None,
+        }
+    }
+}
+
+impl<Rx, Tx> Builder<Rx, Tx> {
+    /// Sets the tokio runtime handle for the provider
+    pub fn with_handle(mut self, handle: Handle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Sets the data room size of the mempool buffers for the provider
+    pub fn with_frame_size(mut self, frame_size: u16) -> Result<Self, MaxMtuError> {
+        self.max_mtu = frame_size.saturating_sub(MIN_FRAME_OVERHEAD).try_into()?;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of packets which are received or transmitted in a single burst
+    pub fn with_burst_size(mut self, burst_size: usize) -> Self {
+        self.burst_size = burst_size.max(1);
+        self
+    }
+
+    /// Sets the configuration for encoding transmitted packets
+    ///
+    /// This can be used to disable the IPv4 header checksum if the port offloads it.
+    pub fn with_encoder_config(mut self, encoder: encoder::Config) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// Sets the RX queue for the provider
+    pub fn with_rx<NewRx>(self, rx: NewRx) -> Builder<NewRx, Tx>
+    where
+        NewRx: RxQueue,
+    {
+        let Self {
+            tx,
+            max_mtu,
+            burst_size,
+            encoder,
+            handle,
+            ..
+        } = self;
+        Builder {
+            rx,
+            tx,
+            max_mtu,
+            burst_size,
+            encoder,
+            handle,
+        }
+    }
+
+    /// Sets the TX queue for the provider
+    pub fn with_tx<NewTx>(self, tx: NewTx) -> Builder<Rx, NewTx>
+    where
+        NewTx: TxQueue,
+    {
+        let Self {
+            rx,
+            max_mtu,
+            burst_size,
+            encoder,
+            handle,
+            ..
+        } = self;
+        Builder {
+            rx,
+            tx,
+            max_mtu,
+            burst_size,
+            encoder,
+            handle,
+        }
+    }
+}
+
+impl<Rx: RxQueue, Tx: TxQueue> Builder<Rx, Tx> {
+    pub fn build(self) -> Provider<rx::Rx<Rx>, tx::Tx<Tx>> {
+        let Self {
+            rx,
+            tx,
+            max_mtu,
+            burst_size,
+            encoder,
+            handle,
+        } = self;
+        Provider {
+            rx: rx::Rx::new(rx, burst_size),
+            tx: tx::Tx::new(tx, burst_size, encoder),
+            max_mtu,
+            handle,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Mbuf, RxQueue};
+use core::{
+    convert::Infallible,
+    task::{Context, Poll},
+};
+use s2n_codec::DecoderBufferMut;
+use s2n_quic_core::{
+    event,
+    inet::datagram,
+    io::rx,
+    xdp::{decoder, path},
+};
+
+/// Receives packets from the RX queue of a DPDK port
+pub struct Rx<Q: RxQueue> {
+    port: Q,
+    queue: Queue<Q::Mbuf>,
+    burst_size: usize,
+}
+
+impl<Q: RxQueue> Rx<Q> {
+    pub(super) fn new(port: Q, burst_size: usize) -> Self {
+        Self {
+            port,
+            queue: Queue {
+                mbufs: Vec::with_capacity(burst_size),
+            },
+            burst_size,
+        }
+    }
+}
+
+impl<Q: RxQueue> rx::Rx for Rx<Q> {
+    type PathHandle = path::Tuple;
+    type Queue = Queue<Q::Mbuf>;
+    type Error = Infallible;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        if self.queue.mbufs.is_empty() {
+            self.port.rx_burst(&mut self.queue.mbufs, self.burst_size);
+        }
+
+        if !self.queue.mbufs.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        // the port doesn't notify us of new packets so poll it again on the next iteration
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+
+    #[inline]
+    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+        f(&mut self.queue);
+    }
+
+    #[inline]
+    fn handle_error<E: event::EndpointPublisher>(self, error: Self::Error, _events: &mut E) {
+        match error {}
+    }
+}
+
+/// The packets which were received in the last burst
+pub struct Queue<M> {
+    mbufs: Vec<M>,
+}
+
+impl<M: Mbuf> rx::Queue for Queue<M> {
+    type Handle = path::Tuple;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        // dropping each buffer returns it to the mempool
+        for mut mbuf in self.mbufs.drain(..) {
+            let decoder = DecoderBufferMut::new(mbuf.data());
+
+            // packets other than IP/UDP can't be processed by the endpoint so drop them
+            if let Ok(Some((header, payload))) = decoder::decode_packet(decoder) {
+                on_packet(header, payload.into_less_safe_slice());
+            }
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.mbufs.is_empty()
+    }
+}
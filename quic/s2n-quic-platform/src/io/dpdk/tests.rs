@@ -0,0 +1,215 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::task::{Context, Poll};
+use futures::task::noop_waker;
+use s2n_quic_core::{
+    inet::{SocketAddressV4, SocketAddressV6},
+    io::{
+        rx::{Queue as _, Rx as _},
+        tx::{Queue as _, Tx as _},
+    },
+    path::Handle as _,
+};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+const FRAME_SIZE: usize = 2048;
+
+/// A buffer which returns itself to the mock mempool when dropped
+#[derive(Debug)]
+struct MockMbuf {
+    frame: Vec<u8>,
+    len: usize,
+    mempool: Arc<AtomicUsize>,
+}
+
+impl Drop for MockMbuf {
+    fn drop(&mut self) {
+        self.mempool.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Mbuf for MockMbuf {
+    fn data(&mut self) -> &mut [u8] {
+        &mut self.frame[..self.len]
+    }
+
+    fn tailroom(&mut self) -> &mut [u8] {
+        &mut self.frame[self.len..]
+    }
+
+    fn append(&mut self, len: u16) {
+        self.len += len as usize;
+        assert!(self.len <= self.frame.len());
+    }
+}
+
+/// The frames which were transmitted by the TX queue and not yet received by the RX queue
+type Wire = Arc<Mutex<VecDeque<MockMbuf>>>;
+
+struct MockRxQueue {
+    wire: Wire,
+}
+
+impl RxQueue for MockRxQueue {
+    type Mbuf = MockMbuf;
+
+    fn rx_burst(&mut self, mbufs: &mut Vec<MockMbuf>, max: usize) {
+        let mut wire = self.wire.lock().unwrap();
+        let len = wire.len().min(max);
+        mbufs.extend(wire.drain(..len));
+    }
+}
+
+struct MockTxQueue {
+    wire: Wire,
+    mempool: Arc<AtomicUsize>,
+}
+
+impl TxQueue for MockTxQueue {
+    type Mbuf = MockMbuf;
+
+    fn alloc(&mut self) -> Option<MockMbuf> {
+        self.mempool
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |free| {
+                free.checked_sub(1)
+            })
+            .ok()?;
+
+        Some(MockMbuf {
+            frame: vec![0; FRAME_SIZE],
+            len: 0,
+            mempool: self.mempool.clone(),
+        })
+    }
+
+    fn tx_burst(&mut self, mbufs: &mut Vec<MockMbuf>) {
+        self.wire.lock().unwrap().extend(mbufs.drain(..));
+    }
+}
+
+fn provider(
+    mempool_size: usize,
+) -> (
+    Provider<rx::Rx<MockRxQueue>, tx::Tx<MockTxQueue>>,
+    Arc<AtomicUsize>,
+) {
+    let wire = Wire::default();
+    let mempool = Arc::new(AtomicUsize::new(mempool_size));
+
+    let provider = Provider::builder()
+        .with_rx(MockRxQueue { wire: wire.clone() })
+        .with_tx(MockTxQueue {
+            wire,
+            mempool: mempool.clone(),
+        })
+        .build();
+
+    (provider, mempool)
+}
+
+fn tuple(local: SocketAddress, remote: SocketAddress) -> PathHandle {
+    PathHandle::from_addresses(local.into(), remote.into())
+}
+
+/// Receives all of the frames on the wire and returns the path and payload of each datagram
+fn receive(rx: &mut rx::Rx<MockRxQueue>) -> Vec<(PathHandle, Vec<u8>)> {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut datagrams = vec![];
+
+    while rx.poll_ready(&mut cx).is_ready() {
+        rx.queue(|queue| {
+            queue.for_each(|header, payload| {
+                // the received path is from the perspective of the receiver
+                let mut path = header.path;
+                path.swap();
+                datagrams.push((path, payload.to_vec()));
+            });
+            assert!(queue.is_empty());
+        });
+    }
+
+    datagrams
+}
+
+#[test]
+fn round_trip_test() {
+    let (Provider { mut rx, mut tx, .. }, mempool) = provider(8);
+
+    let ipv4 = tuple(
+        SocketAddressV4::new([192, 168, 0, 1], 3000).into(),
+        SocketAddressV4::new([192, 168, 0, 2], 4433).into(),
+    );
+    let ipv6 = tuple(
+        SocketAddressV6::new([0u16, 0, 0, 0, 0, 0, 0, 1], 3000).into(),
+        SocketAddressV6::new([0u16, 0, 0, 0, 0, 0, 0, 2], 4433).into(),
+    );
+
+    tx.queue(|queue| {
+        assert_eq!(queue.push((ipv4, [1u8, 2, 3])).unwrap().len, 3);
+        assert_eq!(queue.push((ipv6, [4u8; 1200])).unwrap().len, 1200);
+    });
+
+    // the transmitted buffers are owned by the port until they're received
+    assert_eq!(mempool.load(Ordering::Relaxed), 6);
+
+    let datagrams = receive(&mut rx);
+    assert_eq!(datagrams.len(), 2);
+
+    assert!(datagrams[0].0.strict_eq(&ipv4));
+    assert_eq!(datagrams[0].1, [1, 2, 3]);
+
+    assert!(datagrams[1].0.strict_eq(&ipv6));
+    assert_eq!(datagrams[1].1, [4; 1200]);
+
+    // the received buffers are returned to the mempool
+    assert_eq!(mempool.load(Ordering::Relaxed), 8);
+}
+
+#[test]
+fn exhausted_mempool_test() {
+    let (Provider { mut rx, mut tx, .. }, mempool) = provider(1);
+
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let path = tuple(
+        SocketAddressV4::new([192, 168, 0, 1], 3000).into(),
+        SocketAddressV4::new([192, 168, 0, 2], 4433).into(),
+    );
+
+    tx.queue(|queue| {
+        assert!(queue.push((path, [1u8])).is_ok());
+        assert!(matches!(
+            queue.push((path, [2u8])),
+            Err(io::tx::Error::AtCapacity)
+        ));
+    });
+
+    // the endpoint is woken up to retry the allocation
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+
+    // the failed allocation was already retried
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+
+    // the port frees the transmitted buffer
+    assert_eq!(receive(&mut rx).len(), 1);
+    assert_eq!(mempool.load(Ordering::Relaxed), 1);
+
+    tx.queue(|queue| {
+        assert!(queue.push((path, [2u8])).is_ok());
+    });
+
+    let datagrams = receive(&mut rx);
+    assert_eq!(datagrams.len(), 1);
+    assert_eq!(datagrams[0].1, [2]);
+}
@@ -0,0 +1,150 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{encoder, Mbuf, TxQueue};
+use core::{
+    convert::Infallible,
+    mem::size_of,
+    task::{Context, Poll},
+};
+use s2n_codec::{Encoder as _, EncoderBuffer};
+use s2n_quic_core::{
+    event,
+    inet::{ethernet, ipv6, udp},
+    io::tx,
+    xdp::{encoder::encode_packet, path},
+};
+
+/// The largest size of the headers which are written before the payload
+const MAX_HEADER_LEN: usize =
+    size_of::<ethernet::Header>() + size_of::<ipv6::Header>() + size_of::<udp::Header>();
+
+/// Transmits packets on the TX queue of a DPDK port
+pub struct Tx<Q: TxQueue> {
+    queue: Queue<Q>,
+}
+
+impl<Q: TxQueue> Tx<Q> {
+    pub(super) fn new(port: Q, burst_size: usize, encoder: encoder::Config) -> Self {
+        Self {
+            queue: Queue {
+                port,
+                pending: Vec::with_capacity(burst_size),
+                burst_size,
+                encoder,
+                is_mempool_exhausted: false,
+            },
+        }
+    }
+}
+
+impl<Q: TxQueue> tx::Tx for Tx<Q> {
+    type PathHandle = path::Tuple;
+    type Queue = Queue<Q>;
+    type Error = Infallible;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        // the mempool doesn't notify us when buffers are freed so retry the allocation on the next
+        // iteration
+        if core::mem::take(&mut self.queue.is_mempool_exhausted) {
+            self.queue.flush();
+            return Poll::Ready(Ok(()));
+        }
+
+        // If we didn't fill up the queue then we don't need to poll for capacity
+        if self.queue.pending.is_empty() {
+            return Poll::Pending;
+        }
+
+        self.queue.flush();
+
+        if self.queue.pending.len() < self.queue.burst_size {
+            return Poll::Ready(Ok(()));
+        }
+
+        // the port doesn't notify us of free descriptors so poll it again on the next iteration
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+
+    #[inline]
+    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+        f(&mut self.queue);
+
+        // transmit everything that was just written
+        self.queue.flush();
+    }
+
+    #[inline]
+    fn handle_error<E: event::EndpointPublisher>(self, error: Self::Error, _events: &mut E) {
+        match error {}
+    }
+}
+
+/// The packets which are waiting to be transmitted by the port
+pub struct Queue<Q: TxQueue> {
+    port: Q,
+    pending: Vec<Q::Mbuf>,
+    burst_size: usize,
+    encoder: encoder::Config,
+    /// Set when an allocation failed so the endpoint is woken up to retry it
+    is_mempool_exhausted: bool,
+}
+
+impl<Q: TxQueue> Queue<Q> {
+    #[inline]
+    fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            self.port.tx_burst(&mut self.pending);
+        }
+    }
+}
+
+impl<Q: TxQueue> tx::Queue for Queue<Q> {
+    type Handle = path::Tuple;
+
+    const SUPPORTS_ECN: bool = true;
+    const SUPPORTS_FLOW_LABELS: bool = true;
+
+    #[inline]
+    fn push<M>(&mut self, mut message: M) -> Result<tx::Outcome, tx::Error>
+    where
+        M: tx::Message<Handle = Self::Handle>,
+    {
+        if self.capacity() == 0 {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        // the mempool is exhausted until the port frees the transmitted buffers
+        let mut mbuf = if let Some(mbuf) = self.port.alloc() {
+            mbuf
+        } else {
+            self.is_mempool_exhausted = true;
+            return Err(tx::Error::AtCapacity);
+        };
+
+        // the encoder expects room for the largest headers
+        if mbuf.tailroom().len() <= MAX_HEADER_LEN {
+            return Err(tx::Error::UndersizedBuffer);
+        }
+
+        // if encoding fails the buffer is dropped and returned to the mempool
+        let mut buffer = EncoderBuffer::new(mbuf.tailroom());
+        let payload_len = encode_packet(&mut buffer, &mut message, &mut self.encoder)?;
+        let len = buffer.len();
+
+        mbuf.append(len as _);
+        self.pending.push(mbuf);
+
+        Ok(tx::Outcome {
+            len: payload_len as _,
+            index: 0,
+        })
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.burst_size.saturating_sub(self.pending.len())
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs an endpoint without spawning any tasks or threads
+//!
+//! The application drives the endpoint from its own loop with a [`Driver`]:
+//!
+//! * datagrams read from the network are passed to [`Driver::receive`]
+//! * [`Driver::step`] lets the endpoint process the received datagrams, application wakeups and
+//!   expired timers at the given time
+//! * datagrams returned by [`Driver::pop_transmission`] are written to the network
+//!
+//! The endpoint never reads the system clock. Time only advances as the application calls
+//! [`Driver::step`], which returns the time at which it should be called again if nothing else
+//! happens in the meantime.
+
+use core::{
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    time::Duration,
+};
+use s2n_quic_core::{
+    endpoint::Endpoint,
+    inet::{datagram, ExplicitCongestionNotification, SocketAddress},
+    io::{rx, tx},
+    path::{LocalAddress, MaxMtu, MinMtu, Tuple},
+    time::{Clock, Timestamp},
+};
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+pub type PathHandle = Tuple;
+
+/// The maximum number of datagrams which are queued in each direction
+const QUEUE_CAPACITY: usize = 1024;
+
+/// A datagram which was received from, or is to be sent to, a peer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Datagram {
+    pub remote_address: SocketAddress,
+    pub ecn: ExplicitCongestionNotification,
+    pub payload: Vec<u8>,
+}
+
+impl Datagram {
+    pub fn new(remote_address: SocketAddress, payload: Vec<u8>) -> Self {
+        Self {
+            remote_address,
+            ecn: ExplicitCongestionNotification::NotEct,
+            payload,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Builder {
+    local_address: SocketAddress,
+    max_mtu: MaxMtu,
+    min_mtu: MinMtu,
+}
+
+impl Builder {
+    /// Sets the largest maximum transmission unit (MTU) that can be sent on a path
+    pub fn with_max_mtu(mut self, max_mtu: u16) -> io::Result<Self> {
+        self.max_mtu = max_mtu
+            .try_into()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, format!("{err}")))?;
+        Ok(self)
+    }
+
+    /// Sets the range of maximum transmission units (MTU) that path MTU discovery will use
+    ///
+    /// Paths start with the `min_mtu` and probing never exceeds the `max_mtu`. When a black hole is
+    /// detected, paths fall back to QUIC's minimum datagram size of 1200 bytes. Both values
+    /// include the IP and UDP headers.
+    pub fn with_mtu_range(mut self, min_mtu: u16, max_mtu: u16) -> io::Result<Self> {
+        if min_mtu > max_mtu {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the min_mtu must not exceed the max_mtu",
+            ));
+        }
+
+        self.min_mtu = min_mtu
+            .try_into()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, format!("{err}")))?;
+        self.with_max_mtu(max_mtu)
+    }
+
+    pub fn build(self) -> io::Result<Io> {
+        Ok(Io {
+            builder: self,
+            driver: Driver::default(),
+        })
+    }
+}
+
+/// An IO provider which is driven by the application with a [`Driver`]
+#[derive(Debug)]
+pub struct Io {
+    builder: Builder,
+    driver: Driver,
+}
+
+impl Io {
+    /// Creates an IO provider for an endpoint bound to `local_address`
+    ///
+    /// The address is only used as the local address of the paths. Datagrams are exchanged with
+    /// the network by the application.
+    pub fn new<A: Into<SocketAddress>>(local_address: A) -> io::Result<Self> {
+        Self::builder(local_address).build()
+    }
+
+    pub fn builder<A: Into<SocketAddress>>(local_address: A) -> Builder {
+        Builder {
+            local_address: local_address.into(),
+            max_mtu: MaxMtu::default(),
+            min_mtu: MinMtu::default(),
+        }
+    }
+
+    /// Returns the [`Driver`] for the endpoint which is started with this provider
+    pub fn driver(&self) -> Driver {
+        self.driver.clone()
+    }
+
+    pub fn start<E: Endpoint<PathHandle = PathHandle>>(
+        self,
+        mut endpoint: E,
+    ) -> io::Result<SocketAddress> {
+        let Self { builder, driver } = self;
+        let Builder {
+            local_address,
+            max_mtu,
+            min_mtu,
+        } = builder;
+
+        endpoint.set_max_mtu(max_mtu);
+        endpoint.set_min_mtu(min_mtu);
+
+        let mut state = driver.lock()?;
+
+        if state.endpoint.is_some() || state.is_closed {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "the driver is already in use by another endpoint",
+            ));
+        }
+
+        state.endpoint = Some(Box::new(endpoint));
+        state.local_address = local_address.into();
+        state.max_mtu = max_mtu.into();
+
+        Ok(local_address)
+    }
+}
+
+/// Drives an endpoint from the application's thread
+///
+/// The driver can be cloned and each clone refers to the same endpoint.
+#[derive(Clone, Debug, Default)]
+pub struct Driver {
+    state: Arc<Mutex<State>>,
+}
+
+impl Driver {
+    /// Queues a datagram which was received from the network
+    ///
+    /// The datagram is processed on the next call to [`Driver::step`]. The oldest datagrams are
+    /// dropped if too many are waiting to be processed.
+    pub fn receive(&self, datagram: Datagram) {
+        if let Ok(mut state) = self.lock() {
+            if state.rx.len() == QUEUE_CAPACITY {
+                let _ = state.rx.pop_front();
+            }
+            state.rx.push_back(datagram);
+        }
+    }
+
+    /// Returns the next datagram which should be sent to the network
+    pub fn pop_transmission(&self) -> Option<Datagram> {
+        self.lock().ok()?.tx.pop_front()
+    }
+
+    /// Lets the endpoint make progress at the given time
+    ///
+    /// `now` is the time since an epoch chosen by the application, which must not go backwards
+    /// between calls. The endpoint processes the received datagrams and any expired timers, and
+    /// transmits as much as it can.
+    ///
+    /// Returns the time at which the driver should be stepped again, unless a datagram is
+    /// received or the application interacts with the endpoint before then. `None` is returned
+    /// if the endpoint isn't waiting on any timers, or has closed.
+    pub fn step(&self, now: Duration) -> Option<Duration> {
+        let mut state = self.lock().ok()?;
+        let state = &mut *state;
+
+        let clock = StepClock(unsafe {
+            // Safety: the application is the time source for the driver
+            Timestamp::from_duration(now)
+        });
+
+        let mut endpoint = state.endpoint.take()?;
+
+        let mut rx = RxQueue {
+            local_address: state.local_address,
+            datagrams: &mut state.rx,
+        };
+        let mut tx = TxQueue {
+            max_mtu: state.max_mtu,
+            datagrams: &mut state.tx,
+        };
+
+        let timeout = match endpoint.step(&clock, &mut rx, &mut tx) {
+            Poll::Ready(()) => {
+                // the endpoint has shut down so there's nothing left to drive
+                state.is_closed = true;
+                return None;
+            }
+            Poll::Pending => endpoint.timeout(),
+        };
+
+        state.endpoint = Some(endpoint);
+
+        timeout.map(|timeout| unsafe {
+            // Safety: the timestamp was derived from the application's time source
+            timeout.as_duration()
+        })
+    }
+
+    /// Returns `true` if the endpoint has shut down
+    pub fn is_closed(&self) -> bool {
+        self.lock().map_or(true, |state| state.is_closed)
+    }
+
+    fn lock(&self) -> io::Result<MutexGuard<State>> {
+        self.state
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "driver lock poisoned"))
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    endpoint: Option<Box<dyn Step>>,
+    local_address: LocalAddress,
+    max_mtu: u16,
+    rx: VecDeque<Datagram>,
+    tx: VecDeque<Datagram>,
+    is_closed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            local_address: SocketAddress::default().into(),
+            max_mtu: MaxMtu::default().into(),
+            rx: VecDeque::new(),
+            tx: VecDeque::new(),
+            is_closed: false,
+        }
+    }
+}
+
+/// Erases the type of the endpoint so it can be stored in the [`Driver`]
+trait Step: 'static + Send {
+    /// Performs a single iteration of the endpoint event loop
+    ///
+    /// Returns `Poll::Ready` if the endpoint has shut down.
+    fn step(&mut self, clock: &StepClock, rx: &mut RxQueue, tx: &mut TxQueue) -> Poll<()>;
+
+    fn timeout(&self) -> Option<Timestamp>;
+}
+
+impl core::fmt::Debug for dyn Step {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Endpoint").finish_non_exhaustive()
+    }
+}
+
+impl<E: Endpoint<PathHandle = PathHandle>> Step for E {
+    #[inline]
+    fn step(&mut self, clock: &StepClock, rx: &mut RxQueue, tx: &mut TxQueue) -> Poll<()> {
+        // The application steps the driver again after interacting with the endpoint so there's
+        // nothing to wake.
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        if let Poll::Ready(Err(_)) = self.poll_wakeups(&mut cx, clock) {
+            return Poll::Ready(());
+        }
+
+        if !rx::Queue::is_empty(rx) {
+            self.receive(rx, clock);
+        }
+
+        // transmitting also processes any expired timers
+        self.transmit(tx, clock);
+
+        Poll::Pending
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Timestamp> {
+        Endpoint::timeout(self)
+    }
+}
+
+#[derive(Debug)]
+struct StepClock(Timestamp);
+
+impl Clock for StepClock {
+    #[inline]
+    fn get_time(&self) -> Timestamp {
+        self.0
+    }
+}
+
+struct RxQueue<'a> {
+    local_address: LocalAddress,
+    datagrams: &'a mut VecDeque<Datagram>,
+}
+
+impl<'a> rx::Queue for RxQueue<'a> {
+    type Handle = PathHandle;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        for mut datagram in self.datagrams.drain(..) {
+            let header = datagram::Header {
+                path: Tuple {
+                    local_address: self.local_address,
+                    remote_address: datagram.remote_address.into(),
+                },
+                ecn: datagram.ecn,
+                ttl: None,
+                receive_delay: None,
+            };
+            on_packet(header, &mut datagram.payload);
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.datagrams.is_empty()
+    }
+}
+
+struct TxQueue<'a> {
+    max_mtu: u16,
+    datagrams: &'a mut VecDeque<Datagram>,
+}
+
+impl<'a> tx::Queue for TxQueue<'a> {
+    type Handle = PathHandle;
+
+    const SUPPORTS_ECN: bool = true;
+
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        if !self.has_capacity() {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        let mut payload = vec![0u8; self.max_mtu as usize];
+        let len = message.write_payload(tx::PayloadBuffer::new(&mut payload), 0)?;
+        payload.truncate(len);
+
+        let remote_address = message.path_handle().remote_address.0;
+        let ecn = message.ecn();
+
+        self.datagrams.push_back(Datagram {
+            remote_address,
+            ecn,
+            payload,
+        });
+
+        Ok(tx::Outcome { len, index: 0 })
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        QUEUE_CAPACITY.saturating_sub(self.datagrams.len())
+    }
+}
+
+fn noop_waker() -> Waker {
+    unsafe fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(core::ptr::null(), &NOOP_WAKER_VTABLE)
+    }
+
+    unsafe fn noop(_: *const ()) {}
+
+    static NOOP_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    unsafe {
+        // Safety: the vtable functions don't access the data pointer
+        Waker::from_raw(RawWaker::new(core::ptr::null(), &NOOP_WAKER_VTABLE))
+    }
+}
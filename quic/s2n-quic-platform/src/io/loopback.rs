@@ -0,0 +1,456 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Connects endpoints in memory without opening any sockets
+//!
+//! Each endpoint is attached to a [`Network`], which assigns it an address. Datagrams sent to an
+//! address on the network are delivered to the endpoint which owns it and datagrams sent to any
+//! other address are dropped.
+//!
+//! The endpoints run on the tokio runtime and use its clock.
+
+use crate::io::tokio::Clock;
+use core::task::{Context, Poll, Waker};
+use s2n_quic_core::{
+    endpoint::Endpoint,
+    event::{self, EndpointPublisher as _},
+    inet::{datagram, ExplicitCongestionNotification, SocketAddress},
+    io::{
+        event_loop::select::{self, Select},
+        rx, tx,
+    },
+    path::{LocalAddress, MaxMtu, Tuple},
+    time::{
+        clock::{ClockWithTimer as _, Timer as _},
+        Clock as ClockTrait,
+    },
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tokio::runtime::Handle;
+
+pub type PathHandle = Tuple;
+
+/// The maximum number of datagrams which are queued for each endpoint
+const QUEUE_CAPACITY: usize = 1024;
+
+/// An in-memory network which endpoints are attached to
+///
+/// The network can be cloned and each clone refers to the same network.
+#[derive(Clone, Debug, Default)]
+pub struct Network {
+    state: Arc<Mutex<NetworkState>>,
+}
+
+impl Network {
+    /// Returns a builder for an endpoint on the network
+    pub fn builder(&self) -> Builder {
+        Builder {
+            network: self.clone(),
+            address: None,
+            max_mtu: MaxMtu::default(),
+        }
+    }
+
+    fn attach(&self, address: Option<SocketAddress>) -> io::Result<(SocketAddress, Arc<Inbox>)> {
+        let mut state = self.lock()?;
+
+        let address = match address {
+            Some(address) => address,
+            None => state.next_address()?,
+        };
+
+        if state.inboxes.contains_key(&address) {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                "the address is already in use on the network",
+            ));
+        }
+
+        let inbox = Arc::new(Inbox::default());
+        state.inboxes.insert(address, inbox.clone());
+
+        Ok((address, inbox))
+    }
+
+    fn detach(&self, address: &SocketAddress) {
+        if let Ok(mut state) = self.lock() {
+            state.inboxes.remove(address);
+        }
+    }
+
+    fn deliver(&self, datagram: Datagram, remote_address: &SocketAddress) {
+        // release the network lock before taking the lock of the receiver
+        let inbox = self
+            .lock()
+            .ok()
+            .and_then(|state| state.inboxes.get(remote_address).cloned());
+
+        if let Some(inbox) = inbox {
+            inbox.push(datagram);
+        }
+    }
+
+    fn lock(&self) -> io::Result<MutexGuard<NetworkState>> {
+        self.state
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "network lock poisoned"))
+    }
+}
+
+#[derive(Debug, Default)]
+struct NetworkState {
+    next_port: u16,
+    inboxes: HashMap<SocketAddress, Arc<Inbox>>,
+}
+
+impl NetworkState {
+    /// Returns an unused address on the loopback interface
+    fn next_address(&mut self) -> io::Result<SocketAddress> {
+        for _ in 0..u16::MAX {
+            self.next_port = self.next_port.wrapping_add(1).max(1);
+            let address: SocketAddress =
+                SocketAddr::from((Ipv4Addr::LOCALHOST, self.next_port)).into();
+
+            if !self.inboxes.contains_key(&address) {
+                return Ok(address);
+            }
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            "all of the addresses on the network are in use",
+        ))
+    }
+}
+
+/// A datagram which is waiting to be received by an endpoint
+#[derive(Debug)]
+struct Datagram {
+    remote_address: SocketAddress,
+    ecn: ExplicitCongestionNotification,
+    payload: Vec<u8>,
+}
+
+/// The datagrams which were delivered to an endpoint
+#[derive(Debug, Default)]
+struct Inbox(Mutex<InboxState>);
+
+#[derive(Debug, Default)]
+struct InboxState {
+    datagrams: VecDeque<Datagram>,
+    waker: Option<Waker>,
+}
+
+impl Inbox {
+    fn push(&self, datagram: Datagram) {
+        let waker = if let Ok(mut state) = self.0.lock() {
+            // the datagram is dropped if the endpoint isn't keeping up, the same as with a full
+            // socket receive buffer
+            if state.datagrams.len() < QUEUE_CAPACITY {
+                state.datagrams.push_back(datagram);
+            }
+            state.waker.take()
+        } else {
+            None
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn poll_ready(&self, cx: &mut Context) -> Poll<()> {
+        let mut state = match self.0.lock() {
+            Ok(state) => state,
+            Err(_) => return Poll::Pending,
+        };
+
+        if state.datagrams.is_empty() {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
+    fn take(&self) -> VecDeque<Datagram> {
+        self.0
+            .lock()
+            .map(|mut state| core::mem::take(&mut state.datagrams))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug)]
+pub struct Builder {
+    network: Network,
+    address: Option<SocketAddress>,
+    max_mtu: MaxMtu,
+}
+
+impl Builder {
+    /// Sets the address of the endpoint on the network
+    ///
+    /// By default, the network assigns an unused address on the loopback interface.
+    pub fn with_address<A: Into<SocketAddress>>(mut self, address: A) -> io::Result<Self> {
+        self.address = Some(address.into());
+        Ok(self)
+    }
+
+    /// Sets the largest maximum transmission unit (MTU) that can be sent on a path
+    pub fn with_max_mtu(mut self, max_mtu: u16) -> io::Result<Self> {
+        self.max_mtu = max_mtu
+            .try_into()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, format!("{err}")))?;
+        Ok(self)
+    }
+
+    pub fn build(self) -> io::Result<Io> {
+        Ok(Io { builder: self })
+    }
+}
+
+/// An IO provider which exchanges datagrams with the other endpoints on a [`Network`]
+#[derive(Debug)]
+pub struct Io {
+    builder: Builder,
+}
+
+impl Io {
+    /// Creates an IO provider for an endpoint with an unused address on the network
+    pub fn new(network: &Network) -> io::Result<Self> {
+        network.builder().build()
+    }
+
+    pub fn start<E: Endpoint<PathHandle = PathHandle>>(
+        self,
+        mut endpoint: E,
+    ) -> io::Result<(tokio::task::JoinHandle<()>, SocketAddress)> {
+        let Builder {
+            network,
+            address,
+            max_mtu,
+        } = self.builder;
+
+        let handle =
+            Handle::try_current().map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        endpoint.set_max_mtu(max_mtu);
+
+        let (local_address, inbox) = network.attach(address)?;
+
+        let instance = Instance {
+            network,
+            local_address,
+            inbox,
+            max_mtu: max_mtu.into(),
+            endpoint,
+        };
+
+        let task = handle.spawn(instance.event_loop());
+
+        Ok((task, local_address))
+    }
+}
+
+struct Instance<E> {
+    network: Network,
+    local_address: SocketAddress,
+    inbox: Arc<Inbox>,
+    max_mtu: u16,
+    endpoint: E,
+}
+
+impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
+    async fn event_loop(self) {
+        let Self {
+            network,
+            local_address,
+            inbox,
+            max_mtu,
+            mut endpoint,
+        } = self;
+
+        let clock = Clock::default();
+        let mut timer = clock.timer();
+
+        loop {
+            let rx_task = futures::future::poll_fn(|cx| inbox.poll_ready(cx));
+
+            // datagrams are delivered as soon as they're transmitted so there's never anything
+            // to wait on
+            let tx_task = futures::future::pending::<()>();
+
+            let wakeups = endpoint.wakeups(&clock);
+            // pin the wakeups future so we don't have to move it into the Select future.
+            tokio::pin!(wakeups);
+
+            let timer_ready = timer.ready();
+
+            let select::Outcome {
+                rx_result,
+                tx_result: _,
+                timeout_expired,
+                application_wakeup,
+            } = if let Ok(res) = Select::new(rx_task, tx_task, &mut wakeups, timer_ready).await {
+                res
+            } else {
+                // The endpoint has shut down
+                break;
+            };
+
+            let wakeup_timestamp = clock.get_time();
+            let subscriber = endpoint.subscriber();
+            let mut publisher = event::EndpointPublisherSubscriber::new(
+                event::builder::EndpointMeta {
+                    endpoint_type: E::ENDPOINT_TYPE,
+                    timestamp: wakeup_timestamp,
+                },
+                None,
+                subscriber,
+            );
+
+            publisher.on_platform_event_loop_wakeup(event::builder::PlatformEventLoopWakeup {
+                timeout_expired,
+                rx_ready: rx_result.is_some(),
+                tx_ready: false,
+                application_wakeup,
+            });
+
+            if rx_result.is_some() {
+                let mut datagrams = inbox.take();
+
+                publisher.on_platform_rx(event::builder::PlatformRx {
+                    count: datagrams.len(),
+                });
+
+                let mut rx = RxQueue {
+                    local_address: local_address.into(),
+                    datagrams: &mut datagrams,
+                };
+                endpoint.receive(&mut rx, &clock);
+            }
+
+            let mut tx = TxQueue {
+                network: &network,
+                local_address,
+                max_mtu,
+                count: 0,
+            };
+            endpoint.transmit(&mut tx, &clock);
+            let tx_count = tx.count;
+
+            let timeout = endpoint.timeout();
+
+            if let Some(timeout) = timeout {
+                timer.update(timeout);
+            }
+
+            let timestamp = clock.get_time();
+            let subscriber = endpoint.subscriber();
+            let mut publisher = event::EndpointPublisherSubscriber::new(
+                event::builder::EndpointMeta {
+                    endpoint_type: E::ENDPOINT_TYPE,
+                    timestamp,
+                },
+                None,
+                subscriber,
+            );
+
+            if tx_count > 0 {
+                publisher.on_platform_tx(event::builder::PlatformTx { count: tx_count });
+            }
+
+            // notify the application that we're going to sleep
+            let timeout = timeout.map(|t| t.saturating_duration_since(timestamp));
+            publisher.on_platform_event_loop_sleep(event::builder::PlatformEventLoopSleep {
+                timeout,
+                processing_duration: timestamp.saturating_duration_since(wakeup_timestamp),
+            });
+        }
+
+        // free up the address for other endpoints
+        network.detach(&local_address);
+    }
+}
+
+struct RxQueue<'a> {
+    local_address: LocalAddress,
+    datagrams: &'a mut VecDeque<Datagram>,
+}
+
+impl<'a> rx::Queue for RxQueue<'a> {
+    type Handle = PathHandle;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        for mut datagram in self.datagrams.drain(..) {
+            let header = datagram::Header {
+                path: Tuple {
+                    local_address: self.local_address,
+                    remote_address: datagram.remote_address.into(),
+                },
+                ecn: datagram.ecn,
+                ttl: None,
+                receive_delay: None,
+            };
+            on_packet(header, &mut datagram.payload);
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.datagrams.is_empty()
+    }
+}
+
+struct TxQueue<'a> {
+    network: &'a Network,
+    local_address: SocketAddress,
+    max_mtu: u16,
+    /// The number of datagrams transmitted in this iteration of the event loop
+    count: usize,
+}
+
+impl<'a> tx::Queue for TxQueue<'a> {
+    type Handle = PathHandle;
+
+    const SUPPORTS_ECN: bool = true;
+
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        if !self.has_capacity() {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        let mut payload = vec![0u8; self.max_mtu as usize];
+        let len = message.write_payload(tx::PayloadBuffer::new(&mut payload), 0)?;
+        payload.truncate(len);
+
+        let remote_address = message.path_handle().remote_address.0;
+
+        // the receiver sees the sender's address as the remote address
+        let datagram = Datagram {
+            remote_address: self.local_address,
+            ecn: message.ecn(),
+            payload,
+        };
+
+        self.network.deliver(datagram, &remote_address);
+        self.count += 1;
+
+        Ok(tx::Outcome { len, index: 0 })
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        QUEUE_CAPACITY.saturating_sub(self.count)
+    }
+}
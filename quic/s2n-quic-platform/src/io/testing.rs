@@ -4,7 +4,10 @@
 use bach::time::scheduler;
 use core::task::Poll;
 use s2n_quic_core::{
-    endpoint::Endpoint, inet::SocketAddress, io::event_loop::EventLoop, path::MaxMtu,
+    endpoint::Endpoint,
+    inet::SocketAddress,
+    io::event_loop::EventLoop,
+    path::{MaxMtu, MinMtu},
 };
 
 type Error = std::io::Error;
@@ -220,6 +223,7 @@ impl Handle {
             handle: self.clone(),
             address: None,
             max_mtu: MaxMtu::default(),
+            min_mtu: MinMtu::default(),
         }
     }
 }
@@ -228,6 +232,7 @@ pub struct Builder {
     handle: Handle,
     address: Option<SocketAddress>,
     max_mtu: MaxMtu,
+    min_mtu: MinMtu,
 }
 
 impl Builder {
@@ -239,6 +244,12 @@ impl Builder {
         self.max_mtu = max_mtu.try_into().unwrap();
         self
     }
+
+    pub fn with_mtu_range(mut self, min_mtu: u16, max_mtu: u16) -> Self {
+        assert!(min_mtu <= max_mtu);
+        self.min_mtu = min_mtu.try_into().unwrap();
+        self.with_max_mtu(max_mtu)
+    }
 }
 
 pub struct Io {
@@ -254,8 +265,10 @@ impl Io {
             handle: Handle { executor, buffers },
             address,
             max_mtu,
+            min_mtu,
         } = self.builder;
         endpoint.set_max_mtu(max_mtu);
+        endpoint.set_min_mtu(min_mtu);
 
         let handle = address.unwrap_or_else(|| buffers.generate_addr());
 
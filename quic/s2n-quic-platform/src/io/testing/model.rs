@@ -3,11 +3,15 @@
 
 use super::network::{Buffers, Network, Packet};
 use core::time::Duration;
-use s2n_quic_core::{havoc, path::MaxMtu};
+use s2n_quic_core::{
+    havoc,
+    inet::{ExplicitCongestionNotification, SocketAddress},
+    path::MaxMtu,
+};
 use std::{
     borrow::Cow,
     sync::{
-        atomic::{AtomicU16, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering},
         Arc, Mutex,
     },
 };
@@ -179,6 +183,83 @@ impl Model {
             .store(value, Ordering::SeqCst);
         self
     }
+
+    pub fn ecn_bleaching(&self) -> bool {
+        self.0.ecn_bleaching.load(Ordering::SeqCst)
+    }
+
+    /// Clears the ECN markings of all of the packets, simulating a middlebox which bleaches ECN
+    pub fn set_ecn_bleaching(&self, value: bool) -> &Self {
+        self.0.ecn_bleaching.store(value, Ordering::SeqCst);
+        self
+    }
+
+    fn ce_rate(&self) -> u64 {
+        self.0.ce_rate.load(Ordering::SeqCst)
+    }
+
+    /// The odds an ECN-capable packet will be marked Congestion Experienced.
+    ///
+    /// Each packet will make an independent decision with odds of `0.0..1.0`, with `0.0` having no
+    /// chance and `1.0` occurring with each packet.
+    pub fn set_ce_rate(&self, value: f64) -> &Self {
+        let value = rate_to_u64(value);
+        self.0.ce_rate.store(value, Ordering::SeqCst);
+        self
+    }
+
+    /// Rebinds the address of an endpoint on the network, simulating a NAT rebinding
+    ///
+    /// Packets sent from `address` will appear to come from `rebound_address` and packets sent
+    /// to `rebound_address` will be delivered to `address`.
+    pub fn rebind(&self, address: SocketAddress, rebound_address: SocketAddress) -> &Self {
+        self.0
+            .rebinds
+            .lock()
+            .unwrap()
+            .push((address, rebound_address));
+        self
+    }
+
+    /// Assigns an additional address to an endpoint on the network
+    ///
+    /// Packets sent to `alias` will be delivered to the endpoint bound to `address`. This
+    /// allows the endpoint to send from and receive on multiple local addresses.
+    pub fn alias(&self, address: SocketAddress, alias: SocketAddress) -> &Self {
+        self.0.aliases.lock().unwrap().push((address, alias));
+        self
+    }
+
+    /// Partitions two addresses on the network
+    ///
+    /// All packets sent between `a` and `b`, in either direction, are dropped until the
+    /// partition is repaired with [`Self::repair`].
+    pub fn partition(&self, a: SocketAddress, b: SocketAddress) -> &Self {
+        let mut partitions = self.0.partitions.lock().unwrap();
+        if !partitions.iter().any(|pair| is_partitioned(pair, a, b)) {
+            partitions.push((a, b));
+        }
+        self
+    }
+
+    /// Repairs a partition between two addresses which was created with [`Self::partition`]
+    pub fn repair(&self, a: SocketAddress, b: SocketAddress) -> &Self {
+        self.0
+            .partitions
+            .lock()
+            .unwrap()
+            .retain(|pair| !is_partitioned(pair, a, b));
+        self
+    }
+}
+
+#[inline]
+fn is_partitioned(
+    pair: &(SocketAddress, SocketAddress),
+    a: SocketAddress,
+    b: SocketAddress,
+) -> bool {
+    (pair.0 == a && pair.1 == b) || (pair.0 == b && pair.1 == a)
 }
 
 fn rate_to_u64(rate: f64) -> u64 {
@@ -200,6 +281,11 @@ struct State {
     inflight_delay: AtomicU64,
     inflight_delay_threshold: AtomicU64,
     current_inflight: AtomicU64,
+    ecn_bleaching: AtomicBool,
+    ce_rate: AtomicU64,
+    rebinds: Mutex<Vec<(SocketAddress, SocketAddress)>>,
+    aliases: Mutex<Vec<(SocketAddress, SocketAddress)>>,
+    partitions: Mutex<Vec<(SocketAddress, SocketAddress)>>,
 }
 
 impl Default for State {
@@ -217,6 +303,11 @@ impl Default for State {
             inflight_delay: AtomicU64::new(0),
             inflight_delay_threshold: AtomicU64::new(u64::MAX),
             current_inflight: AtomicU64::new(0),
+            ecn_bleaching: AtomicBool::new(false),
+            ce_rate: AtomicU64::new(0),
+            rebinds: Mutex::new(Vec::new()),
+            aliases: Mutex::new(Vec::new()),
+            partitions: Mutex::new(Vec::new()),
         }
     }
 }
@@ -232,6 +323,11 @@ impl Network for Model {
         let max_udp_payload = self.max_udp_payload() as usize;
         let inflight_delay = self.inflight_delay();
         let inflight_delay_threshold = self.inflight_delay_threshold();
+        let ecn_bleaching = self.ecn_bleaching();
+        let ce_rate = self.ce_rate();
+        let rebinds = self.0.rebinds.lock().unwrap().clone();
+        let aliases = self.0.aliases.lock().unwrap().clone();
+        let partitions = self.0.partitions.lock().unwrap().clone();
 
         let now = super::time::now();
         let mut transmit_time = now + self.delay();
@@ -254,6 +350,16 @@ impl Network for Model {
                 return 0;
             }
 
+            // drop packets sent between partitioned addresses
+            let sender = packet.path.local_address.0;
+            let receiver = packet.path.remote_address.0;
+            if partitions
+                .iter()
+                .any(|pair| is_partitioned(pair, sender, receiver))
+            {
+                return 0;
+            }
+
             // drop the packet if enabled
             if gen_rate(drop_rate) {
                 return 0;
@@ -261,6 +367,14 @@ impl Network for Model {
 
             let mut packet = packet.into_owned();
 
+            if ecn_bleaching {
+                packet.ecn = ExplicitCongestionNotification::NotEct;
+            }
+
+            if packet.ecn.using_ecn() && gen_rate(ce_rate) {
+                packet.ecn = ExplicitCongestionNotification::Ce;
+            }
+
             if !packet.payload.is_empty() && gen_rate(corrupt_rate) {
                 use havoc::Strategy as _;
 
@@ -296,9 +410,23 @@ impl Network for Model {
                 transmit_time += inflight_delay * mul as u32;
             }
 
+            for (address, rebound_address) in rebinds.iter() {
+                if packet.path.local_address.0 == *address {
+                    packet.path.local_address = (*rebound_address).into();
+                } else if packet.path.remote_address.0 == *rebound_address {
+                    packet.path.remote_address = (*address).into();
+                }
+            }
+
             // reverse the addresses so the dst/src are correct for the receiver
             packet.switch();
 
+            // deliver packets sent to an alias to the endpoint which owns it
+            let receiver = aliases
+                .iter()
+                .find(|(_address, alias)| *alias == *packet.path.local_address)
+                .map_or(*packet.path.local_address, |(address, _alias)| *address);
+
             let buffers = buffers.clone();
 
             // spawn a task that will push the packet onto the receiver queue at the transit time
@@ -309,7 +437,7 @@ impl Network for Model {
                     super::time::delay_until(transmit_time).await;
                 }
 
-                buffers.rx(*packet.path.local_address, |queue| {
+                buffers.rx(receiver, |queue| {
                     model.0.current_inflight.fetch_sub(1, Ordering::SeqCst);
                     queue.receive(packet);
                 });
@@ -360,6 +360,8 @@ impl io::rx::Queue for Queue {
             let header = datagram::Header {
                 path: packet.path,
                 ecn: packet.ecn,
+                ttl: None,
+                receive_delay: None,
             };
             let payload = &mut packet.payload;
             on_packet(header, payload);
@@ -376,6 +378,7 @@ impl io::rx::Queue for Queue {
 pub struct Packet {
     pub path: Tuple,
     pub ecn: ExplicitCongestionNotification,
+    pub dscp: u8,
     pub payload: Vec<u8>,
 }
 
@@ -387,6 +390,7 @@ impl Packet {
                 remote_address: Default::default(),
             },
             ecn: Default::default(),
+            dscp: 0,
             payload: vec![0u8; mtu as usize],
         }
     }
@@ -410,8 +414,14 @@ impl Packet {
         let len = message.write_payload(buffer, 0)?;
 
         self.payload.truncate(len);
-        self.path.remote_address = message.path_handle().remote_address;
+        let path = message.path_handle();
+        self.path.remote_address = path.remote_address;
+        // send from the address on the path if the connection picked one
+        if path.local_address.port() != 0 {
+            self.path.local_address = path.local_address;
+        }
         self.ecn = message.ecn();
+        self.dscp = message.dscp();
 
         Ok(len)
     }
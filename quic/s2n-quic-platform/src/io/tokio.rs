@@ -3,15 +3,16 @@
 
 use crate::{buffer::default as buffer, features::gso, socket::default as socket, syscall};
 use cfg_if::cfg_if;
+use core::time::Duration;
 use s2n_quic_core::{
     endpoint::Endpoint,
     event::{self, EndpointPublisher as _},
     inet::{self, SocketAddress},
     io::event_loop::select::{self, Select},
-    path::MaxMtu,
+    path::{MaxMtu, MinMtu},
     time::{
         clock::{ClockWithTimer as _, Timer as _},
-        Clock as ClockTrait,
+        Clock as ClockTrait, Timestamp,
     },
 };
 use std::{convert::TryInto, io, io::ErrorKind};
@@ -19,12 +20,15 @@ use tokio::{net::UdpSocket, runtime::Handle};
 
 mod builder;
 mod clock;
+mod filter;
+mod raw;
 #[cfg(test)]
 mod tests;
 
 pub type PathHandle = socket::Handle;
 pub use builder::Builder;
 pub(crate) use clock::Clock;
+pub use raw::RawSender;
 
 impl crate::socket::std::Socket for UdpSocket {
     type Error = io::Error;
@@ -57,7 +61,7 @@ impl Io {
 
     pub fn start<E: Endpoint<PathHandle = PathHandle>>(
         self,
-        mut endpoint: E,
+        endpoint: E,
     ) -> io::Result<(tokio::task::JoinHandle<()>, SocketAddress)> {
         let Builder {
             handle,
@@ -66,12 +70,27 @@ impl Io {
             recv_addr,
             send_addr,
             recv_buffer_size,
+            max_buffered_recv_bytes,
             send_buffer_size,
             mut max_mtu,
+            mut min_mtu,
             max_segments,
+            rx_ring_size,
+            tx_ring_size,
+            max_batch_size,
             reuse_port,
+            ttl_reporting,
+            rx_timestamps,
+            outbound_datagram_filter,
+            raw_sender,
+            io_uring,
+            txtime,
+            flow_labels,
+            interface,
         } = self.builder;
 
+        let mut endpoint = raw::Endpoint::new(endpoint, raw_sender);
+
         let clock = Clock::default();
 
         let mut publisher = event::EndpointPublisherSubscriber::new(
@@ -124,6 +143,11 @@ impl Io {
         // ensure the socket is non-blocking
         tx_socket.set_nonblocking(true)?;
 
+        if let Some(interface) = &interface {
+            syscall::bind_interface(&rx_socket, interface)?;
+            syscall::bind_interface(&tx_socket, interface)?;
+        }
+
         if let Some(size) = send_buffer_size {
             tx_socket.set_send_buffer_size(size)?;
         }
@@ -132,6 +156,24 @@ impl Io {
             rx_socket.set_recv_buffer_size(size)?;
         }
 
+        // report the sizes the platform chose, which may be capped or rounded from the requested
+        // sizes, e.g. by `net.core.rmem_max` on Linux
+        if let Ok(size) = rx_socket.recv_buffer_size() {
+            publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+                configuration: event::builder::PlatformFeatureConfiguration::RecvBufferSize {
+                    size,
+                },
+            });
+        }
+
+        if let Ok(size) = tx_socket.send_buffer_size() {
+            publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+                configuration: event::builder::PlatformFeatureConfiguration::SendBufferSize {
+                    size,
+                },
+            });
+        }
+
         fn convert_addr_to_std(addr: socket2::SockAddr) -> io::Result<std::net::SocketAddr> {
             addr.as_socket().ok_or_else(|| {
                 io::Error::new(io::ErrorKind::InvalidInput, "invalid domain for socket")
@@ -148,6 +190,7 @@ impl Io {
         if !syscall::configure_mtu_disc(&tx_socket) {
             // disable MTU probing if we can't prevent fragmentation
             max_mtu = MaxMtu::MIN;
+            min_mtu = MinMtu::MIN;
         }
 
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
@@ -159,6 +202,16 @@ impl Io {
         // Configure packet info CMSG
         syscall::configure_pktinfo(&rx_socket);
 
+        // Configure TTL/hop limit CMSG
+        if ttl_reporting {
+            syscall::configure_ttl(&rx_socket);
+        }
+
+        // Configure receive timestamp CMSG
+        if rx_timestamps {
+            syscall::configure_rx_timestamp(&rx_socket);
+        }
+
         // Configure TOS/ECN
         let tos_enabled = syscall::configure_tos(&rx_socket);
 
@@ -168,12 +221,43 @@ impl Io {
             },
         });
 
-        let rx_buffer = buffer::Buffer::new_with_mtu(max_mtu.into());
-        let tx_buffer = buffer::Buffer::new_with_mtu(max_mtu.into());
+        let new_buffer = |ring_size: Option<usize>| -> io::Result<buffer::Buffer> {
+            let mtu: usize = max_mtu.into();
+            match ring_size {
+                Some(messages) => {
+                    // make sure the ring can be allocated before allocating it
+                    messages.checked_mul(mtu).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "the ring size is too large for the max MTU",
+                        )
+                    })?;
+                    Ok(buffer::Buffer::new(messages, mtu))
+                }
+                None => Ok(buffer::Buffer::new_with_mtu(mtu)),
+            }
+        };
+        let rx_buffer = new_buffer(rx_ring_size)?;
+        let tx_buffer = new_buffer(tx_ring_size)?;
         cfg_if! {
             if #[cfg(any(s2n_quic_platform_socket_msg, s2n_quic_platform_socket_mmsg))] {
                 let mut rx = socket::Queue::<buffer::Buffer>::new(rx_buffer, max_segments.into());
-                let tx = socket::Queue::<buffer::Buffer>::new(tx_buffer, max_segments.into());
+                let mut tx = socket::Queue::<buffer::Buffer>::new(tx_buffer, max_segments.into());
+
+                if let Some(max_batch_size) = max_batch_size {
+                    rx.set_max_batch(max_batch_size);
+                    tx.set_max_batch(max_batch_size);
+                }
+
+                // Configure transmit times
+                if txtime && syscall::configure_txtime(&tx_socket) {
+                    tx.enable_txtime();
+                }
+
+                // Configure IPv6 flow labels, which fails on IPv4 sockets
+                if flow_labels && syscall::configure_flow_labels(&tx_socket) {
+                    tx.enable_flow_labels();
+                }
             } else {
                 // If you are using an LSP to jump into this code, it will
                 // probably take you to the wrong implementation. socket.rs does
@@ -181,6 +265,12 @@ impl Io {
                 // actually in socket/std.rs, not socket/mmsg.rs
                 let mut rx = socket::Queue::new(rx_buffer);
                 let tx = socket::Queue::new(tx_buffer);
+
+                // the builder rejects txtime and flow labels on platforms that don't support them
+                let _ = (txtime, flow_labels);
+
+                // datagrams are sent and received one at a time
+                let _ = max_batch_size;
             }
         }
 
@@ -190,8 +280,22 @@ impl Io {
             addr.into()
         });
 
+        #[cfg(s2n_quic_platform_io_uring)]
+        let io_uring = if io_uring {
+            let receiver =
+                crate::socket::io_uring::Receiver::new(&rx_socket, rx.free_len(), max_mtu.into())?;
+            Some((crate::socket::io_uring::Ring::new()?, receiver))
+        } else {
+            None
+        };
+
+        // the builder rejects io_uring on platforms that don't support it
+        #[cfg(not(s2n_quic_platform_io_uring))]
+        let _ = io_uring;
+
         // Notify the endpoint of the MTU that we chose
         endpoint.set_max_mtu(max_mtu);
+        endpoint.set_min_mtu(min_mtu);
 
         let instance = Instance {
             clock,
@@ -199,7 +303,11 @@ impl Io {
             tx_socket: tx_socket.into(),
             rx,
             tx,
+            max_buffered_recv_bytes,
+            outbound_datagram_filter,
             endpoint,
+            #[cfg(s2n_quic_platform_io_uring)]
+            io_uring,
         };
 
         let local_addr = instance.rx_socket.local_addr()?.into();
@@ -221,6 +329,56 @@ impl Io {
     }
 }
 
+/// Binds `workers` sockets to `addr` which share the port with SO_REUSEPORT
+///
+/// Datagrams are steered to a socket by the first byte of their destination connection ID, so
+/// each socket can be driven by its own endpoint, e.g. one per thread. Each endpoint needs a
+/// connection ID format which encodes the index of its socket, such as the default format built
+/// with `with_worker`. The sockets are returned in the order of their index.
+///
+/// The index is the position of the socket in the group. Closing one of the sockets moves the
+/// last socket of the group into its position, which reshuffles the indexes the connection IDs
+/// are steered to, so the sockets should be closed together.
+///
+/// This is only supported on Linux.
+pub fn bind_reuse_port_group(
+    addr: std::net::SocketAddr,
+    workers: u8,
+) -> io::Result<Vec<std::net::UdpSocket>> {
+    if !cfg!(target_os = "linux") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "connection ID steering is not supported on the current platform",
+        ));
+    }
+
+    if workers == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "at least one worker is required",
+        ));
+    }
+
+    let mut addr = addr;
+    let mut sockets = Vec::with_capacity(workers as usize);
+
+    for _ in 0..workers {
+        let socket = syscall::bind_udp(addr, true)?;
+
+        // the rest of the sockets need to use the port that was assigned to the first one
+        if let Some(local_addr) = socket.local_addr()?.as_socket() {
+            addr = local_addr;
+        }
+
+        sockets.push(socket);
+    }
+
+    #[cfg(target_os = "linux")]
+    syscall::attach_reuse_port_steering(&sockets[0], workers)?;
+
+    Ok(sockets.into_iter().map(Into::into).collect())
+}
+
 #[derive(Debug)]
 struct Instance<E> {
     clock: Clock,
@@ -228,7 +386,15 @@ struct Instance<E> {
     tx_socket: std::net::UdpSocket,
     rx: socket::Queue<buffer::Buffer>,
     tx: socket::Queue<buffer::Buffer>,
+    max_buffered_recv_bytes: Option<usize>,
+    outbound_datagram_filter: Option<filter::OutboundDatagramFilter>,
     endpoint: E,
+    /// The ring used to transmit and the receiver, which stops receiving when it is dropped
+    #[cfg(s2n_quic_platform_io_uring)]
+    io_uring: Option<(
+        crate::socket::io_uring::Ring,
+        crate::socket::io_uring::Receiver,
+    )>,
 }
 
 impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
@@ -239,13 +405,31 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
             tx_socket,
             mut rx,
             mut tx,
+            max_buffered_recv_bytes,
+            mut outbound_datagram_filter,
             mut endpoint,
+            #[cfg(s2n_quic_platform_io_uring)]
+            io_uring,
         } = self;
 
         cfg_if! {
             if #[cfg(any(s2n_quic_platform_socket_msg, s2n_quic_platform_socket_mmsg))] {
                 let rx_socket = tokio::io::unix::AsyncFd::new(rx_socket)?;
                 let tx_socket = tokio::io::unix::AsyncFd::new(tx_socket)?;
+
+                // the receiver consumes the datagrams so the readiness of its ring is polled
+                // instead of the socket
+                #[cfg(s2n_quic_platform_io_uring)]
+                let (mut tx_ring, mut receiver) = match io_uring {
+                    Some((ring, receiver)) => (
+                        Some(ring),
+                        Some(tokio::io::unix::AsyncFd::with_interest(
+                            receiver,
+                            tokio::io::Interest::READABLE,
+                        )?),
+                    ),
+                    None => (None, None),
+                };
             } else {
                 let rx_socket = async_fd_shim::AsyncFd::new(rx_socket)?;
                 let tx_socket = async_fd_shim::AsyncFd::new(tx_socket)?;
@@ -253,16 +437,26 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
         }
 
         let mut timer = clock.timer();
+        let mut rx_drops = RxDrops::default();
 
         loop {
             // Poll for readability if we have free slots available
             let rx_interest = rx.free_len() > 0;
             let rx_task = async {
-                if rx_interest {
-                    rx_socket.readable().await
-                } else {
-                    futures::future::pending().await
+                if !rx_interest {
+                    return futures::future::pending().await;
+                }
+
+                #[cfg(s2n_quic_platform_io_uring)]
+                if let Some(receiver) = receiver.as_ref() {
+                    // completions were left in the queue on the last iteration
+                    if !receiver.get_ref().has_backlog() {
+                        receiver.readable().await?.clear_ready();
+                    }
+                    return Ok(None);
                 }
+
+                rx_socket.readable().await.map(Some)
             };
 
             // Poll for writablity if we have occupied slots available
@@ -311,20 +505,63 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
                 application_wakeup,
             });
 
+            rx_drops.poll(
+                &socket2::SockRef::from(rx_socket.get_ref()),
+                wakeup_timestamp,
+                &mut publisher,
+            );
+
             if let Some(guard) = tx_result {
-                if let Ok(result) = guard?.try_io(|socket| tx.tx(socket, &mut publisher)) {
+                if let Ok(result) = guard?.try_io(|socket| {
+                    #[cfg(s2n_quic_platform_io_uring)]
+                    if let Some(ring) = tx_ring.as_mut() {
+                        return tx.tx_io_uring(socket, ring, &mut publisher);
+                    }
+
+                    tx.tx(socket, &mut publisher)
+                }) {
                     result?;
                 }
             }
 
-            if let Some(guard) = rx_result {
-                if let Ok(result) = guard?.try_io(|socket| rx.rx(socket, &mut publisher)) {
-                    result?;
+            if let Some(result) = rx_result {
+                match result? {
+                    Some(guard) => {
+                        if let Ok(result) = guard.try_io(|socket| rx.rx(socket, &mut publisher)) {
+                            result?;
+                        }
+                    }
+                    #[cfg(s2n_quic_platform_io_uring)]
+                    None => {
+                        if let Some(receiver) = receiver.as_mut() {
+                            rx.rx_io_uring(receiver.get_mut(), &mut publisher)?;
+                        }
+                    }
+                    #[cfg(not(s2n_quic_platform_io_uring))]
+                    None => {}
+                }
+
+                if let Some(max_len) = max_buffered_recv_bytes {
+                    rx.rx_queue().release_excess(max_len, |len| {
+                        publisher.on_endpoint_datagram_dropped(
+                            event::builder::EndpointDatagramDropped {
+                                len: len.try_into().unwrap_or(u16::MAX),
+                                reason:
+                                    event::builder::DatagramDropReason::ReceiveBufferLimitExceeded,
+                            },
+                        );
+                    });
                 }
+
                 endpoint.receive(&mut rx.rx_queue(), &clock);
             }
 
-            endpoint.transmit(&mut tx.tx_queue(), &clock);
+            if let Some(filter) = outbound_datagram_filter.as_mut() {
+                let mut tx_queue = tx.tx_queue();
+                endpoint.transmit(&mut filter::Queue::new(&mut tx_queue, filter), &clock);
+            } else {
+                endpoint.transmit(&mut tx.tx_queue(), &clock);
+            }
 
             let timeout = endpoint.timeout();
 
@@ -353,6 +590,46 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
     }
 }
 
+/// Periodically reports the datagrams which the kernel dropped on the rx socket
+#[derive(Debug, Default)]
+struct RxDrops {
+    /// The value of the kernel's drop counter at the previous check
+    total: Option<u32>,
+    /// The time at which the counter is checked next
+    next_check: Option<Timestamp>,
+}
+
+impl RxDrops {
+    /// Reading the counter requires a syscall so it's only checked once per interval
+    const INTERVAL: Duration = Duration::from_secs(1);
+
+    fn poll<Pub: event::EndpointPublisher>(
+        &mut self,
+        socket: &socket2::Socket,
+        now: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        if self.next_check.map_or(false, |next_check| next_check > now) {
+            return;
+        }
+
+        self.next_check = Some(now + Self::INTERVAL);
+
+        if let Some(total) = syscall::rx_socket_drops(socket) {
+            if let Some(previous) = self.total.replace(total) {
+                // the kernel's counter wraps around
+                let count = total.wrapping_sub(previous);
+
+                if count > 0 {
+                    publisher.on_platform_rx_socket_drops(event::builder::PlatformRxSocketDrops {
+                        count: count as usize,
+                    });
+                }
+            }
+        }
+    }
+}
+
 /// A shim for the AsyncFd API
 ///
 /// Tokio only provides the AsyncFd interface for unix platforms so for
@@ -371,6 +648,10 @@ mod async_fd_shim {
             Ok(Self(socket))
         }
 
+        pub fn get_ref(&self) -> &tokio::net::UdpSocket {
+            &self.0
+        }
+
         pub async fn readable(&self) -> io::Result<TryIo<'_>> {
             self.0.readable().await?;
             Ok(TryIo(&self.0))
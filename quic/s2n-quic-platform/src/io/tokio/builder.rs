@@ -11,10 +11,23 @@ pub struct Builder {
     pub(super) recv_addr: Option<std::net::SocketAddr>,
     pub(super) send_addr: Option<std::net::SocketAddr>,
     pub(super) recv_buffer_size: Option<usize>,
+    pub(super) max_buffered_recv_bytes: Option<usize>,
     pub(super) send_buffer_size: Option<usize>,
     pub(super) max_mtu: MaxMtu,
+    pub(super) min_mtu: MinMtu,
     pub(super) max_segments: gso::MaxSegments,
+    pub(super) rx_ring_size: Option<usize>,
+    pub(super) tx_ring_size: Option<usize>,
+    pub(super) max_batch_size: Option<usize>,
     pub(super) reuse_port: bool,
+    pub(super) ttl_reporting: bool,
+    pub(super) rx_timestamps: bool,
+    pub(super) outbound_datagram_filter: Option<filter::OutboundDatagramFilter>,
+    pub(super) raw_sender: Option<raw::RawSender>,
+    pub(super) io_uring: bool,
+    pub(super) txtime: bool,
+    pub(super) flow_labels: bool,
+    pub(super) interface: Option<String>,
 }
 
 impl Builder {
@@ -27,6 +40,10 @@ impl Builder {
     /// Sets the local address for the runtime to listen on. If no send address
     /// or tx socket is specified, this address will also be used for transmitting from.
     ///
+    /// Binding to an IPv6 address, such as `[::]:443`, serves both IPv4 and IPv6 peers where the
+    /// platform supports dual-stack sockets. IPv4 peers are reported with IPv4-mapped IPv6
+    /// addresses, which compare equal to their IPv4 form when matching paths.
+    ///
     /// NOTE: this method is mutually exclusive with `with_rx_socket`
     pub fn with_receive_address(mut self, addr: std::net::SocketAddr) -> io::Result<Self> {
         debug_assert!(self.rx_socket.is_none(), "rx socket has already been set");
@@ -47,16 +64,32 @@ impl Builder {
     /// Sets the socket used for receiving for the runtime. If no tx_socket or send address is
     /// specified, this socket will be used for transmitting.
     ///
+    /// The socket must already be bound. Any options applied to it are kept, apart from
+    /// making it non-blocking and enabling the options used for ECN, packet info, and MTU
+    /// discovery.
+    ///
     /// NOTE: this method is mutually exclusive with `with_receive_address`
     pub fn with_rx_socket(mut self, socket: std::net::UdpSocket) -> io::Result<Self> {
         debug_assert!(
             self.recv_addr.is_none(),
             "recv address has already been set"
         );
-        self.rx_socket = Some(socket.into());
+        self.rx_socket = Some(datagram_socket(socket)?);
         Ok(self)
     }
 
+    /// Sets the socket used for receiving for the runtime from a file descriptor, such as one
+    /// passed by systemd socket activation or a parent process
+    ///
+    /// The file descriptor must refer to a bound UDP socket. See `with_rx_socket` for more
+    /// details.
+    ///
+    /// NOTE: this method is mutually exclusive with `with_receive_address`
+    #[cfg(unix)]
+    pub fn with_rx_fd(self, fd: std::os::unix::io::OwnedFd) -> io::Result<Self> {
+        self.with_rx_socket(fd.into())
+    }
+
     /// Sets the socket used for transmitting on for the runtime. If no tx_socket or send address is
     /// specified, the rx_socket will be used for transmitting.
     ///
@@ -66,10 +99,36 @@ impl Builder {
             self.send_addr.is_none(),
             "send address has already been set"
         );
-        self.tx_socket = Some(socket.into());
+        self.tx_socket = Some(datagram_socket(socket)?);
         Ok(self)
     }
 
+    /// Sets the socket used for transmitting on for the runtime from a file descriptor, such as
+    /// one passed by systemd socket activation or a parent process
+    ///
+    /// The file descriptor must refer to a bound UDP socket. See `with_tx_socket` for more
+    /// details.
+    ///
+    /// NOTE: this method is mutually exclusive with `with_send_address`
+    #[cfg(unix)]
+    pub fn with_tx_fd(self, fd: std::os::unix::io::OwnedFd) -> io::Result<Self> {
+        self.with_tx_socket(fd.into())
+    }
+
+    /// Sets the socket used for receiving and transmitting to a socket created with tokio
+    ///
+    /// The socket is deregistered from the reactor it was created with and registered with the
+    /// reactor of the runtime handle when the IO provider is started. The socket options used for
+    /// ECN, packet info, and MTU discovery are configured on the underlying file descriptor, the
+    /// same as for sockets bound by the provider.
+    ///
+    /// NOTE: this method is mutually exclusive with `with_receive_address` and `with_rx_socket`
+    pub fn with_tokio_socket(self, socket: tokio::net::UdpSocket) -> io::Result<Self> {
+        debug_assert!(self.rx_socket.is_none(), "rx socket has already been set");
+        let socket = socket.into_std()?;
+        self.with_rx_socket(socket)
+    }
+
     /// Sets the size of the operating system’s send buffer associated with the tx socket
     pub fn with_send_buffer_size(mut self, send_buffer_size: usize) -> io::Result<Self> {
         self.send_buffer_size = Some(send_buffer_size);
@@ -82,6 +141,15 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the maximum number of received bytes buffered before being processed by the endpoint
+    ///
+    /// When a burst of datagrams exceeds the limit, the oldest datagrams are dropped and
+    /// reported with the `EndpointDatagramDropped` event.
+    pub fn with_max_buffered_recv_bytes(mut self, max_bytes: usize) -> io::Result<Self> {
+        self.max_buffered_recv_bytes = Some(max_bytes);
+        Ok(self)
+    }
+
     /// Sets the largest maximum transmission unit (MTU) that can be sent on a path
     pub fn with_max_mtu(mut self, max_mtu: u16) -> io::Result<Self> {
         self.max_mtu = max_mtu
@@ -90,6 +158,27 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the range of maximum transmission units (MTU) that path MTU discovery will use
+    ///
+    /// Paths start with the `min_mtu` and probing never exceeds the `max_mtu`, which is useful to
+    /// avoid probing for jumbo frames on paths known not to support them. When a black hole is
+    /// detected, paths fall back to QUIC's minimum datagram size of 1200 bytes. Both values
+    /// include the IP and UDP headers, and the `min_mtu` must be large enough to carry the
+    /// minimum datagram size.
+    pub fn with_mtu_range(mut self, min_mtu: u16, max_mtu: u16) -> io::Result<Self> {
+        if min_mtu > max_mtu {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "the min_mtu must not exceed the max_mtu",
+            ));
+        }
+
+        self.min_mtu = min_mtu
+            .try_into()
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        self.with_max_mtu(max_mtu)
+    }
+
     /// Disables Generic Segmentation Offload (GSO)
     ///
     /// By default, GSO will be used unless the platform does not support it or an attempt to use
@@ -99,6 +188,54 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the maximum number of segments sent in a single Generic Segmentation Offload (GSO)
+    /// transmission
+    ///
+    /// This can be used to limit the size of GSO bursts for NIC drivers which don't handle large
+    /// bursts well. Setting the value to `1` disables GSO. On platforms without GSO support, only
+    /// `1` is accepted.
+    pub fn with_max_segments(mut self, max_segments: usize) -> io::Result<Self> {
+        self.max_segments = max_segments
+            .try_into()
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        Ok(self)
+    }
+
+    /// Sets the number of message slots in the receive ring
+    ///
+    /// Datagrams received from the socket are buffered in the ring until the endpoint processes
+    /// them. Each slot is allocated with the `max_mtu`. The ring can have at most 65536 slots.
+    pub fn with_rx_ring_size(mut self, messages: usize) -> io::Result<Self> {
+        self.rx_ring_size = Some(ring_size(messages)?);
+        Ok(self)
+    }
+
+    /// Sets the number of message slots in the transmit ring
+    ///
+    /// Datagrams produced by the endpoint are buffered in the ring until they are sent on the
+    /// socket. Each slot is allocated with the `max_mtu`. The ring can have at most 65536 slots.
+    pub fn with_tx_ring_size(mut self, messages: usize) -> io::Result<Self> {
+        self.tx_ring_size = Some(ring_size(messages)?);
+        Ok(self)
+    }
+
+    /// Sets the maximum number of messages sent or received in a single system call
+    ///
+    /// Smaller batches hand datagrams to the endpoint and the network sooner, while larger
+    /// batches reduce the number of system calls. By default, a batch is only limited by the
+    /// size of the ring and the platform. This has no effect on platforms which send and receive
+    /// datagrams one at a time.
+    pub fn with_max_batch_size(mut self, messages: usize) -> io::Result<Self> {
+        if messages == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the max batch size must be at least 1",
+            ));
+        }
+        self.max_batch_size = Some(messages);
+        Ok(self)
+    }
+
     /// Enables the port reuse (SO_REUSEPORT) socket option
     pub fn with_reuse_port(mut self) -> io::Result<Self> {
         if !cfg!(unix) {
@@ -111,7 +248,172 @@ impl Builder {
         Ok(self)
     }
 
+    /// Enables reporting of the IPv4 TTL or IPv6 hop limit of each received datagram
+    ///
+    /// The reported value is included in the `DatagramReceived` connection event.
+    pub fn with_ttl_reporting(mut self) -> io::Result<Self> {
+        if !cfg!(s2n_quic_platform_ttl) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ttl reporting is not supported on the current platform",
+            ));
+        }
+        self.ttl_reporting = true;
+        Ok(self)
+    }
+
+    /// Enables kernel receive timestamps (SO_TIMESTAMPNS) for each received datagram
+    ///
+    /// RTT samples are taken from the time the kernel received the acknowledging datagram, rather
+    /// than when the endpoint read it from the socket. This keeps the time spent in the socket
+    /// buffer under load out of RTT samples.
+    pub fn with_rx_timestamps(mut self) -> io::Result<Self> {
+        if !cfg!(s2n_quic_platform_rx_timestamp) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "rx timestamps are not supported on the current platform",
+            ));
+        }
+        self.rx_timestamps = true;
+        Ok(self)
+    }
+
+    /// Sets a callback which is called with each outgoing datagram and its destination before
+    /// it is queued for transmission
+    ///
+    /// The callback can inspect or rewrite the datagram. Returning `false` drops the datagram,
+    /// which the connection will detect as lost. This is intended for test harnesses and packet
+    /// inspection.
+    pub fn with_outbound_datagram_filter<F>(mut self, filter: F) -> io::Result<Self>
+    where
+        F: 'static + FnMut(&mut [u8], &std::net::SocketAddr) -> bool + Send,
+    {
+        self.outbound_datagram_filter = Some(filter::OutboundDatagramFilter::new(filter));
+        Ok(self)
+    }
+
+    /// Transmits the datagrams queued in `sender` along with the endpoint's datagrams
+    ///
+    /// This allows handlers built on the lower layers to send packets without going through a
+    /// connection, e.g. to respond to packets statelessly.
+    pub fn with_raw_sender(mut self, sender: raw::RawSender) -> io::Result<Self> {
+        self.raw_sender = Some(sender);
+        Ok(self)
+    }
+
+    /// Transmits and receives datagrams with io_uring instead of `sendmmsg` and `recvmmsg`
+    ///
+    /// Datagrams are received with a multishot `recvmsg` operation into buffers registered with
+    /// the ring, so receiving doesn't make any system calls while datagrams keep arriving. Each
+    /// batch of transmitted datagrams is submitted with a single system call. This requires the
+    /// `io-uring` feature and is only supported on Linux 6.0 and later.
+    pub fn with_io_uring(mut self) -> io::Result<Self> {
+        if !cfg!(s2n_quic_platform_io_uring) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "io_uring is not supported on the current platform",
+            ));
+        }
+        self.io_uring = true;
+        Ok(self)
+    }
+
+    /// Attaches transmit times to paced datagrams with `SO_TXTIME`
+    ///
+    /// The kernel holds each datagram until its transmit time, rather than the endpoint waking
+    /// up to send it. This allows paced datagrams to be sent shortly before the pacer would
+    /// release them. This requires the `fq` or `etf` queueing discipline on the outgoing
+    /// interface and is only supported on Linux.
+    pub fn with_txtime(mut self) -> io::Result<Self> {
+        if !cfg!(s2n_quic_platform_txtime) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "txtime is not supported on the current platform",
+            ));
+        }
+        self.txtime = true;
+        Ok(self)
+    }
+
+    /// Sets the IPv6 flow label of each transmitted datagram with `IPV6_FLOWINFO_SEND`
+    ///
+    /// Each path is labeled separately, so routers which hash the flow label spread the
+    /// connections of a single host across routes. The labels are derived from a random key
+    /// which is generated for each endpoint. Datagrams sent to IPv4 addresses don't carry a flow
+    /// label. This is only supported on Linux.
+    pub fn with_flow_labels(mut self) -> io::Result<Self> {
+        if !cfg!(s2n_quic_platform_flow_label) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "flow labels are not supported on the current platform",
+            ));
+        }
+        self.flow_labels = true;
+        Ok(self)
+    }
+
+    /// Binds the sockets to the network interface named `interface`, e.g. `eth1`
+    ///
+    /// Datagrams are only sent and received on the interface, regardless of the routing table,
+    /// which is useful on multi-homed hosts. Binding requires `CAP_NET_RAW` on Linux kernels
+    /// older than 5.7. This is only supported on Linux, Android and macOS.
+    pub fn with_interface(mut self, interface: &str) -> io::Result<Self> {
+        if !cfg!(s2n_quic_platform_bind_interface) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "binding to an interface is not supported on the current platform",
+            ));
+        }
+
+        // interface names are limited to IFNAMSIZ bytes, including the null terminator
+        if interface.is_empty() || interface.len() >= 16 || interface.contains('\0') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid interface name",
+            ));
+        }
+
+        self.interface = Some(interface.to_string());
+        Ok(self)
+    }
+
     pub fn build(self) -> io::Result<Io> {
         Ok(Io { builder: self })
     }
 }
+
+/// The largest number of message slots in a ring
+const MAX_RING_SIZE: usize = 1 << 16;
+
+/// Checks that a ring can hold at least one message and isn't unreasonably large
+fn ring_size(messages: usize) -> io::Result<usize> {
+    if messages == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the ring size must be at least 1",
+        ));
+    }
+
+    if messages > MAX_RING_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("the ring size must be at most {MAX_RING_SIZE}"),
+        ));
+    }
+
+    Ok(messages)
+}
+
+/// Checks that a socket provided by the application can be used for datagrams
+fn datagram_socket(socket: std::net::UdpSocket) -> io::Result<socket2::Socket> {
+    let socket: socket2::Socket = socket.into();
+
+    if socket.r#type()? != socket2::Type::DGRAM {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the provided socket is not a datagram socket",
+        ));
+    }
+
+    Ok(socket)
+}
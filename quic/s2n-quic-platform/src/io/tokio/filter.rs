@@ -0,0 +1,139 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Passes each outgoing datagram to an application-provided callback before it is queued for
+//! transmission
+
+use core::{fmt, time::Duration};
+use s2n_quic_core::{inet::ExplicitCongestionNotification, io::tx, path::Handle as _};
+
+type Callback = dyn FnMut(&mut [u8], &std::net::SocketAddr) -> bool + Send;
+
+/// Inspects, rewrites, or drops outgoing datagrams
+pub struct OutboundDatagramFilter(Box<Callback>);
+
+impl OutboundDatagramFilter {
+    pub fn new<F>(filter: F) -> Self
+    where
+        F: 'static + FnMut(&mut [u8], &std::net::SocketAddr) -> bool + Send,
+    {
+        Self(Box::new(filter))
+    }
+}
+
+impl fmt::Debug for OutboundDatagramFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OutboundDatagramFilter").finish()
+    }
+}
+
+/// Wraps a transmission queue and applies the filter to each pushed message
+pub struct Queue<'a, Q> {
+    queue: &'a mut Q,
+    filter: &'a mut OutboundDatagramFilter,
+}
+
+impl<'a, Q> Queue<'a, Q> {
+    #[inline]
+    pub fn new(queue: &'a mut Q, filter: &'a mut OutboundDatagramFilter) -> Self {
+        Self { queue, filter }
+    }
+}
+
+impl<'a, Q: tx::Queue> tx::Queue for Queue<'a, Q> {
+    type Handle = Q::Handle;
+
+    const SUPPORTS_ECN: bool = Q::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Q::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Q::SUPPORTS_FLOW_LABELS;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        self.queue.push(Message {
+            message,
+            filter: &mut *self.filter,
+        })
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.queue.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
+    #[inline]
+    fn max_transmit_delay(&self) -> core::time::Duration {
+        self.queue.max_transmit_delay()
+    }
+}
+
+struct Message<'a, M> {
+    message: M,
+    filter: &'a mut OutboundDatagramFilter,
+}
+
+impl<'a, M: tx::Message> tx::Message for Message<'a, M> {
+    type Handle = M::Handle;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        self.message.path_handle()
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        self.message.ecn()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> Duration {
+        self.message.delay()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        self.message.ipv6_flow_label()
+    }
+
+    #[inline]
+    fn dscp(&mut self) -> u8 {
+        self.message.dscp()
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool {
+        self.message.can_gso(segment_len, segment_count)
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        buffer: tx::PayloadBuffer,
+        gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        // Safety: the message writes to the buffer through a new `PayloadBuffer` and the filter
+        // only receives the bytes which were written
+        let buffer = unsafe { buffer.into_mut_slice() };
+        let len = self
+            .message
+            .write_payload(tx::PayloadBuffer::new(&mut *buffer), gso_offset)?;
+
+        let remote_address: std::net::SocketAddr =
+            self.message.path_handle().remote_address().0.unmap().into();
+        let payload = buffer.get_mut(..len).ok_or(tx::Error::UndersizedBuffer)?;
+
+        // the datagram is dropped if the filter rejects it
+        if (self.filter.0)(payload, &remote_address) {
+            Ok(len)
+        } else {
+            Err(tx::Error::EmptyPayload)
+        }
+    }
+}
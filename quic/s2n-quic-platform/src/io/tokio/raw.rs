@@ -0,0 +1,192 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sends datagrams provided by the application through the same transmission queue as the
+//! endpoint
+//!
+//! This is intended for handlers which don't need the connection machinery, e.g. a stateless
+//! responder which replies to packets without creating any connection state.
+
+use core::task::{Context, Poll, Waker};
+use s2n_quic_core::{
+    endpoint::{self, CloseError},
+    inet::SocketAddress,
+    io::{rx, tx},
+    path::{self, MaxMtu, MinMtu},
+    time::{Clock, Timestamp},
+};
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Arc, Mutex},
+};
+
+/// The maximum number of datagrams which can be waiting for transmission
+const MAX_QUEUED_DATAGRAMS: usize = 1024;
+
+/// Queues raw datagrams to be sent by an IO provider
+///
+/// The datagrams are written to the transmission queue along with the ones produced by the
+/// endpoint, so they are batched and paced with the normal send path.
+#[derive(Clone, Debug, Default)]
+pub struct RawSender {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    datagrams: VecDeque<(SocketAddress, Vec<u8>)>,
+    waker: Option<Waker>,
+    should_wake: bool,
+}
+
+impl RawSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `payload` to be sent to `remote_address`
+    ///
+    /// An error with the `WouldBlock` kind is returned if too many datagrams are waiting for
+    /// transmission. Datagrams which don't fit in a single packet are dropped by the IO provider.
+    pub fn send(&self, remote_address: std::net::SocketAddr, payload: &[u8]) -> io::Result<()> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "raw sender lock poisoned"))?;
+
+        if state.datagrams.len() >= MAX_QUEUED_DATAGRAMS {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "too many datagrams are waiting for transmission",
+            ));
+        }
+
+        state
+            .datagrams
+            .push_back((remote_address.into(), payload.to_vec()));
+
+        // wake up the event loop so the datagram is transmitted
+        state.should_wake = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+
+    /// Writes the queued datagrams into `queue` until it runs out of capacity
+    fn transmit<Q: tx::Queue>(&self, queue: &mut Q) {
+        let mut state = if let Ok(state) = self.state.lock() {
+            state
+        } else {
+            return;
+        };
+
+        while let Some((remote_address, payload)) = state.datagrams.front() {
+            let handle = <Q::Handle as path::Handle>::from_remote_address((*remote_address).into());
+
+            match queue.push((handle, payload.as_slice())) {
+                Err(tx::Error::AtCapacity) => break,
+                // the datagram is discarded if it was either sent or can't ever be sent
+                _ => {
+                    state.datagrams.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if datagrams were queued since the last call
+    fn poll_wakeup(&self, cx: &mut Context) -> bool {
+        let mut state = if let Ok(state) = self.state.lock() {
+            state
+        } else {
+            return false;
+        };
+
+        if core::mem::take(&mut state.should_wake) {
+            return true;
+        }
+
+        state.waker = Some(cx.waker().clone());
+        false
+    }
+}
+
+/// Wraps an endpoint to transmit the datagrams queued in a [`RawSender`]
+#[derive(Debug)]
+pub(super) struct Endpoint<E> {
+    endpoint: E,
+    sender: Option<RawSender>,
+}
+
+impl<E> Endpoint<E> {
+    #[inline]
+    pub fn new(endpoint: E, sender: Option<RawSender>) -> Self {
+        Self { endpoint, sender }
+    }
+}
+
+impl<E: endpoint::Endpoint> endpoint::Endpoint for Endpoint<E> {
+    type PathHandle = E::PathHandle;
+    type Subscriber = E::Subscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = E::ENDPOINT_TYPE;
+
+    #[inline]
+    fn receive<Rx, C>(&mut self, rx: &mut Rx, clock: &C)
+    where
+        Rx: rx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        self.endpoint.receive(rx, clock)
+    }
+
+    #[inline]
+    fn transmit<Tx, C>(&mut self, tx: &mut Tx, clock: &C)
+    where
+        Tx: tx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        if let Some(sender) = self.sender.as_ref() {
+            sender.transmit(tx);
+        }
+
+        self.endpoint.transmit(tx, clock)
+    }
+
+    #[inline]
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        cx: &mut Context<'_>,
+        clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        let wakeups = self.endpoint.poll_wakeups(cx, clock);
+
+        if wakeups.is_pending() && self.sender.as_ref().map_or(false, |s| s.poll_wakeup(cx)) {
+            return Poll::Ready(Ok(0));
+        }
+
+        wakeups
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Timestamp> {
+        self.endpoint.timeout()
+    }
+
+    #[inline]
+    fn set_max_mtu(&mut self, max_mtu: MaxMtu) {
+        self.endpoint.set_max_mtu(max_mtu)
+    }
+
+    #[inline]
+    fn set_min_mtu(&mut self, min_mtu: MinMtu) {
+        self.endpoint.set_min_mtu(min_mtu)
+    }
+
+    #[inline]
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        self.endpoint.subscriber()
+    }
+}
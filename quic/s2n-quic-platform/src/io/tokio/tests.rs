@@ -13,7 +13,14 @@ use s2n_quic_core::{
     path::Handle as _,
     time::{Clock, Duration, Timestamp},
 };
-use std::{collections::BTreeMap, net::ToSocketAddrs};
+use std::{
+    collections::BTreeMap,
+    net::ToSocketAddrs,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 struct TestEndpoint<const IS_SERVER: bool> {
     handle: PathHandle,
@@ -176,9 +183,17 @@ async fn test<A: ToSocketAddrs>(
     client_rx_addr: A,
     client_tx_addr: Option<A>,
 ) -> io::Result<()> {
-    let (server_io, server_addr) = runtime(server_rx_addr, server_tx_addr).await?;
-    let (client_io, client_addr) = runtime(client_rx_addr, client_tx_addr).await?;
+    let server = runtime(server_rx_addr, server_tx_addr).await?;
+    let client = runtime(client_rx_addr, client_tx_addr).await?;
+
+    transfer(server, client).await
+}
 
+/// Exchanges messages between a server and client endpoint running on the provided IO runtimes
+async fn transfer(
+    (server_io, server_addr): (super::Io, SocketAddress),
+    (client_io, client_addr): (super::Io, SocketAddress),
+) -> io::Result<()> {
     let server_endpoint = {
         let mut handle = PathHandle::from_remote_address(client_addr.into());
         handle.local_address = server_addr.into();
@@ -204,6 +219,16 @@ async fn test<A: ToSocketAddrs>(
     Ok(())
 }
 
+/// Creates a runtime which adopts a socket created with tokio
+async fn tokio_socket_runtime(addr: &str) -> io::Result<(super::Io, SocketAddress)> {
+    let socket = UdpSocket::bind(addr).await?;
+    let addr = socket.local_addr()?;
+
+    let io = Io::builder().with_tokio_socket(socket)?.build()?;
+
+    Ok((io, addr.into()))
+}
+
 static IPV4_LOCALHOST: &str = "127.0.0.1:0";
 static IPV6_LOCALHOST: &str = "[::1]:0";
 
@@ -254,3 +279,449 @@ async fn ipv6_two_socket_test() -> io::Result<()> {
         other => other,
     }
 }
+
+/// Checks that a single IPv6 socket can exchange datagrams with an IPv4 peer
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn dual_stack_test() -> io::Result<()> {
+    let (server_io, server_addr) = match runtime("[::]:0", None).await {
+        Err(err) if err.kind() == io::ErrorKind::AddrNotAvailable => {
+            eprintln!("The current environment does not support IPv6; skipping");
+            return Ok(());
+        }
+        other => other?,
+    };
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+
+    // the client reaches the server over IPv4
+    let server_v4_addr: SocketAddress = ("127.0.0.1", server_addr.port())
+        .to_socket_addrs()?
+        .next()
+        .unwrap()
+        .into();
+
+    let server_endpoint = {
+        let handle = PathHandle::from_remote_address(client_addr.into());
+        TestEndpoint::<true>::new(handle)
+    };
+
+    let client_endpoint = {
+        let mut handle = PathHandle::from_remote_address(server_v4_addr.into());
+        handle.local_address = client_addr.into();
+        TestEndpoint::<false>::new(handle)
+    };
+
+    let (server_task, _) = server_io.start(server_endpoint)?;
+    let (client_task, _) = client_io.start(client_endpoint)?;
+
+    tokio::time::timeout(core::time::Duration::from_secs(10), client_task).await??;
+
+    server_task.abort();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tokio_socket_test() -> io::Result<()> {
+    let server = tokio_socket_runtime(IPV4_LOCALHOST).await?;
+    let client = tokio_socket_runtime(IPV4_LOCALHOST).await?;
+
+    transfer(server, client).await
+}
+
+/// Creates a runtime which adopts the file descriptor of a bound socket
+#[cfg(unix)]
+async fn fd_runtime(addr: &str) -> io::Result<(super::Io, SocketAddress)> {
+    let socket = std::net::UdpSocket::bind(addr)?;
+    let addr = socket.local_addr()?;
+    let fd = std::os::unix::io::OwnedFd::from(socket);
+
+    let io = Io::builder().with_rx_fd(fd)?.build()?;
+
+    Ok((io, addr.into()))
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn fd_test() -> io::Result<()> {
+    let server = fd_runtime(IPV4_LOCALHOST).await?;
+    let client = fd_runtime(IPV4_LOCALHOST).await?;
+
+    transfer(server, client).await
+}
+
+#[cfg(unix)]
+#[test]
+fn stream_fd_test() -> io::Result<()> {
+    let listener = std::net::TcpListener::bind(IPV4_LOCALHOST)?;
+    let fd = std::os::unix::io::OwnedFd::from(listener);
+
+    let err = Io::builder().with_rx_fd(fd).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn outbound_datagram_filter_test() -> io::Result<()> {
+    let server = runtime(IPV4_LOCALHOST, None).await?;
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let client_io = {
+        let dropped = dropped.clone();
+        let mut should_drop = false;
+        let server_addr: std::net::SocketAddr = server.1.into();
+
+        client_io
+            .builder
+            .with_outbound_datagram_filter(move |_payload, remote_address| {
+                assert_eq!(*remote_address, server_addr);
+
+                // drop every other datagram
+                should_drop = !should_drop;
+                if should_drop {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                !should_drop
+            })?
+            .build()?
+    };
+
+    // the client retransmits the dropped messages until they're all echoed by the server
+    transfer(server, (client_io, client_addr)).await?;
+
+    assert!(dropped.load(Ordering::Relaxed) > 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn max_segments_test() -> io::Result<()> {
+    assert!(Io::builder().with_max_segments(0).is_err());
+    assert!(Io::builder()
+        .with_max_segments(usize::from(gso::MaxSegments::MAX) + 1)
+        .is_err());
+
+    let (server_io, server_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let server_io = server_io.builder.with_max_segments(1)?.build()?;
+
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let max_segments = usize::from(gso::MaxSegments::MAX).min(2);
+    let client_io = client_io.builder.with_max_segments(max_segments)?.build()?;
+
+    transfer((server_io, server_addr), (client_io, client_addr)).await
+}
+
+#[tokio::test]
+async fn batching_test() -> io::Result<()> {
+    assert!(Io::builder().with_rx_ring_size(0).is_err());
+    assert!(Io::builder().with_tx_ring_size(0).is_err());
+    assert!(Io::builder().with_rx_ring_size(usize::MAX).is_err());
+    assert!(Io::builder().with_tx_ring_size(usize::MAX).is_err());
+    assert!(Io::builder().with_max_batch_size(0).is_err());
+
+    let (server_io, server_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let server_io = server_io
+        .builder
+        .with_rx_ring_size(4)?
+        .with_tx_ring_size(4)?
+        .with_max_batch_size(1)?
+        .build()?;
+
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let client_io = client_io
+        .builder
+        .with_rx_ring_size(8192)?
+        .with_tx_ring_size(8192)?
+        .build()?;
+
+    transfer((server_io, server_addr), (client_io, client_addr)).await
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn reuse_port_group_test() -> io::Result<()> {
+    let workers = 3;
+    let sockets = bind_reuse_port_group(IPV4_LOCALHOST.parse().unwrap(), workers)?;
+    assert_eq!(sockets.len(), workers as usize);
+
+    let addr = sockets[0].local_addr()?;
+    for socket in &sockets {
+        assert_eq!(socket.local_addr()?, addr);
+        socket.set_read_timeout(Some(core::time::Duration::from_secs(1)))?;
+    }
+
+    let client = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+
+    for dcid in 0u8..=255 {
+        let expected = (dcid % workers) as usize;
+
+        // short header
+        client.send_to(&[0b0100_0000, dcid, 1, 2, 3], addr)?;
+        // long header
+        client.send_to(&[0b1100_0000, 0, 0, 0, 1, 1, dcid], addr)?;
+
+        let mut buffer = [0u8; 16];
+        for _ in 0..2 {
+            let (len, _) = sockets[expected].recv_from(&mut buffer)?;
+            assert!(len == 5 || len == 7);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn rx_socket_drops_test() -> io::Result<()> {
+    let socket = syscall::bind_udp(IPV4_LOCALHOST, false)?;
+    // the kernel raises the size to its minimum
+    socket.set_recv_buffer_size(1)?;
+    let addr = socket.local_addr()?.as_socket().unwrap();
+
+    let initial = syscall::rx_socket_drops(&socket).unwrap();
+
+    // overflow the receive buffer without reading from it
+    let client = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+    for _ in 0..100 {
+        client.send_to(&[0; 1000], addr)?;
+    }
+
+    let drops = syscall::rx_socket_drops(&socket).unwrap();
+    assert!(drops > initial, "{drops} > {initial}");
+
+    Ok(())
+}
+
+#[test]
+fn reuse_port_group_invalid_test() {
+    assert!(bind_reuse_port_group(IPV4_LOCALHOST.parse().unwrap(), 0).is_err());
+}
+
+#[cfg(s2n_quic_platform_txtime)]
+#[tokio::test]
+async fn txtime_test() -> io::Result<()> {
+    let (server_io, server_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let server_io = server_io.builder.with_txtime()?.build()?;
+
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let client_io = client_io.builder.with_txtime()?.build()?;
+
+    transfer((server_io, server_addr), (client_io, client_addr)).await
+}
+
+#[test]
+fn rx_timestamps_builder_test() {
+    let result = Io::builder().with_rx_timestamps();
+    assert_eq!(result.is_ok(), cfg!(s2n_quic_platform_rx_timestamp));
+}
+
+#[test]
+fn txtime_builder_test() {
+    let result = Io::builder().with_txtime();
+    assert_eq!(result.is_ok(), cfg!(s2n_quic_platform_txtime));
+}
+
+#[test]
+fn interface_builder_test() {
+    let result = Io::builder().with_interface("eth1");
+    assert_eq!(result.is_ok(), cfg!(s2n_quic_platform_bind_interface));
+
+    for interface in ["", "interface-name-too-long", "eth\01"] {
+        assert!(Io::builder().with_interface(interface).is_err());
+    }
+}
+
+#[cfg(s2n_quic_platform_io_uring)]
+#[tokio::test]
+async fn io_uring_test() -> io::Result<()> {
+    async fn io_uring_runtime() -> io::Result<(super::Io, SocketAddress)> {
+        let (io, addr) = runtime(IPV4_LOCALHOST, None).await?;
+        let io = io.builder.with_io_uring()?.build()?;
+        Ok((io, addr))
+    }
+
+    let server = io_uring_runtime().await?;
+    let client = io_uring_runtime().await?;
+
+    match transfer(server, client).await {
+        // io_uring can be disabled by the kernel or a seccomp policy, and kernels before 6.0
+        // don't support multishot recvmsg
+        Err(err)
+            if matches!(
+                err.raw_os_error(),
+                Some(libc::ENOSYS) | Some(libc::EPERM) | Some(libc::EINVAL)
+            ) =>
+        {
+            eprintln!("The current environment does not support io_uring; skipping");
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+#[test]
+fn io_uring_builder_test() {
+    let result = Io::builder().with_io_uring();
+    assert_eq!(result.is_ok(), cfg!(s2n_quic_platform_io_uring));
+}
+
+/// Forwards each received datagram to a handler outside of the endpoint
+struct ForwardingEndpoint {
+    datagrams: tokio::sync::mpsc::UnboundedSender<(std::net::SocketAddr, Vec<u8>)>,
+    subscriber: NoopSubscriber,
+}
+
+impl Endpoint for ForwardingEndpoint {
+    type PathHandle = PathHandle;
+    type Subscriber = NoopSubscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
+
+    fn transmit<Tx: tx::Queue<Handle = PathHandle>, C: Clock>(
+        &mut self,
+        _queue: &mut Tx,
+        _clock: &C,
+    ) {
+        // everything is sent by the handler
+    }
+
+    fn receive<Rx: rx::Queue<Handle = PathHandle>, C: Clock>(
+        &mut self,
+        queue: &mut Rx,
+        _clock: &C,
+    ) {
+        queue.for_each(|header, payload| {
+            let remote_address = header.path.remote_address().0.unmap().into();
+            let _ = self.datagrams.send((remote_address, payload.to_vec()));
+        });
+    }
+
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        _cx: &mut Context<'_>,
+        _clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        Poll::Pending
+    }
+
+    fn timeout(&self) -> Option<Timestamp> {
+        None
+    }
+
+    fn set_max_mtu(&mut self, _max_mtu: MaxMtu) {
+        // noop
+    }
+
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        &mut self.subscriber
+    }
+}
+
+#[tokio::test]
+async fn raw_sender_test() -> io::Result<()> {
+    const UNSUPPORTED_VERSION: u32 = 0x0a0a_0a0a;
+    let client_dcid = [1u8; 8];
+    let client_scid = [2u8; 8];
+
+    let (server_io, server_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let sender = RawSender::new();
+    let server_io = server_io.builder.with_raw_sender(sender.clone())?.build()?;
+
+    let (datagrams, mut received) = tokio::sync::mpsc::unbounded_channel();
+    let (server_task, _) = server_io.start(ForwardingEndpoint {
+        datagrams,
+        subscriber: Default::default(),
+    })?;
+
+    // the handler responds to each Initial with a Version Negotiation packet
+    let handler = tokio::spawn(async move {
+        while let Some((remote_address, initial)) = received.recv().await {
+            let dcid_len = initial[5] as usize;
+            let dcid = &initial[6..6 + dcid_len];
+            let scid_len = initial[6 + dcid_len] as usize;
+            let scid = &initial[7 + dcid_len..7 + dcid_len + scid_len];
+
+            let mut version_negotiation = vec![0b1100_0000];
+            version_negotiation.extend_from_slice(&0u32.to_be_bytes());
+            version_negotiation.push(scid.len() as u8);
+            version_negotiation.extend_from_slice(scid);
+            version_negotiation.push(dcid.len() as u8);
+            version_negotiation.extend_from_slice(dcid);
+            version_negotiation.extend_from_slice(&1u32.to_be_bytes());
+
+            sender.send(remote_address, &version_negotiation).unwrap();
+        }
+    });
+
+    // craft an Initial with a version that the server doesn't support
+    let mut initial = vec![0b1100_0000];
+    initial.extend_from_slice(&UNSUPPORTED_VERSION.to_be_bytes());
+    initial.push(client_dcid.len() as u8);
+    initial.extend_from_slice(&client_dcid);
+    initial.push(client_scid.len() as u8);
+    initial.extend_from_slice(&client_scid);
+    initial.resize(1200, 0);
+
+    let client = UdpSocket::bind(IPV4_LOCALHOST).await?;
+    let server_addr: std::net::SocketAddr = server_addr.into();
+    client.send_to(&initial, server_addr).await?;
+
+    let mut response = [0u8; 1500];
+    let (len, from) = tokio::time::timeout(
+        core::time::Duration::from_secs(10),
+        client.recv_from(&mut response),
+    )
+    .await??;
+    let response = &response[..len];
+
+    assert_eq!(from, server_addr);
+    // a Version Negotiation packet has a version of 0
+    assert_eq!(&response[1..5], &[0; 4]);
+    // the connection IDs are flipped in the response
+    assert_eq!(response[5] as usize, client_scid.len());
+    assert_eq!(&response[6..14], &client_scid);
+    assert_eq!(response[14] as usize, client_dcid.len());
+    assert_eq!(&response[15..23], &client_dcid);
+    assert_eq!(&response[23..], &1u32.to_be_bytes());
+
+    handler.abort();
+    server_task.abort();
+
+    Ok(())
+}
+
+#[cfg(all(target_os = "linux", s2n_quic_platform_pktinfo))]
+#[tokio::test]
+async fn local_address_test() -> io::Result<()> {
+    // bind to the wildcard address so the source address is chosen by the packet info
+    let (io, _addr) = runtime("0.0.0.0:0", None).await?;
+
+    let peer = UdpSocket::bind(IPV4_LOCALHOST).await?;
+    let peer_addr: SocketAddress = peer.local_addr()?.into();
+
+    // the entire 127.0.0.0/8 range is assigned to the loopback interface on linux
+    let source: std::net::SocketAddr = "127.0.0.2:0".parse().unwrap();
+
+    let endpoint = {
+        let mut handle = PathHandle::from_remote_address(peer_addr.into());
+        handle.local_address = SocketAddress::from(source).into();
+        TestEndpoint::<false>::new(handle)
+    };
+
+    let (task, _) = io.start(endpoint)?;
+
+    let mut payload = [0u8; 1500];
+    let (_len, from) = tokio::time::timeout(
+        core::time::Duration::from_secs(10),
+        peer.recv_from(&mut payload),
+    )
+    .await??;
+
+    assert_eq!(from.ip(), source.ip());
+
+    task.abort();
+
+    Ok(())
+}
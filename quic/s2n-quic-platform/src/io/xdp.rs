@@ -70,6 +70,10 @@ pub struct Provider<Rx, Tx> {
     tx: Tx,
     max_mtu: MaxMtu,
     handle: Option<tokio::runtime::Handle>,
+    #[cfg(feature = "xdp-program")]
+    program: Option<program::Program>,
+    #[cfg(feature = "xdp-program")]
+    sockets: Vec<(u32, socket::Fd)>,
 }
 
 impl Provider<(), ()> {
@@ -93,8 +97,22 @@ where
             rx,
             max_mtu,
             handle,
+            #[cfg(feature = "xdp-program")]
+            program,
+            #[cfg(feature = "xdp-program")]
+            sockets,
         } = self;
 
+        // direct the packets received on each queue to the provider's sockets
+        #[cfg(feature = "xdp-program")]
+        let program = if let Some(mut program) = program {
+            let sockets = sockets.iter().map(|(queue_id, socket)| (*queue_id, socket));
+            program.set_sockets(sockets)?;
+            Some(program)
+        } else {
+            None
+        };
+
         // tell the endpoint what our MTU is
         endpoint.set_max_mtu(max_mtu);
 
@@ -109,11 +127,21 @@ where
             tx,
         };
 
+        let event_loop = async move {
+            event_loop.start().await;
+
+            // stop directing packets to the sockets once the endpoint has shut down
+            #[cfg(feature = "xdp-program")]
+            if let Some(mut program) = program {
+                let _ = program.set_sockets(core::iter::empty());
+            }
+        };
+
         // spawn the event loop on to the tokio handle
         let task = if let Some(handle) = handle {
-            handle.spawn(event_loop.start())
+            handle.spawn(event_loop)
         } else {
-            tokio::spawn(event_loop.start())
+            tokio::spawn(event_loop)
         };
 
         Ok((task, SocketAddress::default()))
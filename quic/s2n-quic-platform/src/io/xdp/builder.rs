@@ -20,6 +20,10 @@ pub struct Builder<Rx = (), Tx = ()> {
     tx: Tx,
     max_mtu: MaxMtu,
     handle: Option<Handle>,
+    #[cfg(feature = "xdp-program")]
+    program: Option<super::program::Program>,
+    #[cfg(feature = "xdp-program")]
+    sockets: Vec<(u32, super::socket::Fd)>,
 }
 
 impl Default for Builder<(), ()> {
@@ -29,6 +33,10 @@ impl Default for Builder<(), ()> {
             tx: (),
             max_mtu: MaxMtu::try_from(DEFAULT_FRAME_SIZE as u16 - MIN_FRAME_OVERHEAD).unwrap(),
             handle: None,
+            #[cfg(feature = "xdp-program")]
+            program: None,
+            #[cfg(feature = "xdp-program")]
+            sockets: Vec::new(),
         }
     }
 }
@@ -46,6 +54,23 @@ impl<Rx, Tx> Builder<Rx, Tx> {
         Ok(self)
     }
 
+    /// Sets the XDP program which directs packets to the AF_XDP sockets of the provider
+    ///
+    /// The sockets which were added with [`Builder::with_socket`] are set in the socket map of the
+    /// program when the provider is started and removed once the endpoint shuts down.
+    #[cfg(feature = "xdp-program")]
+    pub fn with_program(mut self, program: super::program::Program) -> Self {
+        self.program = Some(program);
+        self
+    }
+
+    /// Adds an AF_XDP socket of the provider which is bound to `queue_id` of the interface
+    #[cfg(feature = "xdp-program")]
+    pub fn with_socket(mut self, queue_id: u32, socket: super::socket::Fd) -> Self {
+        self.sockets.push((queue_id, socket));
+        self
+    }
+
     /// Sets the RX implementation for the provider
     pub fn with_rx<NewRx>(self, rx: NewRx) -> Builder<NewRx, Tx>
     where
@@ -55,6 +80,10 @@ impl<Rx, Tx> Builder<Rx, Tx> {
             tx,
             handle,
             max_mtu,
+            #[cfg(feature = "xdp-program")]
+            program,
+            #[cfg(feature = "xdp-program")]
+            sockets,
             ..
         } = self;
         Builder {
@@ -62,6 +91,10 @@ impl<Rx, Tx> Builder<Rx, Tx> {
             tx,
             handle,
             max_mtu,
+            #[cfg(feature = "xdp-program")]
+            program,
+            #[cfg(feature = "xdp-program")]
+            sockets,
         }
     }
 
@@ -74,6 +107,10 @@ impl<Rx, Tx> Builder<Rx, Tx> {
             rx,
             handle,
             max_mtu,
+            #[cfg(feature = "xdp-program")]
+            program,
+            #[cfg(feature = "xdp-program")]
+            sockets,
             ..
         } = self;
         Builder {
@@ -81,6 +118,10 @@ impl<Rx, Tx> Builder<Rx, Tx> {
             tx,
             handle,
             max_mtu,
+            #[cfg(feature = "xdp-program")]
+            program,
+            #[cfg(feature = "xdp-program")]
+            sockets,
         }
     }
 }
@@ -96,12 +137,20 @@ where
             tx,
             handle,
             max_mtu,
+            #[cfg(feature = "xdp-program")]
+            program,
+            #[cfg(feature = "xdp-program")]
+            sockets,
         } = self;
         super::Provider {
             rx,
             tx,
             handle,
             max_mtu,
+            #[cfg(feature = "xdp-program")]
+            program,
+            #[cfg(feature = "xdp-program")]
+            sockets,
         }
     }
 }
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use core::{cell::UnsafeCell, ffi::c_void, pin::Pin};
+use core::{cell::UnsafeCell, ffi::c_void, pin::Pin, time::Duration};
 use s2n_quic_core::{inet::datagram, io::tx, path};
 
 #[cfg(any(s2n_quic_platform_socket_msg, s2n_quic_platform_socket_mmsg))]
@@ -76,6 +76,20 @@ pub trait Message: 'static + Copy {
         panic!("cannot use GSO on the current platform");
     }
 
+    /// Sets the time at which the kernel should transmit the message, relative to now
+    ///
+    /// This should only be called for sockets configured with `SO_TXTIME`.
+    fn set_transmit_delay(&mut self, _delay: Duration) {
+        panic!("cannot use SO_TXTIME on the current platform");
+    }
+
+    /// Sets the IPv6 flow label for the message
+    ///
+    /// This should only be called for sockets configured with `IPV6_FLOWINFO_SEND`.
+    fn set_ipv6_flow_label(&mut self, _flow_label: u32) {
+        panic!("cannot set IPv6 flow labels on the current platform");
+    }
+
     /// Resets the message for future use
     ///
     /// # Safety
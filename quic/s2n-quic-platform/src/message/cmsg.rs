@@ -8,15 +8,21 @@ use s2n_quic_core::inet::{AncillaryData, ExplicitCongestionNotification};
 
 /// The maximum number of bytes allocated for cmsg data
 ///
-/// This should be enough for UDP_SEGMENT + IP_TOS + IP_PKTINFO. It may need to be increased
-/// to allow for future control messages.
-pub const MAX_LEN: usize = 128;
+/// This should be enough for UDP_SEGMENT + IP_TOS + IP_PKTINFO + IP_TTL + SCM_TXTIME +
+/// SCM_TIMESTAMPNS. It may need to be increased to allow for future control messages.
+pub const MAX_LEN: usize = 160;
 
 #[cfg(s2n_quic_platform_gso)]
 pub type UdpGso = u16;
 #[cfg(s2n_quic_platform_gro)]
 pub type UdpGro = libc::c_int;
 pub type IpTos = libc::c_int;
+#[cfg(s2n_quic_platform_ttl)]
+pub type IpTtl = libc::c_int;
+#[cfg(s2n_quic_platform_txtime)]
+pub type TxTime = u64;
+#[cfg(s2n_quic_platform_rx_timestamp)]
+pub type RxTimestamp = libc::timespec;
 
 #[test]
 fn max_len_test() {
@@ -40,6 +46,12 @@ fn max_len_test() {
             ) as usize;
         }
 
+        // SCM_TXTIME
+        #[cfg(s2n_quic_platform_txtime)]
+        {
+            len += libc::CMSG_LEN(size_of::<TxTime>() as _) as usize;
+        }
+
         len
     }
 
@@ -63,6 +75,18 @@ fn max_len_test() {
             ) as usize;
         }
 
+        // IP_TTL
+        #[cfg(s2n_quic_platform_ttl)]
+        {
+            len += libc::CMSG_LEN(size_of::<IpTtl>() as _) as usize;
+        }
+
+        // SCM_TIMESTAMPNS
+        #[cfg(s2n_quic_platform_rx_timestamp)]
+        {
+            len += libc::CMSG_LEN(size_of::<RxTimestamp>() as _) as usize;
+        }
+
         len
     }
 
@@ -154,19 +178,23 @@ pub fn decode(msghdr: &libc::msghdr) -> AncillaryData {
                 | (libc::IPPROTO_IP, libc::IP_RECVTOS, cmsg_len)
                     if cmsg_len == libc::CMSG_LEN(mem::size_of::<u8>() as _) as usize =>
                 {
-                    result.ecn = ExplicitCongestionNotification::new(decode_value::<u8>(cmsg));
+                    let tos = decode_value::<u8>(cmsg);
+                    result.ecn = ExplicitCongestionNotification::new(tos);
+                    result.dscp = tos >> 2;
                 }
                 (libc::IPPROTO_IP, libc::IP_TOS, cmsg_len)
                 | (libc::IPPROTO_IP, libc::IP_RECVTOS, cmsg_len)
                     if cmsg_len == libc::CMSG_LEN(mem::size_of::<IpTos>() as _) as usize =>
                 {
                     // IP_TOS cmsgs should be 1 byte, but occasionally are reported as 4 bytes
-                    result.ecn =
-                        ExplicitCongestionNotification::new(decode_value::<IpTos>(cmsg) as u8);
+                    let tos = decode_value::<IpTos>(cmsg) as u8;
+                    result.ecn = ExplicitCongestionNotification::new(tos);
+                    result.dscp = tos >> 2;
                 }
                 (libc::IPPROTO_IPV6, libc::IPV6_TCLASS, _) => {
-                    result.ecn =
-                        ExplicitCongestionNotification::new(decode_value::<IpTos>(cmsg) as u8);
+                    let tos = decode_value::<IpTos>(cmsg) as u8;
+                    result.ecn = ExplicitCongestionNotification::new(tos);
+                    result.dscp = tos >> 2;
                 }
                 #[cfg(s2n_quic_platform_pktinfo)]
                 (libc::IPPROTO_IP, libc::IP_PKTINFO, _) => {
@@ -203,11 +231,27 @@ pub fn decode(msghdr: &libc::msghdr) -> AncillaryData {
                     result.local_address = local_address.into();
                     result.local_interface = Some(pkt_info.ipi6_ifindex as _);
                 }
+                #[cfg(s2n_quic_platform_ttl)]
+                (libc::IPPROTO_IP, libc::IP_TTL, _)
+                | (libc::IPPROTO_IPV6, libc::IPV6_HOPLIMIT, _) => {
+                    let ttl = decode_value::<IpTtl>(cmsg);
+                    result.ttl = Some(ttl as _);
+                }
+                #[cfg(s2n_quic_platform_rx_timestamp)]
+                (libc::SOL_SOCKET, libc::SCM_TIMESTAMPNS, _) => {
+                    let timestamp = decode_value::<RxTimestamp>(cmsg);
+                    result.receive_delay = receive_delay(timestamp);
+                }
                 #[cfg(s2n_quic_platform_gso)]
                 (libc::SOL_UDP, libc::UDP_SEGMENT, _) => {
                     // ignore GSO settings when reading
                     continue;
                 }
+                #[cfg(s2n_quic_platform_txtime)]
+                (libc::SOL_SOCKET, libc::SCM_TXTIME, _) => {
+                    // ignore transmit times when reading
+                    continue;
+                }
                 #[cfg(s2n_quic_platform_gro)]
                 (libc::SOL_UDP, libc::UDP_GRO, _) => {
                     let segment_size = decode_value::<UdpGro>(cmsg);
@@ -225,6 +269,26 @@ pub fn decode(msghdr: &libc::msghdr) -> AncillaryData {
     result
 }
 
+/// Computes how long ago the kernel received a datagram from its `CLOCK_REALTIME` timestamp
+///
+/// Returns `None` if the timestamp is in the future, which can happen if the clock was stepped
+/// backwards.
+#[cfg(s2n_quic_platform_rx_timestamp)]
+fn receive_delay(timestamp: RxTimestamp) -> Option<core::time::Duration> {
+    let mut now: libc::timespec = unsafe { core::mem::zeroed() };
+
+    // Safety: the timespec is valid for writes and CLOCK_REALTIME is always available
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_REALTIME, &mut now);
+    }
+
+    let to_duration = |time: libc::timespec| {
+        core::time::Duration::new(time.tv_sec.max(0) as _, time.tv_nsec.max(0) as _)
+    };
+
+    to_duration(now).checked_sub(to_duration(timestamp))
+}
+
 /// Decodes a value of type `T` from the given `cmsghdr`
 /// # Safety
 ///
@@ -50,6 +50,16 @@ impl MessageTrait for mmsghdr {
         self.msg_hdr.set_segment_size(size)
     }
 
+    #[inline]
+    fn set_transmit_delay(&mut self, delay: core::time::Duration) {
+        self.msg_hdr.set_transmit_delay(delay)
+    }
+
+    #[inline]
+    fn set_ipv6_flow_label(&mut self, flow_label: u32) {
+        self.msg_hdr.set_ipv6_flow_label(flow_label)
+    }
+
     #[inline]
     unsafe fn reset(&mut self, mtu: usize) {
         self.set_payload_len(mtu);
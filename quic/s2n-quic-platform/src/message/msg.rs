@@ -78,7 +78,7 @@ impl MessageTrait for msghdr {
 
     #[inline]
     fn can_gso<M: tx::Message<Handle = Self::Handle>>(&self, other: &mut M) -> bool {
-        if let Some((header, _cmsg)) = self.header() {
+        if let Some((header, cmsg)) = self.header() {
             let mut other_handle = *other.path_handle();
 
             // when reading the header back from the msghdr, we don't know the port
@@ -88,7 +88,9 @@ impl MessageTrait for msghdr {
             // check the path handles match
             header.path.strict_eq(&other_handle) &&
                 // check the ECN markings match
-                header.ecn == other.ecn()
+                header.ecn == other.ecn() &&
+                // check the DSCP markings match
+                cmsg.dscp == other.dscp()
         } else {
             false
         }
@@ -101,6 +103,38 @@ impl MessageTrait for msghdr {
         self.encode_cmsg(libc::SOL_UDP, libc::UDP_SEGMENT, size as SegmentType);
     }
 
+    #[cfg(s2n_quic_platform_txtime)]
+    #[inline]
+    fn set_transmit_delay(&mut self, delay: core::time::Duration) {
+        let mut now: libc::timespec = unsafe { zeroed() };
+
+        // Safety: the timespec is valid for writes and CLOCK_MONOTONIC is always available
+        unsafe {
+            libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now);
+        }
+
+        // the socket is configured to interpret transmit times with CLOCK_MONOTONIC
+        let txtime =
+            now.tv_sec as u64 * 1_000_000_000 + now.tv_nsec as u64 + delay.as_nanos() as u64;
+        self.encode_cmsg(libc::SOL_SOCKET, libc::SCM_TXTIME, txtime as cmsg::TxTime);
+    }
+
+    #[cfg(s2n_quic_platform_flow_label)]
+    #[inline]
+    fn set_ipv6_flow_label(&mut self, flow_label: u32) {
+        // IPv4 destinations, including IPv4-mapped addresses, don't carry a flow label
+        if !matches!(
+            self.remote_address().map(SocketAddress::unmap),
+            Some(SocketAddress::IpV6(_))
+        ) {
+            return;
+        }
+
+        // the kernel reads the flow label from the destination address
+        let sockaddr: &mut sockaddr_in6 = unsafe { &mut *(self.msg_name as *mut _) };
+        sockaddr.sin6_flowinfo = (flow_label & 0xf_ffff).to_be();
+    }
+
     #[inline]
     unsafe fn reset(&mut self, mtu: usize) {
         // reset the payload
@@ -214,7 +248,7 @@ impl MessageTrait for msghdr {
 
         let handle = *message.path_handle();
         handle.update_msg_hdr(self);
-        self.set_ecn(message.ecn(), &handle.remote_address.0);
+        self.set_tos(message.ecn(), message.dscp(), &handle.remote_address.0);
 
         Ok(len)
     }
@@ -5,7 +5,12 @@ use super::*;
 
 pub trait Ext: cmsg::Encoder {
     fn header(&self) -> Option<(datagram::Header<Handle>, datagram::AncillaryData)>;
-    fn set_ecn(&mut self, ecn: ExplicitCongestionNotification, remote_address: &SocketAddress);
+    fn set_tos(
+        &mut self,
+        ecn: ExplicitCongestionNotification,
+        dscp: u8,
+        remote_address: &SocketAddress,
+    );
     fn remote_address(&self) -> Option<SocketAddress>;
     fn set_remote_address(&mut self, remote_address: &SocketAddress);
 }
@@ -18,21 +23,37 @@ impl Ext for msghdr {
 
         let ancillary_data = cmsg::decode(self);
         let ecn = ancillary_data.ecn;
+        let ttl = ancillary_data.ttl;
+        let receive_delay = ancillary_data.receive_delay;
 
         path.with_ancillary_data(ancillary_data);
 
-        let header = datagram::Header { path, ecn };
+        let header = datagram::Header {
+            path,
+            ecn,
+            ttl,
+            receive_delay,
+        };
 
         Some((header, ancillary_data))
     }
 
     #[inline]
-    fn set_ecn(&mut self, ecn: ExplicitCongestionNotification, remote_address: &SocketAddress) {
-        if ecn == ExplicitCongestionNotification::NotEct {
+    fn set_tos(
+        &mut self,
+        ecn: ExplicitCongestionNotification,
+        dscp: u8,
+        remote_address: &SocketAddress,
+    ) {
+        // the DSCP occupies the upper 6 bits of the TOS/traffic class field and the ECN markings
+        // the lower 2 bits
+        let tos = (dscp << 2) | ecn as u8;
+
+        if tos == 0 {
             return;
         }
 
-        let ecn = ecn as libc::c_int;
+        let tos = tos as libc::c_int;
 
         // the remote address needs to be unmapped in order to set the appropriate cmsg
         match remote_address.unmap() {
@@ -40,11 +61,11 @@ impl Ext for msghdr {
                 // FreeBSD uses an unsigned_char for IP_TOS
                 // see https://svnweb.freebsd.org/base/stable/8/sys/netinet/ip_input.c?view=markup&pathrev=247944#l1716
                 #[cfg(target_os = "freebsd")]
-                let ecn = ecn as libc::c_uchar;
+                let tos = tos as libc::c_uchar;
 
-                self.encode_cmsg(libc::IPPROTO_IP, libc::IP_TOS, ecn)
+                self.encode_cmsg(libc::IPPROTO_IP, libc::IP_TOS, tos)
             }
-            SocketAddress::IpV6(_) => self.encode_cmsg(libc::IPPROTO_IPV6, libc::IPV6_TCLASS, ecn),
+            SocketAddress::IpV6(_) => self.encode_cmsg(libc::IPPROTO_IPV6, libc::IPV6_TCLASS, tos),
         };
     }
 
@@ -85,6 +106,8 @@ impl Ext for msghdr {
                 sockaddr.sin6_family = AF_INET6 as _;
                 sockaddr.sin6_port = addr.port().to_be();
                 sockaddr.sin6_addr.s6_addr = (*addr.ip()).into();
+                // the messages are reused so clear any flow label from a previous transmission
+                sockaddr.sin6_flowinfo = 0;
                 self.msg_namelen = size_of::<sockaddr_in6>() as _;
             }
         }
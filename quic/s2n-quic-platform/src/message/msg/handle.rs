@@ -76,6 +76,14 @@ impl path::Handle for Handle {
         }
     }
 
+    #[inline]
+    fn from_addresses(local_address: LocalAddress, remote_address: RemoteAddress) -> Self {
+        Self {
+            remote_address,
+            local_address,
+        }
+    }
+
     #[inline]
     fn remote_address(&self) -> RemoteAddress {
         self.remote_address
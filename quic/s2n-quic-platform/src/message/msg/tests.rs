@@ -85,3 +85,252 @@ fn handle_get_set_test() {
             assert!(header.path.remote_address.is_unspecified());
         });
 }
+
+#[test]
+#[cfg(s2n_quic_platform_ttl)]
+fn ttl_test() {
+    use core::mem::zeroed;
+    use std::{net::UdpSocket, os::unix::io::AsRawFd};
+
+    const TTL: u8 = 42;
+
+    let rx_socket = crate::syscall::bind_udp("127.0.0.1:0", false).unwrap();
+    assert!(crate::syscall::configure_ttl(&rx_socket));
+    let rx_socket: UdpSocket = rx_socket.into();
+
+    let tx_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    tx_socket.set_ttl(TTL as _).unwrap();
+    tx_socket
+        .send_to(&[1, 2, 3, 4], rx_socket.local_addr().unwrap())
+        .unwrap();
+
+    let mut msghdr = unsafe { zeroed::<msghdr>() };
+
+    let mut msgname = unsafe { zeroed::<sockaddr_in6>() };
+    msghdr.msg_name = &mut msgname as *mut _ as *mut _;
+    msghdr.msg_namelen = size_of::<sockaddr_in6>() as _;
+
+    let mut iovec = unsafe { zeroed::<iovec>() };
+    let mut iovec_buf = [0u8; 16];
+    iovec.iov_len = iovec_buf.len() as _;
+    iovec.iov_base = (&mut iovec_buf[0]) as *mut u8 as _;
+    msghdr.msg_iov = &mut iovec;
+    msghdr.msg_iovlen = 1;
+
+    let mut cmsg_buf = [0u8; cmsg::MAX_LEN];
+    msghdr.msg_controllen = cmsg_buf.len() as _;
+    msghdr.msg_control = (&mut cmsg_buf[0]) as *mut u8 as _;
+
+    // the datagram was already sent over loopback so the blocking call will return immediately
+    let len = unsafe { libc::recvmsg(rx_socket.as_raw_fd(), &mut msghdr, 0) };
+    assert_eq!(len, 4);
+
+    let (header, ancillary_data) = msghdr.header().unwrap();
+    assert_eq!(ancillary_data.ttl, Some(TTL));
+    assert_eq!(header.ttl, Some(TTL));
+}
+
+#[test]
+#[cfg(s2n_quic_platform_rx_timestamp)]
+fn rx_timestamp_test() {
+    use core::mem::zeroed;
+    use std::{net::UdpSocket, os::unix::io::AsRawFd};
+
+    let rx_socket = crate::syscall::bind_udp("127.0.0.1:0", false).unwrap();
+    assert!(crate::syscall::configure_rx_timestamp(&rx_socket));
+    let rx_socket: UdpSocket = rx_socket.into();
+
+    let tx_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    tx_socket
+        .send_to(&[1, 2, 3, 4], rx_socket.local_addr().unwrap())
+        .unwrap();
+
+    // let the datagram wait in the socket buffer before reading it
+    std::thread::sleep(core::time::Duration::from_millis(20));
+
+    let mut msghdr = unsafe { zeroed::<msghdr>() };
+
+    let mut msgname = unsafe { zeroed::<sockaddr_in6>() };
+    msghdr.msg_name = &mut msgname as *mut _ as *mut _;
+    msghdr.msg_namelen = size_of::<sockaddr_in6>() as _;
+
+    let mut iovec = unsafe { zeroed::<iovec>() };
+    let mut iovec_buf = [0u8; 16];
+    iovec.iov_len = iovec_buf.len() as _;
+    iovec.iov_base = (&mut iovec_buf[0]) as *mut u8 as _;
+    msghdr.msg_iov = &mut iovec;
+    msghdr.msg_iovlen = 1;
+
+    let mut cmsg_buf = [0u8; cmsg::MAX_LEN];
+    msghdr.msg_controllen = cmsg_buf.len() as _;
+    msghdr.msg_control = (&mut cmsg_buf[0]) as *mut u8 as _;
+
+    let len = unsafe { libc::recvmsg(rx_socket.as_raw_fd(), &mut msghdr, 0) };
+    assert_eq!(len, 4);
+
+    let (header, ancillary_data) = msghdr.header().unwrap();
+    let receive_delay = header.receive_delay.unwrap();
+    assert_eq!(ancillary_data.receive_delay, Some(receive_delay));
+    assert!(receive_delay >= core::time::Duration::from_millis(20));
+}
+
+#[test]
+#[cfg(s2n_quic_platform_txtime)]
+fn txtime_test() {
+    use core::mem::zeroed;
+    use std::{net::UdpSocket, os::unix::io::AsRawFd};
+
+    let rx_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    rx_socket
+        .set_read_timeout(Some(core::time::Duration::from_secs(1)))
+        .unwrap();
+
+    let tx_socket = crate::syscall::bind_udp("127.0.0.1:0", false).unwrap();
+    assert!(crate::syscall::configure_txtime(&tx_socket));
+
+    let mut msghdr = unsafe { zeroed::<msghdr>() };
+
+    let mut msgname = unsafe { zeroed::<sockaddr_in6>() };
+    msghdr.msg_name = &mut msgname as *mut _ as *mut _;
+    msghdr.msg_namelen = size_of::<sockaddr_in6>() as _;
+
+    let mut iovec = unsafe { zeroed::<iovec>() };
+    let mut iovec_buf = [1u8, 2, 3, 4];
+    iovec.iov_len = iovec_buf.len() as _;
+    iovec.iov_base = (&mut iovec_buf[0]) as *mut u8 as _;
+    msghdr.msg_iov = &mut iovec;
+    msghdr.msg_iovlen = 1;
+
+    let mut cmsg_buf = [0u8; cmsg::MAX_LEN];
+    msghdr.msg_control = (&mut cmsg_buf[0]) as *mut u8 as _;
+
+    let remote_address: SocketAddress = rx_socket.local_addr().unwrap().into();
+    msghdr.set_remote_address(&remote_address);
+    msghdr.set_transmit_delay(core::time::Duration::from_millis(1));
+
+    // the kernel rejects the control message if the socket isn't configured for transmit times
+    let len = unsafe { libc::sendmsg(tx_socket.as_raw_fd(), &msghdr, 0) };
+    assert_eq!(len, 4);
+
+    let mut payload = [0u8; 16];
+    let (len, _) = rx_socket.recv_from(&mut payload).unwrap();
+    assert_eq!(&payload[..len], &iovec_buf[..]);
+}
+
+#[test]
+#[cfg(s2n_quic_platform_flow_label)]
+fn flow_label_test() {
+    use core::mem::zeroed;
+
+    let mut msghdr = unsafe { zeroed::<msghdr>() };
+
+    let mut msgname = unsafe { zeroed::<sockaddr_in6>() };
+    msghdr.msg_name = &mut msgname as *mut _ as *mut _;
+    msghdr.msg_namelen = size_of::<sockaddr_in6>() as _;
+
+    let flow_info = |msghdr: &msghdr| {
+        let sockaddr: &sockaddr_in6 = unsafe { &*(msghdr.msg_name as *const _) };
+        u32::from_be(sockaddr.sin6_flowinfo)
+    };
+
+    let address =
+        |addr: &str| -> SocketAddress { addr.parse::<std::net::SocketAddr>().unwrap().into() };
+
+    msghdr.set_remote_address(&address("[2001:db8::1]:443"));
+    msghdr.set_ipv6_flow_label(0x1_2345);
+    assert_eq!(flow_info(&msghdr), 0x1_2345);
+
+    // only the lower 20 bits are used
+    msghdr.set_ipv6_flow_label(0xfff1_2345);
+    assert_eq!(flow_info(&msghdr), 0x1_2345);
+
+    // reusing the message clears the previous flow label
+    msghdr.set_remote_address(&address("[2001:db8::1]:443"));
+    assert_eq!(flow_info(&msghdr), 0);
+
+    // IPv4-mapped addresses are sent as IPv4 packets, which don't carry a flow label
+    msghdr.set_remote_address(&address("[::ffff:127.0.0.1]:443"));
+    msghdr.set_ipv6_flow_label(0x1_2345);
+    assert_eq!(flow_info(&msghdr), 0);
+}
+
+#[test]
+#[cfg(s2n_quic_platform_flow_label)]
+fn flow_label_sendmsg_test() {
+    use core::mem::{size_of_val, zeroed};
+    use std::{net::UdpSocket, os::unix::io::AsRawFd};
+
+    let rx_socket = UdpSocket::bind("[::1]:0").unwrap();
+    rx_socket
+        .set_read_timeout(Some(core::time::Duration::from_secs(1)))
+        .unwrap();
+
+    // report the flow label of received datagrams
+    let enabled: libc::c_int = 1;
+    let res = unsafe {
+        libc::setsockopt(
+            rx_socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_FLOWINFO,
+            &enabled as *const _ as _,
+            size_of_val(&enabled) as _,
+        )
+    };
+    assert_eq!(res, 0);
+
+    let tx_socket = crate::syscall::bind_udp("[::1]:0", false).unwrap();
+    assert!(crate::syscall::configure_flow_labels(&tx_socket));
+
+    let mut msghdr = unsafe { zeroed::<msghdr>() };
+
+    let mut msgname = unsafe { zeroed::<sockaddr_in6>() };
+    msghdr.msg_name = &mut msgname as *mut _ as *mut _;
+    msghdr.msg_namelen = size_of::<sockaddr_in6>() as _;
+
+    let mut iovec = unsafe { zeroed::<iovec>() };
+    let mut iovec_buf = [1u8, 2, 3, 4];
+    iovec.iov_len = iovec_buf.len() as _;
+    iovec.iov_base = (&mut iovec_buf[0]) as *mut u8 as _;
+    msghdr.msg_iov = &mut iovec;
+    msghdr.msg_iovlen = 1;
+
+    let remote_address: SocketAddress = rx_socket.local_addr().unwrap().into();
+    msghdr.set_remote_address(&remote_address);
+    msghdr.set_ipv6_flow_label(0x1_2345);
+
+    let len = unsafe { libc::sendmsg(tx_socket.as_raw_fd(), &msghdr, 0) };
+    assert_eq!(len, 4);
+
+    let mut msghdr = unsafe { zeroed::<msghdr>() };
+
+    let mut payload = [0u8; 16];
+    let mut iovec = unsafe { zeroed::<iovec>() };
+    iovec.iov_len = payload.len() as _;
+    iovec.iov_base = (&mut payload[0]) as *mut u8 as _;
+    msghdr.msg_iov = &mut iovec;
+    msghdr.msg_iovlen = 1;
+
+    let mut cmsg_buf = [0u8; cmsg::MAX_LEN];
+    msghdr.msg_controllen = cmsg_buf.len() as _;
+    msghdr.msg_control = (&mut cmsg_buf[0]) as *mut u8 as _;
+
+    let len = unsafe { libc::recvmsg(rx_socket.as_raw_fd(), &mut msghdr, 0) };
+    assert_eq!(len, 4);
+    assert_eq!(&payload[..4], &iovec_buf[..]);
+
+    let mut flow_info = None;
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msghdr);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::IPPROTO_IPV6 && (*cmsg).cmsg_type == libc::IPV6_FLOWINFO
+            {
+                let value = (libc::CMSG_DATA(cmsg) as *const u32).read_unaligned();
+                flow_info = Some(u32::from_be(value));
+            }
+            cmsg = libc::CMSG_NXTHDR(&msghdr, cmsg);
+        }
+    }
+
+    // the kernel sent the datagram with the label from the destination address
+    assert_eq!(flow_info.unwrap() & 0xf_ffff, 0x1_2345);
+}
@@ -51,6 +51,12 @@ pub struct Queue<Ring: message::Ring> {
     free: Segment,
     /// The local address that the queue is bound to
     local_address: LocalAddress,
+    /// Attach transmit times to the messages which are delayed
+    txtime: bool,
+    /// Set the IPv6 flow label of each message
+    flow_labels: bool,
+    /// The maximum number of messages sent or received in a single system call
+    max_batch: usize,
 }
 
 impl<Ring> Default for Queue<Ring>
@@ -82,6 +88,9 @@ impl<Ring: message::Ring> Queue<Ring> {
             occupied,
             free,
             local_address: Default::default(),
+            txtime: false,
+            flow_labels: false,
+            max_batch: usize::MAX,
         }
     }
 
@@ -89,6 +98,33 @@ impl<Ring: message::Ring> Queue<Ring> {
         self.local_address = local_address;
     }
 
+    /// Enables transmit times for delayed messages
+    ///
+    /// The socket the messages are sent on must be configured with `SO_TXTIME`.
+    pub fn enable_txtime(&mut self) {
+        self.txtime = true;
+    }
+
+    /// Enables IPv6 flow labels for the messages
+    ///
+    /// The socket the messages are sent on must be configured with `IPV6_FLOWINFO_SEND`.
+    pub fn enable_flow_labels(&mut self) {
+        self.flow_labels = true;
+    }
+
+    /// Limits the number of messages sent or received in a single system call
+    ///
+    /// By default, the batch is only limited by the capacity of the queue and the platform.
+    pub fn set_max_batch(&mut self, max_batch: usize) {
+        debug_assert_ne!(max_batch, 0, "the batch must include at least one message");
+        self.max_batch = max_batch.max(1);
+    }
+
+    /// Returns the maximum number of messages sent or received in a single system call
+    pub fn max_batch(&self) -> usize {
+        self.max_batch
+    }
+
     /// Returns the maximum size of a payload for any message
     pub fn mtu(&self) -> usize {
         self.ring.mtu()
@@ -129,6 +165,8 @@ impl<Ring: message::Ring> Queue<Ring> {
             max_gso,
             gso_segment: None,
             local_address: &self.local_address,
+            txtime: self.txtime,
+            flow_labels: self.flow_labels,
         }
     }
 
@@ -144,6 +182,8 @@ impl<Ring: message::Ring> Queue<Ring> {
             max_gso,
             gso_segment: None,
             local_address: &self.local_address,
+            txtime: self.txtime,
+            flow_labels: self.flow_labels,
         }
     }
 }
@@ -286,6 +326,179 @@ mod tests {
         };
     }
 
+    #[test]
+    fn release_excess_test() {
+        const LEN: usize = 16;
+        const MAX_LEN: usize = LEN * 4;
+
+        let payloads = VecBuffer::new(32, MTU);
+        let ring = message::simple::Ring::new(payloads, 1);
+        let mut queue = Queue::new(ring);
+        let payload = [0u8; LEN];
+        let address = Handle::from_remote_address(Default::default());
+
+        let mut dropped = 0;
+        let mut dropped_len = 0;
+
+        for _ in 0..10 {
+            // receive datagrams faster than they are processed
+            let mut free = queue.free_mut();
+            let count = free.len().min(8);
+            for message in &mut free[..count] {
+                message.tx_write((address, &payload[..])).unwrap();
+            }
+            free.finish(count);
+
+            let prev_dropped = dropped;
+            dropped += queue.occupied_mut().release_excess(MAX_LEN, |len| {
+                dropped_len += len;
+            });
+            assert!(dropped > prev_dropped);
+            assert_eq!(dropped_len, dropped * LEN);
+
+            // the buffered payloads should stay within the limit
+            let buffered: usize = queue
+                .occupied_mut()
+                .iter()
+                .map(|message| message.payload_len())
+                .sum();
+            assert!(buffered <= MAX_LEN);
+
+            // only process a single datagram
+            queue.occupied_mut().finish(1);
+        }
+
+        // nothing is released when the queue is within the limit
+        assert_eq!(queue.occupied_mut().release_excess(MAX_LEN, |_| {}), 0);
+    }
+
+    /// Pushes more datagrams than the GSO segment limit allows and checks the extra datagram is
+    /// held back until the queue is flushed
+    #[cfg(all(s2n_quic_platform_socket_msg, s2n_quic_platform_gso))]
+    #[test]
+    fn max_gso_test() {
+        use s2n_quic_core::{
+            inet::SocketAddressV4,
+            io::tx::{self, Queue as _},
+        };
+
+        const LEN: usize = 100;
+        const MAX_GSO: usize = 2;
+
+        // allocate enough space for two messages of `MAX_GSO` segments each
+        let payloads = VecBuffer::new(2 * MAX_GSO, MTU);
+        let ring = message::msg::Ring::new(payloads, MAX_GSO);
+        let mut queue = Queue::new(ring);
+        assert_eq!(queue.capacity(), 2);
+
+        let address: path::RemoteAddress = SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+        let address = Handle::from_remote_address(address);
+        let payload = [1u8; LEN];
+
+        let mut free = queue.free_mut();
+        for _ in 0..(2 * MAX_GSO) {
+            free.push((address, &payload[..])).unwrap();
+        }
+
+        // both messages are at the segment limit so the next datagram can't be sent
+        assert!(matches!(
+            free.push((address, &payload[..])),
+            Err(tx::Error::AtCapacity)
+        ));
+        drop(free);
+
+        let mut occupied = queue.occupied_mut();
+        assert_eq!(occupied.len(), 2);
+        for message in occupied.iter_mut() {
+            assert_eq!(message.payload_len(), LEN * MAX_GSO);
+        }
+        occupied.finish(2);
+
+        // the held back datagram is accepted once the messages are transmitted
+        let mut free = queue.free_mut();
+        free.push((address, &payload[..])).unwrap();
+    }
+
+    /// Checks that each GSO payload only contains datagrams with the same transmit time
+    #[cfg(all(
+        s2n_quic_platform_socket_msg,
+        s2n_quic_platform_gso,
+        s2n_quic_platform_txtime
+    ))]
+    #[test]
+    fn txtime_gso_test() {
+        use core::time::Duration;
+        use s2n_quic_core::{
+            inet::{ExplicitCongestionNotification, SocketAddressV4},
+            io::tx::{self, Queue as _},
+        };
+
+        /// Delays the transmission of the wrapped message
+        struct Delayed<M>(M, Duration);
+
+        impl<M: tx::Message> tx::Message for Delayed<M> {
+            type Handle = M::Handle;
+
+            fn path_handle(&self) -> &Self::Handle {
+                self.0.path_handle()
+            }
+
+            fn ecn(&mut self) -> ExplicitCongestionNotification {
+                self.0.ecn()
+            }
+
+            fn delay(&mut self) -> Duration {
+                self.1
+            }
+
+            fn ipv6_flow_label(&mut self) -> u32 {
+                self.0.ipv6_flow_label()
+            }
+
+            fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool {
+                self.0.can_gso(segment_len, segment_count)
+            }
+
+            fn write_payload(
+                &mut self,
+                buffer: tx::PayloadBuffer,
+                gso_offset: usize,
+            ) -> Result<usize, tx::Error> {
+                self.0.write_payload(buffer, gso_offset)
+            }
+        }
+
+        const LEN: usize = 100;
+        const MAX_GSO: usize = 4;
+
+        let payloads = VecBuffer::new(2 * MAX_GSO, MTU);
+        let ring = message::msg::Ring::new(payloads, MAX_GSO);
+        let mut queue = Queue::new(ring);
+        assert_eq!(queue.free_mut().max_transmit_delay(), Duration::ZERO);
+        queue.enable_txtime();
+
+        let address: path::RemoteAddress = SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+        let address = Handle::from_remote_address(address);
+        let payload = [1u8; LEN];
+
+        let mut free = queue.free_mut();
+        assert!(free.max_transmit_delay() > Duration::ZERO);
+
+        // the first batch departs in 1ms and the second in 2ms
+        for delay in [1, 1, 2] {
+            let delay = Duration::from_millis(delay);
+            free.push(Delayed((address, &payload[..]), delay)).unwrap();
+        }
+        drop(free);
+
+        let occupied = queue.occupied_mut();
+        let lens: Vec<_> = occupied
+            .iter()
+            .map(|message| message.payload_len())
+            .collect();
+        assert_eq!(lens, [LEN * 2, LEN]);
+    }
+
     differential_test!(simple_differential_test, message::simple::Ring);
 
     #[cfg(s2n_quic_platform_socket_msg)]
@@ -1,9 +1,12 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{Behavior, Segment};
+use super::{behavior, Behavior, Segment};
 use crate::message;
-use core::ops::{Deref, DerefMut};
+use core::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
 use s2n_quic_core::{
     inet::datagram,
     io::{rx, tx},
@@ -27,13 +30,25 @@ pub struct Slice<'a, Message: message::Message, Behavior> {
     pub(crate) gso_segment: Option<GsoSegment>,
     /// The base handle for all of the messages to inherit
     pub(crate) local_address: &'a LocalAddress,
+    /// Attach transmit times to the messages which are delayed
+    pub(crate) txtime: bool,
+    /// Set the IPv6 flow label of each message
+    pub(crate) flow_labels: bool,
 }
 
+/// How long messages are held by the kernel before their transmit time
+///
+/// This allows paced messages to be sent ahead of the pacer, which reduces the number of times
+/// the endpoint needs to wake up.
+const MAX_TXTIME_DELAY: Duration = Duration::from_millis(2);
+
 #[derive(Debug, Default)]
 pub struct GsoSegment {
     index: usize,
     count: usize,
     size: usize,
+    /// The transmit delay of the segments, which are all sent at the same time
+    delay: Duration,
 }
 
 impl<'a, Message: message::Message, B: Behavior> Slice<'a, Message, B> {
@@ -137,6 +152,7 @@ impl<'a, Message: message::Message, B> Slice<'a, Message, B> {
     fn try_gso<M: tx::Message<Handle = Message::Handle>>(
         &mut self,
         mut message: M,
+        delay: Duration,
     ) -> Result<Result<tx::Outcome, M>, tx::Error> {
         if !Message::SUPPORTS_GSO {
             return Ok(Err(message));
@@ -156,8 +172,11 @@ impl<'a, Message: message::Message, B> Slice<'a, Message, B> {
 
         let prev_message = &mut self.messages[gso.index];
         // check to make sure the message can be GSO'd and can be included in the same
-        // GSO payload as the previous message
-        if !(message.can_gso(gso.size, gso.count) && prev_message.can_gso(&mut message)) {
+        // GSO payload as the previous message. Each GSO payload is transmitted at a single time
+        // so messages with a different departure time start a new payload.
+        if gso.delay != delay
+            || !(message.can_gso(gso.size, gso.count) && prev_message.can_gso(&mut message))
+        {
             self.flush_gso();
             return Ok(Err(message));
         }
@@ -239,6 +258,33 @@ impl<'a, Message: message::Message, B> Slice<'a, Message, B> {
     }
 }
 
+impl<'a, Message: message::Message> Slice<'a, Message, behavior::Occupied> {
+    /// Releases the oldest messages until the remaining payloads fit within `max_len` bytes
+    ///
+    /// `on_release` is called with the payload length of each released message. Returns the
+    /// number of released messages.
+    #[inline]
+    pub fn release_excess<F: FnMut(usize)>(self, max_len: usize, mut on_release: F) -> usize {
+        let mut len: usize = self.iter().map(|message| message.payload_len()).sum();
+        let mut count = 0;
+
+        for message in self.iter() {
+            if len <= max_len {
+                break;
+            }
+
+            let payload_len = message.payload_len();
+            len -= payload_len;
+            on_release(payload_len);
+            count += 1;
+        }
+
+        self.finish(count);
+
+        count
+    }
+}
+
 impl<'a, Message: message::Message, R> Drop for Slice<'a, Message, R> {
     #[inline]
     fn drop(&mut self) {
@@ -300,10 +346,24 @@ impl<'a, Message: message::Message<Handle = H>, B: Behavior, H: path::Handle> tx
     #[inline]
     fn push<M: tx::Message<Handle = Self::Handle>>(
         &mut self,
-        message: M,
+        mut message: M,
     ) -> Result<tx::Outcome, tx::Error> {
+        // the kernel rejects transmit times on sockets which aren't configured for them
+        let delay = if self.txtime {
+            message.delay()
+        } else {
+            Duration::ZERO
+        };
+
+        // the kernel only reads the flow label on sockets which are configured for them
+        let flow_label = if self.flow_labels {
+            message.ipv6_flow_label()
+        } else {
+            0
+        };
+
         // first try to write a GSO payload
-        let message = match self.try_gso(message)? {
+        let message = match self.try_gso(message, delay)? {
             Ok(outcome) => return Ok(outcome),
             Err(message) => message,
         };
@@ -324,6 +384,16 @@ impl<'a, Message: message::Message<Handle = H>, B: Behavior, H: path::Handle> tx
         };
 
         let size = output.tx_write(message)?;
+
+        // only segments with the same delay are added to a GSO payload
+        if !delay.is_zero() {
+            output.set_transmit_delay(delay);
+        }
+
+        if flow_label != 0 {
+            output.set_ipv6_flow_label(flow_label);
+        }
+
         self.advance(1);
 
         if can_gso {
@@ -331,6 +401,7 @@ impl<'a, Message: message::Message<Handle = H>, B: Behavior, H: path::Handle> tx
                 index,
                 count: 1,
                 size,
+                delay,
             });
         }
 
@@ -350,4 +421,13 @@ impl<'a, Message: message::Message<Handle = H>, B: Behavior, H: path::Handle> tx
         // so it's not really worth trying.
         self.flush_gso();
     }
+
+    #[inline]
+    fn max_transmit_delay(&self) -> Duration {
+        if self.txtime {
+            MAX_TXTIME_DELAY
+        } else {
+            Duration::ZERO
+        }
+    }
 }
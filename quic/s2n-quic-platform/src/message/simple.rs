@@ -92,6 +92,8 @@ impl MessageTrait for Message {
         let header = datagram::Header {
             path,
             ecn: self.ecn(),
+            ttl: None,
+            receive_delay: None,
         };
         let payload = self.payload_mut();
 
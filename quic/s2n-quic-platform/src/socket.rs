@@ -3,6 +3,8 @@
 
 use cfg_if::cfg_if;
 
+#[cfg(s2n_quic_platform_io_uring)]
+pub mod io_uring;
 #[cfg(s2n_quic_platform_socket_mmsg)]
 pub mod mmsg;
 #[cfg(s2n_quic_platform_socket_msg)]
@@ -0,0 +1,656 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transmits and receives the messages of an mmsg queue with [io_uring](https://kernel.dk/io_uring.pdf)
+//!
+//! Datagrams are received with a single multishot `recvmsg` operation, which stays armed and
+//! posts a completion for each datagram. The kernel writes each datagram into one of the buffers
+//! registered with the ring, so receiving doesn't need a system call at all: the completions are
+//! read from the shared completion queue and the buffers are handed back to the kernel through
+//! the shared buffer ring. Since the operation consumes the datagrams from the socket, the
+//! readiness of the ring's file descriptor is polled instead of the socket's.
+//!
+//! Each message in a tx batch is submitted as a `sendmsg` operation and the whole batch goes to
+//! the kernel with a single `io_uring_enter` call. The operations are linked so the batch stops
+//! at the first one which fails, e.g. when the socket buffer is full. This keeps the sent
+//! messages at the front of the queue, the same as `sendmmsg`. Each operation is submitted with
+//! `MSG_DONTWAIT`, since io_uring would otherwise park the operation until the socket is ready,
+//! and tokio still drives the readiness of the socket.
+//!
+//! Multishot `recvmsg` requires Linux 6.0.
+
+use super::mmsg::Queue;
+use crate::{
+    buffer::Buffer,
+    message::{cmsg, mmsg::Message, Message as _},
+};
+use core::{fmt, mem::size_of, ptr::NonNull};
+use io_uring::{cqueue, opcode, squeue, types, IoUring};
+use s2n_quic_core::event;
+use std::{
+    alloc::{self, Layout},
+    io,
+    os::unix::io::{AsRawFd, RawFd},
+    sync::atomic::{AtomicU16, Ordering},
+};
+
+/// The maximum number of messages submitted in a single batch
+///
+/// This matches the `UIO_MAXIOV` limit of `sendmmsg`.
+const MAX_BATCH_LEN: u32 = 1024;
+
+pub struct Ring {
+    ring: IoUring,
+}
+
+impl fmt::Debug for Ring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ring")
+            .field("entries", &self.ring.params().sq_entries())
+            .finish()
+    }
+}
+
+impl Ring {
+    pub fn new() -> io::Result<Self> {
+        let ring = IoUring::new(MAX_BATCH_LEN)?;
+        Ok(Self { ring })
+    }
+
+    /// Submits an operation for each message and waits for all of them to complete
+    ///
+    /// The operations are expected to use `MSG_DONTWAIT` so they complete without waiting on
+    /// the socket.
+    ///
+    /// Returns the number of messages which completed, along with the error of the operation
+    /// which stopped the batch, if any.
+    fn submit<F>(&mut self, fd: RawFd, messages: &mut [Message], op: F) -> io::Result<Batch>
+    where
+        F: Fn(types::Fd, &mut Message) -> squeue::Entry,
+    {
+        let capacity = self.ring.params().sq_entries() as usize;
+        let messages = &mut messages[..messages.len().min(capacity)];
+
+        let mut batch = Batch {
+            count: 0,
+            error: None,
+        };
+
+        if messages.is_empty() {
+            return Ok(batch);
+        }
+
+        let last = messages.len() - 1;
+
+        {
+            let mut submission = self.ring.submission();
+
+            for (index, message) in messages.iter_mut().enumerate() {
+                let mut entry = op(types::Fd(fd), message).user_data(index as u64);
+
+                // stop the batch at the first failed operation
+                if index != last {
+                    entry = entry.flags(squeue::Flags::IO_LINK);
+                }
+
+                // Safety: the messages outlive the operations since all of the completions are
+                // reaped below before returning
+                let result = unsafe { submission.push(&entry) };
+                debug_assert!(result.is_ok(), "the batch is limited to the queue capacity");
+            }
+        }
+
+        let mut pending = messages.len();
+
+        while pending > 0 {
+            match self.ring.submit_and_wait(pending) {
+                Ok(_) => {}
+                // the operations were submitted but waiting for them was interrupted
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+
+            for entry in self.ring.completion() {
+                pending -= 1;
+
+                let result = entry.result();
+                let message = &mut messages[entry.user_data() as usize];
+
+                if result >= 0 {
+                    message.msg_len = result as _;
+                    batch.count += 1;
+                } else if result != -libc::ECANCELED {
+                    batch.error = Some(io::Error::from_raw_os_error(-result));
+                }
+            }
+        }
+
+        Ok(batch)
+    }
+}
+
+struct Batch {
+    /// The number of operations which completed successfully
+    count: usize,
+    /// The error of the operation which stopped the batch
+    error: Option<io::Error>,
+}
+
+impl<B: Buffer> Queue<B> {
+    pub fn tx_io_uring<Socket: AsRawFd, Publisher: event::EndpointPublisher>(
+        &mut self,
+        socket: &Socket,
+        ring: &mut Ring,
+        publisher: &mut Publisher,
+    ) -> io::Result<usize> {
+        let max_batch = self.0.max_batch();
+        let mut entries = self.0.occupied_mut();
+        let len = entries.len().min(max_batch);
+
+        let Batch { count, error } =
+            ring.submit(socket.as_raw_fd(), &mut entries[..len], |fd, msg| {
+                opcode::SendMsg::new(fd, &msg.msg_hdr)
+                    .flags(libc::MSG_DONTWAIT as _)
+                    .build()
+            })?;
+
+        if count > 0 {
+            publisher.on_platform_tx(event::builder::PlatformTx { count });
+        }
+
+        match error {
+            None => {
+                entries.finish(count);
+                Ok(count)
+            }
+            Some(err) if count == 0 && err.kind() == io::ErrorKind::WouldBlock => {
+                entries.cancel(0);
+                Err(err)
+            }
+            Some(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+                ) =>
+            {
+                entries.finish(count);
+                Ok(count)
+            }
+            // check to see if we need to disable GSO
+            #[cfg(s2n_quic_platform_gso)]
+            Some(err) if err.raw_os_error() == Some(libc::EIO) => {
+                // unfortunately we've already assembled GSO packets so just drop them
+                // and wait for a retransmission
+                let count = len.min(MAX_BATCH_LEN as usize);
+                entries.finish(count);
+
+                publisher.on_platform_tx_error(event::builder::PlatformTxError {
+                    errno: libc::EIO as _,
+                });
+
+                if self.0.max_gso() > 1 {
+                    self.0.disable_gso();
+
+                    publisher.on_platform_feature_configured(
+                        event::builder::PlatformFeatureConfigured {
+                            configuration: event::builder::PlatformFeatureConfiguration::Gso {
+                                max_segments: self.0.max_gso(),
+                            },
+                        },
+                    );
+                }
+
+                Ok(count)
+            }
+            Some(err) => {
+                // Ignore other transmission errors, the same as the mmsg socket does. The
+                // message which caused the error is dropped and the rest are retried later.
+                publisher.on_platform_tx_error(event::builder::PlatformTxError {
+                    errno: err.raw_os_error().unwrap_or_default(),
+                });
+
+                let count = count + 1;
+                entries.finish(count);
+                Ok(count)
+            }
+        }
+    }
+}
+
+/// The receiver only submits the `recvmsg` operation and the operation which cancels it
+const SUBMISSION_ENTRIES: u32 = 8;
+
+/// The buffer group the receive buffers are registered as
+const BUFFER_GROUP: u16 = 0;
+
+/// Identifies the completions of the multishot `recvmsg` operation
+const RECV_USER_DATA: u64 = 0;
+
+/// Identifies the completion of the operation which cancels the `recvmsg` operation
+const CANCEL_USER_DATA: u64 = 1;
+
+/// The maximum number of buffers which can be registered with a buffer ring
+const MAX_BUFFERS: usize = 1 << 15;
+
+/// The length of `struct io_uring_recvmsg_out`, which the kernel writes at the start of each buffer
+const RECVMSG_OUT_LEN: usize = 16;
+
+/// Receives datagrams with a multishot `recvmsg` operation and provided buffers
+pub struct Receiver {
+    // the ring is declared first so it's closed before the buffers are freed
+    ring: IoUring,
+    buffers: Buffers,
+    /// Tells the kernel how much of each buffer to reserve for the address and control
+    /// messages. The kernel reads it for every datagram, so it needs to outlive the operation.
+    msghdr: Box<libc::msghdr>,
+    socket: RawFd,
+    /// Set while the `recvmsg` operation is posting completions
+    armed: bool,
+    /// Set if completions were left in the queue because the messages were full
+    backlog: bool,
+}
+
+/// The `recvmsg` operation only accesses the heap allocations of the receiver
+unsafe impl Send for Receiver {}
+
+impl fmt::Debug for Receiver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("buffers", &self.buffers.count)
+            .field("buffer_len", &self.buffers.buffer_len)
+            .field("armed", &self.armed)
+            .finish()
+    }
+}
+
+impl AsRawFd for Receiver {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.ring.as_raw_fd()
+    }
+}
+
+impl Receiver {
+    /// Creates a receiver for `socket` with enough buffers for `messages` datagrams of up to
+    /// `mtu` bytes
+    ///
+    /// The socket needs to outlive the receiver.
+    pub fn new<Socket: AsRawFd>(socket: &Socket, messages: usize, mtu: usize) -> io::Result<Self> {
+        let count = messages.clamp(1, MAX_BUFFERS).next_power_of_two();
+
+        // each completion holds a datagram so make sure all of the buffers can complete without
+        // overflowing the completion queue
+        let ring = IoUring::builder()
+            .setup_cqsize((count as u32).max(SUBMISSION_ENTRIES))
+            .build(SUBMISSION_ENTRIES)?;
+
+        let mut msghdr: Box<libc::msghdr> = Box::new(unsafe { core::mem::zeroed() });
+        msghdr.msg_namelen = size_of::<libc::sockaddr_in6>() as _;
+        msghdr.msg_controllen = cmsg::MAX_LEN as _;
+
+        // the kernel writes a header, the address and the control messages ahead of the payload
+        let buffer_len = RECVMSG_OUT_LEN + size_of::<libc::sockaddr_in6>() + cmsg::MAX_LEN + mtu;
+
+        let mut buffers = Buffers::new(count, buffer_len)?;
+
+        // Safety: the buffer ring outlives the registration since the ring is closed first
+        unsafe {
+            ring.submitter().register_buf_ring(
+                buffers.ring.as_ptr() as u64,
+                count as u16,
+                BUFFER_GROUP,
+            )?;
+        }
+
+        for id in 0..count {
+            buffers.provide(id as u16);
+        }
+        buffers.publish();
+
+        let mut receiver = Self {
+            ring,
+            buffers,
+            msghdr,
+            socket: socket.as_raw_fd(),
+            armed: false,
+            backlog: false,
+        };
+
+        // arm the operation up front so the ring becomes readable once a datagram arrives
+        receiver.arm()?;
+
+        Ok(receiver)
+    }
+
+    /// Returns `true` if completions are waiting to be read, without polling the ring
+    #[inline]
+    pub fn has_backlog(&self) -> bool {
+        self.backlog
+    }
+
+    /// Submits the multishot `recvmsg` operation
+    fn arm(&mut self) -> io::Result<()> {
+        let entry = opcode::RecvMsgMulti::new(
+            types::Fd(self.socket),
+            &*self.msghdr as *const _,
+            BUFFER_GROUP,
+        )
+        .build()
+        .user_data(RECV_USER_DATA);
+
+        // Safety: the msghdr and buffers outlive the operation, which is canceled on drop
+        unsafe {
+            self.ring
+                .submission()
+                .push(&entry)
+                .map_err(|_| io::Error::new(io::ErrorKind::Other, "submission queue is full"))?;
+        }
+
+        self.ring.submit()?;
+        self.armed = true;
+        Ok(())
+    }
+
+    /// Copies the received datagrams into `messages`
+    ///
+    /// Returns the number of messages which were filled, along with the error which stopped the
+    /// `recvmsg` operation, if any.
+    fn receive(&mut self, messages: &mut [Message]) -> io::Result<Batch> {
+        if !self.armed {
+            self.arm()?;
+        }
+
+        let mut batch = Batch {
+            count: 0,
+            error: None,
+        };
+
+        let mut completion = self.ring.completion();
+        self.backlog = false;
+
+        while let Some(message) = messages.get_mut(batch.count) {
+            let entry = if let Some(entry) = completion.next() {
+                entry
+            } else {
+                break;
+            };
+
+            if entry.user_data() != RECV_USER_DATA {
+                continue;
+            }
+
+            let flags = entry.flags();
+            let result = entry.result();
+
+            // the operation stops posting completions when it fails, e.g. when it runs out of
+            // buffers, and needs to be submitted again
+            if !cqueue::more(flags) {
+                self.armed = false;
+            }
+
+            if result < 0 {
+                // running out of buffers isn't an error since they're returned below
+                if result != -libc::ENOBUFS {
+                    batch.error = Some(io::Error::from_raw_os_error(-result));
+                }
+                continue;
+            }
+
+            let id = if let Some(id) = cqueue::buffer_select(flags) {
+                id
+            } else {
+                continue;
+            };
+
+            let buffer = self.buffers.get(id, result as usize);
+
+            if let Ok(out) = types::RecvMsgOut::parse(buffer, &self.msghdr) {
+                // drop truncated datagrams, which can't be decrypted anyway
+                if !out.is_payload_truncated() && copy(&out, message) {
+                    batch.count += 1;
+                }
+            }
+
+            self.buffers.provide(id);
+        }
+
+        self.backlog = !completion.is_empty();
+        drop(completion);
+
+        self.buffers.publish();
+
+        // submit the operation again now that the buffers are available
+        if !self.armed {
+            self.arm()?;
+        }
+
+        Ok(batch)
+    }
+}
+
+impl Receiver {
+    /// Leaks the memory the kernel may still write to, rather than freeing it
+    #[cold]
+    fn leak(&mut self) {
+        core::mem::forget(core::mem::replace(&mut self.buffers, Buffers::empty()));
+        core::mem::forget(core::mem::replace(
+            &mut self.msghdr,
+            Box::new(unsafe { core::mem::zeroed() }),
+        ));
+    }
+}
+
+impl Drop for Receiver {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+
+        // the kernel tears down the ring asynchronously once it's closed, so cancel the operation
+        // and wait for its last completion before the buffers are freed
+        let entry = opcode::AsyncCancel::new(RECV_USER_DATA)
+            .build()
+            .user_data(CANCEL_USER_DATA);
+
+        // Safety: the operation doesn't reference any memory
+        if unsafe { self.ring.submission().push(&entry) }.is_err() {
+            self.leak();
+            return;
+        }
+
+        while self.armed {
+            match self.ring.submit_and_wait(1) {
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => {
+                    self.leak();
+                    return;
+                }
+            }
+
+            for entry in self.ring.completion() {
+                if entry.user_data() == RECV_USER_DATA && !cqueue::more(entry.flags()) {
+                    self.armed = false;
+                }
+            }
+        }
+    }
+}
+
+/// Copies a datagram received by the multishot `recvmsg` operation into `message`
+///
+/// Returns `false` if the message can't hold the datagram.
+fn copy(out: &types::RecvMsgOut, message: &mut Message) -> bool {
+    let hdr = &mut message.msg_hdr;
+    let name = out.name_data();
+    let control = out.control_data();
+    let payload = out.payload_data();
+
+    #[allow(clippy::unnecessary_cast)] // some platforms encode lengths as `u32`
+    let fits = name.len() <= hdr.msg_namelen as usize
+        && control.len() <= hdr.msg_controllen as usize
+        && payload.len() <= message.payload_len();
+
+    if !fits {
+        return false;
+    }
+
+    unsafe {
+        // Safety: the message points to its own storage, which is at least as large as checked
+        // above
+        core::ptr::copy_nonoverlapping(name.as_ptr(), hdr.msg_name as *mut u8, name.len());
+        core::ptr::copy_nonoverlapping(control.as_ptr(), hdr.msg_control as *mut u8, control.len());
+        hdr.msg_namelen = name.len() as _;
+        hdr.msg_controllen = control.len() as _;
+        hdr.msg_flags = out.flags() as _;
+
+        core::ptr::copy_nonoverlapping(payload.as_ptr(), message.payload_ptr_mut(), payload.len());
+        message.set_payload_len(payload.len());
+    }
+
+    true
+}
+
+/// The buffers which are provided to the kernel for the multishot `recvmsg` operation
+struct Buffers {
+    /// The ring of buffer descriptors which is shared with the kernel
+    ring: NonNull<BufRingEntry>,
+    /// The number of buffers, which is a power of two
+    count: usize,
+    buffer_len: usize,
+    /// The contiguous storage of all of the buffers
+    data: Box<[u8]>,
+    /// The tail of the ring, which is published to the kernel in batches
+    tail: u16,
+}
+
+/// A buffer descriptor, which matches `struct io_uring_buf`
+#[repr(C)]
+struct BufRingEntry {
+    addr: u64,
+    len: u32,
+    bid: u16,
+    /// The `resv` field of the first entry holds the tail of the ring
+    resv: u16,
+}
+
+impl Buffers {
+    fn new(count: usize, buffer_len: usize) -> io::Result<Self> {
+        let layout = Self::layout(count)?;
+
+        // Safety: the layout has a non-zero size
+        let ring = unsafe { alloc::alloc_zeroed(layout) } as *mut BufRingEntry;
+        let ring = NonNull::new(ring)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::OutOfMemory, "buffer ring allocation"))?;
+
+        let data = vec![0u8; count * buffer_len].into_boxed_slice();
+
+        Ok(Self {
+            ring,
+            count,
+            buffer_len,
+            data,
+            tail: 0,
+        })
+    }
+
+    /// Returns a placeholder which doesn't own any memory
+    fn empty() -> Self {
+        Self {
+            ring: NonNull::dangling(),
+            count: 0,
+            buffer_len: 0,
+            data: Box::new([]),
+            tail: 0,
+        }
+    }
+
+    /// The kernel requires the ring to be page aligned
+    fn layout(count: usize) -> io::Result<Layout> {
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(4096) as usize;
+        Layout::from_size_align(count * size_of::<BufRingEntry>(), page_size)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))
+    }
+
+    /// Returns the first `len` bytes of the buffer with `id`
+    #[inline]
+    fn get(&self, id: u16, len: usize) -> &[u8] {
+        let start = id as usize * self.buffer_len;
+        &self.data[start..start + len.min(self.buffer_len)]
+    }
+
+    /// Adds the buffer with `id` to the ring, which the kernel sees after [`Self::publish`]
+    #[inline]
+    fn provide(&mut self, id: u16) {
+        let index = self.tail as usize & (self.count - 1);
+        let addr = self.data[id as usize * self.buffer_len..].as_ptr() as u64;
+
+        // Safety: the index is within the ring. The `resv` field isn't written since the first
+        // entry's field holds the tail.
+        unsafe {
+            let entry = self.ring.as_ptr().add(index);
+            (*entry).addr = addr;
+            (*entry).len = self.buffer_len as _;
+            (*entry).bid = id;
+        }
+
+        self.tail = self.tail.wrapping_add(1);
+    }
+
+    /// Makes the provided buffers available to the kernel
+    #[inline]
+    fn publish(&mut self) {
+        // Safety: the tail overlays the `resv` field of the first entry, which is 2 byte aligned
+        unsafe {
+            let tail = core::ptr::addr_of_mut!((*self.ring.as_ptr()).resv) as *const AtomicU16;
+            (*tail).store(self.tail, Ordering::Release);
+        }
+    }
+}
+
+impl Drop for Buffers {
+    fn drop(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+
+        if let Ok(layout) = Self::layout(self.count) {
+            // Safety: the ring was allocated with the same layout
+            unsafe { alloc::dealloc(self.ring.as_ptr() as *mut u8, layout) }
+        }
+    }
+}
+
+impl<B: Buffer> Queue<B> {
+    pub fn rx_io_uring<Publisher: event::EndpointPublisher>(
+        &mut self,
+        receiver: &mut Receiver,
+        publisher: &mut Publisher,
+    ) -> io::Result<usize> {
+        let max_batch = self.0.max_batch();
+        let mut entries = self.0.free_mut();
+
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let len = entries.len().min(max_batch);
+        let Batch { count, error } = receiver.receive(&mut entries[..len])?;
+
+        if count == 0 {
+            entries.cancel(0);
+        } else {
+            entries.finish(count);
+            publisher.on_platform_rx(event::builder::PlatformRx { count });
+        }
+
+        if let Some(err) = error {
+            // the operation is submitted again on the next call so the error isn't fatal
+            publisher.on_platform_rx_error(event::builder::PlatformRxError {
+                errno: err.raw_os_error().unwrap_or_default(),
+            });
+        }
+
+        Ok(count)
+    }
+}
@@ -13,7 +13,7 @@ use s2n_quic_core::{event, path::LocalAddress};
 use std::{io, os::unix::io::AsRawFd};
 
 #[derive(Debug, Default)]
-pub struct Queue<B: Buffer>(queue::Queue<Ring<B>>);
+pub struct Queue<B: Buffer>(pub(super) queue::Queue<Ring<B>>);
 
 pub use mmsg::Handle;
 
@@ -36,11 +36,24 @@ impl<B: Buffer> Queue<B> {
         self.0.set_local_address(local_address)
     }
 
+    pub fn enable_txtime(&mut self) {
+        self.0.enable_txtime()
+    }
+
+    pub fn enable_flow_labels(&mut self) {
+        self.0.enable_flow_labels()
+    }
+
+    pub fn set_max_batch(&mut self, max_batch: usize) {
+        self.0.set_max_batch(max_batch)
+    }
+
     pub fn tx<Socket: AsRawFd, Publisher: event::EndpointPublisher>(
         &mut self,
         socket: &Socket,
         publisher: &mut Publisher,
     ) -> io::Result<usize> {
+        let max_batch = self.0.max_batch();
         let mut entries = self.0.occupied_mut();
 
         // Safety: calling a libc function is inherently unsafe as rust cannot
@@ -61,7 +74,7 @@ impl<B: Buffer> Queue<B> {
         // > The size of this array is specified in vlen.
         //
         // > The value specified in vlen is capped to UIO_MAXIOV (1024).
-        let vlen = entries.len().min(1024) as _;
+        let vlen = entries.len().min(max_batch).min(1024) as _;
 
         // > The flags argument contains flags ORed together.
         //
@@ -153,6 +166,7 @@ impl<B: Buffer> Queue<B> {
         socket: &Socket,
         publisher: &mut Publisher,
     ) -> io::Result<usize> {
+        let max_batch = self.0.max_batch();
         let mut entries = self.0.free_mut();
 
         if entries.is_empty() {
@@ -172,7 +186,7 @@ impl<B: Buffer> Queue<B> {
         let msgvec = entries.as_mut_ptr() as _;
 
         // > The size of this array is specified in vlen.
-        let vlen = entries.len() as _;
+        let vlen = entries.len().min(max_batch) as _;
 
         // > The flags argument contains flags ORed together.
         //
@@ -36,15 +36,28 @@ impl<B: Buffer> Queue<B> {
         self.0.set_local_address(local_address)
     }
 
+    pub fn enable_txtime(&mut self) {
+        self.0.enable_txtime()
+    }
+
+    pub fn enable_flow_labels(&mut self) {
+        self.0.enable_flow_labels()
+    }
+
+    pub fn set_max_batch(&mut self, max_batch: usize) {
+        self.0.set_max_batch(max_batch)
+    }
+
     pub fn tx<Socket: AsRawFd, Publisher: event::EndpointPublisher>(
         &mut self,
         socket: &Socket,
         publisher: &mut Publisher,
     ) -> io::Result<usize> {
         let mut count = 0;
+        let max_batch = self.0.max_batch();
         let mut entries = self.0.occupied_mut();
 
-        for entry in entries.iter_mut() {
+        for entry in entries.iter_mut().take(max_batch) {
             // macOS doesn't like when msg_control have valid pointers but the len is 0
             //
             // If that's the case here, then set the `msg_control` to null and restore it after
@@ -155,9 +168,10 @@ impl<B: Buffer> Queue<B> {
         publisher: &mut Publisher,
     ) -> io::Result<usize> {
         let mut count = 0;
+        let max_batch = self.0.max_batch();
         let mut entries = self.0.free_mut();
 
-        for entry in entries.as_mut() {
+        for entry in entries.iter_mut().take(max_batch) {
             // Safety: calling a libc function is inherently unsafe as rust cannot
             // make any invariant guarantees. This has to be reviewed by humans instead
             // so the [docs](https://linux.die.net/man/2/recmsg) are inlined here:
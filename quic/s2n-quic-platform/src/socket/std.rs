@@ -95,36 +95,51 @@ impl<B: Buffer> Queue<B> {
         publisher: &mut Publisher,
     ) -> Result<usize, S::Error> {
         let mut count = 0;
+        // the number of entries at the front of the queue which can be released, including
+        // entries that were skipped
+        let mut consumed = 0;
         let mut entries = self.0.occupied_mut();
 
         for entry in entries.as_mut() {
-            if let Some(remote_address) = entry.remote_address() {
-                match socket.send_to(entry.payload_mut(), &remote_address) {
-                    Ok(_) => {
-                        count += 1;
+            // entries without a remote address can't be sent so they are discarded
+            let remote_address = if let Some(remote_address) = entry.remote_address() {
+                remote_address
+            } else {
+                consumed += 1;
+                continue;
+            };
+
+            // Only the entries which were sent are released from the queue. The remaining
+            // entries stay queued and are sent the next time the socket is writable.
+            match socket.send_to(entry.payload_mut(), &remote_address) {
+                Ok(_) => {
+                    count += 1;
+                    consumed += 1;
 
-                        publisher.on_platform_tx(event::builder::PlatformTx { count: 1 });
-                    }
-                    Err(err) if count > 0 && err.would_block() => {
-                        break;
-                    }
-                    Err(err) if err.was_interrupted() || err.permission_denied() => {
-                        break;
-                    }
-                    Err(err) => {
-                        entries.finish(count);
+                    publisher.on_platform_tx(event::builder::PlatformTx { count: 1 });
+                }
+                Err(err) if count > 0 && err.would_block() => {
+                    break;
+                }
+                Err(err) if err.would_block() => {
+                    entries.finish(consumed);
+                    return Err(err);
+                }
+                Err(err) if err.was_interrupted() || err.permission_denied() => {
+                    break;
+                }
+                Err(err) => {
+                    entries.finish(consumed);
 
-                        publisher.on_platform_tx_error(event::builder::PlatformTxError {
-                            errno: errno().0,
-                        });
+                    publisher
+                        .on_platform_tx_error(event::builder::PlatformTxError { errno: errno().0 });
 
-                        return Err(err);
-                    }
+                    return Err(err);
                 }
             }
         }
 
-        entries.finish(count);
+        entries.finish(consumed);
 
         Ok(count)
     }
@@ -189,3 +204,73 @@ impl<B: Buffer> Queue<B> {
         self.0.free_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::VecBuffer;
+    use core::cell::{Cell, RefCell};
+    use s2n_quic_core::{
+        event::testing::Publisher, inet::SocketAddressV4, io::tx::Queue as _, path::Handle as _,
+    };
+    use std::io;
+
+    /// A socket which only accepts a limited number of datagrams before it would block
+    #[derive(Default)]
+    struct ShortSocket {
+        capacity: Cell<usize>,
+        sent: RefCell<Vec<Vec<u8>>>,
+    }
+
+    impl Socket for ShortSocket {
+        type Error = io::Error;
+
+        fn recv_from(&self, _buf: &mut [u8]) -> Result<(usize, Option<SocketAddress>), io::Error> {
+            Err(io::ErrorKind::WouldBlock.into())
+        }
+
+        fn send_to(&self, buf: &[u8], _addr: &SocketAddress) -> Result<usize, io::Error> {
+            let capacity = self.capacity.get();
+            if capacity == 0 {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            self.capacity.set(capacity - 1);
+            self.sent.borrow_mut().push(buf.to_vec());
+            Ok(buf.len())
+        }
+    }
+
+    #[test]
+    fn short_send_test() {
+        let mut queue = Queue::new(VecBuffer::new(8, 1200));
+        let mut publisher = Publisher::no_snapshot();
+        let socket = ShortSocket::default();
+
+        let remote_address = SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+        let handle = Handle::from_remote_address(remote_address);
+
+        for id in 0u8..5 {
+            queue.tx_queue().push((handle, [id])).unwrap();
+        }
+
+        // the socket only accepts some of the datagrams
+        socket.capacity.set(2);
+        assert_eq!(queue.tx(&socket, &mut publisher).unwrap(), 2);
+        assert_eq!(queue.occupied_len(), 3);
+
+        // the socket blocks without sending anything
+        let err = queue.tx(&socket, &mut publisher).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(queue.occupied_len(), 3);
+        assert_eq!(publisher.platform_tx_error, 0);
+
+        // the remaining datagrams are sent after the socket is writable again
+        socket.capacity.set(usize::MAX);
+        assert_eq!(queue.tx(&socket, &mut publisher).unwrap(), 3);
+        assert_eq!(queue.occupied_len(), 0);
+
+        let sent = socket.sent.borrow();
+        let sent: Vec<u8> = sent.iter().map(|payload| payload[0]).collect();
+        assert_eq!(sent, [0, 1, 2, 3, 4]);
+    }
+}
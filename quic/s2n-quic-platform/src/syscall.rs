@@ -65,6 +65,149 @@ pub fn bind_udp<A: std::net::ToSocketAddrs>(addr: A, reuse_port: bool) -> io::Re
     Ok(socket)
 }
 
+/// Returns the number of datagrams the kernel dropped on the socket
+///
+/// This includes the datagrams which arrived while the socket's receive buffer was full. The
+/// counter is read with `SO_MEMINFO` and is only available on Linux.
+pub fn rx_socket_drops(socket: &Socket) -> Option<u32> {
+    let mut drops = None;
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::io::AsRawFd;
+
+        // from linux/sock_diag.h
+        const SK_MEMINFO_DROPS: usize = 8;
+        const SK_MEMINFO_VARS: usize = 9;
+
+        let mut meminfo = [0u32; SK_MEMINFO_VARS];
+        let mut len = core::mem::size_of_val(&meminfo) as libc::socklen_t;
+
+        let result = libc!(getsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_MEMINFO,
+            meminfo.as_mut_ptr() as _,
+            &mut len,
+        ));
+
+        // older kernels may report fewer fields
+        if result.is_ok() && len as usize > SK_MEMINFO_DROPS * core::mem::size_of::<u32>() {
+            drops = Some(meminfo[SK_MEMINFO_DROPS]);
+        }
+    }
+
+    // mark the variable as "used" regardless of platform support
+    let _ = socket;
+
+    drops
+}
+
+/// Restricts the socket to sending and receiving on the network interface named `interface`
+#[cfg(all(s2n_quic_platform_bind_interface, not(target_os = "macos")))]
+pub fn bind_interface(socket: &Socket, interface: &str) -> io::Result<()> {
+    socket.bind_device(Some(interface.as_bytes()))
+}
+
+/// Restricts the socket to sending and receiving on the network interface named `interface`
+#[cfg(all(s2n_quic_platform_bind_interface, target_os = "macos"))]
+pub fn bind_interface(socket: &Socket, interface: &str) -> io::Result<()> {
+    use std::{ffi::CString, os::unix::io::AsRawFd};
+
+    let name =
+        CString::new(interface).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    if index == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let (level, name) = if socket.local_addr()?.as_socket_ipv6().is_some() {
+        (libc::IPPROTO_IPV6, libc::IPV6_BOUND_IF)
+    } else {
+        (libc::IPPROTO_IP, libc::IP_BOUND_IF)
+    };
+
+    let index = index as libc::c_int;
+    libc!(setsockopt(
+        socket.as_raw_fd(),
+        level,
+        name,
+        &index as *const _ as _,
+        core::mem::size_of_val(&index) as _,
+    ))?;
+
+    Ok(())
+}
+
+/// Restricts the socket to sending and receiving on the network interface named `interface`
+#[cfg(not(s2n_quic_platform_bind_interface))]
+pub fn bind_interface(_socket: &Socket, _interface: &str) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "binding to an interface is not supported on the current platform",
+    ))
+}
+
+/// Steers the datagrams of a SO_REUSEPORT group to the socket of the worker that owns the
+/// destination connection ID
+///
+/// The first byte of the destination connection ID modulo `workers` is used as the index of the
+/// socket in the group, which is the order the sockets were bound in. The program applies to the
+/// whole group so it only needs to be attached to one of the sockets.
+#[cfg(target_os = "linux")]
+pub fn attach_reuse_port_steering(socket: &Socket, workers: u8) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // Classic BPF opcodes from linux/filter.h
+    const BPF_LD: u16 = 0x00;
+    const BPF_ALU: u16 = 0x04;
+    const BPF_JMP: u16 = 0x05;
+    const BPF_RET: u16 = 0x06;
+    const BPF_B: u16 = 0x10;
+    const BPF_ABS: u16 = 0x20;
+    const BPF_K: u16 = 0x00;
+    const BPF_A: u16 = 0x10;
+    const BPF_JA: u16 = 0x00;
+    const BPF_JSET: u16 = 0x40;
+    const BPF_MOD: u16 = 0x90;
+
+    const fn op(code: u16, jt: u8, jf: u8, k: u32) -> libc::sock_filter {
+        libc::sock_filter { code, jt, jf, k }
+    }
+
+    debug_assert_ne!(workers, 0, "at least one worker is required");
+
+    // The program runs with the offsets relative to the UDP payload
+    let mut program = [
+        // load the first byte of the packet
+        op(BPF_LD | BPF_B | BPF_ABS, 0, 0, 0),
+        // check the header form bit
+        op(BPF_JMP | BPF_JSET | BPF_K, 0, 2, 0x80),
+        // long header: the destination connection ID follows the version and length
+        op(BPF_LD | BPF_B | BPF_ABS, 0, 0, 6),
+        op(BPF_JMP | BPF_JA, 0, 0, 1),
+        // short header: the destination connection ID follows the first byte
+        op(BPF_LD | BPF_B | BPF_ABS, 0, 0, 1),
+        op(BPF_ALU | BPF_MOD | BPF_K, 0, 0, workers as u32),
+        op(BPF_RET | BPF_A, 0, 0, 0),
+    ];
+
+    let program = libc::sock_fprog {
+        len: program.len() as _,
+        filter: program.as_mut_ptr(),
+    };
+
+    libc!(setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_REUSEPORT_CBPF,
+        &program as *const _ as _,
+        core::mem::size_of_val(&program) as _,
+    ))?;
+
+    Ok(())
+}
+
 /// Binds a socket to a specified interface by name
 #[cfg(feature = "xdp")]
 pub fn bind_to_interface<F: std::os::unix::io::AsRawFd>(
@@ -196,6 +339,122 @@ pub fn configure_pktinfo(rx_socket: &Socket) -> bool {
     success
 }
 
+/// Configures the socket to return the TTL/hop limit of received datagrams as part of the
+/// ancillary data
+pub fn configure_ttl(rx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(s2n_quic_platform_ttl)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        success |= libc!(setsockopt(
+            rx_socket.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_RECVTTL,
+            &enabled as *const _ as _,
+            core::mem::size_of_val(&enabled) as _,
+        ))
+        .is_ok();
+
+        success |= libc!(setsockopt(
+            rx_socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVHOPLIMIT,
+            &enabled as *const _ as _,
+            core::mem::size_of_val(&enabled) as _,
+        ))
+        .is_ok();
+    }
+
+    success
+}
+
+/// Configures the socket to return the time each datagram was received by the kernel as part of
+/// the ancillary data
+pub fn configure_rx_timestamp(rx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(s2n_quic_platform_rx_timestamp)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        success |= libc!(setsockopt(
+            rx_socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_TIMESTAMPNS,
+            &enabled as *const _ as _,
+            core::mem::size_of_val(&enabled) as _,
+        ))
+        .is_ok();
+    }
+
+    // mark the variable as "used" regardless of platform support
+    let _ = rx_socket;
+
+    success
+}
+
+/// Configures the socket to accept transmit times as part of the ancillary data
+///
+/// The transmit times are interpreted with `CLOCK_MONOTONIC` and are enforced by the `fq` or
+/// `etf` queueing disciplines.
+pub fn configure_txtime(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(s2n_quic_platform_txtime)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let config = libc::sock_txtime {
+            clockid: libc::CLOCK_MONOTONIC,
+            flags: 0,
+        };
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_TXTIME,
+            &config as *const _ as _,
+            core::mem::size_of_val(&config) as _,
+        ))
+        .is_ok();
+    }
+
+    // mark the variable as "used" regardless of platform support
+    let _ = tx_socket;
+
+    success
+}
+
+/// Configures the socket to send the IPv6 flow label included in the destination address
+///
+/// The kernel generates a flow label for messages which don't include one.
+pub fn configure_flow_labels(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(s2n_quic_platform_flow_label)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_FLOWINFO_SEND,
+            &enabled as *const _ as _,
+            core::mem::size_of_val(&enabled) as _,
+        ))
+        .is_ok();
+    }
+
+    // mark the variable as "used" regardless of platform support
+    let _ = tx_socket;
+
+    success
+}
+
 #[allow(dead_code)] // TODO remove once used
 pub fn configure_gro(rx_socket: &Socket) -> bool {
     let mut success = false;
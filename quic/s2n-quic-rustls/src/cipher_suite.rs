@@ -317,6 +317,40 @@ pub static DEFAULT_CIPHERSUITES: &[SupportedCipherSuite] = &[
     ciphers::TLS13_CHACHA20_POLY1305_SHA256,
 ];
 
+/// Maps the cipher suites, in order of preference, to the rustls implementations
+pub(crate) fn supported_cipher_suites<I: IntoIterator<Item = tls::CipherSuite>>(
+    cipher_suites: I,
+) -> Result<Vec<SupportedCipherSuite>, rustls::Error> {
+    let mut supported = vec![];
+
+    for cipher_suite in cipher_suites {
+        let cipher_suite = match cipher_suite {
+            tls::CipherSuite::TLS_AES_128_GCM_SHA256 => ciphers::TLS13_AES_128_GCM_SHA256,
+            tls::CipherSuite::TLS_AES_256_GCM_SHA384 => ciphers::TLS13_AES_256_GCM_SHA384,
+            tls::CipherSuite::TLS_CHACHA20_POLY1305_SHA256 => {
+                ciphers::TLS13_CHACHA20_POLY1305_SHA256
+            }
+            _ => {
+                return Err(rustls::Error::General(
+                    "unsupported cipher suite".to_string(),
+                ))
+            }
+        };
+
+        if !supported.contains(&cipher_suite) {
+            supported.push(cipher_suite);
+        }
+    }
+
+    if supported.is_empty() {
+        return Err(rustls::Error::General(
+            "at least one cipher suite is required".to_string(),
+        ));
+    }
+
+    Ok(supported)
+}
+
 #[test]
 fn test_default_cipher_suites() {
     insta::assert_debug_snapshot!("default_cipher_suites", DEFAULT_CIPHERSUITES);
@@ -90,6 +90,8 @@ pub struct Builder {
     cert_store: rustls::RootCertStore,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    cipher_suites: Vec<rustls::SupportedCipherSuite>,
+    kx_groups: Vec<&'static rustls::SupportedKxGroup>,
 }
 
 impl Default for Builder {
@@ -104,6 +106,8 @@ impl Builder {
             cert_store: rustls::RootCertStore::empty(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            cipher_suites: crate::cipher_suite::DEFAULT_CIPHERSUITES.to_vec(),
+            kx_groups: rustls::ALL_KX_GROUPS.to_vec(),
         }
     }
 
@@ -135,8 +139,40 @@ impl Builder {
         Ok(self)
     }
 
-    pub fn with_key_logging(mut self) -> Result<Self, rustls::Error> {
-        self.key_log = Some(Arc::new(rustls::KeyLogFile::new()));
+    /// Logs the TLS secrets to the file named by the `SSLKEYLOGFILE` environment variable
+    ///
+    /// The file uses the NSS key log format, which tools like Wireshark use to decrypt captures.
+    pub fn with_key_logging(self) -> Result<Self, rustls::Error> {
+        self.with_key_log(Arc::new(rustls::KeyLogFile::new()))
+    }
+
+    /// Passes the TLS secrets to the application-provided `key_log` as they are derived
+    pub fn with_key_log(mut self, key_log: Arc<dyn rustls::KeyLog>) -> Result<Self, rustls::Error> {
+        self.key_log = Some(key_log);
+        Ok(self)
+    }
+
+    /// Sets the cipher suites which can be negotiated, in order of preference
+    pub fn with_cipher_suites<I: IntoIterator<Item = tls::CipherSuite>>(
+        mut self,
+        cipher_suites: I,
+    ) -> Result<Self, rustls::Error> {
+        self.cipher_suites = crate::cipher_suite::supported_cipher_suites(cipher_suites)?;
+        Ok(self)
+    }
+
+    /// Sets the key exchange groups which can be negotiated, in order of preference
+    pub fn with_key_exchange_groups(
+        mut self,
+        kx_groups: &[&'static rustls::SupportedKxGroup],
+    ) -> Result<Self, rustls::Error> {
+        if kx_groups.is_empty() {
+            return Err(rustls::Error::General(
+                "at least one key exchange group is required".to_string(),
+            ));
+        }
+
+        self.kx_groups = kx_groups.to_vec();
         Ok(self)
     }
 
@@ -151,8 +187,8 @@ impl Builder {
         }
 
         let mut config = ClientConfig::builder()
-            .with_cipher_suites(crate::cipher_suite::DEFAULT_CIPHERSUITES)
-            .with_safe_default_kx_groups()
+            .with_cipher_suites(&self.cipher_suites)
+            .with_kx_groups(&self.kx_groups)
             .with_protocol_versions(crate::PROTOCOL_VERSIONS)?
             .with_root_certificates(self.cert_store)
             .with_no_client_auth();
@@ -57,3 +57,112 @@ fn client_server_test() {
 
     pair.finish();
 }
+
+#[test]
+fn key_log_test() {
+    use s2n_quic_core::crypto::tls::{self, testing::certificates::*};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct Labels(Mutex<Vec<String>>);
+
+    impl rustls::KeyLog for Labels {
+        fn log(&self, label: &str, _client_random: &[u8], _secret: &[u8]) {
+            self.0.lock().unwrap().push(label.to_string());
+        }
+    }
+
+    let client_labels = Arc::new(Labels::default());
+    let server_labels = Arc::new(Labels::default());
+
+    let mut client = client::Builder::new()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_key_log(client_labels.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut server = server::Builder::new()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_key_log(server_labels.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+
+    for labels in [client_labels, server_labels] {
+        let labels = labels.0.lock().unwrap();
+        for expected in [
+            "CLIENT_HANDSHAKE_TRAFFIC_SECRET",
+            "SERVER_HANDSHAKE_TRAFFIC_SECRET",
+            "CLIENT_TRAFFIC_SECRET_0",
+            "SERVER_TRAFFIC_SECRET_0",
+        ] {
+            assert!(labels.iter().any(|label| label == expected), "{expected}");
+        }
+    }
+}
+
+#[test]
+fn cipher_suite_preference_test() {
+    use s2n_quic_core::crypto::{
+        tls::{self, testing::certificates::*, CipherSuite},
+        Key,
+    };
+
+    let mut client = client::Builder::new()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_cipher_suites([CipherSuite::TLS_CHACHA20_POLY1305_SHA256])
+        .unwrap()
+        .with_key_exchange_groups(&[&rustls::kx_group::SECP256R1])
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut server = server::Builder::new()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+
+    for (key, _header_key) in [
+        pair.server.context.application.crypto.as_ref().unwrap(),
+        pair.client.context.application.crypto.as_ref().unwrap(),
+    ] {
+        assert_eq!(
+            key.cipher_suite(),
+            CipherSuite::TLS_CHACHA20_POLY1305_SHA256
+        );
+    }
+}
+
+#[test]
+fn invalid_cipher_suites_test() {
+    use s2n_quic_core::crypto::tls::CipherSuite;
+
+    assert!(client::Builder::new().with_cipher_suites([]).is_err());
+    assert!(server::Builder::new()
+        .with_cipher_suites([CipherSuite::Unknown])
+        .is_err());
+    assert!(server::Builder::new()
+        .with_key_exchange_groups(&[])
+        .is_err());
+}
@@ -148,6 +148,16 @@ impl Session {
 
             // the handshake is complete!
             if !self.emitted_handshake_complete {
+                if let Some(certificates) = self.connection.peer_certificates() {
+                    let chain: Vec<_> = certificates
+                        .iter()
+                        .map(|certificate| Bytes::copy_from_slice(&certificate.0))
+                        .collect();
+                    if !chain.is_empty() {
+                        context.on_peer_certificate_chain(chain)?;
+                    }
+                }
+
                 self.rx_phase.transition();
                 context.on_handshake_complete()?;
             }
@@ -310,6 +320,21 @@ impl tls::Session for Session {
         self.emit_events(context)?;
         result
     }
+
+    fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError> {
+        self.connection
+            .export_keying_material(output, label, Some(context))
+            .map_err(|_| {
+                CryptoError::INTERNAL_ERROR
+                    .with_reason("unable to export keying material")
+                    .into()
+            })
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
@@ -9,7 +9,10 @@ use s2n_quic_core::{
     endpoint, transport,
 };
 use s2n_quic_crypto::{
-    handshake::HandshakeKey, hkdf, one_rtt::OneRttKey, ring_aead as aead, Prk, SecretPair, Suite,
+    handshake::HandshakeKey,
+    hkdf,
+    one_rtt::{OneRttHeaderKey, OneRttKey},
+    ring_aead as aead, Prk, SecretPair, Suite,
 };
 use s2n_tls::{connection::Connection, error::Fallible, ffi::*};
 
@@ -29,6 +32,8 @@ pub struct Callback<'a, T, C> {
     pub send_buffer: &'a mut BytesMut,
     pub emitted_server_name: &'a mut bool,
     pub server_name: &'a Option<ServerName>,
+    /// Holds back the client's 1-RTT keys until the server's certificate chain is verified
+    pub verify_peer: bool,
 }
 
 impl<'a, T, C> Callback<'a, T, C>
@@ -132,6 +137,35 @@ where
         }
     }
 
+    /// Installs the 1-RTT keys which were held back until the peer's certificate chain is verified
+    ///
+    /// `verify` is only called if keys are waiting on the verification.
+    pub fn on_peer_verified<F>(&mut self, verify: F) -> Result<(), transport::Error>
+    where
+        F: FnOnce() -> Result<(), CryptoError>,
+    {
+        let PendingOneRttKeys {
+            key,
+            header_key,
+            transport_parameters,
+        } = if let Some(keys) = self.state.pending_one_rtt_keys.take() {
+            keys
+        } else {
+            return Ok(());
+        };
+
+        if let Err(error) = verify() {
+            // the Finished message isn't sent for a rejected chain
+            self.send_buffer.clear();
+            return Err(error.into());
+        }
+
+        let params = tls::ApplicationParameters {
+            transport_parameters: &transport_parameters,
+        };
+        self.context.on_one_rtt_keys(key, header_key, params)
+    }
+
     /// The function s2n-tls calls when it emits secrets
     unsafe extern "C" fn secret_cb(
         context: *mut c_void,
@@ -238,7 +272,27 @@ where
                             get_application_params(conn)?
                         };
 
-                        self.context.on_one_rtt_keys(key, header_key, params)?;
+                        // The client emits its application secrets once it has processed the
+                        // server's Certificate, CertificateVerify and Finished messages. The keys
+                        // are held back until the session verifies the chain, so the handshake
+                        // fails before the client sends its Finished message or any 1-RTT
+                        // packets. Resumed sessions don't present a chain, since it was verified
+                        // when the session was established.
+                        let is_resumed = unsafe {
+                            // Safety: conn is valid for the duration of the callback
+                            s2n_connection_is_session_resumed(conn) == 1
+                        };
+                        if self.verify_peer && self.endpoint.is_client() && !is_resumed {
+                            self.state.pending_one_rtt_keys = Some(PendingOneRttKeys {
+                                key,
+                                header_key,
+                                transport_parameters: Bytes::copy_from_slice(
+                                    params.transport_parameters,
+                                ),
+                            });
+                        } else {
+                            self.context.on_one_rtt_keys(key, header_key, params)?;
+                        }
                     }
                 }
 
@@ -344,6 +398,7 @@ pub struct State {
     rx_phase: HandshakePhase,
     tx_phase: HandshakePhase,
     secrets: Secrets,
+    pending_one_rtt_keys: Option<PendingOneRttKeys>,
 }
 
 impl State {
@@ -358,6 +413,14 @@ impl State {
     }
 }
 
+/// The client's 1-RTT keys, which are held back until the server's certificate chain is verified
+#[derive(Debug)]
+struct PendingOneRttKeys {
+    key: OneRttKey,
+    header_key: OneRttHeaderKey,
+    transport_parameters: Bytes,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 enum HandshakePhase {
     Initial,
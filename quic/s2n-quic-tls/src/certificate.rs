@@ -3,16 +3,9 @@
 
 use bytes::Bytes;
 use s2n_tls::error::Error;
+use std::borrow::Cow;
 
 impl Format {
-    pub fn as_pem(&self) -> Option<&[u8]> {
-        if let Format::Pem(bytes) = &self {
-            Some(bytes.as_ref())
-        } else {
-            None
-        }
-    }
-
     #[allow(dead_code)] // remove if s2n-tls ever starts supporting DER certs
     pub fn as_der(&self) -> Option<&[u8]> {
         if let Format::Der(bytes) = &self {
@@ -109,5 +102,88 @@ macro_rules! cert_type {
 cert_type!(PrivateKey, IntoPrivateKey, into_private_key);
 cert_type!(Certificate, IntoCertificate, into_certificate);
 
+impl Certificate {
+    /// Returns the certificate in PEM format
+    ///
+    /// s2n-tls only loads PEM, so DER contents are wrapped in PEM armor. As with the rustls
+    /// provider, DER contents hold a single certificate.
+    pub(crate) fn to_pem(&self) -> Cow<[u8]> {
+        match &self.0 {
+            Format::Pem(bytes) => Cow::Borrowed(bytes.as_ref()),
+            Format::Der(bytes) => Cow::Owned(der_to_pem(bytes, "CERTIFICATE")),
+            Format::None => unreachable!("certificates are always loaded from PEM or DER"),
+        }
+    }
+}
+
+impl PrivateKey {
+    /// Returns the private key in PEM format
+    ///
+    /// s2n-tls detects whether the key is in PKCS #8, PKCS #1 (RSA) or SEC1 (EC) format from its
+    /// contents, so DER keys are always labeled as PKCS #8. Returns `None` if the private key
+    /// operations are offloaded.
+    pub(crate) fn to_pem(&self) -> Option<Cow<[u8]>> {
+        match &self.0 {
+            Format::Pem(bytes) => Some(Cow::Borrowed(bytes.as_ref())),
+            Format::Der(bytes) => Some(Cow::Owned(der_to_pem(bytes, "PRIVATE KEY"))),
+            Format::None => None,
+        }
+    }
+}
+
+fn der_to_pem(der: &[u8], tag: &str) -> Vec<u8> {
+    let pem = pem::Pem {
+        tag: tag.to_string(),
+        contents: der.to_vec(),
+    };
+    pem::encode(&pem).into_bytes()
+}
+
 #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
 pub const OFFLOAD_PRIVATE_KEY: PrivateKey = PrivateKey(Format::None);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::{pkey::PKey, x509::X509};
+    use s2n_quic_core::crypto::tls::testing::certificates::{
+        CERT_PEM, KEY_PEM, RSA_CERT_PEM, RSA_KEY_PEM,
+    };
+    use s2n_tls::config;
+
+    #[test]
+    fn der_certificate_test() {
+        let der = X509::from_pem(CERT_PEM.as_bytes())
+            .unwrap()
+            .to_der()
+            .unwrap();
+        let certificate = der.into_certificate().unwrap();
+        let pem = X509::from_pem(&certificate.to_pem()).unwrap();
+        assert_eq!(pem.to_der().unwrap(), der);
+    }
+
+    #[test]
+    fn der_private_key_test() {
+        let ec = PKey::private_key_from_pem(KEY_PEM.as_bytes()).unwrap();
+        let rsa = PKey::private_key_from_pem(RSA_KEY_PEM.as_bytes()).unwrap();
+
+        let keys = [
+            (CERT_PEM, ec.private_key_to_pkcs8().unwrap()),
+            (RSA_CERT_PEM, rsa.private_key_to_pkcs8().unwrap()),
+            // SEC1
+            (CERT_PEM, ec.ec_key().unwrap().private_key_to_der().unwrap()),
+            // PKCS #1
+            (
+                RSA_CERT_PEM,
+                rsa.rsa().unwrap().private_key_to_der().unwrap(),
+            ),
+        ];
+
+        for (certificate, private_key) in keys {
+            let private_key = private_key.into_private_key().unwrap();
+            config::Builder::new()
+                .load_pem(certificate.as_bytes(), &private_key.to_pem().unwrap())
+                .unwrap();
+        }
+    }
+}
@@ -5,24 +5,36 @@ use crate::{
     certificate::{IntoCertificate, IntoPrivateKey},
     keylog::KeyLogHandle,
     params::Params,
+    resumption,
     session::Session,
+    verifier::CertificateVerifier,
     ConfigLoader,
 };
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls,
+    endpoint,
+    time::{SystemClock, WallClock},
+};
 use s2n_tls::{
     callbacks::VerifyHostNameCallback,
     config::{self, Config},
-    enums::ClientAuthType,
+    enums::{ClientAuthType, StatusRequestType},
     error::Error,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 pub struct Client<L: ConfigLoader = Config> {
     loader: L,
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    resumption_state: Option<resumption::State>,
+    cache: Option<Arc<resumption::Cache>>,
+    verifier: Option<Arc<dyn CertificateVerifier>>,
+    /// Expires session tickets, which is set to the endpoint's wall clock when it's started
+    clock: Arc<dyn WallClock>,
 }
 
 impl Client {
@@ -44,6 +56,18 @@ impl<L: ConfigLoader> Client<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            resumption_state: None,
+            cache: None,
+            verifier: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_shared_loader(loader: L, keylog: Option<KeyLogHandle>) -> Self {
+        Self {
+            keylog,
+            ..Self::from_loader(loader)
         }
     }
 }
@@ -66,6 +90,9 @@ impl<L: ConfigLoader> ConfigLoader for Client<L> {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    resumption_state: Option<resumption::State>,
+    cache: Option<Arc<resumption::Cache>>,
+    verifier: Option<Arc<dyn CertificateVerifier>>,
 }
 
 impl Default for Builder {
@@ -79,6 +106,9 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            resumption_state: None,
+            cache: None,
+            verifier: None,
         }
     }
 }
@@ -92,13 +122,50 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the security policy, which selects the cipher suites and key exchange groups that
+    /// are negotiated
+    ///
+    /// Any s2n-tls policy can be used, including those looked up by version name with
+    /// [`crate::security::Policy::from_version`]. QUIC requires TLS 1.3, so an error is returned
+    /// for policies which don't negotiate it.
+    ///
+    /// Defaults to [`crate::security::DEFAULT_TLS13`], or [`crate::security::TESTING_PQ`] when
+    /// built with `--cfg s2n_quic_unstable --cfg s2n_quic_enable_pq_tls`.
+    pub fn with_security_policy(mut self, policy: &crate::security::Policy) -> Result<Self, Error> {
+        crate::ensure_tls13(policy)?;
+        self.config.set_security_policy(policy)?;
+        Ok(self)
+    }
+
+    /// Restricts the cipher suites which can be negotiated, in order of preference
+    ///
+    /// s2n-tls only negotiates the cipher suites of the security policy, so this always returns
+    /// an error. Use [`Self::with_security_policy`] with a policy that only includes the
+    /// preferred cipher suites instead.
+    pub fn with_cipher_suites<I: IntoIterator<Item = tls::CipherSuite>>(
+        self,
+        cipher_suites: I,
+    ) -> Result<Self, Error> {
+        let _ = cipher_suites;
+        Err(crate::unsupported_preference("cipher suites"))
+    }
+
+    /// Restricts the key exchange groups which can be negotiated, in order of preference
+    ///
+    /// s2n-tls only negotiates the groups of the security policy, so this always returns an
+    /// error. Use [`Self::with_security_policy`] with a policy that only includes the preferred
+    /// groups instead.
+    pub fn with_key_exchange_groups<'a, I: IntoIterator<Item = &'a str>>(
+        self,
+        kx_groups: I,
+    ) -> Result<Self, Error> {
+        let _ = kx_groups;
+        Err(crate::unsupported_preference("key exchange groups"))
+    }
+
     pub fn with_certificate<C: IntoCertificate>(mut self, certificate: C) -> Result<Self, Error> {
         let certificate = certificate.into_certificate()?;
-        let certificate = certificate
-            .0
-            .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        self.config.trust_pem(certificate)?;
+        self.config.trust_pem(&certificate.to_pem())?;
         Ok(self)
     }
 
@@ -128,16 +195,10 @@ impl Builder {
     ) -> Result<Self, Error> {
         let certificate = certificate.into_certificate()?;
         let private_key = private_key.into_private_key()?;
-        self.config.load_pem(
-            certificate
-                .0
-                .as_pem()
-                .expect("pem is currently the only certificate format supported"),
-            private_key
-                .0
-                .as_pem()
-                .expect("pem is currently the only certificate format supported"),
-        )?;
+        let private_key = private_key
+            .to_pem()
+            .expect("client identities require a private key");
+        self.config.load_pem(&certificate.to_pem(), &private_key)?;
         self.config.set_client_auth_type(ClientAuthType::Required)?;
         Ok(self)
     }
@@ -156,6 +217,42 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets a verifier which decides whether the server's certificate chain is trusted
+    ///
+    /// The verifier replaces the default validation of the chain against the trust store, so
+    /// configured certificates and host name callbacks are no longer consulted. The server must
+    /// still prove ownership of its leaf certificate during the handshake.
+    ///
+    /// The verifier is called during the handshake, once the server's certificate chain has been
+    /// received and before the client installs its 1-RTT keys or sends its Finished message. It
+    /// isn't called when a session is resumed, since the server doesn't present its chain again.
+    pub fn with_certificate_verifier<T: CertificateVerifier>(
+        mut self,
+        verifier: T,
+    ) -> Result<Self, Error> {
+        unsafe {
+            // Safety: the chain is validated by the verifier before the handshake completes
+            self.config.disable_x509_verification()?;
+        }
+        self.verifier = Some(Arc::new(verifier));
+        Ok(self)
+    }
+
+    /// Requests that the server staples an OCSP response to its certificate
+    ///
+    /// A stapled response is validated against the trust store and the handshake fails if it is
+    /// invalid or reports the certificate as revoked. Servers which don't staple a response are
+    /// still accepted.
+    pub fn with_ocsp_stapling(mut self, enabled: bool) -> Result<Self, Error> {
+        let request_type = if enabled {
+            StatusRequestType::OCSP
+        } else {
+            StatusRequestType::None
+        };
+        self.config.set_status_request_type(request_type)?;
+        Ok(self)
+    }
+
     pub fn with_max_cert_chain_depth(mut self, len: u16) -> Result<Self, Error> {
         self.config.set_max_cert_chain_depth(len)?;
         Ok(self)
@@ -181,11 +278,65 @@ impl Builder {
         Ok(self)
     }
 
-    pub fn build(self) -> Result<Client, Error> {
+    /// Enables receiving session tickets from the server
+    ///
+    /// Once a ticket has been received, the session can be exported with
+    /// [`Session::to_resumption_state`] and used to resume it in a future connection.
+    pub fn with_session_tickets(mut self, enabled: bool) -> Result<Self, Error> {
+        self.config.enable_session_tickets(enabled)?;
+        Ok(self)
+    }
+
+    /// Enables caching the session tickets issued by servers
+    ///
+    /// The most recent ticket issued by each server is kept in memory and used to resume the
+    /// session when connecting to the same server name again. Tickets loaded with
+    /// [`Self::with_resumption_state`] are only used if no ticket is cached for the server.
+    pub fn with_session_cache(mut self, enabled: bool) -> Result<Self, Error> {
+        if enabled {
+            self.config.enable_session_tickets(true)?;
+            self.cache = Some(Default::default());
+        } else {
+            self.cache = None;
+        }
+        Ok(self)
+    }
+
+    /// Loads resumption state previously exported with [`Session::to_resumption_state`]
+    ///
+    /// New sessions will attempt to resume the exported session. If the server rejects the
+    /// ticket, a full handshake is performed instead.
+    ///
+    /// Returns an error if the state is corrupt or has expired. New sessions check the
+    /// expiration again against the endpoint's wall clock.
+    pub fn with_resumption_state(self, state: &[u8]) -> Result<Self, resumption::Error> {
+        self.with_resumption_state_at(state, SystemTime::now())
+    }
+
+    /// Loads resumption state previously exported with [`Session::to_resumption_state`],
+    /// checking its expiration against `now` rather than the system time
+    pub fn with_resumption_state_at(
+        mut self,
+        state: &[u8],
+        now: SystemTime,
+    ) -> Result<Self, resumption::Error> {
+        self.resumption_state = Some(resumption::State::decode(state, now)?);
+        Ok(self)
+    }
+
+    pub fn build(mut self) -> Result<Client, Error> {
+        if self.resumption_state.is_some() {
+            self.config.enable_session_tickets(true)?;
+        }
+
         Ok(Client {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            resumption_state: self.resumption_state,
+            cache: self.cache,
+            verifier: self.verifier,
+            clock: Arc::new(SystemClock),
         })
     }
 }
@@ -205,12 +356,39 @@ impl<L: ConfigLoader> tls::Endpoint for Client<L> {
         let config = self.loader.load(crate::ConnectionContext {
             server_name: Some(&server_name),
         });
-        self.params.with(params, |params| {
+        let now = self.clock.now();
+        let cached_state = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(&server_name, now));
+        let mut session = self.params.with(params, |params| {
             Session::new(endpoint::Type::Client, config, params, Some(server_name)).unwrap()
-        })
+        });
+        session.verifier = self.verifier.clone();
+        session.cache = self.cache.clone();
+        session.clock = self.clock.clone();
+
+        if let Some(state) = cached_state {
+            // fall back to a full handshake if the ticket is rejected
+            let _ = session.connection.set_session_ticket(state.ticket());
+        } else if let Some(state) = self.resumption_state.as_ref() {
+            if state.is_expired(now) {
+                // the ticket can no longer be used so don't keep trying to resume with it
+                self.resumption_state = None;
+            } else {
+                // fall back to a full handshake if the ticket is rejected
+                let _ = session.connection.set_session_ticket(state.ticket());
+            }
+        }
+
+        session
     }
 
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn set_wall_clock(&mut self, clock: &Arc<dyn WallClock>) {
+        self.clock = clock.clone();
+    }
 }
@@ -14,6 +14,73 @@ static DEFAULT_POLICY: &s2n_tls::security::Policy = &s2n_tls::security::TESTING_
 #[cfg(not(all(s2n_quic_unstable, s2n_quic_enable_pq_tls)))]
 static DEFAULT_POLICY: &s2n_tls::security::Policy = &s2n_tls::security::DEFAULT_TLS13;
 
+/// Returns an error if the security policy doesn't negotiate TLS 1.3, which QUIC requires
+///
+/// s2n-tls doesn't expose the versions a policy allows, so a client connection with the policy
+/// writes its ClientHello and the highest version it offers is checked.
+fn ensure_tls13(policy: &s2n_tls::security::Policy) -> Result<(), s2n_tls::error::Error> {
+    use s2n_tls::{error::Fallible, ffi::*};
+
+    unsafe extern "C" fn send(
+        _context: *mut libc::c_void,
+        _buf: *const u8,
+        len: u32,
+    ) -> libc::c_int {
+        len as _
+    }
+
+    unsafe extern "C" fn recv(
+        _context: *mut libc::c_void,
+        _buf: *mut u8,
+        _len: u32,
+    ) -> libc::c_int {
+        // block on the ServerHello
+        errno::set_errno(errno::Errno(libc::EWOULDBLOCK));
+        -1
+    }
+
+    let mut config = s2n_tls::config::Builder::default();
+    config.set_security_policy(policy)?;
+
+    let version = unsafe {
+        // Safety: the connection is freed before the config it references
+        let connection = s2n_connection_new(s2n_mode::CLIENT).into_result()?.as_ptr();
+        let version = (|| {
+            s2n_connection_set_config(connection, config.as_mut_ptr()).into_result()?;
+            s2n_connection_set_send_cb(connection, Some(send)).into_result()?;
+            s2n_connection_set_recv_cb(connection, Some(recv)).into_result()?;
+            let mut blocked = s2n_blocked_status::NOT_BLOCKED;
+            // the ClientHello is written before the handshake blocks
+            let _ = s2n_negotiate(connection, &mut blocked);
+            s2n_connection_get_client_protocol_version(connection).into_result()
+        })();
+        let _ = s2n_connection_free(connection);
+        version?
+    };
+
+    if version < S2N_TLS13 as _ {
+        let error = std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the security policy doesn't negotiate TLS 1.3",
+        );
+        return Err(s2n_tls::error::Error::io_error(error));
+    }
+
+    Ok(())
+}
+
+/// Returns the error for cipher suite and key exchange group preferences
+///
+/// The s2n-tls bindings only select named security policies, which fix the cipher suites and
+/// groups along with their order.
+fn unsupported_preference(preference: &str) -> s2n_tls::error::Error {
+    let error = std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("{preference} are selected with a security policy in s2n-tls"),
+    );
+    s2n_tls::error::Error::io_error(error)
+}
+
 #[non_exhaustive]
 pub struct ConnectionContext<'a> {
     pub server_name: Option<&'a ServerName>,
@@ -54,10 +121,17 @@ mod session;
 
 pub mod certificate;
 pub mod client;
+pub mod resumption;
 pub mod server;
+pub mod signature;
+pub mod verifier;
 
 pub use client::Client;
 pub use server::Server;
+pub use session::Session;
+
+// Re-export the security policies so the defaults can be referenced
+pub use s2n_tls::security;
 
 // Re-export the `ClientHelloHandler` and `Connection` to make it easier for users
 // to consume. This depends on experimental behavior in s2n-tls.
@@ -0,0 +1,179 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serialization of the state needed for a client to resume a TLS session
+//!
+//! The state wraps the session ticket issued by the server along with the time at which the
+//! ticket can no longer be used. This allows applications to persist the state across process
+//! restarts and reject it once it has expired.
+
+use core::{fmt, time::Duration};
+use s2n_codec::DecoderBuffer;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Identifies the format of the serialized state
+const VERSION: u32 = 0x7332_6e01;
+
+//= https://www.rfc-editor.org/rfc/rfc8446#section-4.6.1
+//# Servers MUST NOT use any value greater than
+//# 604800 seconds (7 days).
+pub(crate) const MAX_TICKET_LIFETIME: Duration = Duration::from_secs(604_800);
+
+/// The maximum number of servers for which a [`Cache`] holds a session ticket
+const MAX_CACHED_SESSIONS: usize = 256;
+
+/// Returned when serialized resumption state can't be used
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The resumption state is malformed
+    Corrupt,
+    /// The session ticket in the resumption state has expired
+    Expired,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Corrupt => write!(f, "the resumption state is corrupt"),
+            Self::Expired => write!(f, "the resumption state has expired"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A session ticket which was loaded from serialized resumption state
+#[derive(Clone, Debug)]
+pub(crate) struct State {
+    ticket: Vec<u8>,
+    expires_at: SystemTime,
+}
+
+impl State {
+    /// Creates the state for a session ticket which expires at `expires_at`
+    pub fn new(ticket: Vec<u8>, expires_at: SystemTime) -> Self {
+        Self { ticket, expires_at }
+    }
+
+    /// Encodes a session ticket which expires at `expires_at`
+    pub fn encode(ticket: &[u8], expires_at: SystemTime) -> Vec<u8> {
+        let expires_at = expires_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let ticket_len =
+            u16::try_from(ticket.len()).expect("session tickets should not exceed u16::MAX");
+
+        let mut state = Vec::with_capacity(4 + 8 + 2 + ticket.len());
+        state.extend_from_slice(&VERSION.to_be_bytes());
+        state.extend_from_slice(&expires_at.to_be_bytes());
+        state.extend_from_slice(&ticket_len.to_be_bytes());
+        state.extend_from_slice(ticket);
+        state
+    }
+
+    /// Decodes serialized resumption state, rejecting it if it has expired as of `now`
+    pub fn decode(state: &[u8], now: SystemTime) -> Result<Self, Error> {
+        let buffer = DecoderBuffer::new(state);
+
+        let (version, buffer) = buffer.decode::<u32>().map_err(|_| Error::Corrupt)?;
+        if version != VERSION {
+            return Err(Error::Corrupt);
+        }
+
+        let (expires_at, buffer) = buffer.decode::<u64>().map_err(|_| Error::Corrupt)?;
+        let (ticket, buffer) = buffer
+            .decode_slice_with_len_prefix::<u16>()
+            .map_err(|_| Error::Corrupt)?;
+        buffer.ensure_empty().map_err(|_| Error::Corrupt)?;
+
+        let ticket = ticket.into_less_safe_slice();
+        if ticket.is_empty() {
+            return Err(Error::Corrupt);
+        }
+
+        let expires_at = UNIX_EPOCH
+            .checked_add(Duration::from_secs(expires_at))
+            .ok_or(Error::Corrupt)?;
+
+        let state = Self {
+            ticket: ticket.to_vec(),
+            expires_at,
+        };
+
+        if state.is_expired(now) {
+            return Err(Error::Expired);
+        }
+
+        Ok(state)
+    }
+
+    /// Returns the session ticket
+    pub fn ticket(&self) -> &[u8] {
+        &self.ticket
+    }
+
+    /// Returns `true` if the session ticket can no longer be used as of `now`
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Returns the time at which the session ticket can no longer be used
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+}
+
+/// Holds the most recent session ticket issued by each server
+///
+/// The cache is shared by the sessions created by a client so that reconnecting to a server
+/// resumes the previous session.
+#[derive(Debug, Default)]
+pub(crate) struct Cache {
+    sessions: Mutex<HashMap<String, State>>,
+}
+
+impl Cache {
+    /// Returns the session ticket for `server_name`, if one was cached and hasn't expired
+    pub fn get(&self, server_name: &str, now: SystemTime) -> Option<State> {
+        let mut sessions = self.sessions.lock().ok()?;
+
+        if sessions.get(server_name)?.is_expired(now) {
+            sessions.remove(server_name);
+            return None;
+        }
+
+        sessions.get(server_name).cloned()
+    }
+
+    /// Stores the session ticket issued by `server_name`, replacing any previous ticket
+    pub fn insert(&self, server_name: &str, state: State, now: SystemTime) {
+        let mut sessions = if let Ok(sessions) = self.sessions.lock() {
+            sessions
+        } else {
+            return;
+        };
+
+        if sessions.len() >= MAX_CACHED_SESSIONS && !sessions.contains_key(server_name) {
+            sessions.retain(|_, state| !state.is_expired(now));
+
+            // make room by evicting the ticket which would expire first
+            if sessions.len() >= MAX_CACHED_SESSIONS {
+                let oldest = sessions
+                    .iter()
+                    .min_by_key(|(_, state)| state.expires_at)
+                    .map(|(name, _)| name.clone());
+                if let Some(oldest) = oldest {
+                    sessions.remove(&oldest);
+                }
+            }
+        }
+
+        sessions.insert(server_name.to_owned(), state);
+    }
+}
@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    certificate::{Format, IntoCertificate, IntoPrivateKey},
+    certificate::{IntoCertificate, IntoPrivateKey},
+    client::Client,
     keylog::KeyLogHandle,
     params::Params,
+    resumption,
     session::Session,
     ConfigLoader,
 };
+use core::time::Duration;
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
 #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
@@ -18,9 +21,13 @@ use s2n_tls::{
     callbacks::VerifyHostNameCallback,
     config::{self, Config},
     enums::ClientAuthType,
-    error::Error,
+    error::{Error, Fallible},
+    ffi::{
+        s2n_config_set_session_state_lifetime, s2n_config_set_ticket_decrypt_key_lifetime,
+        s2n_config_set_ticket_encrypt_decrypt_key_lifetime,
+    },
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 pub struct Server<L: ConfigLoader = Config> {
     loader: L,
@@ -52,6 +59,18 @@ impl<L: ConfigLoader> Server<L> {
     }
 }
 
+impl<L: ConfigLoader + Clone> Server<L> {
+    /// Creates a [`Client`] which shares the configuration already loaded on the [`Server`]
+    ///
+    /// This is useful for peer-to-peer applications which both accept and initiate connections
+    /// with the same trust material. The client uses the trust store, certificate,
+    /// application protocols and host name verification callback of the server. The certificate
+    /// is offered to peers which require client authentication (mutual TLS).
+    pub fn to_client(&self) -> Client<L> {
+        Client::from_shared_loader(self.loader.clone(), self.keylog.clone())
+    }
+}
+
 impl Default for Server {
     fn default() -> Self {
         Self::builder()
@@ -75,9 +94,19 @@ impl<L: ConfigLoader> From<Server<L>> for Config {
 
 pub struct Builder {
     config: config::Builder,
+    /// How the certificates were loaded, which can't be mixed
+    certificates: Option<Certificates>,
     keylog: Option<KeyLogHandle>,
 }
 
+/// Whether the certificate was loaded with [`Builder::with_certificate`] or the certificates
+/// with [`Builder::with_certificates`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Certificates {
+    Single,
+    Multiple,
+}
+
 impl Default for Builder {
     fn default() -> Self {
         let mut config = config::Builder::default();
@@ -88,6 +117,7 @@ impl Default for Builder {
 
         Self {
             config,
+            certificates: None,
             keylog: None,
         }
     }
@@ -120,21 +150,186 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the security policy, which selects the cipher suites and key exchange groups that
+    /// are negotiated
+    ///
+    /// Any s2n-tls policy can be used, including those looked up by version name with
+    /// [`crate::security::Policy::from_version`]. QUIC requires TLS 1.3, so an error is returned
+    /// for policies which don't negotiate it.
+    ///
+    /// Defaults to [`crate::security::DEFAULT_TLS13`], or [`crate::security::TESTING_PQ`] when
+    /// built with `--cfg s2n_quic_unstable --cfg s2n_quic_enable_pq_tls`.
+    pub fn with_security_policy(mut self, policy: &crate::security::Policy) -> Result<Self, Error> {
+        crate::ensure_tls13(policy)?;
+        self.config.set_security_policy(policy)?;
+        Ok(self)
+    }
+
+    /// Restricts the cipher suites which can be negotiated, in order of preference
+    ///
+    /// s2n-tls only negotiates the cipher suites of the security policy, so this always returns
+    /// an error. Use [`Self::with_security_policy`] with a policy that only includes the
+    /// preferred cipher suites instead.
+    pub fn with_cipher_suites<I: IntoIterator<Item = tls::CipherSuite>>(
+        self,
+        cipher_suites: I,
+    ) -> Result<Self, Error> {
+        let _ = cipher_suites;
+        Err(crate::unsupported_preference("cipher suites"))
+    }
+
+    /// Restricts the key exchange groups which can be negotiated, in order of preference
+    ///
+    /// s2n-tls only negotiates the groups of the security policy, so this always returns an
+    /// error. Use [`Self::with_security_policy`] with a policy that only includes the preferred
+    /// groups instead.
+    pub fn with_key_exchange_groups<'a, I: IntoIterator<Item = &'a str>>(
+        self,
+        kx_groups: I,
+    ) -> Result<Self, Error> {
+        let _ = kx_groups;
+        Err(crate::unsupported_preference("key exchange groups"))
+    }
+
+    /// Enables issuing session tickets encrypted with `key`
+    ///
+    /// Clients can use the tickets to resume the session in a future connection without
+    /// performing a full handshake. The `name` identifies the key when it is rotated.
+    ///
+    /// s2n-tls selects the key for each ticket by the system time, so the key is introduced at
+    /// the current system time rather than by the endpoint's wall clock.
+    pub fn with_session_ticket_key(self, name: &[u8], key: &[u8]) -> Result<Self, Error> {
+        self.with_session_ticket_key_at(name, key, SystemTime::now())
+    }
+
+    /// Adds a key which starts encrypting session tickets at `intro_time`
+    ///
+    /// Keys can be rotated by adding the next key with a later `intro_time`. Once a key stops
+    /// encrypting new tickets, it keeps decrypting the tickets it issued until its decryption
+    /// lifetime expires, so clients can still resume across the rotation.
+    pub fn with_session_ticket_key_at(
+        mut self,
+        name: &[u8],
+        key: &[u8],
+        intro_time: SystemTime,
+    ) -> Result<Self, Error> {
+        self.config.enable_session_tickets(true)?;
+        self.config.add_session_ticket_key(name, key, intro_time)?;
+        Ok(self)
+    }
+
+    /// Sets how long each session ticket key is used
+    ///
+    /// A key encrypts new tickets for `encrypt_decrypt` after its intro time. It then only
+    /// decrypts existing tickets for a further `decrypt`.
+    ///
+    /// Defaults to 2 hours and 13 hours, respectively.
+    pub fn with_session_ticket_key_lifetime(
+        mut self,
+        encrypt_decrypt: Duration,
+        decrypt: Duration,
+    ) -> Result<Self, Error> {
+        unsafe {
+            // Safety: the config pointer is valid for the lifetime of the builder
+            s2n_config_set_ticket_encrypt_decrypt_key_lifetime(
+                self.config.as_mut_ptr(),
+                encrypt_decrypt.as_secs(),
+            )
+            .into_result()?;
+            s2n_config_set_ticket_decrypt_key_lifetime(self.config.as_mut_ptr(), decrypt.as_secs())
+                .into_result()?;
+        }
+        Ok(self)
+    }
+
+    /// Sets how long a session can be resumed after it was established
+    ///
+    /// Values larger than 7 days are reduced to 7 days, which is the longest ticket lifetime
+    /// allowed by TLS 1.3.
+    ///
+    /// Defaults to 15 hours.
+    pub fn with_session_lifetime(mut self, lifetime: Duration) -> Result<Self, Error> {
+        let lifetime = lifetime.min(resumption::MAX_TICKET_LIFETIME);
+        unsafe {
+            // Safety: the config pointer is valid for the lifetime of the builder
+            s2n_config_set_session_state_lifetime(self.config.as_mut_ptr(), lifetime.as_secs())
+                .into_result()?;
+        }
+        Ok(self)
+    }
+
     pub fn with_certificate<C: IntoCertificate, PK: IntoPrivateKey>(
         mut self,
         certificate: C,
         private_key: PK,
     ) -> Result<Self, Error> {
-        let private_key = private_key.into_private_key()?.0;
-        let certificate = certificate.into_certificate()?.0;
-        let certificate = certificate
-            .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        match private_key {
-            Format::Pem(bytes) => self.config.load_pem(certificate, bytes.as_ref())?,
-            Format::None => self.config.load_public_pem(certificate)?,
-            Format::Der(_) => panic!("der private keys not supported"),
+        self.set_certificates(Certificates::Single)?;
+        self.load_certificate(certificate, private_key)?;
+        Ok(self)
+    }
+
+    /// Loads several certificates which are selected by the server name the client requests
+    ///
+    /// Each chain is owned by the config, which maps the subject alternative names of the leaf
+    /// certificate to the chain. The certificate matching the requested server name is sent to
+    /// the client. Names match either exactly or against wildcard names, such as
+    /// `*.example.com`, which match a single label. The first certificate of each key type is the
+    /// default, which is sent if none of the names match or the client doesn't indicate a server
+    /// name.
+    ///
+    /// Returns an error if combined with [`Self::with_certificate`].
+    pub fn with_certificates<I, C, PK>(mut self, certificates: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (C, PK)>,
+        C: IntoCertificate,
+        PK: IntoPrivateKey,
+    {
+        self.set_certificates(Certificates::Multiple)?;
+        for (certificate, private_key) in certificates {
+            self.load_certificate(certificate, private_key)?;
+        }
+        Ok(self)
+    }
+
+    /// Loads a certificate chain into the config, which owns it from then on
+    ///
+    /// s2n-tls adds every chain to its certificate store and uses the first as the default.
+    fn load_certificate<C: IntoCertificate, PK: IntoPrivateKey>(
+        &mut self,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<(), Error> {
+        let private_key = private_key.into_private_key()?;
+        let certificate = certificate.into_certificate()?;
+        let certificate = certificate.to_pem();
+        match private_key.to_pem() {
+            Some(private_key) => self.config.load_pem(&certificate, &private_key)?,
+            // the private key operations are offloaded
+            None => self.config.load_public_pem(&certificate)?,
         };
+        Ok(())
+    }
+
+    fn set_certificates(&mut self, certificates: Certificates) -> Result<(), Error> {
+        match self.certificates {
+            Some(current) if current != certificates => Err(Error::io_error(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "with_certificate and with_certificates can't be combined",
+            ))),
+            _ => {
+                self.certificates = Some(certificates);
+                Ok(())
+            }
+        }
+    }
+
+    /// Staples an OCSP response to the certificate for clients which request it
+    ///
+    /// The `response` is the DER-encoded OCSP response for the server certificate, so it has to
+    /// be called after [`Self::with_certificate`]. Responses expire, so servers should be rebuilt
+    /// with a fresh response before the current one's `nextUpdate` time.
+    pub fn with_ocsp_response(mut self, response: &[u8]) -> Result<Self, Error> {
+        self.config.set_ocsp_data(response)?;
         Ok(self)
     }
 
@@ -143,11 +338,7 @@ impl Builder {
         certificate: C,
     ) -> Result<Self, Error> {
         let certificate = certificate.into_certificate()?;
-        let certificate = certificate
-            .0
-            .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        self.config.trust_pem(certificate)?;
+        self.config.trust_pem(&certificate.to_pem())?;
         Ok(self)
     }
 
@@ -1,23 +1,30 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::callback::{self, Callback};
-use bytes::BytesMut;
-use core::{marker::PhantomData, task::Poll};
+use crate::{
+    callback::{self, Callback},
+    resumption,
+    signature::SignatureScheme,
+    verifier::CertificateVerifier,
+};
+use bytes::{Bytes, BytesMut};
+use core::{fmt, marker::PhantomData, task::Poll};
 use s2n_quic_core::{
     application::ServerName,
     crypto::{tls, CryptoError, CryptoSuite},
-    endpoint, transport,
+    endpoint,
+    time::{SystemClock, WallClock},
+    transport,
 };
 use s2n_quic_crypto::Suite;
 use s2n_tls::{
     config::Config,
     connection::Connection,
     enums::{Blinding, Mode},
-    error::Error,
+    error::{Error, ErrorType},
 };
+use std::sync::Arc;
 
-#[derive(Debug)]
 pub struct Session {
     endpoint: endpoint::Type,
     pub(crate) connection: Connection,
@@ -27,6 +34,25 @@ pub struct Session {
     emitted_server_name: bool,
     // This is only set for the client to avoid an extra allocation
     server_name: Option<ServerName>,
+    /// Replaces the default validation of the peer's certificate chain, if set
+    pub(crate) verifier: Option<Arc<dyn CertificateVerifier>>,
+    /// Stores the session ticket issued by the server, if set
+    pub(crate) cache: Option<Arc<resumption::Cache>>,
+    /// The most recent session ticket issued by the server
+    session_ticket: Option<resumption::State>,
+    /// Sets the expiration of the session tickets issued by the server
+    pub(crate) clock: Arc<dyn WallClock>,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("endpoint", &self.endpoint)
+            .field("connection", &self.connection)
+            .field("handshake_complete", &self.handshake_complete)
+            .field("server_name", &self.server_name)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Session {
@@ -61,8 +87,149 @@ impl Session {
             send_buffer: BytesMut::new(),
             emitted_server_name: false,
             server_name,
+            verifier: None,
+            cache: None,
+            session_ticket: None,
+            clock: Arc::new(SystemClock),
         })
     }
+
+    /// Serializes the state needed to resume this session in a future connection
+    ///
+    /// The state can be persisted by the application and loaded into a new client with
+    /// [`client::Builder::with_resumption_state`](crate::client::Builder::with_resumption_state).
+    ///
+    /// Returns `None` if the session is not a client session or the server has not issued a
+    /// session ticket.
+    pub fn to_resumption_state(&self) -> Option<Vec<u8>> {
+        let state = self.session_ticket.as_ref()?;
+        Some(resumption::State::encode(
+            state.ticket(),
+            state.expires_at(),
+        ))
+    }
+
+    /// Records the most recent session ticket issued by the server and stores it in the
+    /// client's cache
+    fn on_session_ticket(&mut self) {
+        let ticket = match self.read_session_ticket() {
+            Some(ticket) => ticket,
+            None => return,
+        };
+
+        // the ticket is only replaced when the server issues a new one
+        if let Some(state) = self.session_ticket.as_ref() {
+            if state.ticket() == ticket {
+                return;
+            }
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4.6.1
+        //# Clients MUST NOT cache
+        //# tickets for longer than 7 days, regardless of the ticket_lifetime,
+        //# and MAY delete tickets earlier based on local policy.
+        let lifetime = self
+            .connection
+            .session_ticket_lifetime_hint()
+            .ok()
+            .flatten()
+            .map_or(resumption::MAX_TICKET_LIFETIME, |lifetime| {
+                lifetime.min(resumption::MAX_TICKET_LIFETIME)
+            });
+
+        let now = self.clock.now();
+        let state = resumption::State::new(ticket, now + lifetime);
+
+        if let (Some(cache), Some(server_name)) = (self.cache.as_ref(), self.server_name.as_ref()) {
+            cache.insert(server_name, state.clone(), now);
+        }
+
+        self.session_ticket = Some(state);
+    }
+
+    /// Reads the session ticket the server issued from the connection
+    fn read_session_ticket(&self) -> Option<Vec<u8>> {
+        let len = self.connection.session_ticket_length().ok()?;
+        if len == 0 {
+            return None;
+        }
+
+        let mut ticket = vec![0; len];
+        let len = self.connection.session_ticket(&mut ticket).ok()?;
+        ticket.truncate(len);
+        Some(ticket)
+    }
+
+    /// Returns the signature scheme the server used to sign the handshake
+    ///
+    /// Returns `None` until the handshake is complete.
+    pub fn selected_signature_scheme(&self) -> Option<SignatureScheme> {
+        if !self.handshake_complete {
+            return None;
+        }
+
+        let signature = self.connection.selected_signature_algorithm().ok()?;
+        let hash = self.connection.selected_hash_algorithm().ok()?;
+        SignatureScheme::from_algorithms(signature, hash)
+    }
+
+    /// Returns the signature scheme the peer used to sign the handshake
+    ///
+    /// For clients, this is the scheme used by the server. For servers, this is the scheme used
+    /// by the client to prove ownership of its certificate, which is only available if client
+    /// authentication was performed.
+    ///
+    /// Returns `None` until the handshake is complete.
+    pub fn peer_signature_scheme(&self) -> Option<SignatureScheme> {
+        if self.endpoint.is_client() {
+            return self.selected_signature_scheme();
+        }
+
+        if !self.handshake_complete {
+            return None;
+        }
+
+        let signature = self
+            .connection
+            .selected_client_signature_algorithm()
+            .ok()??;
+        let hash = self.connection.selected_client_hash_algorithm().ok()??;
+        SignatureScheme::from_algorithms(signature, hash)
+    }
+
+    /// Returns the DER-encoded certificates the peer authenticated with, starting with its leaf
+    ///
+    /// The chain is empty if the peer didn't present a certificate, e.g. when a session is
+    /// resumed.
+    fn peer_certificate_chain(connection: &Connection) -> Vec<Bytes> {
+        let chain = if let Ok(chain) = connection.peer_cert_chain() {
+            chain
+        } else {
+            return Vec::new();
+        };
+
+        chain
+            .iter()
+            .filter_map(|certificate| {
+                let certificate = certificate.ok()?;
+                let der = certificate.der().ok()?;
+                Some(Bytes::copy_from_slice(der))
+            })
+            .collect()
+    }
+
+    /// Processes any messages sent by the server after the handshake, e.g. `NewSessionTicket`
+    fn poll_post_handshake(&mut self) -> Poll<Result<(), Error>> {
+        // the server can send several messages at once so keep going until we run out of data
+        loop {
+            match self.connection.quic_process_post_handshake_message() {
+                Ok(_) => continue,
+                // there's nothing left to read from the peer
+                Err(error) if error.kind() == ErrorType::Blocked => return Poll::Ready(Ok(())),
+                Err(error) => return Poll::Ready(Err(error)),
+            }
+        }
+    }
 }
 
 impl CryptoSuite for Session {
@@ -91,6 +258,7 @@ impl tls::Session for Session {
             send_buffer: &mut self.send_buffer,
             emitted_server_name: &mut self.emitted_server_name,
             server_name: &self.server_name,
+            verify_peer: self.verifier.is_some(),
         };
 
         unsafe {
@@ -99,17 +267,39 @@ impl tls::Session for Session {
             callback.set(&mut self.connection);
         }
 
-        let result = self.connection.poll_negotiate().map_ok(|_| ());
+        let result = if self.handshake_complete && self.endpoint.is_client() {
+            self.poll_post_handshake()
+        } else {
+            self.connection.poll_negotiate().map_ok(|_| ())
+        };
+
+        // the client's 1-RTT keys are held back until the server's certificate chain is verified
+        let verified = callback.on_peer_verified(|| {
+            let chain = Self::peer_certificate_chain(&self.connection);
+            match self.verifier.as_deref() {
+                Some(verifier) => verifier.verify(&chain, self.server_name.as_ref()),
+                None => Ok(()),
+            }
+        });
 
-        callback.unset(&mut self.connection)?;
+        // the callbacks are unset even if the chain was rejected
+        verified.and(callback.unset(&mut self.connection))?;
 
         match result {
             Poll::Ready(Ok(())) => {
                 // s2n-tls has indicated that the handshake is complete
                 if !self.handshake_complete {
+                    let chain = Self::peer_certificate_chain(&self.connection);
+                    if !chain.is_empty() {
+                        context.on_peer_certificate_chain(chain)?;
+                    }
+
                     self.state.on_handshake_complete();
                     context.on_handshake_complete()?;
                     self.handshake_complete = true;
+                } else if self.endpoint.is_client() {
+                    // the server issues session tickets after the handshake completes
+                    self.on_session_ticket();
                 }
                 Poll::Ready(Ok(()))
             }
@@ -121,4 +311,23 @@ impl tls::Session for Session {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError> {
+        self.connection
+            .tls_exporter(label, context, output)
+            .map_err(|_| {
+                CryptoError::INTERNAL_ERROR
+                    .with_reason("unable to export keying material")
+                    .into()
+            })
+    }
+
+    fn resumption_state(&self) -> Option<Vec<u8>> {
+        self.to_resumption_state()
+    }
 }
@@ -0,0 +1,72 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signature schemes negotiated in the TLS handshake
+
+use core::fmt;
+use s2n_tls::enums::{HashAlgorithm, SignatureAlgorithm};
+
+/// A signature scheme which can be used to sign a TLS 1.3 handshake
+///
+/// See <https://www.rfc-editor.org/rfc/rfc8446#section-4.2.3>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SignatureScheme {
+    EcdsaSecp256r1Sha256,
+    EcdsaSecp384r1Sha384,
+    EcdsaSecp521r1Sha512,
+    RsaPssRsaeSha256,
+    RsaPssRsaeSha384,
+    RsaPssRsaeSha512,
+    RsaPssPssSha256,
+    RsaPssPssSha384,
+    RsaPssPssSha512,
+}
+
+impl SignatureScheme {
+    /// Returns the name of the signature scheme, as registered with IANA
+    ///
+    /// See <https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-signaturescheme>
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EcdsaSecp256r1Sha256 => "ecdsa_secp256r1_sha256",
+            Self::EcdsaSecp384r1Sha384 => "ecdsa_secp384r1_sha384",
+            Self::EcdsaSecp521r1Sha512 => "ecdsa_secp521r1_sha512",
+            Self::RsaPssRsaeSha256 => "rsa_pss_rsae_sha256",
+            Self::RsaPssRsaeSha384 => "rsa_pss_rsae_sha384",
+            Self::RsaPssRsaeSha512 => "rsa_pss_rsae_sha512",
+            Self::RsaPssPssSha256 => "rsa_pss_pss_sha256",
+            Self::RsaPssPssSha384 => "rsa_pss_pss_sha384",
+            Self::RsaPssPssSha512 => "rsa_pss_pss_sha512",
+        }
+    }
+
+    /// Returns the signature scheme for the algorithms reported by s2n-tls
+    ///
+    /// Returns `None` if the combination can't be used in a TLS 1.3 handshake.
+    pub(crate) fn from_algorithms(
+        signature: SignatureAlgorithm,
+        hash: HashAlgorithm,
+    ) -> Option<Self> {
+        // TLS 1.3 binds the ECDSA curve to the hash algorithm, so the curve can be derived from
+        // the hash
+        Some(match (signature, hash) {
+            (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA256) => Self::EcdsaSecp256r1Sha256,
+            (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA384) => Self::EcdsaSecp384r1Sha384,
+            (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA512) => Self::EcdsaSecp521r1Sha512,
+            (SignatureAlgorithm::RSA_PSS_RSAE, HashAlgorithm::SHA256) => Self::RsaPssRsaeSha256,
+            (SignatureAlgorithm::RSA_PSS_RSAE, HashAlgorithm::SHA384) => Self::RsaPssRsaeSha384,
+            (SignatureAlgorithm::RSA_PSS_RSAE, HashAlgorithm::SHA512) => Self::RsaPssRsaeSha512,
+            (SignatureAlgorithm::RSA_PSS_PSS, HashAlgorithm::SHA256) => Self::RsaPssPssSha256,
+            (SignatureAlgorithm::RSA_PSS_PSS, HashAlgorithm::SHA384) => Self::RsaPssPssSha384,
+            (SignatureAlgorithm::RSA_PSS_PSS, HashAlgorithm::SHA512) => Self::RsaPssPssSha512,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for SignatureScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
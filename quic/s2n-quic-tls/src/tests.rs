@@ -1,19 +1,31 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, client, server};
+use crate::{certificate, client, resumption, security, server, signature::SignatureScheme};
+use bytes::Bytes;
 use core::{
-    sync::atomic::{AtomicBool, AtomicU8, Ordering},
+    sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
     task::Poll,
+    time::Duration,
 };
-use openssl::{ec::EcKey, ecdsa::EcdsaSig};
+use openssl::{ec::EcKey, ecdsa::EcdsaSig, x509::X509};
 use pin_project::pin_project;
 use s2n_quic_core::{
-    crypto::tls::{
-        self,
-        testing::certificates::{CERT_PEM, KEY_PEM, UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM},
-        Endpoint,
+    application::ServerName,
+    crypto::{
+        tls::{
+            self,
+            testing::certificates::{
+                CERT_PEM, KEY_DER, KEY_PEM, OCSP_CA_CERT, OCSP_RESPONSE, OCSP_SERVER_CERT,
+                OCSP_SERVER_KEY, RSA_CERT_PEM, RSA_KEY_PEM, SNI_EXACT_CERT, SNI_EXACT_KEY,
+                SNI_WILDCARD_CERT, SNI_WILDCARD_KEY, UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM,
+                UNTRUSTED_OCSP_RESPONSE,
+            },
+            Endpoint, Session as _,
+        },
+        CryptoError,
     },
+    time::WallClock,
     transport,
 };
 #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
@@ -25,7 +37,10 @@ use s2n_tls::{
     connection::Connection,
     error::Error,
 };
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub struct MyCallbackHandler {
     done: Arc<AtomicBool>,
@@ -152,6 +167,34 @@ impl VerifyHostNameCallback for RejectAllClientCertificatesHandler {
     }
 }
 
+/// Counts the number of times the server certificate is verified
+#[derive(Clone, Default)]
+pub struct CountingHostNameVerifier {
+    count: Arc<AtomicUsize>,
+}
+
+impl VerifyHostNameCallback for CountingHostNameVerifier {
+    fn verify_host_name(&self, host_name: &str) -> bool {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        host_name == "localhost"
+    }
+}
+
+impl CountingHostNameVerifier {
+    fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+/// Accepts any server name, which allows the default certificate to be selected
+#[derive(Default)]
+pub struct AcceptAllHostNamesVerifier {}
+impl VerifyHostNameCallback for AcceptAllHostNamesVerifier {
+    fn verify_host_name(&self, _host_name: &str) -> bool {
+        true
+    }
+}
+
 fn s2n_client() -> client::Client {
     client::Builder::default()
         .with_certificate(CERT_PEM)
@@ -160,6 +203,15 @@ fn s2n_client() -> client::Client {
         .unwrap()
 }
 
+/// Returns the DER encoding of `CERT_PEM`
+fn cert_der() -> Vec<u8> {
+    pem_to_der(CERT_PEM)
+}
+
+fn pem_to_der(pem: &str) -> Vec<u8> {
+    X509::from_pem(pem.as_bytes()).unwrap().to_der().unwrap()
+}
+
 fn s2n_client_with_client_auth() -> Result<client::Client, Error> {
     client::Builder::default()
         .with_empty_trust_store()?
@@ -288,6 +340,92 @@ fn s2n_client_s2n_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_der_test() {
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(cert_der())
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(cert_der(), KEY_DER)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+fn cipher_suite_preference_unsupported_test() {
+    let cipher_suites = [tls::CipherSuite::TLS_AES_128_GCM_SHA256];
+
+    assert!(client::Builder::default()
+        .with_cipher_suites(cipher_suites)
+        .is_err());
+    assert!(server::Builder::default()
+        .with_cipher_suites(cipher_suites)
+        .is_err());
+    assert!(client::Builder::default()
+        .with_key_exchange_groups(["x25519"])
+        .is_err());
+    assert!(server::Builder::default()
+        .with_key_exchange_groups(["x25519"])
+        .is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_security_policy_test() {
+    let policy = &security::Policy::from_version("test_all_tls13").unwrap();
+
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_security_policy(policy)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_security_policy(policy)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+fn security_policy_tls13_test() {
+    // policies which also allow older versions still negotiate TLS 1.3
+    for version in ["default_tls13", "20190801"] {
+        let policy = &security::Policy::from_version(version).unwrap();
+        assert!(client::Builder::default()
+            .with_security_policy(policy)
+            .is_ok());
+        assert!(server::Builder::default()
+            .with_security_policy(policy)
+            .is_ok());
+    }
+}
+
+#[test]
+fn security_policy_below_tls13_test() {
+    // these policies negotiate TLS 1.2 and older
+    for version in ["default", "20170210", "test_all_tls12"] {
+        let policy = &security::Policy::from_version(version).unwrap();
+        assert!(client::Builder::default()
+            .with_security_policy(policy)
+            .is_err());
+        assert!(server::Builder::default()
+            .with_security_policy(policy)
+            .is_err());
+    }
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn rustls_client_s2n_server_test() {
@@ -306,6 +444,56 @@ fn s2n_client_rustls_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rustls_export_keying_material_test() {
+    let mut client_endpoint = rustls_client();
+    let mut server_endpoint = rustls_server();
+    export_keying_material_test(&mut server_endpoint, &mut client_endpoint);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_export_keying_material_test() {
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = s2n_server();
+    export_keying_material_test(&mut server_endpoint, &mut client_endpoint);
+}
+
+fn export_keying_material_test<S: Endpoint, C: Endpoint>(
+    server_endpoint: &mut S,
+    client_endpoint: &mut C,
+) {
+    let mut pair = tls::testing::Pair::new(server_endpoint, client_endpoint, "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+    pair.finish();
+
+    fn export<S: tls::Session>(session: &S, label: &[u8], context: &[u8]) -> [u8; 32] {
+        let mut output = [0u8; 32];
+        session
+            .export_keying_material(&mut output, label, context)
+            .unwrap();
+        output
+    }
+
+    let client = export(&pair.client.session, b"EXPORTER-test", b"context");
+    let server = export(&pair.server.session, b"EXPORTER-test", b"context");
+
+    // both peers derive the same keying material
+    assert_eq!(client, server);
+    assert_ne!(client, [0u8; 32]);
+
+    // the keying material is bound to the label and context
+    assert_ne!(
+        client,
+        export(&pair.client.session, b"EXPORTER-other", b"context")
+    );
+    assert_ne!(client, export(&pair.client.session, b"EXPORTER-test", b""));
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_s2n_server_client_auth_test() {
@@ -315,6 +503,41 @@ fn s2n_client_s2n_server_client_auth_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+/// Builds a server which authenticates peers with the same trust material it presents
+fn s2n_peer() -> Result<server::Server, Error> {
+    server::Builder::default()
+        .with_empty_trust_store()?
+        .with_client_authentication()?
+        .with_certificate(CERT_PEM, KEY_PEM)?
+        .with_trusted_certificate(CERT_PEM)?
+        .build()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_server_to_client_test() {
+    let mut server_a = s2n_peer().unwrap();
+    let mut client_a = server_a.to_client();
+    let mut server_b = s2n_peer().unwrap();
+    let mut client_b = server_b.to_client();
+
+    // each peer can connect to the other one with the configuration loaded by its server
+    run(&mut server_a, &mut client_b, None);
+    run(&mut server_b, &mut client_a, None);
+
+    // peers with different trust material are rejected
+    let mut untrusted = server::Builder::default()
+        .with_empty_trust_store()
+        .unwrap()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_trusted_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    run_result(&mut untrusted, &mut client_a, None).unwrap_err();
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_no_client_auth_s2n_server_requires_client_auth_test() {
@@ -398,6 +621,494 @@ fn s2n_client_with_custom_hostname_auth_accepts_server_name() {
     run_result(&mut server_endpoint, &mut client_endpoint, None).unwrap();
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_ocsp_stapling_s2n_server_without_ocsp_response_test() {
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_ocsp_stapling(true)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = s2n_server();
+
+    // servers which don't staple a response are still accepted
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_with_ocsp_response_test() {
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_response(&[1, 2, 3])
+        .unwrap()
+        .build()
+        .unwrap();
+
+    // the response is only sent to clients which request it
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+fn s2n_ocsp_stapling_endpoints(response: &[u8]) -> (server::Server, client::Client) {
+    let server = server::Builder::default()
+        .with_certificate(OCSP_SERVER_CERT, OCSP_SERVER_KEY)
+        .unwrap()
+        .with_ocsp_response(response)
+        .unwrap()
+        .build()
+        .unwrap();
+    let client = client::Builder::default()
+        .with_certificate(OCSP_CA_CERT)
+        .unwrap()
+        .with_ocsp_stapling(true)
+        .unwrap()
+        .build()
+        .unwrap();
+    (server, client)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_ocsp_stapling_s2n_server_with_valid_ocsp_response_test() {
+    let (mut server_endpoint, mut client_endpoint) = s2n_ocsp_stapling_endpoints(OCSP_RESPONSE);
+
+    // the stapled response is signed by the issuer and reports the certificate as good
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_ocsp_stapling_s2n_server_with_invalid_ocsp_response_test() {
+    let (mut server_endpoint, mut client_endpoint) =
+        s2n_ocsp_stapling_endpoints(UNTRUSTED_OCSP_RESPONSE);
+
+    // the stapled response isn't signed by the issuer or a responder it authorized
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+    assert!(test_result.is_err());
+}
+
+fn s2n_client_with_certificate_verifier(pinned: Vec<u8>) -> Result<client::Client, Error> {
+    client::Builder::default()
+        .with_empty_trust_store()?
+        .with_certificate_verifier(move |chain: &[Bytes], server_name: Option<&ServerName>| {
+            assert_eq!(server_name.map(|name| &name[..]), Some("localhost"));
+
+            if chain.first().map_or(false, |leaf| leaf[..] == pinned[..]) {
+                Ok(())
+            } else {
+                Err(CryptoError::BAD_CERTIFICATE)
+            }
+        })?
+        .build()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_certificate_verifier_accepts_pinned_certificate() {
+    let mut client_endpoint = s2n_client_with_certificate_verifier(cert_der()).unwrap();
+    let mut server_endpoint = s2n_server();
+
+    // the server certificate isn't in the trust store but the verifier accepts it
+    run_result(&mut server_endpoint, &mut client_endpoint, None).unwrap();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_certificate_verifier_rejects_certificate() {
+    let mut client_endpoint = s2n_client_with_certificate_verifier(vec![]).unwrap();
+    let mut server_endpoint = s2n_server();
+
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+    let mut test_result = Ok(());
+    while test_result.is_ok() && pair.is_handshaking() {
+        test_result = pair.poll(None);
+    }
+
+    // The handshake should fail because the verifier doesn't recognize the server certificate
+    assert!(test_result.is_err());
+    let e = test_result.unwrap_err();
+    assert_eq!(e.description().unwrap(), "BAD_CERTIFICATE");
+
+    // the chain is rejected during the handshake, before the client derives its 1-RTT keys
+    assert!(pair.client.context.application.crypto.is_none());
+    assert!(!pair.client.context.handshake_complete);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_certificate_verifier_resumption_test() {
+    let mut server_endpoint = s2n_server_with_session_tickets();
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let client = |state: Option<&[u8]>| {
+        let pinned = cert_der();
+        let count = count.clone();
+        let mut builder = client::Builder::default()
+            .with_empty_trust_store()
+            .unwrap()
+            .with_certificate_verifier(move |chain: &[Bytes], _: Option<&ServerName>| {
+                count.fetch_add(1, Ordering::SeqCst);
+                if chain.first().map_or(false, |leaf| leaf[..] == pinned[..]) {
+                    Ok(())
+                } else {
+                    Err(CryptoError::BAD_CERTIFICATE)
+                }
+            })
+            .unwrap()
+            .with_session_tickets(true)
+            .unwrap();
+
+        if let Some(state) = state {
+            builder = builder.with_resumption_state(state).unwrap();
+        }
+
+        builder.build().unwrap()
+    };
+
+    let mut client_endpoint = client(None);
+    let state = handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint)
+        .expect("the client should receive a session ticket");
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    // the server doesn't present its certificate chain on a resumed handshake, which was already
+    // verified when the session was established
+    let mut client_endpoint = client(Some(&state));
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    assert_eq!(
+        count.load(Ordering::SeqCst),
+        1,
+        "the session should be resumed"
+    );
+    assert!(pair.client.context.application.crypto.is_some());
+}
+
+fn s2n_client_with_resumption(
+    verifier: CountingHostNameVerifier,
+    state: Option<&[u8]>,
+) -> client::Client {
+    let mut builder = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_verify_host_name_callback(verifier)
+        .unwrap()
+        .with_session_tickets(true)
+        .unwrap();
+
+    if let Some(state) = state {
+        builder = builder.with_resumption_state(state).unwrap();
+    }
+
+    builder.build().unwrap()
+}
+
+fn s2n_server_with_session_tickets() -> server::Server {
+    server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_session_ticket_key(b"key", &[42; 32])
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_resumption_test() {
+    let mut server_endpoint = s2n_server_with_session_tickets();
+
+    // perform a full handshake and export the state from the issued ticket
+    let verifier = CountingHostNameVerifier::default();
+    let mut client_endpoint = s2n_client_with_resumption(verifier.clone(), None);
+    let state = {
+        let mut pair = tls::testing::Pair::new(
+            &mut server_endpoint,
+            &mut client_endpoint,
+            "localhost".into(),
+        );
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        // the server sends the ticket after the handshake completes
+        pair.server.context.transfer(&mut pair.client.context);
+        if let Poll::Ready(res) = pair.client.session.poll(&mut pair.client.context) {
+            res.unwrap();
+        }
+
+        pair.client
+            .session
+            .to_resumption_state()
+            .expect("the client should receive a session ticket")
+    };
+    assert_eq!(verifier.count(), 1);
+
+    // the state expires with the lifetime of the ticket, which is capped by the lifetime of the
+    // session state on the server (15 hours by default)
+    let now = SystemTime::now();
+    let expires_at = resumption::State::decode(&state, now).unwrap().expires_at();
+    assert!(expires_at <= now + Duration::from_secs(15 * 60 * 60 + 60));
+
+    // load the state into a new client and make sure the server certificate isn't verified again
+    let verifier = CountingHostNameVerifier::default();
+    let mut client_endpoint = s2n_client_with_resumption(verifier.clone(), Some(&state));
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    assert_eq!(verifier.count(), 0, "the session should be resumed");
+}
+
+/// Performs a handshake and delivers the session tickets issued by the server to the client
+///
+/// Returns the client's resumption state, if a ticket was issued.
+fn handshake_with_session_tickets(
+    server: &mut server::Server,
+    client: &mut client::Client,
+) -> Option<Vec<u8>> {
+    let mut pair = tls::testing::Pair::new(server, client, "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    // the server sends the ticket after the handshake completes
+    pair.server.context.transfer(&mut pair.client.context);
+    if let Poll::Ready(res) = pair.client.session.poll(&mut pair.client.context) {
+        res.unwrap();
+    }
+
+    pair.client.session.to_resumption_state()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_resumption_state_lifetime_test() {
+    let now = SystemTime::now();
+    let lifetime = Duration::from_secs(60 * 60);
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_session_ticket_key(b"key", &[42; 32])
+        .unwrap()
+        .with_session_lifetime(lifetime)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let verifier = CountingHostNameVerifier::default();
+    let mut client_endpoint = s2n_client_with_resumption(verifier, None);
+    let state = handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint)
+        .expect("the client should receive a session ticket");
+
+    // the state expires with the lifetime of the ticket rather than the 7 day maximum
+    let state = resumption::State::decode(&state, now).unwrap();
+    assert!(state.expires_at() > now);
+    assert!(state.expires_at() <= now + lifetime + Duration::from_secs(60));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_session_cache_test() {
+    let mut server_endpoint = s2n_server_with_session_tickets();
+
+    let verifier = CountingHostNameVerifier::default();
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_verify_host_name_callback(verifier.clone())
+        .unwrap()
+        .with_session_cache(true)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint);
+    assert_eq!(verifier.count(), 1);
+
+    // reconnecting to the same server resumes the cached session
+    handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint);
+    assert_eq!(verifier.count(), 1, "the session should be resumed");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_wall_clock_test() {
+    struct FixedClock(SystemTime);
+
+    impl WallClock for FixedClock {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+    }
+
+    let mut server_endpoint = s2n_server_with_session_tickets();
+
+    let verifier = CountingHostNameVerifier::default();
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_verify_host_name_callback(verifier.clone())
+        .unwrap()
+        .with_session_cache(true)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint);
+    assert_eq!(verifier.count(), 1);
+
+    // the cached ticket has expired according to the endpoint's wall clock
+    let clock: Arc<dyn WallClock> = Arc::new(FixedClock(
+        SystemTime::now() + resumption::MAX_TICKET_LIFETIME,
+    ));
+    client_endpoint.set_wall_clock(&clock);
+    handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint);
+    assert_eq!(verifier.count(), 2, "the session shouldn't be resumed");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_server_session_ticket_key_rotation_test() {
+    let now = SystemTime::now();
+    let mut server_endpoint = s2n_server_with_session_tickets();
+
+    let verifier = CountingHostNameVerifier::default();
+    let mut client_endpoint = s2n_client_with_resumption(verifier, None);
+    let state = handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint)
+        .expect("the client should receive a session ticket");
+
+    // the original key no longer encrypts tickets but can still decrypt the one it issued
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_session_ticket_key_lifetime(Duration::from_secs(3600), Duration::from_secs(3600))
+        .unwrap()
+        .with_session_ticket_key_at(b"key", &[42; 32], now - Duration::from_secs(5400))
+        .unwrap()
+        .with_session_ticket_key_at(b"next key", &[43; 32], now - Duration::from_secs(1800))
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let verifier = CountingHostNameVerifier::default();
+    let mut client_endpoint = s2n_client_with_resumption(verifier.clone(), Some(&state));
+    handshake_with_session_tickets(&mut server_endpoint, &mut client_endpoint);
+
+    assert_eq!(verifier.count(), 0, "the session should be resumed");
+}
+
+#[test]
+fn resumption_cache_test() {
+    let now = SystemTime::now();
+    let cache = resumption::Cache::default();
+
+    assert!(cache.get("localhost", now).is_none());
+
+    let expires_at = now + Duration::from_secs(60);
+    cache.insert(
+        "localhost",
+        resumption::State::new(vec![1], expires_at),
+        now,
+    );
+    assert_eq!(cache.get("localhost", now).unwrap().ticket(), &[1]);
+    assert!(cache.get("example.com", now).is_none());
+
+    // newer tickets replace the previous one
+    cache.insert(
+        "localhost",
+        resumption::State::new(vec![2], expires_at),
+        now,
+    );
+    assert_eq!(cache.get("localhost", now).unwrap().ticket(), &[2]);
+
+    // expired tickets are discarded
+    assert!(cache.get("localhost", expires_at).is_none());
+    assert!(cache.get("localhost", now).is_none());
+}
+
+#[test]
+fn resumption_state_round_trip_test() {
+    let now = SystemTime::now();
+    let state = resumption::State::encode(&[1, 2, 3], now + Duration::from_secs(60));
+
+    let decoded = resumption::State::decode(&state, now).unwrap();
+    assert_eq!(decoded.ticket(), &[1, 2, 3]);
+    assert!(!decoded.is_expired(now));
+    assert!(decoded.is_expired(now + Duration::from_secs(61)));
+}
+
+#[test]
+fn resumption_state_expired_test() {
+    // use a fixed clock so the result doesn't depend on the system time
+    let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+    let state = resumption::State::encode(&[1, 2, 3], now - Duration::from_secs(60));
+
+    assert_eq!(
+        resumption::State::decode(&state, now).unwrap_err(),
+        resumption::Error::Expired
+    );
+    assert_eq!(
+        client::Builder::default()
+            .with_resumption_state_at(&state, now)
+            .map(|_| ())
+            .unwrap_err(),
+        resumption::Error::Expired
+    );
+}
+
+#[test]
+fn resumption_state_corrupt_test() {
+    use resumption::{Error, State};
+
+    let now = SystemTime::now();
+    let state = State::encode(&[1, 2, 3], now + Duration::from_secs(60));
+
+    // truncated states
+    for len in 0..state.len() {
+        assert_eq!(
+            State::decode(&state[..len], now).unwrap_err(),
+            Error::Corrupt
+        );
+    }
+
+    // trailing data
+    let mut trailing = state.clone();
+    trailing.push(4);
+    assert_eq!(State::decode(&trailing, now).unwrap_err(), Error::Corrupt);
+
+    // unknown version
+    let mut version = state;
+    version[0] ^= 0xff;
+    assert_eq!(State::decode(&version, now).unwrap_err(), Error::Corrupt);
+
+    // empty ticket
+    let empty = State::encode(&[], now + Duration::from_secs(60));
+    assert_eq!(State::decode(&empty, now).unwrap_err(), Error::Corrupt);
+}
+
 /// Executes the handshake to completion
 fn run_result<S: Endpoint, C: Endpoint>(
     server: &mut S,
@@ -423,6 +1134,227 @@ fn run<S: Endpoint, C: Endpoint>(
     run_result(server, client, client_hello_cb_done).unwrap();
 }
 
+/// Performs a handshake and returns the signature schemes reported by the client and server
+///
+/// The schemes are returned as `(client selected, client peer, server selected, server peer)`
+fn signature_schemes(
+    server: &mut server::Server,
+    client: &mut client::Client,
+) -> [Option<SignatureScheme>; 4] {
+    let mut pair = tls::testing::Pair::new(server, client, "localhost".into());
+
+    // the schemes aren't available until the handshake completes
+    assert_eq!(pair.client.session.selected_signature_scheme(), None);
+    assert_eq!(pair.server.session.selected_signature_scheme(), None);
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    [
+        pair.client.session.selected_signature_scheme(),
+        pair.client.session.peer_signature_scheme(),
+        pair.server.session.selected_signature_scheme(),
+        pair.server.session.peer_signature_scheme(),
+    ]
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn ecdsa_signature_scheme_test() {
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = s2n_server();
+
+    let scheme = Some(SignatureScheme::EcdsaSecp256r1Sha256);
+    assert_eq!(
+        signature_schemes(&mut server_endpoint, &mut client_endpoint),
+        // the client doesn't sign the handshake without client authentication
+        [scheme, scheme, scheme, None]
+    );
+    assert_eq!(scheme.unwrap().to_string(), "ecdsa_secp256r1_sha256");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rsa_pss_signature_scheme_test() {
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(RSA_CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(RSA_CERT_PEM, RSA_KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let scheme = Some(SignatureScheme::RsaPssRsaeSha256);
+    assert_eq!(
+        signature_schemes(&mut server_endpoint, &mut client_endpoint),
+        [scheme, scheme, scheme, None]
+    );
+    assert_eq!(scheme.unwrap().to_string(), "rsa_pss_rsae_sha256");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn client_auth_signature_scheme_test() {
+    let mut client_endpoint = s2n_client_with_client_auth().unwrap();
+    let mut server_endpoint = s2n_server_with_client_auth().unwrap();
+
+    let scheme = Some(SignatureScheme::EcdsaSecp256r1Sha256);
+    assert_eq!(
+        signature_schemes(&mut server_endpoint, &mut client_endpoint),
+        [scheme, scheme, scheme, scheme]
+    );
+}
+
+/// Performs a handshake and returns the certificate chains reported to the client and server
+fn peer_certificate_chains(
+    server: &mut server::Server,
+    client: &mut client::Client,
+) -> (Vec<Bytes>, Vec<Bytes>) {
+    let mut pair = tls::testing::Pair::new(server, client, "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+
+    (
+        pair.client.context.peer_certificate_chain,
+        pair.server.context.peer_certificate_chain,
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn peer_certificate_chain_test() {
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = s2n_server();
+
+    let (client_chain, server_chain) =
+        peer_certificate_chains(&mut server_endpoint, &mut client_endpoint);
+
+    assert_eq!(client_chain.first().map(|c| &c[..]), Some(&cert_der()[..]));
+    // the client doesn't present a certificate without client authentication
+    assert!(server_chain.is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn client_auth_peer_certificate_chain_test() {
+    let mut client_endpoint = s2n_client_with_client_auth().unwrap();
+    let mut server_endpoint = s2n_server_with_client_auth().unwrap();
+
+    let (client_chain, server_chain) =
+        peer_certificate_chains(&mut server_endpoint, &mut client_endpoint);
+
+    assert_eq!(client_chain.first().map(|c| &c[..]), Some(&cert_der()[..]));
+    assert_eq!(server_chain.first().map(|c| &c[..]), Some(&cert_der()[..]));
+}
+
+/// Performs a handshake with `server_name` and returns the certificate the server presented
+fn selected_certificate(
+    server: &mut server::Server,
+    client: &mut client::Client,
+    server_name: &str,
+) -> Bytes {
+    let mut pair = tls::testing::Pair::new(server, client, server_name.into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+
+    pair.client.context.peer_certificate_chain.remove(0)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn sni_certificate_selection_test() {
+    let mut server_endpoint = server::Builder::default()
+        .with_certificates([
+            (SNI_EXACT_CERT, SNI_EXACT_KEY),
+            (SNI_WILDCARD_CERT, SNI_WILDCARD_KEY),
+        ])
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(SNI_EXACT_CERT)
+        .unwrap()
+        .with_certificate(SNI_WILDCARD_CERT)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let exact = pem_to_der(SNI_EXACT_CERT);
+    let wildcard = pem_to_der(SNI_WILDCARD_CERT);
+
+    for (server_name, expected) in [
+        ("exact.qlaws", &exact),
+        ("a.wildcard.qlaws", &wildcard),
+        ("b.wildcard.qlaws", &wildcard),
+    ] {
+        let certificate =
+            selected_certificate(&mut server_endpoint, &mut client_endpoint, server_name);
+        assert_eq!(
+            &certificate[..],
+            &expected[..],
+            "server name: {server_name}"
+        );
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn sni_certificate_fallback_test() {
+    let mut server_endpoint = server::Builder::default()
+        .with_certificates([
+            (SNI_EXACT_CERT, SNI_EXACT_KEY),
+            (SNI_WILDCARD_CERT, SNI_WILDCARD_KEY),
+        ])
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(SNI_EXACT_CERT)
+        .unwrap()
+        .with_certificate(SNI_WILDCARD_CERT)
+        .unwrap()
+        .with_verify_host_name_callback(AcceptAllHostNamesVerifier::default())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    // the first certificate is the default for names which don't match any of the certificates
+    let exact = pem_to_der(SNI_EXACT_CERT);
+
+    for server_name in ["unknown.qlaws", "a.b.wildcard.qlaws"] {
+        let certificate =
+            selected_certificate(&mut server_endpoint, &mut client_endpoint, server_name);
+        assert_eq!(&certificate[..], &exact[..], "server name: {server_name}");
+    }
+}
+
+#[test]
+fn sni_certificates_exclusive_test() {
+    assert!(server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_certificates([(SNI_EXACT_CERT, SNI_EXACT_KEY)])
+        .is_err());
+
+    assert!(server::Builder::default()
+        .with_certificates([(SNI_EXACT_CERT, SNI_EXACT_KEY)])
+        .unwrap()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .is_err());
+}
+
 #[test]
 fn config_loader() {
     use crate::{ConfigLoader, Server};
@@ -0,0 +1,39 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Application-defined verification of the peer's certificate chain
+
+use bytes::Bytes;
+use core::fmt;
+use s2n_quic_core::{application::ServerName, crypto::CryptoError};
+
+/// Decides whether the certificate chain presented by the peer is trusted
+///
+/// This can be used to implement certificate pinning or to validate certificates issued by a
+/// private PKI which the default validation doesn't support.
+pub trait CertificateVerifier: 'static + Send + Sync {
+    /// Verifies the peer's certificate chain
+    ///
+    /// `chain` contains the DER-encoded certificates, starting with the peer's leaf certificate.
+    /// `server_name` is the name the client connected to, if any.
+    ///
+    /// Returning an error fails the handshake and closes the connection with the error's alert.
+    fn verify(&self, chain: &[Bytes], server_name: Option<&ServerName>) -> Result<(), CryptoError>;
+}
+
+impl<F> CertificateVerifier for F
+where
+    F: 'static + Send + Sync + Fn(&[Bytes], Option<&ServerName>) -> Result<(), CryptoError>,
+{
+    #[inline]
+    fn verify(&self, chain: &[Bytes], server_name: Option<&ServerName>) -> Result<(), CryptoError> {
+        (self)(chain, server_name)
+    }
+}
+
+impl fmt::Debug for dyn CertificateVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CertificateVerifier")
+            .finish_non_exhaustive()
+    }
+}
@@ -91,6 +91,12 @@ impl AckManager {
         }
     }
 
+    /// Returns the ECN counts of the packets processed in the packet space
+    #[inline]
+    pub fn ecn_counts(&self) -> EcnCounts {
+        self.ecn_counts
+    }
+
     /// Called when an outgoing packet is being assembled
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> bool {
         let constraint = context.transmission_constraint();
@@ -301,20 +307,23 @@ impl AckManager {
             //# *  when the received packet has a packet number less than another
             //#    ack-eliciting packet that has been received, or
 
-            should_activate |= !is_largest;
+            let immediate_ack = self.ack_settings.immediate_ack_on_congestion;
+
+            should_activate |= immediate_ack && !is_largest;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# *  when the packet has a packet number larger than the highest-
             //#    numbered ack-eliciting packet that has been received and there are
             //#    missing packets between that packet and this packet.
 
-            should_activate |= !is_ordered;
+            should_activate |= immediate_ack && !is_ordered;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# Similarly, packets marked with the ECN Congestion Experienced (CE)
             //# codepoint in the IP header SHOULD be acknowledged immediately, to
             //# reduce the peer's response time to congestion events.
-            should_activate |= processed_packet.datagram.ecn.congestion_experienced();
+            should_activate |=
+                immediate_ack && processed_packet.datagram.ecn.congestion_experienced();
 
             // TODO update to draft link after published
             // https://github.com/quicwg/base-drafts/pull/3623
@@ -431,6 +440,8 @@ mod tests {
             timestamp: NoopClock {}.get_time(),
             destination_connection_id: connection::LocalId::TEST_ID,
             source_connection_id: None,
+            ttl: None,
+            receive_delay: None,
         };
         let mut processed_packet = ProcessedPacket::new(pn, &datagram);
         processed_packet.path_challenge_on_active_path = true;
@@ -524,6 +535,67 @@ mod tests {
         assert_eq!(1, manager.ecn_counts.ce_count.as_u64());
     }
 
+    #[test]
+    fn immediate_ack_on_congestion() {
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        let mut publisher = Publisher::snapshot();
+
+        fn new_packet(pn: u8, datagram: &DatagramInfo) -> ProcessedPacket {
+            let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+            let mut processed_packet = ProcessedPacket::new(pn, datagram);
+            processed_packet.ack_elicitation = AckElicitation::Eliciting;
+            processed_packet
+        }
+
+        for enabled in [true, false] {
+            let settings = ack::Settings {
+                immediate_ack_on_congestion: enabled,
+                ..Default::default()
+            };
+            let new_manager = || AckManager::new(PacketNumberSpace::ApplicationData, settings);
+            let not_ect = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+            let now = not_ect.timestamp;
+
+            // a normal packet is acknowledged once the ACK delay timer expires
+            let mut manager = new_manager();
+            manager.on_processed_packet(
+                &new_packet(1, &not_ect),
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+            assert!(!manager.transmission_state.is_active());
+            assert!(!manager.ack_delay_timer.is_expired(now));
+            manager.on_timeout(now + settings.max_ack_delay);
+            assert!(manager.transmission_state.is_active());
+
+            // a CE-marked packet is acknowledged immediately, if enabled
+            let mut manager = new_manager();
+            let ce = helper_datagram_info(ExplicitCongestionNotification::Ce);
+            manager.on_processed_packet(
+                &new_packet(1, &ce),
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+            assert_eq!(manager.transmission_state.is_active(), enabled);
+            assert_eq!(manager.ack_delay_timer.is_armed(), !enabled);
+
+            // a gap in packet numbers is acknowledged immediately, if enabled
+            let mut manager = new_manager();
+            manager.on_processed_packet(
+                &new_packet(1, &not_ect),
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+            manager.on_processed_packet(
+                &new_packet(3, &not_ect),
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+            assert_eq!(manager.transmission_state.is_active(), enabled);
+        }
+    }
+
     /// Helper function to construct `DatagramInfo` with the given `ExplicitCongestionNotification`
     fn helper_datagram_info(ecn: ExplicitCongestionNotification) -> DatagramInfo {
         DatagramInfo {
@@ -532,6 +604,8 @@ mod tests {
             timestamp: NoopClock {}.get_time(),
             destination_connection_id: connection::LocalId::TEST_ID,
             source_connection_id: None,
+            ttl: None,
+            receive_delay: None,
         }
     }
 
@@ -57,6 +57,8 @@ impl Endpoint {
             timestamp: self.env.current_time,
             destination_connection_id: connection::LocalId::TEST_ID,
             source_connection_id: None,
+            ttl: None,
+            receive_delay: None,
         };
 
         if let Some(ack) = packet.ack {
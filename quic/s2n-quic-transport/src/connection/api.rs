@@ -16,7 +16,10 @@ use core::{
 use s2n_quic_core::{
     application,
     application::ServerName,
+    connection::{CloseOnDrop, Stats, Version},
+    crypto::{application::KeyUsage, tls},
     inet::SocketAddress,
+    path::{PathError, PathInfo, PathProbe},
     query::{Query, QueryMut},
     stream::StreamType,
 };
@@ -175,6 +178,31 @@ impl Connection {
         self.api.application_protocol()
     }
 
+    #[inline]
+    pub fn peer_certificate_chain(&self) -> Result<Vec<Bytes>, connection::Error> {
+        self.api.peer_certificate_chain()
+    }
+
+    #[inline]
+    pub fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError> {
+        self.api.export_keying_material(output, label, context)
+    }
+
+    #[inline]
+    pub fn handshake_info(&self) -> Result<tls::HandshakeInfo, connection::Error> {
+        self.api.handshake_info()
+    }
+
+    #[inline]
+    pub fn resumption_state(&self) -> Result<Option<Vec<u8>>, connection::Error> {
+        self.api.resumption_state()
+    }
+
     #[inline]
     pub fn id(&self) -> u64 {
         self.api.id()
@@ -189,6 +217,46 @@ impl Connection {
         self.api.keep_alive(enabled)
     }
 
+    #[inline]
+    pub fn set_close_on_drop(&self, close_on_drop: CloseOnDrop) -> Result<(), connection::Error> {
+        self.api.set_close_on_drop(close_on_drop)
+    }
+
+    #[inline]
+    pub fn set_max_send_rate(&self, max_send_rate: Option<u64>) -> Result<(), connection::Error> {
+        self.api.set_max_send_rate(max_send_rate)
+    }
+
+    #[inline]
+    pub fn set_dscp(&self, dscp: u8) -> Result<(), connection::DscpError> {
+        self.api.set_dscp(dscp)
+    }
+
+    #[inline]
+    pub fn set_local_address(&self, local_address: SocketAddress) -> Result<(), PathError> {
+        self.api.set_local_address(local_address)
+    }
+
+    #[inline]
+    pub fn poll_idle(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api.poll_idle(context)
+    }
+
+    #[inline]
+    pub fn key_usage(&self) -> Result<KeyUsage, connection::Error> {
+        self.api.key_usage()
+    }
+
+    #[inline]
+    pub fn stats(&self) -> Result<Stats, connection::Error> {
+        self.api.stats()
+    }
+
+    #[inline]
+    pub fn quic_version(&self) -> Result<Version, connection::Error> {
+        self.api.quic_version()
+    }
+
     #[inline]
     pub fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.local_address()
@@ -199,6 +267,34 @@ impl Connection {
         self.api.remote_address()
     }
 
+    #[inline]
+    pub fn active_path(&self) -> Result<PathInfo, connection::Error> {
+        self.api.active_path()
+    }
+
+    #[inline]
+    pub fn probe_path(
+        &self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        self.api.probe_path(local_address, remote_address)
+    }
+
+    #[inline]
+    pub fn poll_path_probe(&self, context: &Context) -> Poll<Result<PathProbe, PathError>> {
+        self.api.poll_path_probe(context)
+    }
+
+    #[inline]
+    pub fn migrate_to(
+        &self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        self.api.migrate_to(local_address, remote_address)
+    }
+
     #[inline]
     pub fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api.query_event_context(query)
@@ -17,7 +17,10 @@ use core::{
 use s2n_quic_core::{
     application,
     application::ServerName,
+    connection::{CloseOnDrop, Stats, Version},
+    crypto::{application::KeyUsage, tls},
     inet::SocketAddress,
+    path::{PathError, PathInfo, PathProbe},
     query::{Query, QueryMut},
     stream::{ops, StreamId, StreamType},
 };
@@ -58,16 +61,61 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
 
     fn application_protocol(&self) -> Result<Bytes, connection::Error>;
 
+    fn peer_certificate_chain(&self) -> Result<Vec<Bytes>, connection::Error>;
+
+    fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError>;
+
+    fn handshake_info(&self) -> Result<tls::HandshakeInfo, connection::Error>;
+
+    fn resumption_state(&self) -> Result<Option<Vec<u8>>, connection::Error>;
+
     fn id(&self) -> u64;
 
     fn ping(&self) -> Result<(), connection::Error>;
 
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn set_close_on_drop(&self, close_on_drop: CloseOnDrop) -> Result<(), connection::Error>;
+
+    fn set_max_send_rate(&self, max_send_rate: Option<u64>) -> Result<(), connection::Error>;
+
+    fn set_dscp(&self, dscp: u8) -> Result<(), connection::DscpError>;
+
+    fn set_local_address(&self, local_address: SocketAddress) -> Result<(), PathError>;
+
+    fn poll_idle(&self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    fn key_usage(&self) -> Result<KeyUsage, connection::Error>;
+
+    fn stats(&self) -> Result<Stats, connection::Error>;
+
+    fn quic_version(&self) -> Result<Version, connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn active_path(&self) -> Result<PathInfo, connection::Error>;
+
+    fn probe_path(
+        &self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError>;
+
+    fn poll_path_probe(&self, context: &Context) -> Poll<Result<PathProbe, PathError>>;
+
+    fn migrate_to(
+        &self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError>;
+
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
 
     fn query_event_context_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
@@ -148,7 +148,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher> tx::Message
 
     #[inline]
     fn ipv6_flow_label(&mut self) -> u32 {
-        0
+        self.path.flow_label()
     }
 
     #[inline]
@@ -33,8 +33,11 @@ use intrusive_collections::{
 use s2n_quic_core::{
     application,
     application::ServerName,
+    connection::{CloseOnDrop, Stats, Version},
+    crypto::{application::KeyUsage, tls},
     event::supervisor,
     inet::SocketAddress,
+    path::{PathError, PathInfo, PathProbe},
     query::{Query, QueryMut},
     recovery::K_GRANULARITY,
     time::Timestamp,
@@ -309,6 +312,27 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| Ok(conn.application_protocol()))
     }
 
+    fn peer_certificate_chain(&self) -> Result<Vec<Bytes>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.peer_certificate_chain()))
+    }
+
+    fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError> {
+        self.api_read_call(|conn| conn.export_keying_material(output, label, context))
+    }
+
+    fn handshake_info(&self) -> Result<tls::HandshakeInfo, connection::Error> {
+        self.api_read_call(|conn| conn.handshake_info())
+    }
+
+    fn resumption_state(&self) -> Result<Option<Vec<u8>>, connection::Error> {
+        self.api_read_call(|conn| conn.resumption_state())
+    }
+
     fn id(&self) -> u64 {
         self.internal_connection_id.into()
     }
@@ -321,6 +345,38 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.keep_alive(enabled))
     }
 
+    fn set_close_on_drop(&self, close_on_drop: CloseOnDrop) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_close_on_drop(close_on_drop))
+    }
+
+    fn set_max_send_rate(&self, max_send_rate: Option<u64>) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_max_send_rate(max_send_rate))
+    }
+
+    fn set_dscp(&self, dscp: u8) -> Result<(), connection::DscpError> {
+        self.api_write_call(|conn| conn.set_dscp(dscp))
+    }
+
+    fn set_local_address(&self, local_address: SocketAddress) -> Result<(), PathError> {
+        self.api_write_call(|conn| conn.set_local_address(local_address))
+    }
+
+    fn poll_idle(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_idle(context))
+    }
+
+    fn key_usage(&self) -> Result<KeyUsage, connection::Error> {
+        self.api_read_call(|conn| conn.key_usage())
+    }
+
+    fn stats(&self) -> Result<Stats, connection::Error> {
+        self.api_read_call(|conn| conn.stats())
+    }
+
+    fn quic_version(&self) -> Result<Version, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.quic_version().into()))
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.local_address())
     }
@@ -329,6 +385,30 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| conn.remote_address())
     }
 
+    fn active_path(&self) -> Result<PathInfo, connection::Error> {
+        self.api_read_call(|conn| conn.active_path())
+    }
+
+    fn probe_path(
+        &self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        self.api_write_call(|conn| conn.probe_path(local_address, remote_address))
+    }
+
+    fn poll_path_probe(&self, context: &Context) -> Poll<Result<PathProbe, PathError>> {
+        self.api_poll_call(|conn| conn.poll_path_probe(context))
+    }
+
+    fn migrate_to(
+        &self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        self.api_write_call(|conn| conn.migrate_to(local_address, remote_address))
+    }
+
     #[inline]
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api_read_call(|conn| {
@@ -504,8 +584,8 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
 
             match <C::Config as endpoint::Config>::ENDPOINT_TYPE {
                 endpoint::Type::Server => {
-                    if let Err(error) = accept_queue.unbounded_send(handle) {
-                        error.into_inner().api.close_connection(None);
+                    if let Err(handle) = accept_queue.send(handle) {
+                        handle.api.close_connection(None);
                     }
                 }
                 endpoint::Type::Client => {
@@ -678,6 +758,14 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         !self.accept_queue.is_closed()
     }
 
+    /// Returns the number of connections which completed the handshake but haven't been
+    /// accepted by the application yet
+    pub fn pending_accepts(&self) -> usize {
+        debug_assert!(<C::Config as endpoint::Config>::ENDPOINT_TYPE.is_server());
+
+        self.accept_queue.pending()
+    }
+
     /// Returns `true` if the endpoint can make connection attempts
     fn can_connect(&self) -> bool {
         debug_assert!(<C::Config as endpoint::Config>::ENDPOINT_TYPE.is_client());
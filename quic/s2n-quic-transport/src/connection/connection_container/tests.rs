@@ -17,7 +17,9 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    application, event,
+    application,
+    connection::CloseOnDrop,
+    event,
     event::builder::DatagramDropReason,
     inet::{DatagramInfo, SocketAddress},
     io::tx,
@@ -129,6 +131,8 @@ impl connection::Trait for TestConnection {
     fn on_wakeup(
         &mut self,
         _timestamp: Timestamp,
+        _congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
     ) -> Result<(), connection::Error> {
@@ -185,6 +189,7 @@ impl connection::Trait for TestConnection {
         _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
+        _datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
     ) -> Result<(), ProcessingError> {
         Ok(())
     }
@@ -286,6 +291,27 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn peer_certificate_chain(&self) -> Vec<Bytes> {
+        todo!()
+    }
+
+    fn export_keying_material(
+        &self,
+        _output: &mut [u8],
+        _label: &[u8],
+        _context: &[u8],
+    ) -> Result<(), tls::ExportError> {
+        todo!()
+    }
+
+    fn handshake_info(&self) -> Result<tls::HandshakeInfo, connection::Error> {
+        todo!()
+    }
+
+    fn resumption_state(&self) -> Result<Option<Vec<u8>>, connection::Error> {
+        todo!()
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         todo!()
     }
@@ -294,6 +320,34 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn set_close_on_drop(&mut self, _close_on_drop: CloseOnDrop) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn set_max_send_rate(&mut self, _max_send_rate: Option<u64>) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn set_dscp(&mut self, _dscp: u8) -> Result<(), connection::DscpError> {
+        todo!()
+    }
+
+    fn set_local_address(&mut self, _local_address: SocketAddress) -> Result<(), PathError> {
+        todo!()
+    }
+
+    fn poll_idle(&mut self, _context: &Context) -> Poll<Result<(), connection::Error>> {
+        todo!()
+    }
+
+    fn key_usage(&self) -> Result<KeyUsage, connection::Error> {
+        todo!()
+    }
+
+    fn stats(&self) -> Result<Stats, connection::Error> {
+        todo!()
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         todo!()
     }
@@ -302,6 +356,30 @@ impl connection::Trait for TestConnection {
         Ok(SocketAddress::default())
     }
 
+    fn active_path(&self) -> Result<PathInfo, connection::Error> {
+        todo!()
+    }
+
+    fn probe_path(
+        &mut self,
+        _local_address: SocketAddress,
+        _remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        todo!()
+    }
+
+    fn poll_path_probe(&mut self, _context: &Context) -> Poll<Result<PathProbe, PathError>> {
+        todo!()
+    }
+
+    fn migrate_to(
+        &mut self,
+        _local_address: SocketAddress,
+        _remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        todo!()
+    }
+
     fn error(&self) -> Option<connection::Error> {
         None
     }
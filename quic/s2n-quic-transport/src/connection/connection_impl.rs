@@ -35,15 +35,20 @@ use core::{
 use s2n_quic_core::{
     application,
     application::ServerName,
-    connection::{error::Error, id::Generator as _, InitialId, PeerId},
-    crypto::{tls, CryptoSuite},
+    connection::{
+        error::Error,
+        id::Generator as _,
+        stats::{self, SendLimit},
+        CloseOnDrop, InitialId, PeerId, Stats,
+    },
+    crypto::{application::KeyUsage, tls, CryptoSuite},
     datagram::{Receiver, Sender},
     event::{
         self,
         builder::{DatagramDropReason, MtuUpdatedCause, RxStreamProgress, TxStreamProgress},
         supervisor, ConnectionPublisher as _, IntoEvent as _, Subscriber,
     },
-    inet::{DatagramInfo, SocketAddress},
+    inet::{DatagramInfo, EcnMode, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
@@ -54,9 +59,9 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::{Handle as _, MaxMtu},
+    path::{Handle as _, MaxMtu, PathError, PathInfo, PathProbe},
     query,
-    recovery::CongestionController,
+    recovery::{CongestionController, SendRateLimiter},
     stateless_reset::token::Generator as _,
     time::{timer, Timestamp},
     transport,
@@ -173,6 +178,15 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     close_sender: CloseSender,
     /// Manages all of the different packet spaces and their respective components
     space_manager: PacketSpaceManager<Config>,
+    /// Tracks the time spent in each of the limits on sending data
+    send_limit: stats::Tracker,
+    /// Determines how the connection is closed once the application drops it
+    close_on_drop: CloseOnDrop,
+    /// Caps the rate at which the connection sends, regardless of the congestion window
+    send_rate_limiter: SendRateLimiter,
+    /// Tasks which are woken once the connection has no more application data to send or
+    /// acknowledge
+    idle_wakers: Vec<Waker>,
     /// Holds the handle for waking up the endpoint from a application call
     wakeup_handle: Arc<WakeupHandle<InternalConnectionId>>,
     /// A Waker to the connection.
@@ -247,6 +261,17 @@ macro_rules! transmission_context {
             .ecn_controller
             .ecn($transmission_mode, $timestamp);
 
+        // MTU probes don't carry any application data so they use the connection's code point
+        let default = $self.limits.dscp();
+        let dscp = if $transmission_mode.is_mtu_probing() {
+            default
+        } else {
+            $self
+                .space_manager
+                .application_mut()
+                .map_or(default, |(space, _)| space.transmission_dscp(default))
+        };
+
         ConnectionTransmissionContext {
             quic_version: $self.event_context.quic_version,
             timestamp: $timestamp,
@@ -255,7 +280,9 @@ macro_rules! transmission_context {
             local_id_registry: &mut $self.local_id_registry,
             outcome: $outcome,
             ecn,
+            dscp,
             min_packet_len: None,
+            max_coalesced_packets: $self.limits.max_coalesced_packets(),
             transmission_mode: $transmission_mode,
             publisher: &mut $self.event_context.publisher($timestamp, $subscriber),
             packet_interceptor: $packet_interceptor,
@@ -343,7 +370,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         //# commits to initiating an immediate close (Section 10.2) if it
         //# abandons the connection prior to the effective value.
 
-        let mut duration = self.limits.max_idle_timeout()?.as_millis() as u64;
+        let duration = self.limits.max_idle_timeout()?;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
         //# To avoid excessively small idle timeout periods, endpoints MUST
@@ -351,9 +378,18 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         //# current Probe Timeout (PTO).  This allows for multiple PTOs to
         //# expire, and therefore multiple probes to be sent and lost, prior to
         //# idle timeout.
-        duration = duration.max(3 * self.current_pto().as_millis() as u64);
+        //
+        // The peer is allowed to delay acknowledging the last packet it received by up to its
+        // `max_ack_delay`, so the idle period is extended by that amount to avoid closing a
+        // connection which is only waiting on a delayed ACK.
+        let max_ack_delay = self
+            .path_manager
+            .active_path()
+            .rtt_estimator
+            .max_ack_delay();
+        let duration = duration.saturating_add(max_ack_delay);
 
-        Some(Duration::from_millis(duration))
+        Some(duration.max(3 * self.current_pto()))
     }
 
     fn on_processed_packet(
@@ -386,9 +422,36 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             self.error?;
         }
 
+        // the acknowledgements in the packet may have been the last ones for application data
+        self.wake_idle_wakers();
+
         Ok(())
     }
 
+    /// Records what prevented the connection from sending more data on the active path
+    fn on_send_limit(&mut self, is_paced: bool, timestamp: Timestamp) {
+        let stream_manager = if let Some(space) = self.space_manager.application() {
+            &space.stream_manager
+        } else {
+            return;
+        };
+
+        let limit = if self.path_manager.active_path().is_congestion_limited(0) {
+            SendLimit::CongestionControl
+        } else if stream_manager.has_flow_control_blocked_streams() {
+            SendLimit::FlowControl
+        } else if !stream_manager.has_pending_streams() {
+            SendLimit::Application
+        } else if is_paced {
+            SendLimit::CongestionControl
+        } else {
+            // the remaining data is blocked by something else, e.g. a full transmission queue
+            return;
+        };
+
+        self.send_limit.on_send_limit(limit, timestamp);
+    }
+
     fn on_ack_eliciting_packet_sent(&mut self, timestamp: Timestamp) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
         //# An endpoint also restarts its
@@ -451,7 +514,9 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                         local_id_registry: &mut self.local_id_registry,
                         outcome,
                         min_packet_len: None,
+                        max_coalesced_packets: self.limits.max_coalesced_packets(),
                         ecn,
+                        dscp: self.limits.dscp(),
                         transmission_mode,
                         publisher: &mut self.event_context.publisher(timestamp, subscriber),
                         packet_interceptor,
@@ -545,6 +610,26 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
 
         Poll::Pending
     }
+
+    /// Returns `true` if all of the application data was sent and acknowledged
+    fn is_idle(&self) -> bool {
+        self.space_manager.application().map_or(true, |space| {
+            let streams = &space.stream_manager;
+            !streams.has_pending_streams()
+                && !streams.has_flow_control_blocked_streams()
+                && !streams.has_unacknowledged_streams()
+                && !space.datagram_manager.has_pending_datagrams()
+        })
+    }
+
+    /// Notifies the application if it is waiting for the connection to become idle
+    fn wake_idle_wakers(&mut self) {
+        if !self.idle_wakers.is_empty() && self.is_idle() {
+            for waker in core::mem::take(&mut self.idle_wakers) {
+                waker.wake();
+            }
+        }
+    }
 }
 
 impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
@@ -565,7 +650,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         // The path manager always starts with a single path containing the known peer and local
         // connection ids.
-        let rtt_estimator = RttEstimator::default();
+        let rtt_estimator = RttEstimator::new_with_initial_rtt(
+            Duration::ZERO,
+            parameters.limits.initial_round_trip_time(),
+        );
         // Assume clients validate the server's address implicitly.
         let peer_validated = Self::Config::ENDPOINT_TYPE.is_server();
 
@@ -577,9 +665,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             parameters.congestion_controller,
             peer_validated,
             parameters.max_mtu,
+            parameters.flow_label_key,
         );
 
-        let path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+        let mut path_manager = path::Manager::new(initial_path, parameters.peer_id_registry)
+            .with_max_validating_paths(parameters.limits.max_validating_paths());
 
         let mut publisher =
             event_context.publisher(parameters.timestamp, parameters.event_subscriber);
@@ -595,12 +685,27 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             },
         });
 
+        let active_path_id = path_manager.active_path_id();
+        path_manager.active_path_mut().set_min_mtu(
+            parameters.min_mtu,
+            active_path_id,
+            &mut publisher,
+        );
+
+        if parameters.limits.ecn_mode() == EcnMode::Disabled {
+            path_manager.active_path_mut().ecn_controller.disable();
+        }
+
         publisher.on_mtu_updated(event::builder::MtuUpdated {
             path_id: path_manager.active_path_id().into_event(),
             mtu: path_manager.active_path().mtu_controller.mtu() as u16,
             cause: MtuUpdatedCause::NewPath,
         });
 
+        let mut send_rate_limiter = SendRateLimiter::default();
+        send_rate_limiter.set_max_send_rate(parameters.limits.max_send_rate());
+        send_rate_limiter.set_max_burst(parameters.limits.max_send_burst());
+
         let wakeup_handle = Arc::from(parameters.wakeup_handle);
         let waker = Waker::from(wakeup_handle.clone());
         let mut connection = Self {
@@ -613,6 +718,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             error: Ok(()),
             close_sender: CloseSender::default(),
             space_manager: parameters.space_manager,
+            send_limit: Default::default(),
+            close_on_drop: CloseOnDrop::default(),
+            send_rate_limiter,
+            idle_wakers: Vec::new(),
             wakeup_handle,
             waker,
             event_context,
@@ -701,6 +810,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.timers.cancel();
         // Let the path manager know we're closing
         self.path_manager.on_closing();
+        // Notify the application tasks waiting for the connection to become idle of the error
+        for waker in core::mem::take(&mut self.idle_wakers) {
+            waker.wake();
+        }
         // Update the connection state based on the type of error
         self.state = error.into();
         self.error = Err(error);
@@ -841,65 +954,98 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 let mut outcome = transmission::Outcome::default();
                 let path_id = self.path_manager.active_path_id();
 
+                // Queues which hold datagrams until their departure time allow paced datagrams to
+                // be released early
+                let max_transmit_delay = queue.max_transmit_delay();
+                let departure_horizon = timestamp + max_transmit_delay;
+
                 // Send an MTU probe if necessary and the handshake has completed
                 // MTU probes are prioritized over other data so they are not blocked by the
                 // congestion controller, as they are critical to achieving maximum throughput.
                 if self.state == ConnectionState::Active
-                    && self.path_manager.active_path().can_transmit(timestamp)
+                    && self
+                        .path_manager
+                        .active_path()
+                        .can_transmit(departure_horizon)
+                    && self.send_rate_limiter.can_transmit(timestamp)
                     && self
                         .path_manager
                         .active_path()
                         .mtu_controller
                         .can_transmit(self.path_manager.active_path().transmission_constraint())
-                    && queue
-                        .push(ConnectionTransmission {
-                            context: transmission_context!(
-                                self,
-                                &mut outcome,
-                                path_id,
-                                timestamp,
-                                transmission::Mode::MtuProbing,
-                                subscriber,
-                                packet_interceptor,
-                            ),
-                            space_manager: &mut self.space_manager,
-                        })
-                        .is_ok()
                 {
-                    count += 1;
+                    let result = queue.push(ConnectionTransmission {
+                        context: transmission_context!(
+                            self,
+                            &mut outcome,
+                            path_id,
+                            timestamp,
+                            transmission::Mode::MtuProbing,
+                            subscriber,
+                            packet_interceptor,
+                        ),
+                        space_manager: &mut self.space_manager,
+                    });
+
+                    if let Ok(tx_outcome) = result {
+                        count += 1;
+                        self.send_rate_limiter
+                            .on_datagram_sent(timestamp, tx_outcome.len);
+                    }
                 }
 
                 // Send all other data for the active path
-                while self.path_manager.active_path().can_transmit(timestamp)
-                    && queue
-                        .push(ConnectionTransmission {
-                            context: transmission_context!(
-                                self,
-                                &mut outcome,
-                                path_id,
-                                timestamp,
-                                transmission::Mode::Normal,
-                                subscriber,
-                                packet_interceptor,
-                            ),
-                            space_manager: &mut self.space_manager,
-                        })
-                        .is_ok()
+                while self
+                    .path_manager
+                    .active_path()
+                    .can_transmit(departure_horizon)
+                    && self.send_rate_limiter.can_transmit(timestamp)
                 {
-                    count += 1;
+                    let result = queue.push(ConnectionTransmission {
+                        context: transmission_context!(
+                            self,
+                            &mut outcome,
+                            path_id,
+                            timestamp,
+                            transmission::Mode::Normal,
+                            subscriber,
+                            packet_interceptor,
+                        ),
+                        space_manager: &mut self.space_manager,
+                    });
+
+                    if let Ok(tx_outcome) = result {
+                        count += 1;
+                        self.send_rate_limiter
+                            .on_datagram_sent(timestamp, tx_outcome.len);
+                    } else {
+                        break;
+                    }
                 }
 
                 if outcome.ack_elicitation.is_ack_eliciting() {
                     self.on_ack_eliciting_packet_sent(timestamp);
                 }
 
-                if let Some(edt) = self
+                let mut is_paced = false;
+
+                // The maximum send rate applies on top of the congestion controller pacing, so
+                // transmission resumes once both allow it. Paced datagrams can be released
+                // `max_transmit_delay` before they depart.
+                let edt = self
                     .path_manager
                     .active_path()
                     .congestion_controller
                     .earliest_departure_time()
-                {
+                    .map(|edt| edt.checked_sub(max_transmit_delay).unwrap_or(edt))
+                    .into_iter()
+                    .chain(self.send_rate_limiter.earliest_departure_time())
+                    .max();
+
+                if let Some(edt) = edt {
                     if !edt.has_elapsed(timestamp) {
+                        is_paced = true;
+
                         // We can't transmit more until a future time, so arm the pacing
                         // timer to pause transmission until the earliest departure time.
 
@@ -913,6 +1059,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     }
                 }
 
+                self.on_send_limit(is_paced, timestamp);
+
                 let meta = event::builder::ConnectionMeta {
                     endpoint_type: Config::ENDPOINT_TYPE,
                     id: self.internal_connection_id().into(),
@@ -948,6 +1096,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     // trigger a wake up so we can close
                     self.wakeup_handle.wakeup();
                 }
+
+                // datagrams aren't acknowledged so the connection may be idle once they're sent
+                self.wake_idle_wakers();
             }
             ConnectionState::Closing => {
                 let mut publisher = self.event_context.publisher(timestamp, subscriber);
@@ -1080,6 +1231,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
     fn on_wakeup(
         &mut self,
         timestamp: Timestamp,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        random_generator: &mut Config::RandomGenerator,
         subscriber: &mut Config::EventSubscriber,
         datagram: &mut Config::DatagramEndpoint,
     ) -> Result<(), connection::Error> {
@@ -1089,6 +1242,30 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // check if crypto progress can be made
         self.update_crypto_state(timestamp, subscriber, datagram)?;
 
+        // arm any timers which streams configured since the last wakeup
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.on_wakeup(timestamp);
+        }
+
+        // create the path for a probe requested by the application
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+        self.path_manager.start_probe(
+            congestion_controller_endpoint,
+            random_generator,
+            &mut publisher,
+        );
+
+        // switch to a path with the local address requested by the application
+        self.path_manager.on_pending_local_address(
+            congestion_controller_endpoint,
+            random_generator,
+            &mut publisher,
+        )?;
+
+        // switch to the path the application requested to migrate to
+        self.path_manager
+            .on_pending_migration(random_generator, &mut publisher)?;
+
         // return an error if the application set one
         self.error?;
 
@@ -1132,6 +1309,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         publisher.on_datagram_received(event::builder::DatagramReceived {
             len: datagram.payload_len as u16,
+            ttl: datagram.ttl,
         });
 
         if matches!(self.state, ConnectionState::Closing) {
@@ -1361,6 +1539,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         random_generator: &mut Config::RandomGenerator,
         subscriber: &mut Config::EventSubscriber,
         packet_interceptor: &mut Config::PacketInterceptor,
+        datagram_endpoint: &mut Config::DatagramEndpoint,
     ) -> Result<(), ProcessingError> {
         //= https://www.rfc-editor.org/rfc/rfc9001#section-5.7
         //# Endpoints in either role MUST NOT decrypt 1-RTT packets from
@@ -1446,6 +1625,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 packet_interceptor,
             )?;
 
+            // process any post-handshake messages, e.g. `NewSessionTicket`
+            self.update_crypto_state(datagram.timestamp, subscriber, datagram_endpoint)?;
+
             // notify the connection a packet was processed
             self.on_processed_packet(&processed_packet, subscriber)?;
         }
@@ -1458,40 +1640,53 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         &mut self,
         datagram: &DatagramInfo,
         _path_id: path::Id,
-        _packet: ProtectedVersionNegotiation,
+        packet: ProtectedVersionNegotiation,
         subscriber: &mut Config::EventSubscriber,
         _packet_interceptor: &mut Config::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
+        // Only the client is supposed to receive version negotiation packets
+        if Self::Config::ENDPOINT_TYPE.is_server() {
+            return Ok(());
+        }
+
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
         publisher.on_packet_received(event::builder::PacketReceived {
             packet_header: event::builder::PacketHeader::VersionNegotiation {},
         });
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
-        //= type=TODO
-        //= feature=Version negotiation handler
-        //= tracking-issue=349
-        //# A client that supports only this version of QUIC MUST abandon the
-        //# current connection attempt if it receives a Version Negotiation
-        //# packet, with the following two exceptions.
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
-        //= type=TODO
-        //= feature=Version negotiation handler
-        //= tracking-issue=349
         //# A client MUST discard any
         //# Version Negotiation packet if it has received and successfully
         //# processed any other packet, including an earlier Version Negotiation
         //# packet.
+        //
+        // The connection is closed on the first Version Negotiation packet so only Initial and
+        // Retry packets need to be checked.
+        if self.path_manager.valid_initial_received() || self.space_manager.retry_cid().is_some() {
+            return Ok(());
+        }
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
-        //= type=TODO
-        //= feature=Version negotiation handler
-        //= tracking-issue=349
         //# A client MUST discard a Version Negotiation packet that
         //# lists the QUIC version selected by the client.
+        let quic_version = self.quic_version();
+        if packet.into_iter().any(|version| version == quic_version) {
+            return Ok(());
+        }
 
-        Ok(())
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
+        //# A client that supports only this version of QUIC MUST abandon the
+        //# current connection attempt if it receives a Version Negotiation
+        //# packet, with the following two exceptions.
+        //
+        // The attempt is abandoned with the first mutually supported version so the application
+        // can retry with it.
+        let compatible_version = packet
+            .into_iter()
+            .find(|version| endpoint::SUPPORTED_VERSIONS.contains(version));
+
+        Err(Error::unsupported_version(compatible_version).into())
     }
 
     /// Is called when a zero rtt packet had been received
@@ -1783,6 +1978,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         if let Some(error) = error {
             self.error = Err(connection::Error::application(error));
+        } else if self.close_on_drop == CloseOnDrop::Silent {
+            // abandon the connection without flushing the streams or notifying the peer
+            self.error = Err(connection::Error::immediate_close(
+                "the connection was dropped by the application",
+            ));
         } else {
             // give the connection some time to flush all outstanding streams
             self.state = ConnectionState::Flushing;
@@ -1801,6 +2001,47 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.space_manager.application_protocol.clone()
     }
 
+    fn peer_certificate_chain(&self) -> Vec<Bytes> {
+        self.space_manager.peer_certificate_chain.clone()
+    }
+
+    fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError> {
+        self.error?;
+
+        self.space_manager
+            .export_keying_material(output, label, context)
+    }
+
+    fn handshake_info(&self) -> Result<tls::HandshakeInfo, connection::Error> {
+        self.error?;
+
+        if let Some(space) = self.space_manager.application() {
+            Ok(tls::HandshakeInfo {
+                cipher_suite: space.cipher_suite(),
+                server_name: self.space_manager.server_name.clone(),
+                application_protocol: self.space_manager.application_protocol.clone(),
+                peer_certificate_chain: self.space_manager.peer_certificate_chain.clone(),
+            })
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            Err(connection::Error::unspecified())
+        }
+    }
+
+    fn resumption_state(&self) -> Result<Option<Vec<u8>>, connection::Error> {
+        self.error?;
+
+        Ok(self.space_manager.resumption_state())
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1837,6 +2078,109 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn set_close_on_drop(&mut self, close_on_drop: CloseOnDrop) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.close_on_drop = close_on_drop;
+
+        Ok(())
+    }
+
+    fn set_max_send_rate(&mut self, max_send_rate: Option<u64>) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.send_rate_limiter.set_max_send_rate(max_send_rate);
+
+        Ok(())
+    }
+
+    fn set_dscp(&mut self, dscp: u8) -> Result<(), connection::DscpError> {
+        self.error?;
+
+        // streams which are created after the handshake inherit the code point from the limits
+        self.limits = self
+            .limits
+            .with_dscp(dscp)
+            .map_err(|_| connection::DscpError::InvalidCodePoint)?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.set_dscp(dscp);
+            space.datagram_manager.set_dscp(dscp);
+        }
+
+        Ok(())
+    }
+
+    fn set_local_address(&mut self, local_address: SocketAddress) -> Result<(), PathError> {
+        self.error?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate
+        //# connection migration before the handshake is confirmed, as defined
+        //# in section 4.1.2 of [QUIC-TLS].
+        if !self.space_manager.is_handshake_confirmed() {
+            return Err(PathError::HandshakeNotConfirmed);
+        }
+
+        let path = self.path_manager.active_path();
+        let mut local_address = path::LocalAddress::from(local_address);
+
+        // keep sending from the same socket if the application only specified an IP
+        if local_address.port() == 0 {
+            local_address.set_port(path.handle.local_address().port());
+        }
+
+        let handle =
+            Config::PathHandle::from_addresses(local_address, path.handle.remote_address());
+        self.path_manager.request_local_address(handle)?;
+
+        // the path is created once the endpoint wakes up the connection
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
+    fn poll_idle(&mut self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        if let Err(error) = self.error {
+            return Poll::Ready(Err(error));
+        }
+
+        if self.is_idle() {
+            return Poll::Ready(Ok(()));
+        }
+
+        // multiple tasks can wait for the connection to become idle through cloned handles
+        let waker = context.waker();
+        if !self.idle_wakers.iter().any(|w| w.will_wake(waker)) {
+            self.idle_wakers.push(waker.clone());
+        }
+
+        Poll::Pending
+    }
+
+    fn stats(&self) -> Result<Stats, connection::Error> {
+        self.error?;
+
+        let mut stats = self.send_limit.stats();
+        self.space_manager.update_stats(&mut stats);
+        self.path_manager.active_path().update_stats(&mut stats);
+        Ok(stats)
+    }
+
+    fn key_usage(&self) -> Result<KeyUsage, connection::Error> {
+        self.error?;
+
+        if let Some(space) = self.space_manager.application() {
+            Ok(space.key_usage())
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            Err(connection::Error::unspecified())
+        }
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
@@ -1845,6 +2189,84 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(*self.path_manager.active_path().handle.remote_address())
     }
 
+    fn active_path(&self) -> Result<PathInfo, connection::Error> {
+        let path = self.path_manager.active_path();
+
+        Ok(PathInfo {
+            id: self.path_manager.active_path_id().as_u8() as u64,
+            local_address: *path.local_address(),
+            remote_address: *path.remote_address(),
+            is_validated: path.is_validated(),
+            rtt: path.rtt_estimator.smoothed_rtt(),
+            congestion_window: path.congestion_controller.congestion_window(),
+        })
+    }
+
+    fn probe_path(
+        &mut self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        self.error?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate
+        //# connection migration before the handshake is confirmed, as defined
+        //# in section 4.1.2 of [QUIC-TLS].
+        if !self.space_manager.is_handshake_confirmed() {
+            return Err(PathError::HandshakeNotConfirmed);
+        }
+
+        let mut local_address = path::LocalAddress::from(local_address);
+
+        // probe from the same socket if the application only specified an IP
+        if local_address.port() == 0 {
+            let path = self.path_manager.active_path();
+            local_address.set_port(path.handle.local_address().port());
+        }
+
+        let handle = Config::PathHandle::from_addresses(local_address, remote_address.into());
+        self.path_manager.request_probe(handle)?;
+
+        // the path is created once the endpoint wakes up the connection
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
+    fn poll_path_probe(&mut self, context: &Context) -> Poll<Result<PathProbe, PathError>> {
+        if let Err(error) = self.error {
+            return Poll::Ready(Err(error.into()));
+        }
+
+        self.path_manager.poll_probe(context)
+    }
+
+    fn migrate_to(
+        &mut self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError> {
+        self.error?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate
+        //# connection migration before the handshake is confirmed, as defined
+        //# in section 4.1.2 of [QUIC-TLS].
+        if !self.space_manager.is_handshake_confirmed() {
+            return Err(PathError::HandshakeNotConfirmed);
+        }
+
+        let handle =
+            Config::PathHandle::from_addresses(local_address.into(), remote_address.into());
+        self.path_manager.request_migration(handle)?;
+
+        // the active path is updated once the endpoint wakes up the connection
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
     fn error(&self) -> Option<connection::Error> {
         self.error.err()
     }
@@ -20,6 +20,8 @@ use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application,
     application::ServerName,
+    connection::{CloseOnDrop, Stats},
+    crypto::{application::KeyUsage, tls},
     event::{self, builder::DatagramDropReason, supervisor, ConnectionPublisher, IntoEvent},
     inet::{DatagramInfo, SocketAddress},
     io::tx,
@@ -32,7 +34,7 @@ use s2n_quic_core::{
         zero_rtt::ProtectedZeroRtt,
         ProtectedPacket,
     },
-    path::{Handle as _, MaxMtu},
+    path::{Handle as _, MaxMtu, PathError, PathInfo, PathProbe},
     query,
     time::Timestamp,
 };
@@ -104,6 +106,8 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     fn on_wakeup(
         &mut self,
         timestamp: Timestamp,
+        congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
     ) -> Result<(), connection::Error>;
@@ -150,6 +154,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     ) -> Result<(), ProcessingError>;
 
     /// Is called when a short packet had been received
+    #[allow(clippy::too_many_arguments)]
     fn handle_short_packet(
         &mut self,
         datagram: &DatagramInfo,
@@ -158,6 +163,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
+        datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
     ) -> Result<(), ProcessingError>;
 
     /// Is called when a version negotiation packet had been received
@@ -257,6 +263,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 random_generator,
                 subscriber,
                 packet_interceptor,
+                datagram_endpoint,
             ),
             ProtectedPacket::VersionNegotiation(packet) => self.handle_version_negotiation_packet(
                 datagram,
@@ -434,14 +441,57 @@ pub trait ConnectionTrait: 'static + Send + Sized {
 
     fn application_protocol(&self) -> Bytes;
 
+    fn peer_certificate_chain(&self) -> Vec<Bytes>;
+
+    fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError>;
+
+    fn handshake_info(&self) -> Result<tls::HandshakeInfo, connection::Error>;
+
+    fn resumption_state(&self) -> Result<Option<Vec<u8>>, connection::Error>;
+
     fn ping(&mut self) -> Result<(), connection::Error>;
 
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn set_close_on_drop(&mut self, close_on_drop: CloseOnDrop) -> Result<(), connection::Error>;
+
+    fn set_max_send_rate(&mut self, max_send_rate: Option<u64>) -> Result<(), connection::Error>;
+
+    fn set_dscp(&mut self, dscp: u8) -> Result<(), connection::DscpError>;
+
+    fn set_local_address(&mut self, local_address: SocketAddress) -> Result<(), PathError>;
+
+    fn poll_idle(&mut self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    fn key_usage(&self) -> Result<KeyUsage, connection::Error>;
+
+    fn stats(&self) -> Result<Stats, connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn active_path(&self) -> Result<PathInfo, connection::Error>;
+
+    fn probe_path(
+        &mut self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError>;
+
+    fn poll_path_probe(&mut self, context: &Context) -> Poll<Result<PathProbe, PathError>>;
+
+    fn migrate_to(
+        &mut self,
+        local_address: SocketAddress,
+        remote_address: SocketAddress,
+    ) -> Result<(), PathError>;
+
     fn error(&self) -> Option<connection::Error>;
 
     fn query_event_context(&self, query: &mut dyn query::Query);
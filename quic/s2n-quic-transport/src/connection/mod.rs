@@ -4,7 +4,10 @@
 //! This module contains the implementation of QUIC `Connections` and their management
 
 use crate::{
-    endpoint, path::MaxMtu, recovery::congestion_controller, space::PacketSpaceManager,
+    endpoint,
+    path::{FlowLabelKey, MaxMtu, MinMtu},
+    recovery::congestion_controller,
+    space::PacketSpaceManager,
     wakeup_queue::WakeupHandle,
 };
 use s2n_quic_core::{connection, event, event::supervisor, time::Timestamp};
@@ -75,6 +78,10 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub limits: connection::Limits,
     /// The largest maximum transmission unit (MTU) that can be sent on a path
     pub max_mtu: MaxMtu,
+    /// The smallest maximum transmission unit (MTU) that path MTU discovery will use for a path
+    pub min_mtu: MinMtu,
+    /// The key which the flow labels of the connection's paths are derived from
+    pub flow_label_key: FlowLabelKey,
     /// The context that should be passed to all related connection events
     pub event_context: <Cfg::EventSubscriber as event::Subscriber>::ConnectionContext,
     /// The context passed to the connection supervisor
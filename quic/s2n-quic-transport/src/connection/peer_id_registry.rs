@@ -525,6 +525,13 @@ impl PeerIdRegistry {
             .any(|id_info| peer_id == &id_info.id && id_info.is_active())
     }
 
+    /// Returns `true` if a new peer_id is available to be consumed
+    pub fn has_new_id(&self) -> bool {
+        self.registered_ids
+            .iter()
+            .any(|id_info| id_info.status == New)
+    }
+
     /// Tries to consume a new peer_id if one is available.
     ///
     /// Register the stateless reset token once a connection ID is in use.
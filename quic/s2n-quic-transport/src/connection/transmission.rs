@@ -26,7 +26,11 @@ pub struct ConnectionTransmissionContext<'a, 'sub, Config: endpoint::Config> {
     pub local_id_registry: &'a mut connection::LocalIdRegistry,
     pub outcome: &'a mut transmission::Outcome,
     pub ecn: ExplicitCongestionNotification,
+    /// The DSCP code point the datagram is marked with
+    pub dscp: u8,
     pub min_packet_len: Option<usize>,
+    /// The maximum number of packets written to a single datagram
+    pub max_coalesced_packets: u8,
     pub transmission_mode: transmission::Mode,
     pub publisher: &'a mut event::ConnectionPublisherSubscriber<'sub, Config::EventSubscriber>,
     pub packet_interceptor: &'a mut Config::PacketInterceptor,
@@ -60,16 +64,26 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
         self.context.ecn
     }
 
+    #[inline]
+    fn dscp(&mut self) -> u8 {
+        self.context.dscp
+    }
+
     #[inline]
     fn delay(&mut self) -> Duration {
-        // TODO return delay from pacer
-        Default::default()
+        // the IO provider can hold the datagram until the pacer allows it to depart
+        self.context
+            .path()
+            .congestion_controller
+            .earliest_departure_time()
+            .map_or(Duration::ZERO, |edt| {
+                edt.saturating_duration_since(self.context.timestamp)
+            })
     }
 
     #[inline]
     fn ipv6_flow_label(&mut self) -> u32 {
-        // TODO compute flow label from connection id
-        0
+        self.context.path().flow_label()
     }
 
     #[inline]
@@ -174,13 +188,23 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             // datagram. If there is no ApplicationData packet, the Handshake packet will come at the
             // end, so we check that next. Finally, if there is no ApplicationData or Handshake packet
             // to transmit, the Initial packet itself will be padded.
+            //
+            // Packets which exceed the coalescing limit will be sent in a later datagram, so
+            // they aren't considered.
+            let max_coalesced_packets = self.context.max_coalesced_packets as usize;
             let mut pn_space_to_pad = {
+                let can_coalesce = |count: usize| count < max_coalesced_packets;
+                let has_handshake =
+                    has_transmission(space_manager.handshake(), transmission_constraint);
+
                 if !has_transmission(space_manager.initial(), transmission_constraint) {
                     // There is no Initial packet, so no padding is needed
                     None
-                } else if has_transmission(space_manager.application(), transmission_constraint) {
+                } else if has_transmission(space_manager.application(), transmission_constraint)
+                    && can_coalesce(if has_handshake { 2 } else { 1 })
+                {
                     Some(PacketNumberSpace::ApplicationData)
-                } else if has_transmission(space_manager.handshake(), transmission_constraint) {
+                } else if has_handshake && can_coalesce(1) {
                     Some(PacketNumberSpace::Handshake)
                 } else {
                     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9
@@ -189,6 +213,9 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 }
             };
 
+            // the number of packets written to the current datagram
+            let mut packet_count = 0;
+
             //= https://www.rfc-editor.org/rfc/rfc9001#section-4
             //# When packets of different types need to be sent,
             //# endpoints SHOULD use coalesced packets to send them in the same UDP
@@ -225,6 +252,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                             // The Initial packet was not ack eliciting so there is no need to pad
                             pn_space_to_pad = None;
                         }
+                        packet_count += 1;
                         *self.context.outcome += outcome;
                         encoder
                     }
@@ -252,7 +280,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             let encoder = if let Some((space, handshake_status)) = space_manager
                 .handshake_mut()
                 // MTU probes are only sent in the Application Space
-                .filter(|_| !is_mtu_probing)
+                .filter(|_| !is_mtu_probing && packet_count < max_coalesced_packets)
             {
                 self.context.min_packet_len = pn_space_to_pad
                     .filter(|pn_space| pn_space.is_handshake())
@@ -278,6 +306,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                             );
                         }
 
+                        packet_count += 1;
                         *self.context.outcome += outcome;
                         encoder
                     }
@@ -325,7 +354,10 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             // frames are only allowed in the ApplicationData space, which will always be the highest
             // current-available encryption level.
 
-            let encoder = if let Some((space, handshake_status)) = space_manager.application_mut() {
+            let encoder = if let Some((space, handshake_status)) = space_manager
+                .application_mut()
+                .filter(|_| packet_count < max_coalesced_packets)
+            {
                 self.context.min_packet_len = pn_space_to_pad
                     .filter(|pn_space| pn_space.is_application_data())
                     .map(|_| encoder.capacity());
@@ -28,6 +28,9 @@ pub trait WriteContext {
     /// Returns the number of available bytes remaining in the current payload
     fn remaining_capacity(&self) -> usize;
 
+    /// Returns the DSCP code point the current packet is marked with
+    fn dscp(&self) -> u8;
+
     /// Attempt to write an ack frame.
     ///
     /// If this was successful the number of the packet
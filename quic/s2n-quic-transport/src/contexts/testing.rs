@@ -223,6 +223,7 @@ pub struct MockWriteContext<'a> {
     pub transmission_constraint: Constraint,
     pub transmission_mode: Mode,
     pub endpoint: endpoint::Type,
+    pub dscp: u8,
 }
 
 impl<'a> MockWriteContext<'a> {
@@ -239,6 +240,7 @@ impl<'a> MockWriteContext<'a> {
             transmission_constraint,
             transmission_mode,
             endpoint,
+            dscp: 0,
         }
     }
 }
@@ -260,6 +262,10 @@ impl<'a> WriteContext for MockWriteContext<'a> {
         self.frame_buffer.remaining_capacity()
     }
 
+    fn dscp(&self) -> u8 {
+        self.dscp
+    }
+
     fn write_frame<Frame>(&mut self, frame: &Frame) -> Option<PacketNumber>
     where
         Frame: EncoderValue + FrameTrait,
@@ -12,7 +12,9 @@ use core::{
     task::{Context, Poll},
 };
 use futures_channel::oneshot;
-use s2n_quic_core::{application::ServerName, inet::SocketAddress, path::RemoteAddress};
+use s2n_quic_core::{
+    application::ServerName, connection::Version, inet::SocketAddress, path::RemoteAddress,
+};
 
 /// Held by connection Attempt future. Used to receive the actual connection.
 pub(crate) type ConnectionReceiver = oneshot::Receiver<Result<Connection, connection::Error>>;
@@ -25,6 +27,9 @@ pub(crate) type ConnectionSender = oneshot::Sender<Result<Connection, connection
 pub struct Connect {
     pub(crate) remote_address: RemoteAddress,
     pub(crate) server_name: Option<ServerName>,
+    pub(crate) quic_version: Option<u32>,
+    /// The number of times the attempt was restarted after version negotiation
+    pub(crate) version_negotiation_retries: u8,
 }
 
 impl fmt::Display for Connect {
@@ -49,6 +54,8 @@ impl Connect {
         Self {
             remote_address: addr.into().into(),
             server_name: None,
+            quic_version: None,
+            version_negotiation_retries: 0,
         }
     }
 
@@ -60,8 +67,54 @@ impl Connect {
             ..self
         }
     }
+
+    /// Specifies the QUIC version to use for the first connection attempt
+    ///
+    /// If the server responds with a Version Negotiation packet, the attempt is automatically
+    /// restarted with a version supported by both endpoints. This can be used to offer a
+    /// reserved version to exercise version negotiation with the server.
+    ///
+    /// Returns an error if the version is neither supported by the client nor reserved for
+    /// exercising version negotiation.
+    pub fn with_quic_version(self, version: Version) -> Result<Self, UnsupportedVersionError> {
+        let is_supported = super::SUPPORTED_VERSIONS.contains(&version.as_u32());
+
+        if !is_supported && !version.is_reserved() {
+            return Err(UnsupportedVersionError { version });
+        }
+
+        Ok(Self {
+            quic_version: Some(version.as_u32()),
+            ..self
+        })
+    }
+
+    /// Selects a version the server advertised in a Version Negotiation packet
+    fn with_negotiated_version(self, quic_version: u32) -> Self {
+        Self {
+            quic_version: Some(quic_version),
+            version_negotiation_retries: self.version_negotiation_retries.saturating_add(1),
+            ..self
+        }
+    }
 }
 
+/// The version passed to [`Connect::with_quic_version`] can't be used by the client
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnsupportedVersionError {
+    pub version: Version,
+}
+
+impl fmt::Display for UnsupportedVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "QUIC version {} isn't supported", self.version)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnsupportedVersionError {}
+
 /// Make it easy for applications to create a connection attempt without importing the `Connect` struct
 impl<T: Into<SocketAddress>> From<T> for Connect {
     fn from(addr: T) -> Self {
@@ -79,6 +132,9 @@ pub(crate) struct Request {
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct Attempt {
     state: AttemptState,
+    /// The connection parameters of the current attempt, used to restart it after version
+    /// negotiation
+    connect: Connect,
 }
 
 impl Attempt {
@@ -91,7 +147,17 @@ impl Attempt {
     /// * The attempt returns a `Self` while holding on to the oneshot receiver
     /// * The application polls the `Attempt` until either a successful `Connection` or `connection::Error` is
     ///   received over the oneshot receiver.
+    /// * If the server doesn't support the selected version, the `Attempt` restarts the flow with
+    ///   a compatible version. The endpoint rejects the restarted attempt with the same error once
+    ///   the connection limits' `max_version_negotiation_retries` is exceeded.
     pub(crate) fn new(opener: &ConnectorSender, connect: Connect) -> Self {
+        Self {
+            state: Self::connect_state(opener.clone(), connect.clone()),
+            connect,
+        }
+    }
+
+    fn connect_state(opener: ConnectorSender, connect: Connect) -> AttemptState {
         // open a oneshot channel to receive the connection or error after the endpoint attempted the handshake
         let (response, receiver) = oneshot::channel();
         // The request includes both the connection info and response onshot channel
@@ -99,9 +165,7 @@ impl Attempt {
             connect,
             sender: response,
         };
-        Self {
-            state: AttemptState::Connect(request, opener.clone(), receiver),
-        }
+        AttemptState::Connect(request, opener, receiver)
     }
 }
 
@@ -109,7 +173,7 @@ enum AttemptState {
     /// The attempt is currently waiting for capacity in the `ConnectorSender` to make the `Request`
     Connect(Request, ConnectorSender, ConnectionReceiver),
     /// The attempt is currently waiting for a response back from the endpoint on the `ConnectionReceiver`
-    Waiting(ConnectionReceiver),
+    Waiting(ConnectionReceiver, ConnectorSender),
     /// This is an intermediate state and should not persist across calls to `poll`
     Unreachable,
 }
@@ -126,7 +190,7 @@ impl Future for Attempt {
                             match opener.try_send(request) {
                                 Ok(_) => {
                                     // transition to the waiting state
-                                    self.state = AttemptState::Waiting(response);
+                                    self.state = AttemptState::Waiting(response, opener);
                                     continue;
                                 }
                                 Err(err) if err.is_full() => {
@@ -155,15 +219,25 @@ impl Future for Attempt {
 
                     return Poll::Pending;
                 }
-                AttemptState::Waiting(mut response) => {
+                AttemptState::Waiting(mut response, opener) => {
                     return match Pin::new(&mut response).poll(cx) {
+                        Poll::Ready(Ok(Err(connection::Error::UnsupportedVersion {
+                            compatible_version: Some(version),
+                            ..
+                        }))) if self.connect.quic_version != Some(version) => {
+                            // the server doesn't support the selected version so restart the
+                            // attempt with one that both endpoints support
+                            self.connect = self.connect.clone().with_negotiated_version(version);
+                            self.state = Self::connect_state(opener, self.connect.clone());
+                            continue;
+                        }
                         Poll::Ready(Ok(res)) => Poll::Ready(res),
                         Poll::Ready(Err(_)) => {
                             // The endpoint has closed
                             Err(connection::Error::unspecified()).into()
                         }
                         Poll::Pending => {
-                            self.state = AttemptState::Waiting(response);
+                            self.state = AttemptState::Waiting(response, opener);
                             Poll::Pending
                         }
                     };
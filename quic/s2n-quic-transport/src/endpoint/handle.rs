@@ -8,8 +8,10 @@ use crate::{
     connection::Connection,
     endpoint::{close, close::CloseHandle, connect},
 };
+use alloc::sync::Arc;
 use core::{
     pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
     task::{Context, Poll, Waker},
 };
 use futures_channel::mpsc;
@@ -17,8 +19,44 @@ use futures_core::Stream;
 
 /// Held by application. Used to accept new connections.
 pub(crate) type AcceptorReceiver = mpsc::UnboundedReceiver<Connection>;
+
 /// Held by library. Used to notify the application of newly-accepted connections.
-pub(crate) type AcceptorSender = mpsc::UnboundedSender<Connection>;
+#[derive(Debug)]
+pub(crate) struct AcceptorSender {
+    sender: mpsc::UnboundedSender<Connection>,
+    /// The number of connections which haven't been accepted by the application yet
+    pending: Arc<AtomicUsize>,
+}
+
+impl AcceptorSender {
+    /// Passes the connection to the application
+    ///
+    /// The connection is returned if the application is no longer accepting connections.
+    #[inline]
+    pub fn send(&mut self, connection: Connection) -> Result<(), Connection> {
+        self.sender
+            .unbounded_send(connection)
+            .map_err(|error| error.into_inner())?;
+        self.pending.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the number of connections which haven't been accepted by the application yet
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    #[inline]
+    pub fn close_channel(&self) {
+        self.sender.close_channel()
+    }
+}
 
 /// Held by library. Used to receive connection attempts from the application.
 pub(crate) type ConnectorReceiver = mpsc::Receiver<connect::Request>;
@@ -43,6 +81,11 @@ impl Handle {
         max_opening_connections: usize,
     ) -> (Self, AcceptorSender, ConnectorReceiver, CloseHandle) {
         let (acceptor_sender, acceptor_receiver) = mpsc::unbounded();
+        let pending_accepts = Arc::new(AtomicUsize::new(0));
+        let acceptor_sender = AcceptorSender {
+            sender: acceptor_sender,
+            pending: pending_accepts.clone(),
+        };
         let (connector_sender, connector_receiver) = mpsc::channel(max_opening_connections);
 
         let (close_sender, close_receiver) = mpsc::channel(max_opening_connections);
@@ -52,6 +95,7 @@ impl Handle {
         let handle = Self {
             acceptor: Acceptor {
                 acceptor: acceptor_receiver,
+                pending: pending_accepts,
             },
             connector: Connector {
                 connector: connector_sender,
@@ -70,6 +114,7 @@ impl Handle {
 #[derive(Debug)]
 pub struct Acceptor {
     acceptor: AcceptorReceiver,
+    pending: Arc<AtomicUsize>,
 }
 
 impl Acceptor {
@@ -87,7 +132,10 @@ impl Acceptor {
     ///   the method will yield a different result.
     pub fn poll_accept(&mut self, context: &mut Context) -> Poll<Option<Connection>> {
         match Stream::poll_next(Pin::new(&mut self.acceptor), context) {
-            Poll::Ready(Some(connection)) => Poll::Ready(Some(connection)),
+            Poll::Ready(Some(connection)) => {
+                self.pending.fetch_sub(1, Ordering::Relaxed);
+                Poll::Ready(Some(connection))
+            }
             Poll::Ready(None) => Poll::Ready(None),
             Poll::Pending => Poll::Pending,
         }
@@ -10,7 +10,7 @@ use crate::{
     },
     endpoint,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{new_token, PacketSpaceManager},
 };
 use core::convert::TryInto;
 use s2n_codec::DecoderBufferMut;
@@ -22,6 +22,7 @@ use s2n_quic_core::{
     packet::initial::ProtectedInitial,
     path::Handle as _,
     stateless_reset::token::Generator as _,
+    token::{self, Format as _},
     transport::{self, parameters::ServerTransportParameters},
 };
 
@@ -245,7 +246,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             &mut event_context,
         );
 
-        let space_manager = PacketSpaceManager::new(
+        let mut space_manager = PacketSpaceManager::new(
             original_destination_connection_id,
             tls_session,
             initial_key,
@@ -254,6 +255,29 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             &mut publisher,
         );
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# The server uses the NEW_TOKEN frame (Section 19.7) to provide the
+        //# client with an address validation token that can be used to validate
+        //# future connections.
+        let token_len = <Config::TokenFormat as token::Format>::TOKEN_LEN;
+        if token_len > 0 {
+            let mut token = vec![0; token_len];
+            let mut context = token::Context::new(
+                &remote_address,
+                &source_connection_id,
+                endpoint_context.random_generator,
+                datagram.timestamp,
+            );
+
+            if endpoint_context
+                .token
+                .generate_new_token(&mut context, &initial_connection_id, &mut token)
+                .is_some()
+            {
+                space_manager.set_new_token_manager(new_token::Manager::sender(token));
+            }
+        }
+
         let max_mtu = self.max_mtu;
         let connection_parameters = connection::Parameters {
             internal_connection_id,
@@ -272,6 +296,8 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             quic_version,
             limits,
             max_mtu,
+            min_mtu: self.min_mtu,
+            flow_label_key: self.flow_label_key,
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
@@ -12,8 +12,9 @@ use crate::{
     },
     endpoint,
     endpoint::close::CloseHandle,
+    path::FlowLabelKey,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{new_token, PacketSpaceManager},
     wakeup_queue::WakeupQueue,
 };
 use alloc::collections::VecDeque;
@@ -37,7 +38,7 @@ use s2n_quic_core::{
     io::{rx, tx},
     packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket},
     path,
-    path::{Handle as _, MaxMtu},
+    path::{Handle as _, MaxMtu, MinMtu},
     random::Generator as _,
     stateless_reset::token::{Generator as _, LEN as StatelessResetTokenLen},
     time::{Clock, Timestamp},
@@ -59,6 +60,7 @@ mod version;
 pub use config::{Config, Context};
 pub use packet_buffer::Buffer as PacketBuffer;
 pub use s2n_quic_core::endpoint::*;
+pub(crate) use version::SUPPORTED_VERSIONS;
 
 const DEFAULT_MAX_PEERS: usize = 1024;
 
@@ -87,6 +89,15 @@ pub struct Endpoint<Cfg: Config> {
     close_packet_buffer: packet_buffer::Buffer,
     /// The largest maximum transmission unit (MTU) that can be sent on a path
     max_mtu: MaxMtu,
+    /// The smallest maximum transmission unit (MTU) that path MTU discovery will use for a path
+    min_mtu: MinMtu,
+    /// The key which the flow labels of the endpoint's paths are derived from
+    flow_label_key: FlowLabelKey,
+    /// The tokens received from servers in NEW_TOKEN frames, which are used by the client on
+    /// future connections to the same server
+    new_token_store: new_token::Store,
+    /// The time at which the endpoint started, used to observe the stateless reset quiet time
+    started_at: Option<Timestamp>,
 }
 
 impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
@@ -211,6 +222,8 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
                 if let Err(error) = conn.on_wakeup(
                     timestamp,
+                    endpoint_context.congestion_controller,
+                    endpoint_context.random_generator,
                     endpoint_context.event_subscriber,
                     endpoint_context.datagram,
                 ) {
@@ -266,6 +279,11 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         self.max_mtu = max_mtu
     }
 
+    #[inline]
+    fn set_min_mtu(&mut self, min_mtu: MinMtu) {
+        self.min_mtu = min_mtu
+    }
+
     #[inline]
     fn subscriber(&mut self) -> &mut Self::Subscriber {
         self.config.context().event_subscriber
@@ -301,6 +319,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         let connection_id_mapper =
             ConnectionIdMapper::new(config.context().random_generator, Cfg::ENDPOINT_TYPE);
+        let flow_label_key = FlowLabelKey::new(config.context().random_generator);
 
         let endpoint = Self {
             config,
@@ -315,18 +334,26 @@ impl<Cfg: Config> Endpoint<Cfg> {
             stateless_reset_dispatch: stateless_reset::Dispatch::default(),
             close_packet_buffer: Default::default(),
             max_mtu: Default::default(),
+            min_mtu: Default::default(),
+            flow_label_key,
+            new_token_store: Default::default(),
+            started_at: None,
         };
 
         (endpoint, handle)
     }
 
     /// Determine the next step when a peer attempts a connection
+    ///
+    /// `is_address_validated` is set if the peer presented a valid token from a NEW_TOKEN frame,
+    /// in which case the peer isn't asked to validate its address with a Retry packet.
     fn connection_allowed(
         &mut self,
         header: &datagram::Header<Cfg::PathHandle>,
         packet: &ProtectedInitial,
         payload_len: usize,
         timestamp: Timestamp,
+        is_address_validated: bool,
     ) -> Option<()> {
         if !self.connections.can_accept() {
             return None;
@@ -339,7 +366,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             self.connections.len(),
             &remote_address,
             timestamp.into_event(),
-        );
+        )
+        .with_pending_accepts(self.connections.pending_accepts());
 
         let context = self.config.context();
         let outcome = context.endpoint_limits.on_connection_attempt(&attempt);
@@ -354,6 +382,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         match outcome {
             Outcome::Allow { .. } => Some(()),
+            Outcome::Retry { .. } if is_address_validated => Some(()),
             Outcome::Retry { .. } => {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                 //# A server can also use a Retry packet to defer the state and
@@ -376,7 +405,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     packet,
                     local_connection_id,
                     context.random_generator,
-                    context.token
+                    context.token,
+                    timestamp,
                 );
 
                 None
@@ -533,6 +563,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             ecn: header.ecn,
             destination_connection_id,
             source_connection_id,
+            ttl: header.ttl,
+            receive_delay: header.receive_delay,
         };
 
         // TODO validate the connection ID before looking up the connection in the map
@@ -704,11 +736,18 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 //# In response to processing an Initial packet containing a token that
                 //# was provided in a Retry packet, a server cannot send another Retry
                 //# packet; it can only refuse the connection or permit it to proceed.
-                let retry_token_dcid = if !packet.token().is_empty() {
+                let token_source = if packet.token().is_empty() {
+                    None
+                } else {
+                    Some(endpoint_context.token.token_source(packet.token()))
+                };
+
+                let retry_token_dcid = if token_source == Some(token::Source::RetryPacket) {
                     let mut context = token::Context::new(
                         &remote_address,
                         &source_connection_id,
                         endpoint_context.random_generator,
+                        timestamp,
                     );
 
                     let outcome = endpoint_context
@@ -747,12 +786,41 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     //# the handshake to proceed.
                     outcome
                 } else {
+                    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+                    //# When a server receives an Initial packet with an address validation
+                    //# token, it MUST attempt to validate the token, unless it has already
+                    //# completed address validation.
+                    let is_address_validated = token_source == Some(token::Source::NewTokenFrame)
+                        && {
+                            let mut context = token::Context::new(
+                                &remote_address,
+                                &source_connection_id,
+                                endpoint_context.random_generator,
+                                timestamp,
+                            );
+
+                            endpoint_context
+                                .token
+                                .validate_new_token(&mut context, packet.token())
+                        };
+
+                    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+                    //# If the token is invalid, then the
+                    //# server SHOULD proceed as if the client did not have a validated
+                    //# address, including potentially sending a Retry packet.
+
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                     //# Upon receiving the client's Initial packet, the server can request
                     //# address validation by sending a Retry packet (Section 17.2.5)
                     //# containing a token.
                     if self
-                        .connection_allowed(header, &packet, payload_len, timestamp)
+                        .connection_allowed(
+                            header,
+                            &packet,
+                            payload_len,
+                            timestamp,
+                            is_address_validated,
+                        )
                         .is_none()
                     {
                         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.1
@@ -822,6 +890,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 if !is_stateless_reset
                     && Cfg::StatelessResetTokenGenerator::ENABLED
                     && is_short_header_packet
+                    && !self.is_quiet(timestamp)
                 {
                     self.enqueue_stateless_reset(header, datagram, &destination_connection_id);
                 }
@@ -829,6 +898,19 @@ impl<Cfg: Config> Endpoint<Cfg> {
         }
     }
 
+    /// Returns `true` if the endpoint is still within the quiet time after starting, during which
+    /// no stateless resets are sent
+    fn is_quiet(&mut self, timestamp: Timestamp) -> bool {
+        let started_at = *self.started_at.get_or_insert(timestamp);
+        let quiet_time = self
+            .config
+            .context()
+            .stateless_reset_token_generator
+            .quiet_time();
+
+        timestamp.saturating_duration_since(started_at) < quiet_time
+    }
+
     /// Enqueues sending a stateless reset to a peer.
     ///
     /// Sending the reset was caused through the passed `datagram`.
@@ -916,6 +998,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
     }
 
     fn on_timeout(&mut self, timestamp: Timestamp) {
+        self.started_at.get_or_insert(timestamp);
+
         let connection_id_mapper = &mut self.connection_id_mapper;
         let close_packet_buffer = &mut self.close_packet_buffer;
         let endpoint_context = self.config.context();
@@ -968,10 +1052,26 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 endpoint::connect::Connect {
                     remote_address,
                     server_name: hostname,
+                    quic_version,
+                    version_negotiation_retries,
                 },
             sender,
         } = request;
 
+        let limits = self
+            .config
+            .context()
+            .connection_limits
+            .on_connection(&LimitsInfo::new(&remote_address));
+
+        if version_negotiation_retries > limits.max_version_negotiation_retries() {
+            // The attempt was restarted with the version the server advertised too many times, so
+            // return the version negotiation error to the application instead.
+            let error = connection::Error::unsupported_version(quic_version);
+            let _ = sender.send(Err(error));
+            return Ok(());
+        }
+
         let internal_connection_id = self.connection_id_generator.generate_id();
         let local_connection_id = self
             .config
@@ -1040,7 +1140,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-15
         //# This version of the specification is identified by the number
         //# 0x00000001.
-        let quic_version = 0x00000001;
+        let quic_version = quic_version.unwrap_or(0x00000001);
 
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Cfg::ENDPOINT_TYPE,
@@ -1068,9 +1168,6 @@ impl<Cfg: Config> Endpoint<Cfg> {
             initial_source_connection_id: Some(local_connection_id.into()),
             ..Default::default()
         };
-        let limits = endpoint_context
-            .connection_limits
-            .on_connection(&LimitsInfo::new(&remote_address));
         transport_parameters.load_limits(&limits);
 
         transport_parameters.max_datagram_frame_size = endpoint_context
@@ -1104,7 +1201,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 &transport_parameters,
                 hostname.expect("application should provide a valid server name"),
             );
-        let space_manager = PacketSpaceManager::new(
+        let mut space_manager = PacketSpaceManager::new(
             original_destination_connection_id,
             tls_session,
             initial_key,
@@ -1113,6 +1210,29 @@ impl<Cfg: Config> Endpoint<Cfg> {
             &mut publisher,
         );
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# When connecting to a server for
+        //# which the client retains an applicable and unused token, it SHOULD
+        //# include that token in the Token field of its Initial packet.
+        if let Some(token) = self.new_token_store.take(&remote_address) {
+            if let Some((initial, _)) = space_manager.initial_mut() {
+                initial.set_new_token(token);
+            }
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A client MUST NOT include
+        //# a token that is not applicable to the server that it is connecting
+        //# to, unless the client has the knowledge that the server that issued
+        //# the token and the server the client is connecting to are jointly
+        //# managing the tokens.
+        //
+        // Tokens are stored for the address of the server which issued them.
+        space_manager.set_new_token_manager(new_token::Manager::receiver(
+            self.new_token_store.clone(),
+            *remote_address,
+        ));
+
         let wakeup_handle = self
             .wakeup_queue
             .create_wakeup_handle(internal_connection_id);
@@ -1136,6 +1256,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             quic_version,
             limits,
             max_mtu: self.max_mtu,
+            min_mtu: self.min_mtu,
+            flow_label_key: self.flow_label_key,
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
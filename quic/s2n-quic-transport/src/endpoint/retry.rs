@@ -42,6 +42,7 @@ impl<Path: path::Handle> Dispatch<Path> {
         local_connection_id: connection::LocalId,
         random: &mut dyn random::Generator,
         token_format: &mut T,
+        timestamp: time::Timestamp,
     ) {
         if let Some(transmission) = Transmission::new::<_, C>(
             path_handle,
@@ -49,6 +50,7 @@ impl<Path: path::Handle> Dispatch<Path> {
             local_connection_id,
             random,
             token_format,
+            timestamp,
         ) {
             self.transmissions.push_back(transmission);
         }
@@ -106,6 +108,7 @@ impl<Path: path::Handle> Transmission<Path> {
         local_connection_id: connection::LocalId,
         random: &mut dyn random::Generator,
         token_format: &mut T,
+        timestamp: time::Timestamp,
     ) -> Option<Self> {
         let mut packet_buf = [0u8; MINIMUM_MTU as usize];
         let packet_range = packet::retry::Retry::encode_packet::<_, C>(
@@ -114,6 +117,7 @@ impl<Path: path::Handle> Transmission<Path> {
             &local_connection_id,
             random,
             token_format,
+            timestamp,
             &mut packet_buf,
         )?;
 
@@ -23,7 +23,7 @@ pub struct Negotiator<Config: endpoint::Config> {
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Error;
 
-const SUPPORTED_VERSIONS: &[u32] = &[
+pub(crate) const SUPPORTED_VERSIONS: &[u32] = &[
     0x1, // Draft 34 / Version 1 (https://github.com/quicwg/base-drafts/wiki/21st-Implementation-Draft)
 ];
 
@@ -340,6 +340,8 @@ mod tests {
                 ecn: Default::default(),
                 destination_connection_id: connection::LocalId::TEST_ID,
                 source_connection_id: None,
+                ttl: None,
+                receive_delay: None,
             },
         )
     }
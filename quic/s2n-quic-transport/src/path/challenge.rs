@@ -17,6 +17,8 @@ pub struct Challenge {
     abandon_duration: Duration,
     abandon_timer: Timer,
     data: Data,
+    /// The time at which the first PATH_CHALLENGE was sent
+    sent_at: Option<Timestamp>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -68,6 +70,7 @@ impl Challenge {
             abandon_duration,
             abandon_timer: Timer::default(),
             data,
+            sent_at: None,
         }
     }
 
@@ -77,6 +80,7 @@ impl Challenge {
             abandon_duration: Duration::ZERO,
             abandon_timer: Timer::default(),
             data: DISABLED_DATA,
+            sent_at: None,
         }
     }
 
@@ -95,8 +99,9 @@ impl Challenge {
                     self.state = State::RequiresTransmission(remaining);
 
                     if !self.abandon_timer.is_armed() {
-                        self.abandon_timer
-                            .set(context.current_time() + self.abandon_duration);
+                        let now = context.current_time();
+                        self.abandon_timer.set(now + self.abandon_duration);
+                        self.sent_at = Some(now);
                     }
                 }
             }
@@ -131,6 +136,10 @@ impl Challenge {
         }
     }
 
+    pub fn is_abandoned(&self) -> bool {
+        matches!(self.state, State::Abandoned)
+    }
+
     pub fn is_disabled(&self) -> bool {
         matches!(self.state, State::InitialPathDisabled)
     }
@@ -154,6 +163,11 @@ impl Challenge {
     pub fn challenge_data(&self) -> &[u8] {
         &self.data
     }
+
+    /// Returns the time at which the first PATH_CHALLENGE was sent
+    pub fn sent_at(&self) -> Option<Timestamp> {
+        self.sent_at
+    }
 }
 
 impl timer::Provider for Challenge {
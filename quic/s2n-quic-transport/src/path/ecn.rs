@@ -49,8 +49,8 @@ enum State {
     Testing(u8),
     // ECN capability has been tested, but not validated yet
     Unknown,
-    // ECN validation has failed. Validation will be restarted based on the timer
-    Failed(Timer),
+    // ECN validation has failed for the given reason. Validation will be restarted based on the timer
+    Failed(Timer, FailureReason),
     // ECN validation has succeeded. CE suppression will be tested based on the timer.
     Capable(Timer),
 }
@@ -61,12 +61,44 @@ impl IntoEvent<event::builder::EcnState> for &State {
         match self {
             State::Testing(_) => event::builder::EcnState::Testing,
             State::Unknown => event::builder::EcnState::Unknown,
-            State::Failed(_) => event::builder::EcnState::Failed,
+            State::Failed(_, reason) => event::builder::EcnState::Failed {
+                reason: reason.into_event(),
+            },
             State::Capable(_) => event::builder::EcnState::Capable,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FailureReason {
+    // ECN marked packets were acknowledged without ECN counts in the Ack frame
+    CountsMissing,
+    // The ECN counts in the Ack frame decreased from the baseline
+    CountsDecreased,
+    // ECT(0) marked packets were acknowledged without a matching increase in the ECT(0) or CE counts
+    Ect0Underreported,
+    // The ECT(0) or ECT(1) counts increased by more than the packets sent with each codepoint
+    EctOverreported,
+    // An ECN-CE marked packet was acknowledged without an increase in the CE count
+    CeSuppressed,
+    // Too many ECN marked packets were lost since the last one was acknowledged
+    BlackHole,
+}
+
+impl IntoEvent<event::builder::EcnFailureReason> for FailureReason {
+    #[inline]
+    fn into_event(self) -> event::builder::EcnFailureReason {
+        match self {
+            FailureReason::CountsMissing => event::builder::EcnFailureReason::CountsMissing,
+            FailureReason::CountsDecreased => event::builder::EcnFailureReason::CountsDecreased,
+            FailureReason::Ect0Underreported => event::builder::EcnFailureReason::Ect0Underreported,
+            FailureReason::EctOverreported => event::builder::EcnFailureReason::EctOverreported,
+            FailureReason::CeSuppressed => event::builder::EcnFailureReason::CeSuppressed,
+            FailureReason::BlackHole => event::builder::EcnFailureReason::BlackHole,
+        }
+    }
+}
+
 impl Default for State {
     fn default() -> Self {
         State::Testing(0)
@@ -76,6 +108,9 @@ impl Default for State {
 #[derive(Clone, Debug, Default)]
 pub struct Controller {
     state: State,
+    // Set when the application disabled ECN. Packets are never marked and validation
+    // isn't performed.
+    disabled: bool,
     // A count of the number of packets with ECN marking lost since
     // the last time a packet with ECN marking was acknowledged.
     black_hole_counter: Counter<u8, Saturating>,
@@ -85,12 +120,26 @@ pub struct Controller {
 }
 
 impl Controller {
+    /// Disables ECN on the path
+    pub fn disable(&mut self) {
+        self.disabled = true;
+    }
+
+    /// Returns true if ECN was not disabled by the application
+    pub fn is_enabled(&self) -> bool {
+        !self.disabled
+    }
+
     /// Restart testing of ECN capability
     pub fn restart<Pub: event::ConnectionPublisher>(
         &mut self,
         path: event::builder::Path,
         publisher: &mut Pub,
     ) {
+        if self.disabled {
+            return;
+        }
+
         if self.state != State::Testing(0) {
             self.change_state(State::Testing(0), path, publisher);
         }
@@ -106,8 +155,12 @@ impl Controller {
         rtt: Duration,
         publisher: &mut Pub,
     ) {
+        if self.disabled {
+            return;
+        }
+
         match self.state {
-            State::Failed(ref mut retest_timer) => {
+            State::Failed(ref mut retest_timer, _) => {
                 if retest_timer.poll_expiration(now).is_ready() {
                     self.restart(path, publisher);
                 }
@@ -126,6 +179,10 @@ impl Controller {
         transmission_mode: transmission::Mode,
         now: Timestamp,
     ) -> ExplicitCongestionNotification {
+        if self.disabled {
+            return ExplicitCongestionNotification::NotEct;
+        }
+
         if transmission_mode.is_loss_recovery_probing() {
             // Don't mark loss recovery probes as ECN capable in case the ECN
             // marking is causing packet loss
@@ -156,7 +213,7 @@ impl Controller {
             //# If validation fails, then the endpoint MUST disable ECN. It stops setting the ECT
             //# codepoint in IP packets that it sends, assuming that either the network path or
             //# the peer does not support ECN.
-            State::Failed(_) | State::Unknown => ExplicitCongestionNotification::NotEct,
+            State::Failed(..) | State::Unknown => ExplicitCongestionNotification::NotEct,
         }
     }
 
@@ -214,8 +271,8 @@ impl Controller {
         path: event::builder::Path,
         publisher: &mut Pub,
     ) -> ValidationOutcome {
-        if matches!(self.state, State::Failed(_)) {
-            // Validation had already failed
+        if self.disabled || matches!(self.state, State::Failed(..)) {
+            // ECN isn't used or validation had already failed
             return ValidationOutcome::Skipped;
         }
 
@@ -227,7 +284,7 @@ impl Controller {
                 //# corresponding ECN counts are not present in the ACK frame. This check
                 //# detects a network element that zeroes the ECN field or a peer that does
                 //# not report ECN markings.
-                self.fail(now, path, publisher);
+                self.fail(FailureReason::CountsMissing, now, path, publisher);
                 return ValidationOutcome::Failed;
             }
 
@@ -242,11 +299,19 @@ impl Controller {
                 .unwrap_or_default()
                 .checked_sub(baseline_ecn_counts)
         {
-            if Self::ce_remarking(incremental_ecn_counts, newly_acked_ecn_counts)
-                || Self::remarked_to_ect0_or_ect1(incremental_ecn_counts, sent_packet_ecn_counts)
-                || Self::ce_suppression(incremental_ecn_counts, newly_acked_ecn_counts)
+            let failure = if Self::ce_remarking(incremental_ecn_counts, newly_acked_ecn_counts) {
+                Some(FailureReason::Ect0Underreported)
+            } else if Self::remarked_to_ect0_or_ect1(incremental_ecn_counts, sent_packet_ecn_counts)
             {
-                self.fail(now, path, publisher);
+                Some(FailureReason::EctOverreported)
+            } else if Self::ce_suppression(incremental_ecn_counts, newly_acked_ecn_counts) {
+                Some(FailureReason::CeSuppressed)
+            } else {
+                None
+            };
+
+            if let Some(reason) = failure {
+                self.fail(reason, now, path, publisher);
                 return ValidationOutcome::Failed;
             }
 
@@ -254,7 +319,7 @@ impl Controller {
             incremental_ecn_counts.ce_count - newly_acked_ecn_counts.ce_count
         } else {
             // ECN counts decreased from the baseline
-            self.fail(now, path, publisher);
+            self.fail(FailureReason::CountsDecreased, now, path, publisher);
             return ValidationOutcome::Failed;
         };
 
@@ -365,7 +430,7 @@ impl Controller {
         path: event::builder::Path,
         publisher: &mut Pub,
     ) {
-        if matches!(self.state, State::Failed(_)) {
+        if matches!(self.state, State::Failed(..)) {
             return;
         }
 
@@ -376,7 +441,7 @@ impl Controller {
         }
 
         if self.black_hole_counter > TESTING_PACKET_THRESHOLD {
-            self.fail(now, path, publisher);
+            self.fail(FailureReason::BlackHole, now, path, publisher);
         }
     }
 
@@ -394,9 +459,10 @@ impl Controller {
                 .map_or(true, |last_acked| last_acked < time_sent)
     }
 
-    /// Set the state to Failed and arm the retest timer
+    /// Set the state to Failed with the given reason and arm the retest timer
     fn fail<Pub: event::ConnectionPublisher>(
         &mut self,
+        reason: FailureReason,
         now: Timestamp,
         path: event::builder::Path,
         publisher: &mut Pub,
@@ -406,7 +472,7 @@ impl Controller {
         //# time in the connection. An endpoint could continue to periodically attempt validation.
         let mut retest_timer = Timer::default();
         retest_timer.set(now + RETEST_COOL_OFF_DURATION);
-        self.change_state(State::Failed(retest_timer), path, publisher);
+        self.change_state(State::Failed(retest_timer, reason), path, publisher);
         self.black_hole_counter = Default::default();
     }
 
@@ -430,7 +496,7 @@ impl Controller {
 impl timer::Provider for Controller {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
-        if let State::Failed(timer) = &self.state {
+        if let State::Failed(timer, _) = &self.state {
             timer.timers(query)?
         }
         // The ce suppression timer in State::Capable is not queried here as that
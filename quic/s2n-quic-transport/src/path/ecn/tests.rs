@@ -29,7 +29,7 @@ fn default() {
 fn restart() {
     let mut publisher = Publisher::snapshot();
     let mut controller = Controller {
-        state: State::Failed(Timer::default()),
+        state: State::Failed(Timer::default(), FailureReason::BlackHole),
         ..Default::default()
     };
     controller.black_hole_counter += 1;
@@ -60,9 +60,9 @@ fn on_timeout_failed() {
     let mut publisher = Publisher::snapshot();
     let mut controller = Controller::default();
     let now = time::now();
-    controller.fail(now, Path::test(), &mut publisher);
+    controller.fail(FailureReason::BlackHole, now, Path::test(), &mut publisher);
 
-    if let State::Failed(timer) = &controller.state {
+    if let State::Failed(timer, _) = &controller.state {
         assert!(timer.is_armed());
     } else {
         panic!("State should be Failed");
@@ -81,7 +81,7 @@ fn on_timeout_failed() {
         &mut publisher,
     );
 
-    if let State::Failed(timer) = &controller.state {
+    if let State::Failed(timer, _) = &controller.state {
         assert!(timer.is_armed());
     } else {
         panic!("State should be Failed");
@@ -183,7 +183,12 @@ fn ecn() {
         //# If validation fails, then the endpoint MUST disable ECN. It stops setting the ECT
         //# codepoint in IP packets that it sends, assuming that either the network path or
         //# the peer does not support ECN.
-        controller.fail(time::now(), Path::test(), &mut publisher);
+        controller.fail(
+            FailureReason::BlackHole,
+            time::now(),
+            Path::test(),
+            &mut publisher,
+        );
         assert!(!controller.ecn(transmission_mode, now).using_ecn());
 
         controller.state = State::Unknown;
@@ -225,7 +230,7 @@ fn ecn_loss_recovery_probing() {
         State::Capable(Timer::default()),
         State::Testing(0),
         State::Unknown,
-        State::Failed(Timer::default()),
+        State::Failed(Timer::default(), FailureReason::BlackHole),
     ] {
         let mut controller = Controller {
             state,
@@ -242,7 +247,7 @@ fn is_capable() {
     for state in vec![
         State::Testing(0),
         State::Unknown,
-        State::Failed(Timer::default()),
+        State::Failed(Timer::default(), FailureReason::BlackHole),
     ] {
         let controller = Controller {
             state,
@@ -258,12 +263,58 @@ fn is_capable() {
     assert!(controller.is_capable());
 }
 
+#[test]
+fn disabled() {
+    let mut publisher = Publisher::snapshot();
+    let mut controller = Controller::default();
+    let now = time::now();
+    assert!(controller.is_enabled());
+
+    controller.disable();
+    assert!(!controller.is_enabled());
+
+    // packets are never marked
+    for mode in [
+        transmission::Mode::Normal,
+        transmission::Mode::MtuProbing,
+        transmission::Mode::PathValidationOnly,
+    ] {
+        assert_eq!(
+            ExplicitCongestionNotification::NotEct,
+            controller.ecn(mode, now)
+        );
+    }
+
+    // validation is skipped, even if the ECN counts would fail it
+    let outcome = controller.validate(
+        helper_ecn_counts(1, 0, 0),
+        helper_ecn_counts(1, 0, 0),
+        EcnCounts::default(),
+        None,
+        now,
+        Duration::default(),
+        Path::test(),
+        &mut publisher,
+    );
+    assert_eq!(ValidationOutcome::Skipped, outcome);
+    assert_eq!(State::Testing(0), controller.state);
+
+    // restarting doesn't enable it again
+    controller.state = State::Failed(Timer::default(), FailureReason::BlackHole);
+    controller.restart(Path::test(), &mut publisher);
+    assert_eq!(
+        State::Failed(Timer::default(), FailureReason::BlackHole),
+        controller.state
+    );
+    assert!(!controller.is_enabled());
+}
+
 #[test]
 fn validate_already_failed() {
     let mut publisher = Publisher::snapshot();
     let mut controller = Controller::default();
     let now = time::now();
-    controller.fail(now, Path::test(), &mut publisher);
+    controller.fail(FailureReason::BlackHole, now, Path::test(), &mut publisher);
     let outcome = controller.validate(
         EcnCounts::default(),
         EcnCounts::default(),
@@ -275,7 +326,7 @@ fn validate_already_failed() {
         &mut publisher,
     );
 
-    if let State::Failed(timer) = &controller.state {
+    if let State::Failed(timer, _) = &controller.state {
         assert!(timer.is_armed());
         assert_eq!(
             controller.next_expiration(),
@@ -312,7 +363,10 @@ fn validate_ecn_counts_not_in_ack() {
     );
 
     assert_eq!(ValidationOutcome::Failed, outcome);
-    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(matches!(
+        controller.state,
+        State::Failed(_, FailureReason::CountsMissing)
+    ));
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
@@ -339,7 +393,10 @@ fn validate_ecn_ce_remarking() {
     );
 
     assert_eq!(ValidationOutcome::Failed, outcome);
-    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(matches!(
+        controller.state,
+        State::Failed(_, FailureReason::Ect0Underreported)
+    ));
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
@@ -366,7 +423,10 @@ fn validate_ect_0_remarking() {
     );
 
     assert_eq!(ValidationOutcome::Failed, outcome);
-    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(matches!(
+        controller.state,
+        State::Failed(_, FailureReason::EctOverreported)
+    ));
 }
 
 #[test]
@@ -390,7 +450,10 @@ fn validate_ect_0_remarking_after_restart() {
     );
 
     assert_eq!(ValidationOutcome::Failed, outcome);
-    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(matches!(
+        controller.state,
+        State::Failed(_, FailureReason::Ect0Underreported)
+    ));
 }
 
 #[test]
@@ -434,7 +497,10 @@ fn validate_ecn_decrease() {
     );
 
     assert_eq!(ValidationOutcome::Failed, outcome);
-    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(matches!(
+        controller.state,
+        State::Failed(_, FailureReason::CountsDecreased)
+    ));
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9000#appendix-A.4
@@ -494,7 +560,10 @@ fn validate_ce_suppression_remarked_to_not_ect() {
     );
 
     assert_eq!(ValidationOutcome::Failed, outcome);
-    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(matches!(
+        controller.state,
+        State::Failed(_, FailureReason::CeSuppressed)
+    ));
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9002#section-8.3
@@ -526,7 +595,10 @@ fn validate_ce_suppression_remarked_to_ect0() {
     );
 
     assert_eq!(ValidationOutcome::Failed, outcome);
-    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(matches!(
+        controller.state,
+        State::Failed(_, FailureReason::EctOverreported)
+    ));
 }
 
 #[test]
@@ -640,7 +712,7 @@ fn validate_capable_not_in_unknown_state() {
     for state in vec![
         State::Testing(0),
         State::Capable(Timer::default()),
-        State::Failed(Timer::default()),
+        State::Failed(Timer::default(), FailureReason::BlackHole),
     ] {
         let mut controller = Controller {
             state,
@@ -782,7 +854,7 @@ fn on_packet_loss() {
 
         for i in 0..TESTING_PACKET_THRESHOLD + 1 {
             assert_eq!(i, *controller.black_hole_counter.deref());
-            assert!(!matches!(controller.state, State::Failed(_)));
+            assert!(!matches!(controller.state, State::Failed(..)));
             controller.on_packet_loss(
                 time_sent,
                 ExplicitCongestionNotification::Ect0,
@@ -792,7 +864,8 @@ fn on_packet_loss() {
             );
         }
 
-        if let State::Failed(timer) = &controller.state {
+        if let State::Failed(timer, reason) = &controller.state {
+            assert_eq!(FailureReason::BlackHole, *reason);
             assert!(timer.is_armed());
             assert_eq!(
                 Some(time_sent + RETEST_COOL_OFF_DURATION),
@@ -814,11 +887,11 @@ fn on_packet_loss_already_failed() {
     let time_sent = now + Duration::from_secs(1);
 
     controller.last_acked_ecn_packet_timestamp = Some(now);
-    controller.fail(now, Path::test(), &mut publisher);
+    controller.fail(FailureReason::BlackHole, now, Path::test(), &mut publisher);
 
     for _i in 0..TESTING_PACKET_THRESHOLD + 1 {
         assert_eq!(0, *controller.black_hole_counter.deref());
-        assert!(matches!(controller.state, State::Failed(_)));
+        assert!(matches!(controller.state, State::Failed(..)));
         controller.on_packet_loss(
             time_sent,
             ExplicitCongestionNotification::Ect0,
@@ -828,7 +901,7 @@ fn on_packet_loss_already_failed() {
         );
     }
 
-    if let State::Failed(timer) = &controller.state {
+    if let State::Failed(timer, _) = &controller.state {
         assert!(timer.is_armed());
         assert_eq!(
             Some(now + RETEST_COOL_OFF_DURATION),
@@ -874,7 +947,7 @@ fn fuzz_validate() {
                         ExplicitCongestionNotification::NotEct,
                         controller.ecn(transmission::Mode::Normal, now)
                     );
-                    assert!(matches!(controller.state, State::Failed(_)));
+                    assert!(matches!(controller.state, State::Failed(..)));
                 }
             },
         );
@@ -6,9 +6,10 @@
 use crate::{
     connection::PeerIdRegistry,
     endpoint, path,
-    path::{challenge, Path},
+    path::{challenge, probe::Probe, Path},
     transmission,
 };
+use core::task::{Context, Poll};
 use s2n_quic_core::{
     ack,
     connection::{self, PeerId},
@@ -19,7 +20,7 @@ use s2n_quic_core::{
     packet::number::PacketNumberSpace,
     path::{
         migration::{self, Validator as _},
-        Handle as _, Id, MaxMtu,
+        Handle as _, Id, MaxMtu, PathError, PathProbe,
     },
     random,
     recovery::{
@@ -68,6 +69,18 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// A path which the application is probing without migrating to it
+    probe: Probe<Config::PathHandle>,
+
+    /// The maximum number of paths which can have a PATH_CHALLENGE pending at the same time
+    max_validating_paths: u8,
+
+    /// A validated path which the application requested the connection to migrate to
+    pending_migration: Option<u8>,
+
+    /// A path with a new local address which the application requested the connection to send on
+    pending_local_address: Option<Config::PathHandle>,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -78,12 +91,25 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            probe: Probe::default(),
+            max_validating_paths: MAX_ALLOWED_PATHS as u8,
+            pending_migration: None,
+            pending_local_address: None,
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
         manager
     }
 
+    /// Limits the number of paths which can be validated at the same time
+    ///
+    /// New paths are rejected while `max_validating_paths` paths have a pending PATH_CHALLENGE.
+    #[inline]
+    pub fn with_max_validating_paths(mut self, max_validating_paths: u8) -> Self {
+        self.max_validating_paths = max_validating_paths;
+        self
+    }
+
     /// Update the active path
     fn update_active_path<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -351,6 +377,11 @@ impl<Config: endpoint::Config> Manager<Config> {
             }
         }
 
+        // Reject the new path instead of abandoning validations that are already in progress
+        if !self.can_validate_new_path() {
+            return Err(DatagramDropReason::PathLimitExceeded);
+        }
+
         // Determine which index will be used for the newly created path
         //
         // If a previously allocated path failed to contain an authenticated packet, we
@@ -389,7 +420,10 @@ impl<Config: endpoint::Config> Manager<Config> {
         // estimator for the new path, and they are initialized with initial values,
         // we do not need to reset congestion controller and round-trip time estimator
         // again on confirming the peer's ownership of its new address.
-        let rtt = RttEstimator::new(self.active_path().rtt_estimator.max_ack_delay());
+        let rtt = RttEstimator::new_with_initial_rtt(
+            self.active_path().rtt_estimator.max_ack_delay(),
+            self.active_path().rtt_estimator.initial_rtt(),
+        );
         let path_info = congestion_controller::PathInfo::new(&remote_address);
         let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
 
@@ -434,7 +468,18 @@ impl<Config: endpoint::Config> Manager<Config> {
             cc,
             true,
             max_mtu,
+            self.active_path().flow_label_key,
         );
+        path.set_min_mtu(
+            self.active_path().mtu_controller.min_mtu(),
+            new_path_id,
+            publisher,
+        );
+
+        // new paths inherit the application's ECN preference
+        if !self.active_path().ecn_controller.is_enabled() {
+            path.ecn_controller.disable();
+        }
 
         let unblocked = path.on_bytes_received(datagram.payload_len);
 
@@ -550,6 +595,7 @@ impl<Config: endpoint::Config> Manager<Config> {
     pub fn on_path_response<Pub: event::ConnectionPublisher>(
         &mut self,
         response: &frame::PathResponse,
+        timestamp: Timestamp,
         publisher: &mut Pub,
     ) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.2
@@ -583,11 +629,373 @@ impl<Config: endpoint::Config> Manager<Config> {
                 if path.is_activated() {
                     self.last_known_active_validated_path = Some(id as u8);
                 }
+
+                let rtt = path
+                    .challenge
+                    .sent_at()
+                    .map_or(Default::default(), |sent_at| {
+                        timestamp.saturating_duration_since(sent_at)
+                    });
+                self.probe.on_validated(
+                    path_id(id as u8),
+                    PathProbe {
+                        local_address: *path.local_address(),
+                        remote_address: *path.remote_address(),
+                        rtt,
+                    },
+                );
                 break;
             }
         }
     }
 
+    /// Requests the path for the given handle to be validated without migrating to it
+    ///
+    /// The path is created and challenged in [`Self::start_probe`], since that requires
+    /// resources owned by the endpoint.
+    pub fn request_probe(&mut self, handle: Config::PathHandle) -> Result<(), PathError> {
+        if self.probe.is_in_progress() {
+            return Err(PathError::ProbeInProgress);
+        }
+
+        self.check_local_port(&handle)?;
+
+        let is_validating = match self.path(&handle) {
+            Some((id, _path)) if id == self.active_path_id() => {
+                return Err(PathError::ActivePath);
+            }
+            // the existing path is challenged again
+            Some((_id, path)) => path.is_challenge_pending(),
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+            //# Clients are responsible for initiating all migrations.
+            None if Config::ENDPOINT_TYPE.is_server() => {
+                return Err(PathError::UnknownPath);
+            }
+            None if self.new_path_idx() >= MAX_ALLOWED_PATHS => {
+                return Err(PathError::PathLimitExceeded);
+            }
+            None => false,
+        };
+
+        if !is_validating && !self.can_validate_new_path() {
+            return Err(PathError::ValidationLimitExceeded);
+        }
+
+        self.probe.on_request(handle);
+
+        Ok(())
+    }
+
+    /// Ensures the IO provider can send from the local address of the given handle
+    ///
+    /// The IO providers send every path through the socket the endpoint is bound to, so a path
+    /// can only use a different local IP than the active path.
+    fn check_local_port(&self, handle: &Config::PathHandle) -> Result<(), PathError> {
+        if handle.local_address().port() != self.active_path().handle.local_address().port() {
+            return Err(PathError::UnsupportedLocalPort);
+        }
+
+        Ok(())
+    }
+
+    /// Creates the path for a requested probe and starts sending PATH_CHALLENGE frames on it
+    pub fn start_probe<Pub: event::ConnectionPublisher>(
+        &mut self,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) {
+        let handle = if let Some(handle) = self.probe.requested() {
+            handle
+        } else {
+            return;
+        };
+
+        let probe_path_id = if let Some((id, _path)) = self.path(&handle) {
+            id
+        } else {
+            match self.create_probe_path(handle, congestion_controller_endpoint, publisher) {
+                Ok(id) => id,
+                Err(error) => {
+                    self.probe.on_complete(Err(error));
+                    return;
+                }
+            }
+        };
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.1
+        //# An endpoint MAY probe for peer reachability from a new local address
+        //# using path validation (Section 8.2) prior to migrating the connection
+        //# to the new local address.
+        self.set_challenge(probe_path_id, random_generator);
+        self.probe.on_start(probe_path_id);
+    }
+
+    fn create_probe_path<Pub: event::ConnectionPublisher>(
+        &mut self,
+        handle: Config::PathHandle,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        publisher: &mut Pub,
+    ) -> Result<Id, PathError> {
+        let new_path_idx = self.new_path_idx();
+
+        if new_path_idx >= MAX_ALLOWED_PATHS {
+            return Err(PathError::PathLimitExceeded);
+        }
+        let new_path_id = path_id(new_path_idx as u8);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.5
+        //# Similarly, an endpoint MUST NOT reuse a connection ID when sending to
+        //# more than one destination address.
+        let peer_connection_id = self
+            .peer_id_registry
+            .consume_new_id_for_new_path()
+            .ok_or(PathError::InsufficientConnectionIds)?;
+
+        let remote_address = handle.remote_address();
+        let rtt = RttEstimator::new_with_initial_rtt(
+            self.active_path().rtt_estimator.max_ack_delay(),
+            self.active_path().rtt_estimator.initial_rtt(),
+        );
+        let path_info = congestion_controller::PathInfo::new(&remote_address);
+        let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+
+        let mut path = Path::new(
+            handle,
+            peer_connection_id,
+            self.active_path().local_connection_id,
+            rtt,
+            cc,
+            true,
+            self.max_mtu(),
+            self.active_path().flow_label_key,
+        );
+        path.set_min_mtu(
+            self.active_path().mtu_controller.min_mtu(),
+            new_path_id,
+            publisher,
+        );
+
+        // new paths inherit the application's ECN preference
+        if !self.active_path().ecn_controller.is_enabled() {
+            path.ecn_controller.disable();
+        }
+
+        let active_path = self.active_path();
+        let active_path_id = self.active_path_id();
+        publisher.on_path_created(event::builder::PathCreated {
+            active: path_event!(active_path, active_path_id),
+            new: path_event!(path, new_path_id),
+        });
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: new_path_id.into_event(),
+            mtu: path.mtu_controller.mtu() as u16,
+            cause: MtuUpdatedCause::NewPath,
+        });
+
+        // a path which failed packet authentication is replaced by the probed path
+        self.pending_packet_authentication = None;
+
+        if new_path_idx < self.paths.len() {
+            self.paths[new_path_idx] = path;
+        } else {
+            self.paths.push(path);
+        }
+
+        Ok(new_path_id)
+    }
+
+    /// Requests the connection to migrate to the validated path for the given handle
+    ///
+    /// The active path is updated in [`Self::on_pending_migration`], since that requires
+    /// resources owned by the endpoint.
+    pub fn request_migration(&mut self, handle: Config::PathHandle) -> Result<(), PathError> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# Clients are responsible for initiating all migrations.
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Err(PathError::MigrationNotAllowed);
+        }
+
+        let (id, path) = self.path(&handle).ok_or(PathError::UnknownPath)?;
+
+        if id == self.active_path_id() {
+            return Err(PathError::ActivePath);
+        }
+
+        // Only paths which were already validated can be migrated to. This ensures the
+        // application knows the peer is reachable on the path before committing to it.
+        if !path.is_validated() {
+            return Err(PathError::NotValidated);
+        }
+
+        // the path's connection id might have been retired since it was validated, in which
+        // case a new one is consumed when the path is activated
+        if !self.peer_id_registry.is_active(&path.peer_connection_id)
+            && !self.peer_id_registry.has_new_id()
+        {
+            return Err(PathError::InsufficientConnectionIds);
+        }
+
+        self.pending_migration = Some(id.as_u8());
+
+        Ok(())
+    }
+
+    /// Migrates to the path requested by the application, if any
+    ///
+    /// Packets which were already sent on the previous path continue to be tracked by
+    /// the congestion controller of that path until they are acknowledged or declared lost.
+    pub fn on_pending_migration<Pub: event::ConnectionPublisher>(
+        &mut self,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        let new_path_id = if let Some(id) = self.pending_migration.take() {
+            path_id(id)
+        } else {
+            return Ok(());
+        };
+
+        // the peer could have migrated the connection since the migration was requested
+        if new_path_id == self.active_path_id() {
+            return Ok(());
+        }
+
+        // the peer could have retired the path's connection id since the migration was
+        // requested. The request is dropped if there isn't a new one to replace it, rather than
+        // closing the connection.
+        let peer_connection_id = &self[new_path_id].peer_connection_id;
+        if !self.peer_id_registry.is_active(peer_connection_id)
+            && !self.peer_id_registry.has_new_id()
+        {
+            return Ok(());
+        }
+
+        self.update_active_path(new_path_id, random_generator, publisher)
+    }
+
+    /// Requests the connection to send from the local address of the given handle
+    ///
+    /// Unlike [`Self::request_migration`], the path doesn't need to be validated first. The
+    /// path is created and challenged in [`Self::on_pending_local_address`], since that requires
+    /// resources owned by the endpoint.
+    pub fn request_local_address(&mut self, handle: Config::PathHandle) -> Result<(), PathError> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# Clients are responsible for initiating all migrations.
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Err(PathError::MigrationNotAllowed);
+        }
+
+        self.check_local_port(&handle)?;
+
+        match self.path(&handle) {
+            Some((id, _path)) if id == self.active_path_id() => {
+                return Err(PathError::ActivePath);
+            }
+            Some((_id, path)) => {
+                if !self.peer_id_registry.is_active(&path.peer_connection_id)
+                    && !self.peer_id_registry.has_new_id()
+                {
+                    return Err(PathError::InsufficientConnectionIds);
+                }
+            }
+            None if self.new_path_idx() >= MAX_ALLOWED_PATHS => {
+                return Err(PathError::PathLimitExceeded);
+            }
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-9.5
+            //# Similarly, an endpoint MUST NOT reuse a connection ID when sending to
+            //# more than one destination address.
+            None if !self.peer_id_registry.has_new_id() => {
+                return Err(PathError::InsufficientConnectionIds);
+            }
+            None => {}
+        }
+
+        self.pending_local_address = Some(handle);
+
+        Ok(())
+    }
+
+    /// Migrates to a path with the local address requested by the application, if any
+    ///
+    /// The path is created with a new peer connection ID and its own congestion controller and
+    /// RTT estimator, challenged and then migrated to with [`Self::on_pending_migration`].
+    pub fn on_pending_local_address<Pub: event::ConnectionPublisher>(
+        &mut self,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        let handle = if let Some(handle) = self.pending_local_address.take() {
+            handle
+        } else {
+            return Ok(());
+        };
+
+        let new_path_id = if let Some((id, _path)) = self.path(&handle) {
+            id
+        } else {
+            match self.create_probe_path(handle, congestion_controller_endpoint, publisher) {
+                Ok(id) => id,
+                // the request is dropped if the path can no longer be created, rather than
+                // closing the connection
+                Err(_) => return Ok(()),
+            }
+        };
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+        //# An endpoint can migrate a connection to a new local address by
+        //# sending packets containing non-probing frames from that address.
+        //
+        // The new path is challenged so the application learns whether the peer is reachable
+        // from the new local address.
+        if !self[new_path_id].is_validated() && !self[new_path_id].is_challenge_pending() {
+            self.set_challenge(new_path_id, random_generator);
+        }
+
+        self.pending_migration = Some(new_path_id.as_u8());
+        self.on_pending_migration(random_generator, publisher)
+    }
+
+    /// Returns `true` if fewer than `max_validating_paths` paths are currently being validated
+    #[inline]
+    fn can_validate_new_path(&self) -> bool {
+        let validating_paths = self
+            .paths
+            .iter()
+            .filter(|path| path.is_challenge_pending())
+            .count();
+
+        validating_paths < self.max_validating_paths as usize
+    }
+
+    /// Returns the index that will be used for the next created path
+    ///
+    /// If a previously allocated path failed to contain an authenticated packet, that index
+    /// is reused instead of pushing on to the end.
+    #[inline]
+    fn new_path_idx(&self) -> usize {
+        self.pending_packet_authentication
+            .map_or(self.paths.len(), |idx| idx as usize)
+    }
+
+    /// Polls for the result of the path probe
+    #[inline]
+    pub fn poll_probe(&mut self, cx: &Context) -> Poll<Result<PathProbe, PathError>> {
+        self.probe.poll(cx)
+    }
+
+    /// Completes the path probe if its challenge was abandoned
+    #[inline]
+    fn on_probe_progress(&mut self) {
+        if let Some(probe_path_id) = self.probe.probing_path() {
+            if self[probe_path_id].challenge.is_abandoned() {
+                self.probe.on_complete(Err(PathError::ValidationAbandoned));
+            }
+        }
+    }
+
     /// Process a packet and update internal state.
     ///
     /// Check if the packet is a non-probing (path validation) packet and attempt to
@@ -663,6 +1071,8 @@ impl<Config: endpoint::Config> Manager<Config> {
             let path_id = idx as u64;
             path.abandon_challenge(publisher, path_id);
         }
+
+        self.on_probe_progress();
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3
@@ -725,6 +1135,8 @@ impl<Config: endpoint::Config> Manager<Config> {
             path.on_timeout(timestamp, path_id(id as u8), random_generator, publisher);
         }
 
+        self.on_probe_progress();
+
         if self.active_path().failed_validation() {
             match self.last_known_active_validated_path {
                 Some(last_known_active_validated_path) => {
@@ -768,6 +1180,7 @@ impl<Config: endpoint::Config> Manager<Config> {
     /// Notifies the path manager of the connection closing event
     pub fn on_closing(&mut self) {
         self.active_path_mut().on_closing();
+        self.probe.on_closing();
         // TODO clean up other paths
     }
 
@@ -268,6 +268,8 @@ impl Model {
             ecn: ExplicitCongestionNotification::NotEct,
             destination_connection_id: local_id,
             source_connection_id: None,
+            ttl: None,
+            receive_delay: None,
         };
         let mut migration_validator = path::migration::default::Validator;
         let mut random_generator = Generator::default();
@@ -60,6 +60,7 @@ fn get_path_by_address_test() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
 
     let second_conn_id = connection::PeerId::try_from_bytes(&[5, 4, 3, 2, 1]).unwrap();
@@ -71,6 +72,7 @@ fn get_path_by_address_test() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
 
     let mut manager = manager_server(first_path.clone());
@@ -102,6 +104,7 @@ fn test_invalid_path_fallback() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     // simulate receiving a handshake packet to force path validation
     first_path.on_handshake_packet();
@@ -118,6 +121,7 @@ fn test_invalid_path_fallback() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     second_path.set_challenge(challenge);
 
@@ -251,6 +255,29 @@ fn dont_update_path_to_active_path_if_no_connection_id_available() {
     assert_eq!(helper.manager.active, helper.first_path_id.as_u8());
 }
 
+#[test]
+// Drop a requested migration if the path's connection id was retired in the meantime
+fn drop_pending_migration_if_no_connection_id_available() {
+    // Setup:
+    let mut publisher = Publisher::snapshot();
+    let mut helper = helper_manager_with_paths_base(false, true, &mut publisher);
+    assert!(!helper
+        .manager
+        .peer_id_registry
+        .is_active(&helper.manager[helper.second_path_id].peer_connection_id));
+    helper.manager.pending_migration = Some(helper.second_path_id.as_u8());
+
+    // Trigger:
+    assert!(helper
+        .manager
+        .on_pending_migration(&mut random::testing::Generator(123), &mut publisher)
+        .is_ok());
+
+    // Expectation:
+    assert_eq!(helper.manager.active, helper.first_path_id.as_u8());
+    assert!(helper.manager.pending_migration.is_none());
+}
+
 #[test]
 fn set_path_challenge_on_active_path_on_connection_migration() {
     // Setup:
@@ -348,7 +375,9 @@ fn validate_path_before_challenge_expiration() {
     let frame = s2n_quic_core::frame::PathResponse {
         data: &helper.second_expected_data,
     };
-    helper.manager.on_path_response(&frame, &mut publisher);
+    helper
+        .manager
+        .on_path_response(&frame, helper.now, &mut publisher);
 
     // Expectation 2:
     assert!(helper.manager[helper.second_path_id].is_validated());
@@ -415,7 +444,9 @@ fn dont_validate_path_if_path_challenge_is_abandoned() {
     let frame = s2n_quic_core::frame::PathResponse {
         data: &helper.second_expected_data,
     };
-    helper.manager.on_path_response(&frame, &mut publisher);
+    helper
+        .manager
+        .on_path_response(&frame, helper.now, &mut publisher);
 
     // Expectation 2:
     assert!(!helper.manager[helper.second_path_id].is_validated());
@@ -490,6 +521,7 @@ fn silently_return_when_there_is_no_valid_path() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     first_path.set_challenge(challenge);
     let mut manager = manager_server(first_path);
@@ -693,6 +725,7 @@ fn test_adding_new_path() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
 
@@ -711,6 +744,8 @@ fn test_adding_new_path() {
         ecn: ExplicitCongestionNotification::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
     };
     let (path_id, unblocked) = manager
         .on_datagram_received(
@@ -754,6 +789,7 @@ fn do_not_add_new_path_if_handshake_not_confirmed() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
 
@@ -770,6 +806,8 @@ fn do_not_add_new_path_if_handshake_not_confirmed() {
         ecn: ExplicitCongestionNotification::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
     };
     let handshake_confirmed = false;
     let on_datagram_result = manager.on_datagram_received(
@@ -814,6 +852,7 @@ fn do_not_add_new_path_if_client() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_client(first_path);
     let mut publisher = Publisher::snapshot();
@@ -831,6 +870,8 @@ fn do_not_add_new_path_if_client() {
         ecn: ExplicitCongestionNotification::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
     };
     let on_datagram_result = manager.on_datagram_received(
         &new_addr,
@@ -867,6 +908,7 @@ fn switch_destination_connection_id_after_first_server_response() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_client(zero_path);
     assert_eq!(manager[zero_path_id].peer_connection_id, initial_cid);
@@ -905,6 +947,7 @@ fn limit_number_of_connection_migrations() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
     let mut total_paths = 1;
@@ -920,6 +963,8 @@ fn limit_number_of_connection_migrations() {
             ecn: ExplicitCongestionNotification::default(),
             destination_connection_id: connection::LocalId::TEST_ID,
             source_connection_id: None,
+            ttl: None,
+            receive_delay: None,
         };
 
         let res = manager.handle_connection_migration(
@@ -947,6 +992,81 @@ fn limit_number_of_connection_migrations() {
     assert_eq!(total_paths, MAX_ALLOWED_PATHS);
 }
 
+#[test]
+fn limit_number_of_validating_paths() {
+    // Setup:
+    let mut publisher = Publisher::snapshot();
+    let first_addr: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let first_addr = SocketAddress::from(first_addr);
+    let first_addr = RemoteAddress::from(first_addr);
+    let first_path = ServerPath::new(
+        first_addr,
+        connection::PeerId::try_from_bytes(&[1]).unwrap(),
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        false,
+        DEFAULT_MAX_MTU,
+        Default::default(),
+    );
+    let mut manager = manager_server(first_path).with_max_validating_paths(2);
+    let now = NoopClock {}.get_time();
+    let datagram = DatagramInfo {
+        timestamp: now,
+        payload_len: 0,
+        ecn: ExplicitCongestionNotification::default(),
+        destination_connection_id: connection::LocalId::TEST_ID,
+        source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
+    };
+
+    // Trigger:
+    let mut results = vec![];
+    for i in 2..=4 {
+        let new_addr: SocketAddr = format!("127.0.0.{}:8001", i).parse().unwrap();
+        let new_addr = SocketAddress::from(new_addr);
+        let new_addr = RemoteAddress::from(new_addr);
+
+        let res = manager.handle_connection_migration(
+            &new_addr,
+            &datagram,
+            &mut Default::default(),
+            &mut migration::default::Validator::default(),
+            DEFAULT_MAX_MTU,
+            &mut publisher,
+        );
+
+        if let Ok((id, _)) = res {
+            // probe the new path so the active path doesn't change
+            assert!(manager
+                .on_processed_packet(
+                    id,
+                    None,
+                    path_validation::Probe::Probing,
+                    &mut random::testing::Generator(123),
+                    &mut publisher,
+                )
+                .is_ok());
+        }
+
+        results.push(res.map(|(id, _)| id));
+    }
+
+    // Expectation:
+    assert!(matches!(results[0], Ok(id) if id == path_id(1)));
+    assert!(matches!(results[1], Ok(id) if id == path_id(2)));
+    assert!(matches!(
+        results[2],
+        Err(DatagramDropReason::PathLimitExceeded)
+    ));
+    assert_eq!(manager.active_path_id(), path_id(0));
+
+    // the validations that were already in progress continue
+    assert!(manager[path_id(1)].is_challenge_pending());
+    assert!(manager[path_id(2)].is_challenge_pending());
+}
+
 #[test]
 fn connection_migration_challenge_behavior() {
     // Setup:
@@ -963,6 +1083,7 @@ fn connection_migration_challenge_behavior() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
 
@@ -976,6 +1097,8 @@ fn connection_migration_challenge_behavior() {
         ecn: ExplicitCongestionNotification::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
     };
 
     let (path_id, _unblocked) = manager
@@ -1056,6 +1179,7 @@ fn connection_migration_use_max_ack_delay_from_active_path() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1069,6 +1193,8 @@ fn connection_migration_use_max_ack_delay_from_active_path() {
         ecn: ExplicitCongestionNotification::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
     };
 
     // Trigger 1:
@@ -1134,6 +1260,7 @@ fn connection_migration_new_path_abandon_timer() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1147,6 +1274,8 @@ fn connection_migration_new_path_abandon_timer() {
         ecn: ExplicitCongestionNotification::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
     };
 
     let (second_path_id, _unblocked) = manager
@@ -1254,6 +1383,7 @@ fn stop_using_a_retired_connection_id() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1348,6 +1478,7 @@ fn pending_paths_should_return_paths_pending_validation() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let expected_response_data = [0; 8];
     third_path.on_path_challenge(&expected_response_data);
@@ -1398,6 +1529,8 @@ fn temporary_until_authenticated() {
         ecn: ExplicitCongestionNotification::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay: None,
     };
 
     // create an initial path
@@ -1412,6 +1545,7 @@ fn temporary_until_authenticated() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1640,7 +1774,7 @@ fn last_known_validated_path_should_update_on_path_response() {
     let frame = s2n_quic_core::frame::PathResponse {
         data: &first_expected_data,
     };
-    manager.on_path_response(&frame, &mut publisher);
+    manager.on_path_response(&frame, NoopClock.get_time(), &mut publisher);
     // Expectation 1:
     assert_eq!(manager.active_path_id(), second_path_id);
     // second
@@ -1791,6 +1925,7 @@ pub fn helper_path(peer_id: connection::PeerId) -> ServerPath {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     )
 }
 
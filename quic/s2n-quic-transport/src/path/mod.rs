@@ -14,14 +14,18 @@ use crate::{
 use s2n_quic_core::{
     counter::{Counter, Saturating},
     event::{self, IntoEvent},
-    frame, packet, random,
+    frame,
+    inet::SocketAddress,
+    packet, random,
     time::{timer, Timestamp},
 };
+use siphasher::sip::SipHasher13;
 
 mod challenge;
 pub(crate) mod ecn;
 mod manager;
 pub(crate) mod mtu;
+mod probe;
 
 pub use challenge::*;
 pub use manager::*;
@@ -80,6 +84,12 @@ pub struct Path<Config: endpoint::Config> {
 
     /// True if the path is currently active
     is_active: bool,
+
+    /// The key which the flow labels of the connection's paths are derived from
+    flow_label_key: FlowLabelKey,
+
+    /// The IPv6 flow label of the packets sent on the path
+    flow_label: u32,
 }
 
 impl<Config: endpoint::Config> Clone for Path<Config> {
@@ -99,6 +109,8 @@ impl<Config: endpoint::Config> Clone for Path<Config> {
             response_data: self.response_data,
             activated: self.activated,
             is_active: self.is_active,
+            flow_label_key: self.flow_label_key,
+            flow_label: self.flow_label,
         }
     }
 }
@@ -115,6 +127,7 @@ impl<Config: endpoint::Config> Path<Config> {
         congestion_controller: <Config::CongestionControllerEndpoint as congestion_controller::Endpoint>::CongestionController,
         peer_validated: bool,
         max_mtu: MaxMtu,
+        flow_label_key: FlowLabelKey,
     ) -> Path<Config> {
         let state = match Config::ENDPOINT_TYPE {
             Type::Server => {
@@ -131,6 +144,7 @@ impl<Config: endpoint::Config> Path<Config> {
             Type::Client => State::Validated,
         };
         let peer_socket_address = handle.remote_address();
+        let flow_label = flow_label_key.flow_label(&peer_connection_id, &peer_socket_address);
         Path {
             handle,
             peer_connection_id,
@@ -146,6 +160,8 @@ impl<Config: endpoint::Config> Path<Config> {
             response_data: None,
             activated: false,
             is_active: false,
+            flow_label_key,
+            flow_label,
         }
     }
 
@@ -159,6 +175,15 @@ impl<Config: endpoint::Config> Path<Config> {
         self.handle.local_address()
     }
 
+    /// Returns the IPv6 flow label of the packets sent on the path
+    ///
+    /// The label is derived when the path is created so it stays the same for the lifetime of
+    /// the path. Migrating to a new path changes the label.
+    #[inline]
+    pub fn flow_label(&self) -> u32 {
+        self.flow_label
+    }
+
     #[inline]
     pub fn set_challenge(&mut self, challenge: Challenge) {
         self.challenge = challenge;
@@ -531,6 +556,41 @@ impl<Config: endpoint::Config> Path<Config> {
         self.mtu_controller.max_mtu()
     }
 
+    /// Populates the active path statistics of the connection
+    #[inline]
+    pub fn update_stats(&self, stats: &mut connection::Stats) {
+        stats.smoothed_rtt = self.rtt_estimator.smoothed_rtt();
+        stats.min_rtt = self.rtt_estimator.min_rtt();
+        stats.congestion_window = self.congestion_controller.congestion_window();
+        stats.bytes_in_flight = self.congestion_controller.bytes_in_flight();
+        stats.mtu = self.mtu_controller.mtu() as u16;
+        stats.pacing_rate = self
+            .congestion_controller
+            .pacing_rate()
+            .map(|rate| rate.as_bytes_per_second());
+    }
+
+    /// Raises the MTU of the path to at least `min_mtu`
+    ///
+    /// This is called when the path is created, before any packets are sent on it.
+    #[inline]
+    pub fn set_min_mtu<Pub: event::ConnectionPublisher>(
+        &mut self,
+        min_mtu: MinMtu,
+        path_id: Id,
+        publisher: &mut Pub,
+    ) {
+        let mtu = self.mtu_controller.mtu();
+        self.mtu_controller.set_min_mtu(min_mtu);
+
+        if self.mtu_controller.mtu() != mtu {
+            self.congestion_controller.on_mtu_update(
+                self.mtu_controller.mtu() as u16,
+                &mut congestion_controller::PathPublisher::new(publisher, path_id),
+            );
+        }
+    }
+
     /// Returns `true` if the congestion window does not have sufficient space for a packet of
     /// size `mtu` considering the current bytes in flight and the additional `bytes_sent` provided
     #[inline]
@@ -593,6 +653,56 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Path<Config>
     }
 }
 
+/// The randomly generated key which an endpoint derives the flow labels of its paths from
+///
+/// The connection ID and address of a path are visible on the wire, so the labels are keyed to
+/// keep observers from predicting or correlating them.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(any(test, feature = "testing"), derive(Default))]
+pub struct FlowLabelKey {
+    k0: u64,
+    k1: u64,
+}
+
+impl FlowLabelKey {
+    /// Generates a key with the given random generator
+    pub fn new(random_generator: &mut dyn random::Generator) -> Self {
+        let mut k0 = [0u8; core::mem::size_of::<u64>()];
+        let mut k1 = [0u8; core::mem::size_of::<u64>()];
+
+        random_generator.private_random_fill(&mut k0);
+        random_generator.private_random_fill(&mut k1);
+
+        Self {
+            k0: u64::from_be_bytes(k0),
+            k1: u64::from_be_bytes(k1),
+        }
+    }
+
+    /// Derives a flow label from the peer's connection id and address
+    ///
+    /// The connection ids are chosen at random so each connection from the same host is labeled
+    /// as a separate flow, which spreads them across routes that hash the flow label.
+    fn flow_label(
+        &self,
+        peer_connection_id: &connection::PeerId,
+        remote_address: &SocketAddress,
+    ) -> u32 {
+        use core::hash::Hasher as _;
+
+        let mut hasher = SipHasher13::new_with_keys(self.k0, self.k1);
+        hasher.write(peer_connection_id.as_bytes());
+        match remote_address.unmap() {
+            SocketAddress::IpV4(addr) => hasher.write(&<[u8; 4]>::from(*addr.ip())),
+            SocketAddress::IpV6(addr) => hasher.write(&<[u8; 16]>::from(*addr.ip())),
+        }
+        hasher.write(&remote_address.port().to_be_bytes());
+
+        // truncate the hash to the 20 bits of the label, avoiding 0 which marks an unlabeled flow
+        ((hasher.finish() & 0xf_ffff) as u32).max(1)
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing {
     use crate::{
@@ -611,6 +721,7 @@ pub mod testing {
             Default::default(),
             true,
             DEFAULT_MAX_MTU,
+            Default::default(),
         )
     }
 
@@ -623,6 +734,7 @@ pub mod testing {
             Default::default(),
             false,
             DEFAULT_MAX_MTU,
+            Default::default(),
         )
     }
 }
@@ -1114,6 +1226,7 @@ mod tests {
             Default::default(),
             false,
             DEFAULT_MAX_MTU,
+            Default::default(),
         );
         let now = NoopClock.get_time();
         let random = &mut random::testing::Generator::default();
@@ -1227,4 +1340,44 @@ mod tests {
         // There isn't room for an MTU sized packet after including the 501 bytes, so the path is congestion limited
         assert!(path.is_congestion_limited(501));
     }
+
+    #[test]
+    fn flow_label_test() {
+        let remote_address: SocketAddress = "[2001:db8::1]:443"
+            .parse::<std::net::SocketAddr>()
+            .unwrap()
+            .into();
+        let peer_id = |bytes: &[u8]| connection::PeerId::try_from_bytes(bytes).unwrap();
+        let key = FlowLabelKey::new(&mut random::testing::Generator(123));
+
+        for bytes in [&[][..], &[1, 2, 3, 4], &[0xff; 20]] {
+            let label = key.flow_label(&peer_id(bytes), &remote_address);
+            assert_ne!(label, 0);
+            assert!(label <= 0xf_ffff);
+
+            // the label is stable for the same path
+            assert_eq!(label, key.flow_label(&peer_id(bytes), &remote_address));
+        }
+
+        // connections to the same peer use different labels
+        assert_ne!(
+            key.flow_label(&peer_id(&[1, 2, 3, 4]), &remote_address),
+            key.flow_label(&peer_id(&[5, 6, 7, 8]), &remote_address)
+        );
+
+        // migrating to a new address changes the label
+        let mut migrated_address = remote_address;
+        migrated_address.set_port(444);
+        assert_ne!(
+            key.flow_label(&peer_id(&[1, 2, 3, 4]), &remote_address),
+            key.flow_label(&peer_id(&[1, 2, 3, 4]), &migrated_address)
+        );
+
+        // endpoints with different keys label the same path differently
+        let other_key = FlowLabelKey::new(&mut random::testing::Generator(200));
+        assert_ne!(
+            key.flow_label(&peer_id(&[1, 2, 3, 4]), &remote_address),
+            other_key.flow_label(&peer_id(&[1, 2, 3, 4]), &remote_address)
+        );
+    }
 }
@@ -4,7 +4,7 @@
 use crate::{
     contexts::WriteContext,
     path,
-    path::{MaxMtu, MINIMUM_MTU},
+    path::{MaxMtu, MinMtu, MINIMUM_MTU},
     recovery::congestion_controller,
     transmission,
 };
@@ -96,6 +96,8 @@ pub struct Controller {
     plpmtu: u16,
     /// The maximum size any packet can reach
     max_mtu: MaxMtu,
+    /// The MTU the path starts with before it's raised by probing
+    min_mtu: MinMtu,
     /// The maximum size the UDP payload can reach for any probe packet.
     max_udp_payload: u16,
     //= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.3
@@ -151,6 +153,7 @@ impl Controller {
             plpmtu: BASE_PLPMTU,
             probed_size: initial_probed_size,
             max_mtu,
+            min_mtu: MinMtu::default(),
             max_udp_payload,
             max_probe_size: max_udp_payload,
             probe_count: 0,
@@ -160,6 +163,28 @@ impl Controller {
         }
     }
 
+    /// Sets the MTU the path starts with before it's raised by probing
+    ///
+    /// The path immediately starts using the UDP payload size corresponding to `min_mtu`, which is
+    /// never less than the BASE_PLPMTU or more than the max_udp_payload. The BASE_PLPMTU is still
+    /// the floor the path falls back to when a black hole is detected.
+    pub fn set_min_mtu(&mut self, min_mtu: MinMtu) {
+        let header_len = u16::from(self.max_mtu) - self.max_udp_payload;
+        let initial_plpmtu = u16::from(min_mtu)
+            .saturating_sub(header_len)
+            .clamp(BASE_PLPMTU, self.max_udp_payload);
+
+        self.min_mtu = min_mtu;
+        if self.plpmtu < initial_plpmtu {
+            self.plpmtu = initial_plpmtu;
+        }
+
+        // make sure the next probe is still larger than the current MTU
+        if self.probed_size < self.plpmtu + PROBE_THRESHOLD {
+            self.update_probed_size();
+        }
+    }
+
     /// Enable path MTU probing
     pub fn enable(&mut self) {
         if self.state != State::Disabled {
@@ -351,6 +376,11 @@ impl Controller {
         self.max_mtu
     }
 
+    /// Returns the smallest MTU the path will use
+    pub fn min_mtu(&self) -> MinMtu {
+        self.min_mtu
+    }
+
     /// Gets the MTU currently being probed for
     pub fn probed_sized(&self) -> usize {
         self.probed_size as usize
@@ -915,6 +945,52 @@ mod test {
         );
     }
 
+    #[test]
+    fn min_mtu_black_hole() {
+        let mut controller = new_controller(1500);
+        let mut cc = CongestionController::default();
+        let now = now();
+        let mut publisher = Publisher::snapshot();
+
+        // 1400 - UDP_HEADER_LEN - IPV4_MIN_HEADER_LEN
+        controller.set_min_mtu(1400.try_into().unwrap());
+        assert_eq!(1372, controller.mtu());
+        assert_eq!(1472, controller.probed_sized());
+
+        controller.enable();
+
+        for i in 0..BLACK_HOLE_THRESHOLD + 1 {
+            // Losing packets larger than the BASE_PLPMTU increases the black_hole_counter, even
+            // if they aren't larger than the min MTU
+            controller.on_packet_loss(
+                pn(i as usize),
+                1372,
+                true,
+                now,
+                &mut cc,
+                path::Id::test_id(),
+                &mut publisher,
+            );
+        }
+
+        // the MTU falls back to the BASE_PLPMTU rather than the min MTU
+        assert_eq!(1, cc.on_mtu_update);
+        assert_eq!(BASE_PLPMTU, controller.plpmtu);
+        assert_eq!(State::SearchComplete, controller.state);
+    }
+
+    #[test]
+    fn min_mtu_clamped_to_max_mtu() {
+        let mut controller = new_controller(1500);
+
+        controller.set_min_mtu(9001.try_into().unwrap());
+        assert_eq!(1472, controller.mtu());
+
+        // there is nothing left to probe for
+        controller.enable();
+        assert_eq!(State::SearchComplete, controller.state);
+    }
+
     #[test]
     fn on_packet_loss_disabled_controller() {
         let mut controller = new_controller(1500);
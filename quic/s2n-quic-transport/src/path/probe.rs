@@ -0,0 +1,122 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks a path which the application probes without migrating to it
+
+use super::Id;
+use core::task::{Context, Poll, Waker};
+use s2n_quic_core::path::{PathError, PathProbe};
+
+#[derive(Debug)]
+pub struct Probe<Handle> {
+    state: State<Handle>,
+    waker: Option<Waker>,
+}
+
+#[derive(Debug)]
+enum State<Handle> {
+    /// No probe was requested by the application
+    Idle,
+
+    /// The application requested a probe which still needs a path to be created
+    Requested(Handle),
+
+    /// PATH_CHALLENGE frames are being sent on the path
+    Probing(Id),
+
+    /// The probe finished and the result can be returned to the application
+    Completed(Result<PathProbe, PathError>),
+}
+
+impl<Handle> Default for Probe<Handle> {
+    fn default() -> Self {
+        Self {
+            state: State::Idle,
+            waker: None,
+        }
+    }
+}
+
+impl<Handle: Copy> Probe<Handle> {
+    /// Returns `true` if a probe was requested and hasn't completed yet
+    #[inline]
+    pub fn is_in_progress(&self) -> bool {
+        matches!(self.state, State::Requested(_) | State::Probing(_))
+    }
+
+    /// Returns the handle of a probe which was requested but not started yet
+    #[inline]
+    pub fn requested(&self) -> Option<Handle> {
+        if let State::Requested(handle) = self.state {
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the path being probed
+    #[inline]
+    pub fn probing_path(&self) -> Option<Id> {
+        if let State::Probing(path_id) = self.state {
+            Some(path_id)
+        } else {
+            None
+        }
+    }
+
+    /// Called when the application requests a path to be probed
+    #[inline]
+    pub fn on_request(&mut self, handle: Handle) {
+        debug_assert!(!self.is_in_progress());
+        self.state = State::Requested(handle);
+    }
+
+    /// Called when the path is created and PATH_CHALLENGE frames can be sent
+    #[inline]
+    pub fn on_start(&mut self, path_id: Id) {
+        debug_assert!(self.requested().is_some());
+        self.state = State::Probing(path_id);
+    }
+
+    /// Called when the probe can't make any more progress
+    #[inline]
+    pub fn on_complete(&mut self, result: Result<PathProbe, PathError>) {
+        self.state = State::Completed(result);
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Called when the connection is closing
+    ///
+    /// The application is woken up so it can observe the connection error.
+    #[inline]
+    pub fn on_closing(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Called when the PATH_RESPONSE for the path was received
+    #[inline]
+    pub fn on_validated(&mut self, path_id: Id, probe: PathProbe) {
+        if self.probing_path() == Some(path_id) {
+            self.on_complete(Ok(probe));
+        }
+    }
+
+    /// Polls for the result of the probe
+    #[inline]
+    pub fn poll(&mut self, cx: &Context) -> Poll<Result<PathProbe, PathError>> {
+        match core::mem::replace(&mut self.state, State::Idle) {
+            State::Idle => Poll::Ready(Err(PathError::NotProbing)),
+            State::Completed(result) => Poll::Ready(result),
+            state => {
+                self.state = state;
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
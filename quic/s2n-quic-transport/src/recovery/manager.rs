@@ -12,10 +12,11 @@ use crate::{
 };
 use core::{cmp::max, time::Duration};
 use s2n_quic_core::{
+    connection::SpaceStats,
     event::{self, builder::CongestionSource, IntoEvent},
     frame,
     frame::ack::EcnCounts,
-    inet::ExplicitCongestionNotification,
+    inet::{DatagramInfo, ExplicitCongestionNotification},
     packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace},
     recovery::{congestion_controller, CongestionController, RttEstimator, K_GRANULARITY},
     time::{timer, Timer, Timestamp},
@@ -60,6 +61,9 @@ pub struct Manager<Config: endpoint::Config> {
 
     // The total ecn counts for outstanding (unacknowledged) packets
     sent_packet_ecn_counts: EcnCounts,
+
+    // The number of packets sent, acknowledged and lost in the packet space
+    stats: SpaceStats,
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.1
@@ -77,6 +81,9 @@ const ACKED_PACKETS_INITIAL_CAPACITY: usize = 32;
 
 macro_rules! recovery_event {
     ($path_id:ident, $path:ident) => {
+        recovery_event!($path_id, $path, false)
+    };
+    ($path_id:ident, $path:ident, $rtt_sampled:expr) => {
         event::builder::RecoveryMetrics {
             path: event::builder::Path {
                 local_addr: $path.local_address().into_event(),
@@ -95,6 +102,7 @@ macro_rules! recovery_event {
             congestion_window: $path.congestion_controller.congestion_window(),
             bytes_in_flight: $path.congestion_controller.bytes_in_flight(),
             congestion_limited: $path.transmission_constraint().is_congestion_limited(),
+            rtt_sampled: $rtt_sampled,
         }
     };
 }
@@ -124,9 +132,16 @@ impl<Config: endpoint::Config> Manager<Config> {
             time_of_last_ack_eliciting_packet: None,
             baseline_ecn_counts: EcnCounts::default(),
             sent_packet_ecn_counts: EcnCounts::default(),
+            stats: SpaceStats::default(),
         }
     }
 
+    /// Returns the number of packets sent, acknowledged and lost in the packet space
+    #[inline]
+    pub fn stats(&self) -> SpaceStats {
+        self.stats
+    }
+
     /// Invoked when the Client processes a Retry packet.
     ///
     /// Reset congestion controller state by discarding sent bytes and replacing recovery
@@ -151,7 +166,10 @@ impl<Config: endpoint::Config> Manager<Config> {
             &mut congestion_controller::PathPublisher::new(publisher, path_id),
         );
 
+        // the packets sent before the Retry are still accounted for
+        let stats = self.stats;
         *self = Self::new(self.space);
+        self.stats = stats;
     }
 
     pub fn on_timeout<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
@@ -224,6 +242,8 @@ impl<Config: endpoint::Config> Manager<Config> {
             0
         };
 
+        self.stats.packets_sent += 1;
+
         let path_id = context.path_id();
         let path = context.path_mut();
         let cc_packet_info = path.congestion_controller.on_packet_sent(
@@ -335,7 +355,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         Pub: event::ConnectionPublisher,
     >(
         &mut self,
-        timestamp: Timestamp,
+        datagram: &DatagramInfo,
         frame: frame::Ack<A>,
         packet_number: PacketNumber,
         random_generator: &mut Config::RandomGenerator,
@@ -346,7 +366,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         let largest_acked_packet_number = space.new_packet_number(frame.largest_acknowledged());
 
         self.process_acks(
-            timestamp,
+            datagram.timestamp,
+            datagram.receive_time(),
             frame.ack_ranges().map(|ack_range| {
                 let (start, end) = ack_range.into_inner();
                 PacketNumberRange::new(space.new_packet_number(start), space.new_packet_number(end))
@@ -368,6 +389,7 @@ impl<Config: endpoint::Config> Manager<Config> {
     fn process_acks<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
         timestamp: Timestamp,
+        receive_time: Timestamp,
         ranges: impl Iterator<Item = PacketNumberRange>,
         largest_acked_packet_number: PacketNumber,
         ack_delay: Duration,
@@ -405,12 +427,14 @@ impl<Config: endpoint::Config> Manager<Config> {
         //# *  the largest acknowledged packet number is newly acknowledged, and
         //#
         //# *  at least one of the newly acknowledged packets was ack-eliciting.
+        let mut rtt_sampled = false;
         if let Some(largest_newly_acked) = largest_newly_acked {
-            self.update_congestion_control(
+            rtt_sampled = self.update_congestion_control(
                 largest_newly_acked,
                 largest_acked_packet_number,
                 includes_ack_eliciting,
                 timestamp,
+                receive_time,
                 ack_delay,
                 context,
                 publisher,
@@ -431,7 +455,7 @@ impl<Config: endpoint::Config> Manager<Config> {
 
         let path_id = context.path_id().as_u8();
         let path = context.path_mut();
-        publisher.on_recovery_metrics(recovery_event!(path_id, path));
+        publisher.on_recovery_metrics(recovery_event!(path_id, path, rtt_sampled));
 
         Ok(())
     }
@@ -510,6 +534,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok((largest_newly_acked, includes_ack_eliciting))
     }
 
+    /// Returns `true` if an RTT sample was taken
     #[allow(clippy::too_many_arguments)]
     fn update_congestion_control<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
@@ -517,10 +542,11 @@ impl<Config: endpoint::Config> Manager<Config> {
         largest_acked_packet_number: PacketNumber,
         includes_ack_eliciting: bool,
         timestamp: Timestamp,
+        receive_time: Timestamp,
         ack_delay: Duration,
         context: &mut Ctx,
         publisher: &mut Pub,
-    ) {
+    ) -> bool {
         let mut should_update_rtt = true;
         let is_handshake_confirmed = context.is_handshake_confirmed();
         let (largest_newly_acked_packet_number, largest_newly_acked_info) = largest_newly_acked;
@@ -541,8 +567,13 @@ impl<Config: endpoint::Config> Manager<Config> {
         //# does not newly acknowledge at least one ack-eliciting packet.
         should_update_rtt &= includes_ack_eliciting;
 
+        // The RTT is sampled from the time the platform received the ACK, which excludes the time
+        // it waited to be read. The platform's receive time comes from a different clock than
+        // the send time and can land before it, in which case the sample is skipped.
+        should_update_rtt &= receive_time >= largest_newly_acked_info.time_sent;
+
         if should_update_rtt {
-            let latest_rtt = timestamp - largest_newly_acked_info.time_sent;
+            let latest_rtt = receive_time - largest_newly_acked_info.time_sent;
             let path = context.path_mut_by_id(largest_newly_acked_info.path_id);
             path.rtt_estimator.update_rtt(
                 ack_delay,
@@ -566,6 +597,8 @@ impl<Config: endpoint::Config> Manager<Config> {
             // Notify components the RTT estimate was updated
             context.on_rtt_update();
         }
+
+        should_update_rtt
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -593,6 +626,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         let mut current_path_acked_bytes = 0;
         let mut newly_acked_ecn_counts = EcnCounts::default();
 
+        self.stats.packets_acked += newly_acked_packets.len() as u64;
+
         for acked_packet_info in newly_acked_packets {
             let path = context.path_mut_by_id(acked_packet_info.path_id);
 
@@ -715,6 +750,11 @@ impl<Config: endpoint::Config> Manager<Config> {
             })
         }
 
+        if let Some(ecn_counts) = ack_frame_ecn_counts {
+            // the counts are cumulative so the largest ones reflect all of the reports
+            self.stats.ecn_sent = self.stats.ecn_sent.max(ecn_counts);
+        }
+
         self.baseline_ecn_counts = ack_frame_ecn_counts.unwrap_or_default();
         self.sent_packet_ecn_counts -= newly_acked_ecn_counts;
     }
@@ -893,6 +933,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         for (packet_number, sent_info) in sent_packets_to_remove {
             let path = context.path_mut_by_id(sent_info.path_id);
             self.sent_packets.remove(packet_number);
+            self.stats.packets_lost += 1;
 
             //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
             //# A sender that does not have state for all packet
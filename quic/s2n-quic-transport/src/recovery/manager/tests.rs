@@ -56,6 +56,7 @@ fn one_second_pto_when_no_previous_rtt_available() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
 
     manager
@@ -463,6 +464,7 @@ fn on_ack_frame() {
         MockCongestionController::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     context.path_mut().pto_backoff = 2;
     let ack_receive_time = ack_receive_time + Duration::from_millis(500);
@@ -1157,6 +1159,78 @@ fn no_rtt_update_when_not_acknowledging_the_largest_acknowledged_packet() {
     assert_eq!(1, context.on_rtt_update_count);
 }
 
+#[test]
+fn rtt_sample_uses_platform_receive_time() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let ecn = ExplicitCongestionNotification::default();
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::snapshot();
+
+    let time_sent = time::now() + Duration::from_secs(10);
+
+    for packet_number in 0..=1 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // The ACK was read 500ms after it was sent, but waited 100ms in the socket buffer
+    let addr = context.path().handle;
+    helper_ack_packets_on_path_with_receive_delay(
+        0..=0,
+        time_sent + Duration::from_millis(500),
+        Some(Duration::from_millis(100)),
+        &mut context,
+        &mut manager,
+        addr,
+        None,
+        &mut publisher,
+    );
+
+    // The time spent in the socket buffer is excluded from the RTT sample
+    assert_eq!(
+        context.path().rtt_estimator.latest_rtt(),
+        Duration::from_millis(400)
+    );
+    assert_eq!(1, context.on_rtt_update_count);
+
+    // The platform receive time lands before the packet was sent, so the sample is skipped
+    helper_ack_packets_on_path_with_receive_delay(
+        1..=1,
+        time_sent + Duration::from_millis(500),
+        Some(Duration::from_millis(600)),
+        &mut context,
+        &mut manager,
+        addr,
+        None,
+        &mut publisher,
+    );
+
+    assert_eq!(
+        context.path().rtt_estimator.latest_rtt(),
+        Duration::from_millis(400)
+    );
+    assert_eq!(1, context.on_rtt_update_count);
+    assert_eq!(
+        manager.largest_acked_packet,
+        Some(space.new_packet_number(VarInt::from_u8(1)))
+    );
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-9.4
 //= type=test
 //# Packets sent on the old path MUST NOT contribute to
@@ -2505,6 +2579,7 @@ fn update_pto_timer() {
         MockCongestionController::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
     // simulate receiving a handshake packet to force path validation
     context.path_mut().on_handshake_packet();
@@ -2590,6 +2665,7 @@ fn pto_armed_if_handshake_not_confirmed() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
 
     // simulate receiving a handshake packet to force path validation
@@ -2618,6 +2694,7 @@ fn pto_must_be_at_least_k_granularity() {
         Default::default(),
         false,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
 
     // Update RTT with the smallest possible sample
@@ -2880,6 +2957,31 @@ fn helper_ack_packets_on_path(
     remote_address: RemoteAddress,
     ecn_counts: Option<EcnCounts>,
     publisher: &mut Publisher,
+) {
+    helper_ack_packets_on_path_with_receive_delay(
+        range,
+        ack_receive_time,
+        None,
+        context,
+        manager,
+        remote_address,
+        ecn_counts,
+        publisher,
+    );
+}
+
+// Helper function that will call on_ack_frame with the given packet numbers and the delay
+// reported by the platform between receiving the ACK and it being read
+#[allow(clippy::too_many_arguments)]
+fn helper_ack_packets_on_path_with_receive_delay(
+    range: RangeInclusive<u8>,
+    ack_receive_time: Timestamp,
+    receive_delay: Option<Duration>,
+    context: &mut MockContext,
+    manager: &mut Manager,
+    remote_address: RemoteAddress,
+    ecn_counts: Option<EcnCounts>,
+    publisher: &mut Publisher,
 ) {
     let (id, _) = context
         .path_manager
@@ -2903,6 +3005,8 @@ fn helper_ack_packets_on_path(
         ecn: Default::default(),
         destination_connection_id: connection::LocalId::TEST_ID,
         source_connection_id: None,
+        ttl: None,
+        receive_delay,
     };
 
     let mut ack_range = AckRanges::new(acked_packets.count());
@@ -2918,7 +3022,7 @@ fn helper_ack_packets_on_path(
     };
 
     let _ = manager.on_ack_frame(
-        datagram.timestamp,
+        &datagram,
         frame,
         acked_packets.start(),
         random,
@@ -3143,6 +3247,8 @@ fn helper_generate_multi_path_manager(
             ecn: ExplicitCongestionNotification::default(),
             destination_connection_id: connection::LocalId::TEST_ID,
             source_connection_id: None,
+            ttl: None,
+            receive_delay: None,
         };
         let _ = path_manager
             .on_datagram_received(
@@ -3214,6 +3320,7 @@ fn helper_generate_path_manager_with_first_addr(
         MockCongestionController::default(),
         true,
         DEFAULT_MAX_MTU,
+        Default::default(),
     );
 
     path::Manager::new(path, registry)
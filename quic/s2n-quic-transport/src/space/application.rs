@@ -8,7 +8,10 @@ use crate::{
     path::{path_event, Path},
     processed_packet::ProcessedPacket,
     recovery,
-    space::{datagram, keep_alive::KeepAlive, HandshakeStatus, PacketSpace, TxPacketNumbers},
+    space::{
+        datagram, keep_alive::KeepAlive, new_token, CryptoStream, HandshakeStatus, PacketSpace,
+        TxPacketNumbers,
+    },
     stream::Manager as _,
     sync::flag,
     transmission,
@@ -18,7 +21,11 @@ use core::{convert::TryInto, fmt, marker::PhantomData};
 use once_cell::sync::OnceCell;
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
-    crypto::{application::KeySet, limited, tls, CryptoSuite},
+    connection::Stats,
+    crypto::{
+        application::{KeySet, KeyUsage},
+        limited, tls, CryptoSuite,
+    },
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
@@ -64,6 +71,12 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
     pub datagram_manager: datagram::Manager<Config>,
+    new_token: new_token::Manager,
+    /// Carries post-handshake TLS messages, e.g. `NewSessionTicket`
+    pub crypto_stream: CryptoStream,
+    /// Set when the next packet is marked with the code point of the datagrams rather than the
+    /// streams
+    datagram_dscp_turn: bool,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for ApplicationSpace<Config> {
@@ -89,9 +102,12 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         ack_manager: AckManager,
         keep_alive: KeepAlive,
         max_mtu: MaxMtu,
+        key_update_packet_threshold: u64,
         datagram_manager: datagram::Manager<Config>,
+        new_token: new_token::Manager,
+        replay_window: u64,
     ) -> Self {
-        let key_set = KeySet::new(key, Self::key_limits(max_mtu));
+        let key_set = KeySet::new(key, Self::key_limits(max_mtu, key_update_packet_threshold));
 
         Self {
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::ApplicationData, now),
@@ -102,9 +118,35 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             header_key,
             ping: flag::Ping::default(),
             keep_alive,
-            processed_packet_numbers: SlidingWindow::default(),
+            processed_packet_numbers: SlidingWindow::with_width(replay_window),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
             datagram_manager,
+            new_token,
+            crypto_stream: CryptoStream::new(),
+            datagram_dscp_turn: false,
+        }
+    }
+
+    /// Returns the DSCP code point to mark the next packet with
+    ///
+    /// Streams and datagrams marked with different code points can't share a packet, so the
+    /// packets alternate between the code points to keep either from starving the other. The
+    /// connection's `default` code point is used when there's nothing to send.
+    pub fn transmission_dscp(&mut self, default: u8) -> u8 {
+        let streams = self.stream_manager.transmission_dscp();
+        let datagrams = self.datagram_manager.transmission_dscp();
+
+        match (streams, datagrams) {
+            (Some(streams), Some(datagrams)) if streams != datagrams => {
+                self.datagram_dscp_turn = !self.datagram_dscp_turn;
+                if self.datagram_dscp_turn {
+                    datagrams
+                } else {
+                    streams
+                }
+            }
+            (Some(dscp), _) | (None, Some(dscp)) => dscp,
+            (None, None) => default,
         }
     }
 
@@ -140,6 +182,15 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         handshake_status: &mut HandshakeStatus,
         buffer: EncoderBuffer<'a>,
     ) -> Result<(transmission::Outcome, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+        if let Some(pressure) = self.stream_manager.take_memory_pressure() {
+            context.publisher.on_connection_memory_pressure(
+                event::builder::ConnectionMemoryPressure {
+                    buffered_bytes: pressure.buffered_bytes,
+                    max_buffered_bytes: pressure.max_buffered_bytes,
+                },
+            );
+        }
+
         let mut packet_number = self.tx_packet_numbers.next();
 
         if self.recovery_manager.requires_probe() {
@@ -177,10 +228,13 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 &mut self.stream_manager,
                 &mut self.recovery_manager,
                 &mut self.datagram_manager,
+                &mut self.new_token,
+                &mut self.crypto_stream,
             ),
             timestamp,
             transmission_constraint,
             transmission_mode,
+            dscp: context.dscp,
             tx_packet_numbers: &mut self.tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
@@ -272,6 +326,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             timestamp: context.timestamp,
             transmission_constraint: transmission::Constraint::None,
             transmission_mode: transmission::Mode::Normal,
+            dscp: context.dscp,
             tx_packet_numbers: &mut self.tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
@@ -345,6 +400,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         // Retire the local connection ID used during the handshake to reduce linkability
         local_id_registry.retire_handshake_connection_id();
 
+        self.new_token.on_handshake_confirmed();
+
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
         //# A sender SHOULD restart its PTO timer every time an ack-eliciting
         //# packet is sent or acknowledged, or when Initial or Handshake keys are
@@ -410,6 +467,22 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.keep_alive.update(enabled);
     }
 
+    /// Records the packets sent in the space in `stats`
+    pub fn update_stats(&self, stats: &mut Stats) {
+        stats.application = self.recovery_manager.stats();
+        stats.application.ecn_received = self.ack_manager.ecn_counts();
+    }
+
+    /// Returns the amount of data protected by the current 1-RTT key
+    pub fn key_usage(&self) -> KeyUsage {
+        self.key_set.usage()
+    }
+
+    /// Returns the cipher suite of the 1-RTT keys
+    pub fn cipher_suite(&self) -> tls::CipherSuite {
+        self.key_set.cipher_suite()
+    }
+
     /// Returns the Packet Number to be used when encoding outgoing packets
     fn packet_number_encoder(&self) -> PacketNumber {
         self.tx_packet_numbers.largest_sent_packet_number_acked()
@@ -432,6 +505,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 handshake_status,
                 ping: &mut self.ping,
                 stream_manager: &mut self.stream_manager,
+                new_token: &mut self.new_token,
+                crypto_stream: &mut self.crypto_stream,
                 local_id_registry,
                 path_id,
                 path_manager,
@@ -529,10 +604,11 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         decrypted.map(|x| x.0)
     }
 
-    fn key_limits(max_mtu: MaxMtu) -> limited::Limits {
+    fn key_limits(max_mtu: MaxMtu, key_update_packet_threshold: u64) -> limited::Limits {
         let mut limits = limited::Limits::default();
 
         limits.max_mtu = max_mtu;
+        limits.key_update_packet_threshold = key_update_packet_threshold;
 
         // AEAD optimizations are currently in the testing phase so make them opt-in at runtime
         limits.sealer_optimization_threshold = {
@@ -585,6 +661,8 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ApplicationS
         self.recovery_manager.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
+        self.new_token.transmission_interest(query)?;
+        self.crypto_stream.transmission_interest(query)?;
         Ok(())
     }
 }
@@ -600,6 +678,8 @@ struct RecoveryContext<'a, Config: endpoint::Config> {
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut flag::Ping,
     stream_manager: &'a mut Config::StreamManager,
+    new_token: &'a mut new_token::Manager,
+    crypto_stream: &'a mut CryptoStream,
     local_id_registry: &'a mut connection::LocalIdRegistry,
     path_id: path::Id,
     path_manager: &'a mut path::Manager<Config>,
@@ -651,6 +731,8 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
             .on_packet_ack(packet_number_range, publisher);
         self.ping.on_packet_ack(packet_number_range);
         self.stream_manager.on_packet_ack(packet_number_range);
+        self.new_token.on_packet_ack(packet_number_range);
+        self.crypto_stream.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
     }
@@ -670,6 +752,8 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
             .on_packet_loss(packet_number_range, publisher);
         self.ping.on_packet_loss(packet_number_range);
         self.stream_manager.on_packet_loss(packet_number_range);
+        self.new_token.on_packet_loss(packet_number_range);
+        self.crypto_stream.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
     }
@@ -688,7 +772,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
 
     fn handle_crypto_frame<Pub: event::ConnectionPublisher>(
         &mut self,
-        _frame: CryptoRef,
+        frame: CryptoRef,
         _datagram: &DatagramInfo,
         _path: &mut Path<Config>,
         _publisher: &mut Pub,
@@ -699,14 +783,15 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         //# CRYPTO frames received in the future, or it MAY close the connection
         //# with a CRYPTO_BUFFER_EXCEEDED error code.
 
-        // we currently just discard CRYPTO frames post-handshake
-        Ok(())
+        // the data is passed to the TLS session when the connection polls the crypto state, which
+        // allows clients to receive session tickets
+        self.crypto_stream.on_crypto_frame(frame)
     }
 
     fn handle_ack_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
         &mut self,
         frame: Ack<A>,
-        timestamp: Timestamp,
+        datagram: &DatagramInfo,
         path_id: path::Id,
         path_manager: &mut path::Manager<Config>,
         packet_number: PacketNumber,
@@ -721,7 +806,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
             self.recovery(handshake_status, local_id_registry, path_id, path_manager);
 
         recovery_manager.on_ack_frame(
-            timestamp,
+            datagram,
             frame,
             packet_number,
             random_generator,
@@ -814,7 +899,8 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
                 .with_reason(Self::INVALID_FRAME_ERROR)
                 .with_frame_type(frame.tag().into()));
         }
-        // TODO add support for NEW_TOKEN_FRAMEs on the client
+
+        self.new_token.on_new_token_frame(&frame);
         Ok(())
     }
 
@@ -905,10 +991,11 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
     fn handle_path_response_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         frame: PathResponse,
+        timestamp: Timestamp,
         path_manager: &mut path::Manager<Config>,
         publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
-        path_manager.on_path_response(&frame, publisher);
+        path_manager.on_path_response(&frame, timestamp, publisher);
         Ok(())
     }
 
@@ -26,6 +26,8 @@ pub struct Manager<Config: endpoint::Config> {
     pub sender: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Sender,
     pub receiver: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Receiver,
     max_datagram_payload: u64,
+    /// The DSCP code point of the datagrams which don't set their own
+    dscp: u8,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -33,11 +35,13 @@ impl<Config: endpoint::Config> Manager<Config> {
         sender: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Sender,
         receiver: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Receiver,
         max_datagram_payload: u64,
+        dscp: u8,
     ) -> Self {
         Self {
             sender,
             receiver,
             max_datagram_payload,
+            dscp,
         }
     }
 
@@ -48,15 +52,30 @@ impl<Config: endpoint::Config> Manager<Config> {
         stream_manager: &mut Config::StreamManager,
         datagrams_prioritized: bool,
     ) {
+        // Only streams marked with the same code point as the packet can use the space the
+        // datagrams cede
+        let has_pending_streams = stream_manager.has_pending_streams()
+            && stream_manager.transmission_dscp() == Some(context.dscp());
         let mut packet = Packet {
             context,
-            has_pending_streams: stream_manager.has_pending_streams(),
+            has_pending_streams,
             datagrams_prioritized,
             max_datagram_payload: self.max_datagram_payload,
+            connection_dscp: self.dscp,
         };
         self.sender.on_transmit(&mut packet);
     }
 
+    /// Returns the DSCP code point of the next datagram ready to send
+    pub fn transmission_dscp(&self) -> Option<u8> {
+        self.sender.transmission_dscp(self.dscp)
+    }
+
+    /// Sets the DSCP code point of the datagrams which don't set their own
+    pub fn set_dscp(&mut self, dscp: u8) {
+        self.dscp = dscp;
+    }
+
     // A callback that allows users to access datagrams directly after they are
     // received.
     pub fn on_datagram_frame(
@@ -68,6 +87,11 @@ impl<Config: endpoint::Config> Manager<Config> {
         self.receiver.on_datagram(&context, datagram.data);
     }
 
+    /// Returns `true` if the application has datagrams waiting to be sent
+    pub fn has_pending_datagrams(&self) -> bool {
+        self.sender.has_transmission_interest()
+    }
+
     pub fn datagram_mut(&mut self, query: &mut dyn query::QueryMut) -> Poll<()> {
         // Try to execute the query on the sender side. If that fails, try the receiver side.
         match query.execute_mut(&mut self.sender) {
@@ -100,6 +124,7 @@ struct Packet<'a, C: WriteContext> {
     has_pending_streams: bool,
     datagrams_prioritized: bool,
     max_datagram_payload: u64,
+    connection_dscp: u8,
 }
 
 impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
@@ -145,4 +170,14 @@ impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
     fn datagrams_prioritized(&self) -> bool {
         self.datagrams_prioritized
     }
+
+    /// Returns the DSCP code point the packet is marked with
+    fn dscp(&self) -> u8 {
+        self.context.dscp()
+    }
+
+    /// Returns the connection's DSCP code point
+    fn connection_dscp(&self) -> u8 {
+        self.connection_dscp
+    }
 }
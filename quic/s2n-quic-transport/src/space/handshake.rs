@@ -14,6 +14,7 @@ use crate::{
 use core::{fmt, marker::PhantomData};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
+    connection::Stats,
     crypto::{tls, CryptoSuite},
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{ack::AckRanges, crypto::CryptoRef, Ack, ConnectionClose},
@@ -135,6 +136,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
             timestamp: context.timestamp,
             transmission_constraint,
             transmission_mode: context.transmission_mode,
+            dscp: context.dscp,
             tx_packet_numbers: &mut self.tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
@@ -208,6 +210,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
             timestamp: context.timestamp,
             transmission_constraint: transmission::Constraint::None,
             transmission_mode: transmission::Mode::Normal,
+            dscp: context.dscp,
             tx_packet_numbers: &mut self.tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
@@ -282,6 +285,12 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
         recovery_manager.on_timeout(timestamp, random_generator, &mut context, publisher);
     }
 
+    /// Records the packets sent in the space in `stats`
+    pub fn update_stats(&self, stats: &mut Stats) {
+        stats.handshake = self.recovery_manager.stats();
+        stats.handshake.ecn_received = self.ack_manager.ecn_counts();
+    }
+
     /// Called before the Handshake packet space is discarded
     pub fn on_discard<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -498,7 +507,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for HandshakeSpace<Config> {
     fn handle_ack_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
         &mut self,
         frame: Ack<A>,
-        timestamp: Timestamp,
+        datagram: &DatagramInfo,
         path_id: path::Id,
         path_manager: &mut path::Manager<Config>,
         packet_number: PacketNumber,
@@ -512,7 +521,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for HandshakeSpace<Config> {
         let (recovery_manager, mut context) =
             self.recovery(handshake_status, path_id, path_manager);
         recovery_manager.on_ack_frame(
-            timestamp,
+            datagram,
             frame,
             packet_number,
             random_generator,
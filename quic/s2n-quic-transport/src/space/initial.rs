@@ -14,7 +14,7 @@ use crate::{
 use core::{fmt, marker::PhantomData};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
-    connection::PeerId,
+    connection::{PeerId, Stats},
     crypto::{tls, CryptoSuite, InitialKey},
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{ack::AckRanges, crypto::CryptoRef, Ack, ConnectionClose},
@@ -47,7 +47,12 @@ pub struct InitialSpace<Config: endpoint::Config> {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.3
     //# Subsequent Initial packets from the client include the connection ID
     //# and token values from the Retry packet.
-    retry_token: Vec<u8>,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+    //# The client
+    //# MUST include the token in all Initial packets it sends, unless a
+    //# Retry replaces the token with a newer one.
+    token: Vec<u8>,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
 }
@@ -77,12 +82,18 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             crypto_stream: CryptoStream::new(),
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Initial, now),
             received_hello_message: false,
-            retry_token: Vec::new(),
+            token: Vec::new(),
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::Initial),
         }
     }
 
+    /// Sets the token received in a NEW_TOKEN frame on a previous connection to the server
+    pub fn set_new_token(&mut self, token: Vec<u8>) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.token = token;
+    }
+
     /// This method gets called when a Retry packet is processed.
     ///
     /// Reset the TLS stack and recover state when the first Retry packet is processed.
@@ -96,7 +107,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         publisher: &mut Pub,
     ) {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
-        self.retry_token = retry_token.to_vec();
+        self.token = retry_token.to_vec();
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.2
         //# Changing the Destination Connection ID field also results in
@@ -183,6 +194,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             timestamp: context.timestamp,
             transmission_constraint,
             transmission_mode: context.transmission_mode,
+            dscp: context.dscp,
             tx_packet_numbers: &mut self.tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
@@ -193,7 +205,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             version: context.quic_version,
             destination_connection_id,
             source_connection_id: context.path_manager[context.path_id].local_connection_id,
-            token: self.retry_token.as_slice(),
+            token: self.token.as_slice(),
             packet_number,
             payload,
         };
@@ -257,6 +269,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             timestamp: context.timestamp,
             transmission_constraint: transmission::Constraint::None,
             transmission_mode: transmission::Mode::Normal,
+            dscp: context.dscp,
             tx_packet_numbers: &mut self.tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
@@ -332,6 +345,12 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         recovery_manager.on_timeout(timestamp, random_generator, &mut context, publisher);
     }
 
+    /// Records the packets sent in the space in `stats`
+    pub fn update_stats(&self, stats: &mut Stats) {
+        stats.initial = self.recovery_manager.stats();
+        stats.initial.ecn_received = self.ack_manager.ecn_counts();
+    }
+
     /// Called before the Initial packet space is discarded
     pub fn on_discard<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -651,7 +670,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for InitialSpace<Config> {
     fn handle_ack_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
         &mut self,
         frame: Ack<A>,
-        timestamp: Timestamp,
+        datagram: &DatagramInfo,
         path_id: path::Id,
         path_manager: &mut path::Manager<Config>,
         packet_number: PacketNumber,
@@ -663,7 +682,7 @@ impl<Config: endpoint::Config> PacketSpace<Config> for InitialSpace<Config> {
         let (recovery_manager, mut context) =
             self.recovery(handshake_status, path_id, path_manager);
         recovery_manager.on_ack_frame(
-            timestamp,
+            datagram,
             frame,
             packet_number,
             random_generator,
@@ -18,7 +18,7 @@ use core::{
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application::ServerName,
-    connection::{limits::Limits, InitialId, PeerId},
+    connection::{limits::Limits, InitialId, PeerId, Stats},
     crypto::{tls, tls::Session, CryptoSuite, Key},
     event::{self, IntoEvent},
     frame::{
@@ -40,6 +40,7 @@ mod handshake;
 mod handshake_status;
 mod initial;
 mod keep_alive;
+pub(crate) mod new_token;
 mod session_context;
 mod tx_packet_numbers;
 
@@ -58,6 +59,9 @@ struct SessionInfo<Config: endpoint::Config> {
 
 pub struct PacketSpaceManager<Config: endpoint::Config> {
     session_info: Option<SessionInfo<Config>>,
+    /// The TLS session once the handshake has completed, which is kept to process post-handshake
+    /// messages and export keying material
+    completed_session: Option<SessionInfo<Config>>,
     retry_cid: Option<Box<PeerId>>,
     initial: Option<Box<InitialSpace<Config>>>,
     handshake: Option<Box<HandshakeSpace<Config>>>,
@@ -65,6 +69,10 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     zero_rtt_crypto:
         Option<Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>>,
     handshake_status: HandshakeStatus,
+    /// Sends or receives NEW_TOKEN frames once the application space is created
+    new_token: new_token::Manager,
+    /// The packet counts of the spaces which have been discarded
+    discarded_stats: Stats,
     /// Server Name Indication
     pub server_name: Option<ServerName>,
     //= https://www.rfc-editor.org/rfc/rfc9000#section-7
@@ -75,6 +83,8 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     //# another mechanism is used for agreeing on an application protocol,
     //# endpoints MUST use ALPN for this purpose.
     pub application_protocol: Bytes,
+    /// The DER-encoded certificates the peer authenticated with, starting with its leaf
+    pub peer_certificate_chain: Vec<Bytes>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for PacketSpaceManager<Config> {
@@ -121,6 +131,7 @@ macro_rules! packet_space_api {
                     path.reset_pto_backoff();
 
                     space.on_discard(path, path_id, publisher);
+                    space.update_stats(&mut self.discarded_stats);
                 }
 
                 //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.1
@@ -158,6 +169,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 session,
                 initial_cid,
             }),
+            completed_session: None,
             retry_cid: None,
             initial: Some(Box::new(InitialSpace::new(
                 initial_key,
@@ -169,13 +181,47 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             application: None,
             zero_rtt_crypto: None,
             handshake_status: HandshakeStatus::default(),
+            new_token: new_token::Manager::default(),
+            discarded_stats: Stats::default(),
             server_name: None,
             application_protocol: Bytes::new(),
+            peer_certificate_chain: Vec::new(),
         }
     }
 
     packet_space_api!(InitialSpace<Config>, initial, initial_mut, discard_initial);
 
+    /// Exports keying material from the TLS session
+    pub fn export_keying_material(
+        &self,
+        output: &mut [u8],
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<(), tls::ExportError> {
+        let session_info = self
+            .completed_session
+            .as_ref()
+            .ok_or(tls::ExportError::HandshakeNotComplete)?;
+        session_info
+            .session
+            .export_keying_material(output, label, context)?;
+        Ok(())
+    }
+
+    /// Serializes the state needed to resume the TLS session in a future connection
+    ///
+    /// Returns `None` until the handshake is complete and the peer has issued a session ticket.
+    pub fn resumption_state(&self) -> Option<Vec<u8>> {
+        self.completed_session.as_ref()?.session.resumption_state()
+    }
+
+    /// Sets the manager for NEW_TOKEN frames, which is passed to the application space once it
+    /// is created
+    pub fn set_new_token_manager(&mut self, new_token: new_token::Manager) {
+        debug_assert!(self.application.is_none());
+        self.new_token = new_token;
+    }
+
     packet_space_api!(
         HandshakeSpace<Config>,
         handshake,
@@ -186,6 +232,25 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     packet_space_api!(ApplicationSpace<Config>, application, application_mut);
 
+    /// Records the packets sent in each packet number space in `stats`
+    ///
+    /// The counts of discarded spaces are retained for the lifetime of the connection.
+    pub fn update_stats(&self, stats: &mut Stats) {
+        stats.initial = self.discarded_stats.initial;
+        stats.handshake = self.discarded_stats.handshake;
+        stats.application = self.discarded_stats.application;
+
+        if let Some(space) = self.initial() {
+            space.update_stats(stats);
+        }
+        if let Some(space) = self.handshake() {
+            space.update_stats(stats);
+        }
+        if let Some(space) = self.application() {
+            space.update_stats(stats);
+        }
+    }
+
     #[allow(dead_code)] // 0RTT hasn't been started yet
     pub fn zero_rtt_crypto(
         &self,
@@ -208,35 +273,54 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         publisher: &mut Pub,
         datagram: &mut Config::DatagramEndpoint,
     ) -> Poll<Result<(), transport::Error>> {
-        if let Some(session_info) = self.session_info.as_mut() {
-            let mut context: SessionContext<Config, Pub> = SessionContext {
-                now,
-                initial_cid: &session_info.initial_cid,
-                retry_cid: self.retry_cid.as_deref(),
-                initial: &mut self.initial,
-                handshake: &mut self.handshake,
-                application: &mut self.application,
-                zero_rtt_crypto: &mut self.zero_rtt_crypto,
-                path_manager,
-                handshake_status: &mut self.handshake_status,
-                local_id_registry,
-                limits,
-                server_name: &mut self.server_name,
-                application_protocol: &mut self.application_protocol,
-                waker,
-                publisher,
-                datagram,
-            };
-
-            match session_info.session.poll(&mut context)? {
-                Poll::Ready(_success) => {
-                    // The TLS session and retry_cid is no longer needed
-                    self.session_info = None;
-                    self.retry_cid = None;
-                }
-                Poll::Pending => return Poll::Pending,
-            };
-        }
+        let (session_info, is_complete) = if let Some(session_info) = self.session_info.as_mut() {
+            (session_info, false)
+        } else if let Some(session_info) = self.completed_session.as_mut() {
+            // The session only needs to be polled once the peer has sent post-handshake messages,
+            // e.g. `NewSessionTicket`
+            let has_crypto_data = self
+                .application
+                .as_ref()
+                .map_or(false, |space| !space.crypto_stream.rx.is_empty());
+            if !has_crypto_data {
+                return Poll::Ready(Ok(()));
+            }
+            (session_info, true)
+        } else {
+            return Poll::Ready(Ok(()));
+        };
+
+        let mut context: SessionContext<Config, Pub> = SessionContext {
+            now,
+            initial_cid: &session_info.initial_cid,
+            retry_cid: self.retry_cid.as_deref(),
+            initial: &mut self.initial,
+            handshake: &mut self.handshake,
+            application: &mut self.application,
+            zero_rtt_crypto: &mut self.zero_rtt_crypto,
+            path_manager,
+            handshake_status: &mut self.handshake_status,
+            new_token: &mut self.new_token,
+            local_id_registry,
+            limits,
+            server_name: &mut self.server_name,
+            application_protocol: &mut self.application_protocol,
+            peer_certificate_chain: &mut self.peer_certificate_chain,
+            waker,
+            publisher,
+            datagram,
+        };
+
+        match session_info.session.poll(&mut context)? {
+            Poll::Ready(_success) if !is_complete => {
+                // The TLS session is only needed to process post-handshake messages and export
+                // keying material and the retry_cid is no longer needed
+                self.completed_session = self.session_info.take();
+                self.retry_cid = None;
+            }
+            Poll::Ready(_success) => {}
+            Poll::Pending => return Poll::Pending,
+        };
 
         Poll::Ready(Ok(()))
     }
@@ -424,6 +508,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         publisher: &mut Pub,
     ) {
         self.session_info = None;
+        self.completed_session = None;
         self.retry_cid = None;
         self.discard_initial(path, path_id, publisher);
         self.discard_handshake(path, path_id, publisher);
@@ -539,7 +624,7 @@ pub trait PacketSpace<Config: endpoint::Config> {
     fn handle_ack_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
         &mut self,
         frame: Ack<A>,
-        timestamp: Timestamp,
+        datagram: &DatagramInfo,
         path_id: path::Id,
         path_manager: &mut path::Manager<Config>,
         packet_number: PacketNumber,
@@ -597,6 +682,7 @@ pub trait PacketSpace<Config: endpoint::Config> {
     fn handle_path_response_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         frame: PathResponse,
+        _timestamp: Timestamp,
         _path_manager: &mut path::Manager<Config>,
         _publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
@@ -749,7 +835,7 @@ pub trait PacketSpace<Config: endpoint::Config> {
                     let on_error = on_frame_processed!(frame);
                     self.handle_ack_frame(
                         frame,
-                        datagram.timestamp,
+                        datagram,
                         path_id,
                         path_manager,
                         packet_number,
@@ -852,8 +938,13 @@ pub trait PacketSpace<Config: endpoint::Config> {
                 Frame::PathResponse(frame) => {
                     let on_error = on_frame_processed!(frame);
 
-                    self.handle_path_response_frame(frame, path_manager, publisher)
-                        .map_err(on_error)?;
+                    self.handle_path_response_frame(
+                        frame,
+                        datagram.timestamp,
+                        path_manager,
+                        publisher,
+                    )
+                    .map_err(on_error)?;
                 }
                 Frame::HandshakeDone(frame) => {
                     let on_error = on_frame_processed!(frame);
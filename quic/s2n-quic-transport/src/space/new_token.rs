@@ -0,0 +1,288 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Delivers address validation tokens from servers to clients in NEW_TOKEN frames
+//!
+//! Clients store the received tokens for each server and present them in the Initial packets of
+//! the next connection to the same server, which allows the server to validate the client address
+//! without sending a Retry packet.
+
+use crate::{contexts::WriteContext, transmission};
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use s2n_quic_core::{ack, frame::NewToken, inet::SocketAddress, packet::number::PacketNumber};
+use std::sync::{Arc, Mutex};
+
+/// The maximum number of servers for which a client stores tokens
+const MAX_STORED_TOKENS: usize = 1024;
+
+/// Stores the tokens received by the connections of a client endpoint
+#[derive(Clone, Debug, Default)]
+pub struct Store {
+    tokens: Arc<Mutex<HashMap<SocketAddress, Vec<u8>>>>,
+}
+
+impl Store {
+    /// Removes and returns the token for the server at `remote_address`
+    pub fn take(&self, remote_address: &SocketAddress) -> Option<Vec<u8>> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A client SHOULD NOT reuse a token from a NEW_TOKEN frame for
+        //# different connection attempts.
+        self.tokens.lock().ok()?.remove(remote_address)
+    }
+
+    /// Stores the token for the server at `remote_address`, replacing any older token
+    pub fn insert(&self, remote_address: SocketAddress, token: &[u8]) {
+        let mut tokens = if let Ok(tokens) = self.tokens.lock() {
+            tokens
+        } else {
+            return;
+        };
+
+        if tokens.len() >= MAX_STORED_TOKENS && !tokens.contains_key(&remote_address) {
+            // make room by evicting the token of an arbitrary server
+            if let Some(evicted) = tokens.keys().next().copied() {
+                tokens.remove(&evicted);
+            }
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# For a client, this ambiguity means that
+        //# sending the most recent unused token is most likely to be effective.
+        tokens.insert(remote_address, token.to_vec());
+    }
+}
+
+/// Sends or receives the NEW_TOKEN frames of a connection
+#[derive(Debug, Default)]
+pub struct Manager {
+    state: State,
+}
+
+#[derive(Debug, Default)]
+enum State {
+    /// NEW_TOKEN frames are not used on the connection
+    #[default]
+    Disabled,
+
+    /// The server delivers a token to the client
+    Sender { token: Vec<u8>, delivery: Delivery },
+
+    /// The client stores the tokens of the server at `remote_address`
+    Receiver {
+        store: Store,
+        remote_address: SocketAddress,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Delivery {
+    /// The token is sent once the handshake is confirmed
+    AwaitingConfirmation,
+
+    /// The token needs to be transmitted
+    RequiresTransmission,
+
+    /// The token was lost and needs to be retransmitted
+    RequiresRetransmission,
+
+    /// The token has been transmitted and is pending acknowledgement
+    InFlight(PacketNumber),
+
+    /// The token has been delivered
+    Delivered,
+}
+
+impl Manager {
+    /// Creates a manager which sends `token` to the client
+    pub fn sender(token: Vec<u8>) -> Self {
+        debug_assert!(!token.is_empty());
+        Self {
+            state: State::Sender {
+                token,
+                delivery: Delivery::AwaitingConfirmation,
+            },
+        }
+    }
+
+    /// Creates a manager which stores the tokens of the server at `remote_address`
+    pub fn receiver(store: Store, remote_address: SocketAddress) -> Self {
+        Self {
+            state: State::Receiver {
+                store,
+                remote_address,
+            },
+        }
+    }
+
+    /// Called when the handshake is confirmed
+    pub fn on_handshake_confirmed(&mut self) {
+        if let State::Sender { delivery, .. } = &mut self.state {
+            if *delivery == Delivery::AwaitingConfirmation {
+                *delivery = Delivery::RequiresTransmission;
+            }
+        }
+    }
+
+    /// Called when a NEW_TOKEN frame is received on the client
+    pub fn on_new_token_frame(&mut self, frame: &NewToken) {
+        if let State::Receiver {
+            store,
+            remote_address,
+        } = &self.state
+        {
+            store.insert(*remote_address, frame.token);
+        }
+    }
+
+    /// Called when a packet is acknowledged by the peer
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::Sender { token, delivery } = &mut self.state {
+            if let Delivery::InFlight(packet_number) = delivery {
+                if ack_set.contains(*packet_number) {
+                    *delivery = Delivery::Delivered;
+                    // the token is no longer needed
+                    *token = Vec::new();
+                }
+            }
+        }
+    }
+
+    /// Called when a packet is declared lost
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::Sender { delivery, .. } = &mut self.state {
+            if let Delivery::InFlight(packet_number) = delivery {
+                if ack_set.contains(*packet_number) {
+                    *delivery = Delivery::RequiresRetransmission;
+                }
+            }
+        }
+    }
+
+    /// Writes the NEW_TOKEN frame, if needed
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        if let State::Sender { token, delivery } = &mut self.state {
+            let constraint = context.transmission_constraint();
+            let can_send = match delivery {
+                Delivery::RequiresTransmission => constraint.can_transmit(),
+                Delivery::RequiresRetransmission => constraint.can_retransmit(),
+                _ => false,
+            };
+
+            if !can_send {
+                return;
+            }
+
+            debug_assert!(
+                context.local_endpoint_type().is_server(),
+                "Only servers should transmit NEW_TOKEN frames"
+            );
+
+            if let Some(packet_number) = context.write_frame(&NewToken {
+                token: token.as_slice(),
+            }) {
+                *delivery = Delivery::InFlight(packet_number);
+            }
+        }
+    }
+}
+
+impl transmission::interest::Provider for Manager {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        match &self.state {
+            State::Sender {
+                delivery: Delivery::RequiresTransmission,
+                ..
+            } => query.on_new_data(),
+            State::Sender {
+                delivery: Delivery::RequiresRetransmission,
+                ..
+            } => query.on_lost_data(),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{contexts::testing::*, transmission::interest::Provider};
+    use s2n_quic_core::{endpoint, frame::Frame, time::clock::testing as time};
+
+    #[test]
+    fn sender_test() {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+
+        let mut manager = Manager::sender(vec![1, 2, 3]);
+
+        // the token isn't sent until the handshake is confirmed
+        assert!(!manager.has_transmission_interest());
+        manager.on_transmit(&mut context);
+        assert!(context.frame_buffer.is_empty());
+
+        manager.on_handshake_confirmed();
+        assert_eq!(
+            manager.get_transmission_interest(),
+            transmission::Interest::NewData
+        );
+
+        manager.on_transmit(&mut context);
+        let packet_number = {
+            let mut frame = context.frame_buffer.pop_front().unwrap();
+            assert!(matches!(
+                frame.as_frame(),
+                Frame::NewToken(NewToken { token: &[1, 2, 3] })
+            ));
+            frame.packet_nr
+        };
+        assert!(!manager.has_transmission_interest());
+
+        // the token is retransmitted after it's lost
+        manager.on_packet_loss(&packet_number);
+        assert_eq!(
+            manager.get_transmission_interest(),
+            transmission::Interest::LostData
+        );
+
+        manager.on_transmit(&mut context);
+        let packet_number = context.frame_buffer.pop_front().unwrap().packet_nr;
+
+        manager.on_packet_ack(&packet_number);
+        assert!(!manager.has_transmission_interest());
+
+        // the token isn't sent again after it's delivered
+        manager.on_packet_loss(&packet_number);
+        assert!(!manager.has_transmission_interest());
+    }
+
+    #[test]
+    fn receiver_test() {
+        let store = Store::default();
+        let first_server = SocketAddress::default();
+        let second_server: SocketAddress = "127.0.0.1:443"
+            .parse::<std::net::SocketAddr>()
+            .unwrap()
+            .into();
+
+        let mut manager = Manager::receiver(store.clone(), first_server);
+        manager.on_new_token_frame(&NewToken { token: &[1] });
+        // the most recent token replaces the older token
+        manager.on_new_token_frame(&NewToken { token: &[2] });
+
+        assert_eq!(store.take(&second_server), None);
+        assert_eq!(store.take(&first_server), Some(vec![2]));
+        // the token can only be used once
+        assert_eq!(store.take(&first_server), None);
+    }
+}
@@ -6,8 +6,8 @@ use crate::{
     connection::{self, limits::Limits},
     endpoint, path,
     space::{
-        datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
-        InitialSpace,
+        datagram, keep_alive::KeepAlive, new_token, ApplicationSpace, HandshakeSpace,
+        HandshakeStatus, InitialSpace,
     },
     stream,
 };
@@ -48,10 +48,12 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
         Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>,
     >,
     pub handshake_status: &'a mut HandshakeStatus,
+    pub new_token: &'a mut new_token::Manager,
     pub local_id_registry: &'a mut connection::LocalIdRegistry,
     pub limits: &'a mut Limits,
     pub server_name: &'a mut Option<ServerName>,
     pub application_protocol: &'a mut Bytes,
+    pub peer_certificate_chain: &'a mut Vec<Bytes>,
     pub waker: &'a Waker,
     pub publisher: &'a mut Pub,
     pub datagram: &'a mut Config::DatagramEndpoint,
@@ -406,6 +408,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             datagram_sender,
             datagram_receiver,
             datagram_limits.max_datagram_payload,
+            self.limits.dscp(),
         );
 
         self.path_manager
@@ -423,7 +426,10 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             ack_manager,
             keep_alive,
             max_mtu,
+            self.limits.key_update_packet_threshold(),
             datagram_manager,
+            core::mem::take(self.new_token),
+            self.limits.replay_window(),
         )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::OneRtt { generation: 0 },
@@ -457,6 +463,12 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_peer_certificate_chain(&mut self, chain: Vec<Bytes>) -> Result<(), transport::Error> {
+        *self.peer_certificate_chain = chain;
+
+        Ok(())
+    }
+
     fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
         // After the handshake is complete, the handshake crypto stream should be completely
         // finished
@@ -529,9 +541,13 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             .map(|bytes| bytes.freeze())
     }
 
-    fn receive_application(&mut self, _max_len: Option<usize>) -> Option<Bytes> {
-        // Application doesn't currently have a buffer
-        None
+    fn receive_application(&mut self, max_len: Option<usize>) -> Option<Bytes> {
+        self.application
+            .as_deref_mut()?
+            .crypto_stream
+            .rx
+            .pop_watermarked(max_len.unwrap_or(usize::MAX))
+            .map(|bytes| bytes.freeze())
     }
 
     fn can_send_initial(&self) -> bool {
@@ -567,11 +583,19 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
     }
 
     fn can_send_application(&self) -> bool {
-        false
+        self.application
+            .as_ref()
+            .map(|space| space.crypto_stream.can_send())
+            .unwrap_or_default()
     }
 
-    fn send_application(&mut self, _transmission: Bytes) {
-        unimplemented!("application level crypto frames cannot currently be sent")
+    fn send_application(&mut self, transmission: Bytes) {
+        self.application
+            .as_mut()
+            .expect("can_send_application should be called before sending")
+            .crypto_stream
+            .tx
+            .push(transmission);
     }
 
     fn waker(&self) -> &Waker {
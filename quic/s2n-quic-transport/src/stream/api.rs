@@ -10,10 +10,11 @@ use core::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 pub use s2n_quic_core::{
     application,
-    stream::{ops, StreamError, StreamId, StreamType},
+    stream::{ops, scheduler::Priority, StreamError, StreamId, StreamType},
 };
 
 #[derive(Clone)]
@@ -22,6 +23,8 @@ struct State {
     stream_id: StreamId,
     rx: ops::Status,
     tx: ops::Status,
+    /// The highest acknowledged offset reported by the tx stream
+    tx_acked_offset: u64,
 }
 
 impl State {
@@ -31,6 +34,7 @@ impl State {
             stream_id,
             rx: ops::Status::Open,
             tx: ops::Status::Open,
+            tx_acked_offset: 0,
         }
     }
 
@@ -224,6 +228,46 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Initiates a `RESET` on the stream with the provided `error_code` if the stream
+        /// doesn't make any progress for the given `timeout`.
+        pub fn set_tx_inactivity_timeout(
+            &mut self,
+            timeout: Duration,
+            error_code: application::Error,
+        ) -> Result<(), StreamError> {
+            self.tx_request()?
+                .with_tx_inactivity_timeout(timeout, error_code)
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the transmission priority of the stream.
+        ///
+        /// The priority is used by the connection's stream scheduler to decide which stream
+        /// transmits next when multiple streams have data to send.
+        pub fn set_priority(&mut self, priority: Priority) -> Result<(), StreamError> {
+            self.tx_request()?.with_priority(priority).poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the DSCP code point which marks the packets carrying the stream's data.
+        ///
+        /// Only the lower 6 bits of `dscp` are used.
+        pub fn set_dscp(&mut self, dscp: u8) -> Result<(), StreamError> {
+            self.tx_request()?.with_dscp(dscp).poll(None)?;
+            Ok(())
+        }
+
+        /// Returns the number of bytes at the start of the stream which were acknowledged by the
+        /// peer.
+        ///
+        /// The method will return:
+        /// - `Ok(offset)` with the offset up to which all of the data has been acknowledged.
+        /// - `Err(stream_error)` if the stream had previously entered an error state.
+        pub fn acked_offset(&mut self) -> Result<u64, StreamError> {
+            Ok(self.tx_request()?.poll(None)?.acked_offset)
+        }
     };
 }
 
@@ -302,6 +346,19 @@ macro_rules! rx_stream_apis {
             self.rx_request()?.stop_sending(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Sends a `STOP_SENDING` message to the peer with the provided `error_code` if the
+        /// stream doesn't make any progress for the given `timeout`.
+        pub fn set_rx_inactivity_timeout(
+            &mut self,
+            timeout: Duration,
+            error_code: application::Error,
+        ) -> Result<(), StreamError> {
+            self.rx_request()?
+                .with_rx_inactivity_timeout(timeout, error_code)
+                .poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -494,6 +551,25 @@ macro_rules! tx_request_apis {
             self.request.flush();
             self
         }
+
+        pub fn with_tx_inactivity_timeout(
+            &mut self,
+            timeout: Duration,
+            error_code: application::Error,
+        ) -> &mut Self {
+            self.request.with_tx_inactivity_timeout(timeout, error_code);
+            self
+        }
+
+        pub fn with_priority(&mut self, priority: Priority) -> &mut Self {
+            self.request.with_priority(priority);
+            self
+        }
+
+        pub fn with_dscp(&mut self, dscp: u8) -> &mut Self {
+            self.request.with_dscp(dscp);
+            self
+        }
     };
 }
 
@@ -523,6 +599,15 @@ macro_rules! rx_request_apis {
             self.request.stop_sending(error_code);
             self
         }
+
+        pub fn with_rx_inactivity_timeout(
+            &mut self,
+            timeout: Duration,
+            error_code: application::Error,
+        ) -> &mut Self {
+            self.request.with_rx_inactivity_timeout(timeout, error_code);
+            self
+        }
     };
 }
 
@@ -552,6 +637,7 @@ impl<'state, 'chunks> Request<'state, 'chunks> {
             return Ok(ops::Response {
                 tx: Some(ops::tx::Response {
                     status: ops::Status::Finished,
+                    acked_offset: self.state.tx_acked_offset,
                     ..Default::default()
                 }),
                 rx: Some(ops::rx::Response {
@@ -569,6 +655,7 @@ impl<'state, 'chunks> Request<'state, 'chunks> {
 
         if let Some(tx) = response.tx() {
             self.state.tx = tx.status;
+            self.state.tx_acked_offset = self.state.tx_acked_offset.max(tx.acked_offset);
         }
 
         Ok(response)
@@ -600,6 +687,7 @@ impl<'state, 'chunks> TxRequest<'state, 'chunks> {
             // removed from the stream container already
             return Ok(ops::tx::Response {
                 status: ops::Status::Finished,
+                acked_offset: self.state.tx_acked_offset,
                 ..Default::default()
             });
         }
@@ -611,6 +699,7 @@ impl<'state, 'chunks> TxRequest<'state, 'chunks> {
             .expect("invalid response");
 
         self.state.tx = response.status;
+        self.state.tx_acked_offset = self.state.tx_acked_offset.max(response.acked_offset);
 
         Ok(response)
     }
@@ -0,0 +1,212 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bounds the amount of stream data which is buffered by a connection
+
+use alloc::{rc::Rc, vec::Vec};
+use core::{cell::RefCell, task::Waker};
+
+/// The amount of buffered bytes at which memory pressure is signaled, as a fraction of the
+/// maximum
+const PRESSURE_THRESHOLD_PERCENT: usize = 80;
+
+/// Describes the memory usage of a connection which is approaching its maximum
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryPressure {
+    pub buffered_bytes: usize,
+    pub max_buffered_bytes: usize,
+}
+
+/// The actual implementation/state of the per Connection buffer budget
+#[derive(Debug)]
+struct BufferBudgetImpl {
+    /// The maximum number of bytes which can be buffered by all of the streams
+    max_buffered_bytes: usize,
+    /// The number of bytes which are currently buffered by send and receive streams
+    buffered_bytes: usize,
+    /// Set while the connection is above the pressure threshold
+    ///
+    /// This is only cleared once the buffered bytes drop below half of the maximum, in order
+    /// to avoid signaling pressure every time the buffers oscillate around the threshold.
+    is_under_pressure: bool,
+    /// Set if the pressure still needs to be reported to the application
+    pending_pressure: Option<MemoryPressure>,
+    /// Tasks which are waiting for the budget to write more data to a stream
+    blocked_writers: Vec<Waker>,
+}
+
+impl BufferBudgetImpl {
+    fn available(&self) -> usize {
+        self.max_buffered_bytes.saturating_sub(self.buffered_bytes)
+    }
+
+    fn pressure_threshold(&self) -> usize {
+        self.max_buffered_bytes / 100 * PRESSURE_THRESHOLD_PERCENT
+            + self.max_buffered_bytes % 100 * PRESSURE_THRESHOLD_PERCENT / 100
+    }
+
+    fn reserve(&mut self, len: usize) {
+        self.buffered_bytes = self.buffered_bytes.saturating_add(len);
+
+        if !self.is_under_pressure && self.buffered_bytes >= self.pressure_threshold() {
+            self.is_under_pressure = true;
+            self.pending_pressure = Some(MemoryPressure {
+                buffered_bytes: self.buffered_bytes,
+                max_buffered_bytes: self.max_buffered_bytes,
+            });
+        }
+    }
+
+    fn release(&mut self, len: usize) {
+        debug_assert!(
+            len <= self.buffered_bytes,
+            "Can not release more bytes than previously reserved"
+        );
+        self.buffered_bytes = self.buffered_bytes.saturating_sub(len);
+
+        if self.is_under_pressure && self.buffered_bytes < self.max_buffered_bytes / 2 {
+            self.is_under_pressure = false;
+        }
+    }
+}
+
+/// Tracks the memory used for buffering stream data across all of the streams of a connection.
+///
+/// Outgoing data is accounted for from the time it is written by the application until it is
+/// acknowledged by the peer. Incoming data is accounted for from the time flow control credits
+/// are used by the peer until the application reads the data, which includes any gaps that are
+/// still being reassembled.
+#[derive(Clone, Debug)]
+pub struct BufferBudget {
+    inner: Rc<RefCell<BufferBudgetImpl>>,
+}
+
+impl BufferBudget {
+    /// Creates a new `BufferBudget` which allows `max_buffered_bytes` to be buffered
+    pub fn new(max_buffered_bytes: usize) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(BufferBudgetImpl {
+                max_buffered_bytes,
+                buffered_bytes: 0,
+                is_under_pressure: false,
+                pending_pressure: None,
+                blocked_writers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns the number of bytes which can still be buffered
+    pub fn available(&self) -> usize {
+        self.inner.borrow().available()
+    }
+
+    /// Accounts for `len` additional bytes being buffered
+    pub fn reserve(&mut self, len: usize) {
+        self.inner.borrow_mut().reserve(len)
+    }
+
+    /// Accounts for `len` bytes which are no longer buffered
+    pub fn release(&mut self, len: usize) {
+        self.inner.borrow_mut().release(len)
+    }
+
+    /// Registers a task which can't write to a stream until budget is released
+    pub fn on_blocked_writer(&mut self, waker: &Waker) {
+        let blocked_writers = &mut self.inner.borrow_mut().blocked_writers;
+
+        if !blocked_writers.iter().any(|w| w.will_wake(waker)) {
+            blocked_writers.push(waker.clone());
+        }
+    }
+
+    /// Wakes all of the blocked writers if budget is available again
+    pub fn wake_blocked_writers(&mut self) {
+        let blocked_writers = {
+            let mut inner = self.inner.borrow_mut();
+
+            if inner.blocked_writers.is_empty() || inner.available() == 0 {
+                return;
+            }
+
+            core::mem::take(&mut inner.blocked_writers)
+        };
+
+        for waker in blocked_writers {
+            waker.wake();
+        }
+    }
+
+    /// Returns the memory pressure if the connection crossed the pressure threshold since the
+    /// last call
+    pub fn take_pressure(&mut self) -> Option<MemoryPressure> {
+        self.inner.borrow_mut().pending_pressure.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_test::task::new_count_waker;
+
+    #[test]
+    fn pressure_test() {
+        let mut budget = BufferBudget::new(1000);
+
+        budget.reserve(799);
+        assert_eq!(budget.take_pressure(), None);
+
+        budget.reserve(1);
+        assert_eq!(
+            budget.take_pressure(),
+            Some(MemoryPressure {
+                buffered_bytes: 800,
+                max_buffered_bytes: 1000,
+            })
+        );
+        // pressure is only reported once
+        assert_eq!(budget.take_pressure(), None);
+
+        // dropping below the threshold doesn't report pressure again
+        budget.release(100);
+        budget.reserve(100);
+        assert_eq!(budget.take_pressure(), None);
+
+        // dropping below half of the maximum clears the pressure
+        budget.release(301);
+        budget.reserve(301);
+        assert!(budget.take_pressure().is_some());
+    }
+
+    #[test]
+    fn unlimited_test() {
+        let mut budget = BufferBudget::new(usize::MAX);
+
+        budget.reserve(u32::MAX as usize);
+        assert_eq!(budget.take_pressure(), None);
+        assert_eq!(budget.available(), usize::MAX - u32::MAX as usize);
+    }
+
+    #[test]
+    fn blocked_writers_test() {
+        let mut budget = BufferBudget::new(100);
+        let (waker, wake_counter) = new_count_waker();
+
+        budget.reserve(100);
+        assert_eq!(budget.available(), 0);
+
+        // registering the same task multiple times only wakes it once
+        budget.on_blocked_writer(&waker);
+        budget.on_blocked_writer(&waker);
+
+        // writers aren't woken until some budget is available
+        budget.wake_blocked_writers();
+        assert_eq!(wake_counter, 0);
+
+        budget.release(1);
+        budget.wake_blocked_writers();
+        assert_eq!(wake_counter, 1);
+
+        budget.wake_blocked_writers();
+        assert_eq!(wake_counter, 1);
+    }
+}
@@ -75,6 +75,7 @@ impl Controller {
             ),
             remote_bidi_controller: RemoteInitiated::new(
                 initial_local_limits.max_open_remote_bidirectional_streams,
+                stream_limits.remote_stream_limit_policy,
             ),
             local_uni_controller: LocalInitiated::new(
                 initial_peer_limits.max_open_remote_unidirectional_streams,
@@ -82,6 +83,7 @@ impl Controller {
             ),
             remote_uni_controller: RemoteInitiated::new(
                 initial_local_limits.max_open_remote_unidirectional_streams,
+                stream_limits.remote_stream_limit_policy,
             ),
         }
     }
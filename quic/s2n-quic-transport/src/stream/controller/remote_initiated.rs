@@ -8,7 +8,11 @@ use crate::{
     transmission::WriteContext,
 };
 use s2n_quic_core::{
-    ack, frame::MaxStreams, packet::number::PacketNumber, stream::StreamId, transport,
+    ack,
+    frame::MaxStreams,
+    packet::number::PacketNumber,
+    stream::{limits::StreamLimitPolicy, StreamId},
+    transport,
     varint::VarInt,
 };
 
@@ -33,8 +37,10 @@ pub(super) struct RemoteInitiated {
     /// The max stream limit specified by the local endpoint.
     ///
     /// Used to calculate updated max_streams_sync value as the peer
-    /// closes streams.
+    /// closes streams. The limit only changes with the `Grow` policy.
     max_local_limit: VarInt,
+    /// Controls how `max_local_limit` is managed once the peer uses all of its streams
+    policy: StreamLimitPolicy,
     /// Responsible for advertising updated max stream frames as the
     /// peer closes streams
     max_streams_sync: IncrementalValueSync<VarInt, MaxStreamsToFrameWriter>,
@@ -43,9 +49,10 @@ pub(super) struct RemoteInitiated {
 }
 
 impl RemoteInitiated {
-    pub fn new(max_local_limit: VarInt) -> Self {
+    pub fn new(max_local_limit: VarInt, policy: StreamLimitPolicy) -> Self {
         Self {
             max_local_limit,
+            policy,
             max_streams_sync: IncrementalValueSync::new(
                 max_local_limit,
                 max_local_limit,
@@ -80,7 +87,8 @@ impl RemoteInitiated {
             //# An endpoint MUST terminate a connection
             //# with an error of type STREAM_LIMIT_ERROR if a peer opens more streams
             //# than was permitted.
-            return Err(transport::Error::STREAM_LIMIT_ERROR);
+            return Err(transport::Error::STREAM_LIMIT_ERROR
+                .with_reason("The peer opened more streams than permitted by MAX_STREAMS"));
         }
         Ok(())
     }
@@ -89,19 +97,46 @@ impl RemoteInitiated {
     pub fn on_open_stream(&mut self) {
         self.opened_streams += 1;
 
+        if let StreamLimitPolicy::Grow(ceiling) = self.policy {
+            // grow the limit once the peer uses all of its streams, rather than holding the peer
+            // at the limit until streams are closed
+            if self.open_stream_count() >= self.max_local_limit {
+                let ceiling = VarInt::new(ceiling)
+                    .unwrap_or(MAX_STREAMS_MAX_VALUE)
+                    .min(MAX_STREAMS_MAX_VALUE);
+                let limit = self
+                    .max_local_limit
+                    .saturating_mul(VarInt::from_u8(2))
+                    .max(VarInt::from_u8(1))
+                    .min(ceiling);
+
+                if limit > self.max_local_limit {
+                    self.max_local_limit = limit;
+                    self.update_max_streams();
+                }
+            }
+        }
+
         self.check_integrity();
     }
 
     pub fn on_close_stream(&mut self) {
         self.closed_streams += 1;
 
+        if self.policy != StreamLimitPolicy::Reject {
+            self.update_max_streams();
+        }
+
+        self.check_integrity();
+    }
+
+    #[inline]
+    fn update_max_streams(&mut self) {
         let max_streams = self
             .closed_streams
             .saturating_add(self.max_local_limit)
             .min(MAX_STREAMS_MAX_VALUE);
         self.max_streams_sync.update_latest_value(max_streams);
-
-        self.check_integrity();
     }
 
     /// Returns the number of streams currently open
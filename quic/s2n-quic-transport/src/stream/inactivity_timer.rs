@@ -0,0 +1,95 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the inactivity of one half of a `Stream`
+
+use core::time::Duration;
+use s2n_quic_core::{
+    application,
+    time::{timer, Timer, Timestamp},
+};
+
+/// Expires after a half of a stream didn't make any progress for a configured amount of time
+///
+/// Activity on the stream is tracked with a flag rather than a timestamp, since the current
+/// time isn't available in all of the places that make progress. As such, the timer expires
+/// between one and two periods after the last activity.
+#[derive(Debug, Default)]
+pub struct InactivityTimer {
+    /// The configured timeout along with the error which is used to close the stream
+    config: Option<(Duration, application::Error)>,
+    /// The timer which is armed once the current time is known
+    timer: Timer,
+    /// Set if the stream made progress since the timer was last armed
+    has_activity: bool,
+    /// Set if the timer was configured but hasn't been armed yet
+    needs_arming: bool,
+}
+
+impl InactivityTimer {
+    /// Configures the timer to expire after `timeout` without any activity
+    ///
+    /// The timer is armed when the connection is next woken up, which doesn't depend on the
+    /// connection being able to transmit.
+    #[inline]
+    pub fn configure(&mut self, timeout: Duration, error: application::Error) {
+        self.config = Some((timeout, error));
+        self.timer.cancel();
+        self.has_activity = false;
+        self.needs_arming = true;
+    }
+
+    /// Returns `true` if the timer is waiting on the current time to be armed
+    #[inline]
+    pub fn needs_arming(&self) -> bool {
+        self.needs_arming
+    }
+
+    /// Returns `true` if the timer is armed
+    #[inline]
+    pub fn is_armed(&self) -> bool {
+        self.timer.is_armed()
+    }
+
+    /// Called when the stream made progress
+    #[inline]
+    pub fn on_activity(&mut self) {
+        self.has_activity = true;
+    }
+
+    /// Returns the configured error if the stream was inactive for the entire period
+    ///
+    /// A timer which was configured since the last call is armed instead.
+    #[inline]
+    pub fn on_timeout(&mut self, now: Timestamp) -> Option<application::Error> {
+        let (timeout, error) = self.config?;
+
+        if core::mem::take(&mut self.needs_arming) {
+            self.timer.set(now + timeout);
+            return None;
+        }
+
+        if self.timer.poll_expiration(now).is_pending() {
+            return None;
+        }
+
+        if core::mem::take(&mut self.has_activity) {
+            // the stream made progress during the last period so start a new one
+            self.timer.set(now + timeout);
+            return None;
+        }
+
+        // the timer only fires once
+        self.config = None;
+
+        Some(error)
+    }
+}
+
+impl timer::Provider for InactivityTimer {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)?;
+        Ok(())
+    }
+}
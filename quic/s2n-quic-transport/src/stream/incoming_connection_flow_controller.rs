@@ -5,6 +5,7 @@
 
 use crate::{
     contexts::{OnTransmitError, WriteContext},
+    stream::buffer_budget::BufferBudget,
     sync::{IncrementalValueSync, ValueToFrameWriter},
     transmission,
 };
@@ -46,10 +47,16 @@ struct IncomingConnectionFlowControllerImpl {
     /// The amount of flow control credits which had been acquired and where the
     /// data had already been consumed by the application
     pub(super) consumed_window: VarInt,
+    /// Bounds the amount of data which the peer is allowed to send before it is consumed
+    buffer_budget: BufferBudget,
 }
 
 impl IncomingConnectionFlowControllerImpl {
-    pub fn new(initial_window_size: VarInt, desired_flow_control_window: u32) -> Self {
+    pub fn new(
+        initial_window_size: VarInt,
+        desired_flow_control_window: u32,
+        buffer_budget: BufferBudget,
+    ) -> Self {
         Self {
             read_window_sync: IncrementalValueSync::new(
                 VarInt::from_u32(desired_flow_control_window),
@@ -59,6 +66,7 @@ impl IncomingConnectionFlowControllerImpl {
             desired_flow_control_window,
             acquired_window: VarInt::from_u32(0),
             consumed_window: VarInt::from_u32(0),
+            buffer_budget,
         }
     }
 
@@ -77,11 +85,25 @@ impl IncomingConnectionFlowControllerImpl {
             self.consumed_window <= self.acquired_window,
             "Can not consume more window than previously acquired"
         );
+        self.buffer_budget.release(amount.as_u64() as usize);
 
-        self.read_window_sync.update_latest_value(
-            self.consumed_window
-                .saturating_add(VarInt::from_u32(self.desired_flow_control_window)),
-        );
+        self.update_window();
+    }
+
+    /// Extends the window by the desired amount, as long as the connection is able to buffer
+    /// the additional data
+    pub fn update_window(&mut self) {
+        let window = self
+            .consumed_window
+            .saturating_add(VarInt::from_u32(self.desired_flow_control_window));
+
+        // Everything which was acquired is already accounted for in the budget
+        let available = VarInt::try_from(self.buffer_budget.available()).unwrap_or(VarInt::MAX);
+        let window = window.min(self.acquired_window.saturating_add(available));
+
+        if window > self.read_window_sync.latest_value() {
+            self.read_window_sync.update_latest_value(window);
+        }
     }
 
     pub fn acquire_window(&mut self, desired: VarInt) -> Result<(), transport::Error> {
@@ -94,6 +116,7 @@ impl IncomingConnectionFlowControllerImpl {
         }
 
         self.acquired_window += desired;
+        self.buffer_budget.reserve(desired.as_u64() as usize);
         Ok(())
     }
 
@@ -134,11 +157,19 @@ impl IncomingConnectionFlowController {
     /// `desired_flow_control_window`. This means if the window which is indicated
     /// to the peer is lower than this value the new value will be communicated
     /// to the peer.
-    pub fn new(initial_window_size: VarInt, desired_flow_control_window: u32) -> Self {
+    ///
+    /// Data which was received but not yet consumed is accounted for in the `buffer_budget`.
+    /// The window is only extended as far as the budget allows.
+    pub fn new(
+        initial_window_size: VarInt,
+        desired_flow_control_window: u32,
+        buffer_budget: BufferBudget,
+    ) -> Self {
         Self {
             inner: Rc::new(RefCell::new(IncomingConnectionFlowControllerImpl::new(
                 initial_window_size,
                 desired_flow_control_window,
+                buffer_budget,
             ))),
         }
     }
@@ -157,6 +188,13 @@ impl IncomingConnectionFlowController {
         self.inner.borrow_mut().release_window(amount)
     }
 
+    /// Extends the window if the connection is able to buffer more data
+    ///
+    /// This is called after outgoing data was released from the buffer budget.
+    pub fn update_window(&mut self) {
+        self.inner.borrow_mut().update_window()
+    }
+
     /// This method gets called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.inner.borrow_mut().on_packet_ack(ack_set)
@@ -9,6 +9,7 @@ use crate::{
     recovery::RttEstimator,
     stream::{
         self,
+        buffer_budget::{BufferBudget, MemoryPressure},
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         stream_container::{StreamContainer, StreamContainerIterationResult},
@@ -32,7 +33,7 @@ use s2n_quic_core::{
         StopSending, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::PacketNumberSpace,
-    stream::{iter::StreamIter, ops, StreamId, StreamType},
+    stream::{iter::StreamIter, ops, scheduler::Scheduler, StreamId, StreamType},
     time::{timer, Timestamp},
     transport::{self, parameters::InitialFlowControlLimits},
     varint::VarInt,
@@ -165,6 +166,12 @@ pub struct StreamManagerState<S> {
     pub(super) incoming_connection_flow_controller: IncomingConnectionFlowController,
     /// Flow control credit manager for sending data
     pub(super) outgoing_connection_flow_controller: OutgoingConnectionFlowController,
+    /// Bounds the amount of data which is buffered by all of the streams
+    pub(super) buffer_budget: BufferBudget,
+    /// Decides which stream transmits next when multiple streams have data to send
+    scheduler: &'static dyn Scheduler,
+    /// The DSCP code point of the streams which don't set their own
+    dscp: u8,
     /// Controller for managing streams concurrency limits
     stream_controller: stream::Controller,
     /// A container which contains all Streams
@@ -242,12 +249,14 @@ impl<S: StreamTrait> StreamManagerState<S> {
         self.streams.insert_stream(S::new(StreamConfig {
             incoming_connection_flow_controller: self.incoming_connection_flow_controller.clone(),
             outgoing_connection_flow_controller: self.outgoing_connection_flow_controller.clone(),
+            buffer_budget: self.buffer_budget.clone(),
             local_endpoint_type: self.local_endpoint_type,
             stream_id,
             initial_receive_window,
             desired_flow_control_window: initial_receive_window.as_u64() as u32,
             initial_send_window,
             max_send_buffer_size: self.stream_limits.max_send_buffer_size.as_u32(),
+            max_receive_gaps: self.stream_limits.max_receive_gaps,
         }));
     }
 
@@ -549,6 +558,20 @@ impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
 
         result
     }
+
+    /// Arms or expires the inactivity timers of the streams which configured one
+    fn on_inactivity_timeouts(&mut self, now: Timestamp) {
+        self.inner.streams.iterate_inactivity_timeout_list(
+            &mut self.inner.stream_controller,
+            |stream| {
+                // We have to wake inside the lock, since `StreamEvent`s has no capacity
+                // to carry wakers in another iteration
+                let mut events = StreamEvents::new();
+                stream.on_inactivity_timeout(now, &mut events);
+                events.wake_all();
+            },
+        );
+    }
 }
 
 impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
@@ -569,15 +592,21 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             "Receive window must not exceed 32bit range"
         );
 
+        let buffer_budget = BufferBudget::new(connection_limits.max_connection_buffer_bytes());
+
         Self {
             inner: StreamManagerState {
                 incoming_connection_flow_controller: IncomingConnectionFlowController::new(
                     initial_local_limits.max_data,
                     initial_local_limits.max_data.as_u64() as u32,
+                    buffer_budget.clone(),
                 ),
                 outgoing_connection_flow_controller: OutgoingConnectionFlowController::new(
                     initial_peer_limits.max_data,
                 ),
+                buffer_budget,
+                scheduler: connection_limits.stream_scheduler(),
+                dscp: connection_limits.dscp(),
                 stream_controller: stream::Controller::new(
                     local_endpoint_type,
                     initial_peer_limits,
@@ -707,6 +736,13 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 events.wake_all();
             },
         );
+
+        // Acknowledged data is released from the buffer budget, which allows the peer and
+        // other streams to make progress
+        self.inner
+            .incoming_connection_flow_controller
+            .update_window();
+        self.inner.buffer_budget.wake_blocked_writers();
     }
 
     fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
@@ -779,6 +815,12 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 StreamContainerIterationResult::Continue
             },
         );
+        self.on_inactivity_timeouts(now);
+    }
+
+    fn on_wakeup(&mut self, now: Timestamp) {
+        // arm the inactivity timers configured since the last wakeup
+        self.on_inactivity_timeouts(now);
     }
 
     fn close(&mut self, error: connection::Error) {
@@ -812,6 +854,10 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         // transmit interested again
         let mut transmit_result = Ok(());
 
+        // Only streams marked with the same DSCP code point as the packet can write to it
+        let dscp = context.dscp();
+        let default_dscp = self.inner.dscp;
+
         if context.transmission_constraint().can_retransmit() {
             // ensure components only retransmit in this phase
             let mut retransmission_context =
@@ -821,6 +867,10 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             self.inner.streams.iterate_retransmission_list(
                 &mut self.inner.stream_controller,
                 |stream: &mut S| {
+                    if stream.dscp().unwrap_or(default_dscp) != dscp {
+                        return StreamContainerIterationResult::Continue;
+                    }
+
                     transmit_result = stream.on_transmit(&mut retransmission_context);
                     if transmit_result.is_err() {
                         StreamContainerIterationResult::BreakAndInsertAtBack
@@ -835,17 +885,61 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         }
 
         if context.transmission_constraint().can_transmit() {
-            self.inner.streams.iterate_transmission_list(
-                &mut self.inner.stream_controller,
-                |stream: &mut S| {
-                    transmit_result = stream.on_transmit(context);
-                    if transmit_result.is_err() {
-                        StreamContainerIterationResult::BreakAndInsertAtBack
-                    } else {
-                        StreamContainerIterationResult::Continue
-                    }
-                },
-            );
+            let scheduler = self.inner.scheduler;
+
+            loop {
+                let mut skipped_stream = false;
+                let capacity = context.remaining_capacity();
+
+                self.inner.streams.iterate_transmission_list_by_rank(
+                    &mut self.inner.stream_controller,
+                    |stream: &S| scheduler.rank(&stream.priority()),
+                    |stream: &mut S| {
+                        if stream.dscp().unwrap_or(default_dscp) != dscp {
+                            return StreamContainerIterationResult::Continue;
+                        }
+
+                        let priority = stream.priority();
+
+                        if !scheduler.on_turn(&priority, stream.scheduler_state()) {
+                            // let the other streams transmit before this one
+                            skipped_stream = true;
+                            return StreamContainerIterationResult::Continue;
+                        }
+
+                        let remaining_capacity = context.remaining_capacity();
+                        transmit_result = stream.on_transmit(context);
+                        if transmit_result.is_err() {
+                            return StreamContainerIterationResult::BreakAndInsertAtBack;
+                        }
+
+                        let len = remaining_capacity.saturating_sub(context.remaining_capacity());
+                        let yield_turn =
+                            scheduler.on_transmit(&priority, stream.scheduler_state(), len);
+                        let has_more_data = matches!(
+                            stream.get_stream_interests().transmission,
+                            transmission::Interest::NewData
+                        );
+
+                        if yield_turn || len == 0 || !has_more_data {
+                            StreamContainerIterationResult::Continue
+                        } else {
+                            // the stream filled the packet and keeps its turn for the next one
+                            StreamContainerIterationResult::BreakAndInsertAtBack
+                        }
+                    },
+                );
+
+                // Visit the streams again if all of them gave up their turn, since the packet
+                // would be left empty otherwise. Each skipped stream gains credits so this
+                // eventually terminates.
+                if !skipped_stream
+                    || transmit_result.is_err()
+                    || context.remaining_capacity() != capacity
+                {
+                    break;
+                }
+            }
         }
 
         // There is no `finalize_done_streams` here, since we do not expect to
@@ -951,17 +1045,60 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
-        self.perform_api_call(
+        let result = self.perform_api_call(
             stream_id,
             Err(StreamError::invalid_stream()),
             api_call_context,
             |stream| stream.poll_request(request, context),
-        )
+        );
+
+        // Inactivity timers are armed when the connection wakes up, since the connection may not
+        // be able to transmit while the stream is blocked
+        let configures_timer = request
+            .tx
+            .as_ref()
+            .map_or(false, |tx| tx.inactivity_timeout.is_some())
+            || request
+                .rx
+                .as_ref()
+                .map_or(false, |rx| rx.inactivity_timeout.is_some());
+        if configures_timer {
+            api_call_context.wakeup_handle().wakeup();
+        }
+
+        // Reading from or resetting a stream can release budget for streams that are blocked
+        self.inner.buffer_budget.wake_blocked_writers();
+
+        result
+    }
+
+    fn take_memory_pressure(&mut self) -> Option<MemoryPressure> {
+        self.inner.buffer_budget.take_pressure()
     }
 
     fn has_pending_streams(&self) -> bool {
         self.inner.streams.has_pending_streams()
     }
+
+    fn has_flow_control_blocked_streams(&self) -> bool {
+        self.inner.streams.has_flow_control_blocked_streams()
+    }
+
+    fn has_unacknowledged_streams(&self) -> bool {
+        self.inner.streams.has_unacknowledged_streams()
+    }
+
+    fn transmission_dscp(&self) -> Option<u8> {
+        let scheduler = self.inner.scheduler;
+        self.inner.streams.transmission_dscp(
+            |stream: &S| scheduler.rank(&stream.priority()),
+            self.inner.dscp,
+        )
+    }
+
+    fn set_dscp(&mut self, dscp: u8) {
+        self.inner.dscp = dscp;
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
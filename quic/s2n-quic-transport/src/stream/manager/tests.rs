@@ -40,7 +40,12 @@ use s2n_quic_core::{
         StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::{PacketNumberRange, PacketNumberSpace},
-    stream::{ops, StreamId, StreamType},
+    stream::{
+        limits::StreamLimitPolicy,
+        ops,
+        scheduler::{self, Priority, StrictPriority},
+        StreamId, StreamType,
+    },
     time::{
         clock::testing as time,
         timer::{self, Provider as _},
@@ -88,6 +93,9 @@ struct MockStream {
     poll_push_count: usize,
     poll_finish_count: usize,
     reset_count: usize,
+    priority: Priority,
+    scheduler_state: scheduler::State,
+    dscp: u8,
 }
 
 impl MockStream {
@@ -161,6 +169,9 @@ impl StreamTrait for MockStream {
             poll_push_count: 0,
             poll_finish_count: 0,
             reset_count: 0,
+            priority: Priority::default(),
+            scheduler_state: scheduler::State::default(),
+            dscp: 0,
         }
     }
 
@@ -260,6 +271,8 @@ impl StreamTrait for MockStream {
         self.on_timeout_count += 1;
     }
 
+    fn on_inactivity_timeout(&mut self, _now: Timestamp, _events: &mut StreamEvents) {}
+
     fn on_internal_reset(&mut self, _error: StreamError, events: &mut StreamEvents) {
         self.on_internal_reset_count += 1;
         if self.set_finalize_on_internal_reset {
@@ -303,6 +316,18 @@ impl StreamTrait for MockStream {
         self.on_connection_window_available_retrieve_window -= Into::<u64>::into(acquired_window);
     }
 
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    fn scheduler_state(&mut self) -> &mut scheduler::State {
+        &mut self.scheduler_state
+    }
+
+    fn dscp(&self) -> Option<u8> {
+        Some(self.dscp)
+    }
+
     fn poll_request(
         &mut self,
         request: &mut ops::Request,
@@ -1198,13 +1223,14 @@ fn stream_limit_error_on_peer_open_stream_too_large() {
             )
             .is_ok());
 
-        assert_eq!(
-            Err(transport::Error::STREAM_LIMIT_ERROR),
-            manager.with_stream_controller(|ctrl| {
+        let error = manager
+            .with_stream_controller(|ctrl| {
                 let open_id = max_stream_id.next_of_type().unwrap();
                 ctrl.on_open_remote_stream(StreamIter::new(open_id, open_id))
             })
-        );
+            .unwrap_err();
+        assert_eq!(error.code, transport::Error::STREAM_LIMIT_ERROR.code);
+        assert!(!error.reason.is_empty());
     }
 }
 
@@ -3109,3 +3135,290 @@ fn stream_transmission_fairness_test() {
         }
     }
 }
+
+/// Transmits packets which fit two frames each and returns the stream which wrote each packet
+fn transmit_packets(
+    manager: &mut AbstractStreamManager<MockStream>,
+    streams: &[StreamId],
+    packets: usize,
+) -> Vec<StreamId> {
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    frame_buffer.set_max_packet_size(Some(1200));
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+
+    let pending_frames = |manager: &mut AbstractStreamManager<MockStream>| -> Vec<usize> {
+        streams
+            .iter()
+            .map(|stream_id| {
+                manager
+                    .with_asserted_stream(*stream_id, |stream| stream.on_transmit_try_write_frames)
+            })
+            .collect()
+    };
+
+    let mut writers = vec![];
+    for _ in 0..packets {
+        let before = pending_frames(manager);
+
+        write_context.frame_buffer.set_error_write_after_n_frames(2);
+        let _ = manager.on_transmit(&mut write_context);
+        write_context.frame_buffer.flush();
+
+        let after = pending_frames(manager);
+        for (stream_id, (before, after)) in streams.iter().zip(before.iter().zip(after)) {
+            if after < *before {
+                writers.push(*stream_id);
+            }
+        }
+    }
+
+    writers
+}
+
+#[test]
+fn stream_scheduler_round_robin_test() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let streams: Vec<_> = (0..3)
+        .map(|_| try_open(&mut manager, StreamType::Bidirectional).unwrap())
+        .collect();
+
+    // each stream has enough data to fill several packets
+    for stream_id in &streams {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            stream.on_transmit_try_write_frames = 6;
+            stream.on_transmit_limit = Some(2);
+        });
+    }
+
+    let writers = transmit_packets(&mut manager, &streams, 9);
+
+    // the streams take turns filling the packets
+    let expected: Vec<_> = streams.iter().cycle().take(9).copied().collect();
+    assert_eq!(writers, expected);
+    assert!(manager.streams_waiting_for_transmission().is_empty());
+}
+
+#[test]
+fn stream_scheduler_strict_priority_test() {
+    let limits = ConnectionLimits::default()
+        .with_max_open_local_bidirectional_streams(1000)
+        .unwrap()
+        .with_stream_scheduler(&StrictPriority)
+        .unwrap();
+
+    let mut manager = AbstractStreamManager::<MockStream>::new(
+        &limits,
+        endpoint::Type::Server,
+        create_default_initial_flow_control_limits(),
+        create_default_initial_flow_control_limits(),
+    );
+
+    let streams: Vec<_> = (0..3)
+        .map(|_| try_open(&mut manager, StreamType::Bidirectional).unwrap())
+        .collect();
+
+    // the first stream is less urgent than the others
+    for (stream_id, urgency) in streams.iter().zip([5, 1, 1]) {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            stream.on_transmit_try_write_frames = 4;
+            stream.on_transmit_limit = Some(2);
+            stream.priority = Priority::new(urgency, 1);
+        });
+    }
+
+    let writers = transmit_packets(&mut manager, &streams, 6);
+
+    // the urgent streams take turns before the less urgent stream transmits
+    assert_eq!(
+        writers,
+        [streams[1], streams[2], streams[1], streams[2], streams[0], streams[0]]
+    );
+    assert!(manager.streams_waiting_for_transmission().is_empty());
+}
+
+#[test]
+fn stream_dscp_test() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let streams: Vec<_> = (0..2)
+        .map(|_| try_open(&mut manager, StreamType::Bidirectional).unwrap())
+        .collect();
+
+    for stream_id in &streams {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            stream.on_transmit_try_write_frames = 1;
+        });
+    }
+
+    // the second stream is marked with Expedited Forwarding
+    manager.with_asserted_stream(streams[1], |stream| stream.dscp = 46);
+
+    assert_eq!(manager.transmission_dscp(), Some(0));
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+
+    let pending_frames = |manager: &mut AbstractStreamManager<MockStream>| -> Vec<usize> {
+        streams
+            .iter()
+            .map(|stream_id| {
+                manager
+                    .with_asserted_stream(*stream_id, |stream| stream.on_transmit_try_write_frames)
+            })
+            .collect()
+    };
+
+    // only the marked stream writes to the marked packet
+    write_context.dscp = 46;
+    assert!(manager.on_transmit(&mut write_context).is_ok());
+    assert_eq!(pending_frames(&mut manager), [1, 0]);
+    assert_eq!(manager.transmission_dscp(), Some(0));
+
+    write_context.dscp = 0;
+    assert!(manager.on_transmit(&mut write_context).is_ok());
+    assert_eq!(pending_frames(&mut manager), [0, 0]);
+    assert_eq!(manager.transmission_dscp(), None);
+}
+
+fn create_stream_manager_with_policy(
+    policy: StreamLimitPolicy,
+) -> AbstractStreamManager<MockStream> {
+    let limits = ConnectionLimits::default()
+        .with_stream_limit_policy(policy)
+        .unwrap();
+
+    AbstractStreamManager::<MockStream>::new(
+        &limits,
+        endpoint::Type::Server,
+        create_default_initial_flow_control_limits(),
+        create_default_initial_flow_control_limits(),
+    )
+}
+
+/// Opens the peer streams in `range` and returns the MAX_STREAMS frame which is transmitted
+fn open_remote_streams(
+    manager: &mut AbstractStreamManager<MockStream>,
+    range: core::ops::Range<u64>,
+) -> (Result<(), TransportError>, Option<MaxStreams>) {
+    let first = StreamId::nth(
+        endpoint::Type::Client,
+        StreamType::Bidirectional,
+        range.start,
+    );
+    let last = StreamId::nth(
+        endpoint::Type::Client,
+        StreamType::Bidirectional,
+        range.end - 1,
+    );
+    let result = manager.with_stream_controller(|ctrl| {
+        ctrl.on_open_remote_stream(StreamIter::new(first.unwrap(), last.unwrap()))
+    });
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+    assert!(manager.on_transmit(&mut write_context).is_ok());
+
+    let frame = write_context
+        .frame_buffer
+        .pop_front()
+        .map(|mut frame| match frame.as_frame() {
+            Frame::MaxStreams(frame) => frame,
+            frame => panic!("unexpected frame {frame:?}"),
+        });
+
+    (result, frame)
+}
+
+#[test]
+fn stream_limit_policy_grow_test() {
+    let mut manager = create_stream_manager_with_policy(StreamLimitPolicy::Grow(300));
+
+    // the peer gets additional credit once it uses all of its streams
+    let (result, frame) = open_remote_streams(&mut manager, 0..128);
+    assert!(result.is_ok());
+    assert_eq!(
+        frame,
+        Some(MaxStreams {
+            stream_type: StreamType::Bidirectional,
+            maximum_streams: VarInt::from_u32(256),
+        })
+    );
+
+    // the limit grows up to the ceiling
+    let (result, frame) = open_remote_streams(&mut manager, 128..256);
+    assert!(result.is_ok());
+    assert_eq!(
+        frame,
+        Some(MaxStreams {
+            stream_type: StreamType::Bidirectional,
+            maximum_streams: VarInt::from_u32(300),
+        })
+    );
+
+    let (result, frame) = open_remote_streams(&mut manager, 256..300);
+    assert!(result.is_ok());
+    assert_eq!(frame, None);
+
+    let (result, _) = open_remote_streams(&mut manager, 300..301);
+    assert_eq!(
+        result.unwrap_err().code,
+        TransportError::STREAM_LIMIT_ERROR.code
+    );
+}
+
+#[test]
+fn stream_limit_policy_fixed_test() {
+    let mut manager = create_stream_manager_with_policy(StreamLimitPolicy::Fixed);
+
+    // the peer is held at the limit until streams are closed
+    let (result, frame) = open_remote_streams(&mut manager, 0..128);
+    assert!(result.is_ok());
+    assert_eq!(frame, None);
+
+    let (result, _) = open_remote_streams(&mut manager, 128..129);
+    assert_eq!(
+        result.unwrap_err().code,
+        TransportError::STREAM_LIMIT_ERROR.code
+    );
+}
+
+#[test]
+fn stream_limit_policy_reject_test() {
+    let mut manager = create_stream_manager_with_policy(StreamLimitPolicy::Reject);
+
+    let (result, frame) = open_remote_streams(&mut manager, 0..128);
+    assert!(result.is_ok());
+    assert_eq!(frame, None);
+
+    // closing streams doesn't replenish the credit
+    for i in 0..64 {
+        let stream_id = StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, i);
+        manager.with_stream_controller(|ctrl| ctrl.on_close_stream(stream_id.unwrap()));
+    }
+    assert_eq!(
+        manager.with_stream_controller(|ctrl| {
+            ctrl.remote_initiated_max_streams_latest_value(StreamType::Bidirectional)
+        }),
+        VarInt::from_u32(128)
+    );
+    assert!(!manager.has_transmission_interest());
+}
@@ -5,7 +5,7 @@ use crate::{
     connection,
     contexts::{ConnectionApiCallContext, OnTransmitError, WriteContext},
     recovery::RttEstimator,
-    stream::StreamError,
+    stream::{MemoryPressure, StreamError},
     transmission,
 };
 use core::task::{Context, Poll};
@@ -70,6 +70,9 @@ pub trait Manager:
     /// Called when the connection timer expires
     fn on_timeout(&mut self, now: Timestamp);
 
+    /// Called when the connection is woken up by an API call
+    fn on_wakeup(&mut self, now: Timestamp);
+
     /// Closes the manager and resets all streams with the
     /// given error. The current implementation will still
     /// allow to forward frames to the contained Streams as well as to query them
@@ -137,4 +140,21 @@ pub trait Manager:
 
     /// Returns whether or not streams have data to send
     fn has_pending_streams(&self) -> bool;
+
+    /// Returns whether or not streams have data to send which is blocked by flow control
+    fn has_flow_control_blocked_streams(&self) -> bool;
+
+    /// Returns whether or not streams are waiting for sent frames to be acknowledged
+    fn has_unacknowledged_streams(&self) -> bool;
+
+    /// Returns the DSCP code point of the stream which transmits next, if any streams have data
+    /// to send
+    fn transmission_dscp(&self) -> Option<u8>;
+
+    /// Sets the DSCP code point of the streams which don't set their own
+    fn set_dscp(&mut self, dscp: u8);
+
+    /// Returns the memory pressure if the buffered stream data crossed the pressure threshold
+    /// since the last call
+    fn take_memory_pressure(&mut self) -> Option<MemoryPressure>;
 }
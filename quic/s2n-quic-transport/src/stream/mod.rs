@@ -4,7 +4,9 @@
 //! This module contains the implementation of QUIC `Streams` and their management
 
 mod api;
+mod buffer_budget;
 mod controller;
+mod inactivity_timer;
 mod incoming_connection_flow_controller;
 mod manager;
 mod manager_api;
@@ -20,6 +22,7 @@ mod stream_interests;
 pub(crate) mod contract;
 
 pub use api::*;
+pub use buffer_budget::MemoryPressure;
 pub use controller::Controller;
 pub use manager::AbstractStreamManager;
 pub use manager_api::Manager;
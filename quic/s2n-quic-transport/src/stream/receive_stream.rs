@@ -357,6 +357,7 @@ impl ReceiveStream {
         connection_flow_controller: IncomingConnectionFlowController,
         initial_window: VarInt,
         desired_flow_control_window: u32,
+        max_receive_gaps: usize,
     ) -> ReceiveStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -368,7 +369,7 @@ impl ReceiveStream {
 
         let mut result = ReceiveStream {
             state,
-            receive_buffer: StreamReceiveBuffer::new(),
+            receive_buffer: StreamReceiveBuffer::with_max_gaps(max_receive_gaps),
             flow_controller: ReceiveStreamFlowController::new(
                 connection_flow_controller,
                 initial_window,
@@ -463,9 +464,15 @@ impl ReceiveStream {
                             //# limits in Early Data; see Section 7.4.1.
                             StreamReceiveBufferError::OutOfRange => {
                                 transport::Error::FLOW_CONTROL_ERROR
+                                    .with_reason("data reception error")
+                            }
+                            // The peer fragmented the stream data more than we're willing to
+                            // track, so close the connection instead of growing the metadata
+                            StreamReceiveBufferError::TooManyGaps => {
+                                transport::Error::PROTOCOL_VIOLATION
+                                    .with_reason("too many gaps in the received stream data")
                             }
                         }
-                        .with_reason("data reception error")
                         .with_frame_type(frame.tag().into())
                     })?;
 
@@ -574,6 +581,23 @@ impl ReceiveStream {
         self.wake(events);
     }
 
+    /// Requests the peer to stop sending after the inactivity timer expired
+    pub fn on_inactivity_timeout(&mut self, error: application::Error, events: &mut StreamEvents) {
+        // Take the waiter before it gets cleared by the request
+        let read_waiter = self.read_waiter.take();
+
+        let mut request = ops::rx::Request {
+            stop_sending: Some(error),
+            ..Default::default()
+        };
+        let _ = self.poll_request(&mut request, None);
+
+        // Wake up potential users of the stream so they observe the error
+        if let Some((waker, _low_watermark)) = read_waiter {
+            events.store_read_waker(waker);
+        }
+    }
+
     /// This is called when a `RESET_STREAM` frame had been received for
     /// this stream
     pub fn on_reset(
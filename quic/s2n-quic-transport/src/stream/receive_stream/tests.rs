@@ -3,7 +3,10 @@
 
 use super::*;
 use crate::{
-    stream::{stream_interests::StreamInterestProvider, testing::*, StreamEvents, StreamTrait},
+    stream::{
+        stream_interests::StreamInterestProvider, testing::*, MemoryPressure, StreamEvents,
+        StreamTrait,
+    },
     transmission,
     transmission::interest::Provider,
 };
@@ -658,6 +661,55 @@ fn exceed_stream_flow_control_window() {
     test_env.assert_pop_error();
 }
 
+#[test]
+fn exceed_max_receive_gaps() {
+    const MAX_GAPS: usize = 16;
+
+    let mut test_env_config = TestEnvironmentConfig::new(endpoint::Type::Server);
+    test_env_config.stream_id = StreamId::initial(
+        test_env_config.local_endpoint_type.peer_type(),
+        StreamType::Unidirectional,
+    );
+    test_env_config.max_receive_gaps = MAX_GAPS;
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    // send single bytes with a gap in front of each of them
+    for idx in 0..MAX_GAPS {
+        test_env.feed_data(VarInt::from_u32(idx as u32 * 2 + 1), 1);
+    }
+    assert_eq!(
+        test_env.stream.receive_stream.receive_buffer.gaps(),
+        MAX_GAPS
+    );
+
+    // the next gap exceeds the limit and closes the connection
+    let mut events = StreamEvents::new();
+    assert_is_transport_error(
+        test_env.stream.on_data(
+            &stream_data(
+                test_env.stream.stream_id,
+                VarInt::from_u32(MAX_GAPS as u32 * 2 + 1),
+                &[1],
+                false,
+            ),
+            &mut events,
+        ),
+        TransportError::PROTOCOL_VIOLATION,
+    );
+
+    // Reset the connection after the error
+    events = StreamEvents::new();
+    test_env
+        .stream
+        .on_internal_reset(connection::Error::unspecified().into(), &mut events);
+
+    assert_eq!(
+        stream_interests(&[]),
+        test_env.stream.get_stream_interests()
+    );
+    test_env.assert_pop_error();
+}
+
 #[test]
 fn exceed_connection_flow_control_window() {
     let mut test_env = setup_stream_test_env_with_config(conn_flow_control_test_env_config());
@@ -2548,3 +2600,43 @@ fn receiving_into_non_empty_buffers_returns_an_error() {
         "data should not be lost when returning an error"
     );
 }
+
+#[test]
+fn connection_buffer_budget_limits_receive_window() {
+    let mut test_env_config = conn_flow_control_test_env_config();
+    test_env_config.max_connection_buffer_bytes = 10 * 1024;
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    // the budget is shared with the data which is buffered by other streams
+    test_env.buffer_budget.reserve(4 * 1024);
+
+    // received data counts towards the budget until it is read
+    test_env.feed_data(VarInt::from_u32(0), 6 * 1024);
+    assert_eq!(
+        test_env.buffer_budget.take_pressure(),
+        Some(MemoryPressure {
+            buffered_bytes: 10 * 1024,
+            max_buffered_bytes: 10 * 1024,
+        })
+    );
+    assert_eq!(test_env.buffer_budget.available(), 0);
+
+    // reading the data only extends the window as far as the budget allows
+    assert_eq!(test_env.consume_all_data(), 6 * 1024);
+    assert_eq!(
+        test_env
+            .rx_connection_flow_controller
+            .current_receive_window(),
+        VarInt::from_u32(12 * 1024)
+    );
+
+    // the window is extended to the desired size once the other streams release the budget
+    test_env.buffer_budget.release(4 * 1024);
+    test_env.rx_connection_flow_controller.update_window();
+    assert_eq!(
+        test_env
+            .rx_connection_flow_controller
+            .current_receive_window(),
+        VarInt::from_u32(16 * 1024)
+    );
+}
@@ -4,6 +4,7 @@
 use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
+        buffer_budget::BufferBudget,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
@@ -121,6 +122,7 @@ impl ValueToFrameWriter<OutgoingResetData> for ResetStreamToFrameWriter {
 /// - locally via QUIC Stream API call
 /// - locally through the connection in cause of a connection error
 /// - from the remote peer by sending a STOP_SENDING frame.
+/// - locally after the stream was inactive for the configured timeout
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum ResetSource {
     /// The reset had been initiated by the local application calling the
@@ -132,6 +134,9 @@ enum ResetSource {
     /// The reset had been initiated as an internal reset. Likely caused by a
     /// connection error or termination.
     InternalReset,
+    /// The reset had been initiated after the stream didn't make any progress
+    /// within the inactivity timeout configured by the application.
+    InactivityTimeout,
 }
 
 impl ResetSource {
@@ -453,6 +458,10 @@ pub struct SendStream {
     /// If the second value in the tuple is set to true, the stream should be flushed before waking
     /// the waiter.
     pub(super) write_waiter: Option<(Waker, bool)>,
+    /// The connection-wide budget for buffering data
+    buffer_budget: BufferBudget,
+    /// The amount of buffered data which is accounted for in the `buffer_budget`
+    budgeted_len: usize,
     /// Whether the final state had already been observed by the application
     final_state_observed: bool,
     /// Marks the stream as detached from the application
@@ -462,6 +471,7 @@ pub struct SendStream {
 impl SendStream {
     pub fn new(
         connection_flow_controller: OutgoingConnectionFlowController,
+        buffer_budget: BufferBudget,
         is_closed: bool,
         initial_window: VarInt,
         max_buffer_capacity: u32,
@@ -483,6 +493,8 @@ impl SendStream {
             data_sender,
             reset_sync: OnceSync::new(),
             write_waiter: None,
+            buffer_budget,
+            budgeted_len: 0,
             final_state_observed: is_closed,
             detached: is_closed,
         };
@@ -580,6 +592,7 @@ impl SendStream {
     /// This method gets called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents) {
         self.data_sender.on_packet_ack(ack_set);
+        self.sync_buffer_budget();
         self.data_sender
             .flow_controller_mut()
             .on_packet_ack(ack_set);
@@ -685,6 +698,18 @@ impl SendStream {
         self.wake(events);
     }
 
+    /// A reset that is triggered by the expiration of the inactivity timer
+    pub fn on_inactivity_timeout(&mut self, error: application::Error, events: &mut StreamEvents) {
+        let error = StreamError::stream_reset(error);
+
+        if self.init_reset(ResetSource::InactivityTimeout, error) == InitResetResult::ResetInitiated
+        {
+            // Return the waker to wake up potential users of the stream.
+            // If the Stream got reset, then blocked writers need to get woken up.
+            self.wake(events);
+        }
+    }
+
     pub fn on_flush(&mut self, error: StreamError, events: &mut StreamEvents) {
         match self.data_sender.state() {
             data_sender::State::Finishing(_) => {
@@ -749,7 +774,10 @@ impl SendStream {
         request: &mut ops::tx::Request,
         context: Option<&Context>,
     ) -> Result<ops::tx::Response, StreamError> {
-        let mut response = ops::tx::Response::default();
+        let mut response = ops::tx::Response {
+            acked_offset: self.data_sender.total_acknowledged_len().as_u64(),
+            ..Default::default()
+        };
 
         if request.detached {
             self.detach();
@@ -824,6 +852,7 @@ impl SendStream {
 
                 if !self.can_push() {
                     store_waker!(false);
+                    self.on_blocked_by_budget(context);
 
                     // no more progress can be made on the operation
                     return Ok(response);
@@ -834,6 +863,7 @@ impl SendStream {
 
                 self.data_sender
                     .push(core::mem::replace(chunk, Bytes::new()));
+                self.sync_buffer_budget();
             }
         } else if !request.finish && !request.flush && context.is_some() {
             // if `chunks` are `None` or `Some(&[])` and we're not ending or flushing the stream,
@@ -845,6 +875,7 @@ impl SendStream {
             // store the waker if we currently can't push
             if !self.can_push() {
                 store_waker!(false);
+                self.on_blocked_by_budget(context);
 
                 return Ok(response);
             }
@@ -884,7 +915,7 @@ impl SendStream {
         match self.data_sender.state() {
             data_sender::State::Sending => {
                 // inform the caller of the available space to send
-                response.bytes.available = self.data_sender.available_buffer_space();
+                response.bytes.available = self.available_buffer_space();
                 // assume chunks are 1 bytes
                 response.chunks.available = response.bytes.available;
             }
@@ -925,7 +956,40 @@ impl SendStream {
     fn can_push(&self) -> bool {
         // We accept the data if there is at least 1 byte of space
         // available in the flow control window.
-        self.data_sender.available_buffer_space() > 0
+        self.available_buffer_space() > 0
+    }
+
+    /// Returns the amount of data that can be additionally buffered, which is limited by the
+    /// stream and the connection-wide buffer budget
+    fn available_buffer_space(&self) -> usize {
+        self.data_sender
+            .available_buffer_space()
+            .min(self.buffer_budget.available())
+    }
+
+    /// Registers the waiting task with the buffer budget if the stream is only blocked because
+    /// other streams are using the connection-wide budget
+    ///
+    /// Only acknowledgements for this stream are able to unblock it otherwise.
+    fn on_blocked_by_budget(&mut self, context: Option<&Context>) {
+        if let Some(context) = context {
+            if self.data_sender.available_buffer_space() > 0 {
+                self.buffer_budget.on_blocked_writer(context.waker());
+            }
+        }
+    }
+
+    /// Updates the buffer budget with the amount of data which is currently buffered
+    fn sync_buffer_budget(&mut self) {
+        let buffered_len = self.data_sender.buffered_len();
+
+        if buffered_len > self.budgeted_len {
+            self.buffer_budget.reserve(buffered_len - self.budgeted_len);
+        } else {
+            self.buffer_budget.release(self.budgeted_len - buffered_len);
+        }
+
+        self.budgeted_len = buffered_len;
     }
 
     /// Ensures a potential push operation would be valid
@@ -984,6 +1048,7 @@ impl SendStream {
         // Clear the send buffer. Since we initiated a RESET, there is no need
         // to send or resend the remaining data.
         self.data_sender.stop_sending(error);
+        self.sync_buffer_budget();
 
         // For an internal reset (which provides no error_code) we do not need
         // to transmit the reset frame
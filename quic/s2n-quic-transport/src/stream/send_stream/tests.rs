@@ -5,7 +5,7 @@ use super::*;
 use crate::stream::{
     stream_interests::{StreamInterestProvider, StreamInterests},
     testing::*,
-    StreamError, StreamEvents, StreamTrait,
+    MemoryPressure, StreamError, StreamEvents, StreamTrait,
 };
 use bytes::Bytes;
 use core::task::Poll;
@@ -2728,6 +2728,7 @@ fn can_send_multiple_chunks() {
                                     ops::Status::Open
                                 },
                                 will_wake,
+                                acked_offset: 0,
                             }),
                             rx: None,
                         }),
@@ -2847,6 +2848,7 @@ fn can_query_stream_readiness() {
                         },
                         status: ops::Status::Open,
                         will_wake: with_context && expected_buffer_size == 0,
+                        acked_offset: 0,
                     }),
                     rx: None,
                 }),
@@ -2854,3 +2856,50 @@ fn can_query_stream_readiness() {
         }
     }
 }
+
+#[test]
+fn connection_buffer_budget_limits_buffered_data() {
+    const MAX_BUFFER_BYTES: usize = 1000;
+
+    let test_env_config = TestEnvironmentConfig {
+        max_connection_buffer_bytes: MAX_BUFFER_BYTES,
+        max_packet_size: Some(1200),
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u32(0), 700, true),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 700, false, false, pn(0)),
+        ],
+    );
+    assert_eq!(test_env.buffer_budget.take_pressure(), None);
+
+    execute_instructions(
+        &mut test_env,
+        &[Instruction::EnqueueData(VarInt::from_u32(700), 300, true)],
+    );
+    assert_eq!(
+        test_env.buffer_budget.take_pressure(),
+        Some(MemoryPressure {
+            buffered_bytes: MAX_BUFFER_BYTES,
+            max_buffered_bytes: MAX_BUFFER_BYTES,
+        })
+    );
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            // the stream has buffer space left, but the connection budget is used up
+            Instruction::EnqueueData(VarInt::from_u32(1000), 1, false),
+            // transmitting doesn't release the budget until the data is acknowledged
+            Instruction::CheckDataTx(VarInt::from_u32(700), 300, false, false, pn(1)),
+            Instruction::EnqueueData(VarInt::from_u32(1000), 1, false),
+            Instruction::AckPacket(pn(0), ExpectWakeup(Some(true))),
+            Instruction::EnqueueData(VarInt::from_u32(1000), 1, true),
+        ],
+    );
+    assert_eq!(test_env.buffer_budget.available(), MAX_BUFFER_BYTES - 301);
+}
@@ -53,6 +53,12 @@ intrusive_adapter!(WaitingForStreamFlowControlCreditsAdapter<S> = Rc<StreamNode<
     waiting_for_stream_flow_control_credits_link: LinkedListLink
 });
 
+// Intrusive list adapter for managing the list of
+// `waiting_for_inactivity_timeout` streams
+intrusive_adapter!(WaitingForInactivityTimeoutAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
+    waiting_for_inactivity_timeout_link: LinkedListLink
+});
+
 // Intrusive red black tree adapter for managing all streams in a tree for
 // lookup by Stream ID
 intrusive_adapter!(StreamTreeAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
@@ -79,6 +85,8 @@ struct StreamNode<S> {
     waiting_for_connection_flow_control_credits_link: LinkedListLink,
     /// Allows the Stream to be part of the `waiting_for_stream_flow_control_credits` collection
     waiting_for_stream_flow_control_credits_link: LinkedListLink,
+    /// Allows the Stream to be part of the `waiting_for_inactivity_timeout` collection
+    waiting_for_inactivity_timeout_link: LinkedListLink,
 }
 
 impl<S> StreamNode<S> {
@@ -93,6 +101,7 @@ impl<S> StreamNode<S> {
             waiting_for_retransmission_link: LinkedListLink::new(),
             waiting_for_connection_flow_control_credits_link: LinkedListLink::new(),
             waiting_for_stream_flow_control_credits_link: LinkedListLink::new(),
+            waiting_for_inactivity_timeout_link: LinkedListLink::new(),
         }
     }
 }
@@ -145,6 +154,8 @@ struct InterestLists<S> {
     /// stream flow control window to increase
     waiting_for_stream_flow_control_credits:
         LinkedList<WaitingForStreamFlowControlCreditsAdapter<S>>,
+    /// Streams which have an armed inactivity timer
+    waiting_for_inactivity_timeout: LinkedList<WaitingForInactivityTimeoutAdapter<S>>,
 }
 
 impl<S: StreamTrait> InterestLists<S> {
@@ -160,6 +171,9 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits: LinkedList::new(
                 WaitingForStreamFlowControlCreditsAdapter::new(),
             ),
+            waiting_for_inactivity_timeout: LinkedList::new(
+                WaitingForInactivityTimeoutAdapter::new(),
+            ),
         }
     }
 
@@ -226,6 +240,11 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits_link,
             waiting_for_stream_flow_control_credits
         );
+        sync_interests!(
+            interests.inactivity_timer,
+            waiting_for_inactivity_timeout_link,
+            waiting_for_inactivity_timeout
+        );
 
         if !interests.retained != node.done_streams_link.is_linked() {
             if !interests.retained {
@@ -469,6 +488,10 @@ impl<S: StreamTrait> StreamContainer<S> {
                 waiting_for_stream_flow_control_credits,
                 waiting_for_stream_flow_control_credits_link
             );
+            remove_stream_from_list!(
+                waiting_for_inactivity_timeout,
+                waiting_for_inactivity_timeout_link
+            );
 
             controller.on_close_stream(stream.inner.borrow().stream_id());
         }
@@ -537,6 +560,27 @@ impl<S: StreamTrait> StreamContainer<S> {
         );
     }
 
+    /// Iterates over all `Stream`s which have an armed inactivity timer,
+    /// and executes the given function on each `Stream`
+    ///
+    /// The `stream::Controller` will be notified of streams that have been
+    /// closed to allow for further streams to be opened.
+    pub fn iterate_inactivity_timeout_list<F>(
+        &mut self,
+        controller: &mut stream::Controller,
+        mut func: F,
+    ) where
+        F: FnMut(&mut S),
+    {
+        iterate_uninterruptible!(
+            self,
+            waiting_for_inactivity_timeout,
+            waiting_for_inactivity_timeout_link,
+            controller,
+            func
+        );
+    }
+
     /// Iterates over all `Stream`s which are waiting for transmission,
     /// and executes the given function on each `Stream`
     ///
@@ -555,6 +599,76 @@ impl<S: StreamTrait> StreamContainer<S> {
         );
     }
 
+    /// Iterates over all `Stream`s which are waiting for transmission in the order of their
+    /// rank, and executes the given function on each `Stream`
+    ///
+    /// Streams with a lower rank are visited first. Streams with the same rank are visited in
+    /// the order of the transmission list.
+    ///
+    /// The `stream::Controller` will be notified of streams that have been
+    /// closed to allow for further streams to be opened.
+    pub fn iterate_transmission_list_by_rank<R, F>(
+        &mut self,
+        controller: &mut stream::Controller,
+        rank: R,
+        mut func: F,
+    ) where
+        R: Fn(&S) -> u8,
+        F: FnMut(&mut S) -> StreamContainerIterationResult,
+    {
+        let mut extracted_list = self.interest_lists.waiting_for_transmission.take();
+        let mut current_rank = extracted_list
+            .iter()
+            .map(|stream| rank(&stream.inner.borrow()))
+            .min();
+
+        'ranks: while let Some(visited_rank) = current_rank.take() {
+            let mut cursor = extracted_list.front_mut();
+
+            while let Some(stream) = cursor.get() {
+                let stream_rank = rank(&stream.inner.borrow());
+
+                if stream_rank != visited_rank {
+                    // remember the next rank to visit
+                    if stream_rank > visited_rank {
+                        current_rank =
+                            Some(current_rank.map_or(stream_rank, |r| r.min(stream_rank)));
+                    }
+                    cursor.move_next();
+                    continue;
+                }
+
+                let stream = cursor.remove().expect("the cursor points to a stream");
+
+                // Note that while we iterate over the intrusive lists here
+                // `stream` is part of no list anymore, since it also got dropped
+                // from list that is described by the `cursor`.
+                debug_assert!(!stream.waiting_for_transmission_link.is_linked());
+                let mut mut_stream = stream.inner.borrow_mut();
+                let result = func(&mut *mut_stream);
+
+                // Update the interests after the interaction
+                let interests = mut_stream.get_stream_interests();
+                self.interest_lists
+                    .update_interests(&stream, interests, result);
+
+                if let StreamContainerIterationResult::BreakAndInsertAtBack = result {
+                    break 'ranks;
+                }
+            }
+        }
+
+        // Insert any streams which weren't visited after the stream at the front of the list
+        self.interest_lists
+            .waiting_for_transmission
+            .front_mut()
+            .splice_after(extracted_list);
+
+        if !self.interest_lists.done_streams.is_empty() {
+            self.finalize_done_streams(controller);
+        }
+    }
+
     /// Iterates over all `Stream`s which are waiting for retransmission,
     /// and executes the given function on each `Stream`
     ///
@@ -620,6 +734,46 @@ impl<S: StreamTrait> StreamContainer<S> {
         !self.interest_lists.waiting_for_transmission.is_empty()
             || !self.interest_lists.waiting_for_retransmission.is_empty()
     }
+
+    /// Returns whether or not streams are waiting for sent frames to be acknowledged
+    pub fn has_unacknowledged_streams(&self) -> bool {
+        !self.interest_lists.waiting_for_frame_delivery.is_empty()
+    }
+
+    /// Returns whether or not streams have data to send which is blocked by flow control
+    pub fn has_flow_control_blocked_streams(&self) -> bool {
+        !self
+            .interest_lists
+            .waiting_for_connection_flow_control_credits
+            .is_empty()
+            || !self
+                .interest_lists
+                .waiting_for_stream_flow_control_credits
+                .is_empty()
+    }
+
+    /// Returns the DSCP code point of the `Stream` which transmits next, if any
+    ///
+    /// Lost data is retransmitted first, followed by the streams with the lowest rank. Streams
+    /// which don't set a code point use the `default`.
+    pub fn transmission_dscp<R>(&self, rank: R, default: u8) -> Option<u8>
+    where
+        R: Fn(&S) -> u8,
+    {
+        if let Some(stream) = self.interest_lists.waiting_for_retransmission.front().get() {
+            return Some(stream.inner.borrow().dscp().unwrap_or(default));
+        }
+
+        self.interest_lists
+            .waiting_for_transmission
+            .iter()
+            .map(|stream| {
+                let stream = stream.inner.borrow();
+                (rank(&stream), stream.dscp().unwrap_or(default))
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, dscp)| dscp)
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
@@ -633,6 +787,9 @@ impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
         {
             stream.inner.borrow().timers(query)?;
         }
+        for stream in self.interest_lists.waiting_for_inactivity_timeout.iter() {
+            stream.inner.borrow().timers(query)?;
+        }
         Ok(())
     }
 }
@@ -4,6 +4,8 @@
 use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
+        buffer_budget::BufferBudget,
+        inactivity_timer::InactivityTimer,
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         receive_stream::ReceiveStream,
@@ -17,7 +19,11 @@ use core::{task::Context, time::Duration};
 use s2n_quic_core::{
     ack, endpoint,
     frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
-    stream::{ops, StreamId},
+    stream::{
+        ops,
+        scheduler::{self, Priority},
+        StreamId,
+    },
     time::{timer, Timestamp},
     transport,
     varint::VarInt,
@@ -34,6 +40,8 @@ pub struct StreamConfig {
     pub incoming_connection_flow_controller: IncomingConnectionFlowController,
     /// The connection-wide flow controller for sending data
     pub outgoing_connection_flow_controller: OutgoingConnectionFlowController,
+    /// The connection-wide budget for buffering data
+    pub buffer_budget: BufferBudget,
     /// The initial flow control window for receiving data
     pub initial_receive_window: VarInt,
     /// The desired flow control window that we want to maintain on the receiving side
@@ -42,6 +50,8 @@ pub struct StreamConfig {
     pub initial_send_window: VarInt,
     /// The maximum buffered amount of data on the sending side
     pub max_send_buffer_size: u32,
+    /// The maximum number of gaps in the received data
+    pub max_receive_gaps: usize,
 }
 
 /// A trait which represents an internally used `Stream`
@@ -107,6 +117,10 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// Called when the connection timer expires
     fn on_timeout(&mut self, now: Timestamp);
 
+    /// Called when the connection timer expires or the connection is woken up while the stream
+    /// has a configured inactivity timer
+    fn on_inactivity_timeout(&mut self, now: Timestamp, events: &mut StreamEvents);
+
     /// This method gets called when a stream gets reset due to a reason that is
     /// not related to a frame. E.g. due to a connection failure.
     fn on_internal_reset(&mut self, error: StreamError, events: &mut StreamEvents);
@@ -122,6 +136,17 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// This method is called when a connection window is available
     fn on_connection_window_available(&mut self);
 
+    /// Returns the transmission priority of the stream
+    fn priority(&self) -> Priority;
+
+    /// Returns the state which the stream scheduler keeps for the stream
+    fn scheduler_state(&mut self) -> &mut scheduler::State;
+
+    /// Returns the DSCP code point which marks the packets carrying the stream's data
+    ///
+    /// `None` indicates the stream uses the connection's code point.
+    fn dscp(&self) -> Option<u8>;
+
     // These functions are called from the client API
 
     fn poll_request(
@@ -143,6 +168,16 @@ pub struct StreamImpl {
     has_send: bool,
     /// Manages the sending side of the stream
     pub(super) send_stream: SendStream,
+    /// Resets the sending side of the stream if it doesn't make progress
+    tx_inactivity_timer: InactivityTimer,
+    /// Stops the receiving side of the stream if it doesn't make progress
+    rx_inactivity_timer: InactivityTimer,
+    /// The transmission priority of the stream
+    priority: Priority,
+    /// The state which the stream scheduler keeps for the stream
+    scheduler_state: scheduler::State,
+    /// The DSCP code point which marks the packets carrying the stream's data, if it was set
+    dscp: Option<u8>,
 }
 
 impl StreamImpl {
@@ -151,6 +186,22 @@ impl StreamImpl {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
+        if let Some((timeout, error)) = request.rx.as_ref().and_then(|rx| rx.inactivity_timeout) {
+            self.rx_inactivity_timer.configure(timeout, error);
+        }
+
+        if let Some((timeout, error)) = request.tx.as_ref().and_then(|tx| tx.inactivity_timeout) {
+            self.tx_inactivity_timer.configure(timeout, error);
+        }
+
+        if let Some(priority) = request.tx.as_ref().and_then(|tx| tx.priority) {
+            self.priority = priority;
+        }
+
+        if let Some(dscp) = request.tx.as_ref().and_then(|tx| tx.dscp) {
+            self.dscp = Some(dscp & 0b11_1111);
+        }
+
         let mut response = ops::Response::default();
         if let Some(rx) = request.rx.as_mut() {
             match self.receive_stream.poll_request(rx, context) {
@@ -186,8 +237,21 @@ impl StreamImpl {
             }
         }
 
+        // Any data moved between the application and the stream counts as progress
+        let tx_consumed = response.tx().map_or(0, |tx| tx.bytes.consumed);
+        let rx_consumed = response.rx().map_or(0, |rx| rx.bytes.consumed);
+        if tx_consumed > 0 || rx_consumed > 0 {
+            self.on_activity();
+        }
+
         Ok(response)
     }
+
+    #[inline]
+    fn on_activity(&mut self) {
+        self.tx_inactivity_timer.on_activity();
+        self.rx_inactivity_timer.on_activity();
+    }
 }
 
 impl StreamTrait for StreamImpl {
@@ -204,14 +268,21 @@ impl StreamTrait for StreamImpl {
                 config.incoming_connection_flow_controller,
                 config.initial_receive_window,
                 config.desired_flow_control_window,
+                config.max_receive_gaps,
             ),
             has_send: !send_is_closed,
             send_stream: SendStream::new(
                 config.outgoing_connection_flow_controller,
+                config.buffer_budget,
                 send_is_closed,
                 config.initial_send_window,
                 config.max_send_buffer_size,
             ),
+            tx_inactivity_timer: InactivityTimer::default(),
+            rx_inactivity_timer: InactivityTimer::default(),
+            priority: Priority::default(),
+            scheduler_state: scheduler::State::default(),
+            dscp: None,
         }
     }
 
@@ -228,6 +299,7 @@ impl StreamTrait for StreamImpl {
         frame: &StreamRef,
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error> {
+        self.on_activity();
         self.receive_stream.on_data(frame, events)
     }
 
@@ -237,6 +309,7 @@ impl StreamTrait for StreamImpl {
         frame: &StreamDataBlocked,
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error> {
+        self.on_activity();
         self.receive_stream.on_stream_data_blocked(frame, events)
     }
 
@@ -246,6 +319,7 @@ impl StreamTrait for StreamImpl {
         frame: &ResetStream,
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error> {
+        self.on_activity();
         self.receive_stream.on_reset(frame, events)
     }
 
@@ -264,6 +338,7 @@ impl StreamTrait for StreamImpl {
                 .with_reason("MAX_STREAM_DATA sent on receive-only stream"));
         }
 
+        self.on_activity();
         self.send_stream.on_max_stream_data(frame, events)
     }
 
@@ -273,6 +348,7 @@ impl StreamTrait for StreamImpl {
         frame: &StopSending,
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error> {
+        self.on_activity();
         self.send_stream.on_stop_sending(frame, events)
     }
 
@@ -299,6 +375,17 @@ impl StreamTrait for StreamImpl {
         self.send_stream.on_timeout(now)
     }
 
+    #[inline]
+    fn on_inactivity_timeout(&mut self, now: Timestamp, events: &mut StreamEvents) {
+        if let Some(error) = self.tx_inactivity_timer.on_timeout(now) {
+            self.send_stream.on_inactivity_timeout(error, events);
+        }
+
+        if let Some(error) = self.rx_inactivity_timer.on_timeout(now) {
+            self.receive_stream.on_inactivity_timeout(error, events);
+        }
+    }
+
     #[inline]
     fn on_internal_reset(&mut self, error: StreamError, events: &mut StreamEvents) {
         self.receive_stream.on_internal_reset(error, events);
@@ -327,6 +414,21 @@ impl StreamTrait for StreamImpl {
         self.send_stream.on_connection_window_available()
     }
 
+    #[inline]
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    #[inline]
+    fn scheduler_state(&mut self) -> &mut scheduler::State {
+        &mut self.scheduler_state
+    }
+
+    #[inline]
+    fn dscp(&self) -> Option<u8> {
+        self.dscp
+    }
+
     // These functions are called from the client API
 
     fn poll_request(
@@ -350,6 +452,8 @@ impl timer::Provider for StreamImpl {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.send_stream.timers(query)?;
+        self.tx_inactivity_timer.timers(query)?;
+        self.rx_inactivity_timer.timers(query)?;
         Ok(())
     }
 }
@@ -359,5 +463,10 @@ impl StreamInterestProvider for StreamImpl {
     fn stream_interests(&self, interests: &mut StreamInterests) {
         self.send_stream.stream_interests(interests);
         self.receive_stream.stream_interests(interests);
+
+        // timers which still need arming are also tracked so they are armed on the next wakeup
+        interests.inactivity_timer |= [&self.tx_inactivity_timer, &self.rx_inactivity_timer]
+            .iter()
+            .any(|timer| timer.is_armed() || timer.needs_arming());
     }
 }
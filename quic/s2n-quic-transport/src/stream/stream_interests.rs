@@ -20,6 +20,8 @@ pub struct StreamInterests {
     /// Is `true` if the component is interested in packet acknowledge and
     /// loss information
     pub delivery_notifications: bool,
+    /// Is `true` if the `Stream` has an armed inactivity timer, or one waiting to be armed
+    pub inactivity_timer: bool,
     /// Transmission interest for the component
     pub transmission: Interest,
 }
@@ -31,6 +33,7 @@ impl StreamInterests {
         self.stream_flow_control_credits |= other.stream_flow_control_credits;
         self.retained |= other.retained;
         self.delivery_notifications |= other.delivery_notifications;
+        self.inactivity_timer |= other.inactivity_timer;
         let _ = self.transmission.on_interest(other.transmission);
     }
 
@@ -4,6 +4,7 @@
 pub use crate::contexts::testing::*;
 use crate::{
     stream::{
+        buffer_budget::BufferBudget,
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         stream_impl::StreamConfig, stream_interests::StreamInterests, StreamEvents, StreamImpl,
@@ -159,6 +160,7 @@ pub struct TestEnvironment {
     pub stream: StreamImpl,
     pub rx_connection_flow_controller: IncomingConnectionFlowController,
     pub tx_connection_flow_controller: OutgoingConnectionFlowController,
+    pub buffer_budget: BufferBudget,
     pub wake_counter: AwokenCount,
     pub waker: Waker,
     pub current_time: Timestamp,
@@ -484,9 +486,11 @@ pub struct TestEnvironmentConfig {
     pub initial_connection_receive_window_size: u64,
     pub desired_connection_flow_control_window: u32,
     pub max_send_buffer_size: usize,
+    pub max_connection_buffer_bytes: usize,
     pub transmission_constraint: transmission::Constraint,
     pub local_endpoint_type: endpoint::Type,
     pub max_packet_size: Option<usize>,
+    pub max_receive_gaps: usize,
 }
 
 impl Default for TestEnvironmentConfig {
@@ -513,8 +517,10 @@ impl TestEnvironmentConfig {
             desired_connection_flow_control_window:
                 TestEnvironment::DEFAULT_INITIAL_CONNECTION_RECEIVE_WINDOW as u32,
             max_send_buffer_size: TestEnvironment::DEFAULT_MAX_SEND_BUFFER_SIZE,
+            max_connection_buffer_bytes: usize::MAX,
             transmission_constraint: transmission::Constraint::None,
             max_packet_size: None,
+            max_receive_gaps: usize::MAX,
         }
     }
 }
@@ -527,9 +533,12 @@ pub fn setup_stream_test_env() -> TestEnvironment {
 
 /// Sets up a test environment for Stream testing with custom parameters
 pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestEnvironment {
+    let buffer_budget = BufferBudget::new(config.max_connection_buffer_bytes);
+
     let rx_connection_flow_controller = IncomingConnectionFlowController::new(
         VarInt::new(config.initial_connection_receive_window_size).unwrap(),
         config.desired_connection_flow_control_window,
+        buffer_budget.clone(),
     );
 
     let tx_connection_flow_controller = OutgoingConnectionFlowController::new(
@@ -539,12 +548,14 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
     let stream = StreamImpl::new(StreamConfig {
         incoming_connection_flow_controller: rx_connection_flow_controller.clone(),
         outgoing_connection_flow_controller: tx_connection_flow_controller.clone(),
+        buffer_budget: buffer_budget.clone(),
         local_endpoint_type: config.local_endpoint_type,
         stream_id: config.stream_id,
         initial_receive_window: VarInt::new(config.initial_receive_window).unwrap(),
         desired_flow_control_window: config.desired_flow_control_window,
         initial_send_window: VarInt::new(config.initial_send_window).unwrap(),
         max_send_buffer_size: config.max_send_buffer_size as u32,
+        max_receive_gaps: config.max_receive_gaps,
     });
 
     let (waker, wake_counter) = new_count_waker();
@@ -558,6 +569,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         stream,
         rx_connection_flow_controller,
         tx_connection_flow_controller,
+        buffer_budget,
         wake_counter,
         waker,
         current_time: time::now(),
@@ -196,6 +196,17 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.buffer.total_len()
     }
 
+    /// Returns the amount of bytes at the start of the Stream which have been acknowledged by
+    /// the peer.
+    pub fn total_acknowledged_len(&self) -> VarInt {
+        self.buffer.head()
+    }
+
+    /// Returns the amount of data which is buffered until it is acknowledged by the peer
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.enqueued_len().try_into().unwrap_or(usize::MAX)
+    }
+
     /// Returns true if the data sender doesn't have any data enqueued for sending
     pub fn is_empty(&self) -> bool {
         self.buffer.is_empty()
@@ -593,6 +604,7 @@ mod tests {
             transmission_constraint: transmission::Constraint::None,
             transmission_mode: transmission::Mode::Normal,
             endpoint: endpoint::Type::Server,
+            dscp: 0,
         };
         let mut lost = HashSet::new();
         let mut pending = HashSet::new();
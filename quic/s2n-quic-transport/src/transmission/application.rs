@@ -8,7 +8,7 @@ use crate::{
     endpoint, path,
     path::mtu,
     recovery,
-    space::{datagram, HandshakeStatus},
+    space::{datagram, new_token, CryptoStream, HandshakeStatus},
     stream::Manager as _,
     sync::{flag, flag::Ping},
     transmission::{self, Mode},
@@ -38,6 +38,8 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         stream_manager: &'a mut Config::StreamManager,
         recovery_manager: &'a mut recovery::Manager<Config>,
         datagram_manager: &'a mut datagram::Manager<Config>,
+        new_token: &'a mut new_token::Manager,
+        crypto_stream: &'a mut CryptoStream,
     ) -> Self {
         if transmission_mode != Mode::PathValidationOnly {
             debug_assert_eq!(path_id, path_manager.active_path_id());
@@ -54,6 +56,8 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
                     path_manager,
                     recovery_manager,
                     datagram_manager,
+                    new_token,
+                    crypto_stream,
                     prioritize_datagrams: false,
                 })
             }
@@ -110,6 +114,8 @@ pub struct Normal<'a, Config: endpoint::Config> {
     path_manager: &'a mut path::Manager<Config>,
     recovery_manager: &'a mut recovery::Manager<Config>,
     datagram_manager: &'a mut datagram::Manager<Config>,
+    new_token: &'a mut new_token::Manager,
+    crypto_stream: &'a mut CryptoStream,
     prioritize_datagrams: bool,
 }
 
@@ -174,6 +180,9 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         // soon as possible
         self.handshake_status.on_transmit(context);
 
+        // send post-handshake TLS messages, e.g. `NewSessionTicket`
+        let _ = self.crypto_stream.tx.on_transmit((), context);
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2
         //# An endpoint MAY include other frames with the PATH_CHALLENGE and
         //# PATH_RESPONSE frames used for path validation.
@@ -183,6 +192,8 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         self.local_id_registry.on_transmit(context);
 
         self.path_manager.on_transmit(context);
+
+        self.new_token.on_transmit(context);
     }
 }
 
@@ -197,6 +208,8 @@ impl<'a, Config: endpoint::Config> transmission::interest::Provider for Normal<'
         self.datagram_manager.transmission_interest(query)?;
         self.local_id_registry.transmission_interest(query)?;
         self.path_manager.transmission_interest(query)?;
+        self.new_token.transmission_interest(query)?;
+        self.crypto_stream.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
         self.path_manager
             .active_path()
@@ -20,6 +20,7 @@ pub struct Context<'a, 'b, 'sub, Config: endpoint::Config> {
     pub timestamp: Timestamp,
     pub header_len: usize,
     pub tag_len: usize,
+    pub dscp: u8,
     pub config: PhantomData<Config>,
     pub path_id: path::Id,
     pub publisher: &'a mut event::ConnectionPublisherSubscriber<
@@ -81,6 +82,11 @@ impl<'a, 'b, 'sub, Config: endpoint::Config> WriteContext for Context<'a, 'b, 's
         self.buffer.remaining_capacity()
     }
 
+    #[inline]
+    fn dscp(&self) -> u8 {
+        self.dscp
+    }
+
     #[inline]
     fn write_ack_frame<AckRanges: AckRangesTrait>(
         &mut self,
@@ -225,6 +231,11 @@ impl<'a, C: WriteContext> WriteContext for RetransmissionContext<'a, C> {
         self.context.remaining_capacity()
     }
 
+    #[inline]
+    fn dscp(&self) -> u8 {
+        self.context.dscp()
+    }
+
     #[inline]
     fn write_ack_frame<AckRanges: AckRangesTrait>(
         &mut self,
@@ -47,6 +47,7 @@ pub struct Transmission<'a, 'sub, Config: endpoint::Config, P: Payload> {
     pub timestamp: Timestamp,
     pub transmission_constraint: transmission::Constraint,
     pub transmission_mode: transmission::Mode,
+    pub dscp: u8,
     pub tx_packet_numbers: &'a mut TxPacketNumbers,
     pub path_id: path::Id,
     pub publisher: &'a mut event::ConnectionPublisherSubscriber<
@@ -88,6 +89,7 @@ impl<'a, 'sub, Config: endpoint::Config, P: Payload> PacketPayloadEncoder
             timestamp: self.timestamp,
             header_len,
             tag_len,
+            dscp: self.dscp,
             config: Default::default(),
             path_id: self.path_id,
             publisher: self.publisher,
@@ -17,7 +17,7 @@ mod builder;
 mod providers;
 
 pub use builder::*;
-pub use connect::Connect;
+pub use connect::{Connect, UnsupportedVersionError};
 pub use providers::*;
 
 /// A QUIC client endpoint, capable of opening connections
@@ -253,6 +253,14 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-connection-close-formatter"))]
+    impl_provider_method!(
+        /// Sets the connection close formatter provider for the [`Client`]
+        with_connection_close_formatter,
+        connection_close_formatter,
+        ClientProviders
+    );
+
     #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Client`]
@@ -261,6 +269,17 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-wall-clock"))]
+    impl_provider_method!(
+        /// Sets the wall clock provider for the [`Client`]
+        ///
+        /// The clock is read by the TLS, event and packet interceptor providers for calendar
+        /// times, such as session ticket expirations and the timestamps of traces and captures.
+        with_wall_clock,
+        wall_clock,
+        ClientProviders
+    );
+
     #[cfg(feature = "unstable-provider-datagram")]
     impl_provider_method!(
         /// Sets the datagram provider for the [`Client`]
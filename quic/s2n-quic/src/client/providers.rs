@@ -5,6 +5,7 @@ use super::*;
 use core::marker::PhantomData;
 use s2n_quic_core::{connection::id::Generator, crypto, path};
 use s2n_quic_transport::{connection, endpoint, stream};
+use std::sync::Arc;
 
 impl_providers_state! {
     #[derive(Debug, Default)]
@@ -21,6 +22,7 @@ impl_providers_state! {
         sync: Sync,
         tls: Tls,
         datagram: Datagram,
+        wall_clock: WallClock,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -40,6 +42,7 @@ impl<
         Sync: sync::Provider,
         Tls: tls::Provider,
         Datagram: datagram::Provider,
+        WallClock: wall_clock::Provider,
     >
     Providers<
         CongestionController,
@@ -54,6 +57,7 @@ impl<
         Sync,
         Tls,
         Datagram,
+        WallClock,
     >
 {
     pub fn start(self) -> Result<Client, StartError> {
@@ -70,6 +74,7 @@ impl<
             sync,
             tls,
             datagram,
+            wall_clock,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -77,17 +82,24 @@ impl<
             .start()
             .map_err(StartError::new)?;
         let connection_id = connection_id.start().map_err(StartError::new)?;
-        let packet_interceptor = packet_interceptor.start().map_err(StartError::new)?;
+        let mut packet_interceptor = packet_interceptor.start().map_err(StartError::new)?;
         let stateless_reset_token = stateless_reset_token.start().map_err(StartError::new)?;
         let random = random.start().map_err(StartError::new)?;
         let endpoint_limits = EndpointLimits;
         let limits = limits.start().map_err(StartError::new)?;
-        let event = event.start().map_err(StartError::new)?;
+        let mut event = event.start().map_err(StartError::new)?;
         let token = Token;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = PathMigration;
-        let tls = tls.start_client().map_err(StartError::new)?;
+        let mut tls = tls.start_client().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
+        let wall_clock = wall_clock.start().map_err(StartError::new)?;
+
+        // share the endpoint's wall clock with the providers which read calendar time
+        let wall_clock: Arc<dyn s2n_quic_core::time::WallClock> = Arc::new(wall_clock);
+        crypto::tls::Endpoint::set_wall_clock(&mut tls, &wall_clock);
+        s2n_quic_core::event::Subscriber::set_wall_clock(&mut event, &wall_clock);
+        packet_interceptor::PacketInterceptor::set_wall_clock(&mut packet_interceptor, &wall_clock);
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -11,7 +11,14 @@ mod handle;
 
 pub use acceptor::*;
 pub use handle::*;
-pub use s2n_quic_core::connection::Error;
+pub use s2n_quic_core::{
+    connection::{CloseOnDrop, DscpError, Error, Stats, Version},
+    crypto::{
+        application::KeyUsage,
+        tls::{CipherSuite, ExportError, HandshakeInfo},
+    },
+    path::{PathError, PathInfo, PathProbe},
+};
 
 pub mod error {
     pub use s2n_quic_core::transport::error::Code;
@@ -149,6 +149,92 @@ macro_rules! impl_handle_api {
             self.0.remote_address().map(std::net::SocketAddr::from)
         }
 
+        /// Returns information about the path the connection is actively using
+        ///
+        /// The active path can change over the lifetime of the connection, for example when the
+        /// peer migrates to a new address.
+        #[inline]
+        pub fn active_path(&self) -> $crate::connection::Result<$crate::connection::PathInfo> {
+            self.0.active_path()
+        }
+
+        /// Validates the path between `local_address` and `remote_address` without migrating to it
+        ///
+        /// PATH_CHALLENGE frames are sent on the path and the returned
+        /// [`PathProbe`](`crate::connection::PathProbe`) contains the round trip time measured
+        /// once the peer responds. The connection keeps using the active path for all other
+        /// traffic. Only one path can be probed at a time.
+        ///
+        /// The IO providers send every path through the socket the endpoint is bound to, so
+        /// `local_address` must use the local port of the active path, or `0` to keep it. A
+        /// different local IP address can be used where the platform supports setting the source
+        /// address of each packet. Any other local port is rejected with
+        /// [`PathError::UnsupportedLocalPort`](`crate::connection::PathError::UnsupportedLocalPort`).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), s2n_quic::connection::PathError> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #   let local_address: std::net::SocketAddr = todo!();
+        /// #   let remote_address: std::net::SocketAddr = todo!();
+        /// #
+        /// let probe = handle.probe_path(local_address, remote_address).await?;
+        /// println!("the path has an RTT of {:?}", probe.rtt);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn probe_path(
+            &mut self,
+            local_address: std::net::SocketAddr,
+            remote_address: std::net::SocketAddr,
+        ) -> core::result::Result<$crate::connection::PathProbe, $crate::connection::PathError> {
+            self.0
+                .probe_path(local_address.into(), remote_address.into())?;
+            futures::future::poll_fn(|cx| self.poll_path_probe(cx)).await
+        }
+
+        /// Polls for the result of a probe started with [`Self::probe_path`]
+        #[inline]
+        pub fn poll_path_probe(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            core::result::Result<$crate::connection::PathProbe, $crate::connection::PathError>,
+        > {
+            self.0.poll_path_probe(cx)
+        }
+
+        /// Migrates the connection to a path which was validated with [`Self::probe_path`]
+        ///
+        /// The connection switches to the path before transmitting any more packets. Packets
+        /// which were already sent on the previous path are still acknowledged or declared lost
+        /// on that path. Only clients can migrate the connection.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), s2n_quic::connection::PathError> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #   let local_address: std::net::SocketAddr = todo!();
+        /// #   let remote_address: std::net::SocketAddr = todo!();
+        /// #
+        /// let probe = handle.probe_path(local_address, remote_address).await?;
+        /// handle.migrate_to(probe)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn migrate_to(
+            &mut self,
+            path: $crate::connection::PathProbe,
+        ) -> core::result::Result<(), $crate::connection::PathError> {
+            self.0.migrate_to(path.local_address, path.remote_address)
+        }
+
         /// Returns the negotiated server name the connection is using.
         #[inline]
         pub fn server_name(&self) -> $crate::connection::Result<Option<$crate::server::Name>> {
@@ -161,6 +247,61 @@ macro_rules! impl_handle_api {
             self.0.application_protocol()
         }
 
+        /// Returns the DER-encoded certificate chain the peer authenticated with
+        ///
+        /// The chain starts with the peer's leaf certificate and can be used to authorize the
+        /// connection. Servers only receive a chain from clients if client authentication was
+        /// enabled on the TLS provider; otherwise the returned chain is empty.
+        #[inline]
+        pub fn peer_certificate_chain(&self) -> $crate::connection::Result<Vec<::bytes::Bytes>> {
+            self.0.peer_certificate_chain()
+        }
+
+        /// Exports keying material bound to the connection's TLS session
+        ///
+        /// `output` is filled with keying material derived from the `label` and `context`, as
+        /// described in [RFC 8446 Section 7.5](https://www.rfc-editor.org/rfc/rfc8446#section-7.5).
+        /// Both peers derive the same keying material, which can be used to bind
+        /// application-level keys or tokens to the connection.
+        ///
+        /// An error is returned if the handshake hasn't completed.
+        /// [`ExportError::Unsupported`]($crate::connection::ExportError::Unsupported) is returned
+        /// if the TLS provider doesn't support exporters.
+        #[inline]
+        pub fn export_keying_material(
+            &self,
+            output: &mut [u8],
+            label: &[u8],
+            context: &[u8],
+        ) -> core::result::Result<(), $crate::connection::ExportError> {
+            self.0.export_keying_material(output, label, context)
+        }
+
+        /// Returns the parameters negotiated by the TLS handshake
+        ///
+        /// This includes the cipher suite, server name, application protocol and the peer's
+        /// certificate chain, which can be used to authorize requests on the connection.
+        #[inline]
+        pub fn handshake_info(
+            &self,
+        ) -> $crate::connection::Result<$crate::connection::HandshakeInfo> {
+            self.0.handshake_info()
+        }
+
+        /// Serializes the state needed to resume the TLS session in a future connection
+        ///
+        /// Servers issue session tickets after the handshake completes, so the state may not be
+        /// available until some data has been exchanged. The state can be persisted by the
+        /// application and loaded into a new client with the `with_resumption_state` method of
+        /// the s2n-tls client builder.
+        ///
+        /// Returns `None` if the TLS provider doesn't support exporting resumption state or the
+        /// server hasn't issued a session ticket.
+        #[inline]
+        pub fn resumption_state(&self) -> $crate::connection::Result<Option<Vec<u8>>> {
+            self.0.resumption_state()
+        }
+
         /// Returns the internal identifier for the [`Connection`](`crate::Connection`)
         ///
         /// Note: This internal identifier is not the same as the connection ID included in packet
@@ -186,6 +327,140 @@ macro_rules! impl_handle_api {
             self.0.keep_alive(enabled)
         }
 
+        /// Configures how the connection is closed once every handle to it is dropped
+        ///
+        /// By default, the outstanding stream data is flushed and the peer is notified with a
+        /// CONNECTION_CLOSE frame. [`CloseOnDrop::Silent`](crate::connection::CloseOnDrop::Silent)
+        /// discards the connection without notifying the peer, which closes the connection once
+        /// its idle timer expires. Explicitly closing the connection always notifies the peer.
+        #[inline]
+        pub fn set_close_on_drop(
+            &mut self,
+            close_on_drop: $crate::connection::CloseOnDrop,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_close_on_drop(close_on_drop)
+        }
+
+        /// Limits the rate at which the connection sends data to `max_send_rate` bytes per second
+        ///
+        /// The limit is a hard cap which applies regardless of the congestion window, which is
+        /// useful for enforcing fairness between connections. The congestion controller may
+        /// still pace the connection below the limit. `None` removes the limit.
+        ///
+        /// The initial limit is configured with
+        /// [`Limits::with_max_send_rate`](crate::provider::limits::Limits::with_max_send_rate).
+        #[inline]
+        pub fn set_max_send_rate(
+            &mut self,
+            max_send_rate: Option<u64>,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_max_send_rate(max_send_rate)
+        }
+
+        /// Sets the DSCP code point which marks the connection's packets
+        ///
+        /// This overrides the code point configured in the connection's
+        /// [`Limits`](crate::provider::limits::Limits). Streams which set their own code point
+        /// with `SendStream::set_dscp` and datagrams sent with a code point keep it.
+        ///
+        /// Returns [`DscpError::InvalidCodePoint`](crate::connection::DscpError::InvalidCodePoint)
+        /// if `dscp` exceeds 63.
+        #[inline]
+        pub fn set_dscp(
+            &mut self,
+            dscp: u8,
+        ) -> core::result::Result<(), $crate::connection::DscpError> {
+            self.0.set_dscp(dscp)
+        }
+
+        /// Migrates the connection to a path which sends from the given local address
+        ///
+        /// This is useful when the endpoint is bound to a wildcard address on a host with multiple
+        /// addresses and the connection needs to send from a specific one. The address is
+        /// communicated to the socket with `IP_PKTINFO`/`IPV6_PKTINFO`, so it requires a platform
+        /// with support for packet info. If the port is `0`, the current local port is retained.
+        /// The IO providers send every path through the socket the endpoint is bound to, so any
+        /// other local port is rejected with
+        /// [`PathError::UnsupportedLocalPort`](`crate::connection::PathError::UnsupportedLocalPort`).
+        ///
+        /// The new path uses a connection ID which wasn't used on any other path and starts with
+        /// fresh congestion control and RTT state. It is validated with a PATH_CHALLENGE frame
+        /// after the connection migrates to it. Only clients can change their local address.
+        #[inline]
+        pub fn set_local_address(
+            &mut self,
+            local_address: std::net::SocketAddr,
+        ) -> core::result::Result<(), $crate::connection::PathError> {
+            self.0.set_local_address(local_address.into())
+        }
+
+        /// Waits until the connection has no more application data in flight
+        ///
+        /// The connection is idle once all of the data written to streams was sent and
+        /// acknowledged by the peer, and no datagrams are waiting to be sent. The connection stays
+        /// open, so more data can be sent after the future resolves, after which the connection can
+        /// be waited on again. The future resolves immediately if the connection is already idle.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// handle.wait_idle().await?;
+        /// println!("all of the data was delivered to the peer");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn wait_idle(&mut self) -> $crate::connection::Result<()> {
+            futures::future::poll_fn(|cx| self.poll_idle(cx)).await
+        }
+
+        /// Polls for the connection to have no more application data in flight
+        ///
+        /// See [`Self::wait_idle`] for more details.
+        #[inline]
+        pub fn poll_idle(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<()>> {
+            self.0.poll_idle(cx)
+        }
+
+        /// Returns the amount of data protected by the current 1-RTT key
+        ///
+        /// The usage resets each time the key is updated. See
+        /// [`Limits::with_key_update_packet_threshold`](crate::provider::limits::Limits::with_key_update_packet_threshold)
+        /// for configuring how often keys are updated.
+        #[inline]
+        pub fn key_usage(&self) -> $crate::connection::Result<$crate::connection::KeyUsage> {
+            self.0.key_usage()
+        }
+
+        /// Returns the statistics collected over the lifetime of the connection
+        ///
+        /// The time spent sending is split by what prevented the connection from sending more
+        /// data: the application, the peer's flow control limits, or the congestion controller.
+        /// The packets sent, acknowledged and lost are also counted for each packet number space.
+        ///
+        /// The round trip times, congestion window, bytes in flight, MTU and pacing rate reflect
+        /// the current state of the active path.
+        #[inline]
+        pub fn stats(&self) -> $crate::connection::Result<$crate::connection::Stats> {
+            self.0.stats()
+        }
+
+        /// Returns the QUIC version negotiated by the connection
+        ///
+        /// This is the version used on the wire, which is one of the versions supported by both
+        /// endpoints.
+        #[inline]
+        pub fn quic_version(&self) -> $crate::connection::Result<$crate::connection::Version> {
+            self.0.quic_version()
+        }
+
         /// Closes the Connection with the provided error code
         ///
         /// This will immediately terminate all outstanding streams.
@@ -13,6 +13,16 @@
 //!
 //! ## Feature flags
 //!
+//! ### `fips-packet-protection`
+//!
+//! Performs packet and header protection, key derivation, and the address token and retry
+//! integrity crypto with the FIPS-validated [AWS-LC](https://github.com/aws/aws-lc) module. The
+//! hand-written AES-GCM implementation is disabled in this mode.
+//!
+//! **NOTE**: this feature doesn't make the endpoint FIPS compliant on its own. It only covers
+//! packet protection; the handshake, including its key exchange and certificate verification,
+//! is performed by the TLS provider, which is still built with its regular crypto library.
+//!
 //! ### `provider-address-token-default`
 //!
 //! _Enabled by default_
@@ -22,6 +32,23 @@
 //! that multiple servers handle address tokens, this provider should not be used. Instead, a custom
 //! implementation of [`provider::address_token::Format`] should be specified.
 //!
+//! ### `provider-event-opentelemetry`
+//!
+//! Enables the [`provider::event::opentelemetry`] event provider, which records connection
+//! metrics, such as handshake durations, RTT samples and congestion windows, with
+//! [`opentelemetry`](https://docs.rs/opentelemetry).
+//!
+//! ### `provider-event-prometheus`
+//!
+//! Enables the [`provider::event::prometheus`] event provider, which aggregates connection
+//! metrics, such as handshake durations, packet loss and RTT samples, for
+//! [`prometheus`](https://docs.rs/prometheus).
+//!
+//! ### `provider-event-qlog`
+//!
+//! Enables the [`provider::event::qlog`] event provider, which writes a
+//! [qlog](https://github.com/quicwg/qlog) trace for each connection.
+//!
 //! ### `provider-event-tracing`
 //!
 //! Enables event integration with [`tracing`](https://docs.rs/tracing). The
@@ -78,13 +105,19 @@ mod tests;
         // add new unstable features to this list
         any(
             feature = "unstable_client_hello",
+            feature = "unstable-provider-connection-close-formatter",
             feature = "unstable-provider-datagram",
+            feature = "unstable-provider-io-dpdk",
+            feature = "unstable-provider-io-driven",
+            feature = "unstable-provider-io-loopback",
             feature = "unstable-provider-io-testing",
+            feature = "unstable-provider-io-uring",
             feature = "unstable-provider-io-turmoil",
             feature = "unstable-provider-io-xdp",
+            feature = "unstable-provider-io-xdp-program",
             feature = "unstable-provider-packet-interceptor",
             feature = "unstable-provider-random",
-            feature = "unstable-congestion-controller",
+            feature = "unstable-provider-wall-clock",
         ),
         // any unstable features requires at least one of the following conditions
         not(any(
@@ -18,7 +18,6 @@ pub mod stateless_reset_token;
 pub mod tls;
 
 // These providers are not currently exposed to applications
-pub(crate) mod connection_close_formatter;
 pub(crate) mod path_migration;
 pub(crate) mod sync;
 
@@ -38,6 +37,22 @@ cfg_if!(
     }
 );
 
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-connection-close-formatter"))] {
+        pub mod connection_close_formatter;
+    } else {
+        pub(crate) mod connection_close_formatter;
+    }
+);
+
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-wall-clock"))] {
+        pub mod wall_clock;
+    } else {
+        pub(crate) mod wall_clock;
+    }
+);
+
 cfg_if!(
     if #[cfg(any(test, feature = "unstable-provider-datagram"))] {
         pub mod datagram;
@@ -6,9 +6,10 @@
 //! The default provider will randomly generate a 256 bit key. This key will be used to sign and
 //! verify tokens. The key can be rotated at a duration set by the user.
 //!
-//! The default provider does not support tokens delivered in a NEW_TOKEN frame.
+//! Tokens delivered in a NEW_TOKEN frame are signed with separate keys, which are rotated at the
+//! longer period set with [`Builder::with_new_token_lifetime`].
 
-use core::{mem::size_of, time::Duration};
+use core::{convert::Infallible, mem::size_of, time::Duration};
 use hash_hasher::HashHasher;
 use s2n_codec::{DecoderBuffer, DecoderBufferMut};
 use s2n_quic_core::{
@@ -42,14 +43,20 @@ impl BaseKey {
         }
     }
 
-    pub fn hasher(&mut self, random: &mut dyn random::Generator) -> Option<hmac::Context> {
-        let key = self.poll_key(random)?;
+    pub fn hasher(
+        &mut self,
+        random: &mut dyn random::Generator,
+        now: Timestamp,
+    ) -> Option<hmac::Context> {
+        let key = self.poll_key(random, now)?;
         Some(hmac::Context::with_key(&key))
     }
 
-    fn poll_key(&mut self, random: &mut dyn random::Generator) -> Option<hmac::Key> {
-        let now = s2n_quic_platform::time::now();
-
+    fn poll_key(
+        &mut self,
+        random: &mut dyn random::Generator,
+        now: Timestamp,
+    ) -> Option<hmac::Key> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-21.3
         //# Servers SHOULD provide mitigations for this attack by limiting the
         //# usage and lifetime of address validation tokens; see Section 8.1.3.
@@ -81,6 +88,8 @@ impl BaseKey {
 
 const DEFAULT_KEY_ROTATION_PERIOD: Duration = Duration::from_millis(1000);
 
+const DEFAULT_NEW_TOKEN_LIFETIME: Duration = Duration::from_secs(24 * 60 * 60);
+
 #[derive(Debug)]
 pub struct Provider {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
@@ -91,38 +100,79 @@ pub struct Provider {
     /// To fulfill this SHOULD, we rotate the key periodically. This allows
     /// customers to control the token lifetime without adding bytes to the token itself.
     key_rotation_period: Duration,
+
+    /// The rotation period of the keys which sign NEW_TOKEN frame tokens
+    new_token_lifetime: Duration,
 }
 
 impl Default for Provider {
     fn default() -> Self {
         Self {
             key_rotation_period: DEFAULT_KEY_ROTATION_PERIOD,
+            new_token_lifetime: DEFAULT_NEW_TOKEN_LIFETIME,
+        }
+    }
+}
+
+impl Provider {
+    /// Creates a builder for the provider
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+/// A builder for the default address token [`Provider`]
+#[derive(Debug)]
+pub struct Builder {
+    new_token_lifetime: Duration,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            new_token_lifetime: DEFAULT_NEW_TOKEN_LIFETIME,
         }
     }
 }
 
+impl Builder {
+    /// Sets how long tokens delivered in NEW_TOKEN frames are accepted
+    ///
+    /// Tokens are accepted for at least `lifetime` after they were issued, and at most for twice
+    /// as long. Tokens delivered in Retry packets are signed with separate keys and are only
+    /// accepted for a short time.
+    ///
+    /// Defaults to 24 hours.
+    pub fn with_new_token_lifetime(mut self, lifetime: Duration) -> Result<Self, Infallible> {
+        self.new_token_lifetime = lifetime;
+        Ok(self)
+    }
+
+    /// Builds the [`Provider`]
+    pub fn build(self) -> Result<Provider, Infallible> {
+        Ok(Provider {
+            key_rotation_period: DEFAULT_KEY_ROTATION_PERIOD,
+            new_token_lifetime: self.new_token_lifetime,
+        })
+    }
+}
+
 impl super::Provider for Provider {
     type Format = Format;
-    type Error = core::convert::Infallible;
+    type Error = Infallible;
 
     fn start(self) -> Result<Self::Format, Self::Error> {
-        // The keys must remain valid for two rotation periods or they will regenerate their
-        // material and validation will fail.
         let format = Format {
-            key_rotation_period: self.key_rotation_period,
-            current_key_rotates_at: s2n_quic_platform::time::now(),
-            current_key: 0,
-            keys: [
-                BaseKey::new(self.key_rotation_period * 2),
-                BaseKey::new(self.key_rotation_period * 2),
-            ],
+            retry_keys: KeyRing::new(self.key_rotation_period),
+            new_token_keys: KeyRing::new(self.new_token_lifetime),
         };
 
         Ok(format)
     }
 }
 
-pub struct Format {
+/// A pair of keys which alternate signing tokens
+struct KeyRing {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
     //= type=exception
     //= reason=We use a duplicate filter to prevent tokens from being used more than once.
@@ -132,11 +182,11 @@ pub struct Format {
     /// Key validity period
     key_rotation_period: Duration,
 
-    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-    //# Servers SHOULD ensure that
-    //# tokens sent in Retry packets are only accepted for a short time.
     /// Timestamp to rotate current key
-    current_key_rotates_at: s2n_quic_core::time::Timestamp,
+    ///
+    /// This is initialized on the first token generated, since the format has no access to the
+    /// endpoint's clock until then.
+    current_key_rotates_at: Option<Timestamp>,
 
     /// Which key is used to sign
     current_key: u8,
@@ -145,12 +195,29 @@ pub struct Format {
     keys: [BaseKey; 2],
 }
 
-impl Format {
-    fn current_key(&mut self) -> u8 {
-        let now = s2n_quic_platform::time::now();
-        if now > self.current_key_rotates_at {
+impl KeyRing {
+    fn new(key_rotation_period: Duration) -> Self {
+        // The keys must remain valid for two rotation periods or they will regenerate their
+        // material and validation will fail.
+        Self {
+            key_rotation_period,
+            current_key_rotates_at: None,
+            current_key: 0,
+            keys: [
+                BaseKey::new(key_rotation_period * 2),
+                BaseKey::new(key_rotation_period * 2),
+            ],
+        }
+    }
+
+    fn current_key(&mut self, now: Timestamp) -> u8 {
+        let rotates_at = *self
+            .current_key_rotates_at
+            .get_or_insert_with(|| now + self.key_rotation_period);
+
+        if now > rotates_at {
             self.current_key ^= 1;
-            self.current_key_rotates_at = now + self.key_rotation_period;
+            self.current_key_rotates_at = Some(now + self.key_rotation_period);
 
             // TODO either clear the duplicate filter here, or implement in the BaseKey logic
             // https://github.com/aws/s2n-quic/issues/173
@@ -158,6 +225,27 @@ impl Format {
         self.current_key
     }
 
+    fn key(&mut self, token: &Token) -> &mut BaseKey {
+        &mut self.keys[token.header.key_id() as usize]
+    }
+}
+
+pub struct Format {
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# Servers SHOULD ensure that
+    //# tokens sent in Retry packets are only accepted for a short time.
+    /// Keys which sign tokens delivered in Retry packets
+    retry_keys: KeyRing,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# Tokens that are provided
+    //# in NEW_TOKEN frames (Section 19.7) need to be valid for longer but
+    //# SHOULD NOT be accepted multiple times.
+    /// Keys which sign tokens delivered in NEW_TOKEN frames
+    new_token_keys: KeyRing,
+}
+
+impl Format {
     // Retry Tokens need to include the original destination connection id from the transport
     // parameters. This OCID is included in the tag.
     fn tag_retry_token(
@@ -165,7 +253,10 @@ impl Format {
         token: &Token,
         context: &mut super::Context<'_>,
     ) -> Option<hmac::Tag> {
-        let mut ctx = self.keys[token.header.key_id() as usize].hasher(context.random)?;
+        let mut ctx = self
+            .retry_keys
+            .key(token)
+            .hasher(context.random, context.now)?;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
         //# Tokens
@@ -193,16 +284,79 @@ impl Format {
         Some(ctx.sign())
     }
 
+    // NEW_TOKEN tokens are used on a future connection, which will have a different connection
+    // ID and client port. Only the client IP address is included in the tag.
+    fn tag_new_token(
+        &mut self,
+        token: &Token,
+        context: &mut super::Context<'_>,
+    ) -> Option<hmac::Tag> {
+        let mut ctx = self
+            .new_token_keys
+            .key(token)
+            .hasher(context.random, context.now)?;
+
+        ctx.update(token.header.as_bytes());
+        ctx.update(&token.nonce);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //# Tokens sent in NEW_TOKEN frames MUST include information that allows
+        //# the server to verify that the client IP address has not changed from
+        //# when the token was issued.
+        match context.remote_address {
+            SocketAddress::IpV4 { ip, .. } => ctx.update(ip),
+            SocketAddress::IpV6 { ip, .. } => ctx.update(ip),
+            _ => {
+                // we are unable to hash the address so bail
+                return None;
+            }
+        };
+
+        Some(ctx.sign())
+    }
+
     // Using the key id in the token, verify the token
-    fn validate_retry_token(
+    fn validate_new_frame_token(
         &mut self,
         context: &mut super::Context<'_>,
         token: &Token,
-    ) -> Option<connection::InitialId> {
-        if self.keys[token.header.key_id() as usize]
+    ) -> bool {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //# Tokens that are provided
+        //# in NEW_TOKEN frames (Section 19.7) need to be valid for longer but
+        //# SHOULD NOT be accepted multiple times.
+        if self
+            .new_token_keys
+            .key(token)
             .duplicate_filter
             .contains(token)
         {
+            return false;
+        }
+
+        let tag = match self.tag_new_token(token, context) {
+            Some(tag) => tag,
+            None => return false,
+        };
+
+        if constant_time::verify_slices_are_equal(&token.hmac, tag.as_ref()).is_ok() {
+            // Ignore the outcome of adding a token to the filter because we always want to
+            // continue the connection if the filter fails.
+            let _ = self.new_token_keys.key(token).duplicate_filter.add(token);
+
+            return true;
+        }
+
+        false
+    }
+
+    // Using the key id in the token, verify the token
+    fn validate_retry_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        token: &Token,
+    ) -> Option<connection::InitialId> {
+        if self.retry_keys.key(token).duplicate_filter.contains(token) {
             return None;
         }
 
@@ -214,9 +368,7 @@ impl Format {
 
             // Ignore the outcome of adding a token to the filter because we always want to
             // continue the connection if the filter fails.
-            let _ = self.keys[token.header.key_id() as usize]
-                .duplicate_filter
-                .add(token);
+            let _ = self.retry_keys.key(token).duplicate_filter.add(token);
 
             return token.original_destination_connection_id();
         }
@@ -228,47 +380,43 @@ impl Format {
 impl super::Format for Format {
     const TOKEN_LEN: usize = size_of::<Token>();
 
-    /// The default provider does not support NEW_TOKEN frame tokens
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+    //# A server MAY provide clients with an address validation token during
+    //# one connection that can be used on a subsequent connection.
     fn generate_new_token(
         &mut self,
-        _context: &mut super::Context<'_>,
+        context: &mut super::Context<'_>,
         _source_connection_id: &connection::LocalId,
-        _output_buffer: &mut [u8],
+        output_buffer: &mut [u8],
     ) -> Option<()> {
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=418
-        //# A server MAY provide clients with an address validation token during
-        //# one connection that can be used on a subsequent connection.
+        let buffer = DecoderBufferMut::new(output_buffer);
+        let (token, _) = buffer
+            .decode::<&mut Token>()
+            .expect("Provided output buffer did not match TOKEN_LEN");
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-        //= type=TODO
-        //= tracking-issue=346
-        //# Tokens sent in NEW_TOKEN frames MUST include information that allows
-        //# the server to verify that the client IP address has not changed from
-        //# when the token was issued.
+        token.header = Header::new(
+            Source::NewTokenFrame,
+            self.new_token_keys.current_key(context.now),
+        );
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=345
         //# A token issued with NEW_TOKEN MUST NOT include information that would
         //# allow values to be linked by an observer to the connection on which
         //# it was issued.
+        token.odcid_len = 0;
+        token.original_destination_connection_id = [0; 20];
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=387
         //# A server MUST ensure that every NEW_TOKEN frame it sends
         //# is unique across all clients, with the exception of those sent to
         //# repair losses of previously sent NEW_TOKEN frames.
+        context.random.public_random_fill(&mut token.nonce[..]);
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=394
-        //# A server MAY provide clients with an address validation token during
-        //# one connection that can be used on a subsequent connection.
+        let tag = self.tag_new_token(token, context)?;
 
-        None
+        token.hmac.copy_from_slice(tag.as_ref());
+
+        Some(())
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
@@ -289,7 +437,10 @@ impl super::Format for Format {
             .decode::<&mut Token>()
             .expect("Provided output buffer did not match TOKEN_LEN");
 
-        let header = Header::new(Source::RetryPacket, self.current_key());
+        let header = Header::new(
+            Source::RetryPacket,
+            self.retry_keys.current_key(context.now),
+        );
 
         token.header = header;
         token.original_destination_connection_id[..original_destination_connection_id.len()]
@@ -338,14 +489,9 @@ impl super::Format for Format {
 
         match source {
             Source::RetryPacket => self.validate_retry_token(context, token),
-            Source::NewTokenFrame => None, // Not supported in the default provider
+            // NEW_TOKEN frame tokens are validated with `validate_new_token`
+            Source::NewTokenFrame => None,
         }
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-        //= type=TODO
-        //= tracking-issue=347
-        //# Tokens that are provided
-        //# in NEW_TOKEN frames (Section 19.7) need to be valid for longer but
-        //# SHOULD NOT be accepted multiple times.
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
         //= type=TODO
@@ -353,6 +499,41 @@ impl super::Format for Format {
         //# Clients that want to break continuity of identity with a server can
         //# discard tokens provided using the NEW_TOKEN frame.
     }
+
+    fn token_source(&self, token: &[u8]) -> Source {
+        let buffer = DecoderBuffer::new(token);
+        match buffer.decode::<&Token>() {
+            Ok((token, _)) => token.header.token_source(),
+            // Tokens which can't be decoded will fail validation as Retry tokens
+            Err(_) => Source::RetryPacket,
+        }
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+    //# Tokens
+    //# provided with NEW_TOKEN frames and Retry packets can be distinguished
+    //# by servers (see Section 8.1.1), and the latter can be validated more
+    //# strictly.
+    fn validate_new_token(&mut self, context: &mut super::Context<'_>, token: &[u8]) -> bool {
+        let buffer = DecoderBuffer::new(token);
+        let (token, remaining) = match buffer.decode::<&Token>() {
+            Ok(value) => value,
+            Err(_) => return false,
+        };
+
+        // Verify the provided token doesn't have any additional data
+        if remaining.ensure_empty().is_err() {
+            return false;
+        }
+
+        if token.header.version() != TOKEN_VERSION
+            || token.header.token_source() != Source::NewTokenFrame
+        {
+            return false;
+        }
+
+        self.validate_new_frame_token(context, token)
+    }
 }
 
 #[derive(Clone, Copy, Debug, FromBytes, AsBytes, Unaligned)]
@@ -465,22 +646,18 @@ mod tests {
     use s2n_quic_core::{
         inet::SocketAddress,
         random,
+        time::clock::testing as time,
         token::{Context, Format as FormatTrait, Source},
     };
-    use s2n_quic_platform::time;
-    use std::{net::SocketAddr, sync::Arc};
+    use std::net::SocketAddr;
 
     const TEST_KEY_ROTATION_PERIOD: Duration = Duration::from_millis(1000);
+    const TEST_NEW_TOKEN_LIFETIME: Duration = Duration::from_secs(60);
 
     fn get_test_format() -> Format {
         Format {
-            key_rotation_period: TEST_KEY_ROTATION_PERIOD,
-            keys: [
-                BaseKey::new(TEST_KEY_ROTATION_PERIOD * 2),
-                BaseKey::new(TEST_KEY_ROTATION_PERIOD * 2),
-            ],
-            current_key_rotates_at: time::now(),
-            current_key: 0,
+            retry_keys: KeyRing::new(TEST_KEY_ROTATION_PERIOD),
+            new_token_keys: KeyRing::new(TEST_NEW_TOKEN_LIFETIME),
         }
     }
 
@@ -507,10 +684,8 @@ mod tests {
 
     #[test]
     fn test_valid_retry_tokens() {
-        let clock = Arc::new(time::testing::MockClock::new());
-        time::testing::set_local_clock(clock.clone());
-
         let mut format = get_test_format();
+        let mut now = time::now();
         let first_conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
         let second_conn_id = connection::PeerId::try_from_bytes(&[1, 3, 5, 7, 9]).unwrap();
         let orig_conn_id =
@@ -519,30 +694,30 @@ mod tests {
         let mut first_token = [0; Format::TOKEN_LEN];
         let mut second_token = [0; Format::TOKEN_LEN];
         let mut random = random::testing::Generator(5);
-        let mut context = Context::new(&addr, &first_conn_id, &mut random);
+        let mut context = Context::new(&addr, &first_conn_id, &mut random, now);
 
         // Generate two tokens for different connections
         format
             .generate_retry_token(&mut context, &orig_conn_id, &mut first_token)
             .unwrap();
 
-        context = Context::new(&addr, &second_conn_id, &mut random);
+        context = Context::new(&addr, &second_conn_id, &mut random, now);
         format
             .generate_retry_token(&mut context, &orig_conn_id, &mut second_token)
             .unwrap();
 
-        clock.adjust_by(TEST_KEY_ROTATION_PERIOD);
-        context = Context::new(&addr, &first_conn_id, &mut random);
+        now += TEST_KEY_ROTATION_PERIOD;
+        context = Context::new(&addr, &first_conn_id, &mut random, now);
         assert_eq!(
             format.validate_token(&mut context, &first_token),
             Some(orig_conn_id)
         );
-        context = Context::new(&addr, &second_conn_id, &mut random);
+        context = Context::new(&addr, &second_conn_id, &mut random, now);
         assert_eq!(
             format.validate_token(&mut context, &second_token),
             Some(orig_conn_id)
         );
-        context = Context::new(&addr, &first_conn_id, &mut random);
+        context = Context::new(&addr, &first_conn_id, &mut random, now);
         assert_eq!(format.validate_token(&mut context, &second_token), None);
     }
 
@@ -555,6 +730,7 @@ mod tests {
         //# server to verify that the source IP address and port in client
         //# packets remain constant.
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
         let orig_conn_id =
             connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
@@ -564,7 +740,7 @@ mod tests {
         let addr: SocketAddr = ip_address.parse().unwrap();
         let correct_address: SocketAddress = addr.into();
         let mut random = random::testing::Generator(5);
-        let mut context = Context::new(&correct_address, &conn_id, &mut random);
+        let mut context = Context::new(&correct_address, &conn_id, &mut random, now);
         format
             .generate_retry_token(&mut context, &orig_conn_id, &mut token)
             .unwrap();
@@ -572,17 +748,17 @@ mod tests {
         let ip_address = "127.0.0.2:443";
         let addr: SocketAddr = ip_address.parse().unwrap();
         let incorrect_address: SocketAddress = addr.into();
-        context = Context::new(&incorrect_address, &conn_id, &mut random);
+        context = Context::new(&incorrect_address, &conn_id, &mut random, now);
         assert_eq!(format.validate_token(&mut context, &token), None);
 
         let ip_address = "127.0.0.1:444";
         let addr: SocketAddr = ip_address.parse().unwrap();
         let incorrect_port: SocketAddress = addr.into();
-        context = Context::new(&incorrect_port, &conn_id, &mut random);
+        context = Context::new(&incorrect_port, &conn_id, &mut random, now);
         assert_eq!(format.validate_token(&mut context, &token), None);
 
         // Verify the token is still valid after the failed attempts
-        context = Context::new(&correct_address, &conn_id, &mut random);
+        context = Context::new(&correct_address, &conn_id, &mut random, now);
         assert!(format.validate_token(&mut context, &token).is_some());
     }
 
@@ -594,27 +770,25 @@ mod tests {
         //# expiration time, which could be either an explicit expiration time or
         //# an issued timestamp that can be used to dynamically calculate the
         //# expiration time.
-        let clock = Arc::new(time::testing::MockClock::new());
-        time::testing::set_local_clock(clock.clone());
-
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::TEST_ID;
         let orig_conn_id = connection::InitialId::TEST_ID;
         let addr = SocketAddress::default();
         let mut buf = [0; Format::TOKEN_LEN];
         let mut random = random::testing::Generator(5);
-        let mut context = Context::new(&addr, &conn_id, &mut random);
+        let mut context = Context::new(&addr, &conn_id, &mut random, now);
         format
             .generate_retry_token(&mut context, &orig_conn_id, &mut buf)
             .unwrap();
 
         // Validation should succeed because the signing key is still valid, even
         // though it has been rotated from the current signing key
-        clock.adjust_by(TEST_KEY_ROTATION_PERIOD);
+        context.now += TEST_KEY_ROTATION_PERIOD;
         assert!(format.validate_token(&mut context, &buf).is_some());
 
         // Validation should fail because the key used for signing has been regenerated
-        clock.adjust_by(TEST_KEY_ROTATION_PERIOD);
+        context.now += TEST_KEY_ROTATION_PERIOD;
         assert!(format.validate_token(&mut context, &buf).is_none());
     }
 
@@ -624,16 +798,14 @@ mod tests {
         //= type=test
         //# Servers SHOULD ensure that
         //# tokens sent in Retry packets are only accepted for a short time.
-        let clock = Arc::new(time::testing::MockClock::new());
-        time::testing::set_local_clock(clock.clone());
-
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::TEST_ID;
         let orig_conn_id = connection::InitialId::TEST_ID;
         let addr = SocketAddress::default();
         let mut buf = [0; Format::TOKEN_LEN];
         let mut random = random::testing::Generator(5);
-        let mut context = Context::new(&addr, &conn_id, &mut random);
+        let mut context = Context::new(&addr, &conn_id, &mut random, now);
         format
             .generate_retry_token(&mut context, &orig_conn_id, &mut buf)
             .unwrap();
@@ -643,22 +815,20 @@ mod tests {
         //# Servers SHOULD provide mitigations for this attack by limiting the
         //# usage and lifetime of address validation tokens; see Section 8.1.3.
         // Validation should fail because multiple rotation periods have elapsed
-        clock.adjust_by(TEST_KEY_ROTATION_PERIOD * 2);
+        context.now += TEST_KEY_ROTATION_PERIOD * 2;
         assert!(format.validate_token(&mut context, &buf).is_none());
     }
 
     #[test]
     fn test_retry_validation_default_format() {
-        let clock = Arc::new(time::testing::MockClock::new());
-        time::testing::set_local_clock(clock);
-
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::TEST_ID;
         let odcid = connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
         let addr = SocketAddress::default();
         let mut buf = [0; Format::TOKEN_LEN];
         let mut random = random::testing::Generator(5);
-        let mut context = Context::new(&addr, &conn_id, &mut random);
+        let mut context = Context::new(&addr, &conn_id, &mut random, now);
         format
             .generate_retry_token(&mut context, &odcid, &mut buf)
             .unwrap();
@@ -666,7 +836,7 @@ mod tests {
         assert_eq!(format.validate_token(&mut context, &buf), Some(odcid));
 
         let wrong_conn_id = connection::PeerId::try_from_bytes(&[0, 1, 2]).unwrap();
-        context = Context::new(&addr, &wrong_conn_id, &mut random);
+        context = Context::new(&addr, &wrong_conn_id, &mut random, now);
         assert!(format.validate_token(&mut context, &buf).is_none());
     }
 
@@ -677,12 +847,13 @@ mod tests {
         //# To protect against such attacks, servers MUST ensure that
         //# replay of tokens is prevented or limited.
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::TEST_ID;
         let odcid = connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
         let addr = SocketAddress::default();
         let mut buf = [0; Format::TOKEN_LEN];
         let mut random = random::testing::Generator(5);
-        let mut context = Context::new(&addr, &conn_id, &mut random);
+        let mut context = Context::new(&addr, &conn_id, &mut random, now);
         format
             .generate_retry_token(&mut context, &odcid, &mut buf)
             .unwrap();
@@ -701,6 +872,7 @@ mod tests {
         //# the token MUST be covered by integrity protection against
         //# modification or falsification by clients.
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
         let orig_conn_id =
             connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
@@ -708,7 +880,7 @@ mod tests {
         let mut token = [0; Format::TOKEN_LEN];
 
         let mut random = random::testing::Generator(5);
-        let mut context = Context::new(&addr, &conn_id, &mut random);
+        let mut context = Context::new(&addr, &conn_id, &mut random, now);
         // Generate two tokens for different connections
         format
             .generate_retry_token(&mut context, &orig_conn_id, &mut token)
@@ -716,7 +888,7 @@ mod tests {
 
         for i in 0..Format::TOKEN_LEN {
             random = random::testing::Generator(5);
-            context = Context::new(&addr, &conn_id, &mut random);
+            context = Context::new(&addr, &conn_id, &mut random, now);
             token[i] = !token[i];
             assert!(format.validate_token(&mut context, &token).is_none());
             token[i] = !token[i];
@@ -726,12 +898,13 @@ mod tests {
     #[test]
     fn test_token_length_check() {
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
         let addr = SocketAddress::default();
 
         bolero::check!().for_each(move |token| {
             let mut random = random::testing::Generator(5);
-            let mut context = Context::new(&addr, &conn_id, &mut random);
+            let mut context = Context::new(&addr, &conn_id, &mut random, now);
             assert!(format.validate_token(&mut context, token).is_none())
         });
     }
@@ -739,6 +912,7 @@ mod tests {
     #[test]
     fn test_token_falsification_detection() {
         let mut format = get_test_format();
+        let now = time::now();
         let conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
         let addr = SocketAddress::default();
 
@@ -754,8 +928,110 @@ mod tests {
             .with_generator(generator)
             .for_each(move |token| {
                 let mut random = random::testing::Generator(5);
-                let mut context = Context::new(&addr, &conn_id, &mut random);
+                let mut context = Context::new(&addr, &conn_id, &mut random, now);
                 assert!(format.validate_token(&mut context, token).is_none())
             });
     }
+
+    #[test]
+    fn test_new_tokens() {
+        let mut format = get_test_format();
+        let now = time::now();
+        let first_conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
+        let second_conn_id = connection::PeerId::try_from_bytes(&[1, 3, 5, 7, 9]).unwrap();
+        let addr: SocketAddress = "127.0.0.1:443".parse::<SocketAddr>().unwrap().into();
+        let mut buf = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &first_conn_id, &mut random, now);
+        format
+            .generate_new_token(&mut context, &connection::LocalId::TEST_ID, &mut buf)
+            .unwrap();
+
+        assert_eq!(format.token_source(&buf), Source::NewTokenFrame);
+        // NEW_TOKEN frame tokens can't be used in place of Retry tokens
+        assert!(format.validate_token(&mut context, &buf).is_none());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //= type=test
+        //# Tokens sent in NEW_TOKEN frames MUST include information that allows
+        //# the server to verify that the client IP address has not changed from
+        //# when the token was issued.
+        let wrong_addr: SocketAddress = "127.0.0.2:443".parse::<SocketAddr>().unwrap().into();
+        context = Context::new(&wrong_addr, &second_conn_id, &mut random, now);
+        assert!(!format.validate_new_token(&mut context, &buf));
+
+        // The token is valid on a new connection from a different port
+        let new_port: SocketAddress = "127.0.0.1:444".parse::<SocketAddr>().unwrap().into();
+        context = Context::new(&new_port, &second_conn_id, &mut random, now);
+        assert!(format.validate_new_token(&mut context, &buf));
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //= type=test
+        //# Tokens that are provided
+        //# in NEW_TOKEN frames (Section 19.7) need to be valid for longer but
+        //# SHOULD NOT be accepted multiple times.
+        assert!(!format.validate_new_token(&mut context, &buf));
+
+        // Retry tokens can't be used in place of NEW_TOKEN frame tokens
+        format
+            .generate_retry_token(&mut context, &connection::InitialId::TEST_ID, &mut buf)
+            .unwrap();
+        assert_eq!(format.token_source(&buf), Source::RetryPacket);
+        assert!(!format.validate_new_token(&mut context, &buf));
+    }
+
+    #[test]
+    fn test_new_token_lifetime() {
+        let mut format = get_test_format();
+        let conn_id = connection::PeerId::TEST_ID;
+        let addr: SocketAddress = "127.0.0.1:443".parse::<SocketAddr>().unwrap().into();
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random, time::now());
+
+        let mut first_token = [0; Format::TOKEN_LEN];
+        let mut second_token = [0; Format::TOKEN_LEN];
+        for token in [&mut first_token, &mut second_token] {
+            format
+                .generate_new_token(&mut context, &connection::LocalId::TEST_ID, token)
+                .unwrap();
+        }
+        let mut retry_token = [0; Format::TOKEN_LEN];
+        format
+            .generate_retry_token(
+                &mut context,
+                &connection::InitialId::TEST_ID,
+                &mut retry_token,
+            )
+            .unwrap();
+
+        // The Retry keys are regenerated without affecting the NEW_TOKEN keys
+        context.now += TEST_KEY_ROTATION_PERIOD * 2;
+        assert!(format.validate_token(&mut context, &retry_token).is_none());
+        assert!(format.validate_new_token(&mut context, &first_token));
+
+        // NEW_TOKEN frame tokens expire once their key is regenerated
+        context.now += TEST_NEW_TOKEN_LIFETIME * 2;
+        assert!(!format.validate_new_token(&mut context, &second_token));
+    }
+
+    #[test]
+    fn test_new_token_lifetime_builder() {
+        use crate::provider::address_token::Provider as _;
+
+        let format = Provider::builder()
+            .with_new_token_lifetime(TEST_NEW_TOKEN_LIFETIME)
+            .unwrap()
+            .build()
+            .unwrap()
+            .start()
+            .unwrap();
+        assert_eq!(
+            format.new_token_keys.key_rotation_period,
+            TEST_NEW_TOKEN_LIFETIME
+        );
+        assert_eq!(
+            format.retry_keys.key_rotation_period,
+            DEFAULT_KEY_ROTATION_PERIOD
+        );
+    }
 }
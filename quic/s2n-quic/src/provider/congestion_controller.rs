@@ -1,8 +1,92 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use cfg_if::cfg_if;
-pub use s2n_quic_core::recovery::congestion_controller::Endpoint;
+//! Provides congestion controller support for an endpoint
+//!
+//! Two congestion controllers are included:
+//!
+//! * [`Cubic`], the default, implements [CUBIC](https://www.rfc-editor.org/rfc/rfc8312.html)
+//!   with Hybrid Slow Start. The algorithm used to exit slow start can be selected with
+//!   [`SlowStartExit`], including [HyStart++](https://www.rfc-editor.org/rfc/rfc9406.html).
+//! * [`Bbr`] implements [BBRv2](https://datatracker.ietf.org/doc/draft-cardwell-iccrg-bbr-congestion-control/),
+//!   which models the bottleneck bandwidth and round trip time of the path instead of reacting to
+//!   every loss. This tends to perform better than CUBIC on paths with a large bandwidth-delay
+//!   product or shallow buffers.
+//!
+//! The congestion controller is selected when building the endpoint:
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use s2n_quic::{provider::congestion_controller, Server};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let server = Server::builder()
+//!     .with_congestion_controller(congestion_controller::Bbr::default())?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+//!
+//! The included congestion controllers can be tuned with their builders. For example, a larger
+//! initial window can reduce the time to transfer small responses on networks which are known to
+//! have enough capacity:
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use s2n_quic::{provider::congestion_controller, Server};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let cubic = congestion_controller::Cubic::builder()
+//!     .with_initial_window_packets(30)?
+//!     .build();
+//!
+//! let server = Server::builder()
+//!     .with_congestion_controller(cubic)?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+//!
+//! Slow start can be exited with HyStart++, which slows down the window growth once the RTT
+//! increases instead of exiting immediately, avoiding premature exits on paths with a noisy RTT:
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use s2n_quic::{provider::congestion_controller, Server};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let cubic = congestion_controller::Cubic::builder()
+//!     .with_slow_start_exit(congestion_controller::SlowStartExit::HyStartPlusPlus)
+//!     .build();
+//!
+//! let server = Server::builder()
+//!     .with_congestion_controller(cubic)?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+//!
+//! Applications can also provide their own algorithm by implementing [`CongestionController`],
+//! along with an [`Endpoint`] which creates a congestion controller for each path. See the
+//! [custom congestion controller example](https://github.com/aws/s2n-quic/tree/main/examples/custom-congestion-controller)
+//! for a complete implementation.
+
+pub use s2n_quic_core::{
+    event::builder::{BbrState, SlowStartExitCause},
+    random::Generator as RandomGenerator,
+    recovery::{
+        bandwidth::{Bandwidth, RateSample},
+        congestion_controller::{CongestionController, Endpoint, PacingGain, PathInfo, Publisher},
+        RttEstimator,
+    },
+    time::Timestamp,
+};
 
 /// Provides congestion controller support for an endpoint
 pub trait Provider {
@@ -12,18 +96,10 @@ pub trait Provider {
     fn start(self) -> Result<Self::Endpoint, Self::Error>;
 }
 
-cfg_if! {
-    if #[cfg(feature = "unstable-congestion-controller")] {
-        // Export the types needed to implement the CongestionController trait
-        pub use s2n_quic_core::{
-            random::Generator as RandomGenerator,
-            recovery::{congestion_controller::{CongestionController, PathInfo, Publisher}, RttEstimator},
-            time::Timestamp,
-        };
-    }
-}
-
-pub use s2n_quic_core::recovery::{bbr::Endpoint as Bbr, cubic::Endpoint as Cubic};
+pub use s2n_quic_core::recovery::{
+    bbr::Endpoint as Bbr,
+    cubic::{Endpoint as Cubic, SlowStartExit},
+};
 pub type Default = Cubic;
 
 impl_provider_utils!();
@@ -58,6 +58,7 @@ pub mod default {
     pub struct Format {
         len: usize,
         lifetime: Option<Duration>,
+        worker: Option<Worker>,
     }
 
     impl Default for Format {
@@ -65,6 +66,7 @@ pub mod default {
             Self {
                 len: DEFAULT_LEN,
                 lifetime: None,
+                worker: None,
             }
         }
     }
@@ -81,6 +83,7 @@ pub mod default {
     pub struct Builder {
         len: usize,
         lifetime: Option<Duration>,
+        worker: Option<Worker>,
     }
 
     impl Default for Builder {
@@ -88,10 +91,18 @@ pub mod default {
             Self {
                 len: DEFAULT_LEN,
                 lifetime: None,
+                worker: None,
             }
         }
     }
 
+    /// The index of the endpoint which is encoded in the first byte of each connection Id
+    #[derive(Clone, Copy, Debug)]
+    struct Worker {
+        index: u8,
+        count: u8,
+    }
+
     impl Builder {
         /// Sets the length of the generated connection Id
         pub fn with_len(mut self, len: usize) -> Result<Self, connection::id::Error> {
@@ -111,11 +122,30 @@ pub mod default {
             Ok(self)
         }
 
+        /// Encodes the index of the endpoint in each generated connection Id
+        ///
+        /// The first byte of each connection Id modulo `count` is `index`. This lets the peer's
+        /// packets be steered to the endpoint with a stateless load balancer, e.g. the
+        /// SO_REUSEPORT group of a server started with `Server::start_workers`.
+        ///
+        /// Changing `count` reshuffles the workers of the Ids which were already issued, since
+        /// their first byte modulo the new count is a different index. The packets of existing
+        /// connections are then steered to endpoints which don't know about them, so `count`
+        /// should only change when those connections can be dropped.
+        pub fn with_worker(mut self, index: u8, count: u8) -> Result<Self, connection::id::Error> {
+            if index >= count {
+                return Err(connection::id::Error::InvalidWorker);
+            }
+            self.worker = Some(Worker { index, count });
+            Ok(self)
+        }
+
         /// Builds the [`Format`] into a provider
         pub fn build(self) -> Result<Format, core::convert::Infallible> {
             Ok(Format {
                 len: self.len,
                 lifetime: self.lifetime,
+                worker: self.worker,
             })
         }
     }
@@ -125,6 +155,14 @@ pub mod default {
             let mut id = [0u8; connection::id::MAX_LEN];
             let id = &mut id[..self.len];
             rand::thread_rng().fill_bytes(id);
+
+            if let Some(Worker { index, count }) = self.worker {
+                // keep the first byte random while making its remainder the worker index
+                let count = count as u16;
+                let first = (id[0] as u16 % (256 / count)) * count + index as u16;
+                id[0] = first as u8;
+            }
+
             (&*id).try_into().expect("length already checked")
         }
 
@@ -204,5 +242,32 @@ pub mod default {
                     .err()
             );
         }
+
+        #[test]
+        fn worker_test() {
+            let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+            let connection_info = ConnectionInfo::new(remote_address);
+
+            for count in [1, 2, 3, 7, 16, 255] {
+                for index in 0..count {
+                    let mut format = Format::builder()
+                        .with_worker(index, count)
+                        .unwrap()
+                        .build()
+                        .unwrap();
+
+                    for _ in 0..100 {
+                        let id = format.generate(&connection_info);
+                        assert_eq!(id.as_ref()[0] % count, index);
+                        assert_eq!(id.len(), DEFAULT_LEN);
+                    }
+                }
+
+                assert_eq!(
+                    Some(connection::id::Error::InvalidWorker),
+                    Format::builder().with_worker(count, count).err()
+                );
+            }
+        }
     }
 }
@@ -180,6 +180,7 @@ pub mod default {
     #[derive(Default)]
     pub struct Builder {
         max_inflight_handshake_limit: Option<usize>,
+        accept_backlog: Option<usize>,
     }
 
     impl Builder {
@@ -189,10 +190,22 @@ pub mod default {
             Ok(self)
         }
 
+        /// Sets the maximum number of connections which are waiting to be accepted by the
+        /// application
+        ///
+        /// Handshakes which are still in progress count towards the backlog, since they are
+        /// queued once they complete. Connection attempts which exceed the backlog are refused
+        /// until the application accepts the pending connections.
+        pub fn with_accept_backlog(mut self, limit: usize) -> Result<Self, Infallible> {
+            self.accept_backlog = Some(limit);
+            Ok(self)
+        }
+
         /// Build the limits
         pub fn build(self) -> Result<Limits, Infallible> {
             Ok(Limits {
                 max_inflight_handshake_limit: self.max_inflight_handshake_limit,
+                accept_backlog: self.accept_backlog,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             })
         }
@@ -202,6 +215,9 @@ pub mod default {
     pub struct Limits {
         /// Maximum number of handshakes to allow before Retry packets are queued
         max_inflight_handshake_limit: Option<usize>,
+        /// Maximum number of connections waiting to be accepted before connection attempts are
+        /// refused
+        accept_backlog: Option<usize>,
         rate_limiter: [BasicRateLimiter; THROTTLED_PORTS_LEN],
     }
 
@@ -227,6 +243,12 @@ pub mod default {
                 }
             }
 
+            if let Some(limit) = self.accept_backlog {
+                if info.pending_accepts + info.inflight_handshakes >= limit {
+                    return Outcome::close();
+                }
+            }
+
             if let Some(limit) = self.max_inflight_handshake_limit {
                 if info.inflight_handshakes >= limit {
                     return Outcome::retry();
@@ -242,6 +264,7 @@ pub mod default {
         fn default() -> Self {
             Self {
                 max_inflight_handshake_limit: None,
+                accept_backlog: None,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             }
         }
@@ -257,6 +280,34 @@ pub mod default {
         assert_eq!(elp.max_inflight_handshake_limit, Some(100));
     }
 
+    #[test]
+    fn accept_backlog_connection_attempt() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            inet::SocketAddress,
+            time::{testing::Clock as MockClock, Clock},
+        };
+
+        let mut remote_address = SocketAddress::default();
+        remote_address.set_port(4433);
+        let mut limits = Limits::builder()
+            .with_accept_backlog(2)
+            .unwrap()
+            .build()
+            .unwrap();
+        let now = MockClock::default().get_time().into_event();
+
+        let info = ConnectionAttempt::new(1, 1, &remote_address, now);
+        assert_eq!(Outcome::allow(), limits.on_connection_attempt(&info));
+
+        // in-progress handshakes are queued once they complete
+        let info = ConnectionAttempt::new(1, 2, &remote_address, now).with_pending_accepts(1);
+        assert_eq!(Outcome::close(), limits.on_connection_attempt(&info));
+
+        let info = ConnectionAttempt::new(0, 2, &remote_address, now).with_pending_accepts(2);
+        assert_eq!(Outcome::close(), limits.on_connection_attempt(&info));
+    }
+
     #[test]
     fn blocked_port_connection_attempt() {
         use s2n_quic_core::{
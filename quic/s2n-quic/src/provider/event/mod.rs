@@ -29,6 +29,18 @@ pub mod disabled;
 #[cfg(any(feature = "provider-event-tracing", test))]
 pub mod tracing;
 
+/// This module contains event integration with [`opentelemetry`](https://docs.rs/opentelemetry)
+#[cfg(feature = "provider-event-opentelemetry")]
+pub mod opentelemetry;
+
+/// This module contains event integration with [`prometheus`](https://docs.rs/prometheus)
+#[cfg(feature = "provider-event-prometheus")]
+pub mod prometheus;
+
+/// This module contains a subscriber which writes [qlog](https://github.com/quicwg/qlog) traces
+#[cfg(feature = "provider-event-qlog")]
+pub mod qlog;
+
 cfg_if! {
     if #[cfg(any(feature = "provider-event-tracing", test))] {
         pub use self::tracing as default;
@@ -0,0 +1,261 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records connection metrics with [`opentelemetry`](https://docs.rs/opentelemetry)
+//!
+//! The following instruments are created on the provided [`Meter`]:
+//!
+//! | Name | Instrument | Description |
+//! |------|------------|-------------|
+//! | `s2n_quic.connections.started` | Counter | The number of connections which were started |
+//! | `s2n_quic.connections.closed` | Counter | The number of connections which were closed |
+//! | `s2n_quic.connections.active` | UpDownCounter | The number of open connections |
+//! | `s2n_quic.handshake.duration` | Histogram (s) | The time it took to complete the handshake |
+//! | `s2n_quic.bytes.sent` | Counter (By) | The number of bytes sent in UDP datagrams |
+//! | `s2n_quic.bytes.received` | Counter (By) | The number of bytes received in UDP datagrams |
+//! | `s2n_quic.packets.sent` | Counter | The number of packets sent |
+//! | `s2n_quic.packets.lost` | Counter | The number of packets declared lost |
+//! | `s2n_quic.rtt` | Histogram (s) | The latest RTT samples |
+//! | `s2n_quic.congestion_window` | UpDownCounter (By) | The sum of the congestion windows of the open connections |
+//!
+//! The loss rate can be derived by dividing `s2n_quic.packets.lost` by `s2n_quic.packets.sent`.
+//!
+//! Each measurement includes an `endpoint.type` attribute with either `client` or `server`.
+
+use crate::provider::event::{events, ConnectionInfo, ConnectionMeta, Timestamp};
+use opentelemetry::{
+    metrics::{Counter, Histogram, Meter, Unit, UpDownCounter},
+    KeyValue,
+};
+
+#[derive(Clone, Debug)]
+pub struct Provider {
+    meter: Meter,
+}
+
+impl Provider {
+    /// Creates a provider which records metrics on the given `meter`
+    pub fn new(meter: Meter) -> Self {
+        Self { meter }
+    }
+}
+
+impl Default for Provider {
+    /// Records metrics on a meter named `s2n-quic` from the global meter provider
+    fn default() -> Self {
+        Self::new(opentelemetry::global::meter("s2n-quic"))
+    }
+}
+
+impl super::Provider for Provider {
+    type Subscriber = Subscriber;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Subscriber, Self::Error> {
+        Ok(Subscriber::new(&self.meter))
+    }
+}
+
+/// Records connection events as OpenTelemetry metrics
+#[derive(Clone, Debug)]
+pub struct Subscriber {
+    connections_started: Counter<u64>,
+    connections_closed: Counter<u64>,
+    connections_active: UpDownCounter<i64>,
+    handshake_duration: Histogram<f64>,
+    bytes_sent: Counter<u64>,
+    bytes_received: Counter<u64>,
+    packets_sent: Counter<u64>,
+    packets_lost: Counter<u64>,
+    rtt: Histogram<f64>,
+    congestion_window: UpDownCounter<i64>,
+}
+
+impl Subscriber {
+    /// Creates the instruments on the given `meter`
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            connections_started: meter
+                .u64_counter("s2n_quic.connections.started")
+                .with_description("The number of connections which were started")
+                .init(),
+            connections_closed: meter
+                .u64_counter("s2n_quic.connections.closed")
+                .with_description("The number of connections which were closed")
+                .init(),
+            connections_active: meter
+                .i64_up_down_counter("s2n_quic.connections.active")
+                .with_description("The number of open connections")
+                .init(),
+            handshake_duration: meter
+                .f64_histogram("s2n_quic.handshake.duration")
+                .with_description("The time it took to complete the handshake")
+                .with_unit(Unit::new("s"))
+                .init(),
+            bytes_sent: meter
+                .u64_counter("s2n_quic.bytes.sent")
+                .with_description("The number of bytes sent in UDP datagrams")
+                .with_unit(Unit::new("By"))
+                .init(),
+            bytes_received: meter
+                .u64_counter("s2n_quic.bytes.received")
+                .with_description("The number of bytes received in UDP datagrams")
+                .with_unit(Unit::new("By"))
+                .init(),
+            packets_sent: meter
+                .u64_counter("s2n_quic.packets.sent")
+                .with_description("The number of packets sent")
+                .init(),
+            packets_lost: meter
+                .u64_counter("s2n_quic.packets.lost")
+                .with_description("The number of packets declared lost")
+                .init(),
+            rtt: meter
+                .f64_histogram("s2n_quic.rtt")
+                .with_description("The latest RTT samples")
+                .with_unit(Unit::new("s"))
+                .init(),
+            congestion_window: meter
+                .i64_up_down_counter("s2n_quic.congestion_window")
+                .with_description("The sum of the congestion windows of the open connections")
+                .with_unit(Unit::new("By"))
+                .init(),
+        }
+    }
+}
+
+impl Default for Subscriber {
+    fn default() -> Self {
+        Self::new(&opentelemetry::global::meter("s2n-quic"))
+    }
+}
+
+/// Per-connection state needed to derive the metrics
+#[derive(Debug)]
+pub struct ConnectionContext {
+    attributes: [KeyValue; 1],
+    start: Timestamp,
+    is_handshake_complete: bool,
+    congestion_window: i64,
+}
+
+impl super::Subscriber for Subscriber {
+    type ConnectionContext = ConnectionContext;
+
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let endpoint_type = match meta.endpoint_type {
+            events::EndpointType::Client { .. } => "client",
+            events::EndpointType::Server { .. } => "server",
+        };
+
+        ConnectionContext {
+            attributes: [KeyValue::new("endpoint.type", endpoint_type)],
+            start: meta.timestamp,
+            is_handshake_complete: false,
+            congestion_window: 0,
+        }
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::ConnectionStarted,
+    ) {
+        self.connections_started.add(1, &context.attributes);
+        self.connections_active.add(1, &context.attributes);
+    }
+
+    fn on_handshake_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::HandshakeStatusUpdated,
+    ) {
+        if context.is_handshake_complete
+            || !matches!(event.status, events::HandshakeStatus::Complete { .. })
+        {
+            return;
+        }
+
+        context.is_handshake_complete = true;
+        let duration = meta.timestamp.saturating_duration_since(context.start);
+        self.handshake_duration
+            .record(duration.as_secs_f64(), &context.attributes);
+    }
+
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramSent,
+    ) {
+        self.bytes_sent.add(event.len as u64, &context.attributes);
+    }
+
+    fn on_datagram_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramReceived,
+    ) {
+        self.bytes_received
+            .add(event.len as u64, &context.attributes);
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::PacketSent,
+    ) {
+        self.packets_sent.add(1, &context.attributes);
+    }
+
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::PacketLost,
+    ) {
+        self.packets_lost.add(1, &context.attributes);
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        self.rtt
+            .record(event.latest_rtt.as_secs_f64(), &context.attributes);
+
+        // only the change is recorded so the counter reflects the current windows of the open
+        // connections
+        let congestion_window = event.congestion_window as i64;
+        self.congestion_window.add(
+            congestion_window - context.congestion_window,
+            &context.attributes,
+        );
+        context.congestion_window = congestion_window;
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::ConnectionClosed,
+    ) {
+        self.connections_closed.add(1, &context.attributes);
+        self.connections_active.add(-1, &context.attributes);
+
+        // remove the connection's window from the total
+        self.congestion_window
+            .add(-context.congestion_window, &context.attributes);
+        context.congestion_window = 0;
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Aggregates connection metrics for [`prometheus`](https://docs.rs/prometheus)
+//!
+//! The following metrics are aggregated by the [`Subscriber`]:
+//!
+//! | Name | Type | Description |
+//! |------|------|-------------|
+//! | `s2n_quic_connections_started_total` | Counter | The number of connections which were started |
+//! | `s2n_quic_connections_closed_total` | Counter | The number of connections which were closed |
+//! | `s2n_quic_connections_active` | Gauge | The number of open connections |
+//! | `s2n_quic_handshake_duration_seconds` | Histogram | The time it took to complete the handshake |
+//! | `s2n_quic_bytes_sent_total` | Counter | The number of bytes sent in UDP datagrams |
+//! | `s2n_quic_bytes_received_total` | Counter | The number of bytes received in UDP datagrams |
+//! | `s2n_quic_packets_sent_total` | Counter | The number of packets sent |
+//! | `s2n_quic_packets_lost_total` | Counter | The number of packets declared lost |
+//! | `s2n_quic_rtt_seconds` | Histogram | The latest RTT samples |
+//! | `s2n_quic_congestion_window_bytes` | Gauge | The sum of the congestion windows of the open connections |
+//!
+//! Each metric has an `endpoint_type` label with either `client` or `server`.
+//!
+//! The subscriber implements [`Collector`] so it can be registered with the application's
+//! [`Registry`](https://docs.rs/prometheus/latest/prometheus/struct.Registry.html), or the metrics can be gathered directly:
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use s2n_quic::{provider::event::prometheus, Server};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let metrics = prometheus::Subscriber::new()?;
+//!
+//! let server = Server::builder()
+//!     .with_event(metrics.clone())?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//!
+//! let families = metrics.gather();
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use crate::provider::event::{events, ConnectionInfo, ConnectionMeta, Timestamp};
+use core::fmt;
+use prometheus::{
+    core::{Collector, Desc},
+    exponential_buckets,
+    proto::MetricFamily,
+    Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Result,
+};
+
+const ENDPOINT_TYPE: &str = "endpoint_type";
+
+/// Aggregates connection events into prometheus metrics
+///
+/// Clones of the subscriber share the same metrics, so a clone can be kept to gather the metrics
+/// of the endpoints it was passed to.
+#[derive(Clone)]
+pub struct Subscriber {
+    connections_started: IntCounterVec,
+    connections_closed: IntCounterVec,
+    connections_active: IntGaugeVec,
+    handshake_duration: HistogramVec,
+    bytes_sent: IntCounterVec,
+    bytes_received: IntCounterVec,
+    packets_sent: IntCounterVec,
+    packets_lost: IntCounterVec,
+    rtt: HistogramVec,
+    congestion_window: IntGaugeVec,
+}
+
+impl Subscriber {
+    /// Creates the metrics
+    pub fn new() -> Result<Self> {
+        let counter =
+            |name: &str, help: &str| IntCounterVec::new(Opts::new(name, help), &[ENDPOINT_TYPE]);
+        let gauge =
+            |name: &str, help: &str| IntGaugeVec::new(Opts::new(name, help), &[ENDPOINT_TYPE]);
+
+        Ok(Self {
+            connections_started: counter(
+                "s2n_quic_connections_started_total",
+                "The number of connections which were started",
+            )?,
+            connections_closed: counter(
+                "s2n_quic_connections_closed_total",
+                "The number of connections which were closed",
+            )?,
+            connections_active: gauge(
+                "s2n_quic_connections_active",
+                "The number of open connections",
+            )?,
+            handshake_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "s2n_quic_handshake_duration_seconds",
+                    "The time it took to complete the handshake",
+                )
+                .buckets(exponential_buckets(0.001, 2.0, 14)?),
+                &[ENDPOINT_TYPE],
+            )?,
+            bytes_sent: counter(
+                "s2n_quic_bytes_sent_total",
+                "The number of bytes sent in UDP datagrams",
+            )?,
+            bytes_received: counter(
+                "s2n_quic_bytes_received_total",
+                "The number of bytes received in UDP datagrams",
+            )?,
+            packets_sent: counter("s2n_quic_packets_sent_total", "The number of packets sent")?,
+            packets_lost: counter(
+                "s2n_quic_packets_lost_total",
+                "The number of packets declared lost",
+            )?,
+            rtt: HistogramVec::new(
+                HistogramOpts::new("s2n_quic_rtt_seconds", "The latest RTT samples")
+                    .buckets(exponential_buckets(0.0001, 2.0, 16)?),
+                &[ENDPOINT_TYPE],
+            )?,
+            congestion_window: gauge(
+                "s2n_quic_congestion_window_bytes",
+                "The sum of the congestion windows of the open connections",
+            )?,
+        })
+    }
+
+    /// Returns the current values of the metrics
+    pub fn gather(&self) -> Vec<MetricFamily> {
+        self.collect()
+    }
+
+    fn collectors(&self) -> [&dyn Collector; 10] {
+        [
+            &self.connections_started,
+            &self.connections_closed,
+            &self.connections_active,
+            &self.handshake_duration,
+            &self.bytes_sent,
+            &self.bytes_received,
+            &self.packets_sent,
+            &self.packets_lost,
+            &self.rtt,
+            &self.congestion_window,
+        ]
+    }
+}
+
+impl fmt::Debug for Subscriber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Subscriber").finish()
+    }
+}
+
+impl Collector for Subscriber {
+    fn desc(&self) -> Vec<&Desc> {
+        self.collectors()
+            .into_iter()
+            .flat_map(|collector| collector.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.collectors()
+            .into_iter()
+            .flat_map(|collector| collector.collect())
+            .collect()
+    }
+}
+
+/// The metrics of a connection's endpoint type, along with the state needed to derive them
+pub struct ConnectionContext {
+    connections_started: IntCounter,
+    connections_closed: IntCounter,
+    connections_active: IntGauge,
+    handshake_duration: Histogram,
+    bytes_sent: IntCounter,
+    bytes_received: IntCounter,
+    packets_sent: IntCounter,
+    packets_lost: IntCounter,
+    rtt: Histogram,
+    congestion_window: IntGauge,
+    start: Timestamp,
+    is_handshake_complete: bool,
+    window: i64,
+}
+
+impl fmt::Debug for ConnectionContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConnectionContext")
+            .field("start", &self.start)
+            .field("is_handshake_complete", &self.is_handshake_complete)
+            .field("window", &self.window)
+            .finish()
+    }
+}
+
+impl super::Subscriber for Subscriber {
+    type ConnectionContext = ConnectionContext;
+
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let endpoint_type = match meta.endpoint_type {
+            events::EndpointType::Client { .. } => "client",
+            events::EndpointType::Server { .. } => "server",
+        };
+        let labels = &[endpoint_type];
+
+        // the metrics are looked up once so events don't need to hash the labels
+        ConnectionContext {
+            connections_started: self.connections_started.with_label_values(labels),
+            connections_closed: self.connections_closed.with_label_values(labels),
+            connections_active: self.connections_active.with_label_values(labels),
+            handshake_duration: self.handshake_duration.with_label_values(labels),
+            bytes_sent: self.bytes_sent.with_label_values(labels),
+            bytes_received: self.bytes_received.with_label_values(labels),
+            packets_sent: self.packets_sent.with_label_values(labels),
+            packets_lost: self.packets_lost.with_label_values(labels),
+            rtt: self.rtt.with_label_values(labels),
+            congestion_window: self.congestion_window.with_label_values(labels),
+            start: meta.timestamp,
+            is_handshake_complete: false,
+            window: 0,
+        }
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::ConnectionStarted,
+    ) {
+        context.connections_started.inc();
+        context.connections_active.inc();
+    }
+
+    fn on_handshake_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::HandshakeStatusUpdated,
+    ) {
+        if context.is_handshake_complete
+            || !matches!(event.status, events::HandshakeStatus::Complete { .. })
+        {
+            return;
+        }
+
+        context.is_handshake_complete = true;
+        let duration = meta.timestamp.saturating_duration_since(context.start);
+        context.handshake_duration.observe(duration.as_secs_f64());
+    }
+
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramSent,
+    ) {
+        context.bytes_sent.inc_by(event.len as u64);
+    }
+
+    fn on_datagram_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::DatagramReceived,
+    ) {
+        context.bytes_received.inc_by(event.len as u64);
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::PacketSent,
+    ) {
+        context.packets_sent.inc();
+    }
+
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::PacketLost,
+    ) {
+        context.packets_lost.inc();
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        // The metrics are published on every transmission and ACK, so `latest_rtt` is only
+        // recorded when it's a new sample
+        if event.rtt_sampled {
+            context.rtt.observe(event.latest_rtt.as_secs_f64());
+        }
+
+        // only the change is added so the gauge reflects the current windows of the open
+        // connections
+        let window = event.congestion_window as i64;
+        context.congestion_window.add(window - context.window);
+        context.window = window;
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::ConnectionClosed,
+    ) {
+        context.connections_closed.inc();
+        context.connections_active.dec();
+
+        // remove the connection's window from the total
+        context.congestion_window.sub(context.window);
+        context.window = 0;
+    }
+}
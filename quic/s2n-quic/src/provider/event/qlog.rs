@@ -0,0 +1,667 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes a [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/) trace for
+//! each connection
+//!
+//! Traces use the `JSON-SEQ` serialization, which can be loaded into tools such as
+//! [qvis](https://qvis.quictools.info). Each trace is named after the first local connection ID
+//! and the endpoint type, e.g. `0123456789abcdef_client.sqlog`.
+//!
+//! The following events are recorded:
+//!
+//! * `connectivity:connection_started`
+//! * `connectivity:connection_state_updated`
+//! * `connectivity:connection_closed`
+//! * `transport:parameters_set`
+//! * `transport:packet_sent`
+//! * `transport:packet_received`
+//! * `transport:datagrams_sent`
+//! * `transport:datagrams_received`
+//! * `recovery:metrics_updated`
+//! * `recovery:packet_lost`
+//!
+//! Traces are written by a background thread so connections don't block on the outputs. Records
+//! are queued to the thread in a bounded channel and are dropped if it falls behind; the number
+//! of dropped records can be read from the [`DroppedRecords`] returned by
+//! [`Provider::dropped_records`].
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use s2n_quic::{provider::event::qlog, Server};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let server = Server::builder()
+//!     .with_event(qlog::Provider::new("/tmp/qlog"))?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use crate::provider::event::{events, ConnectionInfo, ConnectionMeta, Timestamp};
+use core::{fmt, time::Duration};
+use s2n_quic_core::time::{SystemClock, WallClock};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::SystemTime,
+};
+
+/// The output a trace is written to
+pub type Output = Box<dyn Write + Send>;
+
+type Open = Box<dyn FnMut(&str) -> io::Result<Output> + Send>;
+
+/// The number of records which can be queued to the background thread before they are dropped
+const CHANNEL_CAPACITY: usize = 4096;
+
+/// An event provider which writes a qlog trace for each connection
+///
+/// See the [module documentation](self) for the recorded events and the trace format.
+pub struct Provider {
+    directory: Option<PathBuf>,
+    open: Open,
+    dropped: DroppedRecords,
+}
+
+impl Provider {
+    /// Creates a provider which writes each trace to a file in `directory`
+    ///
+    /// The directory is created when the endpoint is started, if it doesn't exist.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        let directory = directory.into();
+        let path = directory.clone();
+        Self {
+            directory: Some(directory),
+            open: Box::new(move |name| {
+                let file = fs::File::create(path.join(name))?;
+                Ok(Box::new(file) as Output)
+            }),
+            dropped: DroppedRecords::default(),
+        }
+    }
+
+    /// Creates a provider which writes each trace to the output returned by `open`
+    ///
+    /// `open` is called on the background thread with the name of the trace when a connection
+    /// is started. A connection isn't traced if it returns an error.
+    pub fn with_output<F>(open: F) -> Self
+    where
+        F: 'static + FnMut(&str) -> io::Result<Output> + Send,
+    {
+        Self {
+            directory: None,
+            open: Box::new(open),
+            dropped: DroppedRecords::default(),
+        }
+    }
+
+    /// Returns a handle to the number of records which were dropped because the background
+    /// thread fell behind
+    pub fn dropped_records(&self) -> DroppedRecords {
+        self.dropped.clone()
+    }
+}
+
+/// The number of records which were dropped by a [`Provider`]
+#[derive(Clone, Debug, Default)]
+pub struct DroppedRecords(Arc<AtomicU64>);
+
+impl DroppedRecords {
+    /// Returns the number of dropped records
+    pub fn count(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Provider")
+            .field("directory", &self.directory)
+            .field("dropped", &self.dropped.count())
+            .finish()
+    }
+}
+
+impl super::Provider for Provider {
+    type Subscriber = Subscriber;
+    type Error = io::Error;
+
+    fn start(self) -> Result<Self::Subscriber, Self::Error> {
+        if let Some(directory) = &self.directory {
+            fs::create_dir_all(directory)?;
+        }
+
+        let (commands, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let closed = Arc::<Mutex<Vec<u64>>>::default();
+        let open = self.open;
+        let pending = closed.clone();
+        thread::Builder::new()
+            .name("s2n-quic-qlog".to_string())
+            .spawn(move || write_traces(open, receiver, pending))?;
+
+        Ok(Subscriber {
+            sender: Sender {
+                commands,
+                closed,
+                dropped: self.dropped,
+            },
+            clock: Arc::new(SystemClock),
+            next_id: 0,
+        })
+    }
+}
+
+/// A request to the background thread which writes the traces
+enum Command {
+    Open { id: u64, name: String },
+    Write { id: u64, record: String },
+    Close { id: u64 },
+}
+
+/// Queues commands to the background thread without blocking the endpoint
+#[derive(Clone)]
+struct Sender {
+    commands: mpsc::SyncSender<Command>,
+    /// The traces which were closed while the channel was full
+    closed: Arc<Mutex<Vec<u64>>>,
+    dropped: DroppedRecords,
+}
+
+impl Sender {
+    /// Queues a command, returning `false` if the background thread has exited
+    ///
+    /// The command is dropped and counted if the channel is full.
+    fn send(&self, command: Command) -> bool {
+        match self.commands.try_send(command) {
+            Ok(()) => true,
+            Err(mpsc::TrySendError::Full(_)) => {
+                self.dropped.increment();
+                true
+            }
+            Err(mpsc::TrySendError::Disconnected(_)) => false,
+        }
+    }
+
+    /// Closes a trace once the records which were queued before it are written
+    fn close(&self, id: u64) {
+        if let Err(mpsc::TrySendError::Full(_)) = self.commands.try_send(Command::Close { id }) {
+            // the output would otherwise stay open until the background thread exits
+            if let Ok(mut closed) = self.closed.lock() {
+                closed.push(id);
+            }
+        }
+    }
+}
+
+/// Writes the traces until all of the subscribers and connections are dropped
+fn write_traces(mut open: Open, commands: mpsc::Receiver<Command>, closed: Arc<Mutex<Vec<u64>>>) {
+    let mut outputs = HashMap::new();
+
+    loop {
+        let command = match commands.try_recv() {
+            Ok(command) => command,
+            Err(mpsc::TryRecvError::Empty) => {
+                // the channel has been drained so the traces which were closed while it was full
+                // have been written
+                let closed = closed
+                    .lock()
+                    .map(|mut closed| core::mem::take(&mut *closed))
+                    .unwrap_or_default();
+                for id in closed {
+                    if let Some(mut output) = outputs.remove(&id) {
+                        let _ = output.flush();
+                    }
+                }
+
+                match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => break,
+                }
+            }
+            Err(mpsc::TryRecvError::Disconnected) => break,
+        };
+
+        match command {
+            Command::Open { id, name } => {
+                if let Ok(output) = open(&name) {
+                    outputs.insert(id, BufWriter::new(output));
+                }
+            }
+            Command::Write { id, record } => {
+                if let Some(output) = outputs.get_mut(&id) {
+                    // stop tracing the connection if the output fails
+                    if output.write_all(record.as_bytes()).is_err() {
+                        outputs.remove(&id);
+                    }
+                }
+            }
+            Command::Close { id } => {
+                if let Some(mut output) = outputs.remove(&id) {
+                    let _ = output.flush();
+                }
+            }
+        }
+    }
+
+    for output in outputs.values_mut() {
+        let _ = output.flush();
+    }
+}
+
+/// Writes connection events to qlog traces
+pub struct Subscriber {
+    sender: Sender,
+    /// Provides the reference time of each trace, which is the endpoint's wall clock
+    clock: Arc<dyn WallClock>,
+    next_id: u64,
+}
+
+impl fmt::Debug for Subscriber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Subscriber").finish()
+    }
+}
+
+/// The trace of a connection
+pub struct ConnectionContext {
+    start: Timestamp,
+    vantage_point: &'static str,
+    /// The trace isn't opened until the connection is started, which is when its first
+    /// connection ID is known
+    id: Option<u64>,
+    sender: Sender,
+}
+
+impl fmt::Debug for ConnectionContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConnectionContext")
+            .field("vantage_point", &self.vantage_point)
+            .field("is_open", &self.id.is_some())
+            .finish()
+    }
+}
+
+impl ConnectionContext {
+    /// Writes an event record with the given `data` object
+    fn event(&mut self, meta: &ConnectionMeta, name: &str, data: fmt::Arguments) {
+        if self.id.is_none() {
+            return;
+        }
+
+        let time = Millis(meta.timestamp.saturating_duration_since(self.start));
+        self.write(format!(
+            "\x1e{{\"time\":{time},\"name\":\"{name}\",\"data\":{{{data}}}}}\n"
+        ));
+    }
+
+    /// Sends a record to the background thread, dropping it if the channel is full
+    fn write(&mut self, record: String) {
+        if let Some(id) = self.id {
+            // stop tracing the connection if the background thread has exited
+            if !self.sender.send(Command::Write { id, record }) {
+                self.id = None;
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.sender.close(id);
+        }
+    }
+}
+
+impl Drop for ConnectionContext {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl super::Subscriber for Subscriber {
+    type ConnectionContext = ConnectionContext;
+
+    fn set_wall_clock(&mut self, clock: &Arc<dyn WallClock>) {
+        self.clock = clock.clone();
+    }
+
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let vantage_point = match meta.endpoint_type {
+            events::EndpointType::Client { .. } => "client",
+            events::EndpointType::Server { .. } => "server",
+        };
+
+        ConnectionContext {
+            start: meta.timestamp,
+            vantage_point,
+            id: None,
+            sender: self.sender.clone(),
+        }
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ConnectionStarted,
+    ) {
+        let group_id = Hex(event.path.local_cid.bytes);
+        let name = format!("{group_id}_{}.sqlog", context.vantage_point);
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        if !self.sender.send(Command::Open { id, name }) {
+            return;
+        }
+        context.id = Some(id);
+
+        let reference_time = self
+            .clock
+            .now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+
+        context.write(format!(
+            "\x1e{{\"qlog_version\":\"0.3\",\"qlog_format\":\"JSON-SEQ\",\"title\":\"s2n-quic\",\
+             \"trace\":{{\"vantage_point\":{{\"name\":\"s2n-quic\",\"type\":\"{}\"}},\
+             \"common_fields\":{{\"time_format\":\"relative\",\"reference_time\":{},\
+             \"group_id\":\"{group_id}\"}}}}}}\n",
+            context.vantage_point,
+            Millis(reference_time),
+        ));
+
+        let path = &event.path;
+        let (src, dst) = (Address(&path.local_addr), Address(&path.remote_addr));
+        context.event(
+            meta,
+            "connectivity:connection_started",
+            format_args!(
+                "\"ip_version\":\"{}\",\"src_ip\":\"{}\",\"dst_ip\":\"{}\",\"protocol\":\"QUIC\",\
+                 \"src_port\":{},\"dst_port\":{},\"src_cid\":\"{}\",\"dst_cid\":\"{}\"",
+                src.version(),
+                src.ip(),
+                dst.ip(),
+                src.port(),
+                dst.port(),
+                Hex(path.local_cid.bytes),
+                Hex(path.remote_cid.bytes),
+            ),
+        );
+    }
+
+    fn on_handshake_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::HandshakeStatusUpdated,
+    ) {
+        let state = match event.status {
+            events::HandshakeStatus::Complete { .. } => "handshake_complete",
+            events::HandshakeStatus::Confirmed { .. } => "handshake_confirmed",
+            _ => return,
+        };
+
+        context.event(
+            meta,
+            "connectivity:connection_state_updated",
+            format_args!("\"new\":\"{state}\""),
+        );
+    }
+
+    fn on_transport_parameters_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::TransportParametersReceived,
+    ) {
+        let params = &event.transport_parameters;
+        context.event(
+            meta,
+            "transport:parameters_set",
+            format_args!(
+                "\"owner\":\"remote\",\"max_idle_timeout\":{},\"max_udp_payload_size\":{},\
+                 \"ack_delay_exponent\":{},\"max_ack_delay\":{},\
+                 \"active_connection_id_limit\":{},\
+                 \"initial_max_stream_data_bidi_local\":{},\
+                 \"initial_max_stream_data_bidi_remote\":{},\
+                 \"initial_max_stream_data_uni\":{},\"initial_max_streams_bidi\":{},\
+                 \"initial_max_streams_uni\":{},\"disable_active_migration\":{}",
+                params.max_idle_timeout.as_millis(),
+                params.max_udp_payload_size,
+                params.ack_delay_exponent,
+                params.max_ack_delay.as_millis(),
+                params.active_connection_id_limit,
+                params.initial_max_stream_data_bidi_local,
+                params.initial_max_stream_data_bidi_remote,
+                params.initial_max_stream_data_uni,
+                params.initial_max_streams_bidi,
+                params.initial_max_streams_uni,
+                !params.migration_support,
+            ),
+        );
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        context.event(
+            meta,
+            "transport:packet_sent",
+            format_args!(
+                "\"header\":{},\"raw\":{{\"length\":{}}}",
+                Header(&event.packet_header),
+                event.packet_len
+            ),
+        );
+    }
+
+    fn on_packet_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketReceived,
+    ) {
+        context.event(
+            meta,
+            "transport:packet_received",
+            format_args!("\"header\":{}", Header(&event.packet_header)),
+        );
+    }
+
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::DatagramSent,
+    ) {
+        context.event(
+            meta,
+            "transport:datagrams_sent",
+            format_args!("\"count\":1,\"raw\":[{{\"length\":{}}}]", event.len),
+        );
+    }
+
+    fn on_datagram_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::DatagramReceived,
+    ) {
+        context.event(
+            meta,
+            "transport:datagrams_received",
+            format_args!("\"count\":1,\"raw\":[{{\"length\":{}}}]", event.len),
+        );
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        context.event(
+            meta,
+            "recovery:metrics_updated",
+            format_args!(
+                "\"min_rtt\":{},\"smoothed_rtt\":{},\"latest_rtt\":{},\"rtt_variance\":{},\
+                 \"pto_count\":{},\"congestion_window\":{},\"bytes_in_flight\":{}",
+                Millis(event.min_rtt),
+                Millis(event.smoothed_rtt),
+                Millis(event.latest_rtt),
+                Millis(event.rtt_variance),
+                event.pto_count,
+                event.congestion_window,
+                event.bytes_in_flight,
+            ),
+        );
+    }
+
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketLost,
+    ) {
+        context.event(
+            meta,
+            "recovery:packet_lost",
+            format_args!(
+                "\"header\":{},\"raw\":{{\"length\":{}}}",
+                Header(&event.packet_header),
+                event.bytes_lost
+            ),
+        );
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ConnectionClosed,
+    ) {
+        let reason = event.error.to_string();
+        context.event(
+            meta,
+            "connectivity:connection_closed",
+            format_args!("\"reason\":\"{}\"", Escaped(&reason)),
+        );
+        context.flush();
+    }
+}
+
+/// Formats a duration as fractional milliseconds
+struct Millis(Duration);
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.3}", self.0.as_secs_f64() * 1000.0)
+    }
+}
+
+/// Formats bytes as lowercase hex
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a string with the characters which aren't allowed in a JSON string escaped
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats a packet header as a qlog `PacketHeader` object
+struct Header<'a>(&'a events::PacketHeader);
+
+impl fmt::Display for Header<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use events::PacketHeader::*;
+
+        let (packet_type, number) = match self.0 {
+            Initial { number, .. } => ("initial", Some(number)),
+            Handshake { number, .. } => ("handshake", Some(number)),
+            ZeroRtt { number, .. } => ("0RTT", Some(number)),
+            OneRtt { number, .. } => ("1RTT", Some(number)),
+            Retry { .. } => ("retry", None),
+            VersionNegotiation { .. } => ("version_negotiation", None),
+            StatelessReset { .. } => ("stateless_reset", None),
+            _ => ("unknown", None),
+        };
+
+        write!(f, "{{\"packet_type\":\"{packet_type}\"")?;
+        if let Some(number) = number {
+            write!(f, ",\"packet_number\":{number}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Formats the parts of a socket address
+struct Address<'a, 'b>(&'a events::SocketAddress<'b>);
+
+impl Address<'_, '_> {
+    fn version(&self) -> &'static str {
+        match self.0 {
+            events::SocketAddress::IpV4 { .. } => "v4",
+            _ => "v6",
+        }
+    }
+
+    fn ip(&self) -> std::net::IpAddr {
+        match self.0 {
+            events::SocketAddress::IpV4 { ip, .. } => std::net::Ipv4Addr::from(**ip).into(),
+            events::SocketAddress::IpV6 { ip, .. } => std::net::Ipv6Addr::from(**ip).into(),
+            _ => std::net::Ipv4Addr::UNSPECIFIED.into(),
+        }
+    }
+
+    fn port(&self) -> u16 {
+        match self.0 {
+            events::SocketAddress::IpV4 { port, .. } => *port,
+            events::SocketAddress::IpV6 { port, .. } => *port,
+            _ => 0,
+        }
+    }
+}
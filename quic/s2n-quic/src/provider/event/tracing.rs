@@ -1,6 +1,28 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+//! Each connection is traced in a `conn` span, which records the internal connection `id` and
+//! the first local connection ID as `cid`. The connection's events are emitted inside of the span
+//! with their fields.
+//!
+//! The span can be queried from a connection to instrument the application code which handles
+//! it, so the application's events are correlated with the transport events:
+//!
+//! ```ignore
+//! use tracing::Instrument;
+//!
+//! let span = connection.query_event_context(|span: &tracing::Span| span.clone())?;
+//!
+//! tokio::spawn(
+//!     async move {
+//!         while let Ok(Some(stream)) = connection.accept_bidirectional_stream().await {
+//!             tracing::info!("accepted a stream");
+//!         }
+//!     }
+//!     .instrument(span),
+//! );
+//! ```
+
 pub use s2n_quic_core::event::tracing::Subscriber;
 
 #[derive(Debug, Default)]
@@ -16,6 +16,15 @@ pub trait Provider: 'static {
     ) -> Result<SocketAddress, Self::Error>;
 }
 
+#[cfg(feature = "unstable-provider-io-dpdk")]
+pub mod dpdk;
+
+#[cfg(any(test, feature = "unstable-provider-io-driven"))]
+pub mod driven;
+
+#[cfg(any(test, feature = "unstable-provider-io-loopback"))]
+pub mod loopback;
+
 #[cfg(any(test, feature = "unstable-provider-io-testing"))]
 pub mod testing;
 
@@ -0,0 +1,26 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides an implementation of the [`io::Provider`](crate::provider::io::Provider)
+//! which doesn't spawn any tasks or threads.
+//!
+//! The application drives the endpoint by repeatedly calling [`Driver::step`] and exchanging
+//! datagrams with the network through the [`Driver`].
+
+use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress};
+use s2n_quic_platform::io::driven;
+use std::io;
+
+pub use self::driven::{Builder, Datagram, Driver, Io as Provider, PathHandle};
+
+impl super::Provider for Provider {
+    type PathHandle = PathHandle;
+    type Error = io::Error;
+
+    fn start<E: Endpoint<PathHandle = Self::PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> Result<SocketAddress, Self::Error> {
+        Provider::start(self, endpoint)
+    }
+}
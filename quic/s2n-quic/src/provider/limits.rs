@@ -3,7 +3,11 @@
 
 //! Provides limits support for a connection
 
-pub use s2n_quic_core::connection::limits::{ConnectionInfo, Limiter, Limits};
+pub use s2n_quic_core::{
+    connection::limits::{ConnectionInfo, Limiter, Limits, ValidationError},
+    inet::EcnMode,
+    stream::limits::StreamLimitPolicy,
+};
 
 pub trait Provider {
     type Limits: 'static + Send + Limiter;
@@ -18,9 +22,10 @@ impl_provider_utils!();
 
 impl<T: 'static + Send + Limiter> Provider for T {
     type Limits = T;
-    type Error = core::convert::Infallible;
+    type Error = ValidationError;
 
     fn start(self) -> Result<Self::Limits, Self::Error> {
+        self.validate()?;
         Ok(self)
     }
 }
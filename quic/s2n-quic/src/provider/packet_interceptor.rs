@@ -13,6 +13,10 @@ pub trait Provider: 'static {
     fn start(self) -> Result<Self::PacketInterceptor, Self::Error>;
 }
 
+/// Writes the datagrams of an endpoint to a pcapng capture
+#[cfg(feature = "unstable-provider-packet-interceptor")]
+pub mod pcap;
+
 pub type Default = Disabled;
 
 impl_provider_utils!();
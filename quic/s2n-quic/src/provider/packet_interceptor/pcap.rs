@@ -0,0 +1,539 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Captures the datagrams of an endpoint to a [pcapng](https://www.ietf.org/archive/id/draft-ietf-opsawg-pcapng-01.html)
+//! file
+//!
+//! Each datagram is written with synthesized IP and UDP headers so the capture can be opened in
+//! Wireshark. When a key log file is configured, the TLS secrets written to it are embedded in
+//! the capture as Decryption Secrets Blocks, which allows Wireshark to decrypt the packets without
+//! any additional files.
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use s2n_quic::{provider::packet_interceptor::pcap::Pcap, Server};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! // the TLS provider writes the secrets to the file named by `SSLKEYLOGFILE` when key logging
+//! // is enabled
+//! let pcap = Pcap::create("capture.pcapng")?.with_key_log_file(std::env::var("SSLKEYLOGFILE")?);
+//!
+//! let server = Server::builder()
+//!     .with_packet_interceptor(pcap)?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use crate::provider::{event::events::SocketAddress, packet_interceptor::PacketInterceptor};
+use core::{fmt, time::Duration};
+use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+use s2n_quic_core::{
+    event::api::Subject,
+    packet::interceptor::Datagram,
+    time::{SystemClock, Timestamp, WallClock},
+};
+use std::{
+    fs,
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    net::{IpAddr, Ipv6Addr},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+const SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+const ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+const DECRYPTION_SECRETS_BLOCK: u32 = 0x0000_000A;
+
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+/// Raw IPv4 or IPv6 packets, without a link layer header
+const LINKTYPE_RAW: u16 = 101;
+/// Secrets in the NSS key log format
+const SECRETS_TYPE_TLS_KEY_LOG: u32 = 0x544C_534B;
+
+const OPTION_END: u16 = 0;
+const OPTION_EPB_FLAGS: u16 = 2;
+const EPB_FLAGS_INBOUND: u32 = 0b01;
+const EPB_FLAGS_OUTBOUND: u32 = 0b10;
+
+const UDP_PROTOCOL: u8 = 17;
+
+/// How often the key log file is checked for new secrets
+const KEY_LOG_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The output a capture is written to
+pub type Output = Box<dyn Write + Send>;
+
+/// A packet interceptor which writes each datagram to a pcapng capture
+///
+/// The interceptor can be cloned to write the datagrams of several endpoints to the same capture.
+#[derive(Clone)]
+pub struct Pcap {
+    state: Arc<Mutex<State>>,
+}
+
+impl fmt::Debug for Pcap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pcap").finish()
+    }
+}
+
+impl Pcap {
+    /// Creates a capture file at `path`, replacing any existing file
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = fs::File::create(path)?;
+        Self::new(Box::new(file))
+    }
+
+    /// Writes the capture to `output`
+    pub fn new(output: Output) -> io::Result<Self> {
+        let mut output = BufWriter::new(output);
+        write_header(&mut output)?;
+
+        let state = State {
+            output,
+            key_log: None,
+            epoch: None,
+            clock: Arc::new(SystemClock),
+        };
+
+        Ok(Self {
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Embeds the secrets written to the key log file at `path`
+    ///
+    /// The file is checked for new secrets before a datagram is written, at most every 10ms, so
+    /// most secrets are in the capture before the packets which use them. Wireshark applies the
+    /// secrets to the whole capture once it's loaded. The file is usually configured with the
+    /// `SSLKEYLOGFILE` environment variable and the `with_key_logging` option of the TLS
+    /// provider.
+    #[must_use]
+    pub fn with_key_log_file<P: Into<PathBuf>>(self, path: P) -> Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.key_log = Some(KeyLog {
+                path: path.into(),
+                file: None,
+                offset: 0,
+                next_check: None,
+            });
+        }
+        self
+    }
+
+    /// Embeds secrets in the NSS key log format, e.g. from a TLS provider other than s2n-tls
+    pub fn add_secrets(&self, key_log: &str) -> io::Result<()> {
+        let mut state = self.lock()?;
+        write_secrets(&mut state.output, key_log.as_bytes())
+    }
+
+    /// Flushes the buffered blocks to the output
+    pub fn flush(&self) -> io::Result<()> {
+        self.lock()?.output.flush()
+    }
+
+    fn lock(&self) -> io::Result<std::sync::MutexGuard<State>> {
+        self.state
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "the capture lock was poisoned"))
+    }
+
+    fn capture(&self, datagram: &Datagram, payload: &[u8], direction: Direction) {
+        if let Ok(mut state) = self.state.lock() {
+            // errors are ignored so capturing never affects the connection
+            let _ = state.capture(datagram, payload, direction);
+        }
+    }
+}
+
+impl PacketInterceptor for Pcap {
+    fn set_wall_clock(&mut self, clock: &Arc<dyn WallClock>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.clock = clock.clone();
+        }
+    }
+
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &Subject,
+        datagram: &Datagram,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        let payload = payload.into_less_safe_slice();
+        self.capture(datagram, payload, Direction::Inbound);
+        DecoderBufferMut::new(payload)
+    }
+
+    fn intercept_tx_datagram(
+        &mut self,
+        _subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        self.capture(datagram, payload.as_mut_slice(), Direction::Outbound);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Direction {
+    Inbound,
+    Outbound,
+}
+
+struct State {
+    output: BufWriter<Output>,
+    key_log: Option<KeyLog>,
+    /// The wall clock time of the first datagram, which is used to convert the endpoint's
+    /// timestamps into capture timestamps
+    epoch: Option<(Timestamp, SystemTime)>,
+    /// Provides the capture time of the first datagram, which is the endpoint's wall clock
+    clock: Arc<dyn WallClock>,
+}
+
+struct KeyLog {
+    path: PathBuf,
+    /// The key log file, which is kept open once it's been created
+    file: Option<fs::File>,
+    /// The number of bytes of the file which have been embedded
+    offset: u64,
+    /// The time at which the file should be checked for new secrets
+    next_check: Option<Timestamp>,
+}
+
+impl State {
+    fn capture(
+        &mut self,
+        datagram: &Datagram,
+        payload: &[u8],
+        direction: Direction,
+    ) -> io::Result<()> {
+        self.embed_key_log(datagram.timestamp)?;
+
+        let clock = &self.clock;
+        let (epoch, start) = *self
+            .epoch
+            .get_or_insert_with(|| (datagram.timestamp, clock.now()));
+        let time = start + datagram.timestamp.saturating_duration_since(epoch);
+        let time = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let (src, dst, flags) = match direction {
+            Direction::Inbound => (
+                &datagram.remote_address,
+                &datagram.local_address,
+                EPB_FLAGS_INBOUND,
+            ),
+            Direction::Outbound => (
+                &datagram.local_address,
+                &datagram.remote_address,
+                EPB_FLAGS_OUTBOUND,
+            ),
+        };
+
+        let packet = ip_packet(src, dst, payload);
+        write_packet(&mut self.output, time, &packet, flags)
+    }
+
+    /// Writes any secrets which were appended to the key log file since the last check
+    fn embed_key_log(&mut self, now: Timestamp) -> io::Result<()> {
+        let key_log = if let Some(key_log) = self.key_log.as_mut() {
+            key_log
+        } else {
+            return Ok(());
+        };
+
+        if key_log
+            .next_check
+            .map_or(false, |next_check| now < next_check)
+        {
+            return Ok(());
+        }
+        key_log.next_check = Some(now + KEY_LOG_CHECK_INTERVAL);
+
+        let file = if let Some(file) = key_log.file.as_mut() {
+            file
+        } else {
+            match fs::File::open(&key_log.path) {
+                Ok(file) => key_log.file.insert(file),
+                // the TLS provider creates the file when the first secret is logged
+                Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+                Err(err) => return Err(err),
+            }
+        };
+
+        // only read the file if it has grown
+        if file.metadata()?.len() <= key_log.offset {
+            return Ok(());
+        }
+
+        file.seek(SeekFrom::Start(key_log.offset))?;
+        let mut secrets = vec![];
+        file.read_to_end(&mut secrets)?;
+
+        // only embed complete lines in case a line is still being written
+        let len = match secrets.iter().rposition(|b| *b == b'\n') {
+            Some(index) => index + 1,
+            None => return Ok(()),
+        };
+
+        key_log.offset += len as u64;
+        write_secrets(&mut self.output, &secrets[..len])
+    }
+}
+
+fn write_header<W: Write>(output: &mut W) -> io::Result<()> {
+    // Section Header Block
+    let len = 28u32;
+    output.write_all(&SECTION_HEADER_BLOCK.to_le_bytes())?;
+    output.write_all(&len.to_le_bytes())?;
+    output.write_all(&BYTE_ORDER_MAGIC.to_le_bytes())?;
+    // version 1.0
+    output.write_all(&1u16.to_le_bytes())?;
+    output.write_all(&0u16.to_le_bytes())?;
+    // the section length isn't known ahead of time
+    output.write_all(&(-1i64).to_le_bytes())?;
+    output.write_all(&len.to_le_bytes())?;
+
+    // Interface Description Block with the default microsecond timestamp resolution
+    let len = 20u32;
+    output.write_all(&INTERFACE_DESCRIPTION_BLOCK.to_le_bytes())?;
+    output.write_all(&len.to_le_bytes())?;
+    output.write_all(&LINKTYPE_RAW.to_le_bytes())?;
+    // reserved
+    output.write_all(&0u16.to_le_bytes())?;
+    // no snap length limit
+    output.write_all(&0u32.to_le_bytes())?;
+    output.write_all(&len.to_le_bytes())?;
+
+    Ok(())
+}
+
+fn write_secrets<W: Write>(output: &mut W, secrets: &[u8]) -> io::Result<()> {
+    let padding = padding(secrets.len());
+    let len = (20 + secrets.len() + padding) as u32;
+
+    output.write_all(&DECRYPTION_SECRETS_BLOCK.to_le_bytes())?;
+    output.write_all(&len.to_le_bytes())?;
+    output.write_all(&SECRETS_TYPE_TLS_KEY_LOG.to_le_bytes())?;
+    output.write_all(&(secrets.len() as u32).to_le_bytes())?;
+    output.write_all(secrets)?;
+    output.write_all(&[0; 3][..padding])?;
+    output.write_all(&len.to_le_bytes())?;
+
+    Ok(())
+}
+
+fn write_packet<W: Write>(
+    output: &mut W,
+    time: Duration,
+    packet: &[u8],
+    flags: u32,
+) -> io::Result<()> {
+    let padding = padding(packet.len());
+    // the block includes the `epb_flags` and end of options
+    let len = (32 + packet.len() + padding + 8 + 4) as u32;
+    let time = time.as_micros() as u64;
+
+    output.write_all(&ENHANCED_PACKET_BLOCK.to_le_bytes())?;
+    output.write_all(&len.to_le_bytes())?;
+    // interface id
+    output.write_all(&0u32.to_le_bytes())?;
+    output.write_all(&((time >> 32) as u32).to_le_bytes())?;
+    output.write_all(&(time as u32).to_le_bytes())?;
+    // captured and original lengths
+    output.write_all(&(packet.len() as u32).to_le_bytes())?;
+    output.write_all(&(packet.len() as u32).to_le_bytes())?;
+    output.write_all(packet)?;
+    output.write_all(&[0; 3][..padding])?;
+
+    output.write_all(&OPTION_EPB_FLAGS.to_le_bytes())?;
+    output.write_all(&4u16.to_le_bytes())?;
+    output.write_all(&flags.to_le_bytes())?;
+    output.write_all(&OPTION_END.to_le_bytes())?;
+    output.write_all(&0u16.to_le_bytes())?;
+
+    output.write_all(&len.to_le_bytes())?;
+
+    Ok(())
+}
+
+/// Returns the number of bytes needed to pad `len` to a 32-bit boundary
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+/// Wraps the payload in IP and UDP headers
+fn ip_packet(src: &SocketAddress, dst: &SocketAddress, payload: &[u8]) -> Vec<u8> {
+    let (src_ip, src_port) = ip_and_port(src);
+    let (dst_ip, dst_port) = ip_and_port(dst);
+
+    let udp_len = (8 + payload.len()) as u16;
+    let mut packet = Vec::with_capacity(40 + udp_len as usize);
+
+    // use IPv6 for both addresses if the families don't match, e.g. on dual-stack sockets
+    let pseudo_header = match (src_ip, dst_ip) {
+        (IpAddr::V4(src_ip), IpAddr::V4(dst_ip)) => {
+            let mut header = [0u8; 20];
+            header[0] = 0x45;
+            header[2..4].copy_from_slice(&(20 + udp_len).to_be_bytes());
+            header[8] = 64;
+            header[9] = UDP_PROTOCOL;
+            header[12..16].copy_from_slice(&src_ip.octets());
+            header[16..20].copy_from_slice(&dst_ip.octets());
+            let checksum = checksum(&[&header]);
+            header[10..12].copy_from_slice(&checksum.to_be_bytes());
+            packet.extend_from_slice(&header);
+
+            let mut pseudo_header = vec![];
+            pseudo_header.extend_from_slice(&src_ip.octets());
+            pseudo_header.extend_from_slice(&dst_ip.octets());
+            pseudo_header.extend_from_slice(&[0, UDP_PROTOCOL]);
+            pseudo_header.extend_from_slice(&udp_len.to_be_bytes());
+            pseudo_header
+        }
+        (src_ip, dst_ip) => {
+            let src_ip = to_ipv6(src_ip);
+            let dst_ip = to_ipv6(dst_ip);
+
+            let mut header = [0u8; 40];
+            header[0] = 0x60;
+            header[4..6].copy_from_slice(&udp_len.to_be_bytes());
+            header[6] = UDP_PROTOCOL;
+            header[7] = 64;
+            header[8..24].copy_from_slice(&src_ip.octets());
+            header[24..40].copy_from_slice(&dst_ip.octets());
+            packet.extend_from_slice(&header);
+
+            let mut pseudo_header = vec![];
+            pseudo_header.extend_from_slice(&src_ip.octets());
+            pseudo_header.extend_from_slice(&dst_ip.octets());
+            pseudo_header.extend_from_slice(&(udp_len as u32).to_be_bytes());
+            pseudo_header.extend_from_slice(&[0, 0, 0, UDP_PROTOCOL]);
+            pseudo_header
+        }
+    };
+
+    let mut header = [0u8; 8];
+    header[0..2].copy_from_slice(&src_port.to_be_bytes());
+    header[2..4].copy_from_slice(&dst_port.to_be_bytes());
+    header[4..6].copy_from_slice(&udp_len.to_be_bytes());
+    let checksum = match checksum(&[&pseudo_header, &header, payload]) {
+        // a checksum of 0 means there isn't a checksum so it's sent as all ones
+        0 => 0xffff,
+        checksum => checksum,
+    };
+    header[6..8].copy_from_slice(&checksum.to_be_bytes());
+
+    packet.extend_from_slice(&header);
+    packet.extend_from_slice(payload);
+    packet
+}
+
+fn ip_and_port(address: &SocketAddress) -> (IpAddr, u16) {
+    match address {
+        SocketAddress::IpV4 { ip, port, .. } => ((**ip).into(), *port),
+        SocketAddress::IpV6 { ip, port, .. } => ((**ip).into(), *port),
+        _ => (Ipv6Addr::UNSPECIFIED.into(), 0),
+    }
+}
+
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+/// Computes the internet checksum over the concatenated `parts`
+fn checksum(parts: &[&[u8]]) -> u16 {
+    let mut sum = 0u32;
+    let mut odd = None;
+
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        match odd.take() {
+            Some(high) => sum += u16::from_be_bytes([high, *byte]) as u32,
+            None => odd = Some(*byte),
+        }
+    }
+
+    if let Some(high) = odd {
+        sum += u16::from_be_bytes([high, 0]) as u32;
+    }
+
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_test() {
+        // example from RFC 1071
+        let bytes = [0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7];
+        assert_eq!(checksum(&[&bytes]), !0xddf2);
+        // the checksum doesn't depend on how the bytes are split
+        assert_eq!(checksum(&[&bytes[..3], &bytes[3..]]), !0xddf2);
+    }
+
+    #[test]
+    fn key_log_test() {
+        let path = std::env::temp_dir().join(format!("s2n-quic-pcap-{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let pcap = Pcap::new(Box::new(io::sink()))
+            .unwrap()
+            .with_key_log_file(&path);
+        let mut state = pcap.lock().unwrap();
+        let offset = |state: &State| state.key_log.as_ref().unwrap().offset;
+
+        let now = s2n_quic_core::time::testing::now();
+
+        // the file doesn't exist yet
+        state.embed_key_log(now).unwrap();
+        assert_eq!(offset(&state), 0);
+
+        fs::write(&path, "CLIENT_RANDOM 00 00\nSERVER_").unwrap();
+
+        // the file isn't checked again until the interval has elapsed
+        state.embed_key_log(now + Duration::from_millis(1)).unwrap();
+        assert_eq!(offset(&state), 0);
+
+        // only the complete line is embedded
+        let now = now + KEY_LOG_CHECK_INTERVAL;
+        state.embed_key_log(now).unwrap();
+        assert_eq!(offset(&state), 20);
+
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"HANDSHAKE 00 00\n")
+            .unwrap();
+
+        let now = now + KEY_LOG_CHECK_INTERVAL;
+        state.embed_key_log(now).unwrap();
+        assert_eq!(offset(&state), 43);
+
+        drop(state);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn padding_test() {
+        assert_eq!(padding(0), 0);
+        assert_eq!(padding(1), 3);
+        assert_eq!(padding(4), 0);
+        assert_eq!(padding(7), 1);
+    }
+}
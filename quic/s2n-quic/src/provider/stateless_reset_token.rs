@@ -3,7 +3,7 @@
 
 //! Provides stateless reset token support for an endpoint
 
-pub use s2n_quic_core::stateless_reset::token::Generator;
+pub use s2n_quic_core::stateless_reset::token::{Generator, PerBoot};
 
 pub trait Provider: 'static {
     type Generator: 'static + Generator;
@@ -16,6 +16,15 @@ pub use random::Provider as Default;
 
 impl_provider_utils!();
 
+impl<G: 'static + Generator> Provider for PerBoot<G> {
+    type Generator = Self;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Generator, Self::Error> {
+        Ok(self)
+    }
+}
+
 mod random {
     use core::convert::Infallible;
     use rand::prelude::*;
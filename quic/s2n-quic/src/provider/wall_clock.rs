@@ -0,0 +1,34 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides calendar time for an endpoint
+//!
+//! Timers are driven by the clock of the IO provider. The wall clock is only read by providers
+//! which persist or report absolute times: the TLS provider expires session tickets with it and
+//! the event and packet interceptor providers timestamp qlog traces and pcap captures. Setting a
+//! single clock for the endpoint allows simulations to control all of those times.
+
+pub use s2n_quic_core::time::{SystemClock, WallClock};
+
+/// Provides calendar time for an endpoint
+pub trait Provider: 'static {
+    type WallClock: 'static + WallClock;
+    type Error: core::fmt::Display;
+
+    /// Starts the wall clock provider
+    fn start(self) -> Result<Self::WallClock, Self::Error>;
+}
+
+pub type Default = SystemClock;
+
+/// Implement Provider for all implementations of WallClock
+impl<T: WallClock> Provider for T {
+    type WallClock = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::WallClock, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl_provider_utils!();
@@ -4,20 +4,29 @@
 use crate::{connection::Connection, provider::*};
 use core::{
     fmt,
+    future::Future,
     task::{Context, Poll},
 };
 use s2n_quic_transport::endpoint::handle::Acceptor;
 
 mod builder;
+mod incoming;
 mod providers;
+mod workers;
 
 pub use builder::*;
+pub use incoming::*;
 pub use providers::*;
 pub use s2n_quic_core::application::ServerName as Name;
+pub use workers::*;
 
 /// A QUIC server endpoint, capable of accepting connections
 pub struct Server {
-    acceptor: Acceptor,
+    /// The acceptor of each endpoint, which is more than one for a server started with
+    /// [`Server::start_workers`]
+    acceptors: Vec<Acceptor>,
+    /// The index of the acceptor which is polled first, so each endpoint gets a fair share
+    next_acceptor: usize,
     local_addr: s2n_quic_core::inet::SocketAddress,
 }
 
@@ -82,6 +91,46 @@ impl Server {
         futures::future::poll_fn(|cx| self.poll_accept(cx)).await
     }
 
+    /// Accepts a new incoming [`Connection`] from this [`Server`], giving up once `timeout`
+    /// completes.
+    ///
+    /// Returns `None` if the `timeout` completes before a connection is established, or if the
+    /// server has closed. Connections which complete the handshake after the timeout stay queued
+    /// and are returned by the next call to accept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path, time::Duration};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let timeout = tokio::time::sleep(Duration::from_secs(1));
+    /// if let Some(connection) = server.accept_timeout(timeout).await {
+    ///     println!("new connection: {:?}", connection.remote_addr());
+    /// }
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn accept_timeout<T: Future>(&mut self, timeout: T) -> Option<Connection> {
+        futures::pin_mut!(timeout);
+
+        futures::future::poll_fn(|cx| {
+            if let Poll::Ready(connection) = self.poll_accept(cx) {
+                return Poll::Ready(connection);
+            }
+
+            timeout.as_mut().poll(cx).map(|_| None)
+        })
+        .await
+    }
+
     /// Attempts to accept a new incoming [`Connection`] from this [`Server`].
     ///
     /// # Return value
@@ -94,11 +143,63 @@ impl Server {
     /// - `Poll::Ready(None)` the attempt failed because the server has closed. Once
     /// None is returned, this function should not be called again.
     pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
-        match self.acceptor.poll_accept(cx) {
-            Poll::Ready(Some(connection)) => Poll::Ready(Some(Connection::new(connection))),
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Pending => Poll::Pending,
+        let mut remaining = self.acceptors.len();
+
+        while remaining > 0 {
+            remaining -= 1;
+
+            let index = self.next_acceptor % self.acceptors.len();
+
+            match self.acceptors[index].poll_accept(cx) {
+                Poll::Ready(Some(connection)) => {
+                    self.next_acceptor = index + 1;
+                    return Poll::Ready(Some(Connection::new(connection)));
+                }
+                Poll::Ready(None) => {
+                    // the endpoint has closed so stop polling it
+                    self.acceptors.remove(index);
+                    self.next_acceptor = index;
+                }
+                Poll::Pending => self.next_acceptor = index + 1,
+            }
         }
+
+        if self.acceptors.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Converts the [`Server`] into an [`IncomingStreams`] aggregator, which accepts new
+    /// connections and yields the streams opened by all of them
+    ///
+    /// This is useful for servers that handle each stream independently of the connection
+    /// it arrived on. Connections are polled in a round-robin fashion to ensure fairness.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let mut incoming = server.incoming_streams();
+    ///
+    /// while let Some((connection, stream)) = incoming.accept().await {
+    ///     println!("stream {} opened by {:?}", stream.id(), connection.remote_addr());
+    /// }
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn incoming_streams(self) -> IncomingStreams {
+        IncomingStreams::new(self)
     }
 
     /// Returns the local address that this listener is bound to.
@@ -303,6 +303,14 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-connection-close-formatter"))]
+    impl_provider_method!(
+        /// Sets the connection close formatter provider for the [`Server`]
+        with_connection_close_formatter,
+        connection_close_formatter,
+        ServerProviders
+    );
+
     #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Server`]
@@ -311,6 +319,17 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-wall-clock"))]
+    impl_provider_method!(
+        /// Sets the wall clock provider for the [`Server`]
+        ///
+        /// The clock is read by the TLS, event and packet interceptor providers for calendar
+        /// times, such as session ticket expirations and the timestamps of traces and captures.
+        with_wall_clock,
+        wall_clock,
+        ServerProviders
+    );
+
     #[cfg(feature = "unstable-provider-datagram")]
     impl_provider_method!(
         /// Sets the datagram provider for the [`Server`]
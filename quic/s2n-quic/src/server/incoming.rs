@@ -0,0 +1,123 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection::{Handle, StreamAcceptor},
+    stream::PeerStream,
+    Server,
+};
+use core::{
+    fmt,
+    task::{Context, Poll},
+};
+use std::collections::VecDeque;
+
+/// Multiplexes the incoming streams of every connection accepted by a [`Server`]
+///
+/// Connections are accepted from the [`Server`] as they are established and polled in a
+/// round-robin fashion so a connection with many pending streams cannot starve the others.
+///
+/// See [`Server::incoming_streams`] for more details.
+pub struct IncomingStreams {
+    server: Server,
+    server_closed: bool,
+    connections: VecDeque<(Handle, StreamAcceptor)>,
+}
+
+impl fmt::Debug for IncomingStreams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IncomingStreams")
+            .field("server", &self.server)
+            .field("connections", &self.connections.len())
+            .finish()
+    }
+}
+
+impl IncomingStreams {
+    #[inline]
+    pub(crate) fn new(server: Server) -> Self {
+        Self {
+            server,
+            server_closed: false,
+            connections: VecDeque::new(),
+        }
+    }
+
+    /// Accepts the next incoming [`PeerStream`] from any of the [`Server`]'s connections
+    ///
+    /// The stream is returned along with a [`Handle`] to the connection that opened it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn test() {
+    /// #   let mut incoming: s2n_quic::server::IncomingStreams = todo!();
+    /// #
+    /// while let Some((connection, stream)) = incoming.accept().await {
+    ///     println!("stream {} opened by {:?}", stream.id(), connection.remote_addr());
+    /// }
+    /// # }
+    /// ```
+    pub async fn accept(&mut self) -> Option<(Handle, PeerStream)> {
+        futures::future::poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    /// Attempts to accept the next incoming [`PeerStream`] from any of the [`Server`]'s
+    /// connections
+    ///
+    /// # Return value
+    ///
+    /// This function returns:
+    ///
+    /// - `Poll::Pending` if none of the connections have opened a new stream.
+    /// - `Poll::Ready(Some((handle, stream)))` once a connection has opened a new stream.
+    /// This function can be called again to try and accept new streams.
+    /// - `Poll::Ready(None)` once the server has closed and all of its connections have been
+    /// closed. Once None is returned, this function should not be called again.
+    pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<(Handle, PeerStream)>> {
+        while !self.server_closed {
+            match self.server.poll_accept(cx) {
+                Poll::Ready(Some(connection)) => self.connections.push_back(connection.split()),
+                Poll::Ready(None) => self.server_closed = true,
+                Poll::Pending => break,
+            }
+        }
+
+        // Poll each connection at most once, rotating the connection that yielded a stream to
+        // the back of the queue so every connection gets a turn
+        for _ in 0..self.connections.len() {
+            let (handle, mut acceptor) = match self.connections.pop_front() {
+                Some(connection) => connection,
+                None => break,
+            };
+
+            match acceptor.poll_accept(cx) {
+                Poll::Ready(Ok(Some(stream))) => {
+                    let item = (handle.clone(), stream);
+                    self.connections.push_back((handle, acceptor));
+                    return Poll::Ready(Some(item));
+                }
+                // the connection was closed so stop tracking it
+                Poll::Ready(Ok(None)) | Poll::Ready(Err(_)) => continue,
+                Poll::Pending => self.connections.push_back((handle, acceptor)),
+            }
+        }
+
+        if self.server_closed && self.connections.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl futures::stream::Stream for IncomingStreams {
+    type Item = (Handle, PeerStream);
+
+    fn poll_next(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        self.poll_accept(cx)
+    }
+}
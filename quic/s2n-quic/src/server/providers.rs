@@ -5,6 +5,7 @@ use super::*;
 use core::marker::PhantomData;
 use s2n_quic_core::{connection::id::Generator, crypto, path};
 use s2n_quic_transport::{connection, endpoint, stream};
+use std::sync::Arc;
 
 impl_providers_state! {
     #[derive(Debug, Default)]
@@ -24,6 +25,7 @@ impl_providers_state! {
         tls: Tls,
         address_token: AddressToken,
         datagram: Datagram,
+        wall_clock: WallClock,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -46,6 +48,7 @@ impl<
         Tls: tls::Provider,
         AddressToken: address_token::Provider,
         Datagram: datagram::Provider,
+        WallClock: wall_clock::Provider,
     >
     Providers<
         CongestionController,
@@ -63,6 +66,7 @@ impl<
         Tls,
         AddressToken,
         Datagram,
+        WallClock,
     >
 {
     pub fn start(self) -> Result<Server, StartError> {
@@ -82,6 +86,7 @@ impl<
             sync,
             tls,
             datagram,
+            wall_clock,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -89,17 +94,24 @@ impl<
             .start()
             .map_err(StartError::new)?;
         let connection_id = connection_id.start().map_err(StartError::new)?;
-        let packet_interceptor = packet_interceptor.start().map_err(StartError::new)?;
+        let mut packet_interceptor = packet_interceptor.start().map_err(StartError::new)?;
         let stateless_reset_token = stateless_reset_token.start().map_err(StartError::new)?;
         let random = random.start().map_err(StartError::new)?;
         let endpoint_limits = endpoint_limits.start().map_err(StartError::new)?;
         let limits = limits.start().map_err(StartError::new)?;
-        let event = event.start().map_err(StartError::new)?;
+        let mut event = event.start().map_err(StartError::new)?;
         let address_token = address_token.start().map_err(StartError::new)?;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = path_migration.start().map_err(StartError::new)?;
-        let tls = tls.start_server().map_err(StartError::new)?;
+        let mut tls = tls.start_server().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
+        let wall_clock = wall_clock.start().map_err(StartError::new)?;
+
+        // share the endpoint's wall clock with the providers which read calendar time
+        let wall_clock: Arc<dyn s2n_quic_core::time::WallClock> = Arc::new(wall_clock);
+        crypto::tls::Endpoint::set_wall_clock(&mut tls, &wall_clock);
+        s2n_quic_core::event::Subscriber::set_wall_clock(&mut event, &wall_clock);
+        packet_interceptor::PacketInterceptor::set_wall_clock(&mut packet_interceptor, &wall_clock);
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -137,7 +149,8 @@ impl<
         let local_addr = io.start(endpoint).map_err(StartError::new)?;
 
         Ok(Server {
-            acceptor,
+            acceptors: vec![acceptor],
+            next_acceptor: 0,
             local_addr,
         })
     }
@@ -0,0 +1,145 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    provider::{connection_id, io, StartError},
+    server::{Builder, Server, ServerProviders},
+};
+
+/// One of the endpoints of a [`Server`] started with [`Server::start_workers`]
+///
+/// Each worker owns one of the sockets of the SO_REUSEPORT group and the connection ID format
+/// which steers the peer's packets back to that socket.
+#[derive(Debug)]
+pub struct Worker {
+    index: u8,
+    count: u8,
+    socket: std::net::UdpSocket,
+}
+
+impl Worker {
+    /// Returns the index of the worker in the group
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the number of workers in the group
+    pub fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// Returns the IO and connection ID builders of the worker, for further configuration
+    ///
+    /// The IO builder receives on the worker's socket and the connection ID builder encodes the
+    /// worker's index. Both need to be passed to the worker's [`Builder`] for the packets of its
+    /// connections to be steered back to it.
+    pub fn into_parts(
+        self,
+    ) -> Result<(io::tokio::Builder, connection_id::default::Builder), StartError> {
+        let io = io::tokio::Provider::builder()
+            .with_rx_socket(self.socket)
+            .map_err(StartError::new)?;
+        let connection_id = connection_id::default::Format::builder()
+            .with_worker(self.index, self.count)
+            .map_err(StartError::new)?;
+        Ok((io, connection_id))
+    }
+
+    /// Returns a [`Builder`] with the IO and connection ID providers of the worker
+    ///
+    /// The rest of the providers can be configured on the returned builder before starting it.
+    pub fn builder(self) -> Result<Builder<impl ServerProviders>, StartError> {
+        let (io, connection_id) = self.into_parts()?;
+        let io = io.build().map_err(StartError::new)?;
+        let connection_id = connection_id.build().map_err(StartError::new)?;
+
+        Server::builder()
+            .with_io(io)
+            .map_err(StartError::new)?
+            .with_connection_id(connection_id)
+            .map_err(StartError::new)
+    }
+}
+
+impl Server {
+    /// Starts a [`Server`] which runs an endpoint for each of `workers` sockets sharing `addr`
+    ///
+    /// The sockets are bound with SO_REUSEPORT and the kernel steers each datagram to a socket by
+    /// the first byte of its destination connection ID. `start` is called with each [`Worker`]
+    /// and needs to start its endpoint with the worker's [`Worker::builder`], or with the
+    /// providers returned by [`Worker::into_parts`]. The endpoints run as separate tasks, so a
+    /// multi-threaded runtime spreads them across its threads. The returned [`Server`] accepts
+    /// the connections of all of the endpoints.
+    ///
+    /// Each connection ID encodes the index of its worker as the remainder of its first byte
+    /// divided by the number of workers. Restarting with a different number of workers changes
+    /// that remainder for the IDs which were already issued, so the packets of existing
+    /// connections are steered to a worker which doesn't know about them, and are dropped or
+    /// answered with a stateless reset. The index is also the position of the socket in the
+    /// SO_REUSEPORT group, which the kernel reorders when a socket in the group is closed, so
+    /// the sockets shouldn't be closed independently of each other.
+    ///
+    /// This is only supported on Linux.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::start_workers("0.0.0.0:443".parse()?, 4, |worker| {
+    ///     let server = worker
+    ///         .builder()?
+    ///         .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///         .start()?;
+    ///     Ok::<_, Box<dyn Error>>(server)
+    /// })?;
+    ///
+    /// while let Some(connection) = server.accept().await {
+    ///     println!("new connection: {:?}", connection.remote_addr());
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn start_workers<F, E>(
+        addr: std::net::SocketAddr,
+        workers: u8,
+        mut start: F,
+    ) -> Result<Self, StartError>
+    where
+        F: FnMut(Worker) -> Result<Server, E>,
+        E: 'static + core::fmt::Display,
+    {
+        let sockets = io::tokio::bind_reuse_port_group(addr, workers).map_err(StartError::new)?;
+        let local_addr = sockets[0].local_addr().map_err(StartError::new)?;
+
+        let mut server: Option<Server> = None;
+
+        for (index, socket) in sockets.into_iter().enumerate() {
+            let worker = Worker {
+                index: index as u8,
+                count: workers,
+                socket,
+            };
+
+            let endpoint = start(worker).map_err(StartError::new)?;
+
+            // the packets of the worker's connections are only steered to the worker's socket
+            if endpoint.local_addr().map_err(StartError::new)? != local_addr {
+                return Err(StartError::new(
+                    "the worker was not started with the worker's IO provider",
+                ));
+            }
+
+            match server.as_mut() {
+                Some(server) => server.acceptors.extend(endpoint.acceptors),
+                None => server = Some(endpoint),
+            }
+        }
+
+        Ok(server.expect("at least one worker is started"))
+    }
+}
@@ -40,6 +40,43 @@ impl BidirectionalStream {
 
     impl_send_stream_api!(|stream, call| call!(stream.0));
 
+    /// Resets the stream with the provided [error code](crate::application::Error) if it
+    /// doesn't make any progress for the given `timeout`
+    ///
+    /// Both halves of the stream will be closed, notifying the peer with `RESET_STREAM` and
+    /// `STOP_SENDING` frames.
+    ///
+    /// Sending or receiving data, as well as any stream frames from the peer, count as
+    /// progress. Since activity is checked periodically, the stream may be closed up to twice
+    /// the `timeout` after the last activity. The connection itself is not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use core::time::Duration;
+    /// # async fn test() -> s2n_quic::stream::Result<()> {
+    /// #   let connection: s2n_quic::connection::Connection = todo!();
+    /// #
+    /// let mut stream = connection.open_bidirectional_stream().await?;
+    /// stream.set_inactivity_timeout(Duration::from_secs(10), 123u8.into())?;
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_inactivity_timeout(
+        &mut self,
+        timeout: core::time::Duration,
+        error_code: crate::application::Error,
+    ) -> crate::stream::Result<()> {
+        self.0
+            .request()
+            .with_tx_inactivity_timeout(timeout, error_code)
+            .with_rx_inactivity_timeout(timeout, error_code)
+            .poll(None)?;
+        Ok(())
+    }
+
     /// Splits the stream into [`ReceiveStream`](crate::stream::ReceiveStream) and
     /// [`SendStream`](crate::stream::SendStream) halves.
     ///
@@ -153,6 +153,113 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Receives data from the stream into an uninitialized buffer.
+        ///
+        /// This avoids having to zero-initialize large buffers before reading into them. Only
+        /// the bytes which were received are initialized; the rest of the buffer is left untouched.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok((len, is_fin))` if the stream received data into the buffer, where `len` is the
+        ///   number of bytes written to the front of the buffer and `is_fin` indicates that all of
+        ///   the data on the stream was received. If `is_fin == false` and `buf` is not empty, `len`
+        ///   will be at least `1`. If `is_fin == true`, future calls to
+        ///   [`receive_uninit`](Self::receive_uninit) will always return `Ok((0, true))`.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// use core::mem::MaybeUninit;
+        ///
+        /// let mut buf = vec![MaybeUninit::<u8>::uninit(); 1 << 16];
+        ///
+        /// loop {
+        ///     let (len, is_fin) = stream.receive_uninit(&mut buf).await?;
+        ///
+        ///     // Safety: `receive_uninit` initialized the first `len` bytes
+        ///     let data = unsafe { core::slice::from_raw_parts(buf.as_ptr() as *const u8, len) };
+        ///     println!("received: {:?}", data);
+        ///
+        ///     if is_fin {
+        ///         break;
+        ///     }
+        /// }
+        ///
+        /// println!("finished");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn receive_uninit(
+            &mut self,
+            buf: &mut [core::mem::MaybeUninit<u8>],
+        ) -> $crate::stream::Result<(usize, bool)> {
+            ::futures::future::poll_fn(|cx| self.poll_receive_uninit(buf, cx)).await
+        }
+
+        /// Polls for receiving data from the stream into an uninitialized buffer.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Poll::Pending` if the stream is waiting to receive data from the peer. In this case,
+        ///   the caller should retry receiving after the [`Waker`](core::task::Waker) on the provided
+        ///   [`Context`](core::task::Context) is notified.
+        /// - `Poll::Ready(Ok((len, is_fin)))` if the stream received data into the buffer, where `len`
+        ///   is the number of bytes written to the front of the buffer and `is_fin` indicates that all
+        ///   of the data on the stream was received. If `is_fin == true`, future calls to
+        ///   [`poll_receive_uninit`](Self::poll_receive_uninit) will always return
+        ///   `Poll::Ready(Ok((0, true)))`.
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn poll_receive_uninit(
+            &mut self,
+            buf: &mut [core::mem::MaybeUninit<u8>],
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<(usize, bool)>> {
+            use bytes::Bytes;
+
+            if buf.is_empty() {
+                return Ok((0, false)).into();
+            }
+
+            // create some chunks on the stack to receive into
+            // TODO investigate a better default number
+            let mut chunks = [
+                Bytes::new(),
+                Bytes::new(),
+                Bytes::new(),
+                Bytes::new(),
+                Bytes::new(),
+            ];
+
+            let response = futures::ready!(self
+                .rx_request()?
+                .receive(&mut chunks)
+                // don't receive more than we're capable of storing
+                .with_high_watermark(buf.len())
+                .poll(Some(cx))?
+                .into_poll());
+
+            let chunks = &chunks[..response.chunks.consumed];
+            let copied_len = s2n_quic_core::slice::vectored_copy_uninit(chunks, buf);
+
+            debug_assert_eq!(
+                copied_len, response.bytes.consumed,
+                "the consumed bytes should always have enough capacity in buf"
+            );
+
+            Ok((copied_len, response.is_finished())).into()
+        }
+
         /// Notifies the peer to stop sending data on the stream.
         ///
         /// This requests the peer to finish the stream as soon as possible
@@ -410,6 +517,37 @@ impl ReceiveStream {
     impl_connection_api!(|stream| crate::connection::Handle(stream.0.connection().clone()));
 
     impl_receive_stream_api!(|stream, dispatch| dispatch!(stream.0));
+
+    /// Resets the stream with the provided [error code](crate::application::Error) if it
+    /// doesn't make any progress for the given `timeout`
+    ///
+    /// The peer will be requested to stop sending with a `STOP_SENDING` frame.
+    ///
+    /// Sending or receiving data, as well as any stream frames from the peer, count as
+    /// progress. Since activity is checked periodically, the stream may be closed up to twice
+    /// the `timeout` after the last activity. The connection itself is not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use core::time::Duration;
+    /// # async fn test() -> s2n_quic::stream::Result<()> {
+    /// #   let connection: s2n_quic::connection::Connection = todo!();
+    /// #
+    /// let (mut stream, _) = connection.open_bidirectional_stream().await?.split();
+    /// stream.set_inactivity_timeout(Duration::from_secs(10), 123u8.into())?;
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_inactivity_timeout(
+        &mut self,
+        timeout: core::time::Duration,
+        error_code: crate::application::Error,
+    ) -> crate::stream::Result<()> {
+        self.0.set_rx_inactivity_timeout(timeout, error_code)
+    }
 }
 
 impl_splittable_stream_trait!(ReceiveStream, |stream| (Some(stream), None));
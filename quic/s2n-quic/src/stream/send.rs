@@ -376,6 +376,134 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Sets the transmission [priority](crate::stream::Priority) of the stream.
+        ///
+        /// The priority is used by the connection's
+        /// [stream scheduler](crate::provider::limits::Limits::with_stream_scheduler) to decide
+        /// which stream transmits next when multiple streams have data to send.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the priority was updated.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let connection: s2n_quic::connection::Connection = todo!();
+        /// #
+        /// use s2n_quic::stream::Priority;
+        ///
+        /// let mut stream = connection.open_send_stream().await?;
+        /// stream.set_priority(Priority::new(0, 1))?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_priority(
+            &mut self,
+            priority: $crate::stream::Priority,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_priority(priority)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Sets the Differentiated Services Code Point (DSCP) which marks the packets carrying
+        /// the stream's data.
+        ///
+        /// This allows the network to treat the stream differently from the rest of the
+        /// connection's traffic, e.g. with Expedited Forwarding (46) for latency-sensitive data.
+        /// Streams default to the code point `0`. Only the lower 6 bits of `dscp` are used.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the code point was updated.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let connection: s2n_quic::connection::Connection = todo!();
+        /// #
+        /// let mut stream = connection.open_send_stream().await?;
+        /// // mark the stream with Expedited Forwarding
+        /// stream.set_dscp(46)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_dscp(&mut self, dscp: u8) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_dscp(dscp)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Returns the number of bytes at the start of the stream which were acknowledged by the
+        /// peer.
+        ///
+        /// Data is acknowledged once it was received by the peer's QUIC stack, which doesn't mean
+        /// the peer application has read it yet. This allows applications to implement their own
+        /// windowing on top of the stream.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(offset)` with the offset up to which all of the sent data was acknowledged.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let connection: s2n_quic::connection::Connection = todo!();
+        /// #
+        /// let mut stream = connection.open_send_stream().await?;
+        /// stream.send(bytes::Bytes::from_static(&[1, 2, 3])).await?;
+        /// // wait for the peer to acknowledge the data
+        /// stream.flush().await?;
+        /// assert_eq!(stream.acked_offset()?, 3);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn acked_offset(&mut self) -> $crate::stream::Result<u64> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.acked_offset()
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
@@ -580,6 +708,37 @@ impl SendStream {
     impl_connection_api!(|stream| crate::connection::Handle(stream.0.connection().clone()));
 
     impl_send_stream_api!(|stream, dispatch| dispatch!(stream.0));
+
+    /// Resets the stream with the provided [error code](crate::application::Error) if it
+    /// doesn't make any progress for the given `timeout`
+    ///
+    /// The peer will be notified with a `RESET_STREAM` frame.
+    ///
+    /// Sending or receiving data, as well as any stream frames from the peer, count as
+    /// progress. Since activity is checked periodically, the stream may be closed up to twice
+    /// the `timeout` after the last activity. The connection itself is not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use core::time::Duration;
+    /// # async fn test() -> s2n_quic::stream::Result<()> {
+    /// #   let connection: s2n_quic::connection::Connection = todo!();
+    /// #
+    /// let mut stream = connection.open_send_stream().await?;
+    /// stream.set_inactivity_timeout(Duration::from_secs(10), 123u8.into())?;
+    /// #
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_inactivity_timeout(
+        &mut self,
+        timeout: core::time::Duration,
+        error_code: crate::application::Error,
+    ) -> crate::stream::Result<()> {
+        self.0.set_tx_inactivity_timeout(timeout, error_code)
+    }
 }
 
 impl_splittable_stream_trait!(SendStream, |stream| (None, Some(stream)));
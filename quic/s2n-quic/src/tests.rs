@@ -6,7 +6,12 @@ use crate::{
     provider::{
         self,
         event::{
-            events::{MtuUpdated, MtuUpdatedCause, PacketSent, RecoveryMetrics},
+            events::{
+                ConnectionMemoryPressure, DatagramSent, DuplicatePacket, DuplicatePacketError,
+                EcnFailureReason, EcnState, EcnStateChanged, MtuUpdated, MtuUpdatedCause,
+                PacketHeader, PacketReceived, PacketSent, RecoveryMetrics, RxStreamProgress,
+                TlsClientHello,
+            },
             ConnectionInfo, ConnectionMeta, Subscriber,
         },
         io::testing::{rand, spawn, test, time::delay, Model},
@@ -20,6 +25,7 @@ use s2n_quic_core::event::api::HandshakeStatusUpdated;
 use s2n_quic_core::{crypto::tls::testing::certificates, stream::testing::Data};
 use s2n_quic_platform::io::testing::{network::Packet, primary, TxRecorder};
 use std::{
+    mem::MaybeUninit,
     net::SocketAddr,
     sync::{Arc, Mutex},
     time::Duration,
@@ -31,6 +37,25 @@ use setup::*;
 
 #[cfg(not(target_os = "windows"))]
 mod client_handshake_confirm;
+#[cfg(not(target_os = "windows"))]
+mod resumption;
+
+mod connection_span;
+mod driven;
+mod loopback;
+#[cfg(target_os = "linux")]
+mod workers;
+#[cfg(feature = "unstable-provider-packet-interceptor")]
+mod pcap;
+
+#[cfg(feature = "provider-event-opentelemetry")]
+mod opentelemetry;
+
+#[cfg(feature = "provider-event-prometheus")]
+mod prometheus;
+
+#[cfg(feature = "provider-event-qlog")]
+mod qlog;
 
 #[test]
 fn client_server_test() {
@@ -381,6 +406,29 @@ event_recorder!(
         storage.push(event.pto_count);
     }
 );
+event_recorder!(
+    MemoryPressureRecorder,
+    ConnectionMemoryPressure,
+    on_connection_memory_pressure
+);
+event_recorder!(
+    RxStreamProgressRecorder,
+    RxStreamProgress,
+    on_rx_stream_progress,
+    usize,
+    |event: &RxStreamProgress, storage: &mut Vec<usize>| {
+        storage.push(event.bytes);
+    }
+);
+event_recorder!(
+    ClientHelloFingerprintRecorder,
+    TlsClientHello,
+    on_tls_client_hello,
+    String,
+    |event: &TlsClientHello, storage: &mut Vec<String>| {
+        storage.push(event.fingerprint().unwrap().ja4_r());
+    }
+);
 #[cfg(not(target_os = "windows"))]
 event_recorder!(
     HandshakeStatusRecorder,
@@ -388,6 +436,32 @@ event_recorder!(
     on_handshake_status_updated
 );
 
+#[test]
+fn client_hello_fingerprint_test() {
+    let subscriber = ClientHelloFingerprintRecorder::new();
+    let fingerprints = subscriber.events();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let addr = start_server(server)?;
+        client(handle, addr)?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let fingerprints = fingerprints.lock().unwrap();
+    assert_eq!(fingerprints.len(), 1);
+
+    // the client offers TLS 1.3, a server name and the default `h3` application protocol
+    let fingerprint = &fingerprints[0];
+    assert!(fingerprint.starts_with("q13d"), "{fingerprint}");
+    assert_eq!(&fingerprint[8..11], "h3_", "{fingerprint}");
+}
+
 #[test]
 fn packet_sent_event_test() {
     let recorder = TxRecorder::default();
@@ -597,6 +671,68 @@ fn mtu_blackhole() {
     assert_eq!(1200, events.lock().unwrap().last().unwrap().mtu);
 }
 
+// the discovered MTU starts at the configured min and never exceeds the configured max, even when
+// the network supports larger datagrams. A black hole still falls back to the 1200 byte floor.
+#[test]
+fn mtu_range_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(100);
+    let min_mtu = 1400;
+    let max_mtu = 1500;
+    let subscriber = MtuUpdatedRecorder::new();
+    let events = subscriber.events();
+
+    model.set_delay(rtt / 2);
+    model.set_max_udp_payload(9001);
+
+    test(model.clone(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_mtu_range(min_mtu, max_mtu).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(subscriber)?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().with_mtu_range(min_mtu, max_mtu).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000_000))?;
+
+        spawn(async move {
+            // let all packets go through for 10 RTTs - this will reach the end of MTU probing
+            delay(rtt * 10).await;
+
+            // decrease the MTU to trigger a blackhole
+            model.set_max_udp_payload(1300);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+
+    // min_mtu/max_mtu - UDP_HEADER_LEN - IPV4_HEADER_LEN
+    let initial = 1372;
+    let ceiling = 1472;
+    let floor = 1200;
+
+    // the path starts at the min MTU instead of 1200
+    assert_eq!(events[0].mtu, initial);
+    assert!(matches!(events[0].cause, MtuUpdatedCause::NewPath { .. }));
+
+    // the ceiling is reached, but never exceeded
+    assert!(events.iter().any(|event| event.mtu == ceiling));
+    assert!(events
+        .iter()
+        .all(|event| (floor..=ceiling).contains(&event.mtu)));
+
+    // the black hole falls back to the floor
+    assert!(events.iter().any(|event| {
+        matches!(event.cause, MtuUpdatedCause::Blackhole { .. }) && event.mtu == floor
+    }));
+}
+
 /// Ensures that the client's local path handle is updated after it receives a packet from the
 /// server
 ///
@@ -709,160 +845,2963 @@ fn increasing_pto_count_under_loss() {
     );
 }
 
-// TODO: https://github.com/aws/s2n-quic/issues/1726
-//
-// The rustls tls provider is used on windows and has different
-// build options than s2n-tls. We should build the rustls provider with
-// mTLS enabled and remove the `cfg(target_os("windows"))`.
-#[cfg(not(target_os = "windows"))]
-#[test]
-fn mtls_happy_case() {
-    use crate::provider::event::events::HandshakeStatus;
+/// Drops the first flight of datagrams received by the endpoint
+#[derive(Default)]
+struct DropFirstFlight {
+    first_flight: Option<s2n_quic_core::time::Timestamp>,
+}
 
-    let model = Model::default();
-    model.set_delay(Duration::from_millis(50));
-    const LEN: usize = 1000;
+impl provider::packet_interceptor::PacketInterceptor for DropFirstFlight {
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &s2n_quic_core::event::api::Subject,
+        datagram: &s2n_quic_core::packet::interceptor::Datagram,
+        payload: s2n_codec::DecoderBufferMut<'a>,
+    ) -> s2n_codec::DecoderBufferMut<'a> {
+        // the peer's first flight is delivered all at once so drop everything received at that time
+        let first_flight = *self.first_flight.get_or_insert(datagram.timestamp);
+        if datagram.timestamp == first_flight {
+            return s2n_codec::DecoderBufferMut::new(&mut payload.into_less_safe_slice()[..0]);
+        }
+        payload
+    }
+}
 
-    let server_subscriber = HandshakeStatusRecorder::new();
-    let server_events = server_subscriber.clone();
-    let client_subscriber = HandshakeStatusRecorder::new();
-    let client_events = client_subscriber.clone();
+/// Replaces the frames in the first 1-RTT packet received by the endpoint that is large enough
+struct InjectFrames {
+    frames: Vec<u8>,
+    injected: bool,
+}
+
+impl InjectFrames {
+    fn new<F: s2n_codec::EncoderValue>(frames: &[F]) -> Self {
+        use s2n_codec::{Encoder, EncoderBuffer};
+
+        let len = frames.iter().map(|frame| frame.encoding_size()).sum();
+        let mut bytes = vec![0; len];
+        let mut encoder = EncoderBuffer::new(&mut bytes);
+        for frame in frames {
+            encoder.encode(frame);
+        }
+
+        Self {
+            frames: bytes,
+            injected: false,
+        }
+    }
+}
+
+impl provider::packet_interceptor::PacketInterceptor for InjectFrames {
+    fn intercept_rx_payload<'a>(
+        &mut self,
+        _subject: &s2n_quic_core::event::api::Subject,
+        packet: &s2n_quic_core::packet::interceptor::Packet,
+        payload: s2n_codec::DecoderBufferMut<'a>,
+    ) -> s2n_codec::DecoderBufferMut<'a> {
+        if self.injected
+            || !packet.number.space().is_application_data()
+            || payload.len() < self.frames.len()
+        {
+            return payload;
+        }
+
+        self.injected = true;
+
+        // overwrite the payload with the frames and pad out the rest of the packet
+        let payload = payload.into_less_safe_slice();
+        let (frames, padding) = payload.split_at_mut(self.frames.len());
+        frames.copy_from_slice(&self.frames);
+        padding.fill(0);
+
+        s2n_codec::DecoderBufferMut::new(payload)
+    }
+}
+
+/// Injects frames into the packets received by the server and asserts the server closes the
+/// connection with the `expected` transport error
+fn peer_limit_violation(
+    limits: provider::limits::Limits,
+    interceptor: InjectFrames,
+    expected: s2n_quic_core::transport::error::Code,
+) {
+    let model = Model::default();
 
     test(model, |handle| {
-        let server_tls = build_server_mtls_provider(certificates::MTLS_CA_CERT)?;
-        let mut server = Server::builder()
+        let server = Server::builder()
             .with_io(handle.builder().build()?)?
-            .with_tls(server_tls)?
-            .with_event(server_subscriber)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_limits(limits)?
+            .with_packet_interceptor(interceptor)?
             .start()?;
+        let server_addr = start_server(server)?;
 
-        let addr = server.local_addr()?;
-        spawn(async move {
-            let mut conn = server.accept().await.unwrap();
-            let mut stream = conn.open_bidirectional_stream().await.unwrap();
-            stream.send(vec![42; LEN].into()).await.unwrap();
-            stream.flush().await.unwrap();
-        });
-
-        let client_tls = build_client_mtls_provider(certificates::MTLS_CA_CERT)?;
-        let client = Client::builder()
-            .with_io(handle.builder().build().unwrap())?
-            .with_tls(client_tls)?
-            .with_event(client_subscriber)?
-            .start()?;
+        let client = build_client(handle)?;
 
         primary::spawn(async move {
-            let connect = Connect::new(addr).with_server_name("localhost");
-            let mut conn = client.connect(connect).await.unwrap();
-            let mut stream = conn.accept_bidirectional_stream().await.unwrap().unwrap();
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
 
-            let mut recv_len = 0;
-            while let Some(chunk) = stream.receive().await.unwrap() {
-                recv_len += chunk.len();
+            // send enough data for the injected frames to fit in a packet
+            let _ = stream.send(Bytes::from(vec![42; 1000])).await;
+
+            let error = connection.accept_bidirectional_stream().await.unwrap_err();
+            match error {
+                crate::connection::Error::Transport {
+                    code, initiator, ..
+                } => {
+                    assert_eq!(code, expected);
+                    assert!(initiator.is_remote());
+                }
+                error => panic!("unexpected error: {error:?}"),
             }
-            assert_eq!(LEN, recv_len);
         });
 
-        Ok(addr)
+        Ok(server_addr)
     })
     .unwrap();
+}
 
-    let server_handshake_complete =
-        server_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
-    let server_handshake_confirmed =
-        server_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
-    let client_handshake_complete =
-        client_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
-    let client_handshake_confirmed =
-        client_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
+//= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+//= type=test
+//# After processing a NEW_CONNECTION_ID frame and
+//# adding and retiring active connection IDs, if the number of active
+//# connection IDs exceeds the value advertised in its
+//# active_connection_id_limit transport parameter, an endpoint MUST
+//# close the connection with an error of type CONNECTION_ID_LIMIT_ERROR.
+#[test]
+fn peer_exceeds_connection_id_limit_test() {
+    use s2n_quic_core::{frame::NewConnectionId, varint::VarInt};
 
-    // assert handshake success for both the sever and client
-    assert!(server_handshake_complete);
-    assert!(server_handshake_confirmed);
-    assert!(client_handshake_complete);
-    assert!(client_handshake_confirmed);
+    let connection_ids = [[1; 8], [2; 8], [3; 8], [4; 8]];
+    let tokens = [[1; 16], [2; 16], [3; 16], [4; 16]];
+
+    // issue more connection IDs than the server's active_connection_id_limit
+    let frames: Vec<_> = connection_ids
+        .iter()
+        .zip(tokens.iter())
+        .enumerate()
+        .map(|(idx, (connection_id, token))| NewConnectionId {
+            sequence_number: VarInt::from_u32(100 + idx as u32),
+            retire_prior_to: VarInt::from_u8(0),
+            connection_id,
+            stateless_reset_token: token,
+        })
+        .collect();
+
+    peer_limit_violation(
+        provider::limits::Limits::default(),
+        InjectFrames::new(&frames),
+        s2n_quic_core::transport::Error::CONNECTION_ID_LIMIT_ERROR.code,
+    );
 }
 
-// TODO: https://github.com/aws/s2n-quic/issues/1726
-#[cfg(not(target_os = "windows"))]
+//= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
+//= type=test
+//# An endpoint that receives a frame with a stream ID exceeding the limit it has
+//# sent MUST treat this as a connection error of type
+//# STREAM_LIMIT_ERROR; see Section 11 for details on error handling.
 #[test]
-fn mtls_auth_failure() {
-    use crate::provider::event::events::HandshakeStatus;
-    use std::sync::atomic::{AtomicBool, Ordering};
+fn peer_exceeds_stream_limit_test() {
+    use s2n_quic_core::{frame::MaxStreamData, varint::VarInt};
 
-    let model = Model::default();
-    model.set_delay(Duration::from_millis(50));
+    let limits = provider::limits::Limits::default()
+        .with_max_open_remote_bidirectional_streams(1)
+        .unwrap();
 
-    let server_subscriber = HandshakeStatusRecorder::new();
-    let server_events = server_subscriber.clone();
-    let client_subscriber = HandshakeStatusRecorder::new();
-    let client_events = client_subscriber.clone();
+    // reference the second client-initiated bidirectional stream, which exceeds the limit
+    let frames = [MaxStreamData {
+        stream_id: VarInt::from_u8(4),
+        maximum_stream_data: VarInt::from_u16(1000),
+    }];
 
-    // check that server attempts to accept but rejects a connection
-    let server_connection_closed = Arc::new(AtomicBool::new(false));
-    let server_connection_closed_clone = server_connection_closed.clone();
+    peer_limit_violation(
+        limits,
+        InjectFrames::new(&frames),
+        s2n_quic_core::transport::Error::STREAM_LIMIT_ERROR.code,
+    );
+}
+
+event_recorder!(
+    PtoTimeRecorder,
+    RecoveryMetrics,
+    on_recovery_metrics,
+    (s2n_quic_core::time::Timestamp, u32),
+    |event: &RecoveryMetrics, storage: &mut Vec<(s2n_quic_core::time::Timestamp, u32)>| {
+        storage.push((crate::provider::io::testing::now(), event.pto_count));
+    }
+);
+
+/// Drops the server's first flight and returns the amount of time the client waited before
+/// sending its first probe
+fn handshake_first_probe_delay(limits: provider::limits::Limits) -> Duration {
+    let model = Model::default();
+    // delay the network so the server's retransmissions arrive after the client's probe
+    model.set_delay(Duration::from_millis(10));
+    let subscriber = PtoTimeRecorder::new();
+    let pto_events = subscriber.events();
+    let mut start = None;
 
     test(model, |handle| {
-        let server_tls = build_server_mtls_provider(certificates::UNTRUSTED_CERT_PEM)?;
-        let mut server = Server::builder()
+        let server = Server::builder()
             .with_io(handle.builder().build()?)?
-            .with_tls(server_tls)?
-            .with_event(server_subscriber)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
             .start()?;
+        let addr = start_server(server)?;
 
-        let addr = server.local_addr()?;
-        spawn(async move {
-            match server.accept().await {
-                Some(_) => {
-                    panic!("connection should not be accepted on auth failure");
-                }
-                None => {
-                    assert!(
-                        !server_connection_closed_clone.swap(true, Ordering::SeqCst),
-                        "confirm that this is only called once"
-                    );
-                }
-            }
-        });
-
-        let client_tls = build_client_mtls_provider(certificates::MTLS_CA_CERT)?;
         let client = Client::builder()
-            .with_io(handle.builder().build().unwrap())?
-            .with_tls(client_tls)?
-            .with_event(client_subscriber)?
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits)?
+            .with_event(subscriber)?
+            .with_packet_interceptor(DropFirstFlight::default())?
             .start()?;
 
+        start = Some(crate::provider::io::testing::now());
+
         primary::spawn(async move {
             let connect = Connect::new(addr).with_server_name("localhost");
-            let mut conn = client.connect(connect).await.unwrap();
-            let stream_result = conn.accept_bidirectional_stream().await;
-
-            assert!(stream_result.is_err(), "handshake should fail");
+            // the client should recover from the lost flight by probing the server
+            let conn = client.connect(connect).await.unwrap();
+            let _ = conn;
         });
 
         Ok(addr)
     })
     .unwrap();
 
-    let server_handshake_complete =
-        server_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
-    let server_handshake_confirmed =
-        server_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
-    let client_handshake_complete =
-        client_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
-    let client_handshake_confirmed =
-        client_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
+    let start = start.unwrap();
+    let pto_events = pto_events.lock().unwrap();
+    let (probe, _) = pto_events
+        .iter()
+        .find(|(_, pto_count)| *pto_count > 0)
+        .expect("the client should probe after losing the first flight");
 
-    // expect server handshake to fail
-    assert!(!server_handshake_complete);
-    assert!(!server_handshake_confirmed);
+    probe.saturating_duration_since(start)
+}
 
-    // expect the client handshake status to 'Complete' but not 'Confirmed'. We
-    // expect server's client-certificate-authentication (mTLS) to fail, which
-    // happens after client TLS handshake 'Complete'
-    assert!(client_handshake_complete);
-    assert!(!client_handshake_confirmed);
+#[test]
+fn handshake_pto_default_test() {
+    let delay = handshake_first_probe_delay(provider::limits::Limits::default());
 
-    // confirm server connection was attempted but failed
-    assert!(server_connection_closed.load(Ordering::SeqCst));
+    // PTO = smoothed_rtt + max(4*rttvar, kGranularity) = 333ms + 666ms
+    let expected = Duration::from_millis(999);
+    assert!(delay >= expected, "{delay:?} >= {expected:?}");
+    assert!(delay < expected * 2, "{delay:?} < {:?}", expected * 2);
+}
+
+#[test]
+fn handshake_pto_configured_test() {
+    let initial_rtt = Duration::from_millis(50);
+    let limits = provider::limits::Limits::default()
+        .with_initial_round_trip_time(initial_rtt)
+        .unwrap();
+    let delay = handshake_first_probe_delay(limits);
+
+    // PTO = smoothed_rtt + max(4*rttvar, kGranularity) = 50ms + 100ms
+    let expected = initial_rtt * 3;
+    assert!(delay >= expected, "{delay:?} >= {expected:?}");
+    assert!(delay < expected * 2, "{delay:?} < {:?}", expected * 2);
+}
+
+/// Runs a connection through its full lifecycle on the testing IO provider and returns the
+/// amount of virtual time the connection sat idle before closing, along with the total runtime
+fn idle_lifecycle(idle_timeout: Duration) -> (Duration, Duration) {
+    const LEN: usize = 10_000;
+
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    let idle_duration = Arc::new(Mutex::new(None));
+    let idle_duration_result = idle_duration.clone();
+
+    let runtime = test(model, |handle| {
+        // force a Retry so the address token provider is driven by the endpoint clock as well
+        let endpoint_limits = provider::endpoint_limits::Default::builder()
+            .with_inflight_handshake_limit(0)?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_endpoint_limits(endpoint_limits)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let limits = provider::limits::Limits::default()
+            .with_max_idle_timeout(idle_timeout)
+            .unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits)?
+            .with_event(events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(&[42; LEN])).await.unwrap();
+
+            let mut recv_len = 0;
+            while recv_len < LEN {
+                recv_len += stream.receive().await.unwrap().unwrap().len();
+            }
+
+            let idle_start = crate::provider::io::testing::now();
+
+            // nothing else is sent so the connection should close once the idle timer expires
+            let result = connection.accept_bidirectional_stream().await;
+            assert!(
+                matches!(
+                    result,
+                    Err(crate::connection::Error::IdleTimerExpired { .. })
+                ),
+                "{result:?}"
+            );
+
+            let idle_end = crate::provider::io::testing::now();
+            *idle_duration.lock().unwrap() = Some(idle_end.saturating_duration_since(idle_start));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let idle_duration = idle_duration_result
+        .lock()
+        .unwrap()
+        .expect("the connection should close after going idle");
+
+    (idle_duration, runtime)
+}
+
+#[test]
+fn mock_clock_lifecycle_test() {
+    let idle_timeout = Duration::from_secs(60);
+
+    let wall_clock = std::time::Instant::now();
+    let (idle_duration, runtime) = idle_lifecycle(idle_timeout);
+    let wall_clock = wall_clock.elapsed();
+
+    // the idle timer is driven entirely by the testing clock so it fires on schedule
+    assert!(idle_duration >= idle_timeout, "{idle_duration:?}");
+    assert!(
+        idle_duration < idle_timeout + Duration::from_secs(1),
+        "{idle_duration:?}"
+    );
+    assert!(runtime > idle_timeout, "{runtime:?}");
+
+    // none of the timers should have waited in real time
+    assert!(wall_clock < idle_timeout, "{wall_clock:?}");
+
+    // running the same scenario again should produce the exact same timeline
+    assert_eq!(idle_lifecycle(idle_timeout), (idle_duration, runtime));
+}
+
+#[test]
+fn incoming_streams_fairness_test() {
+    const STREAMS: usize = 10;
+
+    let accepted = Arc::new(Mutex::new(vec![]));
+    let accepted_result = accepted.clone();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        for _ in 0..2 {
+            let client = Client::builder()
+                .with_io(handle.builder().build()?)?
+                .with_tls(certificates::CERT_PEM)?
+                .with_event(events())?
+                .start()?;
+
+            spawn(async move {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut streams = vec![];
+                for _ in 0..STREAMS {
+                    let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                    stream.send(Bytes::from_static(&[42])).await.unwrap();
+                    streams.push(stream);
+                }
+
+                // keep the streams open until the server has accepted all of them
+                delay(Duration::from_secs(10)).await;
+                drop((connection, streams));
+            });
+        }
+
+        primary::spawn(async move {
+            // give both clients a chance to open all of their streams before accepting any
+            delay(Duration::from_secs(1)).await;
+
+            let mut incoming = server.incoming_streams();
+            for _ in 0..STREAMS * 2 {
+                let (connection, _stream) = incoming.accept().await.unwrap();
+                accepted.lock().unwrap().push(connection.id());
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let accepted = accepted_result.lock().unwrap();
+    assert_eq!(accepted.len(), STREAMS * 2);
+
+    // both connections had streams pending so the aggregator should alternate between them
+    for pair in accepted.chunks(2) {
+        assert_ne!(pair[0], pair[1], "{accepted:?}");
+    }
+}
+
+event_recorder!(
+    VersionNegotiationRecorder,
+    PacketReceived,
+    on_packet_received,
+    (),
+    |event: &PacketReceived, storage: &mut Vec<()>| {
+        if matches!(event.packet_header, PacketHeader::VersionNegotiation { .. }) {
+            storage.push(());
+        }
+    }
+);
+
+#[test]
+fn version_negotiation_retry_test() {
+    use crate::connection::Version;
+
+    // reserved versions are never supported by the server
+    let reserved_version = Version::from(0x1a2a_3a4a);
+
+    let subscriber = VersionNegotiationRecorder::new();
+    let version_negotiations = subscriber.events();
+
+    test(Model::default(), |handle| {
+        let addr = server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_quic_version(reserved_version)
+                .unwrap();
+            // the client should retry with version 1 after receiving a Version Negotiation packet
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(version_negotiations.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn version_negotiation_no_retry_test() {
+    use crate::connection::Version;
+
+    let reserved_version = Version::from(0x1a2a_3a4a);
+
+    test(Model::default(), |handle| {
+        let addr = server(handle)?;
+
+        let limits = provider::limits::Limits::default()
+            .with_max_version_negotiation_retries(0)
+            .unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_quic_version(reserved_version)
+                .unwrap();
+            // the version negotiation error is returned instead of retrying
+            let error = client.connect(connect).await.unwrap_err();
+            assert!(matches!(
+                error,
+                crate::connection::Error::UnsupportedVersion {
+                    compatible_version: Some(0x0000_0001),
+                    ..
+                }
+            ));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn unsupported_quic_version_test() {
+    use crate::connection::Version;
+
+    let addr: std::net::SocketAddr = "127.0.0.1:443".parse().unwrap();
+
+    assert!(Connect::new(addr).with_quic_version(Version::V1).is_ok());
+
+    // the client can't use versions which it doesn't implement
+    for version in [Version::V2, Version::from(0xff00_001d), Version::from(0)] {
+        let error = Connect::new(addr).with_quic_version(version).unwrap_err();
+        assert_eq!(error.version, version);
+    }
+}
+
+event_recorder!(
+    RetryRecorder,
+    PacketReceived,
+    on_packet_received,
+    (),
+    |event: &PacketReceived, storage: &mut Vec<()>| {
+        if matches!(event.packet_header, PacketHeader::Retry { .. }) {
+            storage.push(());
+        }
+    }
+);
+
+#[test]
+fn new_token_test() {
+    let subscriber = RetryRecorder::new();
+    let retries = subscriber.events();
+    let retries_result = retries.clone();
+
+    test(Model::default(), |handle| {
+        // the server sends a Retry to every client which doesn't present a valid token
+        let endpoint_limits = provider::endpoint_limits::Default::builder()
+            .with_inflight_handshake_limit(0)?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_endpoint_limits(endpoint_limits)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((events(), subscriber))?
+            .start()?;
+
+        primary::spawn(async move {
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                // the NEW_TOKEN frame is sent before the echoed data
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                let chunk = stream.receive().await.unwrap().unwrap();
+                assert_eq!(chunk, Bytes::from_static(b"hello"));
+
+                // only the first connection should be validated with a Retry
+                assert_eq!(retries.lock().unwrap().len(), 1);
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(retries_result.lock().unwrap().len(), 1);
+}
+
+event_recorder!(
+    DuplicatePacketRecorder,
+    DuplicatePacket,
+    on_duplicate_packet,
+    DuplicatePacketError,
+    |event: &DuplicatePacket, storage: &mut Vec<DuplicatePacketError>| {
+        storage.push(event.error.clone());
+    }
+);
+
+#[test]
+fn replay_window_test() {
+    let model = Model::default();
+    // duplicate and reorder packets
+    model.set_retransmit_rate(0.5);
+    model.set_network_jitter(Duration::from_millis(5));
+
+    let subscriber = DuplicatePacketRecorder::new();
+    let errors = subscriber.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), subscriber))?
+            .with_limits(provider::limits::Limits::default().with_replay_window(4)?)?
+            .start()?;
+        let addr = start_server(server)?;
+        client(handle, addr)?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let errors = errors.lock().unwrap();
+    // packets which were already received are dropped
+    assert!(errors
+        .iter()
+        .any(|error| matches!(error, DuplicatePacketError::Duplicate { .. })));
+    // packets which are reordered further than the window are dropped
+    assert!(errors
+        .iter()
+        .any(|error| matches!(error, DuplicatePacketError::TooOld { .. })));
+}
+
+/// Returns the time it takes to echo a stream on a near-zero RTT path with or without pacing
+fn pacing_transfer_duration(pacing: bool) -> Duration {
+    let model = Model::default();
+    model.set_delay(Duration::from_micros(100));
+
+    let duration = Arc::new(Mutex::new(None));
+    let transfer_duration = duration.clone();
+
+    test(model, |handle| {
+        // CUBIC doesn't pace paths with an RTT below 2ms so BBR is used instead
+        let congestion_controller = || {
+            provider::congestion_controller::Bbr::builder()
+                .with_pacing(pacing)
+                .build()
+        };
+
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_congestion_controller(congestion_controller())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_congestion_controller(congestion_controller())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let start = provider::io::testing::now();
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(1_000_000);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+
+            let elapsed = provider::io::testing::now().saturating_duration_since(start);
+            *duration.lock().unwrap() = Some(elapsed);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let duration = transfer_duration.lock().unwrap().take();
+    duration.expect("the transfer should complete")
+}
+
+#[test]
+fn pacing_disabled_test() {
+    let paced = pacing_transfer_duration(true);
+    let unpaced = pacing_transfer_duration(false);
+
+    // sending without pacing shouldn't be any slower on a path without queueing delay
+    assert!(
+        unpaced <= paced,
+        "unpaced transfer took {unpaced:?}, paced transfer took {paced:?}"
+    );
+}
+
+#[test]
+fn stats_app_limited_test() {
+    test(Model::default(), |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // write a small amount of data at a time, which never fills the congestion window
+            for _ in 0..20 {
+                stream.send(Bytes::from_static(&[42; 100])).await.unwrap();
+                delay(Duration::from_millis(100)).await;
+            }
+            stream.finish().unwrap();
+            stream.flush().await.unwrap();
+
+            let stats = connection.stats().unwrap();
+            assert!(stats.app_limited_ratio() > 0.9, "{stats:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn stats_cwnd_limited_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(1_000_000);
+            let mut recv_data = send_data;
+
+            // write as much data as possible so the congestion window is always full
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+
+            let stats = connection.stats().unwrap();
+            assert!(stats.cwnd_limited_ratio() > 0.5, "{stats:?}");
+            assert!(stats.cwnd_limited > stats.app_limited, "{stats:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn stats_path_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            stream
+                .send(Bytes::from_static(&[42; 10_000]))
+                .await
+                .unwrap();
+            stream.finish().unwrap();
+            while stream.receive().await.unwrap().is_some() {}
+
+            let stats = connection.stats().unwrap();
+
+            // the delay is applied in each direction
+            assert!(stats.min_rtt >= Duration::from_millis(100), "{stats:?}");
+            assert!(stats.smoothed_rtt >= stats.min_rtt, "{stats:?}");
+            assert!(stats.congestion_window > 0, "{stats:?}");
+            assert!(
+                stats.bytes_in_flight <= stats.congestion_window,
+                "{stats:?}"
+            );
+            assert!(stats.mtu >= 1200, "{stats:?}");
+            assert!(stats.pacing_rate.is_some(), "{stats:?}");
+            assert_eq!(stats.packets_lost(), 0, "{stats:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn stream_dscp_test() {
+    const LEN: usize = 20_000;
+
+    let recorder = TxRecorder::default();
+    let network_packets = recorder.get_packets();
+    let mut server_socket = None;
+    test((recorder, Model::default()), |handle| {
+        let addr = server(handle)?;
+        server_socket = Some(addr);
+
+        let client = build_client(handle)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the first stream is marked with Expedited Forwarding and the second keeps the
+            // default code point
+            for dscp in [46, 0] {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.set_dscp(dscp).unwrap();
+
+                primary::spawn(async move {
+                    let mut data = Data::new(LEN as _);
+                    while let Some(chunk) = data.send_one(usize::MAX) {
+                        stream.send(chunk).await.unwrap();
+                    }
+                    stream.finish().unwrap();
+
+                    // wait for the server to echo the data back
+                    while stream.receive().await.unwrap().is_some() {}
+                });
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_socket = server_socket.unwrap();
+    let mut marked_len = 0;
+    let mut unmarked_len = 0;
+
+    for packet in network_packets.lock().unwrap().iter() {
+        let local_socket: SocketAddr = packet.path.local_address.0.into();
+        if local_socket == server_socket {
+            // the server doesn't mark its streams
+            assert_eq!(packet.dscp, 0);
+            continue;
+        }
+
+        match packet.dscp {
+            46 => marked_len += packet.payload.len(),
+            0 => unmarked_len += packet.payload.len(),
+            dscp => panic!("unexpected DSCP {dscp}"),
+        }
+    }
+
+    // each stream's data is carried by the packets marked with the stream's code point
+    assert!(marked_len >= LEN, "{marked_len}");
+    assert!(unmarked_len >= LEN, "{unmarked_len}");
+}
+
+#[test]
+fn connection_dscp_test() {
+    const LEN: usize = 20_000;
+
+    let recorder = TxRecorder::default();
+    let network_packets = recorder.get_packets();
+    let mut server_socket = None;
+    test((recorder, Model::default()), |handle| {
+        // the server marks all of its packets with the endpoint's code point
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_limits(provider::limits::Limits::default().with_dscp(10)?)?
+            .start()?;
+        let addr = start_server(server)?;
+        server_socket = Some(addr);
+
+        let client = build_client(handle)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // code points only have 6 bits
+            assert_eq!(
+                connection.set_dscp(64),
+                Err(crate::connection::DscpError::InvalidCodePoint)
+            );
+
+            // the connection's code point overrides the endpoint's after the handshake
+            connection.set_dscp(34).unwrap();
+
+            // the first stream overrides the connection's code point and the second inherits it
+            for dscp in [Some(46), None] {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                if let Some(dscp) = dscp {
+                    stream.set_dscp(dscp).unwrap();
+                }
+
+                primary::spawn(async move {
+                    let mut data = Data::new(LEN as _);
+                    while let Some(chunk) = data.send_one(usize::MAX) {
+                        stream.send(chunk).await.unwrap();
+                    }
+                    stream.finish().unwrap();
+
+                    // wait for the server to echo the data back
+                    while stream.receive().await.unwrap().is_some() {}
+                });
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_socket = server_socket.unwrap();
+    let mut stream_len = 0;
+    let mut connection_len = 0;
+
+    for packet in network_packets.lock().unwrap().iter() {
+        let local_socket: SocketAddr = packet.path.local_address.0.into();
+        if local_socket == server_socket {
+            assert_eq!(packet.dscp, 10);
+            continue;
+        }
+
+        match packet.dscp {
+            46 => stream_len += packet.payload.len(),
+            34 => connection_len += packet.payload.len(),
+            // the handshake completes before the connection's code point is set
+            0 => {}
+            dscp => panic!("unexpected DSCP {dscp}"),
+        }
+    }
+
+    assert!(stream_len >= LEN, "{stream_len}");
+    assert!(connection_len >= LEN, "{connection_len}");
+}
+
+#[test]
+fn stateless_reset_quiet_time_test() {
+    use crate::provider::stateless_reset_token::{Generator, PerBoot};
+    use s2n_quic_core::stateless_reset::{token::LEN, Token};
+
+    /// Derives tokens from a key which is shared by the original and the restarted server
+    #[derive(Debug, Default)]
+    struct Keyed;
+
+    impl Generator for Keyed {
+        fn generate(&mut self, input: &[u8]) -> Token {
+            let mut token = [0x5a; LEN];
+            for (index, byte) in input.iter().enumerate() {
+                token[index % LEN] ^= byte.rotate_left(index as u32);
+            }
+            token.into()
+        }
+    }
+
+    let recorder = TxRecorder::default();
+    let network_packets = recorder.get_packets();
+    let model = Model::default();
+    let addresses = Arc::new(Mutex::new(None));
+
+    test((recorder, model.clone()), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_stateless_reset_token(PerBoot::new(Keyed).with_boot_id(1))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        // the restarted server shares the key of the original server, but not its boot id
+        let restarted = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_stateless_reset_token(
+                PerBoot::new(Keyed)
+                    .with_boot_id(2)
+                    .with_quiet_time(Duration::from_secs(60)),
+            )?
+            .start()?;
+        let restarted_addr = start_server(restarted)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_limits(
+                provider::limits::Limits::new().with_max_idle_timeout(Duration::from_secs(5))?,
+            )?
+            .start()?;
+        let fresh_client = build_client(handle)?;
+        let addresses = addresses.clone();
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let old_client_addr = connection.local_addr().unwrap();
+            *addresses.lock().unwrap() = Some((restarted_addr, old_client_addr));
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+
+            // simulate a restart by delivering everything sent to the original server to the
+            // restarted server instead
+            model.rebind(restarted_addr.into(), server_addr.into());
+
+            // the restarted server drops the packets of the old connection without resetting it
+            stream.send(Bytes::from_static(b"world")).await.unwrap();
+            let result = connection.accept_bidirectional_stream().await;
+            assert!(
+                matches!(
+                    result,
+                    Err(crate::connection::Error::IdleTimerExpired { .. })
+                ),
+                "{result:?}"
+            );
+
+            // fresh connections are accepted by the restarted server
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = fresh_client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let (restarted_addr, old_client_addr) = addresses
+        .lock()
+        .unwrap()
+        .expect("the old connection should be established");
+
+    // no stateless resets were sent to the old connection during the quiet time
+    for packet in network_packets.lock().unwrap().iter() {
+        let local_address: SocketAddr = packet.path.local_address.0.into();
+        let remote_address: SocketAddr = packet.path.remote_address.0.into();
+        assert!(
+            local_address != restarted_addr || remote_address != old_client_addr,
+            "the restarted server responded to the old connection"
+        );
+    }
+}
+
+#[test]
+fn accept_backlog_test() {
+    use tokio::sync::oneshot;
+
+    test(Model::default(), |handle| {
+        let endpoint_limits = provider::endpoint_limits::Default::builder()
+            .with_accept_backlog(2)?
+            .build()?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_endpoint_limits(endpoint_limits)?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_limits(
+                provider::limits::Limits::new()
+                    .with_max_handshake_duration(Duration::from_secs(5))?,
+            )?
+            .start()?;
+
+        let (full_sender, full_receiver) = oneshot::channel();
+        let (drained_sender, drained_receiver) = oneshot::channel();
+
+        spawn(async move {
+            // the application doesn't accept anything until the backlog is full
+            full_receiver.await.unwrap();
+
+            let mut connections = vec![];
+            for _ in 0..2 {
+                let connection = server.accept_timeout(delay(Duration::from_secs(1))).await;
+                connections.push(connection.expect("pending connections should be accepted"));
+            }
+
+            // the accept times out once the backlog is drained
+            let connection = server.accept_timeout(delay(Duration::from_secs(1))).await;
+            assert!(connection.is_none());
+
+            drained_sender.send(()).unwrap();
+
+            // the server is still accepting connections after the timeout
+            connections.push(server.accept().await.unwrap());
+        });
+
+        primary::spawn(async move {
+            let mut connections = vec![];
+            for _ in 0..2 {
+                let connect = Connect::new(server_addr).with_server_name("localhost");
+                connections.push(client.connect(connect).await.unwrap());
+            }
+
+            // the backlog is full so further handshakes are refused
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            assert!(client.connect(connect).await.is_err());
+
+            full_sender.send(()).unwrap();
+            drained_receiver.await.unwrap();
+
+            // accepting the pending connections makes room for new ones
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            connections.push(client.connect(connect).await.unwrap());
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn quic_version_test() {
+    use crate::connection::Version;
+
+    // only version 1 is currently supported by the endpoints, so there's no version 2 case yet
+    test(Model::default(), |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+        let client = build_client(handle)?;
+
+        spawn(async move {
+            let connection = server.accept().await.unwrap();
+            assert_eq!(connection.quic_version().unwrap(), Version::V1);
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let version = connection.quic_version().unwrap();
+            assert_eq!(version, Version::V1);
+            assert_eq!(version.to_string(), "v1");
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Drops the first datagram received by the endpoint which starts with a Handshake packet
+#[derive(Default)]
+struct DropFirstHandshake {
+    dropped: bool,
+}
+
+impl provider::packet_interceptor::PacketInterceptor for DropFirstHandshake {
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &s2n_quic_core::event::api::Subject,
+        _datagram: &s2n_quic_core::packet::interceptor::Datagram,
+        payload: s2n_codec::DecoderBufferMut<'a>,
+    ) -> s2n_codec::DecoderBufferMut<'a> {
+        // long header packets with a type of 0b10 are Handshake packets
+        let is_handshake = payload
+            .peek_byte(0)
+            .map_or(false, |tag| tag & 0x80 != 0 && tag & 0x30 == 0x20);
+
+        if self.dropped || !is_handshake {
+            return payload;
+        }
+
+        self.dropped = true;
+        s2n_codec::DecoderBufferMut::new(&mut payload.into_less_safe_slice()[..0])
+    }
+}
+
+#[test]
+fn space_stats_test() {
+    let server_stats = Arc::new(Mutex::new(None));
+
+    test(Model::default(), |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_packet_interceptor(DropFirstHandshake::default())?
+            .start()?;
+
+        let stats = server_stats.clone();
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            let mut request = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                request.extend_from_slice(&chunk);
+            }
+
+            // the handshake has completed so the Handshake space counts are final
+            *stats.lock().unwrap() = Some(connection.stats().unwrap());
+
+            stream.send(request.into()).await.unwrap();
+            stream.close().await.unwrap();
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            let request = Bytes::from_static(&[42; 10_000]);
+            stream.send(request.clone()).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut response = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                response.extend_from_slice(&chunk);
+            }
+            assert_eq!(response, request);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let stats = server_stats
+        .lock()
+        .unwrap()
+        .take()
+        .expect("the server should record its stats");
+
+    assert!(stats.initial.packets_sent > 0);
+    assert!(stats.initial.packets_acked > 0);
+
+    // the dropped Handshake packet should be declared lost and retransmitted
+    assert!(stats.handshake.packets_lost >= 1, "{stats:?}");
+    assert!(stats.handshake.packets_sent > stats.handshake.packets_lost);
+
+    // the loss in the Handshake space shouldn't affect the Application space
+    assert_eq!(stats.application.packets_lost, 0, "{stats:?}");
+    assert!(stats.application.packets_acked > 0);
+}
+
+#[test]
+fn ecn_counts_test() {
+    use s2n_quic_core::varint::VarInt;
+
+    let client_stats = Arc::new(Mutex::new(None));
+
+    let model = Model::default();
+    model.set_ce_rate(0.2);
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        let stats = client_stats.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            let request = Bytes::from_static(&[42; 100_000]);
+            stream.send(request.clone()).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut response = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                response.extend_from_slice(&chunk);
+            }
+            assert_eq!(response, request);
+
+            *stats.lock().unwrap() = Some(connection.stats().unwrap());
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let stats = client_stats
+        .lock()
+        .unwrap()
+        .take()
+        .expect("the client should record its stats");
+
+    // the server reports the CE markings of the client's packets in its ACK frames
+    assert!(stats.ecn_sent().ce_count > VarInt::ZERO, "{stats:?}");
+    assert!(stats.ecn_sent().ect_0_count > VarInt::ZERO, "{stats:?}");
+
+    // the client counts the CE markings of the server's packets
+    assert!(stats.ecn_received().ce_count > VarInt::ZERO, "{stats:?}");
+    assert!(stats.ecn_received().ect_0_count > VarInt::ZERO, "{stats:?}");
+    assert!(stats.application.ecn_received.ce_count > VarInt::ZERO);
+}
+
+/// Records the number of packets and the length of each datagram sent by a connection
+#[derive(Clone, Default)]
+struct CoalescingRecorder {
+    datagrams: Arc<Mutex<Vec<(usize, u16)>>>,
+}
+
+#[derive(Default)]
+struct CoalescingContext {
+    datagrams: Arc<Mutex<Vec<(usize, u16)>>>,
+    packets: usize,
+}
+
+impl Subscriber for CoalescingRecorder {
+    type ConnectionContext = CoalescingContext;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        CoalescingContext {
+            datagrams: self.datagrams.clone(),
+            packets: 0,
+        }
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &PacketSent,
+    ) {
+        context.packets += 1;
+    }
+
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &DatagramSent,
+    ) {
+        let packets = core::mem::take(&mut context.packets);
+        context.datagrams.lock().unwrap().push((packets, event.len));
+    }
+}
+
+/// Returns the number of packets and the length of each datagram sent by a client with the
+/// given coalescing limit
+fn coalesced_datagrams(max_coalesced_packets: u8) -> Vec<(usize, u16)> {
+    let subscriber = CoalescingRecorder::default();
+    let datagrams = subscriber.datagrams.clone();
+
+    test(Model::default(), |handle| {
+        let server_addr = server(handle)?;
+
+        let limits =
+            provider::limits::Limits::new().with_max_coalesced_packets(max_coalesced_packets)?;
+        let client = Client::builder()
+            .with_io(handle.builder().with_mtu_range(1400, 1400).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((subscriber, events()))?
+            .with_limits(limits)?
+            .start()?;
+
+        start_client(client, server_addr, Data::new(10_000))?;
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let datagrams = datagrams.lock().unwrap();
+    datagrams.clone()
+}
+
+#[test]
+fn max_coalesced_packets_test() {
+    // 1400 - UDP_HEADER_LEN - IPV4_HEADER_LEN
+    let mtu = 1372;
+
+    // the handshake packets are coalesced, but never exceed the MTU
+    let datagrams = coalesced_datagrams(3);
+    assert!(datagrams.iter().any(|(packets, _)| *packets > 1));
+    assert!(
+        datagrams.iter().all(|(_, len)| *len <= mtu),
+        "{datagrams:?}"
+    );
+
+    // each packet is sent in its own datagram when coalescing is disabled
+    let datagrams = coalesced_datagrams(1);
+    assert!(
+        datagrams.iter().all(|(packets, _)| *packets == 1),
+        "{datagrams:?}"
+    );
+    assert!(
+        datagrams.iter().all(|(_, len)| *len <= mtu),
+        "{datagrams:?}"
+    );
+
+    assert!(provider::limits::Limits::new()
+        .with_max_coalesced_packets(0)
+        .is_err());
+    assert!(provider::limits::Limits::new()
+        .with_max_coalesced_packets(4)
+        .is_err());
+}
+
+/// Drops the client's connection handle with the given policy and returns the server's close
+/// error along with how long it took the server to notice the connection was gone
+fn close_on_drop(
+    close_on_drop: crate::connection::CloseOnDrop,
+) -> (crate::connection::Error, Duration) {
+    let result = Arc::new(Mutex::new(None));
+    let server_result = result.clone();
+
+    test(Model::default(), |handle| {
+        let limits = provider::limits::Limits::default()
+            .with_max_idle_timeout(Duration::from_secs(5))
+            .unwrap();
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_limits(limits)?
+            .with_event(events())?
+            .start()?;
+        let server_addr = server.local_addr()?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let start = crate::provider::io::testing::now();
+
+            let error = connection.accept_bidirectional_stream().await.unwrap_err();
+
+            let elapsed = crate::provider::io::testing::now().saturating_duration_since(start);
+            *server_result.lock().unwrap() = Some((error, elapsed));
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.set_close_on_drop(close_on_drop).unwrap();
+            drop(connection);
+
+            // keep the client endpoint open until the server has closed its side
+            delay(Duration::from_secs(10)).await;
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let result = result.lock().unwrap().take();
+    result.expect("the server connection should close")
+}
+
+#[test]
+fn close_on_drop_gracefully_test() {
+    let (error, elapsed) = close_on_drop(crate::connection::CloseOnDrop::CloseGracefully);
+
+    // the client notifies the server with a CONNECTION_CLOSE frame
+    assert!(
+        matches!(error, crate::connection::Error::Closed { initiator, .. } if initiator.is_remote()),
+        "{error:?}"
+    );
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+}
+
+#[test]
+fn close_on_drop_silent_test() {
+    let (error, elapsed) = close_on_drop(crate::connection::CloseOnDrop::Silent);
+
+    // nothing is sent by the client so the server waits for its idle timer to expire
+    assert!(
+        matches!(error, crate::connection::Error::IdleTimerExpired { .. }),
+        "{error:?}"
+    );
+    assert!(elapsed >= Duration::from_secs(4), "{elapsed:?}");
+}
+
+#[test]
+fn idle_timeout_includes_max_ack_delay_test() {
+    let idle_timeout = Duration::from_secs(30);
+    let max_ack_delay = Duration::from_secs(8);
+    let result = Arc::new(Mutex::new(None));
+    let server_result = result.clone();
+
+    test(Model::default(), |handle| {
+        // the idle timeout is larger than 3 * PTO so the `max_ack_delay` extension is observable
+        let limits = provider::limits::Limits::default()
+            .with_max_idle_timeout(idle_timeout)?
+            .with_max_ack_delay(max_ack_delay)?;
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_limits(limits)?
+            .with_event(events())?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits)?
+            .with_event(events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let start = crate::provider::io::testing::now();
+
+            let error = connection.accept_bidirectional_stream().await.unwrap_err();
+
+            let elapsed = crate::provider::io::testing::now().saturating_duration_since(start);
+            *server_result.lock().unwrap() = Some((error, elapsed));
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection
+                .set_close_on_drop(crate::connection::CloseOnDrop::Silent)
+                .unwrap();
+            drop(connection);
+
+            // keep the client endpoint open until the server has closed its side
+            delay(idle_timeout * 2).await;
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let (error, elapsed) = result.lock().unwrap().take().unwrap();
+    assert!(
+        matches!(error, crate::connection::Error::IdleTimerExpired { .. }),
+        "{error:?}"
+    );
+    // the server waits for the peer's `max_ack_delay` on top of the idle timeout
+    assert!(elapsed >= idle_timeout + max_ack_delay, "{elapsed:?}");
+}
+
+#[test]
+fn idle_timeout_max_ack_delay_test() {
+    let received = Arc::new(Mutex::new(None));
+    let server_received = received.clone();
+
+    test(Model::default(), |handle| {
+        // the peers delay their ACKs for longer than the advertised idle timeout
+        let limits = provider::limits::Limits::default()
+            .with_max_idle_timeout(Duration::from_secs(1))?
+            .with_max_ack_delay(Duration::from_secs(3))?;
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_limits(limits)?
+            .with_event(events())?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits)?
+            .with_event(events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            // nothing is sent back until the client finishes the stream, so the peer only
+            // receives delayed ACKs in the meantime
+            let mut data = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                data.extend_from_slice(&chunk);
+            }
+
+            stream.send(Bytes::from(data.clone())).await.unwrap();
+            stream.finish().unwrap();
+            *server_received.lock().unwrap() = Some(data);
+
+            // keep the connection open until the client has read the response
+            let _ = connection.accept_bidirectional_stream().await;
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+
+            // wait past the advertised idle timeout; the effective timeout is extended to
+            // cover the peer's max_ack_delay
+            delay(Duration::from_secs(5)).await;
+
+            stream.send(Bytes::from_static(b" world")).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut response = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                response.extend_from_slice(&chunk);
+            }
+            assert_eq!(response, b"hello world");
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let received = received.lock().unwrap().take();
+    assert_eq!(received.as_deref(), Some(&b"hello world"[..]));
+}
+
+#[test]
+fn stream_acked_offset_test() {
+    const CHUNK_LEN: usize = 10_000;
+    const CHUNKS: usize = 3;
+
+    let acked = Arc::new(Mutex::new(vec![]));
+    let acked_result = acked.clone();
+
+    test(Model::default(), |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // nothing has been acknowledged before sending any data
+            acked.lock().unwrap().push(stream.acked_offset().unwrap());
+
+            for _ in 0..CHUNKS {
+                stream
+                    .send(Bytes::from_static(&[42; CHUNK_LEN]))
+                    .await
+                    .unwrap();
+                // wait for the peer to acknowledge the data
+                stream.flush().await.unwrap();
+                acked.lock().unwrap().push(stream.acked_offset().unwrap());
+            }
+
+            // the offset is still reported once the stream is finished
+            stream.close().await.unwrap();
+            acked.lock().unwrap().push(stream.acked_offset().unwrap());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let total = (CHUNK_LEN * CHUNKS) as u64;
+    let expected: Vec<u64> = (0..=CHUNKS as u64)
+        .map(|chunk| chunk * CHUNK_LEN as u64)
+        .chain(Some(total))
+        .collect();
+    assert_eq!(*acked_result.lock().unwrap(), expected);
+}
+
+/// Sends `LEN` bytes to an echo server and returns how long it took for the data to be
+/// acknowledged
+fn max_send_rate_transfer(
+    limits: provider::limits::Limits,
+    max_send_rate: Option<u64>,
+) -> Duration {
+    const LEN: usize = 200_000;
+
+    let elapsed = Arc::new(Mutex::new(None));
+    let elapsed_result = elapsed.clone();
+
+    test(Model::default(), |handle| {
+        let addr = server(handle)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            if max_send_rate.is_some() {
+                connection.set_max_send_rate(max_send_rate).unwrap();
+            }
+
+            let (mut recv, mut send) = connection
+                .open_bidirectional_stream()
+                .await
+                .unwrap()
+                .split();
+
+            // drain the echoed data so the server doesn't get blocked
+            primary::spawn(async move { while let Ok(Some(_)) = recv.receive().await {} });
+
+            let start = crate::provider::io::testing::now();
+
+            let mut data = Data::new(LEN as u64);
+            while let Some(chunk) = data.send_one(usize::MAX) {
+                send.send(chunk).await.unwrap();
+            }
+            send.close().await.unwrap();
+
+            let end = crate::provider::io::testing::now();
+            *elapsed.lock().unwrap() = Some(end.saturating_duration_since(start));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let elapsed = elapsed_result.lock().unwrap().take();
+    elapsed.expect("the transfer should complete")
+}
+
+#[test]
+fn max_send_rate_test() {
+    // 100KB/s
+    const MAX_SEND_RATE: u64 = 100_000;
+
+    // the congestion window allows sending the data in a few round trips
+    let unlimited = max_send_rate_transfer(Default::default(), None);
+    assert!(unlimited < Duration::from_secs(1), "{unlimited:?}");
+
+    // sending 200KB at 100KB/s takes at least 2 seconds, which spans many round trips. The
+    // last datagram departs without waiting for its own reservation so allow for it.
+    let limited = max_send_rate_transfer(Default::default(), Some(MAX_SEND_RATE));
+    assert!(limited >= Duration::from_millis(1950), "{limited:?}");
+}
+
+#[test]
+fn max_send_rate_limits_test() {
+    // 100KB/s
+    const MAX_SEND_RATE: u64 = 100_000;
+
+    let limits = provider::limits::Limits::default()
+        .with_max_send_rate(MAX_SEND_RATE)
+        .unwrap();
+    let limited = max_send_rate_transfer(limits, None);
+    assert!(limited >= Duration::from_millis(1950), "{limited:?}");
+
+    // the burst is sent ahead of the rate, which shortens the transfer
+    let burst = max_send_rate_transfer(limits.with_max_send_burst(50_000).unwrap(), None);
+    assert!(burst < limited, "{burst:?} {limited:?}");
+    assert!(burst >= Duration::from_millis(1450), "{burst:?}");
+}
+
+#[test]
+fn connection_wait_idle_test() {
+    const LEN: usize = 100_000;
+
+    let acked = Arc::new(Mutex::new(vec![]));
+    let acked_result = acked.clone();
+
+    test(Model::default(), |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the connection is idle before any data is sent
+            connection.wait_idle().await.unwrap();
+
+            let (mut recv, mut send) = connection
+                .open_bidirectional_stream()
+                .await
+                .unwrap()
+                .split();
+
+            // drain the echoed data so the server doesn't get blocked
+            primary::spawn(async move { while let Ok(Some(_)) = recv.receive().await {} });
+
+            // the connection becomes idle again each time the new data is acknowledged
+            for round in 1..=2 {
+                send.send(Bytes::from_static(&[42; LEN])).await.unwrap();
+
+                // the data was just written so it can't have been acknowledged yet
+                let total = (round * LEN) as u64;
+                assert!(send.acked_offset().unwrap() < total);
+
+                // another task waits on a cloned handle at the same time and is also woken
+                let mut handle = connection.handle();
+                let (a, b) = futures::join!(connection.wait_idle(), handle.wait_idle());
+                a.unwrap();
+                b.unwrap();
+                acked.lock().unwrap().push(send.acked_offset().unwrap());
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(
+        *acked_result.lock().unwrap(),
+        vec![LEN as u64, 2 * LEN as u64]
+    );
+}
+
+event_recorder!(
+    EcnStateRecorder,
+    EcnStateChanged,
+    on_ecn_state_changed,
+    EcnState,
+    |event: &EcnStateChanged, storage: &mut Vec<EcnState>| {
+        storage.push(event.state.clone());
+    }
+);
+
+/// Transfers data from a client using the given ECN mode and returns the client's ECN states
+fn ecn_states(model: Model, ecn: provider::limits::EcnMode) -> Vec<EcnState> {
+    let subscriber = EcnStateRecorder::new();
+    let states = subscriber.events();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let limits = provider::limits::Limits::new().with_ecn(ecn)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((subscriber, events()))?
+            .with_limits(limits)?
+            .start()?;
+
+        // the data is echoed back, even if ECN isn't used
+        start_client(client, server_addr, Data::new(100_000))?;
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let states = states.lock().unwrap();
+    states.clone()
+}
+
+#[test]
+fn ecn_validation_test() {
+    // ECN is validated on a path which preserves the markings
+    let states = ecn_states(Model::default(), provider::limits::EcnMode::Enabled);
+    assert!(states.iter().any(|s| matches!(s, EcnState::Capable { .. })));
+    assert!(!states.iter().any(|s| matches!(s, EcnState::Failed { .. })));
+
+    // ECN is disabled on a path which bleaches the markings
+    let model = Model::default();
+    model.set_ecn_bleaching(true);
+    let states = ecn_states(model, provider::limits::EcnMode::Enabled);
+    // the bleached markings leave the peer without any ECN counts to report
+    assert!(states.iter().any(|s| matches!(
+        s,
+        EcnState::Failed {
+            reason: EcnFailureReason::CountsMissing { .. },
+            ..
+        }
+    )));
+    assert!(!states.iter().any(|s| matches!(s, EcnState::Capable { .. })));
+
+    // ECN isn't tested at all when disabled
+    let states = ecn_states(Model::default(), provider::limits::EcnMode::Disabled);
+    assert!(states.is_empty(), "{states:?}");
+}
+
+#[test]
+fn stream_inactivity_timeout_test() {
+    let inactivity_timeout = Duration::from_secs(1);
+    let error_code: crate::application::Error = 123u8.into();
+
+    test(Model::default(), |handle| {
+        let mut server = build_server(handle)?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            stream
+                .set_inactivity_timeout(inactivity_timeout, error_code)
+                .unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+            stream.send(chunk).await.unwrap();
+
+            // the peer goes silent so the stream should be closed after the timeout
+            let error = stream.receive().await.unwrap_err();
+            assert!(
+                matches!(error, crate::stream::Error::StreamReset { error, .. } if error == error_code),
+                "unexpected error: {:?}",
+                error
+            );
+
+            // the connection should still be usable
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            while let Ok(Some(chunk)) = stream.receive().await {
+                let _ = stream.send(chunk).await;
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+
+            // stay silent on the stream until the server resets it
+            let start = crate::provider::io::testing::now();
+            let error = stream.receive().await.unwrap_err();
+            let elapsed = crate::provider::io::testing::now() - start;
+            assert!(
+                matches!(error, crate::stream::Error::StreamReset { error, .. } if error == error_code),
+                "unexpected error: {:?}",
+                error
+            );
+            assert!(elapsed >= inactivity_timeout);
+            assert!(elapsed < inactivity_timeout * 3);
+
+            // the connection should stay alive after the stream was reset
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"ping")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"ping"));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn blocked_stream_inactivity_timeout_test() {
+    let inactivity_timeout = Duration::from_secs(1);
+    let error_code: crate::application::Error = 123u8.into();
+
+    test(Model::default(), |handle| {
+        // the server only allows a small amount of data and never reads it
+        let limits = provider::limits::Limits::new().with_data_window(1000)?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_limits(limits)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let _stream = connection.accept_bidirectional_stream().await.unwrap();
+            delay(Duration::from_secs(10)).await;
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from(vec![42; 5000])).await.unwrap();
+
+            // wait for the connection to be blocked on flow control before setting the timeout
+            delay(Duration::from_millis(500)).await;
+            stream
+                .set_inactivity_timeout(inactivity_timeout, error_code)
+                .unwrap();
+
+            let start = crate::provider::io::testing::now();
+            let error = stream.flush().await.unwrap_err();
+            let elapsed = crate::provider::io::testing::now() - start;
+            assert!(
+                matches!(error, crate::stream::Error::StreamReset { error, .. } if error == error_code),
+                "unexpected error: {:?}",
+                error
+            );
+            assert!(elapsed >= inactivity_timeout);
+            assert!(elapsed < inactivity_timeout * 3);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn key_update_packet_threshold_test() {
+    let threshold = 100;
+    let data_len = 1_000_000;
+
+    test(Model::default(), |handle| {
+        let addr = server(handle)?;
+
+        let limits = provider::limits::Limits::default()
+            .with_key_update_packet_threshold(threshold)
+            .unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let usage = connection.key_usage().unwrap();
+            assert_eq!(usage.generation, 0);
+            assert!(usage.packets < threshold);
+
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(data_len);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+
+            // the keys should have been updated multiple times and the usage reset with each
+            // update
+            let usage = connection.key_usage().unwrap();
+            assert!(usage.generation > 1, "{usage:?}");
+            assert!(usage.bytes < data_len, "{usage:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn partition_test() {
+    let model = Model::default();
+    let partition_duration = Duration::from_secs(2);
+
+    test(model.clone(), |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+        let client_addr = client.local_addr()?;
+
+        // the client can't reach the server until the partition is repaired
+        model.partition(client_addr.into(), server_addr.into());
+
+        spawn(async move {
+            delay(partition_duration).await;
+            model.repair(client_addr.into(), server_addr.into());
+        });
+
+        primary::spawn(async move {
+            let start = crate::provider::io::testing::now();
+
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let elapsed = crate::provider::io::testing::now().saturating_duration_since(start);
+            assert!(elapsed >= partition_duration, "{elapsed:?}");
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn active_path_migration_test() {
+    let model = Model::default();
+
+    test(model.clone(), |handle| {
+        let mut server = build_server(handle)?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+
+            let before = connection.active_path().unwrap();
+            assert_eq!(before.local_address, addr.into());
+            assert!(before.is_validated);
+            assert!(before.congestion_window > 0);
+
+            // rebind the client to a new port
+            let client_addr: SocketAddr = before.remote_address.into();
+            let rebound_addr = SocketAddr::new(client_addr.ip(), client_addr.port() + 1000);
+            model.rebind(client_addr.into(), rebound_addr.into());
+
+            stream.send(chunk).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+
+            // give the server time to validate the new path
+            delay(Duration::from_secs(1)).await;
+
+            let after = connection.active_path().unwrap();
+            assert_ne!(after.id, before.id);
+            assert_eq!(after.local_address, before.local_address);
+            assert_eq!(after.remote_address, rebound_addr.into());
+            assert!(after.is_validated);
+
+            stream.send(chunk).await.unwrap();
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let path = connection.active_path().unwrap();
+            assert_eq!(path.remote_address, addr.into());
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            for _ in 0..2 {
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                let chunk = stream.receive().await.unwrap().unwrap();
+                assert_eq!(chunk, Bytes::from_static(b"hello"));
+            }
+
+            // the client isn't aware of the rebinding on its side
+            let after = connection.active_path().unwrap();
+            assert_eq!(after.id, path.id);
+            assert_eq!(after.local_address, path.local_address);
+            assert_eq!(after.remote_address, path.remote_address);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn probe_path_test() {
+    let model = Model::default();
+
+    test(model.clone(), |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+            let before = connection.active_path().unwrap();
+            stream.send(chunk).await.unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+
+            // the client never migrated to the probed path
+            let after = connection.active_path().unwrap();
+            assert_eq!(after.local_address, before.local_address);
+            assert_eq!(after.remote_address, before.remote_address);
+
+            stream.send(chunk).await.unwrap();
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // exchange some data so the handshake is confirmed
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+
+            let before = connection.active_path().unwrap();
+
+            // give the client another address to probe from
+            let client_addr: SocketAddr = before.local_address.into();
+            let probe_addr = SocketAddr::new([192, 0, 2, 1].into(), client_addr.port());
+            model.alias(client_addr.into(), probe_addr.into());
+
+            // the IO provider can only send from the port the endpoint is bound to
+            let other_port = SocketAddr::new(probe_addr.ip(), client_addr.port() + 1000);
+            assert_eq!(
+                connection.probe_path(other_port, server_addr).await,
+                Err(crate::connection::PathError::UnsupportedLocalPort)
+            );
+
+            let probe = connection
+                .probe_path(probe_addr, server_addr)
+                .await
+                .unwrap();
+            assert_eq!(probe.local_address, probe_addr.into());
+            assert_eq!(probe.remote_address, server_addr.into());
+            assert!(probe.rtt >= model.delay() * 2);
+
+            // the active path is unchanged
+            let after = connection.active_path().unwrap();
+            assert_eq!(after.local_address, before.local_address);
+            assert_eq!(after.remote_address, before.remote_address);
+
+            // the active path can't be probed
+            assert_eq!(
+                connection.probe_path(client_addr, server_addr).await,
+                Err(crate::connection::PathError::ActivePath)
+            );
+
+            // traffic continues on the original path
+            stream.send(Bytes::from_static(b"world")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"world"));
+            assert_eq!(
+                connection.active_path().unwrap().local_address,
+                client_addr.into()
+            );
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn migrate_to_test() {
+    let model = Model::default();
+
+    test(model.clone(), |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+            let before = connection.active_path().unwrap();
+            stream.send(chunk).await.unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+
+            // the client migrated to the probed path
+            let after = connection.active_path().unwrap();
+            assert_eq!(after.local_address, before.local_address);
+            assert_ne!(after.remote_address, before.remote_address);
+
+            stream.send(chunk).await.unwrap();
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // exchange some data so the handshake is confirmed
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+
+            let client_addr: SocketAddr = connection.active_path().unwrap().local_address.into();
+            let new_addr = SocketAddr::new([192, 0, 2, 1].into(), client_addr.port());
+            model.alias(client_addr.into(), new_addr.into());
+
+            // paths need to be validated before migrating to them
+            let unvalidated = crate::connection::PathProbe {
+                local_address: new_addr.into(),
+                remote_address: server_addr.into(),
+                rtt: Duration::ZERO,
+            };
+            assert_eq!(
+                connection.migrate_to(unvalidated),
+                Err(crate::connection::PathError::UnknownPath)
+            );
+
+            let probe = connection.probe_path(new_addr, server_addr).await.unwrap();
+            connection.migrate_to(probe).unwrap();
+
+            // subsequent traffic is sent on the new path
+            stream.send(Bytes::from_static(b"world")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"world"));
+
+            let path = connection.active_path().unwrap();
+            assert_eq!(path.local_address, new_addr.into());
+            assert_eq!(path.remote_address, server_addr.into());
+            assert!(path.is_validated);
+
+            // the connection is already using the path
+            assert_eq!(
+                connection.migrate_to(probe),
+                Err(crate::connection::PathError::ActivePath)
+            );
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn set_local_address_test() {
+    let model = Model::default();
+
+    test(model.clone(), |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+            let before = connection.active_path().unwrap();
+            stream.send(chunk).await.unwrap();
+
+            // only clients can change their local address
+            assert_eq!(
+                connection.set_local_address(before.local_address.into()),
+                Err(crate::connection::PathError::MigrationNotAllowed)
+            );
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+
+            // the client sends from its new address
+            let after = connection.active_path().unwrap();
+            assert_ne!(after.id, before.id);
+            assert_eq!(after.local_address, before.local_address);
+            assert_ne!(after.remote_address, before.remote_address);
+
+            stream.send(chunk).await.unwrap();
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // exchange some data so the handshake is confirmed
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"hello"));
+
+            let before = connection.active_path().unwrap();
+            let client_addr: SocketAddr = before.local_address.into();
+            let new_addr = SocketAddr::new([192, 0, 2, 1].into(), client_addr.port());
+            model.alias(client_addr.into(), new_addr.into());
+
+            // the IO provider can only send from the port the endpoint is bound to
+            assert_eq!(
+                connection
+                    .set_local_address(SocketAddr::new(new_addr.ip(), client_addr.port() + 1000)),
+                Err(crate::connection::PathError::UnsupportedLocalPort)
+            );
+
+            // the path doesn't need to be probed first
+            connection.set_local_address(new_addr).unwrap();
+
+            stream.send(Bytes::from_static(b"world")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(chunk, Bytes::from_static(b"world"));
+
+            // the connection migrated to a new path, which was validated with a PATH_CHALLENGE
+            let after = connection.active_path().unwrap();
+            assert_ne!(after.id, before.id);
+            assert_eq!(after.local_address, new_addr.into());
+            assert_eq!(after.remote_address, server_addr.into());
+            assert!(after.is_validated);
+
+            // the connection is already sending from the address
+            assert_eq!(
+                connection.set_local_address(new_addr),
+                Err(crate::connection::PathError::ActivePath)
+            );
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn connection_memory_pressure_test() {
+    const MAX_BUFFER_BYTES: usize = 100_000;
+    const LEN: usize = 1_000_000;
+
+    let pressure = MemoryPressureRecorder::new();
+    let pressure_events = pressure.events();
+    let progress = RxStreamProgressRecorder::new();
+    let progress_events = progress.events();
+
+    test(Model::default(), |handle| {
+        let limits =
+            provider::limits::Limits::new().with_max_connection_buffer_bytes(MAX_BUFFER_BYTES)?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), (pressure, progress)))?
+            .with_limits(limits)?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            // leave the data in the receive buffers until the client is blocked
+            delay(Duration::from_secs(5)).await;
+
+            let received: usize = progress_events.lock().unwrap().iter().sum();
+            assert!(received > 0);
+            assert!(
+                received <= MAX_BUFFER_BYTES,
+                "the peer sent {received} bytes while only {MAX_BUFFER_BYTES} could be buffered"
+            );
+
+            {
+                let pressure_events = pressure_events.lock().unwrap();
+                let event = pressure_events
+                    .first()
+                    .expect("memory pressure should be signaled");
+                assert_eq!(event.max_buffered_bytes, MAX_BUFFER_BYTES);
+                assert!(event.buffered_bytes >= MAX_BUFFER_BYTES / 100 * 80);
+            }
+
+            // reading the data releases the budget so the client can finish sending
+            let mut recv_len = 0;
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                recv_len += chunk.len();
+            }
+            assert_eq!(recv_len, LEN);
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            let mut data = Data::new(LEN as u64);
+            while let Some(chunk) = data.send_one(usize::MAX) {
+                stream.send(chunk).await.unwrap();
+            }
+            stream.finish().unwrap();
+
+            // wait for the server to read all of the data
+            let _ = stream.receive().await;
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn receive_uninit_test() {
+    const LEN: usize = 100_000;
+    const SENTINEL: u8 = 0xAA;
+
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+
+            let mut data = Data::new(LEN as u64);
+            // the buffer is filled with a known value so we can check that the tail isn't touched
+            let mut buf = [MaybeUninit::new(SENTINEL); 1000];
+
+            loop {
+                let (len, is_fin) = stream.receive_uninit(&mut buf).await.unwrap();
+
+                // only the first `len` bytes were written by the stream
+                let (received, tail) = buf.split_at(len);
+                let received: Vec<u8> = received
+                    .iter()
+                    .map(|byte| unsafe { byte.assume_init() })
+                    .collect();
+                data.receive(&[received]);
+
+                for byte in tail {
+                    assert_eq!(unsafe { byte.assume_init() }, SENTINEL);
+                }
+
+                if is_fin {
+                    break;
+                }
+
+                assert!(len > 0);
+                buf = [MaybeUninit::new(SENTINEL); 1000];
+            }
+
+            assert!(data.is_finished());
+
+            // the stream will only return the final state after it's finished
+            assert_eq!(stream.receive_uninit(&mut buf).await.unwrap(), (0, true));
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            let mut data = Data::new(LEN as u64);
+            while let Some(chunk) = data.send_one(usize::MAX) {
+                stream.send(chunk).await.unwrap();
+            }
+            stream.finish().unwrap();
+
+            // wait for the server to read all of the data
+            let _ = stream.receive().await;
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+// TODO: https://github.com/aws/s2n-quic/issues/1726
+//
+// The rustls tls provider is used on windows and has different
+// build options than s2n-tls. We should build the rustls provider with
+// mTLS enabled and remove the `cfg(target_os("windows"))`.
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn mtls_happy_case() {
+    use crate::provider::event::events::HandshakeStatus;
+
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    const LEN: usize = 1000;
+
+    let server_subscriber = HandshakeStatusRecorder::new();
+    let server_events = server_subscriber.clone();
+    let client_subscriber = HandshakeStatusRecorder::new();
+    let client_events = client_subscriber.clone();
+
+    test(model, |handle| {
+        let server_tls = build_server_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_event(server_subscriber)?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        spawn(async move {
+            let mut conn = server.accept().await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+            stream.send(vec![42; LEN].into()).await.unwrap();
+            stream.flush().await.unwrap();
+        });
+
+        let client_tls = build_client_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(client_tls)?
+            .with_event(client_subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.accept_bidirectional_stream().await.unwrap().unwrap();
+
+            let mut recv_len = 0;
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                recv_len += chunk.len();
+            }
+            assert_eq!(LEN, recv_len);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_handshake_complete =
+        server_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
+    let server_handshake_confirmed =
+        server_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
+    let client_handshake_complete =
+        client_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
+    let client_handshake_confirmed =
+        client_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
+
+    // assert handshake success for both the sever and client
+    assert!(server_handshake_complete);
+    assert!(server_handshake_confirmed);
+    assert!(client_handshake_complete);
+    assert!(client_handshake_confirmed);
+}
+
+// TODO: https://github.com/aws/s2n-quic/issues/1726
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn mtls_peer_certificate_chain() {
+    let model = Model::default();
+    let server_chain = Arc::new(Mutex::new(vec![]));
+    let client_chain = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let server_tls = build_server_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        let chain = server_chain.clone();
+        spawn(async move {
+            let conn = server.accept().await.unwrap();
+            *chain.lock().unwrap() = conn.peer_certificate_chain().unwrap();
+        });
+
+        let client_tls = build_client_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(client_tls)?
+            .start()?;
+
+        let chain = client_chain.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let conn = client.connect(connect).await.unwrap();
+            *chain.lock().unwrap() = conn.peer_certificate_chain().unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // both peers authenticated with a certificate so each side observes the other's chain
+    assert!(!server_chain.lock().unwrap().is_empty());
+    assert!(!client_chain.lock().unwrap().is_empty());
+}
+
+// TODO: https://github.com/aws/s2n-quic/issues/1726
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn mtls_handshake_info() {
+    use crate::connection::{CipherSuite, HandshakeInfo};
+
+    let model = Model::default();
+    let server_info: Arc<Mutex<Option<HandshakeInfo>>> = Arc::new(Mutex::new(None));
+    let client_info: Arc<Mutex<Option<HandshakeInfo>>> = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server_tls = build_server_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        let info = server_info.clone();
+        spawn(async move {
+            let conn = server.accept().await.unwrap();
+            *info.lock().unwrap() = Some(conn.handshake_info().unwrap());
+        });
+
+        let client_tls = build_client_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(client_tls)?
+            .start()?;
+
+        let info = client_info.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let conn = client.connect(connect).await.unwrap();
+            *info.lock().unwrap() = Some(conn.handshake_info().unwrap());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_info = server_info.lock().unwrap().take().unwrap();
+    let client_info = client_info.lock().unwrap().take().unwrap();
+
+    for info in [&server_info, &client_info] {
+        assert_ne!(info.cipher_suite, CipherSuite::Unknown);
+        assert_eq!(info.server_name.as_deref(), Some("localhost"));
+        assert!(!info.application_protocol.is_empty());
+        assert!(!info.peer_certificate_chain.is_empty());
+    }
+
+    // both peers negotiated the same parameters
+    assert_eq!(server_info.cipher_suite, client_info.cipher_suite);
+    assert_eq!(
+        server_info.application_protocol,
+        client_info.application_protocol
+    );
+}
+
+// TODO: https://github.com/aws/s2n-quic/issues/1726
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn mtls_auth_failure() {
+    use crate::provider::event::events::HandshakeStatus;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let server_subscriber = HandshakeStatusRecorder::new();
+    let server_events = server_subscriber.clone();
+    let client_subscriber = HandshakeStatusRecorder::new();
+    let client_events = client_subscriber.clone();
+
+    // check that server attempts to accept but rejects a connection
+    let server_connection_closed = Arc::new(AtomicBool::new(false));
+    let server_connection_closed_clone = server_connection_closed.clone();
+
+    test(model, |handle| {
+        let server_tls = build_server_mtls_provider(certificates::UNTRUSTED_CERT_PEM)?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_event(server_subscriber)?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        spawn(async move {
+            match server.accept().await {
+                Some(_) => {
+                    panic!("connection should not be accepted on auth failure");
+                }
+                None => {
+                    assert!(
+                        !server_connection_closed_clone.swap(true, Ordering::SeqCst),
+                        "confirm that this is only called once"
+                    );
+                }
+            }
+        });
+
+        let client_tls = build_client_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(client_tls)?
+            .with_event(client_subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let stream_result = conn.accept_bidirectional_stream().await;
+
+            assert!(stream_result.is_err(), "handshake should fail");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_handshake_complete =
+        server_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
+    let server_handshake_confirmed =
+        server_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
+    let client_handshake_complete =
+        client_events.any(|x| matches!(x.status, HandshakeStatus::Complete { .. }));
+    let client_handshake_confirmed =
+        client_events.any(|x| matches!(x.status, HandshakeStatus::Confirmed { .. }));
+
+    // expect server handshake to fail
+    assert!(!server_handshake_complete);
+    assert!(!server_handshake_confirmed);
+
+    // expect the client handshake status to 'Complete' but not 'Confirmed'. We
+    // expect server's client-certificate-authentication (mTLS) to fail, which
+    // happens after client TLS handshake 'Complete'
+    assert!(client_handshake_complete);
+    assert!(!client_handshake_confirmed);
+
+    // confirm server connection was attempted but failed
+    assert!(server_connection_closed.load(Ordering::SeqCst));
+}
+
+#[cfg(not(target_os = "windows"))]
+event_recorder!(
+    ConnectionClosedRecorder,
+    provider::event::events::ConnectionClosed,
+    on_connection_closed,
+    crate::connection::Error,
+    |event: &provider::event::events::ConnectionClosed,
+     storage: &mut Vec<crate::connection::Error>| {
+        storage.push(event.error);
+    }
+);
+
+/// Rejects the client's certificate and returns the error code the client received from the server
+#[cfg(not(target_os = "windows"))]
+fn mtls_alert_error_code<F: provider::connection_close_formatter::Provider>(formatter: F) -> u64 {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let client_subscriber = ConnectionClosedRecorder::new();
+    let client_events = client_subscriber.events();
+
+    test(model, |handle| {
+        let server_tls = build_server_mtls_provider(certificates::UNTRUSTED_CERT_PEM)?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_connection_close_formatter(formatter)?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        spawn(async move {
+            assert!(server.accept().await.is_none());
+        });
+
+        let client_tls = build_client_mtls_provider(certificates::MTLS_CA_CERT)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(client_tls)?
+            .with_event(client_subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            conn.accept_bidirectional_stream().await.unwrap_err();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let client_events = client_events.lock().unwrap();
+    assert_eq!(client_events.len(), 1);
+
+    match client_events[0] {
+        crate::connection::Error::Transport {
+            code, initiator, ..
+        } => {
+            assert!(initiator.is_remote());
+            code.as_u64()
+        }
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn mtls_alert_error_code_test() {
+    use crate::provider::connection_close_formatter::{Development, MapCryptoError, Production};
+    use s2n_quic_core::{crypto::CryptoError, transport};
+
+    //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
+    //= type=test
+    //# A TLS alert is converted into a QUIC connection error.  The
+    //# AlertDescription value is added to 0x0100 to produce a QUIC error
+    //# code from the range reserved for CRYPTO_ERROR; see Section 20.1 of
+    //# [QUIC-TRANSPORT].
+    let code = mtls_alert_error_code(Development);
+    assert!((0x100..=0x1ff).contains(&code), "{code:#x}");
+
+    // the default formatter hides the alert behind handshake_failure
+    let code = mtls_alert_error_code(Production);
+    assert_eq!(code, 0x100 | CryptoError::HANDSHAKE_FAILURE.code as u64);
+
+    // the application can choose the code for specific alerts
+    let code = mtls_alert_error_code(MapCryptoError::new(Production, |_| {
+        Some(transport::Error::from(CryptoError::UNKNOWN_CA))
+    }));
+    assert_eq!(code, 0x100 | CryptoError::UNKNOWN_CA.code as u64);
 }
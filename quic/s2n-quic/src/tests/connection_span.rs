@@ -0,0 +1,125 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::events::ConnectionStarted;
+use core::fmt;
+use tracing::{
+    field::{Field, Visit},
+    span,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    Layer,
+};
+
+/// Captures the `cid` fields which are recorded in `conn` spans
+#[derive(Clone, Default)]
+struct CidLayer(Arc<Mutex<Vec<String>>>);
+
+impl<S> Layer<S> for CidLayer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if ctx.span(id).map_or(true, |span| span.name() != "conn") {
+            return;
+        }
+
+        let mut cid = None;
+        values.record(&mut CidVisitor(&mut cid));
+        if let Some(cid) = cid {
+            self.0.lock().unwrap().push(cid);
+        }
+    }
+}
+
+struct CidVisitor<'a>(&'a mut Option<String>);
+
+impl Visit for CidVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "cid" {
+            *self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+/// Records the first local connection ID of each connection
+#[derive(Clone, Default)]
+struct LocalCidRecorder(Arc<Mutex<Vec<String>>>);
+
+impl Subscriber for LocalCidRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_connection_started(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &ConnectionStarted,
+    ) {
+        let cid = format!("{:?}", event.path.local_cid);
+        self.0.lock().unwrap().push(cid);
+    }
+}
+
+#[test]
+fn connection_span_test() {
+    let span_fields = Arc::new(Mutex::new(None));
+    let recorded_cids = CidLayer::default();
+    let local_cids = LocalCidRecorder::default();
+
+    let dispatch = tracing_subscriber::registry().with(recorded_cids.clone());
+    tracing::subscriber::with_default(dispatch, || {
+        test(Model::default(), |handle| {
+            let addr = server(handle)?;
+
+            let client = Client::builder()
+                .with_io(handle.builder().build()?)?
+                .with_tls(certificates::CERT_PEM)?
+                .with_event((events(), local_cids.clone()))?
+                .start()?;
+
+            let span_fields = span_fields.clone();
+            primary::spawn(async move {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let connection = client.connect(connect).await.unwrap();
+
+                let span = connection
+                    .query_event_context(|span: &tracing::Span| span.clone())
+                    .unwrap();
+
+                let fields = span.metadata().map(|metadata| {
+                    let fields = metadata.fields();
+                    (
+                        metadata.name(),
+                        fields.field("id").is_some(),
+                        fields.field("cid").is_some(),
+                    )
+                });
+                *span_fields.lock().unwrap() = fields;
+            });
+
+            Ok(addr)
+        })
+        .unwrap();
+    });
+
+    assert_eq!(*span_fields.lock().unwrap(), Some(("conn", true, true)));
+
+    // the client's span records the connection's first local connection ID
+    let local_cids = local_cids.0.lock().unwrap();
+    assert_eq!(local_cids.len(), 1);
+    let recorded_cids = recorded_cids.0.lock().unwrap();
+    assert!(
+        recorded_cids.contains(&local_cids[0]),
+        "{recorded_cids:?} doesn't contain {local_cids:?}"
+    );
+}
@@ -0,0 +1,175 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drives a client and server with the application-driven IO provider
+//!
+//! The test doesn't use an async runtime so nothing can be spawned. Instead, the application
+//! tasks are polled with a no-op waker, the endpoints are stepped in the same loop, and the
+//! datagrams are passed between the drivers with a simulated network delay.
+
+use super::*;
+use crate::provider::io::driven::{Datagram, Driver, Provider as Io};
+use core::{
+    future::Future,
+    task::{Context, Poll},
+};
+use futures::task::noop_waker;
+use std::collections::VecDeque;
+
+/// The one-way delay of the simulated network
+const DELAY: Duration = Duration::from_millis(10);
+
+/// Passes the datagrams transmitted by `from` to the network
+fn transmit(
+    from: &Driver,
+    from_addr: SocketAddr,
+    now: Duration,
+    network: &mut VecDeque<(Duration, SocketAddr, Datagram)>,
+) -> bool {
+    let mut transmitted = false;
+
+    while let Some(mut datagram) = from.pop_transmission() {
+        let to_addr: SocketAddr = datagram.remote_address.into();
+        // the receiver sees the sender's address as the remote address
+        datagram.remote_address = from_addr.into();
+        network.push_back((now + DELAY, to_addr, datagram));
+        transmitted = true;
+    }
+
+    transmitted
+}
+
+#[test]
+fn driven_test() {
+    let server_addr: SocketAddr = "127.0.0.1:4433".parse().unwrap();
+    let client_addr: SocketAddr = "127.0.0.1:4434".parse().unwrap();
+
+    let server_io = Io::new(server_addr).unwrap();
+    let server_driver = server_io.driver();
+    let mut server = Server::builder()
+        .with_io(server_io)
+        .unwrap()
+        .with_tls(SERVER_CERTS)
+        .unwrap()
+        .with_event(events())
+        .unwrap()
+        .start()
+        .unwrap();
+    assert_eq!(server.local_addr().unwrap(), server_addr);
+
+    let client_io = Io::new(client_addr).unwrap();
+    let client_driver = client_io.driver();
+    let client = Client::builder()
+        .with_io(client_io)
+        .unwrap()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .with_event(events())
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let request = Bytes::from(vec![42; 100_000]);
+
+    let mut server_task = Some(Box::pin(async move {
+        let mut connection = server.accept().await.unwrap();
+        let mut stream = connection
+            .accept_bidirectional_stream()
+            .await
+            .unwrap()
+            .unwrap();
+
+        while let Some(chunk) = stream.receive().await.unwrap() {
+            stream.send(chunk).await.unwrap();
+        }
+        stream.close().await.unwrap();
+    }));
+
+    let client_request = request.clone();
+    let mut client_task = Some(Box::pin(async move {
+        let connect = Connect::new(server_addr).with_server_name("localhost");
+        let mut connection = client.connect(connect).await.unwrap();
+        let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+        stream.send(client_request).await.unwrap();
+        stream.finish().unwrap();
+
+        let mut response = vec![];
+        while let Some(chunk) = stream.receive().await.unwrap() {
+            response.extend_from_slice(&chunk);
+        }
+        response
+    }));
+
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut network = VecDeque::new();
+    let mut now = Duration::ZERO;
+    let mut response = None;
+
+    for _ in 0..1_000_000 {
+        // the tasks are dropped once they complete, which lets the endpoints shut down
+        if let Some(task) = server_task.as_mut() {
+            if task.as_mut().poll(&mut cx).is_ready() {
+                server_task = None;
+            }
+        }
+        if let Some(task) = client_task.as_mut() {
+            if let Poll::Ready(value) = task.as_mut().poll(&mut cx) {
+                response = Some(value);
+                client_task = None;
+            }
+        }
+
+        if client_driver.is_closed() && server_driver.is_closed() {
+            break;
+        }
+
+        let client_timeout = client_driver.step(now);
+        let server_timeout = server_driver.step(now);
+
+        let mut progress = transmit(&client_driver, client_addr, now, &mut network);
+        progress |= transmit(&server_driver, server_addr, now, &mut network);
+
+        // deliver the datagrams which have arrived
+        while let Some((arrival, ..)) = network.front() {
+            if *arrival > now {
+                break;
+            }
+
+            let (_, to_addr, datagram) = network.pop_front().unwrap();
+            if to_addr == server_addr {
+                server_driver.receive(datagram);
+            } else {
+                client_driver.receive(datagram);
+            }
+            progress = true;
+        }
+
+        if progress {
+            continue;
+        }
+
+        // nothing happened so advance the clock to the next event
+        let next = [
+            client_timeout,
+            server_timeout,
+            network.front().map(|(arrival, ..)| *arrival),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+
+        if let Some(next) = next {
+            // make sure the clock always moves forward
+            now = next.max(now + Duration::from_micros(1));
+        }
+    }
+
+    assert!(client_driver.is_closed(), "the client should shut down");
+    assert!(server_driver.is_closed(), "the server should shut down");
+    assert_eq!(response.expect("the client should complete"), request);
+
+    // the handshake and transfer took a few round trips of simulated time
+    assert!(now >= DELAY * 4, "{now:?}");
+}
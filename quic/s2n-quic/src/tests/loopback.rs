@@ -0,0 +1,118 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Connects a client and server with the in-memory loopback IO provider
+
+use super::*;
+use crate::provider::io::loopback::{Network, Provider as Io};
+
+#[tokio::test]
+async fn loopback_test() {
+    let network = Network::default();
+
+    let mut server = Server::builder()
+        .with_io(Io::new(&network).unwrap())
+        .unwrap()
+        .with_tls(SERVER_CERTS)
+        .unwrap()
+        .with_event(events())
+        .unwrap()
+        .start()
+        .unwrap();
+    let server_addr = server.local_addr().unwrap();
+
+    let client = Client::builder()
+        .with_io(
+            network
+                .builder()
+                .with_max_mtu(1400)
+                .unwrap()
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .with_event(events())
+        .unwrap()
+        .start()
+        .unwrap();
+    let client_addr = client.local_addr().unwrap();
+
+    // each endpoint is assigned its own address on the network
+    assert_ne!(client_addr, server_addr);
+
+    tokio::spawn(async move {
+        let mut connection = server.accept().await.unwrap();
+        let mut stream = connection
+            .accept_bidirectional_stream()
+            .await
+            .unwrap()
+            .unwrap();
+
+        while let Some(chunk) = stream.receive().await.unwrap() {
+            stream.send(chunk).await.unwrap();
+        }
+        stream.close().await.unwrap();
+    });
+
+    let request = Bytes::from(vec![42; 100_000]);
+
+    let connect = Connect::new(server_addr).with_server_name("localhost");
+    let mut connection = client.connect(connect).await.unwrap();
+    assert_eq!(
+        connection.remote_addr().unwrap(),
+        server_addr,
+        "the server is reached through its address on the network"
+    );
+
+    let mut stream = connection.open_bidirectional_stream().await.unwrap();
+    stream.send(request.clone()).await.unwrap();
+    stream.finish().unwrap();
+
+    let mut response = vec![];
+    while let Some(chunk) = stream.receive().await.unwrap() {
+        response.extend_from_slice(&chunk);
+    }
+
+    assert_eq!(response, request);
+}
+
+#[tokio::test]
+async fn loopback_address_in_use_test() {
+    let network = Network::default();
+    let server_addr: SocketAddr = "127.0.0.1:4433".parse().unwrap();
+
+    let _server = Server::builder()
+        .with_io(
+            network
+                .builder()
+                .with_address(server_addr)
+                .unwrap()
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+        .with_tls(SERVER_CERTS)
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let result = Server::builder()
+        .with_io(
+            network
+                .builder()
+                .with_address(server_addr)
+                .unwrap()
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+        .with_tls(SERVER_CERTS)
+        .unwrap()
+        .start();
+    assert!(
+        result.is_err(),
+        "the address is already taken by the first server"
+    );
+}
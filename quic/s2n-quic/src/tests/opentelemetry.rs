@@ -0,0 +1,59 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::opentelemetry;
+use ::opentelemetry::metrics::MeterProvider as _;
+use opentelemetry_sdk::{
+    metrics::{data, MeterProvider, PeriodicReader},
+    runtime,
+    testing::metrics::InMemoryMetricsExporter,
+};
+
+#[test]
+fn handshake_duration_test() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let exporter = InMemoryMetricsExporter::default();
+    let provider = {
+        // the reader exports the metrics from a task on the tokio runtime
+        let _guard = rt.enter();
+        let reader = PeriodicReader::builder(exporter.clone(), runtime::Tokio).build();
+        MeterProvider::builder().with_reader(reader).build()
+    };
+    let meter = provider.meter("s2n-quic");
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), opentelemetry::Subscriber::new(&meter)))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((events(), opentelemetry::Subscriber::new(&meter)))?
+            .start()?;
+        start_client(client, addr, Data::new(1000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    provider.force_flush().unwrap();
+
+    let metrics = exporter.get_finished_metrics().unwrap();
+    let samples: u64 = metrics
+        .iter()
+        .flat_map(|resource| &resource.scope_metrics)
+        .flat_map(|scope| &scope.metrics)
+        .filter(|metric| metric.name == "s2n_quic.handshake.duration")
+        .filter_map(|metric| metric.data.as_any().downcast_ref::<data::Histogram<f64>>())
+        .flat_map(|histogram| &histogram.data_points)
+        .map(|point| point.count)
+        .sum();
+
+    // both the client and the server record the duration of their handshake
+    assert!(samples >= 1, "{metrics:?}");
+}
@@ -0,0 +1,131 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::packet_interceptor::pcap::Pcap;
+use std::io;
+
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns the type of each block in the capture
+fn block_types(capture: &[u8]) -> Vec<u32> {
+    let mut types = vec![];
+    let mut remaining = capture;
+
+    while !remaining.is_empty() {
+        let block_type = u32::from_le_bytes(remaining[0..4].try_into().unwrap());
+        let len = u32::from_le_bytes(remaining[4..8].try_into().unwrap()) as usize;
+        assert_eq!(len % 4, 0);
+        // the length is repeated at the end of the block
+        assert_eq!(remaining[len - 4..len], remaining[4..8]);
+        types.push(block_type);
+        remaining = &remaining[len..];
+    }
+
+    types
+}
+
+#[test]
+fn capture_test() {
+    let capture = Capture::default();
+    let pcap = Pcap::new(Box::new(capture.clone())).unwrap();
+    pcap.add_secrets("CLIENT_RANDOM 00 00\n").unwrap();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_packet_interceptor(pcap.clone())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .with_packet_interceptor(pcap.clone())?
+            .start()?;
+        start_client(client, addr, Data::new(1000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    pcap.flush().unwrap();
+
+    let capture = capture.0.lock().unwrap();
+    let types = block_types(&capture);
+
+    // section header, interface description and the decryption secrets
+    assert_eq!(types[..3], [0x0A0D_0D0A, 0x1, 0xA]);
+    // enhanced packet blocks for the datagrams
+    assert!(types[3..].len() > 2);
+    assert!(types[3..].iter().all(|block_type| *block_type == 0x6));
+}
+
+#[test]
+fn wall_clock_test() {
+    #[derive(Clone, Copy, Debug)]
+    struct FixedClock(std::time::SystemTime);
+
+    impl s2n_quic_core::time::WallClock for FixedClock {
+        fn now(&self) -> std::time::SystemTime {
+            self.0
+        }
+    }
+
+    let start = std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    let capture = Capture::default();
+    let pcap = Pcap::new(Box::new(capture.clone())).unwrap();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(events())?
+            .with_packet_interceptor(pcap.clone())?
+            .with_wall_clock(FixedClock(start))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .start()?;
+        start_client(client, addr, Data::new(1000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    pcap.flush().unwrap();
+
+    let capture = capture.0.lock().unwrap();
+    // skip the section header and interface description blocks
+    let mut remaining = &capture[..];
+    for _ in 0..2 {
+        let len = u32::from_le_bytes(remaining[4..8].try_into().unwrap()) as usize;
+        remaining = &remaining[len..];
+    }
+
+    // the first enhanced packet block is stamped with the endpoint's wall clock
+    assert_eq!(u32::from_le_bytes(remaining[0..4].try_into().unwrap()), 0x6);
+    let high = u32::from_le_bytes(remaining[12..16].try_into().unwrap()) as u64;
+    let low = u32::from_le_bytes(remaining[16..20].try_into().unwrap()) as u64;
+    let time = Duration::from_micros(high << 32 | low);
+    assert_eq!(time, Duration::from_secs(1_000_000));
+}
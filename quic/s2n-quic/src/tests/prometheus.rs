@@ -0,0 +1,61 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::prometheus;
+
+#[test]
+fn metrics_test() {
+    let metrics = prometheus::Subscriber::new().unwrap();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), metrics.clone()))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((events(), metrics.clone()))?
+            .start()?;
+        start_client(client, addr, Data::new(1000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let families = metrics.gather();
+    let family = |name: &str| {
+        families
+            .iter()
+            .find(|family| family.get_name() == name)
+            .unwrap_or_else(|| panic!("missing {name}"))
+    };
+
+    // both the client and the server record the duration of their handshake
+    let handshakes = family("s2n_quic_handshake_duration_seconds");
+    assert_eq!(handshakes.get_metric().len(), 2);
+    for metric in handshakes.get_metric() {
+        assert_eq!(metric.get_histogram().get_sample_count(), 1);
+    }
+
+    let packets: f64 = family("s2n_quic_packets_sent_total")
+        .get_metric()
+        .iter()
+        .map(|metric| metric.get_counter().get_value())
+        .sum();
+    assert!(packets > 0.0);
+
+    // the RTT is only recorded when an ACK produces a new sample, rather than for every
+    // transmission
+    let rtt = family("s2n_quic_rtt_seconds");
+    assert_eq!(rtt.get_metric().len(), 2);
+    for metric in rtt.get_metric() {
+        let samples = metric.get_histogram().get_sample_count();
+        assert!(samples > 0);
+        assert!((samples as f64) < packets);
+    }
+}
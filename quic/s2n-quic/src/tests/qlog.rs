@@ -0,0 +1,184 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::qlog;
+use std::{io, time::Instant};
+
+type Traces = Arc<Mutex<Vec<(String, Arc<Mutex<Vec<u8>>>)>>>;
+
+#[derive(Clone)]
+struct Trace(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Trace {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn provider(traces: &Traces) -> qlog::Provider {
+    let traces = traces.clone();
+    qlog::Provider::with_output(move |name| {
+        let trace = Trace(Default::default());
+        traces
+            .lock()
+            .unwrap()
+            .push((name.to_string(), trace.0.clone()));
+        Ok(Box::new(trace) as qlog::Output)
+    })
+}
+
+#[test]
+fn trace_test() {
+    let traces = Traces::default();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), provider(&traces)))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((events(), provider(&traces)))?
+            .start()?;
+        start_client(client, addr, Data::new(1000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // the traces are written by a background thread which drops each output once it's done
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while traces
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|(_, trace)| Arc::strong_count(trace) > 1)
+    {
+        assert!(Instant::now() < deadline, "the traces were not closed");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    let traces = traces.lock().unwrap();
+    assert!(traces
+        .iter()
+        .any(|(name, _)| name.ends_with("_client.sqlog")));
+    assert!(traces
+        .iter()
+        .any(|(name, _)| name.ends_with("_server.sqlog")));
+
+    for (name, trace) in traces.iter() {
+        let trace = trace.lock().unwrap();
+        let trace = core::str::from_utf8(&trace).unwrap();
+
+        // each record is prefixed with a record separator and terminated with a newline
+        let records: Vec<serde_json::Value> = trace
+            .split_terminator('\n')
+            .map(|record| {
+                let record = record
+                    .strip_prefix('\x1e')
+                    .unwrap_or_else(|| panic!("{name} has a record without a separator"));
+                serde_json::from_str(record)
+                    .unwrap_or_else(|err| panic!("{name} has an invalid record {record:?}: {err}"))
+            })
+            .collect();
+
+        assert_eq!(records[0]["qlog_format"], "JSON-SEQ", "{name}");
+        assert!(records[0]["trace"]["common_fields"]["group_id"].is_string());
+
+        let events: Vec<_> = records[1..]
+            .iter()
+            .map(|record| {
+                assert!(record["time"].is_number(), "{name}");
+                assert!(record["data"].is_object(), "{name}");
+                record["name"].as_str().unwrap()
+            })
+            .collect();
+
+        assert_eq!(events[0], "connectivity:connection_started", "{name}");
+        for event in [
+            "transport:packet_sent",
+            "transport:packet_received",
+            "recovery:metrics_updated",
+            "connectivity:connection_state_updated",
+        ] {
+            assert!(events.contains(&event), "{name} is missing {event}");
+        }
+    }
+}
+
+#[test]
+fn dropped_records_test() {
+    let traces = Traces::default();
+    let (release, blocked) = std::sync::mpsc::channel::<()>();
+    let blocked = Mutex::new(blocked);
+
+    // block the background thread until the connections are done so the channel fills up
+    let provider = {
+        let traces = traces.clone();
+        qlog::Provider::with_output(move |name| {
+            let _ = blocked.lock().unwrap().recv();
+            let trace = Trace(Default::default());
+            traces
+                .lock()
+                .unwrap()
+                .push((name.to_string(), trace.0.clone()));
+            Ok(Box::new(trace) as qlog::Output)
+        })
+    };
+    let dropped = provider.dropped_records();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), provider))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(events())?
+            .start()?;
+        start_client(client, addr, Data::new(10_000_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert!(dropped.count() > 0);
+    drop(release);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while traces.lock().unwrap().is_empty()
+        || traces
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(_, trace)| Arc::strong_count(trace) > 1)
+    {
+        assert!(Instant::now() < deadline, "the traces were not closed");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    // the records which were queued are still written as a valid trace
+    let traces = traces.lock().unwrap();
+    let (name, trace) = &traces[0];
+    let trace = trace.lock().unwrap();
+    let trace = core::str::from_utf8(&trace).unwrap();
+    for record in trace.split_terminator('\n') {
+        let record = record.strip_prefix('\x1e').unwrap();
+        let _: serde_json::Value = serde_json::from_str(record)
+            .unwrap_or_else(|err| panic!("{name} has an invalid record {record:?}: {err}"));
+    }
+}
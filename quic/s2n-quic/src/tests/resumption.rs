@@ -0,0 +1,173 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests TLS session resumption with session tickets issued over a QUIC connection
+
+use super::*;
+use crate::provider::{
+    io::testing::{Handle, Result},
+    tls::default::{self as tls, s2n_tls::callbacks::VerifyHostNameCallback},
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the number of times the server certificate is verified
+///
+/// Resumed sessions don't verify the certificate so a count of 0 means the session was resumed.
+#[derive(Clone, Default)]
+struct CountingVerifier {
+    count: Arc<AtomicUsize>,
+}
+
+impl VerifyHostNameCallback for CountingVerifier {
+    fn verify_host_name(&self, host_name: &str) -> bool {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        host_name == "localhost"
+    }
+}
+
+impl CountingVerifier {
+    fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+fn build_server(handle: &Handle) -> Result<Server> {
+    let tls = tls::Server::builder()
+        .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+        .with_session_ticket_key(b"key", &[42; 32])?
+        .build()?;
+
+    let server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(tls)?
+        .with_event(events())?
+        .start()?;
+
+    Ok(server)
+}
+
+/// Exchanges data with the server and waits for it to issue a session ticket
+async fn exchange_data(connection: &mut crate::Connection) {
+    let mut stream = connection.open_bidirectional_stream().await.unwrap();
+    stream.send(Bytes::from_static(b"hello")).await.unwrap();
+    stream.finish().unwrap();
+    while stream.receive().await.unwrap().is_some() {}
+}
+
+#[test]
+fn resumption_state_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let first_verifier = CountingVerifier::default();
+    let second_verifier = CountingVerifier::default();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let server_addr = start_server(server)?;
+
+        let tls = tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_verify_host_name_callback(first_verifier.clone())?
+            .with_session_tickets(true)?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(tls)?
+            .with_event(events())?
+            .start()?;
+
+        let handle = handle.clone();
+        let second_verifier = second_verifier.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            exchange_data(&mut connection).await;
+
+            // the ticket is sent after the handshake so it may arrive after the stream data
+            let state = loop {
+                if let Some(state) = connection.resumption_state().unwrap() {
+                    break state;
+                }
+                delay(Duration::from_millis(10)).await;
+            };
+            drop(connection);
+
+            // load the exported state into a new client and resume the session
+            let tls = tls::Client::builder()
+                .with_certificate(certificates::CERT_PEM)
+                .unwrap()
+                .with_verify_host_name_callback(second_verifier)
+                .unwrap()
+                .with_resumption_state(&state)
+                .unwrap()
+                .build()
+                .unwrap();
+            let client = Client::builder()
+                .with_io(handle.builder().build().unwrap())
+                .unwrap()
+                .with_tls(tls)
+                .unwrap()
+                .with_event(events())
+                .unwrap()
+                .start()
+                .unwrap();
+
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            exchange_data(&mut connection).await;
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    assert_eq!(first_verifier.count(), 1);
+    assert_eq!(second_verifier.count(), 0, "the session should be resumed");
+}
+
+#[test]
+fn session_cache_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let verifier = CountingVerifier::default();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let server_addr = start_server(server)?;
+
+        let tls = tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_verify_host_name_callback(verifier.clone())?
+            .with_session_cache(true)?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(tls)?
+            .with_event(events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            exchange_data(&mut connection).await;
+
+            // the ticket is cached at the same time it becomes available for export
+            while connection.resumption_state().unwrap().is_none() {
+                delay(Duration::from_millis(10)).await;
+            }
+            drop(connection);
+
+            // reconnecting to the same server name should resume with the cached ticket
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            exchange_data(&mut connection).await;
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    assert_eq!(verifier.count(), 1, "the second session should be resumed");
+}
@@ -0,0 +1,85 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Connects clients to a server which runs an endpoint per SO_REUSEPORT socket
+
+use super::*;
+
+#[tokio::test]
+async fn workers_test() {
+    let mut server = Server::start_workers("127.0.0.1:0".parse().unwrap(), 3, |worker| {
+        worker
+            .builder()?
+            .with_tls(SERVER_CERTS)
+            .unwrap()
+            .with_event(events())
+            .unwrap()
+            .start()
+    })
+    .unwrap();
+    let server_addr = server.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            tokio::spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    while let Some(chunk) = stream.receive().await.unwrap() {
+                        stream.send(chunk).await.unwrap();
+                    }
+                    stream.close().await.unwrap();
+                }
+            });
+        }
+    });
+
+    let client = Client::builder()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .with_event(events())
+        .unwrap()
+        .start()
+        .unwrap();
+
+    // each connection is steered to the worker which owns its connection IDs
+    for _ in 0..10 {
+        let connect = Connect::new(server_addr).with_server_name("localhost");
+        let mut connection = client.connect(connect).await.unwrap();
+
+        let request = Bytes::from(vec![42; 10_000]);
+        let mut stream = connection.open_bidirectional_stream().await.unwrap();
+        stream.send(request.clone()).await.unwrap();
+        stream.finish().unwrap();
+
+        let mut response = vec![];
+        while let Some(chunk) = stream.receive().await.unwrap() {
+            response.extend_from_slice(&chunk);
+        }
+
+        assert_eq!(response, request);
+    }
+}
+
+#[tokio::test]
+async fn workers_io_mismatch_test() {
+    let result = Server::start_workers("127.0.0.1:0".parse().unwrap(), 2, |_worker| {
+        Server::builder()
+            .with_io("127.0.0.1:0")
+            .unwrap()
+            .with_tls(SERVER_CERTS)
+            .unwrap()
+            .start()
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn workers_invalid_test() {
+    let result = Server::start_workers("127.0.0.1:0".parse().unwrap(), 0, |worker| {
+        worker.builder()?.start()
+    });
+
+    assert!(result.is_err());
+}
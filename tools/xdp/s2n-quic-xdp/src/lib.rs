@@ -25,6 +25,9 @@ pub mod if_xdp;
 pub mod io;
 /// Helpers for creating mmap'd regions
 pub mod mmap;
+/// Helpers for loading the default BPF program and maintaining its maps
+#[cfg(feature = "program")]
+pub mod program;
 /// Structures for tracking ring cursors and synchronizing with the kernel
 pub mod ring;
 /// Structure for opening and reference counting an AF-XDP socket
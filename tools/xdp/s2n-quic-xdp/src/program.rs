@@ -0,0 +1,484 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{bpf, socket, Result};
+use aya::{
+    programs::{ProgramFd, Xdp},
+    Bpf,
+};
+use core::{fmt, mem::size_of};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    os::unix::io::{AsRawFd, RawFd},
+};
+
+pub use aya::programs::XdpFlags;
+
+/// A loaded instance of the default BPF program which directs QUIC traffic to AF_XDP sockets
+///
+/// The program only redirects packets which are destined for one of the ports added with
+/// [`Program::add_port`] and were received on a queue which has a socket set with
+/// [`Program::set_socket`]. All other packets are passed on to the kernel.
+///
+/// The program is detached from the interface when this value is dropped.
+pub struct Program {
+    object: Bpf,
+    xsk_map: socket::Fd,
+    port_map: socket::Fd,
+    ports: BTreeSet<u16>,
+    sockets: BTreeMap<u32, socket::Fd>,
+}
+
+impl fmt::Debug for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Program")
+            .field("xsk_map", &self.xsk_map)
+            .field("port_map", &self.port_map)
+            .field("ports", &self.ports)
+            .field("sockets", &self.sockets)
+            .finish()
+    }
+}
+
+impl Program {
+    /// Loads the default program into the kernel
+    ///
+    /// This call requires `CAP_SYS_ADMIN` or `CAP_BPF` capabilities to succeed.
+    pub fn load() -> Result<Self> {
+        Self::load_bytes(bpf::DEFAULT_PROGRAM)
+    }
+
+    /// Loads the default program with tracing enabled into the kernel
+    pub fn load_with_trace() -> Result<Self> {
+        Self::load_bytes(bpf::DEFAULT_PROGRAM_TRACE)
+    }
+
+    fn load_bytes(program: &[u8]) -> Result<Self> {
+        let mut object = Bpf::load(program).map_err(bpf_error)?;
+
+        let program = xdp(&mut object)?;
+        program.load().map_err(bpf_error)?;
+
+        let program_fd = program
+            .fd()
+            .ok_or_else(|| other("the XDP program was not loaded"))?;
+
+        // aya doesn't support XSKMAP so the maps are updated through their fds
+        let xsk_map = map_fd(program_fd, bpf::XSK_MAP_NAME)?;
+        let port_map = map_fd(program_fd, bpf::PORT_MAP_NAME)?;
+
+        Ok(Self {
+            object,
+            xsk_map,
+            port_map,
+            ports: BTreeSet::new(),
+            sockets: BTreeMap::new(),
+        })
+    }
+
+    /// Attaches the program to the interface with the given name
+    ///
+    /// Drivers without native XDP support require [`XdpFlags::SKB_MODE`].
+    pub fn attach(&mut self, interface: &str, flags: XdpFlags) -> Result<()> {
+        xdp(&mut self.object)?
+            .attach(interface, flags)
+            .map_err(other)?;
+        Ok(())
+    }
+
+    /// Directs the UDP packets destined for `port` to the AF_XDP sockets
+    pub fn add_port(&mut self, port: u16) -> Result<()> {
+        // the program only checks that the port is present so the value isn't used
+        map_update(&self.port_map, &port, &1u8)?;
+        self.ports.insert(port);
+        Ok(())
+    }
+
+    /// Stops directing the UDP packets destined for `port` to the AF_XDP sockets
+    pub fn remove_port(&mut self, port: u16) -> Result<()> {
+        if self.ports.remove(&port) {
+            map_delete(&self.port_map, &port)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the ports which are currently in the map
+    pub fn ports(&self) -> impl Iterator<Item = u16> + '_ {
+        self.ports.iter().copied()
+    }
+
+    /// Directs the packets received on `queue_id` to the given socket
+    ///
+    /// The socket should be bound to the same queue of the interface the program is attached to.
+    /// Any socket which was previously set for the queue is replaced.
+    pub fn set_socket(&mut self, queue_id: u32, socket: &socket::Fd) -> Result<()> {
+        let fd = socket.as_raw_fd() as u32;
+        map_update(&self.xsk_map, &queue_id, &fd)?;
+        self.sockets.insert(queue_id, socket.clone());
+        Ok(())
+    }
+
+    /// Stops directing the packets received on `queue_id` to a socket
+    pub fn remove_socket(&mut self, queue_id: u32) -> Result<()> {
+        if self.sockets.remove(&queue_id).is_some() {
+            map_delete(&self.xsk_map, &queue_id)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces all of the sockets in the map with the given queue ids and sockets
+    ///
+    /// This can be used to keep the map consistent with the sockets of a provider after it has
+    /// been reconfigured.
+    pub fn set_sockets<'a, I>(&mut self, sockets: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (u32, &'a socket::Fd)>,
+    {
+        let mut stale = core::mem::take(&mut self.sockets);
+
+        for (queue_id, socket) in sockets {
+            stale.remove(&queue_id);
+            self.set_socket(queue_id, socket)?;
+        }
+
+        for queue_id in stale.into_keys() {
+            map_delete(&self.xsk_map, &queue_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the sockets which are currently in the map, keyed by queue id
+    pub fn sockets(&self) -> impl Iterator<Item = (u32, &socket::Fd)> {
+        self.sockets
+            .iter()
+            .map(|(queue_id, socket)| (*queue_id, socket))
+    }
+}
+
+#[inline]
+fn xdp(object: &mut Bpf) -> Result<&mut Xdp> {
+    object
+        .program_mut(bpf::PROGRAM_NAME)
+        .ok_or_else(|| other("missing XDP program"))?
+        .try_into()
+        .map_err(other)
+}
+
+#[inline]
+fn other<E: fmt::Display>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error.to_string())
+}
+
+/// Converts an aya error into the OS error which caused it, if any
+///
+/// This allows callers to tell a missing capability (`EPERM`) apart from other failures.
+fn bpf_error<E: std::error::Error>(error: E) -> io::Error {
+    let mut source = error.source();
+    while let Some(cause) = source {
+        if let Some(code) = cause
+            .downcast_ref::<io::Error>()
+            .and_then(io::Error::raw_os_error)
+        {
+            return io::Error::from_raw_os_error(code);
+        }
+        source = cause.source();
+    }
+    other(error)
+}
+
+// aya doesn't expose a typed XSKMAP so the `bpf` syscall is called directly.
+//
+// See https://github.com/torvalds/linux/blob/v6.2/include/uapi/linux/bpf.h
+
+#[cfg(test)]
+const BPF_MAP_LOOKUP_ELEM: libc::c_long = 1;
+const BPF_MAP_UPDATE_ELEM: libc::c_long = 2;
+const BPF_MAP_DELETE_ELEM: libc::c_long = 3;
+const BPF_MAP_GET_FD_BY_ID: libc::c_long = 14;
+const BPF_OBJ_GET_INFO_BY_FD: libc::c_long = 15;
+
+/// The maximum length of an object name, including the nul terminator
+const BPF_OBJ_NAME_LEN: usize = 16;
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)] // the layout is shared with the kernel
+struct MapElemAttr {
+    map_fd: u32,
+    _pad: u32,
+    key: u64,
+    value: u64,
+    flags: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)] // the layout is shared with the kernel
+struct GetFdByIdAttr {
+    id: u32,
+    next_id: u32,
+    open_flags: u32,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)] // the layout is shared with the kernel
+struct InfoByFdAttr {
+    bpf_fd: u32,
+    info_len: u32,
+    info: u64,
+}
+
+/// The prefix of `struct bpf_prog_info` up to the map ids
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)] // the layout is shared with the kernel
+struct ProgInfo {
+    ty: u32,
+    id: u32,
+    tag: [u8; 8],
+    jited_prog_len: u32,
+    xlated_prog_len: u32,
+    jited_prog_insns: u64,
+    xlated_prog_insns: u64,
+    load_time: u64,
+    created_by_uid: u32,
+    nr_map_ids: u32,
+    map_ids: u64,
+}
+
+/// The prefix of `struct bpf_map_info` up to the name
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)] // the layout is shared with the kernel
+struct MapInfo {
+    ty: u32,
+    id: u32,
+    key_size: u32,
+    value_size: u32,
+    max_entries: u32,
+    map_flags: u32,
+    name: [u8; BPF_OBJ_NAME_LEN],
+}
+
+#[inline]
+fn sys_bpf<T>(cmd: libc::c_long, attr: &mut T) -> Result<libc::c_long> {
+    let res = unsafe {
+        // Safety: the attribute layouts match the kernel definitions
+        libc::syscall(
+            libc::SYS_bpf,
+            cmd,
+            attr as *mut T as *mut libc::c_void,
+            size_of::<T>() as libc::c_uint,
+        )
+    };
+
+    if res < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(res)
+    }
+}
+
+#[inline]
+fn obj_info<T>(fd: RawFd, info: &mut T) -> Result<()> {
+    let mut attr = InfoByFdAttr {
+        bpf_fd: fd as _,
+        info_len: size_of::<T>() as _,
+        info: info as *mut T as u64,
+    };
+    sys_bpf(BPF_OBJ_GET_INFO_BY_FD, &mut attr)?;
+    Ok(())
+}
+
+/// Opens the map with the given name which is used by the program
+fn map_fd(program_fd: RawFd, name: &str) -> Result<socket::Fd> {
+    // query the number of maps first
+    let mut info = ProgInfo::default();
+    obj_info(program_fd, &mut info)?;
+
+    let mut map_ids = vec![0u32; info.nr_map_ids as usize];
+    let mut info = ProgInfo {
+        nr_map_ids: map_ids.len() as _,
+        map_ids: map_ids.as_mut_ptr() as u64,
+        ..Default::default()
+    };
+    obj_info(program_fd, &mut info)?;
+    map_ids.truncate(info.nr_map_ids as usize);
+
+    // the kernel truncates the names to fit the nul terminator
+    let name = name.as_bytes();
+    let name = &name[..name.len().min(BPF_OBJ_NAME_LEN - 1)];
+
+    for id in map_ids {
+        let mut attr = GetFdByIdAttr {
+            id,
+            ..Default::default()
+        };
+        let fd = sys_bpf(BPF_MAP_GET_FD_BY_ID, &mut attr)? as RawFd;
+        // close the map on drop if it's not the one we're looking for
+        let fd = socket::Fd::from_raw(fd);
+
+        let mut info = MapInfo::default();
+        obj_info(fd.as_raw_fd(), &mut info)?;
+
+        let len = info
+            .name
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(BPF_OBJ_NAME_LEN);
+
+        if &info.name[..len] == name {
+            return Ok(fd);
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("missing BPF map {:?}", String::from_utf8_lossy(name)),
+    ))
+}
+
+#[inline]
+fn map_update<K, V>(map: &socket::Fd, key: &K, value: &V) -> Result<()> {
+    let mut attr = MapElemAttr {
+        map_fd: map.as_raw_fd() as _,
+        key: key as *const K as u64,
+        value: value as *const V as u64,
+        // BPF_ANY
+        flags: 0,
+        ..Default::default()
+    };
+    sys_bpf(BPF_MAP_UPDATE_ELEM, &mut attr)?;
+    Ok(())
+}
+
+#[inline]
+fn map_delete<K>(map: &socket::Fd, key: &K) -> Result<()> {
+    let mut attr = MapElemAttr {
+        map_fd: map.as_raw_fd() as _,
+        key: key as *const K as u64,
+        ..Default::default()
+    };
+
+    match sys_bpf(BPF_MAP_DELETE_ELEM, &mut attr) {
+        Ok(_) => Ok(()),
+        // the entry was already removed
+        Err(err) if err.raw_os_error() == Some(libc::ENOENT) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+fn map_lookup<K, V: Default>(map: &socket::Fd, key: &K) -> Result<Option<V>> {
+    let mut value = V::default();
+    let mut attr = MapElemAttr {
+        map_fd: map.as_raw_fd() as _,
+        key: key as *const K as u64,
+        value: &mut value as *mut V as u64,
+        ..Default::default()
+    };
+
+    match sys_bpf(BPF_MAP_LOOKUP_ELEM, &mut attr) {
+        Ok(_) => Ok(Some(value)),
+        Err(err) if err.raw_os_error() == Some(libc::ENOENT) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Loads the program if the test has the capabilities to do so
+    fn load(test: &str) -> Option<Program> {
+        // This call requires `CAP_BPF`. If the test doesn't have this set, then log and skip
+        // the test. Any other error is a failure.
+        match Program::load() {
+            Ok(program) => Some(program),
+            Err(err) if err.raw_os_error() == Some(libc::EPERM) => {
+                assert!(
+                    std::env::var("CAP_BPF_ENABLED").is_err(),
+                    "expected the program test to be executed"
+                );
+
+                use std::io::Write;
+                let _ = writeln!(
+                    std::io::stdout(),
+                    "WARNING: CAP_BPF capabilities missing; skipping `{test}`",
+                );
+
+                None
+            }
+            Err(err) => panic!("could not load the program: {err}"),
+        }
+    }
+
+    /// Opens a socket with the rings and UMEM the kernel requires for it to be put in an XSKMAP
+    fn open_socket(umem: &crate::umem::Umem) -> socket::Fd {
+        let socket = socket::Fd::open().unwrap();
+        socket.attach_umem(umem).unwrap();
+        crate::syscall::set_fill_ring_size(&socket, 32).unwrap();
+        crate::syscall::set_completion_ring_size(&socket, 32).unwrap();
+        crate::syscall::set_rx_ring_size(&socket, 32).unwrap();
+        socket
+    }
+
+    #[test]
+    fn port_map_test() {
+        let mut program = if let Some(program) = load("port_map_test") {
+            program
+        } else {
+            return;
+        };
+
+        program.add_port(443).unwrap();
+        program.add_port(4433).unwrap();
+        assert_eq!(map_lookup(&program.port_map, &443u16).unwrap(), Some(1u8));
+        assert_eq!(map_lookup(&program.port_map, &4433u16).unwrap(), Some(1u8));
+
+        program.remove_port(443).unwrap();
+        assert_eq!(
+            map_lookup::<_, u8>(&program.port_map, &443u16).unwrap(),
+            None
+        );
+        assert_eq!(program.ports().collect::<Vec<_>>(), [4433]);
+
+        // removing a port which isn't in the map is a no-op
+        program.remove_port(443).unwrap();
+    }
+
+    #[test]
+    fn socket_map_test() {
+        let mut program = if let Some(program) = load("socket_map_test") {
+            program
+        } else {
+            return;
+        };
+
+        let umem = crate::umem::Umem::builder().build().unwrap();
+        let sockets = [open_socket(&umem), open_socket(&umem), open_socket(&umem)];
+
+        program.set_socket(0, &sockets[0]).unwrap();
+        program.set_socket(1, &sockets[1]).unwrap();
+
+        // the stale socket for queue 0 is removed from the map
+        program
+            .set_sockets([(1, &sockets[1]), (2, &sockets[2])])
+            .unwrap();
+        assert_eq!(
+            program.sockets().collect::<Vec<_>>(),
+            [(1, &sockets[1]), (2, &sockets[2])]
+        );
+
+        program.remove_socket(1).unwrap();
+        // removing a socket which isn't in the map is a no-op
+        program.remove_socket(1).unwrap();
+        assert_eq!(program.sockets().collect::<Vec<_>>(), [(2, &sockets[2])]);
+
+        program.set_sockets(core::iter::empty()).unwrap();
+        assert_eq!(program.sockets().count(), 0);
+    }
+}